@@ -0,0 +1,12 @@
+use criterion::{criterion_group, criterion_main};
+use kybelith::bench;
+
+criterion_group!(
+    benches,
+    bench::bench_dilithium_sign,
+    bench::bench_dilithium_verify,
+    bench::bench_block_hash,
+    bench::bench_chain_validation,
+    bench::bench_mempool_admission
+);
+criterion_main!(benches);
@@ -0,0 +1,76 @@
+/*
+ * API C da Kybelith (assinatura Dilithium5 e verificação de transações).
+ *
+ * Compilar a biblioteca com: cargo build --release --features ffi
+ * Buffers devolvidos em QstBuffer pertencem ao chamador e devem ser
+ * liberados com qst_buffer_free, que zera o conteúdo antes de liberar.
+ */
+#ifndef KYBELITH_H
+#define KYBELITH_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum QstStatus {
+    QST_STATUS_OK = 0,
+    QST_STATUS_NULL_POINTER = 1,
+    QST_STATUS_INVALID_UTF8 = 2,
+    QST_STATUS_INVALID_KEY = 3,
+    QST_STATUS_INVALID_SIGNATURE = 4,
+    QST_STATUS_BUFFER_TOO_SMALL = 5,
+    QST_STATUS_PANIC = 6
+} QstStatus;
+
+typedef struct QstBuffer {
+    uint8_t *data;
+    size_t len;
+} QstBuffer;
+
+/*
+ * Campos assinados, na codificação canônica de Transaction (token_id
+ * incluído); strings UTF-8 terminadas em NUL e public_key com a chave
+ * Dilithium5 do remetente.
+ */
+typedef struct QstTransaction {
+    uint64_t token_id;
+    const char *from;
+    const char *to;
+    uint64_t amount;
+    int64_t timestamp;
+    uint64_t nonce;
+    const uint8_t *public_key;
+    size_t public_key_len;
+} QstTransaction;
+
+/* Endereço hex de 40 caracteres mais o NUL */
+#define QST_ADDRESS_BUFFER_LEN 41
+
+QstStatus qst_generate_keypair(QstBuffer *out_public_key, QstBuffer *out_secret_key);
+
+QstStatus qst_sign_transaction(const QstTransaction *tx,
+                               const uint8_t *secret_key,
+                               size_t secret_key_len,
+                               QstBuffer *out_signature);
+
+QstStatus qst_verify_transaction(const QstTransaction *tx,
+                                 const uint8_t *public_key,
+                                 size_t public_key_len,
+                                 const uint8_t *signature,
+                                 size_t signature_len);
+
+QstStatus qst_derive_address(const uint8_t *public_key,
+                             size_t public_key_len,
+                             char *out,
+                             size_t out_len);
+
+void qst_buffer_free(QstBuffer buffer);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KYBELITH_H */
@@ -1,24 +1,43 @@
+use crate::audit::{self, AuditEntry, AuditLog, SharedAuditLog};
+use crate::config::{GenesisConfig, Settings};
+use crate::consensus::QuantumFlexConsensus;
 use crate::constants::TOKEN_CREATION_BASE_FEE_2CHAR;
 use crate::constants::TOKEN_CREATION_BASE_FEE_3CHAR;
 use crate::constants::TOKEN_CREATION_BASE_FEE_4CHAR;
 use crate::constants::TOKEN_CREATION_BASE_FEE_DEFAULT;
-use crate::constants::{
-    BURN_PERCENTAGE, DEV_FUND_ADDRESS, DEV_PERCENTAGE, LIQUIDITY_FUND_ADDRESS, STAKING_PERCENTAGE,
-    STAKING_POOL_ADDRESS, TRANSFER_FEE_DIVISOR, TRANSFER_FEE_MINIMUM,
+use crate::error::Error;
+use crate::events::EventBus;
+use crate::rbac::{AdminRole, RoleCredential};
+use crate::reload::ConfigReloader;
+use crate::rpc::{ChainRouter, NodeMonitor, RpcRateLimiter, RpcService};
+use crate::transaction::{
+    Operation, OperationKind, PipelineConfig, Transaction, TransactionPipeline,
 };
-use crate::transaction::secure_transaction::SecureTransaction;
-use anyhow::{Context, Result};
-use log::info;
+use crate::utils::address::{derive_address, Address};
+use crate::utils::i18n::message;
+use log::{debug, error, info, warn};
+use parking_lot::Mutex;
 use pqcrypto_dilithium::dilithium5;
+use pqcrypto_traits::sign::PublicKey as _;
 use rusqlite::params;
-use std::path::Path;
-
-use crate::blockchain::Blockchain;
+use std::collections::{BTreeMap, BTreeSet};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::{self, MissedTickBehavior};
+
+use crate::blockchain::{Blockchain, SharedBlockchain, TransactionRecord};
 use crate::database::Database;
 use crate::key_manager::KeyManager;
+use crate::network::NodeIdentity;
 use crate::token::custom_token::CustomToken;
-use crate::token::token_builder::TokenBuilder;
-use crate::token::Token;
+use crate::token::FungibleToken;
+
+/// Transações por página em `get_history`
+pub const HISTORY_PAGE_SIZE: usize = 50;
 
 pub struct QuantumBlockchainApp {
     pub blockchain: Blockchain,
@@ -27,30 +46,52 @@ pub struct QuantumBlockchainApp {
 }
 
 impl QuantumBlockchainApp {
-    pub fn new() -> Result<Self> {
-        let key_manager =
-            KeyManager::new().context("Falha ao inicializar gerenciador de chaves")?;
+    pub fn new() -> Result<Self, Error> {
+        Self::open_at(
+            Path::new(crate::BLOCKCHAIN_FILE),
+            Path::new(crate::DB_PATH),
+            &GenesisConfig::default(),
+        )
+    }
+
+    /// Abre a cadeia com os arquivos do `data_dir` de `settings`, criando o
+    /// diretório e uma blockchain nova, a partir de `genesis`, na primeira execução
+    pub fn open(settings: &Settings) -> Result<Self, Error> {
+        let data_dir = Path::new(&settings.node.data_dir);
+        std::fs::create_dir_all(data_dir)
+            .map_err(|e| Error::Other(format!("Falha ao criar {}: {}", data_dir.display(), e)))?;
+        Self::open_at(
+            &settings.blockchain_path(),
+            &settings.database_path(),
+            &settings.genesis,
+        )
+    }
 
-        let blockchain = if Path::new(crate::BLOCKCHAIN_FILE).exists() {
-            let mut blockchain = Blockchain::load_from_file(crate::BLOCKCHAIN_FILE)
-                .context("Falha ao carregar blockchain")?;
+    fn open_at(
+        blockchain_path: &Path,
+        database_path: &Path,
+        genesis: &GenesisConfig,
+    ) -> Result<Self, Error> {
+        let key_manager = KeyManager::new()?;
+
+        let blockchain = if blockchain_path.exists() {
+            let mut blockchain = Blockchain::load_from_file(&blockchain_path.to_string_lossy())?;
 
             // Verifica se o Quantum Secure Token está presente
             if !blockchain.tokens.contains_key(&0.to_string()) {
-                blockchain.create_quantum_secure_token()?;
-                blockchain.save_to_file(crate::BLOCKCHAIN_FILE)?;
+                blockchain.apply_genesis(genesis)?;
+                Self::save_blockchain(&blockchain, blockchain_path)?;
             }
 
             blockchain
         } else {
-            info!("Criando nova blockchain");
-            let blockchain = Blockchain::new().context("Falha ao criar nova blockchain")?;
-            blockchain.save_to_file(crate::BLOCKCHAIN_FILE)?;
+            info!("Criando nova blockchain em {}", blockchain_path.display());
+            let blockchain = Blockchain::from_genesis(genesis)?;
+            Self::save_blockchain(&blockchain, blockchain_path)?;
             blockchain
         };
 
-        let database =
-            Database::new(crate::DB_PATH).context("Falha ao inicializar banco de dados")?;
+        let database = Database::new(&database_path.to_string_lossy())?;
 
         Ok(Self {
             blockchain,
@@ -59,60 +100,109 @@ impl QuantumBlockchainApp {
         })
     }
 
-    pub fn create_token(&mut self, name: String, symbol: String, supply: u64) -> Result<Token> {
-        let token = TokenBuilder::new()
-            .name(name.clone())
-            .symbol(symbol.clone())
-            .total_supply(supply)
-            .creator("admin".to_string())
-            .build()
-            .context("Falha ao construir token")?;
+    fn save_blockchain(blockchain: &Blockchain, path: &Path) -> Result<(), Error> {
+        blockchain
+            .save_to_file(&path.to_string_lossy())
+            .map_err(|e| Error::Other(format!("Falha ao gravar {}: {}", path.display(), e)))
+    }
+
+    /// Cria um token por meio de uma operação assinada pelo criador.
+    ///
+    /// O token passa a existir em `blockchain.tokens` quando o próximo bloco é
+    /// fechado; o ID devolvido já está reservado pela cadeia.
+    pub fn create_token(
+        &mut self,
+        name: String,
+        symbol: String,
+        supply: u64,
+        creator: String,
+        secret_key: &dilithium5::SecretKey,
+    ) -> Result<u64, Error> {
+        self.submit_token_creation(name, symbol, supply, creator, 0, secret_key)
+    }
 
-        let conn = self
-            .database
-            .get_connection_mut()
-            .context("Falha ao obter conexão com banco de dados")?;
+    /// Assina e submete a criação do token com `fee` em KYBL, cobrada do criador
+    /// quando o bloco aplica a operação
+    fn submit_token_creation(
+        &mut self,
+        name: String,
+        symbol: String,
+        supply: u64,
+        creator: String,
+        fee: u64,
+        secret_key: &dilithium5::SecretKey,
+    ) -> Result<u64, Error> {
+        let public_key = self.blockchain.get_public_key(&creator)?;
+        let token_id = self.blockchain.next_token_id;
+        let nonce = self.blockchain.nonces.get(&creator).copied().unwrap_or(0) + 1;
+
+        let mut operation = Operation::new(
+            OperationKind::CreateToken {
+                token_id,
+                name: name.clone(),
+                symbol: symbol.clone(),
+                total_supply: supply,
+            },
+            creator.clone(),
+            nonce,
+            public_key.as_bytes().to_vec(),
+        )?;
+        operation.fee = fee;
+        operation.sign(secret_key)?;
+        self.blockchain.submit_operation(operation)?;
 
-        // Insere o token no banco de dados (sem passar o ID)
+        // Índice local para consultas; o registro de referência é a operação na cadeia
+        let conn = self.database.get_connection_mut()?;
         conn.execute(
             "INSERT INTO tokens (name, symbol, supply, creator) VALUES (?1, ?2, ?3, ?4)",
-            params![token.name, token.symbol, token.total_supply, token.creator],
+            params![name, symbol, supply, creator],
         )
-        .context("Falha ao inserir token no banco de dados")?;
+        .map_err(|e| Error::database("Falha ao inserir token no banco de dados", e))?;
 
-        Ok(token)
+        info!(
+            "Criação do token {} ({}) submetida com ID {}",
+            name, symbol, token_id
+        );
+        Ok(token_id)
     }
 
+    /// Cria um token personalizado cobrando a taxa de criação em KYBL.
+    ///
+    /// O ID é atribuído pela cadeia (`next_token_id`) e o token é registrado em
+    /// `blockchain.tokens` pela mesma operação assinada de `create_token`, que
+    /// carrega a taxa; ela só é cobrada quando o bloco aplica a operação.
     pub fn create_custom_token(
         &mut self,
-        id: u32,
         name: String,
         symbol: String,
         supply: u64,
         owner: String,
-    ) -> Result<CustomToken> {
-        // Validação de ID
-        if id == 0 {
-            return Err(anyhow::anyhow!("ID 0 é reservado para o token nativo KYBL").into());
-        }
+        secret_key: &dilithium5::SecretKey,
+    ) -> Result<CustomToken, Error> {
+        // `CustomToken` usa IDs de 32 bits
+        let id = u32::try_from(self.blockchain.next_token_id)
+            .map_err(|_| Error::InvalidInput("IDs de token personalizado esgotados".to_string()))?;
 
         // Validação de nome
         if name.trim().is_empty() || name.len() > 64 {
-            return Err(anyhow::anyhow!("Nome do token deve ter entre 1 e 64 caracteres").into());
+            return Err(Error::InvalidInput(
+                "Nome do token deve ter entre 1 e 64 caracteres".to_string(),
+            ));
         }
 
         // Validação de símbolo
         if !symbol.chars().all(|c| c.is_ascii_uppercase()) || symbol.len() < 2 || symbol.len() > 10
         {
-            return Err(anyhow::anyhow!(
-                "Símbolo do token deve ter entre 2 e 10 caracteres maiúsculos"
-            )
-            .into());
+            return Err(Error::InvalidInput(
+                "Símbolo do token deve ter entre 2 e 10 caracteres maiúsculos".to_string(),
+            ));
         }
 
         // Validação de supply
         if supply == 0 || supply > u64::MAX / 2 {
-            return Err(anyhow::anyhow!("Supply deve estar entre 1 e 2^63-1").into());
+            return Err(Error::InvalidInput(
+                "Supply deve estar entre 1 e 2^63-1".to_string(),
+            ));
         }
 
         // Calcular a taxa de criação do token
@@ -127,18 +217,14 @@ impl QuantumBlockchainApp {
             .blockchain
             .tokens
             .get(&0.to_string())
-            .ok_or(anyhow::anyhow!("Token KYBL não encontrado"))?
+            .ok_or(Error::TokenNotFound)?
             .balances
             .get(&owner)
             .copied()
             .unwrap_or(0);
 
         if kybl_balance < token_creation_fee {
-            return Err(anyhow::anyhow!(
-                "Saldo insuficiente de KYBL para criar token. Mínimo: {}",
-                token_creation_fee
-            )
-            .into());
+            return Err(Error::InsufficientBalance);
         }
 
         // Limitar tokens por conta (exemplo: consultar banco de dados)
@@ -151,37 +237,33 @@ impl QuantumBlockchainApp {
 
         let max_tokens_per_account = 5;
         if token_count >= max_tokens_per_account {
-            return Err(anyhow::anyhow!(
+            return Err(Error::InvalidInput(format!(
                 "Limite máximo de {} tokens por conta atingido",
                 max_tokens_per_account
-            )
-            .into());
+            )));
         }
 
-        // Cobra a taxa em KYBL
-        let kybl_token = self
-            .blockchain
-            .tokens
-            .get_mut(&0.to_string())
-            .ok_or(anyhow::anyhow!("Token KYBL não encontrado"))?;
-
-        *kybl_token.balances.entry(owner.clone()).or_insert(0) -= token_creation_fee;
-        *kybl_token.balances.entry("system".to_string()).or_insert(0) += token_creation_fee;
+        // Registra a criação na cadeia com a taxa; o ID reservado é o lido acima
+        let token_id = self.submit_token_creation(
+            name.clone(),
+            symbol.clone(),
+            supply,
+            owner.clone(),
+            token_creation_fee,
+            secret_key,
+        )?;
+        debug_assert_eq!(token_id, u64::from(id));
 
         // Criar o token customizado
         let mut token = CustomToken::new(id, name.clone(), symbol.clone(), supply, owner.clone())?;
 
         // Assinando a transação de criação
-        let data = format!("create_token:{}:{}:{}:{}", name, symbol, supply, owner);
+        let data = format!(
+            "create_token:{}:{}:{}:{}:{}",
+            id, name, symbol, supply, owner
+        );
         token.sign_transaction(&data)?;
 
-        // Registra o token no banco de dados
-        let conn = self.database.get_connection_mut()?;
-        conn.execute(
-            "INSERT INTO tokens (name, symbol, supply, creator) VALUES (?1, ?2, ?3, ?4)",
-            params![token.name, token.symbol, supply, token.owner],
-        )?;
-
         Ok(token)
     }
 
@@ -212,60 +294,8 @@ impl QuantumBlockchainApp {
         fee as u64
     }
 
-    // Função para distribuir as taxas coletadas
-    fn distribute_fees(&mut self, fee_amount: u64) -> Result<()> {
-        let kybl_token = self
-            .blockchain
-            .tokens
-            .get_mut(&0.to_string())
-            .ok_or(anyhow::anyhow!("Token KYBL não encontrado"))?;
-
-        // Cálculo dos valores de distribuição
-        let burn_amount = (fee_amount * BURN_PERCENTAGE) / 100;
-        let staking_amount = (fee_amount * STAKING_PERCENTAGE) / 100;
-        let dev_amount = (fee_amount * DEV_PERCENTAGE) / 100;
-        // Usando a subtração para garantir que todas as taxas sejam distribuídas sem erro de arredondamento
-        let liquidity_amount = fee_amount - burn_amount - staking_amount - dev_amount;
-
-        // Atualizar balances
-        kybl_token.total_supply -= burn_amount; // Queima direta
-        *kybl_token
-            .balances
-            .entry(STAKING_POOL_ADDRESS.to_string())
-            .or_insert(0) += staking_amount;
-        *kybl_token
-            .balances
-            .entry(DEV_FUND_ADDRESS.to_string())
-            .or_insert(0) += dev_amount;
-        *kybl_token
-            .balances
-            .entry(LIQUIDITY_FUND_ADDRESS.to_string())
-            .or_insert(0) += liquidity_amount;
-
-        // Registrar a distribuição (log)
-        info!("Distribuição de taxa: Queima: {} KYBL, Staking: {} KYBL, Dev: {} KYBL, Liquidez: {} KYBL",
-              burn_amount, staking_amount, dev_amount, liquidity_amount);
-
-        // Se o banco de dados estiver configurado, registre a distribuição lá também
-        if let Ok(conn) = self.database.get_connection() {
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs() as i64;
-
-            // Ignorar erro se a tabela ainda não existir
-            let _ = conn.execute(
-                "INSERT INTO fee_distributions (timestamp, burn_amount, staking_amount, dev_amount, liquidity_amount) 
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![timestamp, burn_amount, staking_amount, dev_amount, liquidity_amount],
-            );
-        }
-
-        Ok(())
-    }
-
     // Função para obter reputação do criador
-    fn get_creator_reputation(&self, address: &str) -> Result<u64> {
+    fn get_creator_reputation(&self, address: &str) -> Result<u64, Error> {
         // Implementação básica - retorna 0 se o banco de dados não estiver acessível
         let reputation = match self.database.get_connection() {
             Ok(conn) => {
@@ -284,98 +314,684 @@ impl QuantumBlockchainApp {
         Ok(reputation)
     }
 
+    /// Transfere tokens por meio de uma transação assinada enviada ao mempool.
+    ///
+    /// Os saldos do token e a taxa em KYBL mudam quando o próximo bloco aplica a
+    /// transferência; uma transação descartada não paga taxa.
     pub fn transfer_token(
         &mut self,
-        token: &mut CustomToken,
+        token_id: u64,
+        from: String,
         to: String,
         amount: u64,
         secret_key: &dilithium5::SecretKey,
-    ) -> Result<SecureTransaction> {
+    ) -> Result<Transaction, Error> {
         // Validações básicas
         if amount == 0 {
-            return Err(anyhow::anyhow!("Quantidade deve ser maior que zero").into());
+            return Err(Error::InvalidAmount);
+        }
+        if !self.blockchain.tokens.contains_key(&token_id.to_string()) {
+            return Err(Error::TokenNotFound);
         }
 
         // Taxa de transferência: 0.1% do valor transferido (mínimo 1 KYBL)
-        let transfer_fee = std::cmp::max(amount / TRANSFER_FEE_DIVISOR, TRANSFER_FEE_MINIMUM);
+        let transfer_fee = Blockchain::transfer_fee(amount);
 
         // Verificar saldo KYBL para pagamento da taxa
-        let from = token.owner.clone();
         let kybl_balance = self
             .blockchain
             .tokens
             .get(&0.to_string())
-            .ok_or(anyhow::anyhow!("Token KYBL não encontrado"))?
+            .ok_or(Error::TokenNotFound)?
             .balances
             .get(&from)
             .copied()
             .unwrap_or(0);
 
         if kybl_balance < transfer_fee {
-            return Err(anyhow::anyhow!(
-                "Saldo insuficiente de KYBL para taxa de transferência. Necessário: {} KYBL",
-                transfer_fee
-            )
-            .into());
+            return Err(Error::InsufficientBalance);
         }
 
-        // Cobra a taxa
-        let kybl_token = self
-            .blockchain
-            .tokens
-            .get_mut(&0.to_string())
-            .ok_or(anyhow::anyhow!("Token KYBL não encontrado"))?;
+        // Assina a transferência e a envia ao mempool
+        let public_key = self.blockchain.get_public_key(&from)?;
+        let mut transaction = Transaction::new(
+            from.clone(),
+            to.clone(),
+            amount,
+            public_key.as_bytes().to_vec(),
+        )?;
+        transaction.token_id = token_id;
+        transaction.nonce = self.blockchain.nonces.get(&from).copied().unwrap_or(0) + 1;
+        transaction.sign(secret_key)?;
+        self.blockchain.submit_transaction(transaction.clone())?;
+
+        // Índice local para consultas
+        let conn = self.database.get_connection_mut()?;
+        conn.execute(
+            "INSERT INTO transfers (token_id, from_address, to_address, amount) VALUES (?1, ?2, ?3, ?4)",
+            params![token_id, from, to, amount],
+        )
+        .map_err(|e| Error::database("Falha ao registrar transferência no banco de dados", e))?;
+
+        // Log da transferência e taxa
+        info!(
+            "Transferência de {} tokens (ID: {}) para {} submetida. Taxa: {} KYBL",
+            amount, token_id, to, transfer_fee
+        );
 
-        *kybl_token.balances.entry(from.clone()).or_insert(0) -= transfer_fee;
+        Ok(transaction)
+    }
 
-        // Distribuir as taxas
-        self.distribute_fees(transfer_fee)?;
+    /// Saldo confirmado de `address` no token `token_id`
+    pub fn get_balance(&self, address: &str, token_id: u64) -> Result<u64, Error> {
+        let address = Address::parse(address)?;
+        let token = self
+            .blockchain
+            .get_token(&token_id.to_string())
+            .ok_or(Error::TokenNotFound)?;
+        Ok(FungibleToken::balance_of(token, address.as_str()))
+    }
 
-        // Executa a transferência do token original
-        token.transfer(to.clone(), amount)?;
+    /// Transação pelo txid, confirmada ou ainda no mempool
+    pub fn get_transaction(&self, txid: &str) -> Option<TransactionRecord> {
+        self.blockchain.transaction_record(txid)
+    }
 
-        // Registra no banco de dados
-        let conn = self
-            .database
-            .get_connection_mut()
-            .context("Falha ao obter conexão com banco de dados")?;
+    /// Página `page` (a partir de 0) do histórico de `address`, com até
+    /// `HISTORY_PAGE_SIZE` transações, pendentes primeiro e depois da mais recente
+    /// para a mais antiga
+    pub fn get_history(&self, address: &str, page: usize) -> Result<Vec<TransactionRecord>, Error> {
+        let address = Address::parse(address)?;
+        Ok(self
+            .blockchain
+            .history(address.as_str(), page, HISTORY_PAGE_SIZE))
+    }
 
-        conn.execute(
-        "INSERT INTO transfers (token_id, from_address, to_address, amount) VALUES (?1, ?2, ?3, ?4)",
-        params![token.id, token.owner, to.clone(), amount],
-    ).context("Falha ao registrar transferência no banco de dados")?;
+    /// Barramento de eventos da blockchain; assinantes registrados antes de `start`
+    /// continuam recebendo os eventos do nó
+    pub fn events(&self) -> &EventBus {
+        &self.blockchain.events
+    }
 
-        // Criar uma transação segura para retornar
-        let public_key = self.blockchain.get_public_key(&from)?;
-        let nonce = self.blockchain.nonces.get(&from).copied().unwrap_or(0);
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
-
-        // Incluir informação da taxa na transação segura
-        let transaction = SecureTransaction::new(
-            from,
-            to.clone(), // Clone aqui para evitar o erro de move
-            amount,
-            timestamp,
-            nonce + 1,
-            secret_key,
-            &public_key,
+    /// Confere uma credencial de papel contra as atribuições registradas na cadeia
+    pub fn authorize(&self, credential: &RoleCredential, role: AdminRole) -> Result<String, Error> {
+        self.blockchain.authorize(credential, role)
+    }
+
+    /// Atribui ou revoga um papel por meio de uma operação assinada pelo admin; a
+    /// mudança vale quando o próximo bloco é fechado
+    pub fn assign_role(
+        &mut self,
+        subject: String,
+        role: AdminRole,
+        granted: bool,
+        admin_public_key: &dilithium5::PublicKey,
+        admin_secret_key: &dilithium5::SecretKey,
+    ) -> Result<(), Error> {
+        let public_key = admin_public_key.as_bytes().to_vec();
+        let author = derive_address(&public_key);
+        let nonce = self.blockchain.nonces.get(&author).copied().unwrap_or(0) + 1;
+
+        let mut operation = Operation::new(
+            OperationKind::AssignRole {
+                subject: subject.clone(),
+                role,
+                granted,
+            },
+            author,
+            nonce,
+            public_key,
         )?;
+        operation.sign(admin_secret_key)?;
+        self.blockchain.submit_operation(operation)?;
 
-        // Log da transferência e taxa
         info!(
-            "Transferência de {} tokens (ID: {}) para {}. Taxa: {} KYBL",
-            amount, token.id, to, transfer_fee
+            "Papel {} {} para {} submetido",
+            role,
+            if granted { "atribuído" } else { "revogado" },
+            subject
         );
+        Ok(())
+    }
 
-        Ok(transaction)
+    pub fn verify_chain_integrity(&self) -> Result<bool, Error> {
+        self.verify_chain_integrity_with(false)
+    }
+
+    /// Como `verify_chain_integrity`; com `audit_supply`, exige também que o
+    /// supply de todos os tokens confira (ver `Blockchain::supply_audit`)
+    pub fn verify_chain_integrity_with(&self, audit_supply: bool) -> Result<bool, Error> {
+        if !self.blockchain.is_chain_valid()? {
+            return Ok(false);
+        }
+        if audit_supply {
+            for report in self.blockchain.supply_audit() {
+                if !report.is_consistent() {
+                    warn!(
+                        "Supply do token {} ({}) não confere: registrado {}, em circulação {}, histórico {:?}",
+                        report.token_id,
+                        report.symbol,
+                        report.total_supply,
+                        report.circulating,
+                        report.expected_supply
+                    );
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Inicia o nó em segundo plano: pipeline do mempool, consenso, produção de
+    /// blocos a cada `block_interval` e gravação periódica da blockchain.
+    ///
+    /// Deve ser chamado dentro de um runtime tokio.
+    pub async fn start(self, settings: Settings) -> Result<NodeHandle, Error> {
+        let chain_id = settings.node.chain_id.clone();
+        let blockchain_path = settings.blockchain_path();
+        let database_path = settings.database_path();
+        let identity_path = settings.identity_path();
+        for dir in [blockchain_path.parent(), identity_path.parent()]
+            .into_iter()
+            .flatten()
+        {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| Error::Other(format!("Falha ao criar {}: {}", dir.display(), e)))?;
+        }
+        let identity = Arc::new(NodeIdentity::load_or_generate(&identity_path).map_err(|e| {
+            Error::Other(format!(
+                "Falha ao carregar a identidade do nó {}: {}",
+                identity_path.display(),
+                e
+            ))
+        })?);
+
+        // Os blocos produzidos por este nó saem assinados com a chave de identidade
+        let mut blockchain = self.blockchain;
+        blockchain.limits = settings.limits;
+        blockchain.confirmation_depth = settings.consensus.confirmation_depth;
+        blockchain.set_chain_id(chain_id.clone());
+        let proposer = blockchain.set_signer(Arc::clone(&identity));
+        info!("Blocos assinados pelo validador {}", proposer);
+
+        let events = blockchain.events.clone();
+        let audit_log_path = settings.audit_log_path();
+        let audit_log: SharedAuditLog = Arc::new(Mutex::new(
+            AuditLog::open(&audit_log_path).map_err(|e| {
+                Error::Other(format!(
+                    "Falha ao abrir o log de auditoria {}: {}",
+                    audit_log_path.display(),
+                    e
+                ))
+            })?,
+        ));
+        audit::record_events(&audit_log, &events);
+
+        let blockchain = SharedBlockchain::new(blockchain);
+        let pipeline = TransactionPipeline::start(blockchain.clone(), PipelineConfig::default());
+
+        let rpc_limiter = Arc::new(RpcRateLimiter::new(settings.rpc));
+        let reloader = Arc::new(ConfigReloader::new(
+            settings.clone(),
+            blockchain.clone(),
+            Arc::clone(&rpc_limiter),
+        ));
+        let watch_config = settings.source.is_some();
+
+        let block_interval = settings.block_interval();
+        let persist_interval = settings.persist_interval();
+        let max_block_transactions = settings.consensus.max_block_transactions;
+
+        let mut consensus = QuantumFlexConsensus::new(Arc::new(settings), Vec::new());
+        consensus.set_event_bus(events.clone());
+        consensus
+            .start()
+            .await
+            .map_err(|e| Error::Other(format!("Falha ao iniciar o consenso: {}", e)))?;
+        let consensus = Arc::new(consensus);
+        let monitor = Arc::new(NodeMonitor::new().with_storage(database_path));
+
+        let (shutdown, signal) = watch::channel(false);
+        let mut tasks = vec![
+            tokio::spawn(block_production_loop(
+                blockchain.clone(),
+                Arc::clone(&consensus),
+                block_interval,
+                max_block_transactions,
+                signal.clone(),
+            )),
+            tokio::spawn(persistence_loop(
+                blockchain.clone(),
+                blockchain_path.clone(),
+                Arc::clone(&identity),
+                persist_interval,
+                signal.clone(),
+            )),
+        ];
+        if watch_config {
+            tasks.push(tokio::spawn(reload_on_hangup(
+                Arc::clone(&reloader),
+                signal,
+            )));
+        }
+
+        info!(
+            "Nó da cadeia {} iniciado: altura {}, blocos a cada {:?}",
+            chain_id,
+            blockchain.height(),
+            block_interval
+        );
+
+        Ok(NodeHandle {
+            chain_id,
+            blockchain,
+            pipeline,
+            consensus,
+            monitor,
+            rpc_limiter,
+            reloader,
+            events,
+            audit_log,
+            identity,
+            shutdown,
+            tasks,
+            blockchain_path,
+        })
+    }
+
+    /// Executa o nó até Ctrl+C e então encerra gravando o estado final
+    pub async fn run(self, settings: Settings) -> Result<(), Error> {
+        self.run_until(settings, async {
+            if let Err(e) = tokio::signal::ctrl_c().await {
+                warn!("Falha ao aguardar sinal de interrupção: {}", e);
+            }
+        })
+        .await
+    }
+
+    /// Executa o nó até `shutdown` completar
+    pub async fn run_until(
+        self,
+        settings: Settings,
+        shutdown: impl Future<Output = ()>,
+    ) -> Result<(), Error> {
+        let node = self.start(settings).await?;
+        shutdown.await;
+        node.shutdown().await
+    }
+}
+
+/// Nó em execução, devolvido por `QuantumBlockchainApp::start`
+pub struct NodeHandle {
+    chain_id: String,
+    blockchain: SharedBlockchain,
+    pipeline: TransactionPipeline,
+    consensus: Arc<QuantumFlexConsensus>,
+    monitor: Arc<NodeMonitor>,
+    rpc_limiter: Arc<RpcRateLimiter>,
+    reloader: Arc<ConfigReloader>,
+    events: EventBus,
+    audit_log: SharedAuditLog,
+    identity: Arc<NodeIdentity>,
+    shutdown: watch::Sender<bool>,
+    tasks: Vec<JoinHandle<()>>,
+    blockchain_path: PathBuf,
+}
+
+impl NodeHandle {
+    /// Cadeia servida por este nó, vinda de `node.chain_id`
+    pub fn chain_id(&self) -> &str {
+        &self.chain_id
+    }
+
+    pub fn blockchain(&self) -> &SharedBlockchain {
+        &self.blockchain
+    }
+
+    /// Barramento de eventos do nó: blocos, tokens, transferências e banimentos
+    pub fn events(&self) -> &EventBus {
+        &self.events
     }
 
-    pub fn verify_chain_integrity(&self) -> Result<bool> {
+    /// Log de auditoria encadeado das ações administrativas do nó
+    pub fn audit_log(&self) -> &SharedAuditLog {
+        &self.audit_log
+    }
+
+    /// Entradas do log de auditoria para o titular de uma credencial de auditor
+    pub fn audit_entries(&self, credential: &RoleCredential) -> Result<Vec<AuditEntry>, Error> {
         self.blockchain
-            .is_chain_valid()
-            .context("Falha ao verificar integridade da blockchain")
+            .read(|blockchain| blockchain.authorize(credential, AdminRole::Auditor))?;
+        Ok(self.audit_log.lock().entries().to_vec())
+    }
+
+    /// Chave pública que assina as exportações de estado deste nó
+    pub fn identity_public_key(&self) -> &dilithium5::PublicKey {
+        &self.identity.public_key
+    }
+
+    /// Entrada do mempool para transações codificadas
+    pub fn pipeline(&self) -> &TransactionPipeline {
+        &self.pipeline
+    }
+
+    pub fn consensus(&self) -> &QuantumFlexConsensus {
+        &self.consensus
+    }
+
+    /// Contadores de peers e sincronização, atualizados pela camada de rede
+    pub fn monitor(&self) -> &Arc<NodeMonitor> {
+        &self.monitor
+    }
+
+    /// Recarregamento das configurações que dispensam reinício
+    pub fn reloader(&self) -> &Arc<ConfigReloader> {
+        &self.reloader
+    }
+
+    /// Serviço RPC sobre o estado do nó, com validadores, status, `/health`,
+    /// limite de chamadas e `reload_config`
+    pub fn rpc_service(&self) -> RpcService {
+        RpcService::new(self.blockchain.clone())
+            .with_consensus(Arc::clone(&self.consensus))
+            .with_monitor(Arc::clone(&self.monitor))
+            .with_rate_limiter(Arc::clone(&self.rpc_limiter))
+            .with_reloader(Arc::clone(&self.reloader))
+    }
+
+    /// Interrompe as tarefas de fundo, para o consenso e grava a blockchain
+    pub async fn shutdown(self) -> Result<(), Error> {
+        let _ = self.shutdown.send(true);
+        for task in self.tasks {
+            task.await
+                .map_err(|e| Error::Other(format!("Tarefa do nó falhou: {}", e)))?;
+        }
+
+        self.consensus
+            .stop()
+            .await
+            .map_err(|e| Error::Other(format!("Falha ao parar o consenso: {}", e)))?;
+
+        persist(&self.blockchain, &self.blockchain_path, &self.identity).await?;
+        info!(
+            "Nó da cadeia {} encerrado na altura {}",
+            self.chain_id,
+            self.blockchain.height()
+        );
+        Ok(())
+    }
+}
+
+/// Cadeias isoladas servidas pelo mesmo processo, como mainnet e testnet ou
+/// sidechains de uma aplicação, indexadas pelo `chain_id`.
+///
+/// Cada cadeia tem configurações, arquivos, consenso e tarefas de fundo próprios;
+/// só o runtime é compartilhado. A primeira cadeia informada é a padrão das
+/// chamadas RPC que não indicam cadeia.
+pub struct ChainHost {
+    nodes: BTreeMap<String, NodeHandle>,
+    default_chain: String,
+}
+
+impl ChainHost {
+    /// Abre pelo `data_dir` de cada configuração e inicia todas as cadeias
+    pub async fn open(settings: Vec<Settings>) -> Result<Self, Error> {
+        validate_chains(&settings)?;
+        let mut chains = Vec::with_capacity(settings.len());
+        for settings in settings {
+            chains.push((QuantumBlockchainApp::open(&settings)?, settings));
+        }
+        Self::start(chains).await
+    }
+
+    /// Inicia cada aplicação com as suas configurações. Se alguma falhar, as já
+    /// iniciadas são encerradas antes de devolver o erro.
+    pub async fn start(chains: Vec<(QuantumBlockchainApp, Settings)>) -> Result<Self, Error> {
+        let settings: Vec<Settings> = chains.iter().map(|(_, s)| s.clone()).collect();
+        validate_chains(&settings)?;
+        let default_chain = settings[0].node.chain_id.clone();
+
+        let mut nodes = BTreeMap::new();
+        for (app, settings) in chains {
+            match app.start(settings).await {
+                Ok(node) => {
+                    nodes.insert(node.chain_id().to_string(), node);
+                }
+                Err(e) => {
+                    let host = Self {
+                        nodes,
+                        default_chain,
+                    };
+                    if let Err(stop) = host.shutdown().await {
+                        warn!("Falha ao encerrar as cadeias já iniciadas: {}", stop);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        Ok(Self {
+            nodes,
+            default_chain,
+        })
+    }
+
+    pub fn default_chain(&self) -> &str {
+        &self.default_chain
+    }
+
+    pub fn chain_ids(&self) -> impl Iterator<Item = &str> {
+        self.nodes.keys().map(String::as_str)
+    }
+
+    pub fn node(&self, chain_id: &str) -> Option<&NodeHandle> {
+        self.nodes.get(chain_id)
+    }
+
+    /// Serviços RPC de todas as cadeias, roteados pelo `chain_id`
+    pub fn rpc_router(&self) -> ChainRouter {
+        let mut router = ChainRouter::new(
+            self.default_chain.clone(),
+            self.nodes[&self.default_chain].rpc_service(),
+        );
+        for (chain_id, node) in &self.nodes {
+            if *chain_id != self.default_chain {
+                router = router.with_chain(chain_id.clone(), node.rpc_service());
+            }
+        }
+        router
+    }
+
+    /// Encerra todas as cadeias, mesmo que alguma falhe; devolve o primeiro erro
+    pub async fn shutdown(self) -> Result<(), Error> {
+        let mut result = Ok(());
+        for (chain_id, node) in self.nodes {
+            if let Err(e) = node.shutdown().await {
+                warn!("Falha ao encerrar a cadeia {}: {}", chain_id, e);
+                result = result.and(Err(e));
+            }
+        }
+        result
+    }
+
+    /// Executa as cadeias até Ctrl+C e então encerra todas
+    pub async fn run(settings: Vec<Settings>) -> Result<(), Error> {
+        let host = Self::open(settings).await?;
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("Falha ao aguardar sinal de interrupção: {}", e);
+        }
+        host.shutdown().await
+    }
+}
+
+/// Confere que as cadeias não compartilham `chain_id` nem arquivos: duas cadeias
+/// gravando no mesmo arquivo corromperiam uma à outra
+fn validate_chains(chains: &[Settings]) -> Result<(), Error> {
+    if chains.is_empty() {
+        return Err(Error::InvalidInput(
+            "Nenhuma cadeia configurada".to_string(),
+        ));
+    }
+    let mut chain_ids = BTreeSet::new();
+    let mut owners: BTreeMap<PathBuf, &str> = BTreeMap::new();
+    for settings in chains {
+        let chain_id = settings.node.chain_id.as_str();
+        if chain_id.is_empty() || chain_id.contains('/') {
+            return Err(Error::InvalidInput(format!(
+                "Identificador de cadeia inválido: '{}'",
+                chain_id
+            )));
+        }
+        if !chain_ids.insert(chain_id) {
+            return Err(Error::InvalidInput(format!(
+                "Cadeia {} configurada mais de uma vez",
+                chain_id
+            )));
+        }
+        let files = [
+            settings.blockchain_path(),
+            settings.database_path(),
+            settings.identity_path(),
+            settings.audit_log_path(),
+            settings.peer_store_path(),
+            settings.ban_list_path(),
+        ];
+        for file in files {
+            let file = std::path::absolute(&file).unwrap_or(file);
+            if let Some(other) = owners.insert(file.clone(), chain_id) {
+                if other != chain_id {
+                    return Err(Error::InvalidInput(format!(
+                        "As cadeias {} e {} usam o mesmo arquivo {}",
+                        other,
+                        chain_id,
+                        file.display()
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn persist(
+    blockchain: &SharedBlockchain,
+    path: &Path,
+    identity: &Arc<NodeIdentity>,
+) -> Result<(), Error> {
+    blockchain
+        .save_signed_async(&path.to_string_lossy(), Arc::clone(identity))
+        .await
+        .map(|_| ())
+        .map_err(|e| Error::Other(format!("Falha ao gravar {}: {}", path.display(), e)))
+}
+
+async fn block_production_loop(
+    blockchain: SharedBlockchain,
+    consensus: Arc<QuantumFlexConsensus>,
+    interval: Duration,
+    max_transactions: usize,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let mut timer = time::interval(interval);
+    timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    // O primeiro tick é imediato; o primeiro bloco sai após um intervalo completo
+    timer.tick().await;
+    // Altura de ativação do último aviso de atualização, para não repetir a cada bloco
+    let mut upgrade_warned = None;
+
+    loop {
+        tokio::select! {
+            _ = timer.tick() => {}
+            _ = shutdown.changed() => break,
+        }
+
+        let upcoming = blockchain.read(|chain| chain.pending_upgrade(chain.height()).cloned());
+        if let Some(signal) =
+            upcoming.filter(|signal| upgrade_warned != Some(signal.activation_height))
+        {
+            warn!(
+                "{}: {} no bloco {}",
+                message("node.upgrade_scheduled"),
+                signal.min_version,
+                signal.activation_height
+            );
+            upgrade_warned = Some(signal.activation_height);
+        }
+
+        if let Err(e) = consensus.request_block_proposal().await {
+            debug!("Consenso não aceitou a solicitação de proposta: {}", e);
+        }
+
+        match blockchain.produce_block_async(max_transactions).await {
+            Ok(Some(block)) => {
+                info!(
+                    "{} {} ({})",
+                    message("node.block_produced"),
+                    block.index,
+                    block.hash
+                );
+                if let Err(e) = consensus
+                    .notify_block_finalized(block.hash, block.index)
+                    .await
+                {
+                    debug!("Consenso não recebeu o bloco {}: {}", block.index, e);
+                }
+            }
+            Ok(None) => debug!("Mempool vazio; nenhum bloco produzido"),
+            Err(e @ Error::UpgradeRequired(_)) => error!("{}: {}", message("node.block_failed"), e),
+            Err(e) => warn!("{}: {}", message("node.block_failed"), e),
+        }
+    }
+}
+
+/// Relê o arquivo de configuração a cada SIGHUP; fora do Unix só a chamada RPC
+/// `reload_config` recarrega
+async fn reload_on_hangup(reloader: Arc<ConfigReloader>, mut shutdown: watch::Receiver<bool>) {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(e) => {
+                warn!("Falha ao instalar o tratador de SIGHUP: {}", e);
+                return;
+            }
+        };
+        loop {
+            tokio::select! {
+                _ = hangup.recv() => {
+                    // Erros já são registrados por `reload`
+                    let _ = reloader.reload();
+                }
+                _ = shutdown.changed() => break,
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = reloader;
+        let _ = shutdown.changed().await;
+    }
+}
+
+async fn persistence_loop(
+    blockchain: SharedBlockchain,
+    path: PathBuf,
+    identity: Arc<NodeIdentity>,
+    interval: Duration,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let mut timer = time::interval(interval);
+    timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    timer.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = timer.tick() => {}
+            _ = shutdown.changed() => break,
+        }
+
+        if let Err(e) = persist(&blockchain, &path, &identity).await {
+            warn!("{}: {}", message("node.persist_failed"), e);
+        }
     }
 }
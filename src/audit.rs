@@ -0,0 +1,303 @@
+use crate::error::Error;
+use crate::events::{AppEvent, EventBus, SubscriptionId};
+use crate::rbac::AdminRole;
+use crate::utils::clock::clock;
+use log::warn;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Hash anterior da primeira entrada do log
+pub const AUDIT_GENESIS_HASH: &str =
+    "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Ações administrativas registradas no log de auditoria
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum AuditAction {
+    /// Rotação das chaves do nó; `key_id` identifica a nova chave pública
+    KeyRotated { key_id: String },
+    TokenMinted {
+        token_id: u64,
+        to: String,
+        amount: u64,
+    },
+    GovernanceExecuted {
+        proposal_id: String,
+        description: String,
+    },
+    ValidatorBanned {
+        validator_id: String,
+        duration_secs: u64,
+    },
+    RoleAssigned {
+        subject: String,
+        role: AdminRole,
+        granted: bool,
+    },
+    /// Parada (`halted`) ou retomada de emergência da cadeia
+    ChainHaltChanged {
+        halted: bool,
+        reason: String,
+        approvers: Vec<String>,
+        height: u64,
+    },
+}
+
+/// Entrada do log; `hash` cobre todos os outros campos, inclusive o hash da
+/// entrada anterior
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct AuditEntry {
+    pub sequence: u64,
+    pub timestamp: i64,
+    /// Quem executou a ação (endereço, validador ou componente do nó)
+    pub actor: String,
+    pub action: AuditAction,
+    pub previous_hash: String,
+    pub hash: String,
+}
+
+impl AuditEntry {
+    fn compute_hash(
+        sequence: u64,
+        timestamp: i64,
+        actor: &str,
+        action: &AuditAction,
+        previous_hash: &str,
+    ) -> Result<String, Error> {
+        let action = serde_json::to_vec(action)?;
+        let mut hasher = Sha3_256::new();
+        hasher.update(sequence.to_le_bytes());
+        hasher.update(timestamp.to_le_bytes());
+        hasher.update((actor.len() as u64).to_le_bytes());
+        hasher.update(actor.as_bytes());
+        hasher.update((action.len() as u64).to_le_bytes());
+        hasher.update(&action);
+        hasher.update(previous_hash.as_bytes());
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    fn expected_hash(&self) -> Result<String, Error> {
+        Self::compute_hash(
+            self.sequence,
+            self.timestamp,
+            &self.actor,
+            &self.action,
+            &self.previous_hash,
+        )
+    }
+}
+
+/// Log de auditoria somente de acréscimo, encadeado por hash.
+///
+/// Alterar, remover ou reordenar qualquer entrada quebra a cadeia a partir dela;
+/// truncar o final só é detectável comparando `head_hash` com um valor guardado
+/// fora do nó. Com arquivo, cada entrada vira uma linha JSON gravada antes de
+/// `append` retornar.
+#[derive(Debug, Default)]
+pub struct AuditLog {
+    path: Option<PathBuf>,
+    entries: Vec<AuditEntry>,
+}
+
+/// Log compartilhado entre o nó e o barramento de eventos
+pub type SharedAuditLog = Arc<Mutex<AuditLog>>;
+
+impl AuditLog {
+    /// Log apenas em memória
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Abre (ou cria) o log em `path`, recusando um arquivo cuja cadeia não confere
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let entries = if path.exists() {
+            let contents = std::fs::read_to_string(&path).map_err(|e| {
+                Error::Other(format!(
+                    "Falha ao ler log de auditoria de {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+            contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(serde_json::from_str)
+                .collect::<Result<Vec<AuditEntry>, _>>()?
+        } else {
+            Vec::new()
+        };
+        Self::verify_entries(&entries)?;
+
+        Ok(Self {
+            path: Some(path),
+            entries,
+        })
+    }
+
+    pub fn entries(&self) -> &[AuditEntry] {
+        &self.entries
+    }
+
+    /// Hash da última entrada, ou `AUDIT_GENESIS_HASH` com o log vazio
+    pub fn head_hash(&self) -> &str {
+        self.entries
+            .last()
+            .map(|entry| entry.hash.as_str())
+            .unwrap_or(AUDIT_GENESIS_HASH)
+    }
+
+    /// Acrescenta uma entrada encadeada à anterior e a grava no arquivo
+    pub fn append(&mut self, actor: &str, action: AuditAction) -> Result<&AuditEntry, Error> {
+        self.append_at(actor, action, clock().now_secs())
+    }
+
+    pub fn append_at(
+        &mut self,
+        actor: &str,
+        action: AuditAction,
+        timestamp: i64,
+    ) -> Result<&AuditEntry, Error> {
+        let sequence = self.entries.len() as u64;
+        let previous_hash = self.head_hash().to_string();
+        let hash = AuditEntry::compute_hash(sequence, timestamp, actor, &action, &previous_hash)?;
+        let entry = AuditEntry {
+            sequence,
+            timestamp,
+            actor: actor.to_string(),
+            action,
+            previous_hash,
+            hash,
+        };
+
+        if let Some(path) = &self.path {
+            Self::write_line(path, &entry)?;
+        }
+        self.entries.push(entry);
+        Ok(&self.entries[self.entries.len() - 1])
+    }
+
+    fn write_line(path: &Path, entry: &AuditEntry) -> Result<(), Error> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| Error::Other(format!("Falha ao criar {}: {}", parent.display(), e)))?;
+        }
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| {
+                file.write_all(&line)?;
+                file.sync_data()
+            })
+            .map_err(|e| {
+                Error::Other(format!(
+                    "Falha ao gravar log de auditoria em {}: {}",
+                    path.display(),
+                    e
+                ))
+            })
+    }
+
+    /// Confere a cadeia inteira deste log
+    pub fn verify(&self) -> Result<(), Error> {
+        Self::verify_entries(&self.entries).map(|_| ())
+    }
+
+    /// Confere sequência, encadeamento e hash de cada entrada; devolve o hash da
+    /// última, para comparação com um valor ancorado externamente
+    pub fn verify_entries(entries: &[AuditEntry]) -> Result<String, Error> {
+        let mut previous = AUDIT_GENESIS_HASH.to_string();
+        for (index, entry) in entries.iter().enumerate() {
+            if entry.sequence != index as u64 || entry.previous_hash != previous {
+                return Err(Error::InvalidFormat(format!(
+                    "Log de auditoria fora de ordem ou com entradas faltando na posição {}",
+                    index
+                )));
+            }
+            if entry.hash != entry.expected_hash()? {
+                return Err(Error::InvalidFormat(format!(
+                    "Entrada {} do log de auditoria adulterada",
+                    entry.sequence
+                )));
+            }
+            previous = entry.hash.clone();
+        }
+        Ok(previous)
+    }
+}
+
+/// Registra no log os eventos do nó que são ações administrativas: criação de
+/// tokens (emissão do suprimento inicial), banimentos de validadores, mudanças de
+/// papéis e paradas de emergência
+pub fn record_events(log: &SharedAuditLog, events: &EventBus) -> SubscriptionId {
+    let log = Arc::clone(log);
+    events.on(move |event| {
+        let (actor, action) = match event {
+            AppEvent::TokenCreated {
+                token_id,
+                owner,
+                total_supply,
+                ..
+            } => (
+                owner.as_str(),
+                AuditAction::TokenMinted {
+                    token_id: *token_id,
+                    to: owner.clone(),
+                    amount: *total_supply,
+                },
+            ),
+            AppEvent::ValidatorBanned {
+                validator_id,
+                duration_secs,
+            } => (
+                "consensus",
+                AuditAction::ValidatorBanned {
+                    validator_id: validator_id.clone(),
+                    duration_secs: *duration_secs,
+                },
+            ),
+            AppEvent::RoleAssigned {
+                subject,
+                role,
+                granted,
+                by,
+                ..
+            } => (
+                by.as_str(),
+                AuditAction::RoleAssigned {
+                    subject: subject.clone(),
+                    role: *role,
+                    granted: *granted,
+                },
+            ),
+            AppEvent::ChainHaltChanged {
+                halted,
+                reason,
+                approvers,
+                by,
+                height,
+            } => (
+                by.as_str(),
+                AuditAction::ChainHaltChanged {
+                    halted: *halted,
+                    reason: reason.clone(),
+                    approvers: approvers.clone(),
+                    height: *height,
+                },
+            ),
+            _ => return,
+        };
+        if let Err(e) = log.lock().append(actor, action) {
+            warn!("Falha ao registrar ação no log de auditoria: {}", e);
+        }
+    })
+}
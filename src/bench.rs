@@ -0,0 +1,203 @@
+// Drivers de benchmark reutilizáveis (feature `bench`).
+// Cada função `bench_*` registra um grupo no Criterion; os construtores de
+// fixtures ficam públicos para que outros crates meçam os mesmos cenários.
+
+use crate::blockchain::merkle::EMPTY_ROOT;
+use crate::blockchain::{Block, Blockchain};
+use crate::transaction::{SecureTransaction, Transaction};
+use criterion::{black_box, BatchSize, BenchmarkId, Criterion, Throughput};
+use pqcrypto_dilithium::dilithium5::{self, PublicKey, SecretKey};
+use pqcrypto_traits::sign::PublicKey as _;
+
+/// Hash usado como `previous_hash` do bloco gênese das fixtures
+pub const BENCH_GENESIS_HASH: &str =
+    "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Gera um endereço alfanumérico determinístico de 40 caracteres
+pub fn bench_address(prefix: char, index: usize) -> String {
+    format!("{}{:039}", prefix, index)
+}
+
+/// Par de chaves Dilithium5 para as fixtures
+pub fn bench_keypair() -> (PublicKey, SecretKey) {
+    dilithium5::keypair()
+}
+
+/// Transação não assinada com remetente derivado de `index`
+pub fn unsigned_transaction(index: usize, public_key: &PublicKey) -> Transaction {
+    let mut tx = Transaction::new(
+        bench_address('a', index),
+        bench_address('b', index),
+        100 + index as u64,
+        public_key.as_bytes().to_vec(),
+    )
+    .expect("endereços de benchmark devem ser válidos");
+    tx.update_hash().expect("hash deve ser calculável");
+    tx
+}
+
+/// Transação assinada com remetente derivado de `index`
+pub fn signed_transaction(index: usize, keys: &(PublicKey, SecretKey)) -> Transaction {
+    let mut tx = unsigned_transaction(index, &keys.0);
+    tx.sign(&keys.1).expect("assinatura deve funcionar");
+    tx
+}
+
+/// Transações seguras assinadas com o mesmo par de chaves e nonces crescentes
+pub fn secure_transactions(count: usize, keys: &(PublicKey, SecretKey)) -> Vec<SecureTransaction> {
+    let timestamp = chrono::Utc::now().timestamp();
+    (0..count)
+        .map(|i| {
+            SecureTransaction::new(
+                bench_address('a', i),
+                bench_address('b', i),
+                100 + i as u64,
+                timestamp,
+                i as u64 + 1,
+                &keys.1,
+                &keys.0,
+            )
+            .expect("transação segura deve ser criada")
+        })
+        .collect()
+}
+
+/// Blockchain com `blocks` blocos encadeados (além do gênese), cada um com
+/// `transactions_per_block` transações seguras válidas
+pub fn build_chain(
+    blocks: usize,
+    transactions_per_block: usize,
+    keys: &(PublicKey, SecretKey),
+) -> Blockchain {
+    let mut blockchain = Blockchain::new().expect("blockchain deve ser criada");
+    let genesis = Block::new(0, Vec::new(), Vec::new(), BENCH_GENESIS_HASH.to_string())
+        .expect("bloco gênese deve ser criado");
+    blockchain.chain.push(genesis);
+
+    for index in 1..=blocks {
+        let previous_hash = blockchain.chain[index - 1].hash.clone();
+        let block = Block::new(
+            index as u64,
+            secure_transactions(transactions_per_block, keys),
+            Vec::new(),
+            previous_hash,
+        )
+        .expect("bloco deve ser criado");
+        blockchain.chain.push(block);
+    }
+
+    blockchain
+}
+
+/// Assinatura Dilithium5 de uma transação
+pub fn bench_dilithium_sign(c: &mut Criterion) {
+    let keys = bench_keypair();
+    let tx = unsigned_transaction(0, &keys.0);
+
+    let mut group = c.benchmark_group("dilithium");
+    group.bench_function("sign", |b| {
+        b.iter_batched(
+            || tx.clone(),
+            |mut tx| tx.sign(black_box(&keys.1)).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+/// Verificação Dilithium5 de uma transação assinada
+pub fn bench_dilithium_verify(c: &mut Criterion) {
+    let keys = bench_keypair();
+    let tx = signed_transaction(0, &keys);
+
+    let mut group = c.benchmark_group("dilithium");
+    group.bench_function("verify", |b| {
+        b.iter(|| black_box(&tx).verify(black_box(&keys.0)).unwrap())
+    });
+    group.finish();
+}
+
+/// Hash de bloco para diferentes quantidades de transações
+pub fn bench_block_hash(c: &mut Criterion) {
+    let keys = bench_keypair();
+
+    let mut group = c.benchmark_group("block_hash");
+    for count in [0usize, 16, 128] {
+        let transactions = secure_transactions(count, &keys);
+        group.throughput(Throughput::Elements(count.max(1) as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(count),
+            &transactions,
+            |b, transactions| {
+                b.iter(|| {
+                    Block::calculate_hash(
+                        black_box(1),
+                        black_box(1_700_000_000),
+                        black_box(transactions),
+                        &Vec::new(),
+                        black_box(BENCH_GENESIS_HASH),
+                        &EMPTY_ROOT,
+                    )
+                    .unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Validação completa da cadeia (encadeamento, assinaturas e hashes)
+pub fn bench_chain_validation(c: &mut Criterion) {
+    let keys = bench_keypair();
+
+    let mut group = c.benchmark_group("chain_validation");
+    group.sample_size(10);
+    for blocks in [4usize, 16] {
+        let blockchain = build_chain(blocks, 4, &keys);
+        group.throughput(Throughput::Elements(blocks as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(blocks),
+            &blockchain,
+            |b, blockchain| b.iter(|| assert!(blockchain.is_chain_valid().unwrap())),
+        );
+    }
+    group.finish();
+}
+
+/// Admissão de um lote de transações assinadas no mempool
+pub fn bench_mempool_admission(c: &mut Criterion) {
+    const BATCH: usize = 32;
+    let keys = bench_keypair();
+    let transactions: Vec<Transaction> = (0..BATCH).map(|i| signed_transaction(i, &keys)).collect();
+
+    let mut group = c.benchmark_group("mempool");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(BATCH as u64));
+    group.bench_function("admission", |b| {
+        b.iter_batched(
+            || {
+                (
+                    Blockchain::new().expect("blockchain deve ser criada"),
+                    transactions.clone(),
+                )
+            },
+            |(mut blockchain, transactions)| {
+                for tx in transactions {
+                    blockchain.submit_transaction(tx).unwrap();
+                }
+                blockchain
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+/// Registra todos os drivers de benchmark
+pub fn bench_all(c: &mut Criterion) {
+    bench_dilithium_sign(c);
+    bench_dilithium_verify(c);
+    bench_block_hash(c);
+    bench_chain_validation(c);
+    bench_mempool_admission(c);
+}
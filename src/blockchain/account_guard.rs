@@ -0,0 +1,148 @@
+use super::blockchain::{Address, Blockchain};
+use crate::error::TransactionError;
+use crate::transaction::{GuardRule, Operation, OperationKind};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Valor com mudança agendada: `current` vale a partir de `effective_at`; antes
+/// disso continua valendo `previous`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Scheduled<T> {
+    pub current: T,
+    pub previous: T,
+    pub effective_at: u64,
+}
+
+impl<T: Clone> Scheduled<T> {
+    pub fn at(&self, height: u64) -> &T {
+        if height >= self.effective_at {
+            &self.current
+        } else {
+            &self.previous
+        }
+    }
+
+    /// Agenda `value` para `height + delay`, mantendo até lá o que vale em `height`
+    fn set(&mut self, value: T, height: u64, delay: u64) {
+        self.previous = self.at(height).clone();
+        self.current = value;
+        self.effective_at = height.saturating_add(delay);
+    }
+}
+
+/// Restrições de saída de uma conta, definidas pelo próprio dono. Servem de rede de
+/// proteção contra roubo de chave: quem tomar a chave só consegue afrouxá-las
+/// depois do atraso, tempo em que o dono ainda pode reagir
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct AccountGuard {
+    /// Só destinos com `GuardRule::Allow` recebem
+    pub allow_only: Scheduled<bool>,
+    /// Atraso, em blocos, das mudanças que afrouxam as restrições
+    pub delay_blocks: Scheduled<u64>,
+    pub destinations: BTreeMap<Address, Scheduled<Option<GuardRule>>>,
+}
+
+/// Ordem de permissividade das regras de destino: bloqueado, sem regra, liberado
+fn rule_rank(rule: Option<GuardRule>) -> u8 {
+    match rule {
+        Some(GuardRule::Deny) => 0,
+        None => 1,
+        Some(GuardRule::Allow) => 2,
+    }
+}
+
+impl AccountGuard {
+    pub fn delay_at(&self, height: u64) -> u64 {
+        *self.delay_blocks.at(height)
+    }
+
+    pub fn rule_at(&self, destination: &str, height: u64) -> Option<GuardRule> {
+        self.destinations
+            .get(destination)
+            .and_then(|rule| *rule.at(height))
+    }
+
+    /// Se a conta pode enviar para `destination` no bloco em `height`
+    pub fn permits(&self, destination: &str, height: u64) -> bool {
+        match self.rule_at(destination, height) {
+            Some(GuardRule::Deny) => false,
+            Some(GuardRule::Allow) => true,
+            None => !*self.allow_only.at(height),
+        }
+    }
+}
+
+impl Blockchain {
+    pub fn account_guard(&self, address: &str) -> Option<&AccountGuard> {
+        self.account_guards.get(address)
+    }
+
+    /// Recusa a saída de `from` para `to` quando as restrições de `from` não a
+    /// admitem; contas sem restrições enviam para qualquer destino
+    pub(super) fn check_account_guard(
+        &self,
+        from: &str,
+        to: &str,
+        height: u64,
+    ) -> Result<(), TransactionError> {
+        match self.account_guard(from) {
+            Some(guard) if !guard.permits(to, height) => Err(TransactionError::DestinationBlocked(
+                format!("{} não envia para {}", from, to),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Só o dono da conta, assinando com a chave do próprio endereço, muda as
+    /// restrições dela
+    pub(super) fn check_account_guard_operation(
+        &self,
+        operation: &Operation,
+    ) -> Result<(), TransactionError> {
+        if !self.is_account_key(&operation.author, &operation.public_key) {
+            return Err(TransactionError::InvalidParameter(format!(
+                "Chave da operação não corresponde a {}",
+                operation.author
+            )));
+        }
+        Ok(())
+    }
+
+    /// Mudanças que apertam as restrições valem já no bloco em `height`; as que
+    /// afrouxam esperam o atraso vigente da conta
+    pub(super) fn apply_account_guard_operation(
+        &mut self,
+        operation: &Operation,
+        height: u64,
+    ) -> Result<(), TransactionError> {
+        self.check_account_guard_operation(operation)?;
+        let guard = self
+            .account_guards
+            .entry(operation.author.clone())
+            .or_default();
+        let delay = guard.delay_at(height);
+        match &operation.kind {
+            OperationKind::SetAccountGuard {
+                allow_only,
+                delay_blocks,
+            } => {
+                let loosens = !*allow_only && *guard.allow_only.at(height);
+                guard
+                    .allow_only
+                    .set(*allow_only, height, if loosens { delay } else { 0 });
+                let shortens = *delay_blocks < delay;
+                guard
+                    .delay_blocks
+                    .set(*delay_blocks, height, if shortens { delay } else { 0 });
+            }
+            OperationKind::SetGuardRule { destination, rule } => {
+                let entry = guard.destinations.entry(destination.clone()).or_default();
+                let loosens = rule_rank(*rule) > rule_rank(*entry.at(height));
+                entry.set(*rule, height, if loosens { delay } else { 0 });
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,278 @@
+use super::block::Block;
+use super::blockchain::Blockchain;
+use crate::error::Error;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Modo de armazenamento do nó
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StorageMode {
+    /// Permite podar assinaturas abaixo de checkpoints; não guarda histórico de estado
+    #[default]
+    Pruned,
+    /// Retém todas as assinaturas, recibos e o histórico de estado por altura
+    Archive,
+}
+
+/// Recibo de inclusão de uma transação em um bloco
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionReceipt {
+    pub height: u64,
+    pub position: usize,
+    pub block_hash: String,
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub nonce: u64,
+    pub timestamp: i64,
+}
+
+/// Estado completo da blockchain após o bloco em `height`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct HistoricalState {
+    pub height: u64,
+    pub block_hash: String,
+    /// Saldos por token e endereço
+    pub balances: HashMap<String, HashMap<String, u64>>,
+    pub nonces: HashMap<String, u64>,
+    /// Estado (campo `data`) de cada contrato, por endereço
+    pub contracts: HashMap<String, Vec<u8>>,
+}
+
+impl HistoricalState {
+    pub fn balance_of(&self, token_id: &str, address: &str) -> u64 {
+        self.balances
+            .get(token_id)
+            .and_then(|balances| balances.get(address))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// Alterações de estado introduzidas por um bloco
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct StateDiff {
+    block_hash: String,
+    balances: Vec<(String, String, u64)>,
+    nonces: Vec<(String, u64)>,
+    contracts: Vec<(String, Vec<u8>)>,
+    receipts: Vec<TransactionReceipt>,
+}
+
+/// Histórico de estado de um nó de arquivo, guardado como diferenças por altura
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchiveStore {
+    diffs: BTreeMap<u64, StateDiff>,
+    #[serde(skip)]
+    latest: Option<HistoricalState>,
+}
+
+impl ArchiveStore {
+    /// Alturas com estado registrado
+    pub fn heights(&self) -> impl Iterator<Item = u64> + '_ {
+        self.diffs.keys().copied()
+    }
+
+    fn replay(&self, height: u64) -> HistoricalState {
+        let mut state = HistoricalState::default();
+        for (h, diff) in self.diffs.range(..=height) {
+            state.height = *h;
+            state.block_hash = diff.block_hash.clone();
+            for (token_id, address, balance) in &diff.balances {
+                state
+                    .balances
+                    .entry(token_id.clone())
+                    .or_default()
+                    .insert(address.clone(), *balance);
+            }
+            for (address, nonce) in &diff.nonces {
+                state.nonces.insert(address.clone(), *nonce);
+            }
+            for (address, data) in &diff.contracts {
+                state.contracts.insert(address.clone(), data.clone());
+            }
+        }
+        state
+    }
+
+    /// Descarta o estado das alturas a partir de `height`
+    pub(super) fn truncate(&mut self, height: u64) {
+        self.diffs.split_off(&height);
+        self.latest = None;
+    }
+
+    /// Registra o estado atual da blockchain após a inclusão de `block`
+    fn record(&mut self, blockchain: &Blockchain, block: &Block) {
+        let previous = match self.latest.take() {
+            Some(state) => state,
+            None => self
+                .diffs
+                .keys()
+                .next_back()
+                .map(|h| self.replay(*h))
+                .unwrap_or_default(),
+        };
+
+        let mut diff = StateDiff {
+            block_hash: block.hash.clone(),
+            ..StateDiff::default()
+        };
+        let mut current = HistoricalState {
+            height: block.index,
+            block_hash: block.hash.clone(),
+            contracts: previous.contracts.clone(),
+            ..HistoricalState::default()
+        };
+
+        for (token_id, token) in &blockchain.tokens {
+            for (address, balance) in &token.balances {
+                let before = previous
+                    .balances
+                    .get(token_id)
+                    .and_then(|balances| balances.get(address));
+                if before != Some(balance) {
+                    diff.balances
+                        .push((token_id.clone(), address.clone(), *balance));
+                }
+            }
+            current
+                .balances
+                .insert(token_id.clone(), token.balances.clone());
+        }
+        // Endereços que sumiram do mapa de saldos passam a valer zero
+        for (token_id, balances) in &previous.balances {
+            for address in balances.keys() {
+                let still_present = current
+                    .balances
+                    .get(token_id)
+                    .is_some_and(|b| b.contains_key(address));
+                if !still_present {
+                    diff.balances.push((token_id.clone(), address.clone(), 0));
+                    current
+                        .balances
+                        .entry(token_id.clone())
+                        .or_default()
+                        .insert(address.clone(), 0);
+                }
+            }
+        }
+
+        for (address, nonce) in &blockchain.nonces {
+            if previous.nonces.get(address) != Some(nonce) {
+                diff.nonces.push((address.clone(), *nonce));
+            }
+        }
+        current.nonces = blockchain.nonces.clone();
+
+        for contract in &block.contracts {
+            if current.contracts.get(&contract.address) != Some(&contract.data) {
+                diff.contracts
+                    .push((contract.address.clone(), contract.data.clone()));
+                current
+                    .contracts
+                    .insert(contract.address.clone(), contract.data.clone());
+            }
+        }
+
+        diff.receipts = block
+            .transactions
+            .iter()
+            .enumerate()
+            .map(|(position, tx)| TransactionReceipt {
+                height: block.index,
+                position,
+                block_hash: block.hash.clone(),
+                from: tx.from.clone(),
+                to: tx.to.clone(),
+                amount: tx.amount,
+                nonce: tx.nonce,
+                timestamp: tx.timestamp,
+            })
+            .collect();
+
+        self.diffs.insert(block.index, diff);
+        self.latest = Some(current);
+    }
+}
+
+impl Blockchain {
+    /// Ativa o modo de arquivo e registra o estado atual na altura do último bloco
+    pub fn enable_archive_mode(&mut self) {
+        self.storage_mode = StorageMode::Archive;
+        self.record_archive_state();
+    }
+
+    pub fn is_archive(&self) -> bool {
+        self.storage_mode == StorageMode::Archive
+    }
+
+    /// Registra o estado atual na altura do último bloco (apenas em modo de arquivo)
+    pub(crate) fn record_archive_state(&mut self) {
+        if !self.is_archive() {
+            return;
+        }
+        if let Some(block) = self.chain.last() {
+            let mut archive = std::mem::take(&mut self.archive);
+            archive.record(self, block);
+            self.archive = archive;
+        }
+    }
+
+    fn require_archive(&self) -> Result<(), Error> {
+        if self.is_archive() {
+            Ok(())
+        } else {
+            Err(Error::Other(
+                "Consulta histórica disponível apenas em nós de arquivo".to_string(),
+            ))
+        }
+    }
+
+    /// Estado completo (saldos, nonces e contratos) após o bloco em `height`
+    pub fn state_at(&self, height: u64) -> Result<HistoricalState, Error> {
+        self.require_archive()?;
+
+        let first = self.archive.diffs.keys().next();
+        let last = self.archive.diffs.keys().next_back();
+        match (first, last) {
+            (Some(first), Some(last)) if *first <= height && height <= *last => {
+                Ok(self.archive.replay(height))
+            }
+            _ => Err(Error::InvalidBlock(format!(
+                "Sem estado registrado para a altura {}",
+                height
+            ))),
+        }
+    }
+
+    /// Recibos das transações incluídas no bloco em `height`
+    pub fn receipts_at(&self, height: u64) -> Result<Vec<TransactionReceipt>, Error> {
+        self.require_archive()?;
+
+        self.archive
+            .diffs
+            .get(&height)
+            .map(|diff| diff.receipts.clone())
+            .ok_or_else(|| {
+                Error::InvalidBlock(format!("Sem recibos registrados para a altura {}", height))
+            })
+    }
+
+    /// Histórico do saldo de um endereço: (altura, saldo) a cada alteração
+    pub fn balance_history(&self, token_id: &str, address: &str) -> Result<Vec<(u64, u64)>, Error> {
+        self.require_archive()?;
+
+        Ok(self
+            .archive
+            .diffs
+            .iter()
+            .flat_map(|(height, diff)| {
+                diff.balances
+                    .iter()
+                    .filter(|(t, a, _)| t == token_id && a == address)
+                    .map(|(_, _, balance)| (*height, *balance))
+            })
+            .collect())
+    }
+}
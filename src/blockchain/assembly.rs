@@ -0,0 +1,133 @@
+use super::block::Block;
+use super::spv::transaction_id;
+use crate::error::Error;
+use crate::transaction::{Operation, SecureTransaction, Transaction};
+use std::collections::HashMap;
+
+/// Itens do mempool escolhidos para um bloco, na ordem em que são executados:
+/// operações e depois transferências
+#[derive(Debug, Clone, Default)]
+pub struct MempoolSelection {
+    pub transactions: Vec<SecureTransaction>,
+    pub operations: Vec<Operation>,
+    pub transfers: Vec<Transaction>,
+}
+
+impl From<Vec<SecureTransaction>> for MempoolSelection {
+    fn from(transactions: Vec<SecureTransaction>) -> Self {
+        Self {
+            transactions,
+            ..Self::default()
+        }
+    }
+}
+
+impl MempoolSelection {
+    /// Itens de `block` na ordem de `item_ids`, a lista de uma proposta
+    /// (`Block::item_ids`). Falha se a lista citar um item que o bloco não traz
+    /// ou deixar algum de fora
+    pub fn from_ids(block: &Block, item_ids: &[String]) -> Result<Self, Error> {
+        let transactions: HashMap<_, _> = block
+            .transactions
+            .iter()
+            .map(|tx| (transaction_id(tx), tx))
+            .collect();
+        let operations: HashMap<_, _> = block
+            .operations
+            .iter()
+            .map(|operation| (operation.id(), operation))
+            .collect();
+        let transfers: HashMap<_, _> = block.transfers.iter().map(|tx| (tx.txid(), tx)).collect();
+
+        let mut selection = Self::default();
+        for id in item_ids {
+            if let Some(tx) = transactions.get(id) {
+                selection.transactions.push((*tx).clone());
+            } else if let Some(operation) = operations.get(id) {
+                selection.operations.push((*operation).clone());
+            } else if let Some(tx) = transfers.get(id) {
+                selection.transfers.push((*tx).clone());
+            } else {
+                return Err(Error::InvalidBlock(format!(
+                    "Item {} da proposta ausente do bloco {}",
+                    id, block.index
+                )));
+            }
+        }
+        if item_ids.len() != block.item_count() {
+            return Err(Error::InvalidBlock(format!(
+                "Bloco {} traz itens fora da proposta",
+                block.index
+            )));
+        }
+        Ok(selection)
+    }
+}
+
+impl Block {
+    /// Identificadores dos itens do bloco, como listados nas propostas:
+    /// transações, operações e transferências, na ordem do corpo
+    pub fn item_ids(&self) -> Vec<String> {
+        self.transactions
+            .iter()
+            .map(transaction_id)
+            .chain(self.operations.iter().map(Operation::id))
+            .chain(self.transfers.iter().map(Transaction::txid))
+            .collect()
+    }
+
+    fn item_count(&self) -> usize {
+        self.transactions.len() + self.operations.len() + self.transfers.len()
+    }
+}
+
+/// Monta o bloco `height` sobre o bloco de hash `parent`, com os itens
+/// escolhidos do mempool e o instante `time` (segundos).
+///
+/// Não lê relógio nem estado: as mesmas entradas dão sempre os mesmos bytes. O
+/// proponente monta o bloco por aqui e o verificador o remonta a partir do que
+/// recebeu (`verify_assembly`), de modo que qualquer diferença entre os dois
+/// lados aparece como bloco divergente em vez de estado divergente.
+pub fn assemble_block(
+    mempool_selection: impl Into<MempoolSelection>,
+    parent: &str,
+    height: u64,
+    time: u64,
+) -> Result<Block, Error> {
+    let selection = mempool_selection.into();
+    let mut block = Block::with_timestamp(
+        height,
+        time,
+        selection.transactions,
+        Vec::new(),
+        parent.to_string(),
+    )?;
+    block.operations = selection.operations;
+    block.transfers = selection.transfers;
+    block.hash = block.compute_hash()?;
+    Ok(block)
+}
+
+/// Confere que `block` tem exatamente os bytes que `assemble_block` produz com
+/// os itens listados em `item_ids`, na ordem da lista, e o mesmo pai, altura e
+/// instante, desconsiderando o que o proponente acrescenta depois da montagem:
+/// assinatura e resumo de execução
+pub fn verify_assembly(block: &Block, item_ids: &[String]) -> Result<(), Error> {
+    let expected = assemble_block(
+        MempoolSelection::from_ids(block, item_ids)?,
+        &block.previous_hash,
+        block.index,
+        block.timestamp,
+    )?;
+    let mut assembled = block.clone();
+    assembled.proposer = None;
+    assembled.validator_signature = None;
+    assembled.execution = None;
+    if assembled.to_bytes()? != expected.to_bytes()? {
+        return Err(Error::InvalidBlock(format!(
+            "Bloco {} diverge da montagem determinística",
+            block.index
+        )));
+    }
+    Ok(())
+}
@@ -1,19 +1,34 @@
+use super::execution::ExecutionSummary;
+use super::merkle::{contract_leaf, merkle_root, transaction_leaf, MerkleHash, EMPTY_ROOT};
 use crate::error::Error;
-use crate::smart_contract::SmartContract;
-use crate::transaction::SecureTransaction;
+use crate::network::NodeIdentity;
+use crate::smart_contract::{ContractLimits, SmartContract};
+use crate::transaction::{Operation, SecureTransaction, Transaction};
+use crate::utils::address::derive_address;
+use crate::utils::clock::clock;
+use crate::utils::compression::{self, Codec};
+use crate::utils::timestamp_policy::{TimestampContext, TimestampPolicy, TimestampViolation};
+use crate::utils::versioned::{self, Magic};
+use bincode::Options;
 use pqcrypto_dilithium::dilithium5;
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Sha3_256};
 use std::collections::HashSet;
-use std::time::{SystemTime, UNIX_EPOCH};
 use subtle::ConstantTimeEq;
 
 // Constantes
 pub const MAX_BLOCK_SIZE: usize = 1024 * 1024; // 1MB
-pub const MAX_FUTURE_TIME_DRIFT: u64 = 3600; // 1 hora
-pub const MAX_PAST_TIME_DRIFT: u64 = 7200; // 2 horas
+const BLOCK_SIGNATURE_DOMAIN: &str = "kybelith-block-v1";
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Versão da codificação binária de `Block::to_bytes`. Blocos gravados antes do
+/// envelope (versão 0) têm o mesmo layout bincode da versão 1, sem prefixo; a
+/// versão 2 acrescenta o resumo de execução e a 3, operações e transferências.
+pub const BLOCK_ENCODING_VERSION: u8 = 3;
+const BLOCK_MAGIC: &Magic = b"KBK";
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Block {
     pub index: u64,
     pub timestamp: u64,
@@ -21,9 +36,101 @@ pub struct Block {
     pub contracts: Vec<SmartContract>,
     pub previous_hash: String,
     pub hash: String,
+    /// Endereço do validador que propôs o bloco, derivado da sua chave de consenso
+    #[serde(default)]
+    pub proposer: Option<String>,
+    /// Assinatura Dilithium do proponente sobre `header_bytes`
+    #[cfg_attr(
+        feature = "cbor",
+        serde(with = "serde_bytes"),
+        schemars(with = "Option<Vec<u8>>")
+    )]
     pub validator_signature: Option<Vec<u8>>,
     pub nonce: u64,
     pub processed_transactions: HashSet<String>,
+    /// Resumo da execução, assinado junto com o cabeçalho; ausente em blocos
+    /// anteriores aos recibos de execução
+    #[serde(default)]
+    pub execution: Option<ExecutionSummary>,
+    /// Operações processadas no bloco, antes das transferências; as descartadas
+    /// na execução ficam marcadas nos recibos
+    #[serde(default)]
+    pub operations: Vec<Operation>,
+    /// Transferências processadas no bloco, no formato do mempool: a assinatura de
+    /// `Transaction` cobre o token, que `SecureTransaction` não guarda
+    #[serde(default)]
+    pub transfers: Vec<Transaction>,
+}
+
+/// Layout bincode das versões 0 e 1, sem o resumo de execução
+#[derive(Deserialize)]
+struct LegacyBlock {
+    index: u64,
+    timestamp: u64,
+    transactions: Vec<SecureTransaction>,
+    contracts: Vec<SmartContract>,
+    previous_hash: String,
+    hash: String,
+    proposer: Option<String>,
+    validator_signature: Option<Vec<u8>>,
+    nonce: u64,
+    processed_transactions: HashSet<String>,
+}
+
+impl From<LegacyBlock> for Block {
+    fn from(legacy: LegacyBlock) -> Self {
+        Block {
+            index: legacy.index,
+            timestamp: legacy.timestamp,
+            transactions: legacy.transactions,
+            contracts: legacy.contracts,
+            previous_hash: legacy.previous_hash,
+            hash: legacy.hash,
+            proposer: legacy.proposer,
+            validator_signature: legacy.validator_signature,
+            nonce: legacy.nonce,
+            processed_transactions: legacy.processed_transactions,
+            execution: None,
+            operations: Vec::new(),
+            transfers: Vec::new(),
+        }
+    }
+}
+
+/// Layout bincode da versão 2, sem operações e transferências
+#[derive(Deserialize)]
+struct BlockV2 {
+    index: u64,
+    timestamp: u64,
+    transactions: Vec<SecureTransaction>,
+    contracts: Vec<SmartContract>,
+    previous_hash: String,
+    hash: String,
+    proposer: Option<String>,
+    validator_signature: Option<Vec<u8>>,
+    nonce: u64,
+    processed_transactions: HashSet<String>,
+    execution: Option<ExecutionSummary>,
+}
+
+impl From<BlockV2> for Block {
+    fn from(v2: BlockV2) -> Self {
+        Block {
+            index: v2.index,
+            timestamp: v2.timestamp,
+            transactions: v2.transactions,
+            contracts: v2.contracts,
+            previous_hash: v2.previous_hash,
+            hash: v2.hash,
+            proposer: v2.proposer,
+            validator_signature: v2.validator_signature,
+            nonce: v2.nonce,
+            processed_transactions: v2.processed_transactions,
+            execution: v2.execution,
+            operations: Vec::new(),
+            transfers: Vec::new(),
+        }
+    }
 }
 
 impl Block {
@@ -33,13 +140,26 @@ impl Block {
         contracts: Vec<SmartContract>,
         previous_hash: String,
     ) -> Result<Self, Error> {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let timestamp = clock().now_secs().max(0) as u64;
+        Self::with_timestamp(index, timestamp, transactions, contracts, previous_hash)
+    }
 
-        let hash =
-            Self::calculate_hash(index, timestamp, &transactions, &contracts, &previous_hash)?;
+    /// Como `new`, com o instante dado em vez do relógio local
+    pub(super) fn with_timestamp(
+        index: u64,
+        timestamp: u64,
+        transactions: Vec<SecureTransaction>,
+        contracts: Vec<SmartContract>,
+        previous_hash: String,
+    ) -> Result<Self, Error> {
+        let hash = Self::calculate_hash(
+            index,
+            timestamp,
+            &transactions,
+            &contracts,
+            &previous_hash,
+            &EMPTY_ROOT,
+        )?;
 
         Ok(Block {
             index,
@@ -48,12 +168,159 @@ impl Block {
             contracts,
             previous_hash,
             hash,
+            proposer: None,
             validator_signature: None,
             nonce: 0,
             processed_transactions: HashSet::new(),
+            execution: None,
+            operations: Vec::new(),
+            transfers: Vec::new(),
         })
     }
 
+    /// Cabeçalho canônico assinado pelo proponente. O hash já cobre o conteúdo
+    /// pelas raízes Merkle (ver `calculate_hash`), que entram também aqui para
+    /// que blocos mantidos sem transações e contratos em memória ainda possam
+    /// ter a assinatura conferida; o proponente entra para que a
+    /// assinatura não possa ser atribuída a outro validador. Raízes de listas
+    /// vazias e o resumo de execução ausente ficam de fora, para que assinaturas
+    /// de blocos antigos continuem válidas.
+    pub fn header_bytes(&self) -> Vec<u8> {
+        self.header_bytes_for(&self.transactions_root(), &self.contracts_root())
+    }
+
+    /// `header_bytes` com as raízes de transações e contratos já conhecidas, para
+    /// blocos mantidos em memória sem esses itens
+    pub(super) fn header_bytes_for(
+        &self,
+        transactions_root: &MerkleHash,
+        contracts_root: &MerkleHash,
+    ) -> Vec<u8> {
+        let mut header = format!(
+            "{}:{}:{}:{}:{}:{}",
+            BLOCK_SIGNATURE_DOMAIN,
+            self.index,
+            self.timestamp,
+            self.previous_hash,
+            self.hash,
+            self.proposer.as_deref().unwrap_or_default()
+        );
+        let roots = [
+            ("tx", *transactions_root),
+            ("contracts", *contracts_root),
+            ("body", self.body_root()),
+        ];
+        for (label, root) in roots {
+            if root != EMPTY_ROOT {
+                header.push_str(&format!(":{}={}", label, hex::encode(root)));
+            }
+        }
+        if let Some(execution) = &self.execution {
+            header.push_str(&execution.header_suffix());
+        }
+        header.into_bytes()
+    }
+
+    /// Assina o bloco com a chave de consenso do nó, registrando-o como proponente
+    pub fn sign(&mut self, identity: &NodeIdentity) {
+        self.proposer = Some(derive_address(identity.public_key.as_bytes()));
+        self.validator_signature = Some(identity.sign_message(&self.header_bytes()));
+    }
+
+    /// Confere a assinatura do proponente com a chave `public_key`
+    pub fn verify_signature(&self, public_key: &[u8]) -> Result<(), Error> {
+        self.verify_header(public_key, &self.header_bytes())
+    }
+
+    /// Confere a assinatura do proponente sobre `header`, o `header_bytes` do bloco
+    pub(super) fn verify_header(&self, public_key: &[u8], header: &[u8]) -> Result<(), Error> {
+        let proposer = self
+            .proposer
+            .as_deref()
+            .ok_or_else(|| Error::InvalidBlock(format!("Bloco {} sem proponente", self.index)))?;
+        if derive_address(public_key) != proposer {
+            return Err(Error::InvalidPublicKey);
+        }
+        let signature = self
+            .validator_signature
+            .as_deref()
+            .ok_or(Error::InvalidSignature)?;
+
+        let public_key =
+            dilithium5::PublicKey::from_bytes(public_key).map_err(|_| Error::InvalidPublicKey)?;
+        let signature = dilithium5::DetachedSignature::from_bytes(signature)
+            .map_err(|_| Error::InvalidSignature)?;
+        dilithium5::verify_detached_signature(&signature, header, &public_key)
+            .map_err(|_| Error::InvalidSignature)
+    }
+
+    /// Decodifica um bloco recebido da rede, limitando o tamanho da entrada.
+    /// Aceita qualquer versão conhecida da codificação, inclusive a sem prefixo.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        if data.len() > MAX_BLOCK_SIZE {
+            return Err(Error::BlockTooLarge);
+        }
+
+        let options = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .with_limit(MAX_BLOCK_SIZE as u64);
+        let block: Block = match versioned::split(BLOCK_MAGIC, data) {
+            None => options.deserialize::<LegacyBlock>(data)?.into(),
+            Some((1, payload)) => options.deserialize::<LegacyBlock>(payload)?.into(),
+            Some((2, payload)) => options.deserialize::<BlockV2>(payload)?.into(),
+            Some((3, payload)) => options.deserialize(payload)?,
+            Some((version, _)) => {
+                return Err(Error::InvalidFormat(format!(
+                    "Codificação de bloco na versão {}; suportadas até {}",
+                    version, BLOCK_ENCODING_VERSION
+                )))
+            }
+        };
+
+        if block.size() > MAX_BLOCK_SIZE {
+            return Err(Error::BlockTooLarge);
+        }
+
+        Ok(block)
+    }
+
+    /// Codifica o bloco no formato binário usado na rede e em disco, com o
+    /// prefixo de versão
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let payload = bincode::serialize(self)?;
+        Ok(versioned::tag(BLOCK_MAGIC, BLOCK_ENCODING_VERSION, &payload))
+    }
+
+    /// Codifica o bloco em CBOR (ver `utils::cbor`)
+    #[cfg(feature = "cbor")]
+    pub fn to_cbor(&self) -> Result<Vec<u8>, Error> {
+        crate::utils::cbor::encode(self).map_err(Error::InvalidFormat)
+    }
+
+    /// Decodifica um bloco em CBOR, com os mesmos limites de `from_bytes`
+    #[cfg(feature = "cbor")]
+    pub fn from_cbor(data: &[u8]) -> Result<Self, Error> {
+        if data.len() > MAX_BLOCK_SIZE {
+            return Err(Error::BlockTooLarge);
+        }
+        let block: Block =
+            crate::utils::cbor::decode(data, MAX_BLOCK_SIZE).map_err(Error::InvalidFormat)?;
+        if block.size() > MAX_BLOCK_SIZE {
+            return Err(Error::BlockTooLarge);
+        }
+        Ok(block)
+    }
+
+    /// Codifica o bloco comprimido com `codec`, para disco ou gossip
+    pub fn to_compressed_bytes(&self, codec: Codec) -> Result<Vec<u8>, Error> {
+        compression::encode(codec, &self.to_bytes()?)
+    }
+
+    /// Decodifica um bloco comprimido; o codec vem no primeiro byte do payload
+    pub fn from_compressed_bytes(data: &[u8]) -> Result<Self, Error> {
+        Self::from_bytes(&compression::decode(data, MAX_BLOCK_SIZE)?)
+    }
+
     pub fn size(&self) -> usize {
         let mut size = 0;
 
@@ -82,11 +349,30 @@ impl Block {
         // Tamanho do nonce (u64 = 8 bytes)
         size += 8;
 
+        // Tamanho do proponente (se houver)
+        if let Some(proposer) = &self.proposer {
+            size += proposer.len();
+        }
+
         // Tamanho do validator_signature (se houver)
         if let Some(signature) = &self.validator_signature {
             size += signature.len();
         }
 
+        // Resumo de execução: raiz de 32 bytes e cinco contadores u64
+        if self.execution.is_some() {
+            size += 32 + 5 * 8;
+        }
+
+        // Corpo: operações e transferências codificadas
+        for operation in &self.operations {
+            let encoded = bincode::serialized_size(operation).map_or(usize::MAX, |n| n as usize);
+            size = size.saturating_add(encoded);
+        }
+        for transfer in &self.transfers {
+            size = size.saturating_add(transfer.encoded_size());
+        }
+
         size
     }
 
@@ -99,45 +385,34 @@ impl Block {
             return Err(Error::InvalidPreviousHash);
         }
 
-        self.verify_timestamp(
-            SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .map_err(|_| Error::InvalidTimestamp("Falha ao obter o tempo atual".to_string()))?
-                .as_secs() as i64,
-        )?;
+        self.verify_timestamp()?;
 
         for tx in &self.transactions {
             tx.verify(public_key, &tx.signature)?;
         }
 
+        let limits = ContractLimits::default();
+        for contract in &self.contracts {
+            contract.check_limits(&limits).map_err(|e| {
+                Error::InvalidBlock(format!("Contrato {} recusado: {}", contract.address, e))
+            })?;
+        }
+
         Ok(())
     }
 
-    fn verify_timestamp(&self, current_time: i64) -> Result<(), Error> {
-        let timestamp_u64 = self.timestamp as u64;
-
-        if timestamp_u64 > (current_time as u64 + MAX_FUTURE_TIME_DRIFT) {
-            return Err(Error::InvalidTimestamp("Timestamp no futuro".to_string()));
-        }
-
-        if (current_time as u64) > (timestamp_u64 + MAX_PAST_TIME_DRIFT) {
-            return Err(Error::StaleBlock);
+    fn verify_timestamp(&self) -> Result<(), Error> {
+        match TimestampPolicy::for_context(TimestampContext::Block)
+            .validate_secs(self.timestamp as i64)
+        {
+            Err(TimestampViolation::TooOld { .. }) => Err(Error::StaleBlock),
+            result => Ok(result?),
         }
-
-        Ok(())
     }
 
     fn validate_previous_hash(&self) -> Result<bool, Error> {
-        let block_data = format!(
-            "{}:{}:{}:{}",
-            self.index,
-            self.timestamp,
-            self.transactions.len(), // Usar o número de transações em vez de serializá-las
-            self.previous_hash
-        );
-
-        let current_hash = Self::calculate_quantum_hash(&block_data)?;
-        Ok(current_hash.as_slice().ct_eq(self.hash.as_bytes()).into())
+        let current_hash = self.compute_hash()?;
+        Ok(current_hash.as_bytes().ct_eq(self.hash.as_bytes()).into())
     }
 
     fn calculate_quantum_hash(data: &str) -> Result<Vec<u8>, Error> {
@@ -146,14 +421,19 @@ impl Block {
         Ok(hasher.finalize().to_vec())
     }
 
+    /// Hash de um bloco com estes campos e o corpo de raiz `body_root`. Cobre
+    /// altura, timestamp, contagens e bloco anterior e, pelas raízes Merkle, o
+    /// conteúdo de transações, contratos e corpo; como em `header_bytes`, raízes
+    /// de listas vazias ficam de fora e o hash de blocos sem itens não muda.
     pub fn calculate_hash(
         index: u64,
         timestamp: u64,
         transactions: &Vec<SecureTransaction>,
         contracts: &Vec<SmartContract>,
         previous_hash: &str,
+        body_root: &MerkleHash,
     ) -> Result<String, Error> {
-        let data = format!(
+        let mut data = format!(
             "{}:{}:{}:{}:{}",
             index,
             timestamp,
@@ -161,7 +441,31 @@ impl Block {
             contracts.len(),
             previous_hash
         );
+        let transaction_leaves: Vec<_> = transactions.iter().map(transaction_leaf).collect();
+        let contract_leaves: Vec<_> = contracts.iter().map(contract_leaf).collect();
+        let roots = [
+            ("tx", merkle_root(&transaction_leaves)),
+            ("contracts", merkle_root(&contract_leaves)),
+            ("body", *body_root),
+        ];
+        for (label, root) in roots {
+            if root != EMPTY_ROOT {
+                data.push_str(&format!(":{}={}", label, hex::encode(root)));
+            }
+        }
         let hash = Self::calculate_quantum_hash(&data)?;
         Ok(hex::encode(hash))
     }
+
+    /// Hash esperado para o conteúdo atual do bloco, corpo incluído
+    pub fn compute_hash(&self) -> Result<String, Error> {
+        Self::calculate_hash(
+            self.index,
+            self.timestamp,
+            &self.transactions,
+            &self.contracts,
+            &self.previous_hash,
+            &self.body_root(),
+        )
+    }
 }
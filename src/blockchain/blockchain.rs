@@ -1,88 +1,324 @@
+use super::account_guard::AccountGuard;
+use super::archive::{ArchiveStore, StorageMode};
+use super::assembly::{assemble_block, MempoolSelection};
 use super::block::Block;
+use super::execution::{BodySource, ExecutionReceipt, ExecutionSummary};
+use super::export;
+use super::format::BLOCKCHAIN_FORMAT_VERSION;
+use super::governance::Proposal;
+use super::halt::HaltRecord;
+use super::indexer::{TransactionIndex, TransactionRecord};
+use super::key_rotation::KeyRotation;
+use super::names::NameRecord;
+use super::oracle::OracleFeed;
+use super::pruning::CheckpointAttestation;
+use super::recovery::AccountRecovery;
+use super::state::ChainState;
+use super::status::{LifecycleState, TransactionStatusStore};
+use super::streaming::DetachedBlock;
+use super::supply::{SupplyChange, SupplyChangeKind};
+use super::throttle::SubmissionThrottle;
+use super::upgrade::UpgradeSignal;
+use super::validation_context::ValidationContext;
 use crate::blockchain::validacao;
 use crate::blockchain::validacao::Validator;
-use crate::constants::{MAX_BLOCK_SIZE, MAX_TIME_DRIFT, MAX_TRANSACTION_SIZE};
+use crate::config::{GenesisConfig, Limits};
+use crate::constants::{DEFAULT_CONFIRMATION_DEPTH, MAX_BLOCK_SIZE, MAX_SNAPSHOT_SIZE};
 use crate::error::Error;
 use crate::error::TransactionError;
+use crate::events::{AppEvent, EventBus};
+use crate::interchain::InterchainState;
 use crate::key_manager::KeyManager;
+use crate::network::NodeIdentity;
 use crate::quantum_crypto::QuantumCrypto;
-use crate::token::Token;
-use crate::transaction::{SecureTransaction, Transaction};
-use anyhow::{Context, Result};
-use oqs::kem::{Algorithm, Kem};
+use crate::rbac::AdminRole;
+use crate::token::{FungibleToken, Token, VestingSchedule};
+use crate::transaction::{
+    Operation, OperationKind, SecureTransaction, Transaction, TransactionProcessor, TransferRule,
+    VerificationService,
+};
+use crate::utils::address::derive_address;
+use crate::utils::clock::{clock, SharedClock};
+use crate::utils::compression::{self, Codec};
+use crate::utils::dilithium;
+use crate::utils::entropy;
+use crate::utils::timestamp_policy::{TimestampContext, TimestampPolicy};
+use crate::utils::version::ClientVersion;
+use oqs::kem::Algorithm;
 use oqs::Error as OqsError;
 use pqcrypto_dilithium::dilithium5::{self, SecretKey};
 use pqcrypto_traits::sign::PublicKey as PublicKeyTrait;
-use rusqlite::{params, Connection, Result as SqlResult};
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Sha3_256};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs::File;
-use std::io::{Read, Write};
+use std::io::Write;
+use std::sync::Arc;
 
 pub type Address = String;
 
-impl From<anyhow::Error> for TransactionError {
-    fn from(err: anyhow::Error) -> Self {
-        TransactionError::Other(err.to_string())
-    }
-}
-
 #[derive(Serialize, Deserialize)]
 pub struct Blockchain {
+    /// Versão do formato serializado; ver `BLOCKCHAIN_FORMAT_VERSION`
+    #[serde(default)]
+    pub format_version: u32,
+    /// Blocos confirmados, do gênesis ao topo; única cópia da cadeia
     pub chain: Vec<Block>,
+    /// Altura do primeiro bloco de `chain`; só as cópias de execução (ver
+    /// `scratch_at`) começam acima do gênesis
+    #[serde(skip)]
+    pub base_height: u64,
     pub tokens: HashMap<String, Token>, // Changed from u64 to String
     pub stakers: HashMap<Address, u64>,
     pub nonces: HashMap<Address, u64>,
     pub pending_transactions: Vec<Transaction>,
-    pub blocks: Vec<Block>,
-    pub transactions: Vec<Transaction>,
+    /// Transferências do mempool já aplicadas por blocos confirmados
+    #[serde(default)]
+    pub committed_transactions: Vec<Transaction>,
+    /// Operações assinadas (criação de tokens) aguardando o próximo bloco
+    #[serde(default)]
+    pub pending_operations: Vec<Operation>,
+    /// Operações já aplicadas por blocos confirmados
+    #[serde(default)]
+    pub operations: Vec<Operation>,
+    /// Localização das transações confirmadas, por txid e por endereço
+    #[serde(default)]
+    pub index: TransactionIndex,
+    /// Ciclo de vida de cada transação vista pelo nó, por txid
+    #[serde(default)]
+    pub transaction_statuses: TransactionStatusStore,
     pub next_token_id: u64,
     pub public_keys: HashMap<String, Vec<u8>>,
     #[serde(skip)] // Não serializar o validator
     pub validator: Validator,
+    /// Limites de tamanho do nó; configuração, não estado da cadeia
+    #[serde(skip)]
+    pub limits: Limits,
+    /// Submissões recentes por remetente, contadas contra `Limits`
+    #[serde(skip)]
+    pub throttle: SubmissionThrottle,
+    /// Blocos por cima para um bloco ser `Finality::Safe`; configuração do nó
+    #[serde(skip, default = "default_confirmation_depth")]
+    pub confirmation_depth: u64,
     #[serde(skip)]
     pub secret_keys: HashMap<String, SecretKey>,
+    /// Último checkpoint aceito; ancora a poda de assinaturas e as provas SPV
+    #[serde(default)]
+    pub checkpoint: Option<CheckpointAttestation>,
+    /// Resumo das assinaturas podadas, por altura
+    #[serde(default)]
+    pub pruned_blocks: HashMap<u64, String>,
+    #[serde(default)]
+    pub storage_mode: StorageMode,
+    /// Histórico de estado por altura, mantido apenas em modo de arquivo
+    #[serde(default)]
+    pub archive: ArchiveStore,
+    /// Chaves de consenso dos validadores autorizados a propor blocos, por endereço
+    #[serde(default)]
+    pub validator_keys: HashMap<Address, Vec<u8>>,
+    /// Altura a partir da qual todo bloco precisa da assinatura do proponente;
+    /// definida quando a primeira chave de validador é registrada
+    #[serde(default)]
+    pub signed_from: Option<u64>,
+    /// Chaves públicas de visualização registradas, por endereço
+    #[serde(default)]
+    pub view_keys: HashMap<Address, Vec<u8>>,
+    /// Chaves de visualização dos auditores; toda transferência confidencial precisa
+    /// trazer a abertura do valor cifrada para cada uma delas
+    #[serde(default)]
+    pub auditor_view_keys: Vec<Vec<u8>>,
+    /// Saldos confidenciais: compromisso de Pedersen por token e endereço
+    #[serde(default)]
+    pub confidential_balances: HashMap<String, HashMap<Address, Vec<u8>>>,
+    /// Papéis administrativos atribuídos, por endereço
+    #[serde(default)]
+    pub roles: HashMap<Address, BTreeSet<AdminRole>>,
+    /// Regras de transferência registradas pelos criadores, por token
+    #[serde(default)]
+    pub transfer_policies: HashMap<String, Vec<TransferRule>>,
+    /// Endereços marcados como verificados por KYC
+    #[serde(default)]
+    pub kyc_verified: BTreeSet<Address>,
+    /// Cronogramas de liberação, na ordem de criação; a posição é o identificador
+    #[serde(default)]
+    pub vesting_schedules: Vec<VestingSchedule>,
+    /// Propostas de governança, na ordem de criação; a posição é o identificador
+    #[serde(default)]
+    pub proposals: Vec<Proposal>,
+    /// Aprovações de admins para inverter o estado de parada, com o motivo de cada
+    #[serde(default)]
+    pub halt_approvals: BTreeMap<Address, String>,
+    /// Paradas e retomadas de emergência, na ordem em que ocorreram
+    #[serde(default)]
+    pub halt_history: Vec<HaltRecord>,
+    /// Versões mínimas do cliente agendadas, na ordem em que foram sinalizadas
+    #[serde(default)]
+    pub upgrade_signals: Vec<UpgradeSignal>,
+    /// Versão deste binário, comparada com `upgrade_signals` antes de propor
+    #[serde(skip)]
+    pub client_version: ClientVersion,
+    /// Oráculos de dados externos, por identificador
+    #[serde(default)]
+    pub oracle_feeds: BTreeMap<u64, OracleFeed>,
+    /// Registro de nomes (`alice.qst`), incluindo os expirados ainda não
+    /// registrados de novo
+    #[serde(default)]
+    pub names: BTreeMap<String, NameRecord>,
+    /// Restrições de saída que cada conta impôs a si mesma
+    #[serde(default)]
+    pub account_guards: BTreeMap<Address, AccountGuard>,
+    /// Esquemas de recuperação por guardiões, por conta
+    #[serde(default)]
+    pub recoveries: BTreeMap<Address, AccountRecovery>,
+    /// Trocas de chave por rotação ou recuperação; a última é a chave vigente
+    #[serde(default)]
+    pub key_rotations: BTreeMap<Address, Vec<KeyRotation>>,
+    /// Propostas de troca de dono ainda não aceitas, por token
+    #[serde(default)]
+    pub pending_token_owners: HashMap<String, Address>,
+    /// Administradores secundários nomeados pelo dono, por token
+    #[serde(default)]
+    pub token_admins: HashMap<String, BTreeSet<Address>>,
+    /// Tokens com as transferências públicas suspensas
+    #[serde(default)]
+    pub paused_tokens: BTreeSet<String>,
+    /// Emissões e queimas de cada token, na ordem em que ocorreram
+    #[serde(default)]
+    pub supply_history: Vec<SupplyChange>,
+    /// Recibos de execução de cada bloco, por altura; sua raiz vai no cabeçalho
+    #[serde(default)]
+    pub execution_receipts: BTreeMap<u64, Vec<ExecutionReceipt>>,
+    /// Estado anterior a cada bloco recente com corpo executado, por altura; é
+    /// para ele que `reorganize` volta ao reverter esses blocos. Só cobre blocos
+    /// com menos de `confirmation_depth` blocos por cima e não é persistido
+    #[serde(skip)]
+    pub revert_points: BTreeMap<u64, ChainState>,
+    /// Clientes leves, pacotes enviados e recebidos e vouchers entre cadeias
+    #[serde(default)]
+    pub interchain: InterchainState,
+    /// Blocos carregados só com o cabeçalho por `load_recent_from_file` ou
+    /// `load_recent_from_db`, com o resumo das assinaturas de cada um; o corpo
+    /// continua no disco de onde a cadeia foi lida
+    #[serde(skip)]
+    pub detached_blocks: BTreeMap<u64, DetachedBlock>,
+    /// Identidade com que este nó assina os blocos que produz
+    #[serde(skip)]
+    pub signer: Option<Arc<NodeIdentity>>,
+    /// Relógio do controle de envios e dos timestamps dos blocos produzidos; sem
+    /// ele, vale o relógio do nó
+    #[serde(skip)]
+    pub clock: Option<SharedClock>,
+    /// Assinantes de blocos confirmados, tokens criados e transferências aplicadas
+    #[serde(skip)]
+    pub events: EventBus,
+}
+
+fn default_confirmation_depth() -> u64 {
+    DEFAULT_CONFIRMATION_DEPTH
 }
 
 impl Blockchain {
-    pub fn new() -> anyhow::Result<Self> {
-        let mut blockchain = Blockchain {
+    pub fn new() -> Result<Self, Error> {
+        Self::from_genesis(&GenesisConfig::default())
+    }
+
+    /// Cadeia vazia com o estado inicial descrito por `genesis`
+    pub fn from_genesis(genesis: &GenesisConfig) -> Result<Self, Error> {
+        let mut blockchain = Self::empty();
+        blockchain.apply_genesis(genesis)?;
+        Ok(blockchain)
+    }
+
+    /// Cadeia sem blocos nem estado, nem mesmo o token nativo
+    pub(super) fn empty() -> Self {
+        Blockchain {
+            format_version: BLOCKCHAIN_FORMAT_VERSION,
             tokens: HashMap::new(),
             stakers: HashMap::new(),
             chain: Vec::new(),
+            base_height: 0,
             next_token_id: 0,
             nonces: HashMap::new(),
             pending_transactions: Vec::new(),
-            blocks: Vec::new(),
-            transactions: Vec::new(),
+            committed_transactions: Vec::new(),
+            pending_operations: Vec::new(),
+            operations: Vec::new(),
+            index: TransactionIndex::default(),
+            transaction_statuses: TransactionStatusStore::default(),
             public_keys: HashMap::new(),
             validator: Validator::new(MAX_BLOCK_SIZE, 300), // 5 minutos de desvio máximo
+            limits: Limits::default(),
+            throttle: SubmissionThrottle::default(),
+            confirmation_depth: DEFAULT_CONFIRMATION_DEPTH,
             secret_keys: HashMap::new(),
-        };
-
-        blockchain.create_quantum_secure_token()?;
-        Ok(blockchain)
+            checkpoint: None,
+            pruned_blocks: HashMap::new(),
+            storage_mode: StorageMode::default(),
+            archive: ArchiveStore::default(),
+            validator_keys: HashMap::new(),
+            signed_from: None,
+            view_keys: HashMap::new(),
+            auditor_view_keys: Vec::new(),
+            confidential_balances: HashMap::new(),
+            roles: HashMap::new(),
+            transfer_policies: HashMap::new(),
+            kyc_verified: BTreeSet::new(),
+            vesting_schedules: Vec::new(),
+            proposals: Vec::new(),
+            halt_approvals: BTreeMap::new(),
+            halt_history: Vec::new(),
+            upgrade_signals: Vec::new(),
+            client_version: ClientVersion::current(),
+            oracle_feeds: BTreeMap::new(),
+            names: BTreeMap::new(),
+            account_guards: BTreeMap::new(),
+            recoveries: BTreeMap::new(),
+            key_rotations: BTreeMap::new(),
+            pending_token_owners: HashMap::new(),
+            token_admins: HashMap::new(),
+            paused_tokens: BTreeSet::new(),
+            supply_history: Vec::new(),
+            execution_receipts: BTreeMap::new(),
+            revert_points: BTreeMap::new(),
+            interchain: InterchainState::default(),
+            detached_blocks: BTreeMap::new(),
+            signer: None,
+            clock: None,
+            events: EventBus::default(),
+        }
     }
 
     /// Cria o Quantum Secure Token (ID = 0).
-    pub fn create_quantum_secure_token(&mut self) -> anyhow::Result<()> {
+    pub fn create_quantum_secure_token(&mut self) -> Result<(), Error> {
+        self.apply_genesis(&GenesisConfig::default())
+    }
+
+    /// Cria o token nativo (ID 0) e cunha as alocações do gênesis
+    pub fn apply_genesis(&mut self, genesis: &GenesisConfig) -> Result<(), Error> {
         let kybelith_token = Token::new(
-            "Kybelith".to_string(),
-            "KYBL".to_string(),
-            10_000_000,           // Supply inicial aumentado
-            "system".to_string(), // Criador
+            genesis.token_name.clone(),
+            genesis.token_symbol.clone(),
+            genesis.initial_supply,
+            genesis.creator.clone(),
         )?;
 
+        let supply = kybelith_token.total_supply;
         self.tokens.insert(0.to_string(), kybelith_token);
-
-        // Adicionar saldo inicial para o administrador durante o desenvolvimento
-        let admin_address = "0x123...".to_string(); // Use o endereço real do admin
-        if let Some(token) = self.tokens.get_mut(&0.to_string()) {
-            *token.balances.entry(admin_address).or_insert(0) += 1_000_000; // Saldo inicial de desenvolvimento
+        self.record_supply_change("0", SupplyChangeKind::Issue, supply, "gênese");
+
+        for (address, amount) in &genesis.allocations {
+            let token = self.tokens.get_mut("0").ok_or(Error::TokenNotFound)?;
+            token.total_supply = token.total_supply.checked_add(*amount).ok_or_else(|| {
+                Error::InvalidInput("Alocações do gênese excedem o supply máximo".to_string())
+            })?;
+            *token.balances.entry(address.clone()).or_insert(0) += amount;
+            self.record_supply_change("0", SupplyChangeKind::Mint, *amount, "alocação de gênese");
         }
 
         self.next_token_id = 1;
+        self.index_balances([0], self.chain.len() as u64);
+        self.index
+            .record_stakes(self.chain.len() as u64, &self.stakers);
         Ok(())
     }
 
@@ -90,6 +326,25 @@ impl Blockchain {
         self.tokens.get(id)
     }
 
+    /// Blocos confirmados, do gênesis ao topo
+    pub fn blocks(&self) -> &[Block] {
+        &self.chain
+    }
+
+    pub fn latest_block(&self) -> Option<&Block> {
+        self.chain.last()
+    }
+
+    /// Número de blocos confirmados
+    pub fn height(&self) -> u64 {
+        self.base_height + self.chain.len() as u64
+    }
+
+    /// Transferências do mempool já aplicadas, na ordem de confirmação
+    pub fn committed_transactions(&self) -> &[Transaction] {
+        &self.committed_transactions
+    }
+
     /// Cria um novo token (para usuários).
     pub fn create_token(
         &mut self,
@@ -101,6 +356,12 @@ impl Blockchain {
         let token = Token::new(name, symbol, initial_supply, creator)?;
         let token_id = self.next_token_id.to_string();
         self.tokens.insert(token_id.clone(), token);
+        self.record_supply_change(
+            &token_id,
+            SupplyChangeKind::Issue,
+            initial_supply,
+            "criação",
+        );
         self.next_token_id += 1;
         Ok(token_id)
     }
@@ -131,7 +392,7 @@ impl Blockchain {
         signature: Vec<u8>,
     ) -> Result<(), TransactionError> {
         // Validações iniciais
-        let key_manager = KeyManager::new()?;
+        let key_manager = KeyManager::new().map_err(|e| TransactionError::Other(e.to_string()))?;
         key_manager.validate_transaction_params(&from, &to, amount)?;
 
         // Validar formato de endereço usando a função utilitária
@@ -147,11 +408,8 @@ impl Blockchain {
         // Obtém a chave pública
         let public_key = self.get_public_key(&from)?;
 
-        // Timestamp atual
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
+        // Timestamp atual, pelo relógio injetado na cadeia
+        let timestamp = self.now_secs();
 
         // Validar timestamp
         let current_time = timestamp;
@@ -202,11 +460,184 @@ impl Blockchain {
 
         // Atualiza o nonce e adiciona a transação
         self.nonces.insert(from, current_nonce + 1);
+        self.transaction_statuses
+            .set(transaction.txid(), LifecycleState::Pending);
         self.pending_transactions.push(transaction);
 
         Ok(())
     }
 
+    /// Verificações de uma transação assinada que não dependem do estado da cadeia
+    /// (valor, endereços, timestamp, tamanhos e assinatura Dilithium5).
+    pub fn check_transaction(tx: &Transaction, limits: &Limits) -> Result<(), TransactionError> {
+        let processor = TransactionProcessor;
+        processor.cheap_checks_with(tx, limits)?;
+        processor.verify_signature(tx)
+    }
+
+    /// Submete uma transação já assinada ao mempool.
+    ///
+    /// A assinatura só vale se a chave embutida responder por `tx.from` (ver
+    /// `check_account_key`); uma assinatura íntegra de outra chave é recusada.
+    pub fn submit_transaction(&mut self, tx: Transaction) -> Result<(), TransactionError> {
+        if let Err(e) = Self::check_transaction(&tx, &self.limits) {
+            self.record_rejection(&tx, &e);
+            return Err(e);
+        }
+        self.admit_transaction(tx)
+    }
+
+    /// Admite no mempool uma transação que já passou por `check_transaction`.
+    pub(crate) fn admit_transaction(&mut self, tx: Transaction) -> Result<(), TransactionError> {
+        self.check_admission(&tx)
+            .inspect_err(|e| self.record_rejection(&tx, e))?;
+        self.throttle
+            .record(&tx.from, self.now_secs(), &self.limits);
+        self.nonces.insert(tx.from.clone(), tx.nonce);
+        self.transaction_statuses
+            .set(tx.txid(), LifecycleState::Pending);
+        self.pending_transactions.push(tx);
+        Ok(())
+    }
+
+    /// Nonce, duplicidade e limites do remetente: o que a admissão confere contra
+    /// o estado atual
+    pub(super) fn check_admission(&self, tx: &Transaction) -> Result<(), TransactionError> {
+        if self.is_halted() {
+            return Err(TransactionError::ChainHalted);
+        }
+        let pending = self
+            .pending_transactions
+            .iter()
+            .filter(|p| p.from == tx.from)
+            .count();
+        if pending >= self.limits.max_pending_per_sender {
+            return Err(TransactionError::TooManyPending(
+                self.limits.max_pending_per_sender,
+            ));
+        }
+        self.throttle
+            .check(&tx.from, self.now_secs(), &self.limits)?;
+        if Self::transfer_fee(tx.amount) < self.limits.fee_floor {
+            return Err(TransactionError::FeeBelowFloor(self.limits.fee_floor));
+        }
+
+        self.check_account_key(&tx.from, &tx.public_key)?;
+
+        let current_nonce = self.nonces.get(&tx.from).copied().unwrap_or(0);
+        if tx.nonce != current_nonce + 1 {
+            return Err(TransactionError::NonceInvalido);
+        }
+
+        let in_mempool = self
+            .pending_transactions
+            .iter()
+            .any(|p| p.from == tx.from && p.nonce == tx.nonce && p.timestamp == tx.timestamp);
+        if in_mempool || self.transaction_exists(tx) {
+            return Err(TransactionError::TransacaoRepetida);
+        }
+        Ok(())
+    }
+
+    /// Submete uma operação assinada; ela é aplicada quando o próximo bloco é fechado.
+    ///
+    /// Na criação de tokens o ID é reservado na admissão, de modo que duas criações
+    /// pendentes nunca disputam o mesmo ID.
+    pub fn submit_operation(&mut self, operation: Operation) -> Result<(), TransactionError> {
+        self.reserve_operation(&operation)?;
+        self.pending_operations.push(operation);
+        Ok(())
+    }
+
+    /// Verificações de `submit_operation` contra o estado atual; consome o nonce do
+    /// autor e, na criação de tokens, o ID
+    pub(super) fn reserve_operation(
+        &mut self,
+        operation: &Operation,
+    ) -> Result<(), TransactionError> {
+        operation.check()?;
+        self.limits.check_operation(operation)?;
+
+        let current_nonce = self.nonces.get(&operation.author).copied().unwrap_or(0);
+        if operation.nonce != current_nonce + 1 {
+            return Err(TransactionError::NonceInvalido);
+        }
+
+        if self.is_halted() && !matches!(operation.kind, OperationKind::SetChainHalted { .. }) {
+            return Err(TransactionError::ChainHalted);
+        }
+        // A conclusão de uma recuperação é a única operação assinada com a chave nova
+        // antes de ela valer; `check_recovery_operation` a confere
+        if !matches!(operation.kind, OperationKind::CompleteRecovery) {
+            self.check_account_key(&operation.author, &operation.public_key)?;
+        }
+
+        match &operation.kind {
+            OperationKind::CreateToken { token_id, .. } => {
+                if *token_id != self.next_token_id
+                    || self.tokens.contains_key(&token_id.to_string())
+                {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "ID de token {} indisponível; próximo ID livre: {}",
+                        token_id, self.next_token_id
+                    )));
+                }
+                self.next_token_id += 1;
+            }
+            OperationKind::ConfidentialTransfer { .. } => self.check_confidential(operation)?,
+            OperationKind::AssignRole { .. } => self.check_role_assignment(operation)?,
+            OperationKind::SetTransferPolicy { .. } | OperationKind::SetKyc { .. } => {
+                self.check_policy_operation(operation)?
+            }
+            OperationKind::CreateVesting { .. } | OperationKind::ClaimVested { .. } => {
+                self.check_vesting_operation(operation)?
+            }
+            OperationKind::ProposeTokenOwner { .. }
+            | OperationKind::AcceptTokenOwner { .. }
+            | OperationKind::SetTokenAdmin { .. } => self.check_ownership_operation(operation)?,
+            OperationKind::MintTokens { .. }
+            | OperationKind::BurnTokens { .. }
+            | OperationKind::SetTokenPaused { .. } => self.check_issuance_operation(operation)?,
+            OperationKind::SubmitProposal { .. } | OperationKind::CastVote { .. } => {
+                self.check_governance_operation(operation, self.height())?
+            }
+            OperationKind::SetChainHalted { .. } => self.check_halt_operation(operation)?,
+            OperationKind::ScheduleUpgrade { .. } => {
+                self.check_upgrade_operation(operation, self.height())?
+            }
+            OperationKind::SetOracleFeeder { .. } | OperationKind::SubmitOracleData { .. } => {
+                self.check_oracle_operation(operation)?
+            }
+            OperationKind::RegisterName { .. }
+            | OperationKind::RenewName { .. }
+            | OperationKind::TransferName { .. } => {
+                self.check_name_operation(operation, self.height())?
+            }
+            OperationKind::SetAccountGuard { .. } | OperationKind::SetGuardRule { .. } => {
+                self.check_account_guard_operation(operation)?
+            }
+            OperationKind::SetGuardians { .. }
+            | OperationKind::ApproveRecovery { .. }
+            | OperationKind::CancelRecovery
+            | OperationKind::CompleteRecovery => {
+                self.check_recovery_operation(operation, self.height())?
+            }
+            OperationKind::RotateKey { .. } => self.check_key_rotation(operation)?,
+            OperationKind::RegisterChainClient { .. }
+            | OperationKind::SendPacket { .. }
+            | OperationKind::ReceivePacket { .. } => {
+                self.reserve_interchain_operation(operation)?
+            }
+            OperationKind::RegisterViewKey { .. }
+            | OperationKind::Shield { .. }
+            | OperationKind::Unshield { .. } => {}
+        }
+
+        self.nonces
+            .insert(operation.author.clone(), operation.nonce);
+        Ok(())
+    }
+
     fn get_secret_key(&self, address: &str) -> Result<&SecretKey, TransactionError> {
         self.secret_keys.get(address).ok_or_else(|| {
             TransactionError::InvalidData(format!(
@@ -216,127 +647,612 @@ impl Blockchain {
         })
     }
 
-    /// Valida uma transação.
-    fn validar_transacao(
-        &self,
-        transaction: &Transaction,
-        nonce_atual: u64,
-    ) -> Result<(), TransactionError> {
-        // Verifica o nonce
-        if transaction.nonce != nonce_atual + 1 {
-            return Err(TransactionError::InvalidSignature(format!(
-                "Nonce inválido: esperado {}, recebido {}",
-                nonce_atual + 1,
-                transaction.nonce
-            )));
+    /// Contexto de validação com caches de tokens, saldos e nonces para um bloco
+    pub fn validation_context(&self) -> ValidationContext<'_> {
+        ValidationContext::new(self)
+    }
+
+    /// Autoriza a chave de consenso `public_key` a propor blocos e retorna o endereço
+    /// do validador. Blocos a partir da altura atual passam a exigir assinatura.
+    pub fn register_validator_key(&mut self, public_key: Vec<u8>) -> Address {
+        let address = derive_address(&public_key);
+        self.signed_from.get_or_insert(self.height());
+        self.validator_keys.insert(address.clone(), public_key);
+        address
+    }
+
+    /// Define a identidade que assina os blocos produzidos por este nó e registra
+    /// sua chave como validador
+    pub fn set_signer(&mut self, identity: Arc<NodeIdentity>) -> Address {
+        let address = self.register_validator_key(identity.public_key.as_bytes().to_vec());
+        self.signer = Some(identity);
+        address
+    }
+
+    /// Substitui o relógio do controle de envios e dos timestamps dos blocos
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = Some(clock);
+    }
+
+    /// Hora Unix em segundos pelo relógio da cadeia
+    pub(super) fn now_secs(&self) -> i64 {
+        match &self.clock {
+            Some(clock) => clock.unix_millis() / 1000,
+            None => clock().now_secs(),
         }
+    }
 
-        // Verifica a assinatura
-        let public_key = &transaction.public_key;
-        let pk_bytes = public_key.as_slice();
-        let pk = match dilithium5::PublicKey::from_bytes(pk_bytes) {
-            Ok(pk) => pk,
-            Err(_) => {
-                return Err(TransactionError::InvalidSignature(
-                    "Chave pública inválida".to_string(),
-                ))
+    /// Confere a assinatura do proponente de `block` contra as chaves registradas.
+    /// Blocos abaixo de `signed_from`, anteriores aos blocos assinados, podem não ter
+    /// assinatura.
+    pub fn verify_block_signature(&self, block: &Block) -> Result<(), Error> {
+        let proposer = match &block.proposer {
+            Some(proposer) => proposer,
+            None if block.validator_signature.is_none()
+                && self.signed_from.is_none_or(|height| block.index < height) =>
+            {
+                return Ok(())
+            }
+            None => {
+                return Err(Error::InvalidBlock(format!(
+                    "Bloco {} sem assinatura do validador",
+                    block.index
+                )))
             }
         };
-
-        if transaction.verify(&pk).is_err() {
-            return Err(TransactionError::InvalidSignature(
-                "Assinatura inválida".to_string(),
-            ));
+        let public_key = self.validator_keys.get(proposer).ok_or_else(|| {
+            Error::Unauthorized(format!("Proponente {} não é um validador", proposer))
+        })?;
+        match self.detached_blocks.get(&block.index) {
+            // Sem transações e contratos em memória, as raízes vêm do que sobrou
+            Some(detached) => block.verify_header(
+                public_key,
+                &block.header_bytes_for(&detached.transactions_root, &detached.contracts_root),
+            ),
+            None => block.verify_signature(public_key),
         }
+    }
 
-        // Verifica duplicação
-        if self.transaction_exists(transaction) {
-            return Err(TransactionError::InvalidSignature(
-                "Transação duplicada".to_string(),
-            ));
+    /// Adiciona um bloco à blockchain.
+    ///
+    /// Operações e transferências do corpo são executadas como em `produce_block`
+    /// (ver `execute_body`) antes de o bloco ser anexado.
+    pub fn add_block(&mut self, block: Block) -> Result<(), Error> {
+        self.validate_new_block(&block)?;
+        self.log_appended_block(&block)?;
+        self.save_revert_point(&block);
+        let events = self.execute_block(&block);
+        self.push_block(block);
+        for event in events {
+            self.events.emit(event);
         }
-
         Ok(())
     }
 
-    /// Adiciona um staker à blockchain.
-    pub fn add_staker(&mut self, address: String, amount: u64) {
-        let current = self.stakers.entry(address).or_insert(0);
-        *current += amount;
+    /// Validações de um bloco candidato ao topo da cadeia, sem alterar o estado
+    pub fn validate_new_block(&self, block: &Block) -> Result<(), Error> {
+        self.validate_block_envelope(block)?;
+        // O resumo atestado só vale se o corpo, reexecutado sobre uma cópia do
+        // estado, chegar a ele
+        self.replay_body(block)
     }
 
-    /// Adiciona um bloco à blockchain.
-    pub fn add_block(&mut self, block: Block) -> Result<(), Error> {
+    /// Validações de `validate_new_block` que não executam o corpo; o bloco
+    /// montado por `produce_block` passa só por elas, já que é executado em seguida
+    fn validate_block_envelope(&self, block: &Block) -> Result<(), Error> {
         // Validação de tamanho do bloco
-        if block.size() > MAX_BLOCK_SIZE {
+        if block.size() > self.limits.max_block_size {
             return Err(Error::BlockTooLarge);
         }
 
-        // Validação de timestamp
-        let current_time = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
-
-        if block.timestamp > (current_time + MAX_TIME_DRIFT) as u64 {
-            return Err(Error::InvalidTimestamp("Timestamp no futuro".to_string()));
-        }
-
-        if block.timestamp < (current_time - MAX_TIME_DRIFT) as u64 {
-            return Err(Error::InvalidTimestamp("Timestamp no passado".to_string()));
-        }
+        // Validação de timestamp, contra o relógio injetado na cadeia
+        TimestampPolicy::for_context(TimestampContext::Block)
+            .check_secs(block.timestamp as i64, self.now_secs())?;
 
         // Validação com entropia quântica
         self.validate_timestamp_with_quantum_entropy(block.timestamp)?;
 
+        // Peso somado de todos os itens do corpo: transações legadas, operações
+        // e transferências
+        let weights = self.limits.weights;
+        let block_weight = block
+            .transactions
+            .iter()
+            .map(|tx| weights.transfer_weight(tx.size()))
+            .chain(
+                block
+                    .operations
+                    .iter()
+                    .map(|op| weights.operation_weight(op)),
+            )
+            .chain(
+                block
+                    .transfers
+                    .iter()
+                    .map(|tx| weights.transfer_weight(tx.encoded_size())),
+            )
+            .fold(0u64, u64::saturating_add);
+        if block_weight > self.limits.max_block_weight {
+            return Err(Error::InvalidBlock(format!(
+                "Peso do bloco {} excede o limite: {} > {}",
+                block.index, block_weight, self.limits.max_block_weight
+            )));
+        }
+
         // Validação das transações no bloco
+        let mut context = self.validation_context();
         for secure_transaction in &block.transactions {
-            // Valide o tamanho da transação
-            if secure_transaction.size() > MAX_TRANSACTION_SIZE {
+            // Valide o tamanho da transação e da assinatura
+            if secure_transaction.size() > self.limits.max_transaction_size {
                 return Err(Error::TransactionError(Box::new(
                     TransactionError::DataSizeExceeded,
                 )));
             }
+            if secure_transaction.signature.len() > self.limits.max_signature_size {
+                return Err(Error::TransactionError(Box::new(
+                    TransactionError::SignatureSizeExceeded,
+                )));
+            }
 
-            // Converte SecureTransaction para Transaction
-            let transaction: Transaction = secure_transaction.clone().into();
+            // Valida a transação contra o estado acumulado do bloco
+            context.validate_secure_transaction(secure_transaction)?;
+        }
 
-            // Obtém o nonce atual do remetente
-            let nonce_atual = self.nonces.get(&transaction.from).copied().unwrap_or(0);
+        // Os mesmos limites de tamanho e assinatura para operações e transferências
+        for operation in &block.operations {
+            self.limits.check_operation(operation)?;
+        }
+        for tx in &block.transfers {
+            self.limits.check_transaction(tx)?;
+        }
+
+        // Validação da assinatura do proponente
+        self.verify_block_signature(block)?;
 
-            // Valida a transação
-            self.validar_transacao(&transaction, nonce_atual)?;
+        // Contagens e teto das taxas do resumo, conferidos antes da reexecução
+        if let Some(execution) = &block.execution {
+            execution.check_body(block)?;
+            if execution.gas_used > self.limits.max_block_weight {
+                return Err(Error::InvalidBlock(format!(
+                    "Gás do bloco {} excede o limite de peso: {} > {}",
+                    block.index, execution.gas_used, self.limits.max_block_weight
+                )));
+            }
         }
 
         // Validação do hash do bloco
-        let calculated_hash = Block::calculate_hash(
-            block.index,
-            block.timestamp,
-            &block.transactions,
-            &block.contracts,
-            &block.previous_hash,
-        )?;
-
-        if block.hash != calculated_hash {
+        // O hash cobre também o conteúdo: transações, contratos e corpo
+        if block.hash != block.compute_hash()? {
             return Err(Error::InvalidBlock("Hash do bloco inválido".to_string()));
         }
 
-        // Registra evento seguro
+        Ok(())
+    }
+
+    pub(super) fn append_block(&mut self, block: Block) -> Result<(), Error> {
+        self.log_appended_block(&block)?;
+        self.push_block(block);
+        Ok(())
+    }
+
+    /// Registra no log de auditoria a entrada de `block`, o único ponto em que
+    /// `append_block` pode falhar
+    pub(super) fn log_appended_block(&self, block: &Block) -> Result<(), Error> {
         self.log_secure_event(&format!(
             "Bloco adicionado: índice={}, hash={}",
             block.index, block.hash
-        ))?;
+        ))
+    }
+
+    /// Indexa e anexa o bloco sem passar pelo log de auditoria
+    pub(super) fn push_block(&mut self, block: Block) {
+        for tx in &block.transactions {
+            let record = TransactionRecord::from_secure(tx, block.index);
+            self.transaction_statuses.set(
+                record.txid.clone(),
+                LifecycleState::Included {
+                    height: block.index,
+                },
+            );
+            self.index.record(record);
+        }
 
         // Adiciona o bloco à cadeia
         self.chain.push(block);
+        self.record_archive_state();
+    }
 
-        Ok(())
+    /// Fecha um bloco com até `max_transactions` itens do mempool, operações antes de
+    /// transferências, na ordem de admissão, e aplica seus efeitos ao estado.
+    ///
+    /// Retorna `None` quando o mempool está vazio. Assinatura e nonce já foram verificados
+    /// na admissão; saldo e existência do token só são conhecidos na confirmação, e os
+    /// itens que falham nesse ponto são descartados. Operações e transferências
+    /// processadas vão para `Block::operations` e `Block::transfers`, cobertos pela
+    /// assinatura; as aplicadas ficam também em `operations` e
+    /// `committed_transactions`. Se o bloco for rejeitado, o lote volta para o
+    /// início do mempool.
+    ///
+    /// Falha com `Error::UpgradeRequired`, sem tocar no mempool, quando a cadeia já
+    /// exige nesta altura uma versão acima de `client_version`.
+    pub fn produce_block(&mut self, max_transactions: usize) -> Result<Option<Block>, Error> {
+        self.check_client_version(self.height())?;
+
+        // Com a cadeia parada só as aprovações de parada e retomada entram no bloco;
+        // o restante do mempool espera, na mesma ordem, pela retomada
+        let halted = self.is_halted();
+        let available_operations = if halted {
+            self.pending_operations.sort_by_key(|operation| {
+                !matches!(operation.kind, OperationKind::SetChainHalted { .. })
+            });
+            self.pending_operations
+                .iter()
+                .take_while(|operation| {
+                    matches!(operation.kind, OperationKind::SetChainHalted { .. })
+                })
+                .count()
+        } else {
+            self.pending_operations.len()
+        };
+        let available_transactions = if halted {
+            0
+        } else {
+            self.pending_transactions.len()
+        };
+        if available_transactions == 0 && available_operations == 0 {
+            return Ok(None);
+        }
+        self.throttle.prune(self.now_secs(), &self.limits);
+
+        let limit = max_transactions.max(1);
+        let limits = self.limits;
+        // Operações e depois transferências entram, em ordem, enquanto o peso
+        // somado couber no bloco; as transferências respeitam também o tamanho
+        // codificado. A admissão recusa itens que não cabem sozinhos num bloco,
+        // então o primeiro sempre entra, a menos que os limites tenham diminuído
+        // depois que ele foi admitido
+        let mut block_weight = 0u64;
+        let mut within_weight = |weight: u64| {
+            block_weight = block_weight.saturating_add(weight);
+            block_weight <= limits.max_block_weight
+        };
+        let op_count = limit.min(available_operations);
+        let op_count = self.pending_operations[..op_count]
+            .iter()
+            .take_while(|operation| {
+                limits.check_operation(operation).is_ok()
+                    && within_weight(limits.weights.operation_weight(operation))
+            })
+            .count();
+        let tx_count = if op_count < available_operations.min(limit) {
+            0
+        } else {
+            (limit - op_count).min(available_transactions)
+        };
+        let mut batch_size = 0;
+        let tx_count = self.pending_transactions[..tx_count]
+            .iter()
+            .take_while(|tx| {
+                batch_size += tx.encoded_size();
+                batch_size <= limits.max_block_size
+                    && limits.check_transaction(tx).is_ok()
+                    && within_weight(limits.weights.transfer_weight(tx.encoded_size()))
+            })
+            .count();
+        if op_count == 0 && tx_count == 0 {
+            // Pular o item quebraria a sequência de nonces do autor para quem
+            // reexecuta o bloco
+            return Err(Error::InvalidBlock(
+                "O primeiro item do mempool não cabe num bloco com os limites atuais".to_string(),
+            ));
+        }
+        // O ponto de retorno do bloco é tomado com o lote ainda no mempool, onde
+        // seus nonces foram reservados
+        let revert_point = self.chain_state();
+        let operations: Vec<Operation> = self.pending_operations.drain(..op_count).collect();
+        let batch: Vec<Transaction> = self.pending_transactions.drain(..tx_count).collect();
+
+        let previous_hash = self
+            .chain
+            .last()
+            .map(|block| block.hash.clone())
+            .unwrap_or_else(|| "0".repeat(64));
+        let selection = MempoolSelection {
+            operations: operations.clone(),
+            transfers: batch.clone(),
+            ..MempoolSelection::default()
+        };
+        let result = assemble_block(
+            selection,
+            &previous_hash,
+            self.height(),
+            self.now_secs().max(0) as u64,
+        )
+        .map(|mut block| {
+            if let Some(signer) = &self.signer {
+                block.sign(signer);
+            }
+            block
+        })
+        .and_then(|block| self.validate_block_envelope(&block).map(|()| block));
+
+        let mut block = match result {
+            Ok(block) => block,
+            Err(e) => {
+                self.pending_operations.splice(0..0, operations);
+                self.pending_transactions.splice(0..0, batch);
+                return Err(e);
+            }
+        };
+
+        // Efeitos aplicados antes de anexar o bloco, para que o arquivo histórico
+        // registre o estado já atualizado nesta altura
+        self.keep_revert_point(block.index, revert_point);
+        let mut execution = self.execute_body(
+            block.index,
+            block.timestamp,
+            operations,
+            batch,
+            BodySource::Mempool,
+        );
+        self.pending_operations
+            .splice(0..0, std::mem::take(&mut execution.deferred_operations));
+        self.pending_transactions
+            .splice(0..0, std::mem::take(&mut execution.deferred_transfers));
+
+        // O corpo fica com o que foi processado, na ordem dos recibos; o que uma
+        // parada adiou volta ao mempool e sai do bloco
+        block.operations = std::mem::take(&mut execution.operations);
+        block.transfers = std::mem::take(&mut execution.transfers);
+        block.hash = block.compute_hash()?;
+
+        // O resumo só existe depois da execução, então o bloco é assinado de novo
+        // para cobri-lo junto com o corpo final
+        block.execution = Some(ExecutionSummary::from_receipts(&execution.receipts));
+        if let Some(signer) = &self.signer {
+            block.sign(signer);
+        }
+
+        let events = self.finish_execution(&block, execution);
+        self.append_block(block.clone())?;
+        for event in events {
+            self.events.emit(event);
+        }
+        Ok(Some(block))
+    }
+
+    /// Token cujos saldos públicos a operação altera
+    pub(super) fn public_balance_token(&self, kind: &OperationKind) -> Option<u64> {
+        match kind {
+            OperationKind::CreateToken { token_id, .. }
+            | OperationKind::Shield { token_id, .. }
+            | OperationKind::Unshield { token_id, .. }
+            | OperationKind::CreateVesting { token_id, .. }
+            | OperationKind::SendPacket { token_id, .. } => Some(*token_id),
+            OperationKind::ClaimVested { schedule_id } => self
+                .vesting_schedule(*schedule_id)
+                .map(|schedule| schedule.token_id),
+            OperationKind::ReceivePacket {
+                packet,
+                voucher_token_id,
+                ..
+            } => voucher_token_id.or_else(|| self.native_denom(packet)?.parse().ok()),
+            _ => None,
+        }
+    }
+
+    /// Aplica a operação cobrando do autor a taxa `operation.fee` (ver `with_fee`);
+    /// devolve o evento e a taxa cobrada
+    pub(super) fn apply_operation(
+        &mut self,
+        operation: &Operation,
+        height: u64,
+        timestamp: u64,
+    ) -> Result<(AppEvent, u64), Error> {
+        // A chave pode ter sido trocada depois de a operação entrar no mempool
+        if !matches!(operation.kind, OperationKind::CompleteRecovery) {
+            self.check_account_key(&operation.author, &operation.public_key)?;
+        }
+        self.with_fee(&operation.author, operation.fee, |chain| {
+            chain.apply_operation_kind(operation, height, timestamp)
+        })
+    }
+
+    fn apply_operation_kind(
+        &mut self,
+        operation: &Operation,
+        height: u64,
+        timestamp: u64,
+    ) -> Result<AppEvent, Error> {
+        match &operation.kind {
+            OperationKind::CreateToken {
+                token_id,
+                name,
+                symbol,
+                total_supply,
+            } => {
+                let mut token = Token::new(
+                    name.clone(),
+                    symbol.clone(),
+                    *total_supply,
+                    operation.author.clone(),
+                )?;
+                token.id = *token_id;
+                self.tokens.insert(token_id.to_string(), token);
+                self.record_supply_change(
+                    &token_id.to_string(),
+                    SupplyChangeKind::Issue,
+                    *total_supply,
+                    "criação",
+                );
+
+                Ok(AppEvent::TokenCreated {
+                    token_id: *token_id,
+                    symbol: symbol.clone(),
+                    owner: operation.author.clone(),
+                    total_supply: *total_supply,
+                    height,
+                })
+            }
+            OperationKind::AssignRole {
+                subject,
+                role,
+                granted,
+            } => {
+                self.apply_role_assignment(subject, *role, *granted);
+                Ok(AppEvent::RoleAssigned {
+                    subject: subject.clone(),
+                    role: *role,
+                    granted: *granted,
+                    by: operation.author.clone(),
+                    height,
+                })
+            }
+            OperationKind::SetTransferPolicy { .. } | OperationKind::SetKyc { .. } => {
+                self.apply_policy_operation(operation);
+                Ok(AppEvent::OperationApplied {
+                    author: operation.author.clone(),
+                    nonce: operation.nonce,
+                    height,
+                })
+            }
+            OperationKind::CreateVesting { .. } | OperationKind::ClaimVested { .. } => {
+                self.apply_vesting_operation(operation, timestamp)?;
+                Ok(AppEvent::OperationApplied {
+                    author: operation.author.clone(),
+                    nonce: operation.nonce,
+                    height,
+                })
+            }
+            OperationKind::ProposeTokenOwner { .. }
+            | OperationKind::AcceptTokenOwner { .. }
+            | OperationKind::SetTokenAdmin { .. } => {
+                self.apply_ownership_operation(operation)?;
+                Ok(AppEvent::OperationApplied {
+                    author: operation.author.clone(),
+                    nonce: operation.nonce,
+                    height,
+                })
+            }
+            OperationKind::MintTokens { .. }
+            | OperationKind::BurnTokens { .. }
+            | OperationKind::SetTokenPaused { .. } => {
+                self.apply_issuance_operation(operation)?;
+                Ok(AppEvent::OperationApplied {
+                    author: operation.author.clone(),
+                    nonce: operation.nonce,
+                    height,
+                })
+            }
+            OperationKind::SubmitProposal { .. } | OperationKind::CastVote { .. } => {
+                self.apply_governance_operation(operation, height)?;
+                Ok(AppEvent::OperationApplied {
+                    author: operation.author.clone(),
+                    nonce: operation.nonce,
+                    height,
+                })
+            }
+            OperationKind::ScheduleUpgrade { .. } => {
+                self.apply_upgrade_operation(operation, height)?;
+                Ok(AppEvent::OperationApplied {
+                    author: operation.author.clone(),
+                    nonce: operation.nonce,
+                    height,
+                })
+            }
+            OperationKind::SetOracleFeeder { .. } | OperationKind::SubmitOracleData { .. } => {
+                self.apply_oracle_operation(operation, height)?;
+                Ok(AppEvent::OperationApplied {
+                    author: operation.author.clone(),
+                    nonce: operation.nonce,
+                    height,
+                })
+            }
+            OperationKind::RegisterName { .. }
+            | OperationKind::RenewName { .. }
+            | OperationKind::TransferName { .. } => {
+                self.apply_name_operation(operation, height)?;
+                Ok(AppEvent::OperationApplied {
+                    author: operation.author.clone(),
+                    nonce: operation.nonce,
+                    height,
+                })
+            }
+            OperationKind::SetAccountGuard { .. } | OperationKind::SetGuardRule { .. } => {
+                self.apply_account_guard_operation(operation, height)?;
+                Ok(AppEvent::OperationApplied {
+                    author: operation.author.clone(),
+                    nonce: operation.nonce,
+                    height,
+                })
+            }
+            OperationKind::SetGuardians { .. }
+            | OperationKind::ApproveRecovery { .. }
+            | OperationKind::CancelRecovery
+            | OperationKind::CompleteRecovery => {
+                self.apply_recovery_operation(operation, height)?;
+                Ok(AppEvent::OperationApplied {
+                    author: operation.author.clone(),
+                    nonce: operation.nonce,
+                    height,
+                })
+            }
+            OperationKind::RotateKey { .. } => {
+                self.apply_key_rotation(operation, height)?;
+                Ok(AppEvent::OperationApplied {
+                    author: operation.author.clone(),
+                    nonce: operation.nonce,
+                    height,
+                })
+            }
+            OperationKind::RegisterChainClient { .. }
+            | OperationKind::SendPacket { .. }
+            | OperationKind::ReceivePacket { .. } => {
+                self.apply_interchain_operation(operation, height)?;
+                Ok(AppEvent::OperationApplied {
+                    author: operation.author.clone(),
+                    nonce: operation.nonce,
+                    height,
+                })
+            }
+            OperationKind::SetChainHalted { .. } => Ok(self
+                .apply_halt_operation(operation, height)?
+                .unwrap_or_else(|| AppEvent::OperationApplied {
+                    author: operation.author.clone(),
+                    nonce: operation.nonce,
+                    height,
+                })),
+            _ => {
+                self.apply_confidential(operation)?;
+                Ok(AppEvent::OperationApplied {
+                    author: operation.author.clone(),
+                    nonce: operation.nonce,
+                    height,
+                })
+            }
+        }
+    }
+
+    /// Move o valor da transferência e cobra do remetente, em KYBL, a taxa de
+    /// `transfer_fee` (ver `with_fee`); devolve a taxa cobrada
+    pub(super) fn apply_transfer(&mut self, tx: &Transaction) -> Result<u64, TransactionError> {
+        self.check_account_key(&tx.from, &tx.public_key)?;
+        self.check_transfer_policies(tx)?;
+
+        self.with_fee(&tx.from, Self::transfer_fee(tx.amount), |chain| {
+            let token = chain
+                .tokens
+                .get_mut(&tx.token_id.to_string())
+                .ok_or(TransactionError::TokenNaoEncontrado)?;
+            FungibleToken::transfer(token, &tx.from, &tx.to, tx.amount)
+        })
+        .map(|((), fee)| fee)
     }
 
     /// Valida o timestamp usando entropia quântica.
     pub fn validate_timestamp_with_quantum_entropy(&self, timestamp: u64) -> Result<(), Error> {
         // Gera entropia quântica usando Kyber
-        let kem = Kem::new(Algorithm::Kyber512).map_err(|e| Error::OqsError(e))?;
+        let kem = entropy::kem(Algorithm::Kyber512).map_err(|e| Error::OqsError(e))?;
         let (pk, _) = kem.keypair().map_err(|e| Error::OqsError(e))?;
 
         // Gera hash SHA-3 da chave pública
@@ -348,7 +1264,7 @@ impl Blockchain {
         let payload = format!("{}:{}", timestamp, hash);
 
         // Gera uma assinatura usando Dilithium
-        let keypair = dilithium5::keypair();
+        let keypair = dilithium::keypair();
         let signature = dilithium5::detached_sign(payload.as_bytes(), &keypair.1);
 
         // Verifica a assinatura
@@ -365,39 +1281,11 @@ impl Blockchain {
 
     /// Salva a blockchain em um arquivo JSON.
     pub fn save_to_file(&self, filename: &str) -> std::io::Result<()> {
+        self.ensure_complete().map_err(std::io::Error::other)?;
         let json = serde_json::to_string(self)?;
         let mut file = File::create(filename)?;
         file.write_all(json.as_bytes())?;
-        Ok(())
-    }
-
-    /// Salva a blockchain em um banco de dados SQLite.
-    pub fn save_to_db(&self, db_path: &str) -> SqlResult<()> {
-        let conn = Connection::open(db_path)?;
-
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS blocks (
-                id INTEGER PRIMARY KEY,
-                index INTEGER NOT NULL,
-                timestamp INTEGER NOT NULL,
-                previous_hash TEXT NOT NULL,
-                hash TEXT NOT NULL
-            )",
-            [],
-        )?;
-
-        for block in &self.chain {
-            conn.execute(
-                "INSERT INTO blocks (index, timestamp, previous_hash, hash) VALUES (?1, ?2, ?3, ?4)",
-                params![
-                    block.index,
-                    block.timestamp,
-                    &block.previous_hash,
-                    &block.hash
-                ],
-            )?;
-        }
-
+        export::discard_signature(std::path::Path::new(filename));
         Ok(())
     }
 
@@ -414,7 +1302,13 @@ impl Blockchain {
 
     /// Verifica se uma transação já existe na blockchain.
     pub fn transaction_exists(&self, tx: &Transaction) -> bool {
-        self.chain.iter().any(|block| {
+        Self::includes_transaction(&self.chain, tx)
+    }
+
+    /// Se algum de `blocks` traz uma transação legada com o remetente, o nonce e o
+    /// timestamp de `tx`
+    pub(super) fn includes_transaction(blocks: &[Block], tx: &Transaction) -> bool {
+        blocks.iter().any(|block| {
             block
                 .transactions
                 .iter()
@@ -422,69 +1316,22 @@ impl Blockchain {
         })
     }
 
-    /// Carrega a blockchain de um banco de dados SQLite.
-    pub fn load_from_db(db_path: &str) -> SqlResult<Self> {
-        let conn = Connection::open(db_path)?;
-        let mut stmt = conn.prepare("SELECT index, timestamp, previous_hash, hash FROM blocks")?;
-        let blocks = stmt.query_map([], |row| {
-            Ok(Block {
-                index: row.get(0)?,
-                timestamp: row.get(1)?,
-                transactions: vec![],
-                contracts: vec![],
-                previous_hash: row.get(2)?,
-                hash: row.get(3)?,
-                validator_signature: None,
-                nonce: 0,
-                processed_transactions: std::collections::HashSet::new(),
-            })
-        })?;
-
-        let chain: Vec<Block> = blocks.collect::<SqlResult<_>>()?;
-
-        Ok(Blockchain {
-            tokens: HashMap::new(),
-            stakers: HashMap::new(),
-            chain,
-            next_token_id: 0,
-            nonces: HashMap::new(),
-            pending_transactions: Vec::new(),
-            blocks: Vec::new(),
-            transactions: Vec::new(),
-            public_keys: HashMap::new(),
-            validator: Validator::new(MAX_BLOCK_SIZE, 300),
-            secret_keys: HashMap::new(), //
-        })
-    }
-
     /// Carrega a blockchain de um arquivo JSON.
-    pub fn load_from_file(filename: &str) -> anyhow::Result<Self> {
+    pub fn load_from_file(filename: &str) -> Result<Self, Error> {
         // Verifica se o arquivo existe
         if !std::path::Path::new(filename).exists() {
             println!("Arquivo não encontrado. Criando nova blockchain.");
             return Blockchain::new();
         }
 
-        // Abre o arquivo e lê o conteúdo
-        let mut file = File::open(filename)
-            .with_context(|| format!("Falha ao abrir o arquivo: {}", filename))?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)
-            .with_context(|| format!("Falha ao ler o arquivo: {}", filename))?;
-
-        // Tenta deserializar o conteúdo do arquivo
-        let result = match serde_json::from_str::<Blockchain>(&contents) {
-            Ok(mut blockchain) => {
-                // Inicializa o validator que foi ignorado na deserialização
-                blockchain.validator = Validator::new(MAX_BLOCK_SIZE, 300);
-
-                // Inicializa public_keys se não existir (para compatibilidade)
-                if blockchain.public_keys.is_empty() {
-                    blockchain.public_keys = HashMap::new();
-                }
+        // Lê o conteúdo; um arquivo com assinatura que não confere é rejeitado
+        let contents = export::read_verified(std::path::Path::new(filename), None)?;
 
-                Ok(blockchain)
-            }
+        // Tenta deserializar o conteúdo do arquivo, migrando formatos antigos. Um
+        // arquivo de versão desconhecida é mantido intacto em vez de substituído.
+        let result = match Blockchain::from_json(&contents) {
+            Ok(blockchain) => Ok(blockchain),
+            Err(e @ Error::InvalidFormat(_)) => Err(e),
             Err(e) => {
                 println!(
                     "Erro ao deserializar blockchain existente: {}. Criando nova blockchain.",
@@ -497,8 +1344,49 @@ impl Blockchain {
         result
     }
 
+    /// Salva um snapshot da blockchain comprimido com `codec`
+    pub fn save_snapshot(&self, filename: &str, codec: Codec) -> Result<(), Error> {
+        self.ensure_complete()?;
+        let json = serde_json::to_vec(self)?;
+        let frame = compression::encode(codec, &json)?;
+        std::fs::write(filename, frame)
+            .map_err(|e| Error::Other(format!("Falha ao gravar snapshot {}: {}", filename, e)))?;
+        export::discard_signature(std::path::Path::new(filename));
+        Ok(())
+    }
+
+    /// Carrega um snapshot gravado por `save_snapshot`, com qualquer codec suportado
+    pub fn load_snapshot(filename: &str) -> Result<Self, Error> {
+        let frame = export::read_verified(std::path::Path::new(filename), None)?;
+        let json = compression::decode(&frame, MAX_SNAPSHOT_SIZE)?;
+
+        Blockchain::from_json(&json)
+    }
+
     /// Verifica se a blockchain é válida.
     pub fn is_chain_valid(&self) -> Result<bool, TransactionError> {
+        // Blocos podados dependem de um checkpoint válido que os cubra
+        if !self.pruned_blocks.is_empty() {
+            let covered = match &self.checkpoint {
+                Some(checkpoint) => {
+                    self.is_checkpoint_trusted(checkpoint)
+                        && self.pruned_blocks.keys().all(|h| *h <= checkpoint.height)
+                }
+                None => false,
+            };
+            if !covered {
+                return Ok(false);
+            }
+        }
+
+        if self
+            .chain
+            .iter()
+            .any(|block| self.verify_block_signature(block).is_err())
+        {
+            return Ok(false);
+        }
+
         for i in 1..self.chain.len() {
             let current_block = &self.chain[i];
             let previous_block = &self.chain[i - 1];
@@ -506,31 +1394,58 @@ impl Blockchain {
             if current_block.previous_hash != previous_block.hash {
                 return Ok(false);
             }
+            // Sem o corpo em memória; conferido ao ser lido do disco
+            if self.is_detached(current_block.index) {
+                continue;
+            }
 
-            for transaction in &current_block.transactions {
-                let public_key = &transaction.public_key;
-                let pk_bytes = public_key.as_slice();
-                let pk = match dilithium5::PublicKey::from_bytes(pk_bytes) {
-                    Ok(pk) => pk,
-                    Err(_) => {
-                        return Err(TransactionError::InvalidSignature(
-                            "Chave pública inválida".to_string(),
-                        ))
-                    }
-                };
-
-                if !transaction.verify(&pk, &transaction.signature)? {
+            // Assinaturas do bloco verificadas em lote no pool dedicado; o primeiro
+            // resultado inválido, na ordem das transações, decide o retorno
+            let transactions: &[SecureTransaction] = if self.is_pruned(current_block.index) {
+                &[]
+            } else {
+                &current_block.transactions
+            };
+            for verified in VerificationService::global().verify_secure_batch(transactions) {
+                if !verified? {
                     return Ok(false);
                 }
             }
+            // Corpo: operações e transferências também não podem ter sido editadas
+            if current_block
+                .operations
+                .iter()
+                .any(|op| op.verify().is_err())
+                || VerificationService::global()
+                    .verify_batch(&current_block.transfers)
+                    .iter()
+                    .any(Result::is_err)
+            {
+                return Ok(false);
+            }
+            // Cada item assinado com a chave vigente da conta no seu nonce
+            let stale_key = current_block
+                .transactions
+                .iter()
+                .map(|tx| (&tx.from, &tx.public_key, tx.nonce))
+                .chain(
+                    current_block
+                        .operations
+                        .iter()
+                        .map(|op| (&op.author, &op.public_key, op.nonce)),
+                )
+                .chain(
+                    current_block
+                        .transfers
+                        .iter()
+                        .map(|tx| (&tx.from, &tx.public_key, tx.nonce)),
+                )
+                .any(|(from, key, nonce)| self.check_historical_key(from, key, nonce).is_err());
+            if stale_key {
+                return Ok(false);
+            }
 
-            let calculated_hash = match Block::calculate_hash(
-                current_block.index,
-                current_block.timestamp,
-                &current_block.transactions,
-                &current_block.contracts,
-                &current_block.previous_hash,
-            ) {
+            let calculated_hash = match current_block.compute_hash() {
                 Ok(hash) => hash,
                 Err(_) => {
                     return Err(TransactionError::OqsError(Box::new(
@@ -555,9 +1470,38 @@ impl std::fmt::Debug for Blockchain {
             .field("stakers", &self.stakers)
             .field("nonces", &self.nonces)
             .field("pending_transactions", &self.pending_transactions)
-            .field("transactions", &self.transactions)
+            .field("committed_transactions", &self.committed_transactions)
+            .field("pending_operations", &self.pending_operations)
             .field("next_token_id", &self.next_token_id)
+            .field("transaction_statuses", &self.transaction_statuses.len())
+            .field("limits", &self.limits)
+            .field("throttle", &self.throttle)
+            .field("confirmation_depth", &self.confirmation_depth)
             .field("public_keys", &self.public_keys)
+            .field("validator_keys", &self.validator_keys)
+            .field("view_keys", &self.view_keys)
+            .field("confidential_balances", &self.confidential_balances)
+            .field("roles", &self.roles)
+            .field("transfer_policies", &self.transfer_policies)
+            .field("vesting_schedules", &self.vesting_schedules)
+            .field("proposals", &self.proposals)
+            .field("halt_approvals", &self.halt_approvals)
+            .field("halt_history", &self.halt_history)
+            .field("upgrade_signals", &self.upgrade_signals)
+            .field("client_version", &self.client_version)
+            .field("oracle_feeds", &self.oracle_feeds)
+            .field("names", &self.names)
+            .field("account_guards", &self.account_guards)
+            .field("recoveries", &self.recoveries)
+            .field("key_rotations", &self.key_rotations)
+            .field("pending_token_owners", &self.pending_token_owners)
+            .field("token_admins", &self.token_admins)
+            .field("paused_tokens", &self.paused_tokens)
+            .field("supply_history", &self.supply_history)
+            .field("execution_receipts", &self.execution_receipts)
+            .field("interchain", &self.interchain)
+            .field("detached_blocks", &self.detached_blocks)
+            .field("clock", &self.clock)
             .finish_non_exhaustive() // Oculta campos sensíveis
     }
 }
@@ -0,0 +1,103 @@
+use super::archive::TransactionReceipt;
+use super::block::Block;
+use crate::smart_contract::SmartContract;
+use crate::transaction::SecureTransaction;
+use crate::utils::canonical_json::{hex_field, CanonicalJson};
+use serde_json::{json, Value};
+
+fn contract_fields(contract: &SmartContract) -> Value {
+    json!({
+        "address": contract.address,
+        "creator": contract.creator,
+        "timestamp": contract.timestamp,
+        "quantum_secure": contract.quantum_secure,
+        "code": hex_field(&contract.code),
+        "data": hex_field(&contract.data),
+    })
+}
+
+impl CanonicalJson for SecureTransaction {
+    const KIND: &'static str = "secure_transaction";
+
+    fn canonical_fields(&self) -> Value {
+        let grants: Vec<Value> = self
+            .payload_grants
+            .iter()
+            .map(|grant| {
+                json!({
+                    "view_key_id": grant.view_key_id,
+                    "encapsulated_key": hex_field(&grant.encapsulated_key),
+                    "ciphertext": hex_field(&grant.ciphertext),
+                })
+            })
+            .collect();
+        json!({
+            "from": self.from,
+            "to": self.to,
+            "amount": self.amount,
+            "timestamp": self.timestamp,
+            "nonce": self.nonce,
+            "signature": hex_field(&self.signature),
+            "public_key": hex_field(&self.public_key),
+            "cipher_key": hex_field(&self.cipher_key),
+            "encrypted_data": hex_field(&self.encrypted_data),
+            "iv": hex_field(&self.iv),
+            "salt": hex_field(&self.salt),
+            "mac": hex_field(&self.mac),
+            "payload_grants": grants,
+        })
+    }
+}
+
+impl CanonicalJson for Block {
+    const KIND: &'static str = "block";
+
+    fn canonical_fields(&self) -> Value {
+        let transactions: Vec<Value> = self
+            .transactions
+            .iter()
+            .map(CanonicalJson::canonical_fields)
+            .collect();
+        let contracts: Vec<Value> = self.contracts.iter().map(contract_fields).collect();
+        // `HashSet` não tem ordem; o formato canônico lista os hashes ordenados
+        let mut processed: Vec<&String> = self.processed_transactions.iter().collect();
+        processed.sort();
+        json!({
+            "index": self.index,
+            "timestamp": self.timestamp,
+            "previous_hash": self.previous_hash,
+            "hash": self.hash,
+            "proposer": self.proposer,
+            "validator_signature": self.validator_signature.as_deref().map(hex_field),
+            "nonce": self.nonce,
+            "transactions": transactions,
+            "contracts": contracts,
+            "processed_transactions": processed,
+            "execution": self.execution.as_ref().map(|execution| json!({
+                "receipts_root": hex_field(&execution.receipts_root),
+                "transactions": execution.transactions,
+                "operations": execution.operations,
+                "discarded": execution.discarded,
+                "total_fees": execution.total_fees,
+                "gas_used": execution.gas_used,
+            })),
+        })
+    }
+}
+
+impl CanonicalJson for TransactionReceipt {
+    const KIND: &'static str = "receipt";
+
+    fn canonical_fields(&self) -> Value {
+        json!({
+            "height": self.height,
+            "position": self.position,
+            "block_hash": self.block_hash,
+            "from": self.from,
+            "to": self.to,
+            "amount": self.amount,
+            "nonce": self.nonce,
+            "timestamp": self.timestamp,
+        })
+    }
+}
@@ -0,0 +1,252 @@
+use super::blockchain::{Address, Blockchain};
+use crate::error::TransactionError;
+use crate::transaction::confidential::{
+    add_commitments, sub_commitments, sub_public_amount, verify_range, view_key_id, Opening,
+    ViewKey,
+};
+use crate::transaction::{Operation, OperationKind};
+
+impl Blockchain {
+    /// Compromisso do saldo confidencial de `address` no token `token_id`
+    pub fn confidential_balance(&self, token_id: u64, address: &str) -> Option<&[u8]> {
+        self.confidential_balances
+            .get(&token_id.to_string())
+            .and_then(|balances| balances.get(address))
+            .map(Vec::as_slice)
+    }
+
+    /// Memorandos das transferências confidenciais confirmadas para `address` que
+    /// `view_key` consegue decifrar, com o remetente, na ordem em que foram aplicadas
+    pub fn memos_for(&self, address: &str, view_key: &ViewKey) -> Vec<(Address, String)> {
+        self.operations
+            .iter()
+            .filter(|operation| match &operation.kind {
+                OperationKind::ConfidentialTransfer { to, .. } => to == address,
+                _ => false,
+            })
+            .filter_map(|operation| {
+                view_key
+                    .read_memo(&operation.kind)
+                    .map(|memo| (operation.author.clone(), memo))
+            })
+            .collect()
+    }
+
+    /// Payloads das transações confirmadas de `address` (como remetente ou
+    /// destinatário) que `view_key` recebeu acesso para ler, em ordem de altura
+    pub fn audit_payloads(&self, address: &str, view_key: &ViewKey) -> Vec<(u64, String)> {
+        self.chain
+            .iter()
+            .flat_map(|block| {
+                block
+                    .transactions
+                    .iter()
+                    .map(move |transaction| (block.index, transaction))
+            })
+            .filter(|(_, transaction)| transaction.from == address || transaction.to == address)
+            .filter_map(|(height, transaction)| {
+                transaction
+                    .open_payload(view_key)
+                    .ok()
+                    .map(|payload| (height, payload))
+            })
+            .collect()
+    }
+
+    /// Registra a chave de visualização de um auditor; transferências confidenciais
+    /// admitidas a partir daí precisam incluir uma abertura cifrada para ela
+    pub fn add_auditor_view_key(&mut self, public_key: Vec<u8>) {
+        if !self.auditor_view_keys.contains(&public_key) {
+            self.auditor_view_keys.push(public_key);
+        }
+    }
+
+    /// Verificações de admissão das operações confidenciais que dependem do estado:
+    /// destinatário com chave de visualização, aberturas para ele e para cada auditor
+    /// e a prova de intervalo do valor transferido.
+    ///
+    /// A prova do saldo restante só é conferida na aplicação, contra o saldo vigente.
+    pub(super) fn check_confidential(&self, operation: &Operation) -> Result<(), TransactionError> {
+        let OperationKind::ConfidentialTransfer {
+            to,
+            commitment,
+            range_proof,
+            openings,
+            memo,
+            ..
+        } = &operation.kind
+        else {
+            return Ok(());
+        };
+
+        self.check_account_guard(&operation.author, to, self.height())?;
+        let recipient_key = self.view_keys.get(to).ok_or_else(|| {
+            TransactionError::InvalidParameter(format!(
+                "Destinatário {} sem chave de visualização registrada",
+                to
+            ))
+        })?;
+        let sealed_for = |public_key: &[u8]| {
+            let id = view_key_id(public_key);
+            openings.iter().any(|sealed| sealed.view_key_id == id)
+        };
+        if !sealed_for(recipient_key) {
+            return Err(TransactionError::InvalidParameter(
+                "Valor não cifrado para o destinatário".to_string(),
+            ));
+        }
+        if memo
+            .as_ref()
+            .is_some_and(|memo| memo.view_key_id != view_key_id(recipient_key))
+        {
+            return Err(TransactionError::InvalidParameter(
+                "Memorando cifrado para outra chave que não a do destinatário".to_string(),
+            ));
+        }
+        if let Some(missing) = self.auditor_view_keys.iter().find(|key| !sealed_for(key)) {
+            return Err(TransactionError::InvalidParameter(format!(
+                "Valor não cifrado para o auditor {}",
+                view_key_id(missing)
+            )));
+        }
+
+        verify_range(commitment, range_proof)
+    }
+
+    /// Aplica uma operação confidencial ao estado. Os compromissos são somados e
+    /// subtraídos sem revelar valores; o saldo restante do autor precisa vir
+    /// acompanhado de uma prova de que não ficou negativo.
+    pub(super) fn apply_confidential(
+        &mut self,
+        operation: &Operation,
+    ) -> Result<(), TransactionError> {
+        let author = &operation.author;
+        match &operation.kind {
+            OperationKind::RegisterViewKey { public_key } => {
+                self.view_keys.insert(author.clone(), public_key.clone());
+            }
+            OperationKind::Shield {
+                token_id,
+                amount,
+                blinding,
+            } => {
+                let opening = Opening {
+                    value: *amount,
+                    blinding: blinding
+                        .as_slice()
+                        .try_into()
+                        .map_err(|_| TransactionError::InvalidDataFormat)?,
+                };
+                let credited = opening.commitment()?;
+
+                let token = self
+                    .tokens
+                    .get_mut(&token_id.to_string())
+                    .ok_or(TransactionError::TokenNaoEncontrado)?;
+                let public = token.balances.get(author).copied().unwrap_or(0);
+                let remaining = public
+                    .checked_sub(*amount)
+                    .ok_or(TransactionError::InsufficientFunds)?;
+                token.balances.insert(author.clone(), remaining);
+
+                self.credit_confidential(*token_id, author, &credited)?;
+            }
+            OperationKind::ConfidentialTransfer {
+                token_id,
+                to,
+                commitment,
+                remaining_proof,
+                ..
+            } => {
+                // As restrições podem ter mudado desde a admissão
+                self.check_account_guard(author, to, self.height())?;
+                let balance = self.spendable(*token_id, author)?;
+                let remaining = sub_commitments(&balance, commitment)?;
+                verify_range(&remaining, remaining_proof)?;
+
+                self.set_confidential(*token_id, author, remaining);
+                self.credit_confidential(*token_id, to, commitment)?;
+            }
+            OperationKind::Unshield {
+                token_id,
+                amount,
+                remaining_proof,
+            } => {
+                let balance = self.spendable(*token_id, author)?;
+                let remaining = sub_public_amount(&balance, *amount)?;
+                verify_range(&remaining, remaining_proof)?;
+
+                let token = self
+                    .tokens
+                    .get_mut(&token_id.to_string())
+                    .ok_or(TransactionError::TokenNaoEncontrado)?;
+                let public = token.balances.get(author).copied().unwrap_or(0);
+                let credited = public
+                    .checked_add(*amount)
+                    .ok_or(TransactionError::ValorInvalido)?;
+                token.balances.insert(author.clone(), credited);
+
+                self.set_confidential(*token_id, author, remaining);
+            }
+            OperationKind::CreateToken { .. }
+            | OperationKind::AssignRole { .. }
+            | OperationKind::SetTransferPolicy { .. }
+            | OperationKind::SetKyc { .. }
+            | OperationKind::CreateVesting { .. }
+            | OperationKind::ClaimVested { .. }
+            | OperationKind::ProposeTokenOwner { .. }
+            | OperationKind::AcceptTokenOwner { .. }
+            | OperationKind::SetTokenAdmin { .. }
+            | OperationKind::MintTokens { .. }
+            | OperationKind::BurnTokens { .. }
+            | OperationKind::SetTokenPaused { .. }
+            | OperationKind::SubmitProposal { .. }
+            | OperationKind::CastVote { .. }
+            | OperationKind::SetChainHalted { .. }
+            | OperationKind::ScheduleUpgrade { .. }
+            | OperationKind::SetOracleFeeder { .. }
+            | OperationKind::SubmitOracleData { .. }
+            | OperationKind::RegisterName { .. }
+            | OperationKind::RenewName { .. }
+            | OperationKind::TransferName { .. }
+            | OperationKind::SetAccountGuard { .. }
+            | OperationKind::SetGuardRule { .. }
+            | OperationKind::SetGuardians { .. }
+            | OperationKind::ApproveRecovery { .. }
+            | OperationKind::CancelRecovery
+            | OperationKind::CompleteRecovery
+            | OperationKind::RotateKey { .. }
+            | OperationKind::RegisterChainClient { .. }
+            | OperationKind::SendPacket { .. }
+            | OperationKind::ReceivePacket { .. } => {}
+        }
+        Ok(())
+    }
+
+    fn spendable(&self, token_id: u64, address: &str) -> Result<Vec<u8>, TransactionError> {
+        self.confidential_balance(token_id, address)
+            .map(<[u8]>::to_vec)
+            .ok_or(TransactionError::InsufficientFunds)
+    }
+
+    fn set_confidential(&mut self, token_id: u64, address: &str, commitment: Vec<u8>) {
+        self.confidential_balances
+            .entry(token_id.to_string())
+            .or_default()
+            .insert(address.to_string(), commitment);
+    }
+
+    fn credit_confidential(
+        &mut self,
+        token_id: u64,
+        address: &str,
+        commitment: &[u8],
+    ) -> Result<(), TransactionError> {
+        let updated = match self.confidential_balance(token_id, address) {
+            Some(current) => add_commitments(current, commitment)?,
+            None => commitment.to_vec(),
+        };
+        self.set_confidential(token_id, address, updated);
+        Ok(())
+    }
+}
@@ -0,0 +1,468 @@
+use super::block::Block;
+use super::blockchain::Blockchain;
+use super::indexer::{TransactionRecord, TransactionStatus};
+use super::merkle::{leaf_hash, merkle_root, MerkleHash, MerkleProof};
+use super::status::LifecycleState;
+use crate::error::{Error, ErrorCode, TransactionError};
+use crate::events::AppEvent;
+use crate::transaction::{Operation, OperationKind, Transaction};
+use log::warn;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Resultado de um item (operação ou transferência) processado ao fechar um bloco.
+///
+/// Itens descartados também geram recibo: o nonce foi consumido na admissão, e
+/// o cliente leve precisa provar que o item entrou no bloco sem efeito.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ExecutionReceipt {
+    /// Posição na ordem de execução: operações primeiro, depois transferências
+    pub position: u32,
+    /// Identificador da transferência; `None` para operações
+    pub txid: Option<String>,
+    pub sender: String,
+    pub nonce: u64,
+    /// Código estável do erro que descartou o item (ver `ErrorCode`)
+    pub error_code: Option<u32>,
+    /// Taxa em KYBL cobrada do remetente; itens descartados não pagam
+    pub fee: u64,
+    /// Peso consumido pelo item no bloco (ver `WeightSchedule`), aplicado ou não
+    pub gas_used: u64,
+}
+
+impl ExecutionReceipt {
+    pub fn applied(&self) -> bool {
+        self.error_code.is_none()
+    }
+
+    /// Folha do recibo na árvore de `ExecutionSummary::receipts_root`
+    pub fn leaf(&self) -> MerkleHash {
+        let data = format!(
+            "{}:{}:{}:{}:{}:{}:{}",
+            self.position,
+            self.txid.as_deref().unwrap_or_default(),
+            self.sender,
+            self.nonce,
+            self.error_code
+                .map(|code| code.to_string())
+                .unwrap_or_default(),
+            self.fee,
+            self.gas_used
+        );
+        leaf_hash(data.as_bytes())
+    }
+}
+
+/// Resumo da execução de um bloco, comprometido no cabeçalho assinado.
+///
+/// `check_body` confere de forma barata as contagens e o teto das taxas contra o
+/// corpo; quem importa o bloco ainda o reexecuta com `replay_body` e exige o
+/// mesmo resumo, com a raiz dos recibos, as taxas exatas e o gás.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ExecutionSummary {
+    /// Raiz Merkle dos recibos, na ordem de execução
+    pub receipts_root: MerkleHash,
+    /// Transferências aplicadas
+    pub transactions: u64,
+    /// Operações aplicadas
+    pub operations: u64,
+    /// Itens descartados ao fechar o bloco
+    pub discarded: u64,
+    pub total_fees: u64,
+    pub gas_used: u64,
+}
+
+impl ExecutionSummary {
+    pub fn from_receipts(receipts: &[ExecutionReceipt]) -> Self {
+        let leaves: Vec<MerkleHash> = receipts.iter().map(ExecutionReceipt::leaf).collect();
+        let applied = || receipts.iter().filter(|receipt| receipt.applied());
+        let transactions = applied().filter(|receipt| receipt.txid.is_some()).count() as u64;
+        let operations = applied().count() as u64 - transactions;
+        ExecutionSummary {
+            receipts_root: merkle_root(&leaves),
+            transactions,
+            operations,
+            discarded: receipts.len() as u64 - transactions - operations,
+            total_fees: applied().map(|receipt| receipt.fee).sum(),
+            gas_used: applied().map(|receipt| receipt.gas_used).sum(),
+        }
+    }
+
+    /// Confere o resumo contra o corpo do bloco: um recibo por operação e
+    /// transferência, nenhum item aplicado além dos que o corpo traz e taxas que
+    /// não passam das que os itens do corpo pagariam
+    pub fn check_body(&self, block: &Block) -> Result<(), Error> {
+        let operations = block.operations.len() as u64;
+        let transfers = block.transfers.len() as u64;
+        let max_fees = block
+            .transfers
+            .iter()
+            .map(|tx| Blockchain::transfer_fee(tx.amount))
+            .chain(block.operations.iter().map(|operation| operation.fee))
+            .fold(0u64, u64::saturating_add);
+        let processed = self
+            .transactions
+            .saturating_add(self.operations)
+            .saturating_add(self.discarded);
+        if self.operations > operations
+            || self.transactions > transfers
+            || processed != operations + transfers
+            || self.total_fees > max_fees
+        {
+            return Err(Error::InvalidBlock(format!(
+                "Resumo de execução do bloco {} não corresponde ao corpo",
+                block.index
+            )));
+        }
+        Ok(())
+    }
+
+    /// Confere que `receipt` está na árvore deste resumo
+    pub fn verify_receipt(&self, receipt: &ExecutionReceipt, path: &MerkleProof) -> bool {
+        path.leaf_index == receipt.position as usize
+            && path.verify(&receipt.leaf(), &self.receipts_root)
+    }
+
+    /// Texto acrescentado ao cabeçalho assinado e à folha do cabeçalho
+    pub(crate) fn header_suffix(&self) -> String {
+        format!(
+            ":{}:{}:{}:{}:{}:{}",
+            hex::encode(self.receipts_root),
+            self.transactions,
+            self.operations,
+            self.discarded,
+            self.total_fees,
+            self.gas_used
+        )
+    }
+}
+
+impl Blockchain {
+    /// Recibos do bloco na altura `height`, na ordem de execução
+    pub fn execution_receipts(&self, height: u64) -> Option<&[ExecutionReceipt]> {
+        self.execution_receipts.get(&height).map(Vec::as_slice)
+    }
+
+    /// Recibo na posição `position` do bloco `height` e seu caminho até a
+    /// `receipts_root` do cabeçalho
+    pub fn receipt_proof(
+        &self,
+        height: u64,
+        position: u32,
+    ) -> Result<(ExecutionReceipt, MerkleProof), Error> {
+        let receipts = self.execution_receipts(height).ok_or_else(|| {
+            Error::InvalidInput(format!("Bloco {} sem recibos de execução", height))
+        })?;
+        let receipt = receipts.get(position as usize).cloned().ok_or_else(|| {
+            Error::InvalidInput(format!(
+                "Recibo {} não encontrado no bloco {}",
+                position, height
+            ))
+        })?;
+        let leaves: Vec<MerkleHash> = receipts.iter().map(ExecutionReceipt::leaf).collect();
+        let path = MerkleProof::build(&leaves, position as usize)
+            .ok_or_else(|| Error::Other("Falha ao construir caminho Merkle".to_string()))?;
+        Ok((receipt, path))
+    }
+}
+
+/// Origem dos itens executados por `Blockchain::execute_body`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum BodySource {
+    /// Selecionados do mempool deste nó: assinatura e nonce já conferidos na
+    /// admissão, e o que uma parada adiar volta ao mempool
+    Mempool,
+    /// Corpo de um bloco recebido: itens ainda fora do mempool passam aqui pela
+    /// assinatura e pelo nonce, e nada é adiado
+    Block,
+}
+
+/// Efeitos da execução do corpo de um bloco
+#[derive(Debug, Default)]
+pub(super) struct BodyExecution {
+    pub receipts: Vec<ExecutionReceipt>,
+    pub events: Vec<AppEvent>,
+    /// Itens processados, aplicados ou não, na ordem dos recibos
+    pub operations: Vec<Operation>,
+    pub transfers: Vec<Transaction>,
+    /// Itens do mempool adiados por uma parada aprovada no meio do bloco
+    pub deferred_operations: Vec<Operation>,
+    pub deferred_transfers: Vec<Transaction>,
+    pub applied_operations: usize,
+    pub applied_transfers: usize,
+    touched_tokens: BTreeSet<u64>,
+    /// Itens de um bloco recebido recusados na admissão, que o proponente não
+    /// poderia ter incluído
+    inadmissible: u64,
+}
+
+impl Blockchain {
+    /// Executa as operações e depois as transferências do corpo de um bloco na
+    /// altura `height`, na ordem, com um recibo por item processado.
+    ///
+    /// É o único caminho que altera o estado por blocos: `produce_block`, com os
+    /// itens tirados do mempool, e `add_block` e `reorganize`, com o corpo
+    /// recebido. Itens que falham são descartados sem efeito e ficam no recibo
+    /// com o código do erro.
+    pub(super) fn execute_body(
+        &mut self,
+        height: u64,
+        timestamp: u64,
+        operations: Vec<Operation>,
+        transfers: Vec<Transaction>,
+        source: BodySource,
+    ) -> BodyExecution {
+        let weights = self.limits.weights;
+        let mut execution = BodyExecution::default();
+
+        for operation in operations {
+            // Uma parada aprovada no meio do bloco adia, de volta ao mempool, o
+            // que vem depois dela
+            if source == BodySource::Mempool
+                && self.is_halted()
+                && !matches!(operation.kind, OperationKind::SetChainHalted { .. })
+            {
+                execution.deferred_operations.push(operation);
+                continue;
+            }
+            let mut receipt = ExecutionReceipt {
+                position: execution.receipts.len() as u32,
+                txid: None,
+                sender: operation.author.clone(),
+                nonce: operation.nonce,
+                error_code: None,
+                fee: 0,
+                gas_used: weights.operation_weight(&operation),
+            };
+            let applied = match source {
+                BodySource::Mempool => Ok(()),
+                BodySource::Block => {
+                    let admitted = self.admit_block_operation(&operation);
+                    execution.inadmissible += u64::from(admitted.is_err());
+                    admitted
+                }
+            }
+            .map_err(Error::from)
+            .and_then(|()| self.apply_operation(&operation, height, timestamp));
+            match applied {
+                Ok((event, fee)) => {
+                    receipt.fee = fee;
+                    execution
+                        .touched_tokens
+                        .extend(self.public_balance_token(&operation.kind));
+                    if fee > 0 {
+                        execution.touched_tokens.insert(0);
+                    }
+                    execution.events.push(event);
+                    self.operations.push(operation.clone());
+                    execution.applied_operations += 1;
+                }
+                Err(e) => {
+                    warn!(
+                        "Operação de {} (nonce {}) descartada no bloco {}: {}",
+                        operation.author, operation.nonce, height, e
+                    );
+                    receipt.error_code = Some(e.code());
+                }
+            }
+            execution.operations.push(operation);
+            execution.receipts.push(receipt);
+        }
+
+        if source == BodySource::Mempool && self.is_halted() {
+            execution.deferred_transfers = transfers;
+            return execution;
+        }
+        for tx in transfers {
+            let mut receipt = ExecutionReceipt {
+                position: execution.receipts.len() as u32,
+                txid: Some(tx.txid()),
+                sender: tx.from.clone(),
+                nonce: tx.nonce,
+                error_code: None,
+                fee: 0,
+                gas_used: weights.transfer_weight(tx.encoded_size()),
+            };
+            let applied = match source {
+                BodySource::Mempool => Ok(()),
+                BodySource::Block => {
+                    let admitted = self.admit_block_transfer(&tx);
+                    execution.inadmissible += u64::from(admitted.is_err());
+                    admitted
+                }
+            }
+            .and_then(|()| self.limits.check_transaction(&tx))
+            .and_then(|()| self.apply_transfer(&tx));
+            match applied {
+                Ok(fee) => {
+                    receipt.fee = fee;
+                    let record = TransactionRecord::from_transaction(
+                        &tx,
+                        TransactionStatus::Committed { height },
+                    );
+                    execution.events.push(AppEvent::TransferApplied {
+                        txid: record.txid.clone(),
+                        token_id: record.token_id,
+                        from: record.from.clone(),
+                        to: record.to.clone(),
+                        amount: record.amount,
+                        height,
+                    });
+                    self.transaction_statuses
+                        .set(record.txid.clone(), LifecycleState::Included { height });
+                    self.index.record(record);
+                    // O token movido e o KYBL da taxa
+                    execution.touched_tokens.extend([tx.token_id, 0]);
+                    self.committed_transactions.push(tx.clone());
+                    execution.applied_transfers += 1;
+                }
+                Err(e) => {
+                    warn!(
+                        "Transação de {} (nonce {}) descartada no bloco {}: {}",
+                        tx.from, tx.nonce, height, e
+                    );
+                    receipt.error_code = Some(e.code());
+                    self.transaction_statuses.set(
+                        tx.txid(),
+                        LifecycleState::Dropped {
+                            code: Some(e.code()),
+                            reason: e.to_string(),
+                        },
+                    );
+                }
+            }
+            execution.transfers.push(tx);
+            execution.receipts.push(receipt);
+        }
+        execution
+    }
+
+    /// Conclui a execução de `block`: saldos indexados na altura, recibos
+    /// guardados e os eventos a emitir depois de anexá-lo, com o do bloco por último
+    pub(super) fn finish_execution(
+        &mut self,
+        block: &Block,
+        execution: BodyExecution,
+    ) -> Vec<AppEvent> {
+        self.index_balances(execution.touched_tokens, block.index);
+        if !execution.receipts.is_empty() {
+            self.execution_receipts
+                .insert(block.index, execution.receipts);
+        }
+        let mut events = execution.events;
+        events.push(AppEvent::BlockCommitted {
+            height: block.index,
+            hash: block.hash.clone(),
+            transactions: execution.applied_transfers,
+            operations: execution.applied_operations,
+        });
+        events
+    }
+
+    /// Executa o corpo de um bloco recebido já validado; ver `execute_body`
+    pub(super) fn execute_block(&mut self, block: &Block) -> Vec<AppEvent> {
+        let execution = self.execute_body(
+            block.index,
+            block.timestamp,
+            block.operations.clone(),
+            block.transfers.clone(),
+            BodySource::Block,
+        );
+        self.finish_execution(block, execution)
+    }
+
+    /// Reexecuta o corpo de `block` sobre uma cópia do estado, que fica intacto,
+    /// e exige que o resumo atestado pelo proponente seja o dos recibos obtidos.
+    ///
+    /// Um item recusado na admissão (assinatura, chave, nonce ou repetição)
+    /// invalida o bloco, já que o proponente só inclui itens admitidos; saldo
+    /// insuficiente e as demais falhas de aplicação apenas descartam o item, como
+    /// no proponente.
+    pub fn replay_body(&self, block: &Block) -> Result<(), Error> {
+        if block.operations.is_empty() && block.transfers.is_empty() {
+            return Ok(());
+        }
+        // A cópia não leva os blocos anteriores, então a repetição de uma
+        // transação legada, que a admissão recusaria, é procurada aqui
+        let repeated = block.transfers.iter().any(|tx| {
+            let txid = tx.txid();
+            !self
+                .pending_transactions
+                .iter()
+                .any(|pending| pending.txid() == txid)
+                && self.transaction_exists(tx)
+        });
+        if repeated {
+            return Err(Error::InvalidBlock(format!(
+                "Bloco {} repete uma transação já incluída na cadeia",
+                block.index
+            )));
+        }
+        let mut scratch = self.scratch_copy()?;
+        let execution = scratch.execute_body(
+            block.index,
+            block.timestamp,
+            block.operations.clone(),
+            block.transfers.clone(),
+            BodySource::Block,
+        );
+        if execution.inadmissible > 0 {
+            return Err(Error::InvalidBlock(format!(
+                "Bloco {} traz {} itens recusados na admissão",
+                block.index, execution.inadmissible
+            )));
+        }
+        let replayed = ExecutionSummary::from_receipts(&execution.receipts);
+        if block.execution.as_ref() != Some(&replayed) {
+            return Err(Error::InvalidBlock(format!(
+                "Resumo de execução do bloco {} diverge da reexecução do corpo",
+                block.index
+            )));
+        }
+        Ok(())
+    }
+
+    /// Admissão de uma operação de bloco recebido: se ela já está no mempool deste
+    /// nó, sai dele com o nonce já consumido; senão passa pelas verificações de
+    /// `submit_operation`
+    fn admit_block_operation(&mut self, operation: &Operation) -> Result<(), TransactionError> {
+        let id = operation.id();
+        if let Some(position) = self
+            .pending_operations
+            .iter()
+            .position(|pending| pending.id() == id)
+        {
+            self.pending_operations.remove(position);
+            return Ok(());
+        }
+        self.reserve_operation(operation)
+    }
+
+    /// Admissão de uma transferência de bloco recebido, como `admit_block_operation`:
+    /// fora do mempool, assinatura, nonce e duplicidade são conferidos aqui
+    fn admit_block_transfer(&mut self, tx: &Transaction) -> Result<(), TransactionError> {
+        let txid = tx.txid();
+        if let Some(position) = self
+            .pending_transactions
+            .iter()
+            .position(|pending| pending.txid() == txid)
+        {
+            self.pending_transactions.remove(position);
+            return Ok(());
+        }
+        if self.is_halted() {
+            return Err(TransactionError::ChainHalted);
+        }
+        Self::check_transaction(tx, &self.limits)?;
+        let current_nonce = self.nonces.get(&tx.from).copied().unwrap_or(0);
+        if tx.nonce != current_nonce + 1 {
+            return Err(TransactionError::NonceInvalido);
+        }
+        if self.transaction_exists(tx) {
+            return Err(TransactionError::TransacaoRepetida);
+        }
+        self.nonces.insert(tx.from.clone(), tx.nonce);
+        Ok(())
+    }
+}
@@ -0,0 +1,194 @@
+use super::blockchain::Blockchain;
+use crate::error::Error;
+use crate::network::NodeIdentity;
+use crate::utils::compression::{self, Codec};
+use pqcrypto_dilithium::dilithium5::{self, PublicKey};
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::path::{Path, PathBuf};
+
+const EXPORT_DOMAIN: &str = "kybelith-export-v1";
+
+/// Assinatura de um arquivo de estado exportado, gravada ao lado dele em `<arquivo>.sig`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportSignature {
+    /// SHA3-256 (hex) dos bytes do arquivo exatamente como gravados
+    pub content_hash: String,
+    /// Altura da cadeia no momento da exportação
+    pub height: u64,
+    /// Chave de identidade do nó que exportou
+    pub public_key: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+fn content_hash(content: &[u8]) -> String {
+    hex::encode(Sha3_256::digest(content))
+}
+
+fn signed_message(content_hash: &str, height: u64) -> Vec<u8> {
+    format!("{}:{}:{}", EXPORT_DOMAIN, height, content_hash).into_bytes()
+}
+
+impl ExportSignature {
+    pub fn create(content: &[u8], height: u64, identity: &NodeIdentity) -> Self {
+        let content_hash = content_hash(content);
+        let signature = identity.sign_message(&signed_message(&content_hash, height));
+        ExportSignature {
+            content_hash,
+            height,
+            public_key: identity.public_key.as_bytes().to_vec(),
+            signature,
+        }
+    }
+
+    /// Confere hash e assinatura de `content`. Com `trusted`, exige também que a
+    /// assinatura seja dessa chave.
+    pub fn verify(&self, content: &[u8], trusted: Option<&PublicKey>) -> Result<(), Error> {
+        self.verify_hash(&content_hash(content), trusted)
+    }
+
+    fn verify_hash(&self, content_hash: &str, trusted: Option<&PublicKey>) -> Result<(), Error> {
+        if let Some(trusted) = trusted {
+            if trusted.as_bytes() != self.public_key.as_slice() {
+                return Err(Error::Unauthorized(
+                    "Exportação assinada por uma chave não confiável".to_string(),
+                ));
+            }
+        }
+        if content_hash != self.content_hash {
+            return Err(Error::InvalidFormat(
+                "Conteúdo do arquivo não corresponde ao hash assinado".to_string(),
+            ));
+        }
+
+        let public_key =
+            PublicKey::from_bytes(&self.public_key).map_err(|_| Error::InvalidPublicKey)?;
+        let signature = dilithium5::DetachedSignature::from_bytes(&self.signature)
+            .map_err(|_| Error::InvalidSignature)?;
+        dilithium5::verify_detached_signature(
+            &signature,
+            &signed_message(&self.content_hash, self.height),
+            &public_key,
+        )
+        .map_err(|_| Error::InvalidSignature)
+    }
+
+    /// Caminho do arquivo de assinatura de `path`
+    pub fn path_for(path: &Path) -> PathBuf {
+        let mut name = path.as_os_str().to_owned();
+        name.push(".sig");
+        PathBuf::from(name)
+    }
+
+    /// Lê a assinatura de `path`, se houver uma
+    pub fn read_for(path: &Path) -> Result<Option<Self>, Error> {
+        let sig_path = Self::path_for(path);
+        if !sig_path.exists() {
+            return Ok(None);
+        }
+        let bytes = std::fs::read(&sig_path)
+            .map_err(|e| Error::Other(format!("Falha ao ler {}: {}", sig_path.display(), e)))?;
+        Ok(Some(serde_json::from_slice(&bytes)?))
+    }
+
+    fn write_for(&self, path: &Path) -> Result<(), Error> {
+        let sig_path = Self::path_for(path);
+        std::fs::write(&sig_path, serde_json::to_vec(self)?)
+            .map_err(|e| Error::Other(format!("Falha ao gravar {}: {}", sig_path.display(), e)))
+    }
+}
+
+/// Remove a assinatura de uma exportação anterior, que não vale para o novo conteúdo
+pub(crate) fn discard_signature(path: &Path) {
+    let _ = std::fs::remove_file(ExportSignature::path_for(path));
+}
+
+/// Lê `path` e confere a assinatura; sem `trusted`, a ausência de assinatura é aceita
+pub(crate) fn read_verified(path: &Path, trusted: Option<&PublicKey>) -> Result<Vec<u8>, Error> {
+    let content = std::fs::read(path)
+        .map_err(|e| Error::Other(format!("Falha ao ler o arquivo {}: {}", path.display(), e)))?;
+
+    match (ExportSignature::read_for(path)?, trusted) {
+        (Some(signature), trusted) => signature.verify(&content, trusted)?,
+        (None, Some(_)) => {
+            return Err(Error::Unauthorized(format!(
+                "Arquivo {} sem assinatura",
+                path.display()
+            )))
+        }
+        (None, None) => {}
+    }
+    Ok(content)
+}
+
+/// Como `read_verified`, mas só confere a assinatura, lendo o arquivo em blocos
+pub(crate) fn verify_file(path: &Path, trusted: Option<&PublicKey>) -> Result<(), Error> {
+    let signature = match (ExportSignature::read_for(path)?, trusted) {
+        (Some(signature), _) => signature,
+        (None, Some(_)) => {
+            return Err(Error::Unauthorized(format!(
+                "Arquivo {} sem assinatura",
+                path.display()
+            )))
+        }
+        (None, None) => return Ok(()),
+    };
+    let mut hasher = Sha3_256::new();
+    std::fs::File::open(path)
+        .and_then(|mut file| std::io::copy(&mut file, &mut hasher))
+        .map_err(|e| Error::Other(format!("Falha ao ler o arquivo {}: {}", path.display(), e)))?;
+    signature.verify_hash(&hex::encode(hasher.finalize()), trusted)
+}
+
+impl Blockchain {
+    fn write_signed(
+        &self,
+        path: &Path,
+        content: &[u8],
+        identity: &NodeIdentity,
+    ) -> Result<ExportSignature, Error> {
+        self.ensure_complete()?;
+        std::fs::write(path, content)
+            .map_err(|e| Error::Other(format!("Falha ao gravar {}: {}", path.display(), e)))?;
+        let signature = ExportSignature::create(content, self.height(), identity);
+        signature.write_for(path)?;
+        Ok(signature)
+    }
+
+    /// Grava a blockchain em JSON, como `save_to_file`, com a assinatura do nó
+    pub fn save_signed(
+        &self,
+        filename: &str,
+        identity: &NodeIdentity,
+    ) -> Result<ExportSignature, Error> {
+        let json = serde_json::to_vec(self)?;
+        self.write_signed(Path::new(filename), &json, identity)
+    }
+
+    /// Grava um snapshot comprimido, como `save_snapshot`, com a assinatura do nó
+    pub fn save_snapshot_signed(
+        &self,
+        filename: &str,
+        codec: Codec,
+        identity: &NodeIdentity,
+    ) -> Result<ExportSignature, Error> {
+        let frame = compression::encode(codec, &serde_json::to_vec(self)?)?;
+        self.write_signed(Path::new(filename), &frame, identity)
+    }
+
+    /// Carrega um arquivo JSON que precisa estar assinado por `trusted`
+    pub fn load_signed(filename: &str, trusted: &PublicKey) -> Result<Self, Error> {
+        let json = read_verified(Path::new(filename), Some(trusted))?;
+        Blockchain::from_json(&json)
+    }
+
+    /// Carrega um snapshot que precisa estar assinado por `trusted`
+    pub fn load_snapshot_signed(filename: &str, trusted: &PublicKey) -> Result<Self, Error> {
+        let frame = read_verified(Path::new(filename), Some(trusted))?;
+        Blockchain::from_json(&compression::decode(
+            &frame,
+            crate::constants::MAX_SNAPSHOT_SIZE,
+        )?)
+    }
+}
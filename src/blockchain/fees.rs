@@ -0,0 +1,94 @@
+use super::blockchain::Blockchain;
+use super::supply::SupplyChangeKind;
+use crate::constants::{
+    BURN_PERCENTAGE, DEV_FUND_ADDRESS, DEV_PERCENTAGE, LIQUIDITY_FUND_ADDRESS, STAKING_PERCENTAGE,
+    STAKING_POOL_ADDRESS,
+};
+use crate::error::TransactionError;
+use log::info;
+
+impl Blockchain {
+    /// Saldo em KYBL (token 0) de `address`, de onde saem as taxas
+    pub fn fee_balance(&self, address: &str) -> u64 {
+        self.get_token("0")
+            .map(|token| token.balance_of(&address.to_string()))
+            .unwrap_or(0)
+    }
+
+    /// Aplica `apply` com `fee` já retida do saldo KYBL de `payer` e devolve o
+    /// resultado junto da taxa efetivamente cobrada.
+    ///
+    /// Se `apply` falhar a taxa volta ao pagador e o item não deixa rastro; senão
+    /// ela é distribuída: `BURN_PERCENTAGE` queimado e o restante dividido entre
+    /// os fundos de staking, desenvolvimento e liquidez.
+    pub(super) fn with_fee<T, E: From<TransactionError>>(
+        &mut self,
+        payer: &str,
+        fee: u64,
+        apply: impl FnOnce(&mut Self) -> Result<T, E>,
+    ) -> Result<(T, u64), E> {
+        if fee == 0 {
+            return apply(self).map(|value| (value, 0));
+        }
+        self.move_fee(payer, fee, true)?;
+        match apply(self) {
+            Ok(value) => {
+                self.distribute_fee(fee);
+                Ok((value, fee))
+            }
+            Err(e) => {
+                self.move_fee(payer, fee, false)?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Debita (`retain`) ou devolve `fee` ao saldo KYBL de `payer`
+    fn move_fee(&mut self, payer: &str, fee: u64, retain: bool) -> Result<(), TransactionError> {
+        let token = self
+            .tokens
+            .get_mut("0")
+            .ok_or(TransactionError::TokenNaoEncontrado)?;
+        let balance = token.balances.get(payer).copied().unwrap_or(0);
+        let balance = if retain {
+            balance
+                .checked_sub(fee)
+                .ok_or(TransactionError::InsufficientFunds)?
+        } else {
+            balance.saturating_add(fee)
+        };
+        token.balances.insert(payer.to_string(), balance);
+        Ok(())
+    }
+
+    fn distribute_fee(&mut self, fee: u64) {
+        let share = |percent: u64| (u128::from(fee) * u128::from(percent) / 100) as u64;
+        let burn_amount = share(BURN_PERCENTAGE);
+        let staking_amount = share(STAKING_PERCENTAGE);
+        let dev_amount = share(DEV_PERCENTAGE);
+        // A liquidez fica com o resto, para que o arredondamento não perca taxa
+        let liquidity_amount = fee - burn_amount - staking_amount - dev_amount;
+
+        let Some(token) = self.tokens.get_mut("0") else {
+            return;
+        };
+        token.total_supply = token.total_supply.saturating_sub(burn_amount);
+        for (address, amount) in [
+            (STAKING_POOL_ADDRESS, staking_amount),
+            (DEV_FUND_ADDRESS, dev_amount),
+            (LIQUIDITY_FUND_ADDRESS, liquidity_amount),
+        ] {
+            if amount > 0 {
+                *token.balances.entry(address.to_string()).or_insert(0) += amount;
+            }
+        }
+        if burn_amount > 0 {
+            self.record_supply_change("0", SupplyChangeKind::Burn, burn_amount, "queima de taxa");
+        }
+
+        info!(
+            "Distribuição de taxa: Queima: {} KYBL, Staking: {} KYBL, Dev: {} KYBL, Liquidez: {} KYBL",
+            burn_amount, staking_amount, dev_amount, liquidity_amount
+        );
+    }
+}
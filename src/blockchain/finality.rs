@@ -0,0 +1,56 @@
+use super::blockchain::Blockchain;
+use super::indexer::TransactionStatus;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Grau de finalidade de um bloco ou transação, do mais fraco ao mais forte
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Finality {
+    /// No mempool, sem bloco
+    #[default]
+    Pending,
+    /// Em um bloco com menos de `confirmation_depth` blocos por cima; pode ser
+    /// revertido por uma reorganização
+    Included,
+    /// Com ao menos `confirmation_depth` blocos por cima, mas acima do último
+    /// checkpoint
+    Safe,
+    /// Coberto por um checkpoint assinado; não é revertido
+    Finalized,
+}
+
+impl Blockchain {
+    /// Finalidade do bloco em `height`; `None` se a altura não existe na cadeia
+    pub fn finality_at(&self, height: u64) -> Option<Finality> {
+        let built_on = self.height().checked_sub(height + 1)?;
+        let finality = if self
+            .checkpoint
+            .as_ref()
+            .is_some_and(|checkpoint| checkpoint.height >= height)
+        {
+            Finality::Finalized
+        } else if built_on >= self.confirmation_depth {
+            Finality::Safe
+        } else {
+            Finality::Included
+        };
+        Some(finality)
+    }
+
+    /// Altura coberta pelo último checkpoint; blocos até ela não são revertidos
+    pub fn finalized_height(&self) -> Option<u64> {
+        self.checkpoint.as_ref().map(|checkpoint| checkpoint.height)
+    }
+
+    pub fn transaction_finality(&self, status: TransactionStatus) -> Finality {
+        match status {
+            TransactionStatus::Pending => Finality::Pending,
+            TransactionStatus::Committed { height } => {
+                self.finality_at(height).unwrap_or(Finality::Included)
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use super::blockchain::Blockchain;
+use crate::blockchain::validacao::Validator;
+use crate::constants::MAX_BLOCK_SIZE;
+use crate::error::Error;
+use serde_json::{Map, Value};
+
+/// Versão do formato JSON gravado por `save_to_file` e `save_snapshot`.
+///
+/// Arquivos sem o campo `format_version` são da versão 0, que ainda trazia a lista
+/// `blocks` duplicando `chain` e as transações confirmadas em `transactions`.
+pub const BLOCKCHAIN_FORMAT_VERSION: u32 = 1;
+
+fn migrate_v0(state: &mut Map<String, Value>) {
+    // `blocks` nunca foi mantida em sincronia com `chain`; só é aproveitada quando
+    // `chain` está vazia
+    let blocks = state.remove("blocks");
+    let chain_is_empty = state
+        .get("chain")
+        .and_then(Value::as_array)
+        .is_none_or(Vec::is_empty);
+    if let Some(Value::Array(blocks)) = blocks {
+        if chain_is_empty && !blocks.is_empty() {
+            state.insert("chain".to_string(), Value::Array(blocks));
+        }
+    }
+
+    if let Some(transactions) = state.remove("transactions") {
+        state.insert("committed_transactions".to_string(), transactions);
+    }
+}
+
+/// Atualiza um documento JSON de qualquer versão conhecida para a atual
+fn migrate(mut value: Value) -> Result<Value, Error> {
+    let state = value.as_object_mut().ok_or_else(|| {
+        Error::InvalidFormat("Formato de blockchain inválido: objeto esperado".to_string())
+    })?;
+
+    let version = match state.get("format_version") {
+        None => 0,
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| Error::InvalidFormat(format!("Versão de formato inválida: {}", v)))?,
+    };
+    if version > BLOCKCHAIN_FORMAT_VERSION {
+        return Err(Error::InvalidFormat(format!(
+            "Formato de blockchain {} mais novo que o suportado ({})",
+            version, BLOCKCHAIN_FORMAT_VERSION
+        )));
+    }
+
+    if version < 1 {
+        migrate_v0(state);
+    }
+    state.insert(
+        "format_version".to_string(),
+        Value::from(BLOCKCHAIN_FORMAT_VERSION),
+    );
+
+    Ok(value)
+}
+
+impl Blockchain {
+    /// Desserializa uma blockchain gravada em qualquer versão do formato JSON
+    pub fn from_json(bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_value(serde_json::from_slice(bytes)?)
+    }
+
+    /// Como `from_json`, para um documento já lido
+    pub(super) fn from_value(value: Value) -> Result<Self, Error> {
+        let mut blockchain: Blockchain = serde_json::from_value(migrate(value)?)?;
+        // Campos não serializados
+        blockchain.validator = Validator::new(MAX_BLOCK_SIZE, 300);
+        Ok(blockchain)
+    }
+
+    pub fn format_version(&self) -> u32 {
+        self.format_version
+    }
+}
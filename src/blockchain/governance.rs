@@ -0,0 +1,197 @@
+use super::blockchain::{Address, Blockchain};
+use crate::error::{Error, TransactionError};
+use crate::transaction::{Operation, OperationKind, VoteChoice};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Percentual do stake do snapshot que precisa votar (abstenções incluídas) para
+/// que uma proposta possa ser aprovada
+pub const GOVERNANCE_QUORUM_PERCENT: u64 = 33;
+
+/// Proposta de governança aberta por `OperationKind::SubmitProposal`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Proposal {
+    pub proposer: Address,
+    pub description: String,
+    /// Altura cujo stake vinculado define o poder de voto; stake vinculado ou
+    /// desvinculado depois dela não altera a contagem
+    pub snapshot_height: u64,
+    /// Último bloco que ainda aceita votos
+    pub voting_ends_at: u64,
+    pub votes: BTreeMap<Address, VoteChoice>,
+}
+
+/// Situação de uma proposta pela contagem e pela altura atual
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum ProposalStatus {
+    Voting,
+    /// Quórum atingido e mais stake a favor do que contra
+    Passed,
+    Rejected,
+}
+
+/// Contagem de uma proposta, com cada voto pesado pelo stake do eleitor no snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ProposalTally {
+    pub proposal_id: u64,
+    pub snapshot_height: u64,
+    pub yes: u64,
+    pub no: u64,
+    pub abstain: u64,
+    /// Stake vinculado total no snapshot, base do quórum
+    pub eligible: u64,
+    pub status: ProposalStatus,
+}
+
+impl ProposalTally {
+    /// Stake que votou, em qualquer opção
+    pub fn turnout(&self) -> u64 {
+        self.yes
+            .saturating_add(self.no)
+            .saturating_add(self.abstain)
+    }
+
+    pub fn quorum_reached(&self) -> bool {
+        u128::from(self.turnout()) * 100
+            >= u128::from(self.eligible) * u128::from(GOVERNANCE_QUORUM_PERCENT)
+    }
+}
+
+impl Blockchain {
+    pub fn proposal(&self, proposal_id: u64) -> Option<&Proposal> {
+        usize::try_from(proposal_id)
+            .ok()
+            .and_then(|index| self.proposals.get(index))
+    }
+
+    /// Soma os votos da proposta pelo stake de cada eleitor na altura do snapshot,
+    /// não pelo stake atual
+    pub fn proposal_tally(&self, proposal_id: u64) -> Result<ProposalTally, Error> {
+        let proposal = self
+            .proposal(proposal_id)
+            .ok_or_else(|| Error::InvalidInput(format!("Proposta {} inexistente", proposal_id)))?;
+        let stakes = self.stake_snapshot(proposal.snapshot_height)?;
+
+        let (mut yes, mut no, mut abstain) = (0u64, 0u64, 0u64);
+        for (voter, choice) in &proposal.votes {
+            let weight = stakes.balance_of(voter);
+            let total = match choice {
+                VoteChoice::Yes => &mut yes,
+                VoteChoice::No => &mut no,
+                VoteChoice::Abstain => &mut abstain,
+            };
+            *total = total.saturating_add(weight);
+        }
+
+        let mut tally = ProposalTally {
+            proposal_id,
+            snapshot_height: proposal.snapshot_height,
+            yes,
+            no,
+            abstain,
+            eligible: stakes.total(),
+            status: ProposalStatus::Voting,
+        };
+        if self.height() > proposal.voting_ends_at {
+            tally.status = if tally.quorum_reached() && tally.yes > tally.no {
+                ProposalStatus::Passed
+            } else {
+                ProposalStatus::Rejected
+            };
+        }
+        Ok(tally)
+    }
+
+    /// Stake vinculado de `address` no snapshot, ou erro se for nulo
+    fn voting_power(&self, address: &str, snapshot_height: u64) -> Result<u64, TransactionError> {
+        self.stakes_at(snapshot_height)
+            .map(|stakes| stakes.balance_of(address))
+            .filter(|stake| *stake > 0)
+            .ok_or_else(|| {
+                TransactionError::InvalidParameter(format!(
+                    "{} não tinha stake vinculado na altura {}",
+                    address, snapshot_height
+                ))
+            })
+    }
+
+    /// Propostas e votos valem no bloco em `height` e exigem stake no snapshot,
+    /// assinados com a chave do próprio endereço
+    pub(super) fn check_governance_operation(
+        &self,
+        operation: &Operation,
+        height: u64,
+    ) -> Result<(), TransactionError> {
+        if !self.is_account_key(&operation.author, &operation.public_key) {
+            return Err(TransactionError::InvalidParameter(format!(
+                "{} precisa assinar com a chave do próprio endereço para participar da governança",
+                operation.author
+            )));
+        }
+
+        match &operation.kind {
+            OperationKind::SubmitProposal { .. } => {
+                let snapshot_height = height.checked_sub(1).ok_or_else(|| {
+                    TransactionError::InvalidParameter(
+                        "Propostas exigem ao menos um bloco confirmado".to_string(),
+                    )
+                })?;
+                self.voting_power(&operation.author, snapshot_height)?;
+            }
+            OperationKind::CastVote { proposal_id, .. } => {
+                let proposal = self.proposal(*proposal_id).ok_or_else(|| {
+                    TransactionError::InvalidParameter(format!(
+                        "Proposta {} inexistente",
+                        proposal_id
+                    ))
+                })?;
+                if height > proposal.voting_ends_at {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "Votação da proposta {} encerrada no bloco {}",
+                        proposal_id, proposal.voting_ends_at
+                    )));
+                }
+                self.voting_power(&operation.author, proposal.snapshot_height)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Reconfere a operação no bloco em `height` e registra a proposta ou o voto
+    pub(super) fn apply_governance_operation(
+        &mut self,
+        operation: &Operation,
+        height: u64,
+    ) -> Result<(), TransactionError> {
+        self.check_governance_operation(operation, height)?;
+        match &operation.kind {
+            OperationKind::SubmitProposal {
+                description,
+                voting_blocks,
+            } => {
+                self.proposals.push(Proposal {
+                    proposer: operation.author.clone(),
+                    description: description.clone(),
+                    snapshot_height: height - 1,
+                    voting_ends_at: height.saturating_add(*voting_blocks),
+                    votes: BTreeMap::new(),
+                });
+            }
+            OperationKind::CastVote {
+                proposal_id,
+                choice,
+            } => {
+                if let Some(proposal) = usize::try_from(*proposal_id)
+                    .ok()
+                    .and_then(|index| self.proposals.get_mut(index))
+                {
+                    proposal.votes.insert(operation.author.clone(), *choice);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use super::blockchain::{Address, Blockchain};
+use crate::error::TransactionError;
+use crate::events::AppEvent;
+use crate::rbac::AdminRole;
+use crate::transaction::{Operation, OperationKind};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Parada ou retomada de emergência aplicada na cadeia
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct HaltRecord {
+    pub halted: bool,
+    /// Motivo da aprovação que completou a maioria
+    pub reason: String,
+    pub height: u64,
+    /// Admins que aprovaram a mudança, em ordem de endereço
+    pub approvers: Vec<Address>,
+}
+
+impl Blockchain {
+    /// Se a cadeia está parada: blocos só carregam aprovações de retomada e o
+    /// mempool recusa transferências e outras operações
+    pub fn is_halted(&self) -> bool {
+        self.halt_history.last().is_some_and(|record| record.halted)
+    }
+
+    /// Aprovações de admins necessárias para parar ou retomar: maioria simples
+    pub fn halt_threshold(&self) -> usize {
+        self.role_members(AdminRole::Admin).len() / 2 + 1
+    }
+
+    /// Admins que já aprovaram a inversão do estado atual e ainda têm o papel
+    pub fn halt_approvers(&self) -> Vec<Address> {
+        self.halt_approvals
+            .keys()
+            .filter(|address| self.has_role(address, AdminRole::Admin))
+            .cloned()
+            .collect()
+    }
+
+    /// Só admins, assinando com a chave do próprio endereço, e só no sentido
+    /// contrário ao estado atual
+    pub(super) fn check_halt_operation(
+        &self,
+        operation: &Operation,
+    ) -> Result<(), TransactionError> {
+        let OperationKind::SetChainHalted { halted, .. } = &operation.kind else {
+            return Ok(());
+        };
+
+        if !self.is_account_key(&operation.author, &operation.public_key)
+            || !self.has_role(&operation.author, AdminRole::Admin)
+        {
+            return Err(TransactionError::InvalidParameter(format!(
+                "{} não pode parar nem retomar a cadeia",
+                operation.author
+            )));
+        }
+        if *halted == self.is_halted() {
+            return Err(TransactionError::InvalidParameter(
+                if *halted {
+                    "Cadeia já está parada"
+                } else {
+                    "Cadeia não está parada"
+                }
+                .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Registra a aprovação e, com a maioria dos admins, inverte o estado da
+    /// cadeia. Devolve o evento da mudança, se houve
+    pub(super) fn apply_halt_operation(
+        &mut self,
+        operation: &Operation,
+        height: u64,
+    ) -> Result<Option<AppEvent>, TransactionError> {
+        self.check_halt_operation(operation)?;
+        let OperationKind::SetChainHalted { halted, reason } = &operation.kind else {
+            return Ok(None);
+        };
+
+        self.halt_approvals
+            .insert(operation.author.clone(), reason.clone());
+        let approvers = self.halt_approvers();
+        if approvers.len() < self.halt_threshold() {
+            return Ok(None);
+        }
+
+        self.halt_approvals.clear();
+        self.halt_history.push(HaltRecord {
+            halted: *halted,
+            reason: reason.clone(),
+            height,
+            approvers: approvers.clone(),
+        });
+        Ok(Some(AppEvent::ChainHaltChanged {
+            halted: *halted,
+            reason: reason.clone(),
+            approvers,
+            by: operation.author.clone(),
+            height,
+        }))
+    }
+}
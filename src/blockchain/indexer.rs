@@ -0,0 +1,344 @@
+use super::blockchain::Blockchain;
+use super::finality::Finality;
+use super::rejection::RejectionReason;
+use super::spv::transaction_id;
+use crate::error::Error;
+use crate::token::BalanceSnapshot;
+use crate::transaction::{SecureTransaction, Transaction};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// Quantos motivos de rejeição o índice guarda antes de descartar os mais antigos
+pub const MAX_RECORDED_REJECTIONS: usize = 1024;
+
+/// Situação de uma transação conhecida pelo nó
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum TransactionStatus {
+    /// Admitida no mempool, ainda sem bloco
+    Pending,
+    /// Confirmada no bloco em `height`
+    Committed { height: u64 },
+}
+
+/// Visão de uma transação para carteiras e CLI, independente do formato de origem
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionRecord {
+    pub txid: String,
+    pub token_id: u64,
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub nonce: u64,
+    pub timestamp: i64,
+    pub status: TransactionStatus,
+    /// Atualizada a cada consulta, conforme a altura do topo e o último checkpoint
+    #[serde(default)]
+    pub finality: Finality,
+}
+
+impl TransactionRecord {
+    pub fn from_transaction(tx: &Transaction, status: TransactionStatus) -> Self {
+        TransactionRecord {
+            txid: tx.txid(),
+            token_id: tx.token_id,
+            from: tx.from.clone(),
+            to: tx.to.clone(),
+            amount: tx.amount,
+            nonce: tx.nonce,
+            timestamp: tx.timestamp,
+            status,
+            finality: match status {
+                TransactionStatus::Pending => Finality::Pending,
+                TransactionStatus::Committed { .. } => Finality::Included,
+            },
+        }
+    }
+
+    /// Transações de bloco recebidas da rede movimentam sempre o token nativo
+    pub fn from_secure(tx: &SecureTransaction, height: u64) -> Self {
+        TransactionRecord {
+            txid: transaction_id(tx),
+            token_id: 0,
+            from: tx.from.clone(),
+            to: tx.to.clone(),
+            amount: tx.amount,
+            nonce: tx.nonce,
+            timestamp: tx.timestamp,
+            status: TransactionStatus::Committed { height },
+            finality: Finality::Included,
+        }
+    }
+}
+
+/// Índice das transações confirmadas, por txid e por endereço envolvido
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransactionIndex {
+    records: HashMap<String, TransactionRecord>,
+    /// txids de cada endereço, na ordem de confirmação
+    by_address: HashMap<String, Vec<String>>,
+    /// Saldo de cada endereço por token, como pontos `(altura, saldo)` em ordem
+    /// crescente de altura; só há ponto nas alturas em que o saldo mudou
+    #[serde(default)]
+    balances: HashMap<u64, HashMap<String, Vec<(u64, u64)>>>,
+    /// Primeira altura coberta pelo histórico de saldos de cada token
+    #[serde(default)]
+    balances_since: HashMap<u64, u64>,
+    /// Stake vinculado de cada endereço, nos mesmos pontos `(altura, valor)` dos
+    /// saldos; base do poder de voto na governança
+    #[serde(default)]
+    stakes: HashMap<String, Vec<(u64, u64)>>,
+    /// Primeira altura coberta pelo histórico de stake
+    #[serde(default)]
+    stakes_since: Option<u64>,
+    /// Rejeições mais recentes, da mais antiga para a mais nova; só para
+    /// depuração, não sobrevivem a uma recarga
+    #[serde(skip)]
+    rejections: VecDeque<RejectionReason>,
+}
+
+impl TransactionIndex {
+    pub fn record(&mut self, record: TransactionRecord) {
+        if self.records.contains_key(&record.txid) {
+            return;
+        }
+
+        self.by_address
+            .entry(record.from.clone())
+            .or_default()
+            .push(record.txid.clone());
+        if record.to != record.from {
+            self.by_address
+                .entry(record.to.clone())
+                .or_default()
+                .push(record.txid.clone());
+        }
+        self.records.insert(record.txid.clone(), record);
+    }
+
+    pub fn get(&self, txid: &str) -> Option<&TransactionRecord> {
+        self.records.get(txid)
+    }
+
+    /// Esquece a transação `txid`, por exemplo quando seu bloco é revertido
+    pub fn remove(&mut self, txid: &str) -> Option<TransactionRecord> {
+        let record = self.records.remove(txid)?;
+        for address in [&record.from, &record.to] {
+            if let Some(txids) = self.by_address.get_mut(address) {
+                txids.retain(|known| known != txid);
+            }
+        }
+        Some(record)
+    }
+
+    /// Quantidade de transações confirmadas envolvendo `address`
+    pub fn count_for(&self, address: &str) -> usize {
+        self.by_address.get(address).map_or(0, Vec::len)
+    }
+
+    /// Transações confirmadas envolvendo `address`, da mais recente para a mais antiga
+    pub fn for_address<'a>(
+        &'a self,
+        address: &str,
+    ) -> impl Iterator<Item = &'a TransactionRecord> + 'a {
+        self.by_address
+            .get(address)
+            .into_iter()
+            .flat_map(|txids| txids.iter().rev())
+            .filter_map(|txid| self.records.get(txid))
+    }
+
+    /// Registra os saldos de `token_id` após o bloco em `height`.
+    ///
+    /// O primeiro registro de um token marca o início do seu histórico; endereços
+    /// que saíram do mapa passam a valer zero.
+    pub fn record_balances(&mut self, token_id: u64, height: u64, current: &HashMap<String, u64>) {
+        self.balances_since.entry(token_id).or_insert(height);
+        Self::record_points(self.balances.entry(token_id).or_default(), height, current);
+    }
+
+    /// Registra o stake vinculado de todos os stakers a partir de `height`
+    pub fn record_stakes(&mut self, height: u64, current: &HashMap<String, u64>) {
+        self.stakes_since.get_or_insert(height);
+        Self::record_points(&mut self.stakes, height, current);
+    }
+
+    fn record_points(
+        history: &mut HashMap<String, Vec<(u64, u64)>>,
+        height: u64,
+        current: &HashMap<String, u64>,
+    ) {
+        for (address, balance) in current {
+            Self::push_balance(
+                history.entry(address.clone()).or_default(),
+                height,
+                *balance,
+            );
+        }
+        for (address, points) in history.iter_mut() {
+            if !current.contains_key(address) {
+                Self::push_balance(points, height, 0);
+            }
+        }
+    }
+
+    fn push_balance(points: &mut Vec<(u64, u64)>, height: u64, balance: u64) {
+        match points.last_mut() {
+            Some((_, last)) if *last == balance => {}
+            Some((h, last)) if *h == height => *last = balance,
+            _ => points.push((height, balance)),
+        }
+    }
+
+    /// Saldos não nulos de `token_id` após o bloco em `height`, ou `None` se o
+    /// histórico do token não cobre essa altura
+    pub fn balances_at(&self, token_id: u64, height: u64) -> Option<BTreeMap<String, u64>> {
+        if height < *self.balances_since.get(&token_id)? {
+            return None;
+        }
+        Some(Self::points_at(self.balances.get(&token_id)?, height))
+    }
+
+    /// Stake vinculado não nulo de cada endereço na altura `height`, ou `None` se
+    /// o histórico de stake não cobre essa altura
+    pub fn stakes_at(&self, height: u64) -> Option<BTreeMap<String, u64>> {
+        if height < self.stakes_since? {
+            return None;
+        }
+        Some(Self::points_at(&self.stakes, height))
+    }
+
+    /// Esquece os saldos e o stake registrados a partir de `height`, altura
+    /// revertida por uma reorganização
+    pub fn truncate_history(&mut self, height: u64) {
+        self.balances_since.retain(|_, since| *since < height);
+        let since = &self.balances_since;
+        self.balances
+            .retain(|token_id, _| since.contains_key(token_id));
+        if self.stakes_since.is_some_and(|since| since >= height) {
+            self.stakes_since = None;
+        }
+        for points in self
+            .balances
+            .values_mut()
+            .flat_map(HashMap::values_mut)
+            .chain(self.stakes.values_mut())
+        {
+            points.truncate(points.partition_point(|(h, _)| *h < height));
+        }
+    }
+
+    /// Índice só com o histórico de stake, a parte que a execução de blocos lê
+    /// (pesos das votações); o das cópias de execução
+    pub(super) fn stake_history(&self) -> Self {
+        TransactionIndex {
+            stakes: self.stakes.clone(),
+            stakes_since: self.stakes_since,
+            ..TransactionIndex::default()
+        }
+    }
+
+    fn points_at(history: &HashMap<String, Vec<(u64, u64)>>, height: u64) -> BTreeMap<String, u64> {
+        history
+            .iter()
+            .filter_map(|(address, points)| {
+                let position = points.partition_point(|(h, _)| *h <= height);
+                match position.checked_sub(1).map(|i| points[i].1) {
+                    Some(balance) if balance > 0 => Some((address.clone(), balance)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Guarda o motivo de uma rejeição, substituindo o anterior do mesmo txid
+    pub fn record_rejection(&mut self, reason: RejectionReason) {
+        self.rejections.retain(|known| known.txid != reason.txid);
+        if self.rejections.len() >= MAX_RECORDED_REJECTIONS {
+            self.rejections.pop_front();
+        }
+        self.rejections.push_back(reason);
+    }
+
+    pub fn rejection(&self, txid: &str) -> Option<&RejectionReason> {
+        self.rejections
+            .iter()
+            .rev()
+            .find(|reason| reason.txid == txid)
+    }
+
+    /// Rejeições guardadas, da mais recente para a mais antiga
+    pub fn rejections(&self) -> impl Iterator<Item = &RejectionReason> {
+        self.rejections.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+impl Blockchain {
+    /// Procura a transação no índice de confirmadas e, se não estiver lá, no mempool
+    pub fn transaction_record(&self, txid: &str) -> Option<TransactionRecord> {
+        if let Some(record) = self.index.get(txid) {
+            return Some(self.with_finality(record.clone()));
+        }
+        self.pending_transactions
+            .iter()
+            .find(|tx| tx.txid() == txid)
+            .map(|tx| TransactionRecord::from_transaction(tx, TransactionStatus::Pending))
+    }
+
+    /// Registra no índice os saldos dos tokens alterados pelo bloco em `height`
+    pub(super) fn index_balances(&mut self, token_ids: impl IntoIterator<Item = u64>, height: u64) {
+        for token_id in token_ids {
+            if let Some(token) = self.tokens.get(&token_id.to_string()) {
+                self.index
+                    .record_balances(token_id, height, &token.balances);
+            }
+        }
+    }
+
+    /// Saldos de `token_id` após o bloco em `height`, que não pode estar acima do topo
+    pub fn snapshot(&self, token_id: u64, height: u64) -> Result<BalanceSnapshot, Error> {
+        let token = self
+            .tokens
+            .get(&token_id.to_string())
+            .ok_or(Error::TokenNotFound)?;
+        if self.chain.last().is_none_or(|block| height > block.index) {
+            return Err(Error::InvalidInput(format!(
+                "Altura {} ainda não foi confirmada",
+                height
+            )));
+        }
+        token.snapshot(height, &self.index)
+    }
+
+    /// Página `page` (a partir de 0) do histórico de `address`: pendentes primeiro,
+    /// depois as confirmadas da mais recente para a mais antiga
+    pub fn history(&self, address: &str, page: usize, page_size: usize) -> Vec<TransactionRecord> {
+        let pending = self
+            .pending_transactions
+            .iter()
+            .rev()
+            .filter(|tx| tx.from == address || tx.to == address)
+            .map(|tx| TransactionRecord::from_transaction(tx, TransactionStatus::Pending));
+        let committed = self.index.for_address(address).cloned();
+
+        pending
+            .chain(committed)
+            .skip(page.saturating_mul(page_size))
+            .take(page_size)
+            .map(|record| self.with_finality(record))
+            .collect()
+    }
+
+    fn with_finality(&self, mut record: TransactionRecord) -> TransactionRecord {
+        record.finality = self.transaction_finality(record.status);
+        record
+    }
+}
@@ -0,0 +1,76 @@
+use super::blockchain::Blockchain;
+use super::export;
+use super::read_snapshot::ReadSnapshot;
+use super::sqlite;
+use crate::error::Error;
+use std::fs::File;
+use std::io::Read;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// Cabeçalho de todo arquivo de banco SQLite
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Blockchain aberta por `Blockchain::open_read_only`. Só dá acesso de leitura
+/// (`&Blockchain`); nada do que for consultado volta ao disco.
+#[derive(Debug)]
+pub struct ReadOnlyBlockchain {
+    blockchain: Blockchain,
+    path: PathBuf,
+}
+
+impl ReadOnlyBlockchain {
+    /// Arquivo ou banco de onde a cadeia foi lida
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Mesma foto que o RPC usa para responder consultas
+    pub fn snapshot(&self) -> ReadSnapshot {
+        ReadSnapshot::capture(&self.blockchain, 0)
+    }
+
+    /// Cópia em memória para quem precisa de uma `Blockchain` própria, como o
+    /// serviço RPC; alterá-la não afeta o arquivo de origem
+    pub fn into_inner(self) -> Blockchain {
+        self.blockchain
+    }
+}
+
+impl Deref for ReadOnlyBlockchain {
+    type Target = Blockchain;
+
+    fn deref(&self) -> &Blockchain {
+        &self.blockchain
+    }
+}
+
+fn is_sqlite(path: &Path) -> Result<bool, Error> {
+    let mut header = [0u8; 16];
+    let mut file = File::open(path)
+        .map_err(|e| Error::Other(format!("Falha ao abrir {}: {}", path.display(), e)))?;
+    Ok(file.read_exact(&mut header).is_ok() && &header == SQLITE_HEADER)
+}
+
+impl Blockchain {
+    /// Abre para inspeção os dados de um nó, possivelmente em execução: arquivo
+    /// JSON de `save_to_file`/`save_signed` ou banco de `save_to_db`, reconhecido
+    /// pelo conteúdo.
+    ///
+    /// Ao contrário de `load_from_file` e `load_from_db`, nada é criado ou
+    /// migrado: arquivo ausente ou ilegível é erro, o token QST não é recriado e
+    /// o banco é aberto só para leitura, sem travas de escrita.
+    pub fn open_read_only(path: impl AsRef<Path>) -> Result<ReadOnlyBlockchain, Error> {
+        let path = path.as_ref();
+        let blockchain = if is_sqlite(path)? {
+            sqlite::read_only(path)
+                .map_err(|e| Error::database(format!("Falha ao ler {}", path.display()), e))?
+        } else {
+            Blockchain::from_json(&export::read_verified(path, None)?)?
+        };
+        Ok(ReadOnlyBlockchain {
+            blockchain,
+            path: path.to_path_buf(),
+        })
+    }
+}
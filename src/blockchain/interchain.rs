@@ -0,0 +1,395 @@
+use super::blockchain::Blockchain;
+use super::merkle::MerkleProof;
+use super::supply::SupplyChangeKind;
+use crate::error::TransactionError;
+use crate::interchain::{
+    escrow_address, ChainHeader, InterchainError, LightClient, Packet, SignedHeader,
+};
+use crate::rbac::AdminRole;
+use crate::token::Token;
+use crate::transaction::{Operation, OperationKind};
+use log::info;
+use pqcrypto_dilithium::dilithium5::{PublicKey, SecretKey};
+
+/// Rastro do voucher que representa, nesta cadeia, a denominação do pacote
+fn voucher_trace(packet: &Packet) -> String {
+    format!("{}/{}", packet.source_chain, packet.denom)
+}
+
+impl Blockchain {
+    /// Define o identificador desta cadeia nos pacotes e cabeçalhos entre cadeias
+    pub fn set_chain_id(&mut self, chain_id: impl Into<String>) {
+        self.interchain.chain_id = chain_id.into();
+    }
+
+    pub fn chain_id(&self) -> &str {
+        &self.interchain.chain_id
+    }
+
+    pub fn interchain_client(&self, chain_id: &str) -> Option<&LightClient> {
+        self.interchain.clients.get(chain_id)
+    }
+
+    /// ID do token local que representa o voucher com este rastro
+    pub fn voucher_token(&self, trace: &str) -> Option<u64> {
+        self.interchain.vouchers.get(trace).copied()
+    }
+
+    /// Se o pacote vindo de `source_chain` com esta sequência já foi recebido
+    pub fn packet_received(&self, source_chain: &str, sequence: u64) -> bool {
+        self.interchain
+            .received
+            .contains(&(source_chain.to_string(), sequence))
+    }
+
+    /// Pacotes enviados pelo bloco da altura `height`
+    pub fn sent_packets(&self, height: u64) -> &[Packet] {
+        self.interchain
+            .sent
+            .get(&height)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Altura e prova de inclusão de um pacote enviado por esta cadeia
+    pub fn packet_proof(&self, packet: &Packet) -> Option<(u64, MerkleProof)> {
+        self.interchain.packet_proof(packet)
+    }
+
+    /// Cabeçalho desta cadeia na altura `height`, pronto para ser assinado
+    pub fn interchain_header(&self, height: u64) -> Option<ChainHeader> {
+        let block = self.chain.get(usize::try_from(height).ok()?)?;
+        Some(ChainHeader {
+            chain_id: self.interchain.chain_id.clone(),
+            height,
+            block_hash: block.hash.clone(),
+            previous_hash: block.previous_hash.clone(),
+            transactions_root: block.transactions_root(),
+            packets_root: self.interchain.packets_root(height),
+        })
+    }
+
+    /// Cabeçalho assinado por um único validador
+    pub fn sign_interchain_header(
+        &self,
+        height: u64,
+        public_key: &PublicKey,
+        secret_key: &SecretKey,
+    ) -> Option<SignedHeader> {
+        let mut signed = SignedHeader::new(self.interchain_header(height)?);
+        signed.sign(public_key, secret_key);
+        Some(signed)
+    }
+
+    /// ID do token nativo que o pacote devolve, quando a denominação é prefixada
+    /// com esta cadeia
+    pub(super) fn native_denom<'a>(&self, packet: &'a Packet) -> Option<&'a str> {
+        if self.interchain.chain_id.is_empty() {
+            return None;
+        }
+        packet
+            .denom
+            .strip_prefix(&format!("{}/", self.interchain.chain_id))
+    }
+
+    /// Clientes leves são registrados por admins. Envios exigem o cliente do
+    /// destino e passam pelas restrições de saída e regras do token; recebimentos, um cabeçalho aceito pelo cliente da origem que
+    /// prove o pacote, ainda não recebido, e o ID de voucher esperado
+    pub(super) fn check_interchain_operation(
+        &self,
+        operation: &Operation,
+    ) -> Result<(), TransactionError> {
+        let local = self.interchain.chain_id.as_str();
+        match &operation.kind {
+            OperationKind::RegisterChainClient { chain_id, .. } => {
+                if !self.is_account_key(&operation.author, &operation.public_key)
+                    || !self.has_role(&operation.author, AdminRole::Admin)
+                {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "{} não pode registrar o cliente leve de {}",
+                        operation.author, chain_id
+                    )));
+                }
+                if chain_id == local {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "{} é esta cadeia",
+                        chain_id
+                    )));
+                }
+            }
+            OperationKind::SendPacket {
+                destination_chain,
+                receiver,
+                token_id,
+                amount,
+            } => {
+                if local.is_empty() {
+                    return Err(TransactionError::InvalidParameter(
+                        "Cadeia sem identificador não envia pacotes".to_string(),
+                    ));
+                }
+                if !self.interchain.clients.contains_key(destination_chain) {
+                    return Err(InterchainError::UnknownChain(destination_chain.clone()).into());
+                }
+                if self.get_token(&token_id.to_string()).is_none() {
+                    return Err(TransactionError::TokenNaoEncontrado);
+                }
+                // O envio é uma saída como qualquer transferência: valem as
+                // restrições do autor e as regras do token para o destinatário
+                self.check_transfer_rules(*token_id, &operation.author, receiver, *amount)?;
+            }
+            OperationKind::ReceivePacket {
+                packet,
+                proof,
+                header,
+                voucher_token_id,
+            } => {
+                if local.is_empty() || packet.destination_chain != local {
+                    return Err(InterchainError::InvalidPacket(format!(
+                        "Pacote destinado a {}",
+                        packet.destination_chain
+                    ))
+                    .into());
+                }
+                if self.packet_received(&packet.source_chain, packet.sequence) {
+                    return Err(InterchainError::PacketReplayed {
+                        chain: packet.source_chain.clone(),
+                        sequence: packet.sequence,
+                    }
+                    .into());
+                }
+                let client = self
+                    .interchain_client(&packet.source_chain)
+                    .ok_or_else(|| InterchainError::UnknownChain(packet.source_chain.clone()))?;
+                client.check_header(header)?;
+                client.check_packet(packet, proof, &header.header)?;
+
+                if let Some(native) = self.native_denom(packet) {
+                    if voucher_token_id.is_some() {
+                        return Err(TransactionError::InvalidParameter(format!(
+                            "Token nativo {} não leva ID de voucher",
+                            native
+                        )));
+                    }
+                    let escrowed = self
+                        .get_token(native)
+                        .ok_or_else(|| InterchainError::TokenNotFound(native.to_string()))?
+                        .balance_of(&escrow_address(&packet.source_chain));
+                    if escrowed < packet.amount {
+                        return Err(InterchainError::InsufficientBalance.into());
+                    }
+                } else {
+                    let trace = voucher_trace(packet);
+                    let expected = match self.voucher_token(&trace) {
+                        Some(token_id) => token_id,
+                        None if !self.tokens.contains_key(&self.next_token_id.to_string()) => {
+                            self.next_token_id
+                        }
+                        None => {
+                            return Err(TransactionError::InvalidParameter(format!(
+                                "ID de token {} indisponível para o voucher {}",
+                                self.next_token_id, trace
+                            )))
+                        }
+                    };
+                    if *voucher_token_id != Some(expected) {
+                        return Err(TransactionError::InvalidParameter(format!(
+                            "Voucher {} usa o ID de token {}",
+                            trace, expected
+                        )));
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Verificações de admissão; no primeiro recebimento de um voucher, reserva o
+    /// ID do token local como a criação de tokens faz
+    pub(super) fn reserve_interchain_operation(
+        &mut self,
+        operation: &Operation,
+    ) -> Result<(), TransactionError> {
+        self.check_interchain_operation(operation)?;
+        if let OperationKind::ReceivePacket {
+            packet,
+            voucher_token_id: Some(token_id),
+            ..
+        } = &operation.kind
+        {
+            let trace = voucher_trace(packet);
+            if !self.interchain.vouchers.contains_key(&trace) {
+                self.interchain.vouchers.insert(trace, *token_id);
+                self.next_token_id += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reconfere a operação no bloco e move saldos, supply e o estado entre
+    /// cadeias. Pacotes enviados entram na raiz do cabeçalho da altura `height`
+    pub(super) fn apply_interchain_operation(
+        &mut self,
+        operation: &Operation,
+        height: u64,
+    ) -> Result<(), TransactionError> {
+        self.check_interchain_operation(operation)?;
+        match &operation.kind {
+            OperationKind::RegisterChainClient {
+                chain_id,
+                validators,
+                threshold,
+            } => {
+                let client =
+                    LightClient::new(chain_id.clone(), validators.clone(), *threshold as usize)?;
+                self.interchain.clients.insert(chain_id.clone(), client);
+            }
+            OperationKind::SendPacket {
+                destination_chain,
+                receiver,
+                token_id,
+                amount,
+            } => {
+                let key = token_id.to_string();
+                let trace = self.interchain.trace_of(*token_id).map(str::to_string);
+                let returning = trace
+                    .as_deref()
+                    .is_some_and(|t| t.starts_with(&format!("{}/", destination_chain)));
+
+                let token = self
+                    .tokens
+                    .get_mut(&key)
+                    .ok_or(TransactionError::TokenNaoEncontrado)?;
+                let remaining = token
+                    .balances
+                    .get(&operation.author)
+                    .copied()
+                    .unwrap_or(0)
+                    .checked_sub(*amount)
+                    .ok_or(TransactionError::InsufficientFunds)?;
+                if returning {
+                    token.balances.insert(operation.author.clone(), remaining);
+                    token.total_supply = token.total_supply.saturating_sub(*amount);
+                    self.record_supply_change(
+                        &key,
+                        SupplyChangeKind::Burn,
+                        *amount,
+                        &format!("voucher devolvido a {}", destination_chain),
+                    );
+                } else {
+                    let escrow = escrow_address(destination_chain);
+                    let escrowed = token
+                        .balances
+                        .get(&escrow)
+                        .copied()
+                        .unwrap_or(0)
+                        .checked_add(*amount)
+                        .ok_or(TransactionError::ValorInvalido)?;
+                    token.balances.insert(operation.author.clone(), remaining);
+                    token.balances.insert(escrow, escrowed);
+                }
+
+                let sequence = self
+                    .interchain
+                    .next_sequence
+                    .entry(destination_chain.clone())
+                    .or_insert(1);
+                let packet = Packet {
+                    sequence: *sequence,
+                    source_chain: self.interchain.chain_id.clone(),
+                    destination_chain: destination_chain.clone(),
+                    sender: operation.author.clone(),
+                    receiver: receiver.clone(),
+                    denom: trace.unwrap_or(key),
+                    amount: *amount,
+                };
+                *sequence += 1;
+                info!(
+                    "Pacote {}#{} enviado para {} na altura {}",
+                    packet.source_chain, packet.sequence, destination_chain, height
+                );
+                self.interchain.sent.entry(height).or_default().push(packet);
+            }
+            OperationKind::ReceivePacket {
+                packet,
+                header,
+                voucher_token_id,
+                ..
+            } => {
+                // A verificação acima garante o ID nos vouchers e o prefixo desta
+                // cadeia nos tokens nativos que voltam
+                let escrow = escrow_address(&packet.source_chain);
+                let key = match voucher_token_id {
+                    Some(token_id) => token_id.to_string(),
+                    None => self.native_denom(packet).unwrap_or_default().to_string(),
+                };
+                // Créditos que estourariam o saldo ou o supply são recusados antes
+                // de qualquer efeito
+                let (supply, received) = self.tokens.get(&key).map_or((0, 0), |token| {
+                    (token.total_supply, token.balance_of(&packet.receiver))
+                });
+                let credited = received
+                    .checked_add(packet.amount)
+                    .ok_or(TransactionError::ValorInvalido)?;
+                let supply = match voucher_token_id {
+                    Some(_) => supply
+                        .checked_add(packet.amount)
+                        .ok_or(TransactionError::ValorInvalido)?,
+                    None => supply,
+                };
+                if let Some(token_id) = voucher_token_id.filter(|_| !self.tokens.contains_key(&key))
+                {
+                    let mut token = Token::new(
+                        voucher_trace(packet),
+                        format!("ibc/{}", packet.denom),
+                        0,
+                        escrow.clone(),
+                    )?;
+                    token.id = token_id;
+                    self.tokens.insert(key.clone(), token);
+                    self.record_supply_change(
+                        &key,
+                        SupplyChangeKind::Issue,
+                        0,
+                        "criação de voucher",
+                    );
+                }
+                if let Some(client) = self.interchain.clients.get_mut(&packet.source_chain) {
+                    client.update(header.clone())?;
+                }
+
+                let token = self
+                    .tokens
+                    .get_mut(&key)
+                    .ok_or(TransactionError::TokenNaoEncontrado)?;
+                if voucher_token_id.is_none() {
+                    let escrowed = token.balances.get(&escrow).copied().unwrap_or(0);
+                    token
+                        .balances
+                        .insert(escrow, escrowed.saturating_sub(packet.amount));
+                }
+                token.total_supply = supply;
+                token.balances.insert(packet.receiver.clone(), credited);
+                if voucher_token_id.is_some() {
+                    self.record_supply_change(
+                        &key,
+                        SupplyChangeKind::Mint,
+                        packet.amount,
+                        &format!("voucher recebido de {}", packet.source_chain),
+                    );
+                }
+
+                self.interchain
+                    .received
+                    .insert((packet.source_chain.clone(), packet.sequence));
+                info!(
+                    "Pacote {}#{} recebido: {} creditados a {}",
+                    packet.source_chain, packet.sequence, packet.amount, packet.receiver
+                );
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
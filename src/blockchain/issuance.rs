@@ -0,0 +1,99 @@
+use super::blockchain::Blockchain;
+use super::supply::SupplyChangeKind;
+use crate::error::TransactionError;
+use crate::transaction::{Operation, OperationKind};
+
+impl Blockchain {
+    /// Se as transferências públicas do token estão suspensas
+    pub fn is_token_paused(&self, token_id: u64) -> bool {
+        self.paused_tokens.contains(&token_id.to_string())
+    }
+
+    /// Emissão, queima e pausa exigem o dono ou um administrador do token,
+    /// assinando com a chave do próprio endereço
+    pub(super) fn check_issuance_operation(
+        &self,
+        operation: &Operation,
+    ) -> Result<(), TransactionError> {
+        let token_id = match &operation.kind {
+            OperationKind::MintTokens { token_id, .. }
+            | OperationKind::BurnTokens { token_id, .. }
+            | OperationKind::SetTokenPaused { token_id, .. } => *token_id,
+            _ => return Ok(()),
+        };
+
+        if self.get_token(&token_id.to_string()).is_none() {
+            return Err(TransactionError::TokenNaoEncontrado);
+        }
+        if !self.is_account_key(&operation.author, &operation.public_key)
+            || !self.can_manage_token(token_id, &operation.author)
+        {
+            return Err(TransactionError::InvalidParameter(format!(
+                "{} não pode emitir, queimar ou pausar o token {}",
+                operation.author, token_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reconfere a autorização no bloco e ajusta saldos, supply e histórico de
+    /// emissão. A queima falha se o autor não tiver o saldo público.
+    pub(super) fn apply_issuance_operation(
+        &mut self,
+        operation: &Operation,
+    ) -> Result<(), TransactionError> {
+        self.check_issuance_operation(operation)?;
+        match &operation.kind {
+            OperationKind::MintTokens {
+                token_id,
+                to,
+                amount,
+            } => {
+                let key = token_id.to_string();
+                let token = self
+                    .tokens
+                    .get_mut(&key)
+                    .ok_or(TransactionError::TokenNaoEncontrado)?;
+                let supply = token
+                    .total_supply
+                    .checked_add(*amount)
+                    .ok_or(TransactionError::ValorInvalido)?;
+                let balance = token
+                    .balances
+                    .get(to)
+                    .copied()
+                    .unwrap_or(0)
+                    .checked_add(*amount)
+                    .ok_or(TransactionError::ValorInvalido)?;
+                token.total_supply = supply;
+                token.balances.insert(to.clone(), balance);
+                self.record_supply_change(&key, SupplyChangeKind::Mint, *amount, "emissão");
+            }
+            OperationKind::BurnTokens { token_id, amount } => {
+                let key = token_id.to_string();
+                let token = self
+                    .tokens
+                    .get_mut(&key)
+                    .ok_or(TransactionError::TokenNaoEncontrado)?;
+                let balance = token.balances.get(&operation.author).copied().unwrap_or(0);
+                if balance < *amount {
+                    return Err(TransactionError::InsufficientFunds);
+                }
+                token
+                    .balances
+                    .insert(operation.author.clone(), balance - amount);
+                token.total_supply = token.total_supply.saturating_sub(*amount);
+                self.record_supply_change(&key, SupplyChangeKind::Burn, *amount, "queima");
+            }
+            OperationKind::SetTokenPaused { token_id, paused } => {
+                if *paused {
+                    self.paused_tokens.insert(token_id.to_string());
+                } else {
+                    self.paused_tokens.remove(&token_id.to_string());
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use super::blockchain::{Address, Blockchain};
+use crate::error::TransactionError;
+use crate::transaction::{Operation, OperationKind, TransactionVerifier};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Registro de uma troca de chave, pelo próprio dono (`RotateKey`) ou por
+/// recuperação; fica no histórico para verificar blocos antigos
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct KeyRotation {
+    pub public_key: Vec<u8>,
+    pub height: u64,
+    /// Primeiro nonce da conta assinado com `public_key`
+    #[serde(default)]
+    pub first_nonce: u64,
+    /// Guardiões que aprovaram a troca; vazio quando o dono a fez com a chave antiga
+    pub approved_by: Vec<Address>,
+}
+
+impl Blockchain {
+    /// Trocas de chave de `address`, da mais antiga à vigente
+    pub fn key_rotations(&self, address: &str) -> &[KeyRotation] {
+        self.key_rotations
+            .get(address)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Chave vigente de uma conta que já trocou de chave
+    pub fn rotated_key(&self, address: &str) -> Option<&[u8]> {
+        self.key_rotations(address)
+            .last()
+            .map(|rotation| rotation.public_key.as_slice())
+    }
+
+    /// Chave que assinava pela conta no `nonce` dado; `None` enquanto valia a
+    /// chave original, da qual o endereço é derivado
+    pub fn key_for_nonce(&self, address: &str, nonce: u64) -> Option<&[u8]> {
+        self.key_rotations(address)
+            .iter()
+            .rev()
+            .find(|rotation| rotation.first_nonce <= nonce)
+            .map(|rotation| rotation.public_key.as_slice())
+    }
+
+    /// Chave registrada para o endereço em `public_keys`, se houver
+    fn registered_key(&self, address: &str) -> Option<&[u8]> {
+        self.public_keys.get(address).map(Vec::as_slice)
+    }
+
+    /// Recusa chaves que não pertencem à conta ou que uma troca já substituiu
+    pub(super) fn check_account_key(
+        &self,
+        address: &str,
+        public_key: &[u8],
+    ) -> Result<(), TransactionError> {
+        TransactionVerifier.verify_account_key(
+            address,
+            public_key,
+            self.rotated_key(address),
+            self.registered_key(address),
+        )
+    }
+
+    /// Confere a chave de uma transação ou operação já confirmada contra a chave
+    /// em vigor no nonce dela, e não contra a atual
+    pub fn check_historical_key(
+        &self,
+        address: &str,
+        public_key: &[u8],
+        nonce: u64,
+    ) -> Result<(), TransactionError> {
+        TransactionVerifier.verify_account_key(
+            address,
+            public_key,
+            self.key_for_nonce(address, nonce),
+            self.registered_key(address),
+        )
+    }
+
+    /// Se `public_key` é a chave da conta: a registrada na última troca ou, sem
+    /// troca, a que deriva o endereço ou a registrada em `public_keys`
+    pub fn is_account_key(&self, address: &str, public_key: &[u8]) -> bool {
+        self.check_account_key(address, public_key).is_ok()
+    }
+
+    /// A rotação é assinada pela chave vigente da conta
+    pub(super) fn check_key_rotation(&self, operation: &Operation) -> Result<(), TransactionError> {
+        if !self.is_account_key(&operation.author, &operation.public_key) {
+            return Err(TransactionError::InvalidParameter(format!(
+                "Rotação de {} precisa ser assinada pela chave vigente",
+                operation.author
+            )));
+        }
+        Ok(())
+    }
+
+    /// Registra a chave nova; ela vale a partir do nonce seguinte ao da rotação
+    pub(super) fn apply_key_rotation(
+        &mut self,
+        operation: &Operation,
+        height: u64,
+    ) -> Result<(), TransactionError> {
+        self.check_key_rotation(operation)?;
+        if let OperationKind::RotateKey { new_public_key } = &operation.kind {
+            self.key_rotations
+                .entry(operation.author.clone())
+                .or_default()
+                .push(KeyRotation {
+                    public_key: new_public_key.clone(),
+                    height,
+                    first_nonce: operation.nonce + 1,
+                    approved_by: Vec::new(),
+                });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+use super::block::Block;
+use crate::smart_contract::SmartContract;
+use crate::transaction::{Operation, SecureTransaction, Transaction};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+pub type MerkleHash = [u8; 32];
+
+/// Raiz de uma árvore sem folhas
+pub const EMPTY_ROOT: MerkleHash = [0u8; 32];
+
+// Prefixos de domínio: impedem que um nó interno seja apresentado como folha
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+pub fn leaf_hash(data: &[u8]) -> MerkleHash {
+    let mut hasher = Sha3_256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &MerkleHash, right: &MerkleHash) -> MerkleHash {
+    let mut hasher = Sha3_256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Raiz Merkle das folhas. Um nó sem par sobe de nível sem ser duplicado,
+/// de modo que listas diferentes nunca produzem a mesma raiz.
+pub fn merkle_root(leaves: &[MerkleHash]) -> MerkleHash {
+    if leaves.is_empty() {
+        return EMPTY_ROOT;
+    }
+
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => node_hash(left, right),
+                [single] => *single,
+                _ => unreachable!("chunks(2) produz no máximo dois elementos"),
+            })
+            .collect();
+    }
+    level[0]
+}
+
+/// Caminho de inclusão de uma folha até a raiz
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct MerkleProof {
+    pub leaf_index: usize,
+    pub leaf_count: usize,
+    /// Irmãos encontrados da folha até a raiz (níveis sem irmão são omitidos)
+    pub siblings: Vec<MerkleHash>,
+}
+
+impl MerkleProof {
+    pub fn build(leaves: &[MerkleHash], leaf_index: usize) -> Option<Self> {
+        if leaf_index >= leaves.len() {
+            return None;
+        }
+
+        let mut siblings = Vec::new();
+        let mut level = leaves.to_vec();
+        let mut index = leaf_index;
+        while level.len() > 1 {
+            let sibling = index ^ 1;
+            if sibling < level.len() {
+                siblings.push(level[sibling]);
+            }
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [single] => *single,
+                    _ => unreachable!("chunks(2) produz no máximo dois elementos"),
+                })
+                .collect();
+            index /= 2;
+        }
+
+        Some(MerkleProof {
+            leaf_index,
+            leaf_count: leaves.len(),
+            siblings,
+        })
+    }
+
+    /// Recalcula a raiz a partir da folha; `None` se o caminho for inconsistente
+    pub fn compute_root(&self, leaf: &MerkleHash) -> Option<MerkleHash> {
+        if self.leaf_index >= self.leaf_count {
+            return None;
+        }
+
+        let mut hash = *leaf;
+        let mut index = self.leaf_index;
+        let mut width = self.leaf_count;
+        let mut siblings = self.siblings.iter();
+        while width > 1 {
+            if index % 2 == 1 {
+                hash = node_hash(siblings.next()?, &hash);
+            } else if index + 1 < width {
+                hash = node_hash(&hash, siblings.next()?);
+            }
+            index /= 2;
+            width = width.div_ceil(2);
+        }
+
+        match siblings.next() {
+            Some(_) => None,
+            None => Some(hash),
+        }
+    }
+
+    pub fn verify(&self, leaf: &MerkleHash, root: &MerkleHash) -> bool {
+        self.compute_root(leaf).as_ref() == Some(root)
+    }
+}
+
+/// Folha de uma transação: cobre os campos da transação, mas não a assinatura,
+/// para que a prova continue válida após a poda de assinaturas
+pub fn transaction_leaf(tx: &SecureTransaction) -> MerkleHash {
+    let data = format!(
+        "{}:{}:{}:{}:{}",
+        tx.from, tx.to, tx.amount, tx.timestamp, tx.nonce
+    );
+    leaf_hash(data.as_bytes())
+}
+
+/// Folha de uma operação do corpo do bloco: o seu identificador, que cobre a
+/// assinatura
+pub fn operation_leaf(operation: &Operation) -> MerkleHash {
+    leaf_hash(operation.id().as_bytes())
+}
+
+/// Folha de uma transferência do corpo do bloco: o seu `txid`
+pub fn transfer_leaf(tx: &Transaction) -> MerkleHash {
+    leaf_hash(tx.txid().as_bytes())
+}
+
+/// Folha de um contrato: a codificação bincode do contrato inteiro
+pub fn contract_leaf(contract: &SmartContract) -> MerkleHash {
+    leaf_hash(&bincode::serialize(contract).unwrap_or_default())
+}
+
+impl Block {
+    pub fn transaction_leaves(&self) -> Vec<MerkleHash> {
+        self.transactions.iter().map(transaction_leaf).collect()
+    }
+
+    /// Raiz Merkle das transações do bloco
+    pub fn transactions_root(&self) -> MerkleHash {
+        merkle_root(&self.transaction_leaves())
+    }
+
+    /// Raiz Merkle dos contratos do bloco
+    pub fn contracts_root(&self) -> MerkleHash {
+        let leaves: Vec<_> = self.contracts.iter().map(contract_leaf).collect();
+        merkle_root(&leaves)
+    }
+
+    /// Folhas do corpo: operações e depois transferências, na ordem de execução
+    pub fn body_leaves(&self) -> Vec<MerkleHash> {
+        self.operations
+            .iter()
+            .map(operation_leaf)
+            .chain(self.transfers.iter().map(transfer_leaf))
+            .collect()
+    }
+
+    /// Raiz Merkle do corpo do bloco, coberta pela assinatura do proponente
+    pub fn body_root(&self) -> MerkleHash {
+        merkle_root(&self.body_leaves())
+    }
+
+    /// Prova de inclusão da transação na posição `position`
+    pub fn transaction_proof(&self, position: usize) -> Option<MerkleProof> {
+        MerkleProof::build(&self.transaction_leaves(), position)
+    }
+}
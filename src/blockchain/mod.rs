@@ -1,5 +1,84 @@
+mod account_guard;
+mod archive;
+mod assembly;
 mod block;
 mod blockchain;
+mod canonical;
+mod confidential;
+mod execution;
+mod export;
+mod fees;
+mod finality;
+mod format;
+mod governance;
+mod halt;
+mod indexer;
+mod inspect;
+mod interchain;
+mod issuance;
+mod key_rotation;
+pub mod merkle;
+mod names;
+mod nonces;
+mod oracle;
+mod ownership;
+mod policies;
+mod pruning;
+mod quorum;
+mod read_snapshot;
+mod recovery;
+mod rejection;
+mod reorg;
+mod roles;
+mod shared;
+mod simulation;
+mod spv;
+mod sqlite;
+mod staking;
+mod state;
+mod status;
+mod streaming;
+mod supply;
+mod throttle;
+mod upgrade;
 mod validacao;
+mod validation_context;
+mod vesting;
 
+pub use account_guard::{AccountGuard, Scheduled};
+pub use archive::{ArchiveStore, HistoricalState, StorageMode, TransactionReceipt};
+pub use assembly::{assemble_block, verify_assembly, MempoolSelection};
+pub use block::{Block, BLOCK_ENCODING_VERSION};
 pub use blockchain::Blockchain;
+pub use execution::{ExecutionReceipt, ExecutionSummary};
+pub use export::ExportSignature;
+pub use finality::Finality;
+pub use format::BLOCKCHAIN_FORMAT_VERSION;
+pub use governance::{Proposal, ProposalStatus, ProposalTally, GOVERNANCE_QUORUM_PERCENT};
+pub use halt::HaltRecord;
+pub use indexer::{TransactionIndex, TransactionRecord, TransactionStatus};
+pub use inspect::ReadOnlyBlockchain;
+pub use key_rotation::KeyRotation;
+pub use merkle::{merkle_root, MerkleHash, MerkleProof};
+pub use names::{NameRecord, NAME_FEE_PER_BLOCK};
+pub use nonces::{AccountNonce, NonceRepair};
+pub use oracle::{
+    OracleAggregate, OracleFeed, OraclePoint, ORACLE_MAX_AGE_BLOCKS, ORACLE_MAX_DEVIATION_PERCENT,
+    ORACLE_MIN_SOURCES,
+};
+pub use pruning::{signatures_digest, CheckpointAttestation, SignatureArchive};
+pub use quorum::{validator_set_digest, QuorumCheckpoint};
+pub use read_snapshot::ReadSnapshot;
+pub use recovery::{AccountRecovery, PendingRecovery};
+pub use rejection::RejectionReason;
+pub use shared::SharedBlockchain;
+pub use simulation::{SimulationResult, SimulationStage, SimulationStatus};
+pub use spv::{transaction_id, BlockHeader, InclusionProof};
+pub use status::{LifecycleState, TransactionLifecycle, TransactionStatusStore};
+pub use streaming::DetachedBlock;
+pub use supply::{SupplyChange, SupplyChangeKind, SupplyReport};
+pub use throttle::SubmissionThrottle;
+pub use upgrade::UpgradeSignal;
+pub use validation_context::{
+    CacheStats, TokenMetadata, ValidationContext, DEFAULT_CACHE_CAPACITY,
+};
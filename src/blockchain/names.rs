@@ -0,0 +1,171 @@
+use super::blockchain::{Address, Blockchain};
+use super::supply::SupplyChangeKind;
+use crate::error::TransactionError;
+use crate::transaction::operation::MAX_NAME_REGISTRATION_BLOCKS;
+use crate::transaction::{Operation, OperationKind};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Taxa de registro e de renovação, em unidades do token nativo por bloco de
+/// validade; o valor é queimado
+pub const NAME_FEE_PER_BLOCK: u64 = 1;
+
+/// Registro de um nome: quem o resolve e até quando
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct NameRecord {
+    pub owner: Address,
+    pub registered_at: u64,
+    /// Primeiro bloco em que o nome já não vale
+    pub expires_at: u64,
+}
+
+impl NameRecord {
+    pub fn is_active(&self, height: u64) -> bool {
+        height < self.expires_at
+    }
+}
+
+impl Blockchain {
+    pub fn name_record(&self, name: &str) -> Option<&NameRecord> {
+        self.names.get(name)
+    }
+
+    /// Dono de um nome que ainda vale no próximo bloco
+    pub fn resolve_name(&self, name: &str) -> Option<&Address> {
+        self.name_record(name)
+            .filter(|record| record.is_active(self.height()))
+            .map(|record| &record.owner)
+    }
+
+    /// Nomes ainda válidos de `owner`, em ordem alfabética
+    pub fn names_of(&self, owner: &str) -> Vec<&str> {
+        let height = self.height();
+        self.names
+            .iter()
+            .filter(|(_, record)| record.owner == owner && record.is_active(height))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Taxa de registro ou renovação por `blocks` blocos
+    pub fn name_fee(blocks: u64) -> u64 {
+        blocks.saturating_mul(NAME_FEE_PER_BLOCK)
+    }
+
+    /// O autor assina com a chave do próprio endereço e tem saldo nativo para a
+    /// taxa. Registro só de nome livre ou expirado; renovação e transferência só
+    /// pelo dono, antes da expiração
+    pub(super) fn check_name_operation(
+        &self,
+        operation: &Operation,
+        height: u64,
+    ) -> Result<(), TransactionError> {
+        if !self.is_account_key(&operation.author, &operation.public_key) {
+            return Err(TransactionError::InvalidParameter(format!(
+                "Chave da operação não corresponde a {}",
+                operation.author
+            )));
+        }
+        match &operation.kind {
+            OperationKind::RegisterName { name, blocks } => {
+                if let Some(record) = self.names.get(name).filter(|r| r.is_active(height)) {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "Nome {} registrado até o bloco {}",
+                        name, record.expires_at
+                    )));
+                }
+                self.check_name_fee(&operation.author, *blocks)
+            }
+            OperationKind::RenewName { name, blocks } => {
+                let record = self.owned_name(name, &operation.author, height)?;
+                if record.expires_at - height + blocks > MAX_NAME_REGISTRATION_BLOCKS {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "Renovação deixaria {} válido por mais de {} blocos",
+                        name, MAX_NAME_REGISTRATION_BLOCKS
+                    )));
+                }
+                self.check_name_fee(&operation.author, *blocks)
+            }
+            OperationKind::TransferName { name, .. } => {
+                self.owned_name(name, &operation.author, height).map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn owned_name(
+        &self,
+        name: &str,
+        author: &str,
+        height: u64,
+    ) -> Result<&NameRecord, TransactionError> {
+        match self.names.get(name) {
+            Some(record) if record.is_active(height) && record.owner == author => Ok(record),
+            _ => Err(TransactionError::InvalidParameter(format!(
+                "{} não é dono do nome {} ou ele expirou",
+                author, name
+            ))),
+        }
+    }
+
+    fn check_name_fee(&self, author: &str, blocks: u64) -> Result<(), TransactionError> {
+        let balance = self
+            .get_token("0")
+            .map(|token| token.balance_of(&author.to_string()))
+            .unwrap_or(0);
+        if balance < Self::name_fee(blocks) {
+            return Err(TransactionError::InsufficientFunds);
+        }
+        Ok(())
+    }
+
+    pub(super) fn apply_name_operation(
+        &mut self,
+        operation: &Operation,
+        height: u64,
+    ) -> Result<(), TransactionError> {
+        self.check_name_operation(operation, height)?;
+        match &operation.kind {
+            OperationKind::RegisterName { name, blocks } => {
+                self.burn_name_fee(&operation.author, *blocks)?;
+                self.names.insert(
+                    name.clone(),
+                    NameRecord {
+                        owner: operation.author.clone(),
+                        registered_at: height,
+                        expires_at: height + blocks,
+                    },
+                );
+            }
+            OperationKind::RenewName { name, blocks } => {
+                self.burn_name_fee(&operation.author, *blocks)?;
+                if let Some(record) = self.names.get_mut(name) {
+                    record.expires_at += blocks;
+                }
+            }
+            OperationKind::TransferName { name, to } => {
+                if let Some(record) = self.names.get_mut(name) {
+                    record.owner = to.clone();
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn burn_name_fee(&mut self, author: &str, blocks: u64) -> Result<(), TransactionError> {
+        let fee = Self::name_fee(blocks);
+        let token = self
+            .tokens
+            .get_mut("0")
+            .ok_or(TransactionError::TokenNaoEncontrado)?;
+        let balance = token.balances.get(author).copied().unwrap_or(0);
+        if balance < fee {
+            return Err(TransactionError::InsufficientFunds);
+        }
+        token.balances.insert(author.to_string(), balance - fee);
+        token.total_supply = token.total_supply.saturating_sub(fee);
+        self.record_supply_change("0", SupplyChangeKind::Burn, fee, "taxa de nome");
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use super::blockchain::Blockchain;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Nonces de uma conta, para carteiras montarem a próxima transação sem adivinhar.
+///
+/// Transferências e operações compartilham a mesma sequência, e o nonce é
+/// consumido na admissão ao mempool: um item descartado ao fechar o bloco (saldo
+/// insuficiente, por exemplo) não devolve o nonce.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct AccountNonce {
+    pub address: String,
+    /// Último nonce consumido por blocos já fechados
+    pub committed: u64,
+    /// Último nonce admitido, contando o mempool
+    pub pending: u64,
+    /// Nonces aguardando bloco, em ordem crescente
+    pub pending_nonces: Vec<u64>,
+    /// Nonce que o próximo item deve usar; `None` quando a sequência se esgotou
+    pub next: Option<u64>,
+    /// Presente quando há nonces consumidos sem item correspondente nos blocos
+    pub repair: Option<NonceRepair>,
+}
+
+/// Itens perdidos de uma conta e como reenviá-los
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct NonceRepair {
+    /// Nonces consumidos por itens descartados ao fechar o bloco. Não podem ser
+    /// reutilizados; os itens precisam ser assinados de novo com nonces novos.
+    pub missing: Vec<u64>,
+    /// Nonce do primeiro item reenviado, seguido dos consecutivos
+    pub resubmit_from: u64,
+}
+
+impl Blockchain {
+    pub fn account_nonce(&self, address: &str) -> AccountNonce {
+        let pending = self.nonces.get(address).copied().unwrap_or(0);
+        let mut pending_nonces: Vec<u64> = self
+            .pending_transactions
+            .iter()
+            .filter(|tx| tx.from == address)
+            .map(|tx| tx.nonce)
+            .chain(
+                self.pending_operations
+                    .iter()
+                    .filter(|operation| operation.author == address)
+                    .map(|operation| operation.nonce),
+            )
+            .collect();
+        pending_nonces.sort_unstable();
+        // A admissão exige nonces consecutivos, então os pendentes são o final
+        // da sequência
+        let committed = pending.saturating_sub(pending_nonces.len() as u64);
+
+        let included: BTreeSet<u64> = self
+            .index
+            .for_address(address)
+            .filter(|record| record.from == address)
+            .map(|record| record.nonce)
+            .chain(
+                self.operations
+                    .iter()
+                    .filter(|operation| operation.author == address)
+                    .map(|operation| operation.nonce),
+            )
+            .collect();
+        let missing: Vec<u64> = (1..=committed)
+            .filter(|nonce| !included.contains(nonce))
+            .collect();
+
+        let next = pending.checked_add(1);
+        let repair = match next {
+            Some(resubmit_from) if !missing.is_empty() => Some(NonceRepair {
+                missing,
+                resubmit_from,
+            }),
+            _ => None,
+        };
+
+        AccountNonce {
+            address: address.to_string(),
+            committed,
+            pending,
+            pending_nonces,
+            next,
+            repair,
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use super::blockchain::{Address, Blockchain};
+use crate::error::TransactionError;
+use crate::rbac::AdminRole;
+use crate::transaction::{Operation, OperationKind};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Blocos em que um ponto segue entrando na agregação depois de confirmado
+pub const ORACLE_MAX_AGE_BLOCKS: u64 = 10;
+/// Pontos recentes de alimentadores distintos exigidos para atualizar o valor
+pub const ORACLE_MIN_SOURCES: usize = 3;
+/// Desvio máximo em relação à mediana, em porcentagem, antes de o ponto ser
+/// descartado como outlier
+pub const ORACLE_MAX_DEVIATION_PERCENT: u64 = 10;
+
+/// Último valor enviado por um alimentador
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct OraclePoint {
+    pub value: u64,
+    pub height: u64,
+}
+
+/// Valor agregado de um oráculo, o que os contratos leem
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct OracleAggregate {
+    /// Mediana dos pontos que sobraram após descartar os outliers
+    pub value: u64,
+    pub height: u64,
+    pub sources: Vec<Address>,
+    pub outliers: Vec<Address>,
+}
+
+/// Alimentadores habilitados, seus pontos e o último valor agregado de um oráculo
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct OracleFeed {
+    pub feeders: BTreeSet<Address>,
+    pub points: BTreeMap<Address, OraclePoint>,
+    pub aggregate: Option<OracleAggregate>,
+}
+
+impl OracleFeed {
+    /// Agrega os pontos recentes no bloco em `height`: com ao menos
+    /// `ORACLE_MIN_SOURCES` alimentadores, descarta os que se afastam da mediana
+    /// mais que `ORACLE_MAX_DEVIATION_PERCENT` e toma a mediana do restante.
+    /// Sem pontos suficientes, o valor anterior é mantido
+    fn aggregate_at(&mut self, height: u64) {
+        let fresh: Vec<(&Address, u64)> = self
+            .points
+            .iter()
+            .filter(|(_, point)| point.height.saturating_add(ORACLE_MAX_AGE_BLOCKS) >= height)
+            .map(|(feeder, point)| (feeder, point.value))
+            .collect();
+        if fresh.len() < ORACLE_MIN_SOURCES {
+            return;
+        }
+
+        let center = median(fresh.iter().map(|(_, value)| *value).collect());
+        let (sources, outliers): (Vec<_>, Vec<_>) = fresh
+            .into_iter()
+            .partition(|(_, value)| !is_outlier(*value, center));
+        if sources.is_empty() {
+            return;
+        }
+        self.aggregate = Some(OracleAggregate {
+            value: median(sources.iter().map(|(_, value)| *value).collect()),
+            height,
+            sources: sources
+                .into_iter()
+                .map(|(feeder, _)| feeder.clone())
+                .collect(),
+            outliers: outliers
+                .into_iter()
+                .map(|(feeder, _)| feeder.clone())
+                .collect(),
+        });
+    }
+}
+
+/// Mediana; com quantidade par, a média (truncada) dos dois valores centrais
+fn median(mut values: Vec<u64>) -> u64 {
+    values.sort_unstable();
+    let middle = values.len() / 2;
+    if values.len() % 2 == 1 {
+        values[middle]
+    } else {
+        ((u128::from(values[middle - 1]) + u128::from(values[middle])) / 2) as u64
+    }
+}
+
+fn is_outlier(value: u64, center: u64) -> bool {
+    u128::from(value.abs_diff(center)) * 100
+        > u128::from(center) * u128::from(ORACLE_MAX_DEVIATION_PERCENT)
+}
+
+impl Blockchain {
+    pub fn oracle_feed(&self, feed_id: u64) -> Option<&OracleFeed> {
+        self.oracle_feeds.get(&feed_id)
+    }
+
+    /// Último valor agregado do oráculo, se já houve agregação
+    pub fn oracle_value(&self, feed_id: u64) -> Option<u64> {
+        self.oracle_feed(feed_id)?
+            .aggregate
+            .as_ref()
+            .map(|aggregate| aggregate.value)
+    }
+
+    /// Valores agregados de todos os oráculos, como os contratos os enxergam
+    pub fn oracle_values(&self) -> BTreeMap<u64, u64> {
+        self.oracle_feeds
+            .keys()
+            .filter_map(|feed_id| Some((*feed_id, self.oracle_value(*feed_id)?)))
+            .collect()
+    }
+
+    /// Alimentadores são habilitados por admins; pontos só vêm de alimentadores
+    /// habilitados no oráculo, assinando com a chave do próprio endereço
+    pub(super) fn check_oracle_operation(
+        &self,
+        operation: &Operation,
+    ) -> Result<(), TransactionError> {
+        let key_bound = self.is_account_key(&operation.author, &operation.public_key);
+        match &operation.kind {
+            OperationKind::SetOracleFeeder { feed_id, .. }
+                if !key_bound || !self.has_role(&operation.author, AdminRole::Admin) =>
+            {
+                Err(TransactionError::InvalidParameter(format!(
+                    "{} não pode habilitar alimentadores do oráculo {}",
+                    operation.author, feed_id
+                )))
+            }
+            OperationKind::SubmitOracleData { feed_id, .. }
+                if !key_bound
+                    || !self
+                        .oracle_feed(*feed_id)
+                        .is_some_and(|feed| feed.feeders.contains(&operation.author)) =>
+            {
+                Err(TransactionError::InvalidParameter(format!(
+                    "{} não é alimentador habilitado do oráculo {}",
+                    operation.author, feed_id
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(super) fn apply_oracle_operation(
+        &mut self,
+        operation: &Operation,
+        height: u64,
+    ) -> Result<(), TransactionError> {
+        self.check_oracle_operation(operation)?;
+        match &operation.kind {
+            OperationKind::SetOracleFeeder {
+                feed_id,
+                feeder,
+                allowed,
+            } => {
+                let feed = self.oracle_feeds.entry(*feed_id).or_default();
+                if *allowed {
+                    feed.feeders.insert(feeder.clone());
+                } else {
+                    // O ponto de quem sai não entra mais em agregações
+                    feed.feeders.remove(feeder);
+                    feed.points.remove(feeder);
+                }
+            }
+            OperationKind::SubmitOracleData { feed_id, value } => {
+                let feed = self.oracle_feeds.entry(*feed_id).or_default();
+                feed.points.insert(
+                    operation.author.clone(),
+                    OraclePoint {
+                        value: *value,
+                        height,
+                    },
+                );
+                feed.aggregate_at(height);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use super::blockchain::{Address, Blockchain};
+use crate::error::TransactionError;
+use crate::transaction::{Operation, OperationKind};
+
+impl Blockchain {
+    /// Dono atual do token (o criador, até a primeira troca aceita)
+    pub fn token_owner(&self, token_id: u64) -> Option<&str> {
+        self.get_token(&token_id.to_string())
+            .map(|token| token.creator.as_str())
+    }
+
+    /// Endereço indicado pelo dono e que ainda não aceitou
+    pub fn pending_token_owner(&self, token_id: u64) -> Option<&str> {
+        self.pending_token_owners
+            .get(&token_id.to_string())
+            .map(String::as_str)
+    }
+
+    /// Administradores secundários do token, em ordem
+    pub fn token_admins(&self, token_id: u64) -> Vec<Address> {
+        self.token_admins
+            .get(&token_id.to_string())
+            .map(|admins| admins.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Dono ou administrador secundário do token
+    pub fn can_manage_token(&self, token_id: u64, address: &str) -> bool {
+        self.token_owner(token_id) == Some(address)
+            || self
+                .token_admins
+                .get(&token_id.to_string())
+                .is_some_and(|admins| admins.contains(address))
+    }
+
+    /// Propostas e administradores exigem o dono; a aceitação, o endereço proposto.
+    /// Em todos os casos o autor assina com a chave do próprio endereço.
+    pub(super) fn check_ownership_operation(
+        &self,
+        operation: &Operation,
+    ) -> Result<(), TransactionError> {
+        let (token_id, allowed) = match &operation.kind {
+            OperationKind::ProposeTokenOwner { token_id, .. }
+            | OperationKind::SetTokenAdmin { token_id, .. } => {
+                (*token_id, self.token_owner(*token_id))
+            }
+            OperationKind::AcceptTokenOwner { token_id } => {
+                (*token_id, self.pending_token_owner(*token_id))
+            }
+            _ => return Ok(()),
+        };
+
+        if self.get_token(&token_id.to_string()).is_none() {
+            return Err(TransactionError::TokenNaoEncontrado);
+        }
+        if !self.is_account_key(&operation.author, &operation.public_key)
+            || allowed != Some(operation.author.as_str())
+        {
+            return Err(TransactionError::InvalidParameter(format!(
+                "{} não pode alterar a administração do token {}",
+                operation.author, token_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Confere de novo a autorização, que pode ter mudado entre a admissão e o
+    /// bloco. A troca de dono descarta a proposta e os administradores nomeados
+    /// pelo dono anterior.
+    pub(super) fn apply_ownership_operation(
+        &mut self,
+        operation: &Operation,
+    ) -> Result<(), TransactionError> {
+        self.check_ownership_operation(operation)?;
+        match &operation.kind {
+            OperationKind::ProposeTokenOwner {
+                token_id,
+                new_owner,
+            } => {
+                self.pending_token_owners
+                    .insert(token_id.to_string(), new_owner.clone());
+            }
+            OperationKind::AcceptTokenOwner { token_id } => {
+                let key = token_id.to_string();
+                let token = self
+                    .tokens
+                    .get_mut(&key)
+                    .ok_or(TransactionError::TokenNaoEncontrado)?;
+                token.creator = operation.author.clone();
+                self.pending_token_owners.remove(&key);
+                self.token_admins.remove(&key);
+            }
+            OperationKind::SetTokenAdmin {
+                token_id,
+                admin,
+                granted,
+            } => {
+                let key = token_id.to_string();
+                if *granted {
+                    self.token_admins
+                        .entry(key)
+                        .or_default()
+                        .insert(admin.clone());
+                } else if let Some(admins) = self.token_admins.get_mut(&key) {
+                    admins.remove(admin);
+                    if admins.is_empty() {
+                        self.token_admins.remove(&key);
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use super::blockchain::Blockchain;
+use crate::error::TransactionError;
+use crate::rbac::AdminRole;
+use crate::smart_contract::ContractLimits;
+use crate::token::policy::{self, TransferCheck};
+use crate::transaction::{Operation, OperationKind, Transaction, TransferRule};
+
+impl Blockchain {
+    /// Regras de transferência em vigor para o token
+    pub fn transfer_policy(&self, token_id: u64) -> &[TransferRule] {
+        self.transfer_policies
+            .get(&token_id.to_string())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn is_kyc_verified(&self, address: &str) -> bool {
+        self.kyc_verified.contains(address)
+    }
+
+    /// Políticas só são definidas pelo dono do token ou por seus administradores, e
+    /// as que delegam a um contrato exigem também o papel de operador e código
+    /// dentro de `ContractLimits`. Marcas de KYC exigem operador.
+    pub(super) fn check_policy_operation(
+        &self,
+        operation: &Operation,
+    ) -> Result<(), TransactionError> {
+        match &operation.kind {
+            OperationKind::SetTransferPolicy { token_id, rules } => {
+                if self.get_token(&token_id.to_string()).is_none() {
+                    return Err(TransactionError::TokenNaoEncontrado);
+                }
+                if !self.can_manage_token(*token_id, &operation.author) {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "Só o dono ou administradores do token {} definem sua política",
+                        token_id
+                    )));
+                }
+                let has_contract = rules
+                    .iter()
+                    .any(|rule| matches!(rule, TransferRule::Contract { .. }));
+                if has_contract && !self.is_key_bound_operator(operation) {
+                    return Err(TransactionError::InvalidParameter(
+                        "Políticas com contrato exigem o papel de operador".to_string(),
+                    ));
+                }
+                let limits = ContractLimits::default();
+                for rule in rules {
+                    if let TransferRule::Contract { code } = rule {
+                        limits.inspect(code).map_err(|e| {
+                            TransactionError::InvalidParameter(format!(
+                                "Contrato da política recusado: {}",
+                                e
+                            ))
+                        })?;
+                    }
+                }
+            }
+            OperationKind::SetKyc { .. } if !self.is_key_bound_operator(operation) => {
+                return Err(TransactionError::InvalidParameter(format!(
+                    "{} não pode marcar KYC",
+                    operation.author
+                )));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn is_key_bound_operator(&self, operation: &Operation) -> bool {
+        self.is_account_key(&operation.author, &operation.public_key)
+            && self.has_role(&operation.author, AdminRole::Operator)
+    }
+
+    pub(super) fn apply_policy_operation(&mut self, operation: &Operation) {
+        match &operation.kind {
+            OperationKind::SetTransferPolicy { token_id, rules } => {
+                if rules.is_empty() {
+                    self.transfer_policies.remove(&token_id.to_string());
+                } else {
+                    self.transfer_policies
+                        .insert(token_id.to_string(), rules.clone());
+                }
+            }
+            OperationKind::SetKyc { subject, verified } => {
+                if *verified {
+                    self.kyc_verified.insert(subject.clone());
+                } else {
+                    self.kyc_verified.remove(subject);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Avalia as regras do token contra a transferência, antes de qualquer efeito
+    pub(super) fn check_transfer_policies(&self, tx: &Transaction) -> Result<(), TransactionError> {
+        self.check_transfer_rules(tx.token_id, &tx.from, &tx.to, tx.amount)
+    }
+
+    /// Suspensão do token, restrições de saída de `from` e regras do token para
+    /// qualquer movimento de saldo público, seja transferência ou envio entre cadeias
+    pub(super) fn check_transfer_rules(
+        &self,
+        token_id: u64,
+        from: &str,
+        to: &str,
+        amount: u64,
+    ) -> Result<(), TransactionError> {
+        if self.is_token_paused(token_id) {
+            return Err(TransactionError::TokenPaused(token_id));
+        }
+        self.check_account_guard(from, to, self.height())?;
+        let rules = self.transfer_policy(token_id);
+        if rules.is_empty() {
+            return Ok(());
+        }
+        policy::evaluate_with_oracle(
+            rules,
+            &TransferCheck {
+                token_id,
+                from,
+                to,
+                amount,
+                from_kyc: self.is_kyc_verified(from),
+                to_kyc: self.is_kyc_verified(to),
+            },
+            &self.oracle_values(),
+        )
+    }
+}
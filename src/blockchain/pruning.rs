@@ -0,0 +1,325 @@
+use super::block::Block;
+use super::blockchain::Blockchain;
+use crate::error::Error;
+use crate::utils::address::derive_address;
+use pqcrypto_dilithium::dilithium5::{self, PublicKey, SecretKey};
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+/// Resumo das assinaturas das transações de um bloco (SHA3-256, hex).
+///
+/// Fica registrado quando as assinaturas são podadas, para que as cópias
+/// obtidas de um nó de arquivo possam ser conferidas antes de restauradas.
+pub fn signatures_digest(block: &Block) -> String {
+    let mut hasher = Sha3_256::new();
+    for tx in &block.transactions {
+        hasher.update((tx.signature.len() as u64).to_le_bytes());
+        hasher.update(&tx.signature);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Mensagem assinada pelos validadores para um checkpoint
+pub(super) fn checkpoint_payload(
+    height: u64,
+    block_hash: &str,
+    chain_digest: &str,
+    headers_root: &str,
+) -> Vec<u8> {
+    format!(
+        "checkpoint:{}:{}:{}:{}",
+        height, block_hash, chain_digest, headers_root
+    )
+    .into_bytes()
+}
+
+/// Atestado de checkpoint assinado por um validador.
+///
+/// Após a finalidade, substitui as assinaturas individuais das transações dos
+/// blocos até `height`: quem confia no validador não precisa reverificá-las.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CheckpointAttestation {
+    pub height: u64,
+    pub block_hash: String,
+    pub chain_digest: String,
+    /// Raiz Merkle (hex) dos cabeçalhos até `height`; ancora provas SPV
+    #[serde(default)]
+    pub headers_root: String,
+    pub validator_public_key: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+impl CheckpointAttestation {
+    /// Cria e assina o atestado para a cadeia local até `height`
+    pub fn create(
+        blockchain: &Blockchain,
+        height: u64,
+        public_key: &PublicKey,
+        secret_key: &SecretKey,
+    ) -> Result<Self, Error> {
+        let chain_digest = blockchain.chain_digest(height)?;
+        let headers_root = hex::encode(blockchain.headers_root(height)?);
+        let block_hash = blockchain
+            .chain
+            .iter()
+            .find(|block| block.index == height)
+            .map(|block| block.hash.clone())
+            .ok_or_else(|| Error::InvalidBlock(format!("Bloco {} não encontrado", height)))?;
+
+        let mut attestation = CheckpointAttestation {
+            height,
+            block_hash,
+            chain_digest,
+            headers_root,
+            validator_public_key: public_key.as_bytes().to_vec(),
+            signature: Vec::new(),
+        };
+        attestation.signature = dilithium5::detached_sign(&attestation.payload(), secret_key)
+            .as_bytes()
+            .to_vec();
+
+        Ok(attestation)
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        checkpoint_payload(
+            self.height,
+            &self.block_hash,
+            &self.chain_digest,
+            &self.headers_root,
+        )
+    }
+
+    /// Verifica a assinatura do atestado com a chave embutida
+    pub fn verify(&self) -> Result<(), Error> {
+        let public_key = PublicKey::from_bytes(&self.validator_public_key)
+            .map_err(|_| Error::InvalidPublicKey)?;
+        let signature = dilithium5::DetachedSignature::from_bytes(&self.signature)
+            .map_err(|_| Error::InvalidSignature)?;
+
+        dilithium5::verify_detached_signature(&signature, &self.payload(), &public_key)
+            .map_err(|_| Error::InvalidSignature)
+    }
+
+    /// Verifica também se o atestado foi emitido pelo validador esperado
+    pub fn verify_from(&self, validator: &PublicKey) -> Result<(), Error> {
+        if self.validator_public_key != validator.as_bytes() {
+            return Err(Error::InvalidPublicKey);
+        }
+        self.verify()
+    }
+}
+
+/// Fonte de assinaturas completas, tipicamente um nó de arquivo
+pub trait SignatureArchive {
+    /// Assinaturas das transações do bloco em `height`, na ordem do bloco
+    fn fetch_signatures(&self, height: u64) -> Result<Vec<Vec<u8>>, Error>;
+}
+
+impl SignatureArchive for Blockchain {
+    fn fetch_signatures(&self, height: u64) -> Result<Vec<Vec<u8>>, Error> {
+        if self.pruned_blocks.contains_key(&height) {
+            return Err(Error::Other(format!(
+                "Assinaturas do bloco {} foram podadas neste nó",
+                height
+            )));
+        }
+        if self.is_detached(height) {
+            return Err(Error::Other(format!(
+                "Bloco {} carregado só com o cabeçalho",
+                height
+            )));
+        }
+
+        self.chain
+            .iter()
+            .find(|block| block.index == height)
+            .map(|block| {
+                block
+                    .transactions
+                    .iter()
+                    .map(|tx| tx.signature.clone())
+                    .collect()
+            })
+            .ok_or_else(|| Error::InvalidBlock(format!("Bloco {} não encontrado", height)))
+    }
+}
+
+impl Blockchain {
+    /// Registra um checkpoint sem podar assinaturas, para que a cadeia possa
+    /// servir provas de inclusão (inclusive em nós de arquivo)
+    pub fn record_checkpoint(
+        &mut self,
+        attestation: CheckpointAttestation,
+        validator: &PublicKey,
+    ) -> Result<(), Error> {
+        self.check_checkpoint(&attestation, validator)?;
+        self.transaction_statuses
+            .finalize_through(attestation.height);
+        self.checkpoint = Some(attestation);
+        Ok(())
+    }
+
+    fn check_checkpoint(
+        &self,
+        attestation: &CheckpointAttestation,
+        validator: &PublicKey,
+    ) -> Result<(), Error> {
+        let registered = self
+            .validator_keys
+            .get(&derive_address(validator.as_bytes()))
+            .is_some_and(|key| key.as_slice() == validator.as_bytes());
+        if !registered {
+            return Err(Error::Unauthorized(
+                "Checkpoint atestado por chave que não é validadora da cadeia".to_string(),
+            ));
+        }
+        attestation.verify_from(validator)?;
+
+        if let Some(current) = &self.checkpoint {
+            if attestation.height <= current.height {
+                return Err(Error::StaleBlock);
+            }
+        }
+
+        if self.chain_digest(attestation.height)? != attestation.chain_digest
+            || hex::encode(self.headers_root(attestation.height)?) != attestation.headers_root
+        {
+            return Err(Error::InvalidBlock(
+                "Atestado de checkpoint não corresponde à cadeia local".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Indica se o checkpoint registrado foi emitido por um validador da cadeia e
+    /// ainda corresponde ao compromisso recalculado a partir dos blocos locais
+    pub(super) fn is_checkpoint_trusted(&self, checkpoint: &CheckpointAttestation) -> bool {
+        let issuer = derive_address(&checkpoint.validator_public_key);
+        let signed = self
+            .validator_keys
+            .get(&issuer)
+            .and_then(|key| PublicKey::from_bytes(key).ok())
+            .is_some_and(|key| checkpoint.verify_from(&key).is_ok());
+
+        signed
+            && self
+                .chain_digest(checkpoint.height)
+                .is_ok_and(|digest| digest == checkpoint.chain_digest)
+    }
+
+    /// Compromisso com a cadeia até `height`: hash de cada bloco e resumo de suas
+    /// assinaturas (o resumo registrado na poda, para blocos já podados)
+    fn chain_digest(&self, height: u64) -> Result<String, Error> {
+        let mut hasher = Sha3_256::new();
+        let mut last_index = None;
+        for block in self.chain.iter().take_while(|block| block.index <= height) {
+            let recorded = self.pruned_blocks.get(&block.index).or_else(|| {
+                self.detached_blocks
+                    .get(&block.index)
+                    .map(|detached| &detached.signatures_digest)
+            });
+            let digest = match recorded {
+                Some(digest) => digest.clone(),
+                None => signatures_digest(block),
+            };
+            hasher.update(block.hash.as_bytes());
+            hasher.update(digest.as_bytes());
+            last_index = Some(block.index);
+        }
+
+        if last_index != Some(height) {
+            return Err(Error::InvalidBlock(format!(
+                "Altura {} não encontrada na cadeia",
+                height
+            )));
+        }
+
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// Poda as assinaturas das transações dos blocos cobertos pelo atestado.
+    ///
+    /// O atestado precisa ser válido, emitido por `validator`, que deve ser um
+    /// validador registrado da cadeia, e corresponder à cadeia local. Retorna a quantidade de assinaturas removidas.
+    pub fn prune_signatures(
+        &mut self,
+        attestation: CheckpointAttestation,
+        validator: &PublicKey,
+    ) -> Result<usize, Error> {
+        if self.is_archive() {
+            return Err(Error::Other(
+                "Nó de arquivo retém todas as assinaturas".to_string(),
+            ));
+        }
+        self.check_checkpoint(&attestation, validator)?;
+
+        let mut pruned = 0;
+        for block in self
+            .chain
+            .iter_mut()
+            .take_while(|block| block.index <= attestation.height)
+        {
+            if self.pruned_blocks.contains_key(&block.index) {
+                continue;
+            }
+            self.pruned_blocks
+                .insert(block.index, signatures_digest(block));
+            for tx in &mut block.transactions {
+                tx.signature = Vec::new();
+                pruned += 1;
+            }
+        }
+
+        self.transaction_statuses
+            .finalize_through(attestation.height);
+        self.checkpoint = Some(attestation);
+        Ok(pruned)
+    }
+
+    /// Restaura as assinaturas de um bloco podado a partir de um arquivo,
+    /// conferindo-as com o resumo registrado na poda
+    pub fn restore_signatures(
+        &mut self,
+        height: u64,
+        archive: &dyn SignatureArchive,
+    ) -> Result<(), Error> {
+        let expected = self
+            .pruned_blocks
+            .get(&height)
+            .cloned()
+            .ok_or_else(|| Error::InvalidBlock(format!("Bloco {} não está podado", height)))?;
+
+        let signatures = archive.fetch_signatures(height)?;
+        let block = self
+            .chain
+            .iter_mut()
+            .find(|block| block.index == height)
+            .ok_or_else(|| Error::InvalidBlock(format!("Bloco {} não encontrado", height)))?;
+
+        if signatures.len() != block.transactions.len() {
+            return Err(Error::InvalidBlock(
+                "Quantidade de assinaturas diverge do bloco".to_string(),
+            ));
+        }
+
+        let mut candidate = block.clone();
+        for (tx, signature) in candidate.transactions.iter_mut().zip(signatures) {
+            tx.signature = signature;
+        }
+        if signatures_digest(&candidate) != expected {
+            return Err(Error::InvalidSignature);
+        }
+
+        *block = candidate;
+        self.pruned_blocks.remove(&height);
+        Ok(())
+    }
+
+    /// Indica se as assinaturas do bloco foram substituídas pelo checkpoint
+    pub fn is_pruned(&self, height: u64) -> bool {
+        self.pruned_blocks.contains_key(&height)
+    }
+}
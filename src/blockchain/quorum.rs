@@ -0,0 +1,171 @@
+use super::pruning::{checkpoint_payload, CheckpointAttestation};
+use crate::error::Error;
+use pqcrypto_dilithium::dilithium5::{self, PublicKey};
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+/// Resumo (SHA3-256, hex) das chaves do conjunto de validadores, na ordem usada
+/// pelo mapa de bits de `QuorumCheckpoint`
+pub fn validator_set_digest(validators: &[Vec<u8>]) -> String {
+    let mut hasher = Sha3_256::new();
+    for public_key in validators {
+        hasher.update((public_key.len() as u64).to_le_bytes());
+        hasher.update(public_key);
+    }
+    hex::encode(hasher.finalize())
+}
+
+fn bitmap_len(validators: usize) -> usize {
+    validators.div_ceil(8)
+}
+
+/// Checkpoint atestado por ao menos `threshold` de `n` validadores.
+///
+/// Guarda o checkpoint uma única vez, um mapa de bits com os signatários (bit `i`
+/// é o validador `i` do conjunto) e apenas as assinaturas, na ordem dos bits. As
+/// chaves não viajam no contêiner: clientes leves e a ponte já conhecem o conjunto
+/// de validadores, e `validator_set` amarra o mapa a essa ordem.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct QuorumCheckpoint {
+    pub height: u64,
+    pub block_hash: String,
+    pub chain_digest: String,
+    pub headers_root: String,
+    /// `validator_set_digest` do conjunto que assinou
+    pub validator_set: String,
+    pub signers: Vec<u8>,
+    pub signatures: Vec<Vec<u8>>,
+}
+
+impl QuorumCheckpoint {
+    /// Combina atestados individuais do mesmo checkpoint em um contêiner compacto.
+    ///
+    /// Cada atestado precisa ter assinatura válida e vir de um membro de
+    /// `validators`; atestados repetidos do mesmo validador contam uma vez.
+    pub fn aggregate(
+        attestations: &[CheckpointAttestation],
+        validators: &[Vec<u8>],
+        threshold: usize,
+    ) -> Result<Self, Error> {
+        let first = attestations
+            .first()
+            .ok_or_else(|| Error::InvalidInput("Nenhum atestado para combinar".to_string()))?;
+
+        let mut signatures: Vec<Option<Vec<u8>>> = vec![None; validators.len()];
+        for attestation in attestations {
+            if attestation.height != first.height
+                || attestation.block_hash != first.block_hash
+                || attestation.chain_digest != first.chain_digest
+                || attestation.headers_root != first.headers_root
+            {
+                return Err(Error::InvalidBlock(
+                    "Atestados de checkpoints diferentes".to_string(),
+                ));
+            }
+            attestation.verify()?;
+
+            let index = validators
+                .iter()
+                .position(|pk| *pk == attestation.validator_public_key)
+                .ok_or(Error::InvalidPublicKey)?;
+            signatures[index] = Some(attestation.signature.clone());
+        }
+
+        let mut signers = vec![0u8; bitmap_len(validators.len())];
+        for (index, _) in signatures.iter().enumerate().filter(|(_, s)| s.is_some()) {
+            signers[index / 8] |= 1 << (index % 8);
+        }
+
+        let quorum = QuorumCheckpoint {
+            height: first.height,
+            block_hash: first.block_hash.clone(),
+            chain_digest: first.chain_digest.clone(),
+            headers_root: first.headers_root.clone(),
+            validator_set: validator_set_digest(validators),
+            signers,
+            signatures: signatures.into_iter().flatten().collect(),
+        };
+        quorum.check_threshold(threshold)?;
+        Ok(quorum)
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        checkpoint_payload(
+            self.height,
+            &self.block_hash,
+            &self.chain_digest,
+            &self.headers_root,
+        )
+    }
+
+    /// Quantidade de validadores marcados no mapa de bits
+    pub fn signer_count(&self) -> usize {
+        self.signers
+            .iter()
+            .map(|byte| byte.count_ones() as usize)
+            .sum()
+    }
+
+    fn check_threshold(&self, threshold: usize) -> Result<(), Error> {
+        if threshold == 0 || self.signer_count() < threshold {
+            return Err(Error::Unauthorized(format!(
+                "Checkpoint {} atestado por {} validadores; requerido {}",
+                self.height,
+                self.signer_count(),
+                threshold
+            )));
+        }
+        Ok(())
+    }
+
+    /// Índices, em `validators`, dos signatários marcados no mapa de bits
+    pub fn signer_indices(&self) -> Vec<usize> {
+        (0..self.signers.len() * 8)
+            .filter(|index| self.signers[index / 8] & (1 << (index % 8)) != 0)
+            .collect()
+    }
+
+    /// Verifica o contêiner contra o conjunto de validadores conhecido: mapa de
+    /// bits coerente, ao menos `threshold` signatários e todas as assinaturas.
+    pub fn verify(&self, validators: &[Vec<u8>], threshold: usize) -> Result<(), Error> {
+        if self.validator_set != validator_set_digest(validators) {
+            return Err(Error::Unauthorized(
+                "Checkpoint assinado por outro conjunto de validadores".to_string(),
+            ));
+        }
+
+        let indices = self.signer_indices();
+        if self.signers.len() != bitmap_len(validators.len())
+            || indices
+                .last()
+                .is_some_and(|index| *index >= validators.len())
+            || indices.len() != self.signatures.len()
+        {
+            return Err(Error::InvalidFormat(
+                "Mapa de bits dos signatários inconsistente".to_string(),
+            ));
+        }
+        self.check_threshold(threshold)?;
+
+        let payload = self.payload();
+        for (index, signature) in indices.into_iter().zip(&self.signatures) {
+            let public_key =
+                PublicKey::from_bytes(&validators[index]).map_err(|_| Error::InvalidPublicKey)?;
+            let signature = dilithium5::DetachedSignature::from_bytes(signature)
+                .map_err(|_| Error::InvalidSignature)?;
+            dilithium5::verify_detached_signature(&signature, &payload, &public_key)
+                .map_err(|_| Error::InvalidSignature)?;
+        }
+        Ok(())
+    }
+
+    /// Indica se `checkpoint` atesta a mesma cadeia até a mesma altura
+    pub fn covers(&self, checkpoint: &CheckpointAttestation) -> bool {
+        self.height == checkpoint.height
+            && self.block_hash == checkpoint.block_hash
+            && self.chain_digest == checkpoint.chain_digest
+            && self.headers_root == checkpoint.headers_root
+    }
+}
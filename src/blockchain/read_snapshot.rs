@@ -0,0 +1,99 @@
+use super::blockchain::Blockchain;
+use crate::token::{FungibleToken, TokenMetadata};
+use crate::transaction::Transaction;
+use std::collections::HashMap;
+
+/// Token como visto pela foto: metadados, supply e saldos
+#[derive(Debug, Clone)]
+struct TokenView {
+    metadata: TokenMetadata,
+    total_supply: u64,
+    balances: HashMap<String, u64>,
+    paused: bool,
+}
+
+/// Foto imutável do estado consultado pelo RPC, tirada sob uma única trava de
+/// leitura.
+///
+/// Saldos, nonces, mempool e topo da cadeia são sempre do mesmo instante: um
+/// bloco aplicado durante a resposta não aparece pela metade. A foto é
+/// compartilhada entre leitores até a próxima escrita (ver
+/// `SharedBlockchain::read_snapshot`) e não segura a trava enquanto é usada.
+#[derive(Debug)]
+pub struct ReadSnapshot {
+    version: u64,
+    height: u64,
+    latest_hash: Option<String>,
+    tokens: HashMap<String, TokenView>,
+    nonces: HashMap<String, u64>,
+    pending_transactions: Vec<Transaction>,
+}
+
+impl ReadSnapshot {
+    pub(super) fn capture(blockchain: &Blockchain, version: u64) -> Self {
+        let tokens = blockchain
+            .tokens
+            .iter()
+            .map(|(id, token)| {
+                let view = TokenView {
+                    metadata: token.metadata(),
+                    total_supply: token.total_supply(),
+                    balances: token.balances.clone(),
+                    paused: blockchain.paused_tokens.contains(id),
+                };
+                (id.clone(), view)
+            })
+            .collect();
+        ReadSnapshot {
+            version,
+            height: blockchain.height(),
+            latest_hash: blockchain.chain.last().map(|block| block.hash.clone()),
+            tokens,
+            nonces: blockchain.nonces.clone(),
+            pending_transactions: blockchain.pending_transactions.clone(),
+        }
+    }
+
+    /// Número de escritas na blockchain até a foto
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn height(&self) -> u64 {
+        self.height
+    }
+
+    pub fn latest_hash(&self) -> Option<&str> {
+        self.latest_hash.as_deref()
+    }
+
+    pub fn balance_of(&self, token_id: &str, address: &str) -> Option<u64> {
+        let token = self.tokens.get(token_id)?;
+        Some(token.balances.get(address).copied().unwrap_or(0))
+    }
+
+    pub fn token_supply(&self, token_id: &str) -> Option<u64> {
+        self.tokens.get(token_id).map(|token| token.total_supply)
+    }
+
+    pub fn token_metadata(&self, token_id: &str) -> Option<&TokenMetadata> {
+        self.tokens.get(token_id).map(|token| &token.metadata)
+    }
+
+    /// Se as transferências do token estão suspensas; `false` para token inexistente
+    pub fn is_token_paused(&self, token_id: &str) -> bool {
+        self.tokens.get(token_id).is_some_and(|token| token.paused)
+    }
+
+    /// Último nonce admitido para o endereço, contando o mempool
+    pub fn nonce_of(&self, address: &str) -> u64 {
+        self.nonces.get(address).copied().unwrap_or(0)
+    }
+
+    /// Transações do mempool enviadas por ou para `address`, na ordem de admissão
+    pub fn pending_for<'a>(&'a self, address: &'a str) -> impl Iterator<Item = &'a Transaction> {
+        self.pending_transactions
+            .iter()
+            .filter(move |tx| tx.from == address || tx.to == address)
+    }
+}
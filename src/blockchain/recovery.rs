@@ -0,0 +1,187 @@
+use super::blockchain::{Address, Blockchain};
+use super::key_rotation::KeyRotation;
+use crate::error::TransactionError;
+use crate::transaction::{Operation, OperationKind};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Recuperação aprovada pelo limiar de guardiões, aguardando o prazo
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PendingRecovery {
+    pub new_public_key: Vec<u8>,
+    pub approved_by: Vec<Address>,
+    /// Primeiro bloco em que a recuperação pode ser concluída
+    pub executable_at: u64,
+}
+
+/// Esquema de recuperação de uma conta: guardiões, limiar, prazo e o andamento
+/// das aprovações
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct AccountRecovery {
+    pub guardians: BTreeSet<Address>,
+    pub threshold: u32,
+    pub delay_blocks: u64,
+    /// Chave nova aprovada por cada guardião
+    pub approvals: BTreeMap<Address, Vec<u8>>,
+    pub pending: Option<PendingRecovery>,
+}
+
+impl AccountRecovery {
+    /// Guardiões que aprovaram `public_key`
+    fn approvers_of(&self, public_key: &[u8]) -> Vec<Address> {
+        self.approvals
+            .iter()
+            .filter(|(guardian, key)| {
+                self.guardians.contains(*guardian) && key.as_slice() == public_key
+            })
+            .map(|(guardian, _)| guardian.clone())
+            .collect()
+    }
+}
+
+impl Blockchain {
+    pub fn account_recovery(&self, address: &str) -> Option<&AccountRecovery> {
+        self.recoveries.get(address)
+    }
+
+    /// Guardiões e cancelamento só pelo dono com a chave vigente; aprovação só por
+    /// guardião da conta; conclusão com a chave aprovada e depois do prazo
+    pub(super) fn check_recovery_operation(
+        &self,
+        operation: &Operation,
+        height: u64,
+    ) -> Result<(), TransactionError> {
+        let author = &operation.author;
+        let key_bound = self.is_account_key(author, &operation.public_key);
+        match &operation.kind {
+            OperationKind::SetGuardians { .. } | OperationKind::CancelRecovery if !key_bound => {
+                Err(TransactionError::InvalidParameter(format!(
+                    "Chave da operação não corresponde a {}",
+                    author
+                )))
+            }
+            OperationKind::CancelRecovery
+                if !self.account_recovery(author).is_some_and(|recovery| {
+                    recovery.pending.is_some() || !recovery.approvals.is_empty()
+                }) =>
+            {
+                Err(TransactionError::InvalidParameter(format!(
+                    "{} não tem recuperação em andamento",
+                    author
+                )))
+            }
+            OperationKind::ApproveRecovery { account, .. }
+                if !key_bound
+                    || !self
+                        .account_recovery(account)
+                        .is_some_and(|recovery| recovery.guardians.contains(author)) =>
+            {
+                Err(TransactionError::InvalidParameter(format!(
+                    "{} não é guardião de {}",
+                    author, account
+                )))
+            }
+            OperationKind::CompleteRecovery => {
+                let pending = self
+                    .account_recovery(author)
+                    .and_then(|recovery| recovery.pending.as_ref())
+                    .filter(|pending| pending.new_public_key == operation.public_key)
+                    .ok_or_else(|| {
+                        TransactionError::InvalidParameter(format!(
+                            "Nenhuma recuperação de {} aprovada para esta chave",
+                            author
+                        ))
+                    })?;
+                if height < pending.executable_at {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "Recuperação de {} só pode ser concluída a partir do bloco {}",
+                        author, pending.executable_at
+                    )));
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(super) fn apply_recovery_operation(
+        &mut self,
+        operation: &Operation,
+        height: u64,
+    ) -> Result<(), TransactionError> {
+        self.check_recovery_operation(operation, height)?;
+        let author = &operation.author;
+        match &operation.kind {
+            OperationKind::SetGuardians {
+                guardians,
+                threshold,
+                delay_blocks,
+            } => {
+                if guardians.is_empty() {
+                    self.recoveries.remove(author);
+                } else {
+                    // Aprovações feitas sob o esquema anterior não valem para o novo
+                    self.recoveries.insert(
+                        author.clone(),
+                        AccountRecovery {
+                            guardians: guardians.iter().cloned().collect(),
+                            threshold: *threshold,
+                            delay_blocks: *delay_blocks,
+                            ..AccountRecovery::default()
+                        },
+                    );
+                }
+            }
+            OperationKind::ApproveRecovery {
+                account,
+                new_public_key,
+            } => {
+                if let Some(recovery) = self.recoveries.get_mut(account) {
+                    recovery
+                        .approvals
+                        .insert(author.clone(), new_public_key.clone());
+                    let approvers = recovery.approvers_of(new_public_key);
+                    let already_pending = recovery
+                        .pending
+                        .as_ref()
+                        .is_some_and(|pending| &pending.new_public_key == new_public_key);
+                    if approvers.len() >= recovery.threshold as usize && !already_pending {
+                        recovery.pending = Some(PendingRecovery {
+                            new_public_key: new_public_key.clone(),
+                            approved_by: approvers,
+                            executable_at: height.saturating_add(recovery.delay_blocks),
+                        });
+                    }
+                }
+            }
+            OperationKind::CancelRecovery => {
+                if let Some(recovery) = self.recoveries.get_mut(author) {
+                    recovery.approvals.clear();
+                    recovery.pending = None;
+                }
+            }
+            OperationKind::CompleteRecovery => {
+                let pending = self
+                    .recoveries
+                    .get_mut(author)
+                    .and_then(|recovery| {
+                        recovery.approvals.clear();
+                        recovery.pending.take()
+                    })
+                    .ok_or(TransactionError::InvalidTransaction)?;
+                self.key_rotations
+                    .entry(author.clone())
+                    .or_default()
+                    .push(KeyRotation {
+                        public_key: pending.new_public_key,
+                        height,
+                        first_nonce: operation.nonce,
+                        approved_by: pending.approved_by,
+                    });
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
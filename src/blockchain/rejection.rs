@@ -0,0 +1,137 @@
+use super::blockchain::Blockchain;
+use crate::error::{ErrorCategory, ErrorCode, TransactionError};
+use crate::transaction::Transaction;
+use log::debug;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Motivo estruturado da rejeição de uma transação, devolvido a quem a submeteu
+/// e guardado no índice para depuração pelo operador
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct RejectionReason {
+    pub txid: String,
+    pub from: String,
+    /// Código estável do erro (ver `ErrorCode`)
+    pub code: u32,
+    pub category: ErrorCategory,
+    /// Se reenviar a mesma transação mais tarde pode ter sucesso
+    pub retryable: bool,
+    pub message: String,
+    /// Campo da transação que provocou a rejeição, quando é possível apontá-lo
+    pub field: Option<String>,
+    /// Valor ou faixa que o nó aceitaria no campo
+    pub expected: Option<String>,
+    /// Valor encontrado no campo
+    pub actual: Option<String>,
+    /// Segundos desde a época, no relógio do nó
+    pub rejected_at: i64,
+}
+
+impl Blockchain {
+    /// Descreve a rejeição de `tx` por `error` contra o estado atual: o campo
+    /// culpado e, quando o estado permite, o valor esperado e o encontrado
+    pub fn rejection_reason(&self, tx: &Transaction, error: &TransactionError) -> RejectionReason {
+        let limits = &self.limits;
+        let (field, expected, actual) = match error {
+            TransactionError::NonceInvalido | TransactionError::NonceReused => {
+                let current = self.nonces.get(&tx.from).copied().unwrap_or(0);
+                (
+                    Some("nonce"),
+                    Some((current + 1).to_string()),
+                    Some(tx.nonce.to_string()),
+                )
+            }
+            TransactionError::NonceOverflow => (Some("nonce"), None, Some(tx.nonce.to_string())),
+            TransactionError::TransacaoRepetida => (Some("txid"), None, Some(tx.txid())),
+            TransactionError::SignatureSizeExceeded => (
+                Some("signature"),
+                Some(format!("<= {}", limits.max_signature_size)),
+                Some(tx.signature.len().to_string()),
+            ),
+            TransactionError::DataSizeExceeded => (
+                Some("encoded_size"),
+                Some(format!("<= {}", limits.max_transaction_size)),
+                Some(tx.encoded_size().to_string()),
+            ),
+            TransactionError::TooManyPending(limit) => {
+                let pending = self
+                    .pending_transactions
+                    .iter()
+                    .filter(|p| p.from == tx.from)
+                    .count();
+                (
+                    Some("from"),
+                    Some(format!("< {}", limit)),
+                    Some(pending.to_string()),
+                )
+            }
+            TransactionError::SubmissionRateExceeded(limit) => {
+                let recent = self
+                    .throttle
+                    .recent_submissions(&tx.from, self.now_secs(), limits);
+                (
+                    Some("from"),
+                    Some(format!("< {}", limit)),
+                    Some(recent.to_string()),
+                )
+            }
+            TransactionError::InsufficientFunds => {
+                let balance = self
+                    .tokens
+                    .get(&tx.token_id.to_string())
+                    .and_then(|token| token.balances.get(&tx.from).copied())
+                    .unwrap_or(0);
+                (
+                    Some("amount"),
+                    Some(format!("<= {}", balance)),
+                    Some(tx.amount.to_string()),
+                )
+            }
+            TransactionError::ValorInvalido => (Some("amount"), None, Some(tx.amount.to_string())),
+            TransactionError::FeeBelowFloor(floor) => (
+                Some("amount"),
+                Some(format!("taxa >= {}", floor)),
+                Some(format!("taxa {}", Self::transfer_fee(tx.amount))),
+            ),
+            TransactionError::TokenNaoEncontrado | TransactionError::TokenPaused(_) => {
+                (Some("token_id"), None, Some(tx.token_id.to_string()))
+            }
+            TransactionError::InvalidTimestamp(_) | TransactionError::TimestampInvalid => {
+                (Some("timestamp"), None, Some(tx.timestamp.to_string()))
+            }
+            TransactionError::InvalidSignature(_) | TransactionError::InvalidSignatures(_) => {
+                (Some("signature"), None, None)
+            }
+            TransactionError::InvalidPublicKey(_) => (Some("public_key"), None, None),
+            _ => (None, None, None),
+        };
+
+        RejectionReason {
+            txid: tx.txid(),
+            from: tx.from.clone(),
+            code: error.code(),
+            category: error.category(),
+            retryable: error.is_retryable(),
+            message: error.to_string(),
+            field: field.map(str::to_string),
+            expected,
+            actual,
+            rejected_at: self.now_secs(),
+        }
+    }
+
+    /// Guarda no índice o motivo da rejeição de `tx`
+    pub(crate) fn record_rejection(&mut self, tx: &Transaction, error: &TransactionError) {
+        let reason = self.rejection_reason(tx, error);
+        debug!(
+            "Transação {} de {} rejeitada ({}): {}",
+            reason.txid, reason.from, reason.code, reason.message
+        );
+        self.index.record_rejection(reason);
+    }
+
+    /// Último motivo de rejeição registrado para `txid`
+    pub fn rejection(&self, txid: &str) -> Option<&RejectionReason> {
+        self.index.rejection(txid)
+    }
+}
@@ -0,0 +1,234 @@
+use super::block::Block;
+use super::blockchain::Blockchain;
+use super::status::LifecycleState;
+use crate::error::{Error, ErrorCode};
+use crate::events::AppEvent;
+use crate::transaction::{Operation, Transaction};
+use log::{info, warn};
+use std::collections::BTreeSet;
+
+impl Blockchain {
+    /// Regra de escolha de ramo: adota `branch` quando ele termina acima do topo
+    /// atual (cadeia mais longa; em empate fica a atual) e retorna se houve troca.
+    ///
+    /// O ramo começa no primeiro bloco que a cadeia não tem e precisa se ligar a
+    /// ela por `previous_hash`. O corpo de cada bloco do ramo é executado como em
+    /// `add_block`. Blocos cobertos pelo checkpoint não são revertidos. Quando os
+    /// revertidos incluem blocos de corpo executado, o estado volta ao ponto de
+    /// retorno do primeiro deles (ver `revert_points`), com o mempool de então, o
+    /// que limita a reversão a blocos com menos de `confirmation_depth`
+    /// confirmações executados desde que o nó subiu; os itens revertidos e os que
+    /// chegaram depois, se o ramo não os traz, passam de novo pela admissão. O ramo inteiro é validado e executado antes sobre uma cópia
+    /// do estado, de modo que um bloco inválido no meio dele não deixa efeitos.
+    pub fn reorganize(&mut self, mut branch: Vec<Block>) -> Result<bool, Error> {
+        let known = branch
+            .iter()
+            .take_while(|block| {
+                self.chain
+                    .get(block.index as usize)
+                    .is_some_and(|ours| ours.hash == block.hash)
+            })
+            .count();
+        let branch: Vec<Block> = branch.split_off(known);
+        let Some(first) = branch.first() else {
+            return Ok(false);
+        };
+
+        let fork_height = first.index;
+        if fork_height > self.height() {
+            return Err(Error::InvalidBlock(format!(
+                "Ramo começa na altura {}, acima do topo {}",
+                fork_height,
+                self.height()
+            )));
+        }
+        let mut parent = match fork_height.checked_sub(1) {
+            Some(height) => self.chain[height as usize].hash.clone(),
+            None => "0".repeat(64),
+        };
+        for (offset, block) in branch.iter().enumerate() {
+            if block.index != fork_height + offset as u64 || block.previous_hash != parent {
+                return Err(Error::InvalidBlock(format!(
+                    "Ramo não encadeado na altura {}",
+                    block.index
+                )));
+            }
+            parent = block.hash.clone();
+        }
+
+        if fork_height + branch.len() as u64 <= self.height() {
+            return Ok(false);
+        }
+        if let Some(checkpoint) = self
+            .checkpoint
+            .as_ref()
+            .filter(|checkpoint| checkpoint.height >= fork_height)
+        {
+            return Err(Error::InvalidBlock(format!(
+                "Ramo reverteria blocos até a altura {}, coberta por checkpoint",
+                checkpoint.height
+            )));
+        }
+        // Com blocos de corpo executado entre os revertidos, o ramo parte do estado
+        // e do mempool anteriores ao primeiro deles
+        let revert_to = self
+            .execution_receipts
+            .range(fork_height..)
+            .next()
+            .map(|(height, _)| self.revert_point(*height))
+            .transpose()?;
+
+        // A cópia em que o ramo é validado só leva o bloco anterior a ele; a
+        // repetição de transações legadas dos blocos mantidos é conferida aqui
+        let kept = &self.chain[..fork_height as usize];
+        if let Some(block) = branch.iter().find(|block| {
+            block
+                .transfers
+                .iter()
+                .any(|tx| Self::includes_transaction(kept, tx))
+        }) {
+            return Err(Error::InvalidBlock(format!(
+                "Bloco {} do ramo repete uma transação já incluída na cadeia",
+                block.index
+            )));
+        }
+
+        let mut scratch = self.scratch_at(fork_height)?;
+        if let Some(state) = &revert_to {
+            scratch.restore_state(state.try_clone()?, fork_height);
+        }
+        for block in &branch {
+            scratch.validate_new_block(block)?;
+            scratch.execute_block(block);
+            scratch.push_block(block.clone());
+        }
+        for block in &branch {
+            self.log_appended_block(block)?;
+        }
+
+        // O ramo já passou pela cópia, partindo do mesmo estado: daqui em diante
+        // nada falha
+        let old_tip = self.chain.last().map(|block| block.hash.clone());
+        let reverted = self.truncate_chain(fork_height);
+        let mempool = revert_to.map(|state| {
+            let mempool = (
+                std::mem::take(&mut self.pending_operations),
+                std::mem::take(&mut self.pending_transactions),
+            );
+            self.restore_state(state, fork_height);
+            mempool
+        });
+        let mut committed = Vec::new();
+        for block in &branch {
+            self.save_revert_point(block);
+            committed.extend(self.execute_block(block));
+            self.push_block(block.clone());
+        }
+        if let Some((operations, transfers)) = mempool {
+            // O mempool restaurado já sem o que o ramo incluiu; os itens revertidos
+            // e os que chegaram depois do ponto de retorno, fora dele e do ramo,
+            // passam de novo pela admissão
+            for tx in &self.pending_transactions {
+                let txid = tx.txid();
+                if self
+                    .transaction_statuses
+                    .get(&txid)
+                    .is_none_or(|entry| entry.state != LifecycleState::Pending)
+                {
+                    self.transaction_statuses.set(txid, LifecycleState::Pending);
+                }
+            }
+            let mut known: BTreeSet<String> = branch.iter().flat_map(Block::item_ids).collect();
+            known.extend(self.pending_operations.iter().map(Operation::id));
+            known.extend(self.pending_transactions.iter().map(Transaction::txid));
+            self.readmit(
+                reverted
+                    .iter()
+                    .flat_map(|block| block.operations.iter().cloned())
+                    .chain(operations)
+                    .filter(|operation| !known.contains(&operation.id()))
+                    .collect(),
+                reverted
+                    .iter()
+                    .flat_map(|block| block.transfers.iter().cloned())
+                    .chain(transfers)
+                    .filter(|tx| !known.contains(&tx.txid()))
+                    .collect(),
+            );
+        }
+
+        let txids =
+            |blocks: &[Block]| -> Vec<String> { blocks.iter().flat_map(Block::item_ids).collect() };
+        if !reverted.is_empty() {
+            info!(
+                "Reorganização na altura {}: {} blocos revertidos, {} aplicados",
+                fork_height,
+                reverted.len(),
+                branch.len()
+            );
+            self.events.emit(AppEvent::ChainReorganized {
+                fork_height,
+                old_tip: old_tip.unwrap_or_default(),
+                new_tip: parent,
+                reverted: txids(&reverted),
+                applied: txids(&branch),
+            });
+        }
+        for event in committed {
+            self.events.emit(event);
+        }
+        Ok(true)
+    }
+
+    /// Passa de novo pela admissão, na ordem, operações e transferências que
+    /// ficaram fora da cadeia; as que o estado atual recusa são descartadas
+    fn readmit(&mut self, operations: Vec<Operation>, transfers: Vec<Transaction>) {
+        for operation in operations {
+            let (author, nonce) = (operation.author.clone(), operation.nonce);
+            if let Err(e) = self.submit_operation(operation) {
+                warn!(
+                    "Operação de {} (nonce {}) descartada após reorganização: {}",
+                    author, nonce, e
+                );
+            }
+        }
+        for tx in transfers {
+            match self.check_admission(&tx) {
+                Ok(()) => {
+                    self.nonces.insert(tx.from.clone(), tx.nonce);
+                    self.transaction_statuses
+                        .set(tx.txid(), LifecycleState::Pending);
+                    self.pending_transactions.push(tx);
+                }
+                Err(e) => {
+                    warn!(
+                        "Transação de {} (nonce {}) descartada após reorganização: {}",
+                        tx.from, tx.nonce, e
+                    );
+                    self.transaction_statuses.set(
+                        tx.txid(),
+                        LifecycleState::Dropped {
+                            code: Some(e.code()),
+                            reason: e.to_string(),
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    /// Remove os blocos a partir de `height`, com os registros de todos os seus
+    /// itens no índice, os recibos, os pontos de retorno e o arquivo histórico
+    fn truncate_chain(&mut self, height: u64) -> Vec<Block> {
+        let removed = self.chain.split_off(height as usize);
+        for id in removed.iter().flat_map(Block::item_ids) {
+            self.index.remove(&id);
+        }
+        self.execution_receipts.retain(|h, _| *h < height);
+        self.revert_points.retain(|h, _| *h < height);
+        self.archive.truncate(height);
+        self.transaction_statuses
+            .revert_from(height, "Bloco revertido por reorganização");
+        removed
+    }
+}
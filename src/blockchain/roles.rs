@@ -0,0 +1,110 @@
+use super::blockchain::{Address, Blockchain};
+use crate::error::{Error, TransactionError};
+use crate::rbac::{AdminRole, RoleCredential};
+use crate::transaction::{Operation, OperationKind};
+
+impl Blockchain {
+    pub fn has_role(&self, address: &str, role: AdminRole) -> bool {
+        self.roles
+            .get(address)
+            .is_some_and(|roles| roles.contains(&role))
+    }
+
+    /// Endereços com o papel `role`, em ordem
+    pub fn role_members(&self, role: AdminRole) -> Vec<Address> {
+        let mut members: Vec<Address> = self
+            .roles
+            .iter()
+            .filter(|(_, roles)| roles.contains(&role))
+            .map(|(address, _)| address.clone())
+            .collect();
+        members.sort();
+        members
+    }
+
+    /// Define o primeiro admin de uma cadeia sem nenhum; depois disso papéis só
+    /// mudam por operações `AssignRole` assinadas por um admin
+    pub fn bootstrap_admin(&mut self, address: Address) -> Result<(), Error> {
+        if !self.role_members(AdminRole::Admin).is_empty() {
+            return Err(Error::Unauthorized(
+                "Cadeia já tem administrador; use uma operação AssignRole".to_string(),
+            ));
+        }
+        self.roles
+            .entry(address)
+            .or_default()
+            .insert(AdminRole::Admin);
+        Ok(())
+    }
+
+    /// Confere a credencial e a atribuição de `role` ao titular na cadeia,
+    /// devolvendo o endereço autorizado
+    pub fn authorize(
+        &self,
+        credential: &RoleCredential,
+        role: AdminRole,
+    ) -> Result<Address, Error> {
+        if credential.role != role {
+            return Err(Error::Unauthorized(format!(
+                "Credencial de {} apresentada; exigido {}",
+                credential.role, role
+            )));
+        }
+        credential.verify()?;
+        if !self.has_role(&credential.subject, role) {
+            return Err(Error::Unauthorized(format!(
+                "{} não tem o papel {} na cadeia",
+                credential.subject, role
+            )));
+        }
+        Ok(credential.subject.clone())
+    }
+
+    /// Só um admin, assinando com a chave do próprio endereço, muda papéis; o último
+    /// admin não pode revogar o próprio papel
+    pub(super) fn check_role_assignment(
+        &self,
+        operation: &Operation,
+    ) -> Result<(), TransactionError> {
+        let OperationKind::AssignRole {
+            subject,
+            role,
+            granted,
+        } = &operation.kind
+        else {
+            return Ok(());
+        };
+
+        if !self.is_account_key(&operation.author, &operation.public_key)
+            || !self.has_role(&operation.author, AdminRole::Admin)
+        {
+            return Err(TransactionError::InvalidParameter(format!(
+                "{} não pode atribuir papéis",
+                operation.author
+            )));
+        }
+        if !granted
+            && *role == AdminRole::Admin
+            && self.role_members(AdminRole::Admin) == [subject.clone()]
+        {
+            return Err(TransactionError::InvalidParameter(
+                "Não é possível revogar o último administrador".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub(super) fn apply_role_assignment(&mut self, subject: &str, role: AdminRole, granted: bool) {
+        if granted {
+            self.roles
+                .entry(subject.to_string())
+                .or_default()
+                .insert(role);
+        } else if let Some(roles) = self.roles.get_mut(subject) {
+            roles.remove(&role);
+            if roles.is_empty() {
+                self.roles.remove(subject);
+            }
+        }
+    }
+}
@@ -0,0 +1,468 @@
+use super::archive::{HistoricalState, TransactionReceipt};
+use super::block::Block;
+use super::blockchain::Blockchain;
+use super::export::ExportSignature;
+use super::finality::Finality;
+use super::indexer::TransactionRecord;
+use super::nonces::AccountNonce;
+use super::read_snapshot::ReadSnapshot;
+use super::rejection::RejectionReason;
+use super::simulation::SimulationResult;
+use super::spv::InclusionProof;
+use super::status::TransactionLifecycle;
+use crate::config::Limits;
+use crate::error::{Error, TransactionError};
+use crate::network::NodeIdentity;
+use crate::token::{FungibleToken, TokenMetadata};
+use crate::transaction::{Operation, Transaction, TransactionProcessor, VerificationService};
+use oqs::Error as OqsError;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use tokio::task::{self, JoinError};
+
+/// Handle thread-safe para a blockchain.
+///
+/// Leituras (consultas RPC, validação de cadeia) podem ocorrer em paralelo;
+/// escritas (novos blocos, transações, tokens) são serializadas pelo lock de escrita.
+/// Clonar o handle é barato e todos os clones compartilham o mesmo estado.
+#[derive(Clone)]
+pub struct SharedBlockchain {
+    inner: Arc<RwLock<Blockchain>>,
+    /// Escritas realizadas; invalida a foto de leitura em cache
+    version: Arc<AtomicU64>,
+    snapshot: Arc<Mutex<Option<Arc<ReadSnapshot>>>>,
+}
+
+impl SharedBlockchain {
+    pub fn new(blockchain: Blockchain) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(blockchain)),
+            version: Arc::new(AtomicU64::new(0)),
+            snapshot: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn read_guard(&self) -> RwLockReadGuard<'_, Blockchain> {
+        self.inner
+            .read()
+            .expect("Lock da blockchain envenenado por uma escrita interrompida")
+    }
+
+    fn write_guard(&self) -> RwLockWriteGuard<'_, Blockchain> {
+        let guard = self
+            .inner
+            .write()
+            .expect("Lock da blockchain envenenado por uma escrita interrompida");
+        // Incrementado com a trava de escrita já tomada: quem lê a versão sob a
+        // trava de leitura sabe que o estado não mudou desde então
+        self.version.fetch_add(1, Ordering::AcqRel);
+        guard
+    }
+
+    /// Executa uma leitura sobre o estado atual, concorrente com outras leituras
+    pub fn read<R>(&self, f: impl FnOnce(&Blockchain) -> R) -> R {
+        f(&self.read_guard())
+    }
+
+    /// Foto consistente do estado para consultas com vários itens.
+    ///
+    /// Cópia sob demanda: a foto só é refeita na primeira leitura depois de uma
+    /// escrita, e até lá todos os leitores recebem a mesma. Quem a segura não
+    /// bloqueia escritas nem vê as que vierem depois.
+    pub fn read_snapshot(&self) -> Arc<ReadSnapshot> {
+        let blockchain = self.read_guard();
+        let version = self.version.load(Ordering::Acquire);
+        let mut cached = self
+            .snapshot
+            .lock()
+            .expect("Cache da foto de leitura envenenado");
+        match cached.as_ref() {
+            Some(snapshot) if snapshot.version() == version => Arc::clone(snapshot),
+            _ => {
+                let snapshot = Arc::new(ReadSnapshot::capture(&blockchain, version));
+                *cached = Some(Arc::clone(&snapshot));
+                snapshot
+            }
+        }
+    }
+
+    /// Executa uma escrita exclusiva; leituras aguardam até a escrita terminar
+    pub fn write<R>(&self, f: impl FnOnce(&mut Blockchain) -> R) -> R {
+        f(&mut self.write_guard())
+    }
+
+    /// Altura atual da cadeia (número de blocos)
+    pub fn height(&self) -> u64 {
+        self.read_guard().chain.len() as u64
+    }
+
+    pub fn limits(&self) -> Limits {
+        self.read_guard().limits
+    }
+
+    /// Hash do último bloco, se houver
+    pub fn latest_hash(&self) -> Option<String> {
+        self.read_guard()
+            .chain
+            .last()
+            .map(|block| block.hash.clone())
+    }
+
+    /// Cópia do bloco no índice informado
+    pub fn block(&self, index: u64) -> Option<Block> {
+        self.read_guard()
+            .chain
+            .iter()
+            .find(|block| block.index == index)
+            .cloned()
+    }
+
+    /// Cópia do bloco na altura `index` com sua finalidade atual, lidos sob a
+    /// mesma trava
+    pub fn block_with_finality(&self, index: u64) -> Option<(Block, Finality)> {
+        let blockchain = self.read_guard();
+        let block = blockchain
+            .chain
+            .iter()
+            .find(|block| block.index == index)?
+            .clone();
+        let finality = blockchain.finality_at(index)?;
+        Some((block, finality))
+    }
+
+    pub fn block_by_hash_with_finality(&self, hash: &str) -> Option<(Block, Finality)> {
+        let blockchain = self.read_guard();
+        let block = blockchain
+            .chain
+            .iter()
+            .find(|block| block.hash == hash)?
+            .clone();
+        let finality = blockchain.finality_at(block.index)?;
+        Some((block, finality))
+    }
+
+    /// Transação pelo txid, confirmada ou ainda no mempool
+    pub fn transaction_record(&self, txid: &str) -> Option<TransactionRecord> {
+        self.read_guard().transaction_record(txid)
+    }
+
+    pub fn transaction_status(&self, txid: &str) -> Option<TransactionLifecycle> {
+        self.read_guard().transaction_status(txid).cloned()
+    }
+
+    /// Cópia do último bloco da cadeia
+    pub fn latest_block(&self) -> Option<Block> {
+        self.read_guard().chain.last().cloned()
+    }
+
+    /// Saldo de um endereço em um token
+    pub fn balance_of(&self, token_id: &str, address: &str) -> Option<u64> {
+        self.read_guard()
+            .get_token(token_id)
+            .map(|token| FungibleToken::balance_of(token, address))
+    }
+
+    /// Supply total de um token
+    pub fn token_supply(&self, token_id: &str) -> Option<u64> {
+        self.read_guard()
+            .get_token(token_id)
+            .map(FungibleToken::total_supply)
+    }
+
+    /// Nome, símbolo e dono de um token
+    pub fn token_metadata(&self, token_id: &str) -> Option<TokenMetadata> {
+        self.read_guard()
+            .get_token(token_id)
+            .map(FungibleToken::metadata)
+    }
+
+    /// Último nonce registrado para o endereço
+    pub fn nonce_of(&self, address: &str) -> u64 {
+        self.read_guard().nonces.get(address).copied().unwrap_or(0)
+    }
+
+    /// Nonces confirmado e pendente do endereço, com sugestão de reparo
+    pub fn account_nonce(&self, address: &str) -> AccountNonce {
+        self.read_guard().account_nonce(address)
+    }
+
+    /// Simula a submissão e a confirmação de `tx` sem alterar o estado
+    pub fn simulate_transaction(&self, tx: &Transaction) -> SimulationResult {
+        self.read_guard().simulate_transaction(tx)
+    }
+
+    /// Quantidade de transações aguardando inclusão em bloco
+    pub fn pending_count(&self) -> usize {
+        self.read_guard().pending_transactions.len()
+    }
+
+    /// Cópia das transações pendentes
+    pub fn pending_transactions(&self) -> Vec<Transaction> {
+        self.read_guard().pending_transactions.clone()
+    }
+
+    pub fn transaction_exists(&self, tx: &Transaction) -> bool {
+        self.read_guard().transaction_exists(tx)
+    }
+
+    pub fn is_chain_valid(&self) -> Result<bool, TransactionError> {
+        self.read_guard().is_chain_valid()
+    }
+
+    /// Estado histórico após o bloco em `height` (somente nós de arquivo)
+    pub fn state_at(&self, height: u64) -> Result<HistoricalState, Error> {
+        self.read_guard().state_at(height)
+    }
+
+    pub fn receipts_at(&self, height: u64) -> Result<Vec<TransactionReceipt>, Error> {
+        self.read_guard().receipts_at(height)
+    }
+
+    /// Prova SPV da transação para verificadores externos
+    pub fn get_proof(&self, txid: &str) -> Result<InclusionProof, Error> {
+        self.read_guard().get_proof(txid)
+    }
+
+    pub fn add_block(&self, block: Block) -> Result<(), Error> {
+        self.write_guard().add_block(block)
+    }
+
+    /// Adota um ramo concorrente mais longo (ver `Blockchain::reorganize`)
+    pub fn reorganize(&self, branch: Vec<Block>) -> Result<bool, Error> {
+        self.write_guard().reorganize(branch)
+    }
+
+    /// Fecha um bloco com transações do mempool (ver `Blockchain::produce_block`)
+    pub fn produce_block(&self, max_transactions: usize) -> Result<Option<Block>, Error> {
+        self.write_guard().produce_block(max_transactions)
+    }
+
+    pub fn add_transaction(
+        &self,
+        from: String,
+        to: String,
+        amount: u64,
+        signature: Vec<u8>,
+    ) -> Result<(), TransactionError> {
+        self.write_guard()
+            .add_transaction(from, to, amount, signature)
+    }
+
+    /// Submete uma transação assinada ao mempool. A verificação da assinatura
+    /// ocorre fora do lock; o vínculo entre a chave e `tx.from`, nonce,
+    /// duplicação e inserção são conferidos sob ele.
+    pub fn submit_transaction(&self, tx: Transaction) -> Result<(), TransactionError> {
+        if let Err(e) = Blockchain::check_transaction(&tx, &self.limits()) {
+            self.record_rejection(&tx, &e);
+            return Err(e);
+        }
+        self.write_guard().admit_transaction(tx)
+    }
+
+    /// Submete uma operação assinada (ver `Blockchain::submit_operation`)
+    pub fn submit_operation(&self, operation: Operation) -> Result<(), TransactionError> {
+        self.write_guard().submit_operation(operation)
+    }
+
+    /// Submete um lote ao mempool: as assinaturas são verificadas em paralelo no
+    /// `VerificationService` e as aprovadas são admitidas na ordem do lote
+    pub fn submit_batch(
+        &self,
+        transactions: Vec<Transaction>,
+    ) -> Vec<Result<(), TransactionError>> {
+        let processor = TransactionProcessor;
+        let limits = self.limits();
+        let checked: Vec<Result<(), TransactionError>> = transactions
+            .iter()
+            .map(|tx| processor.cheap_checks_with(tx, &limits))
+            .collect();
+        let verified = VerificationService::global().verify_batch(&transactions);
+
+        let mut blockchain = self.write_guard();
+        transactions
+            .into_iter()
+            .zip(checked.into_iter().zip(verified))
+            .map(|(tx, (checked, verified))| {
+                checked
+                    .and(verified)
+                    .inspect_err(|e| blockchain.record_rejection(&tx, e))?;
+                blockchain.admit_transaction(tx)
+            })
+            .collect()
+    }
+
+    /// Admite uma transação cuja assinatura já foi verificada pelo chamador
+    pub(crate) fn admit_transaction(&self, tx: Transaction) -> Result<(), TransactionError> {
+        self.write_guard().admit_transaction(tx)
+    }
+
+    /// Registra no índice uma rejeição decidida fora da blockchain
+    pub(crate) fn record_rejection(&self, tx: &Transaction, error: &TransactionError) {
+        self.write_guard().record_rejection(tx, error)
+    }
+
+    /// Último motivo de rejeição registrado para `txid`
+    pub fn rejection(&self, txid: &str) -> Option<RejectionReason> {
+        self.read_guard().rejection(txid).cloned()
+    }
+
+    pub fn create_token(
+        &self,
+        name: String,
+        symbol: String,
+        initial_supply: u64,
+        creator: String,
+    ) -> Result<String, OqsError> {
+        self.write_guard()
+            .create_token(name, symbol, initial_supply, creator)
+    }
+
+    pub fn add_staker(&self, address: String, amount: u64) {
+        self.write_guard().add_staker(address, amount)
+    }
+
+    pub fn unbond_stake(&self, address: &str, amount: u64) -> Result<(), Error> {
+        self.write_guard().unbond_stake(address, amount)
+    }
+
+    /// Salva o estado atual em arquivo sem bloquear outras leituras
+    pub fn save_to_file(&self, filename: &str) -> std::io::Result<()> {
+        self.read_guard().save_to_file(filename)
+    }
+
+    /// Recupera a blockchain se este for o último handle
+    pub fn try_into_inner(self) -> Result<Blockchain, Self> {
+        let Self {
+            inner,
+            version,
+            snapshot,
+        } = self;
+        match Arc::try_unwrap(inner) {
+            Ok(lock) => Ok(lock
+                .into_inner()
+                .expect("Lock da blockchain envenenado por uma escrita interrompida")),
+            Err(inner) => Err(Self {
+                inner,
+                version,
+                snapshot,
+            }),
+        }
+    }
+}
+
+// Variantes assíncronas: operações com Dilithium, Kyber ou disco são executadas
+// em `spawn_blocking` para não travar as threads do runtime tokio.
+impl SharedBlockchain {
+    async fn run_blocking<T, F>(&self, f: F) -> Result<T, JoinError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&SharedBlockchain) -> T + Send + 'static,
+    {
+        let shared = self.clone();
+        task::spawn_blocking(move || f(&shared)).await
+    }
+
+    pub async fn submit_transaction_async(&self, tx: Transaction) -> Result<(), TransactionError> {
+        self.run_blocking(move |shared| shared.submit_transaction(tx))
+            .await
+            .map_err(|e| TransactionError::Other(format!("Tarefa de submissão falhou: {}", e)))?
+    }
+
+    pub async fn add_block_async(&self, block: Block) -> Result<(), Error> {
+        self.run_blocking(move |shared| shared.add_block(block))
+            .await
+            .map_err(|e| Error::Other(format!("Tarefa de adição de bloco falhou: {}", e)))?
+    }
+
+    pub async fn produce_block_async(
+        &self,
+        max_transactions: usize,
+    ) -> Result<Option<Block>, Error> {
+        self.run_blocking(move |shared| shared.produce_block(max_transactions))
+            .await
+            .map_err(|e| Error::Other(format!("Tarefa de produção de bloco falhou: {}", e)))?
+    }
+
+    pub async fn is_chain_valid_async(&self) -> Result<bool, TransactionError> {
+        self.run_blocking(|shared| shared.is_chain_valid())
+            .await
+            .map_err(|e| TransactionError::Other(format!("Tarefa de validação falhou: {}", e)))?
+    }
+
+    pub async fn save_to_file_async(&self, filename: &str) -> std::io::Result<()> {
+        let filename = filename.to_string();
+        self.run_blocking(move |shared| shared.save_to_file(&filename))
+            .await
+            .map_err(std::io::Error::other)?
+    }
+
+    /// Exporta o estado em JSON com a assinatura do nó, sem bloquear o runtime
+    pub async fn save_signed_async(
+        &self,
+        filename: &str,
+        identity: Arc<NodeIdentity>,
+    ) -> Result<ExportSignature, Error> {
+        let filename = filename.to_string();
+        self.run_blocking(move |shared| {
+            shared.read(|blockchain| blockchain.save_signed(&filename, &identity))
+        })
+        .await
+        .map_err(|e| Error::Other(format!("Tarefa de exportação falhou: {}", e)))?
+    }
+
+    pub async fn save_to_db_async(&self, db_path: &str) -> Result<(), Error> {
+        let db_path = db_path.to_string();
+        self.run_blocking(move |shared| shared.read(|blockchain| blockchain.save_to_db(&db_path)))
+            .await
+            .map_err(|e| {
+                Error::Other(format!(
+                    "Tarefa de gravação no banco de dados falhou: {}",
+                    e
+                ))
+            })?
+            .map_err(|e| Error::database("Falha ao salvar blockchain no banco de dados", e))
+    }
+
+    /// Grava no banco apenas os blocos a partir de `height`, mais o estado atual
+    pub async fn persist_new_blocks_since_async(
+        &self,
+        db_path: &str,
+        height: u64,
+    ) -> Result<usize, Error> {
+        let db_path = db_path.to_string();
+        self.run_blocking(move |shared| {
+            shared.read(|blockchain| blockchain.persist_new_blocks_since(&db_path, height))
+        })
+        .await
+        .map_err(|e| {
+            Error::Other(format!(
+                "Tarefa de gravação no banco de dados falhou: {}",
+                e
+            ))
+        })?
+        .map_err(|e| Error::database("Falha ao gravar novos blocos no banco de dados", e))
+    }
+
+    /// Carrega a blockchain de um arquivo JSON sem bloquear o runtime
+    pub async fn load_from_file_async(filename: &str) -> Result<Self, Error> {
+        let filename = filename.to_string();
+        let blockchain = task::spawn_blocking(move || Blockchain::load_from_file(&filename))
+            .await
+            .map_err(|e| Error::Other(format!("Tarefa de carregamento falhou: {}", e)))??;
+        Ok(Self::new(blockchain))
+    }
+}
+
+impl From<Blockchain> for SharedBlockchain {
+    fn from(blockchain: Blockchain) -> Self {
+        Self::new(blockchain)
+    }
+}
+
+impl std::fmt::Debug for SharedBlockchain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedBlockchain")
+            .field("height", &self.height())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,123 @@
+use super::blockchain::Blockchain;
+use crate::constants::{TRANSFER_FEE_DIVISOR, TRANSFER_FEE_MINIMUM};
+use crate::error::{ErrorCode, TransactionError};
+use crate::events::AppEvent;
+use crate::token::fungible::move_balance;
+use crate::transaction::Transaction;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Etapa em que uma transação simulada seria recusada
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulationStage {
+    /// Formato, tamanhos, assinatura, nonce e duplicidade: recusa na submissão
+    Admission,
+    /// Políticas do token e saldo: a transação entraria no mempool e seria
+    /// descartada ao fechar o bloco, consumindo o nonce
+    Execution,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SimulationStatus {
+    Accepted,
+    Rejected {
+        stage: SimulationStage,
+        error: String,
+        /// Código estável do erro (ver `ErrorCode`)
+        error_code: u32,
+    },
+}
+
+/// Resultado de `Blockchain::simulate_transaction`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SimulationResult {
+    #[serde(flatten)]
+    pub status: SimulationStatus,
+    /// Taxa em KYBL cobrada pela transferência quando o bloco a aplica
+    pub fee: u64,
+    /// Peso que a transferência consome do limite do bloco
+    pub weight: u64,
+    /// Eventos que a confirmação emitiria, na altura do próximo bloco; vazio
+    /// quando a transação é recusada
+    pub events: Vec<AppEvent>,
+}
+
+impl Blockchain {
+    /// Taxa de transferência: 0,1% do valor, com mínimo de `TRANSFER_FEE_MINIMUM`
+    pub fn transfer_fee(amount: u64) -> u64 {
+        std::cmp::max(amount / TRANSFER_FEE_DIVISOR, TRANSFER_FEE_MINIMUM)
+    }
+
+    /// Executa sobre uma cópia do estado as mesmas verificações da submissão e da
+    /// confirmação em bloco, sem alterar mempool, nonces ou saldos.
+    ///
+    /// A transação é avaliada isoladamente: itens do mesmo remetente ainda no
+    /// mempool não são aplicados antes dela.
+    pub fn simulate_transaction(&self, tx: &Transaction) -> SimulationResult {
+        let fee = Self::transfer_fee(tx.amount);
+        let weight = self.limits.weights.transfer_weight(tx.encoded_size());
+        let rejected = |stage, error: TransactionError| SimulationResult {
+            status: SimulationStatus::Rejected {
+                stage,
+                error: error.to_string(),
+                error_code: error.code(),
+            },
+            fee,
+            weight,
+            events: Vec::new(),
+        };
+
+        if let Err(e) =
+            Self::check_transaction(tx, &self.limits).and_then(|()| self.check_admission(tx))
+        {
+            return rejected(SimulationStage::Admission, e);
+        }
+        if let Err(e) = self.check_execution(tx) {
+            return rejected(SimulationStage::Execution, e);
+        }
+
+        SimulationResult {
+            status: SimulationStatus::Accepted,
+            fee,
+            weight,
+            events: vec![AppEvent::TransferApplied {
+                txid: tx.txid(),
+                token_id: tx.token_id,
+                from: tx.from.clone(),
+                to: tx.to.clone(),
+                amount: tx.amount,
+                height: self.height(),
+            }],
+        }
+    }
+
+    /// `apply_transfer` sobre uma cópia dos saldos de origem e destino, com a
+    /// taxa em KYBL conferida contra o que sobra ao remetente
+    fn check_execution(&self, tx: &Transaction) -> Result<(), TransactionError> {
+        self.check_transfer_policies(tx)?;
+        let token = self
+            .tokens
+            .get(&tx.token_id.to_string())
+            .ok_or(TransactionError::TokenNaoEncontrado)?;
+        let mut balances: HashMap<String, u64> = [&tx.from, &tx.to]
+            .into_iter()
+            .filter_map(|address| {
+                let balance = token.balances.get(address)?;
+                Some((address.clone(), *balance))
+            })
+            .collect();
+        move_balance(&mut balances, &tx.from, &tx.to, tx.amount)?;
+
+        let fee_balance = match tx.token_id {
+            0 => balances.get(&tx.from).copied().unwrap_or(0),
+            _ => self.fee_balance(&tx.from),
+        };
+        if fee_balance < Self::transfer_fee(tx.amount) {
+            return Err(TransactionError::InsufficientFunds);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use super::block::Block;
+use super::blockchain::Blockchain;
+use super::execution::ExecutionSummary;
+use super::merkle::{leaf_hash, merkle_root, transaction_leaf, MerkleHash, MerkleProof};
+use super::pruning::CheckpointAttestation;
+use super::quorum::QuorumCheckpoint;
+use crate::error::Error;
+use crate::transaction::SecureTransaction;
+use pqcrypto_dilithium::dilithium5::PublicKey;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Identificador de uma transação: hex da sua folha Merkle
+pub fn transaction_id(tx: &SecureTransaction) -> String {
+    hex::encode(transaction_leaf(tx))
+}
+
+/// Cabeçalho de bloco usado por verificadores que não mantêm a cadeia
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct BlockHeader {
+    pub index: u64,
+    pub timestamp: u64,
+    pub hash: String,
+    pub previous_hash: String,
+    pub transactions_root: MerkleHash,
+    /// Resumo de execução do bloco, quando houver
+    #[serde(default)]
+    pub execution: Option<ExecutionSummary>,
+}
+
+impl BlockHeader {
+    pub fn from_block(block: &Block) -> Self {
+        BlockHeader {
+            index: block.index,
+            timestamp: block.timestamp,
+            hash: block.hash.clone(),
+            previous_hash: block.previous_hash.clone(),
+            transactions_root: block.transactions_root(),
+            execution: block.execution.clone(),
+        }
+    }
+
+    /// Folha do cabeçalho na árvore ancorada pelos checkpoints. Cobre o resumo
+    /// de execução, então um recibo provado contra `execution` fica ancorado no
+    /// mesmo checkpoint que as transações.
+    pub fn leaf(&self) -> MerkleHash {
+        let mut data = format!(
+            "{}:{}:{}:{}:{}",
+            self.index,
+            self.timestamp,
+            self.hash,
+            self.previous_hash,
+            hex::encode(self.transactions_root)
+        );
+        if let Some(execution) = &self.execution {
+            data.push_str(&execution.header_suffix());
+        }
+        leaf_hash(data.as_bytes())
+    }
+}
+
+/// Prova SPV de que uma transação foi incluída em um bloco coberto por checkpoint.
+///
+/// A transação leva à raiz do cabeçalho; o cabeçalho leva à raiz de cabeçalhos
+/// assinada pelo validador no checkpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InclusionProof {
+    pub txid: String,
+    pub transaction: SecureTransaction,
+    pub header: BlockHeader,
+    pub transaction_path: MerkleProof,
+    pub header_path: MerkleProof,
+    pub checkpoint: CheckpointAttestation,
+}
+
+impl InclusionProof {
+    /// Verifica a prova sem acesso à cadeia, confiando apenas em `validator`
+    pub fn verify(&self, validator: &PublicKey) -> Result<(), Error> {
+        self.checkpoint.verify_from(validator)?;
+        self.verify_paths()
+    }
+
+    /// Verifica a prova confiando em um checkpoint atestado por ao menos
+    /// `threshold` de `validators`, em vez de um único validador
+    pub fn verify_quorum(
+        &self,
+        quorum: &QuorumCheckpoint,
+        validators: &[Vec<u8>],
+        threshold: usize,
+    ) -> Result<(), Error> {
+        quorum.verify(validators, threshold)?;
+        if !quorum.covers(&self.checkpoint) {
+            return Err(Error::InvalidBlock(
+                "Checkpoint da prova difere do atestado pelo quórum".to_string(),
+            ));
+        }
+        self.verify_paths()
+    }
+
+    fn verify_paths(&self) -> Result<(), Error> {
+        if transaction_id(&self.transaction) != self.txid {
+            return Err(Error::InvalidInput(
+                "Transação não corresponde ao txid".to_string(),
+            ));
+        }
+
+        if !self.transaction_path.verify(
+            &transaction_leaf(&self.transaction),
+            &self.header.transactions_root,
+        ) {
+            return Err(Error::InvalidBlock(
+                "Caminho Merkle da transação inválido".to_string(),
+            ));
+        }
+
+        // O cabeçalho precisa ocupar sua própria altura na árvore do checkpoint
+        let headers_root = self
+            .header_path
+            .compute_root(&self.header.leaf())
+            .map(hex::encode);
+        if self.header.index > self.checkpoint.height
+            || self.header_path.leaf_index as u64 != self.header.index
+            || self.header_path.leaf_count as u64 != self.checkpoint.height + 1
+            || headers_root.as_deref() != Some(self.checkpoint.headers_root.as_str())
+        {
+            return Err(Error::InvalidBlock(
+                "Cabeçalho não coberto pelo checkpoint".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Blockchain {
+    fn header_leaves(&self, height: u64) -> Vec<MerkleHash> {
+        self.chain
+            .iter()
+            .take_while(|block| block.index <= height)
+            .map(|block| {
+                let mut header = BlockHeader::from_block(block);
+                if let Some(detached) = self.detached_blocks.get(&block.index) {
+                    header.transactions_root = detached.transactions_root;
+                }
+                header.leaf()
+            })
+            .collect()
+    }
+
+    /// Raiz Merkle dos cabeçalhos do gênesis até `height`
+    pub fn headers_root(&self, height: u64) -> Result<MerkleHash, Error> {
+        let leaves = self.header_leaves(height);
+        if leaves.len() as u64 != height + 1 {
+            return Err(Error::InvalidBlock(format!(
+                "Altura {} não encontrada na cadeia",
+                height
+            )));
+        }
+        Ok(merkle_root(&leaves))
+    }
+
+    /// Prova de inclusão da transação `txid` ancorada no último checkpoint
+    pub fn get_proof(&self, txid: &str) -> Result<InclusionProof, Error> {
+        let (block, position) = self
+            .chain
+            .iter()
+            .find_map(|block| {
+                block
+                    .transactions
+                    .iter()
+                    .position(|tx| transaction_id(tx) == txid)
+                    .map(|position| (block, position))
+            })
+            .ok_or_else(|| Error::InvalidInput(format!("Transação {} não encontrada", txid)))?;
+
+        let checkpoint = self
+            .checkpoint
+            .clone()
+            .filter(|checkpoint| checkpoint.height >= block.index)
+            .ok_or_else(|| {
+                Error::InvalidBlock(format!(
+                    "Bloco {} ainda não coberto por checkpoint",
+                    block.index
+                ))
+            })?;
+
+        let transaction_path = block
+            .transaction_proof(position)
+            .ok_or_else(|| Error::Other("Falha ao construir caminho Merkle".to_string()))?;
+        let header_path =
+            MerkleProof::build(&self.header_leaves(checkpoint.height), block.index as usize)
+                .ok_or_else(|| Error::Other("Falha ao construir caminho Merkle".to_string()))?;
+
+        Ok(InclusionProof {
+            txid: txid.to_string(),
+            transaction: block.transactions[position].clone(),
+            header: BlockHeader::from_block(block),
+            transaction_path,
+            header_path,
+            checkpoint,
+        })
+    }
+}
@@ -0,0 +1,807 @@
+use super::block::Block;
+use super::blockchain::Blockchain;
+use super::format::BLOCKCHAIN_FORMAT_VERSION;
+use super::status::{TransactionLifecycle, TransactionStatusStore};
+use super::streaming::RecentWindow;
+use super::throttle::SubmissionThrottle;
+use crate::blockchain::validacao::Validator;
+use crate::config::Limits;
+use crate::constants::{DEFAULT_CONFIRMATION_DEPTH, MAX_BLOCK_SIZE};
+use crate::error::Error;
+use crate::events::EventBus;
+use crate::token::Token;
+use crate::utils::compression::{self, Codec};
+use crate::utils::version::ClientVersion;
+use rusqlite::types::{Type, Value};
+use rusqlite::{params, Connection, OpenFlags, Result as SqlResult, Transaction as SqlTransaction};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha3::{Digest, Sha3_256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS blocks (
+        id INTEGER PRIMARY KEY,
+        height INTEGER NOT NULL,
+        timestamp INTEGER NOT NULL,
+        previous_hash TEXT NOT NULL,
+        hash TEXT NOT NULL,
+        proposer TEXT,
+        validator_signature BLOB,
+        nonce INTEGER NOT NULL,
+        processed_transactions TEXT NOT NULL,
+        execution TEXT,
+        checksum TEXT,
+        body TEXT
+    );
+    CREATE TABLE IF NOT EXISTS block_transactions (
+        block_hash TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS block_contracts (
+        block_hash TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS chain_tokens (
+        id TEXT PRIMARY KEY,
+        token_id INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        symbol TEXT NOT NULL,
+        total_supply INTEGER NOT NULL,
+        creator TEXT NOT NULL,
+        quantum_crypto TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS token_balances (
+        token_id TEXT NOT NULL,
+        address TEXT NOT NULL,
+        balance INTEGER NOT NULL,
+        PRIMARY KEY (token_id, address)
+    );
+    CREATE TABLE IF NOT EXISTS stakers (
+        address TEXT PRIMARY KEY,
+        amount INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS nonces (
+        address TEXT PRIMARY KEY,
+        nonce INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS public_keys (
+        address TEXT PRIMARY KEY,
+        public_key BLOB NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS chain_state (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS transaction_status (
+        txid TEXT PRIMARY KEY,
+        state TEXT NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+";
+
+/// Unicidade de blocos e de seus itens. Bancos gravados antes dessas restrições
+/// podem ter linhas repetidas, removidas antes de criar os índices.
+const UNIQUE_INDEXES: &str = "
+    DELETE FROM blocks WHERE id NOT IN (SELECT MIN(id) FROM blocks GROUP BY height, hash);
+    DELETE FROM block_transactions WHERE rowid NOT IN
+        (SELECT MIN(rowid) FROM block_transactions GROUP BY block_hash, position);
+    DELETE FROM block_contracts WHERE rowid NOT IN
+        (SELECT MIN(rowid) FROM block_contracts GROUP BY block_hash, position);
+    CREATE UNIQUE INDEX IF NOT EXISTS blocks_height_hash ON blocks (height, hash);
+    CREATE UNIQUE INDEX IF NOT EXISTS block_transactions_position
+        ON block_transactions (block_hash, position);
+    CREATE UNIQUE INDEX IF NOT EXISTS block_contracts_position
+        ON block_contracts (block_hash, position);
+";
+
+/// Colunas de `blocks` que bancos antigos não têm: as anteriores aos blocos
+/// assinados não têm a do proponente, as anteriores aos recibos não têm a do
+/// resumo de execução, as anteriores às somas de verificação não têm a do checksum
+/// e as anteriores ao corpo de operações e transferências não têm a do corpo
+const OPTIONAL_BLOCK_COLUMNS: [&str; 4] = ["proposer", "execution", "checksum", "body"];
+
+fn has_block_column(conn: &Connection, column: &str) -> SqlResult<bool> {
+    conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM pragma_table_info('blocks') WHERE name = ?1)",
+        [column],
+        |row| row.get(0),
+    )
+}
+
+fn ensure_schema(conn: &Connection) -> SqlResult<()> {
+    conn.execute_batch(SCHEMA)?;
+    let indexed: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'index' AND name = 'blocks_height_hash')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !indexed {
+        conn.execute_batch(UNIQUE_INDEXES)?;
+    }
+    for column in OPTIONAL_BLOCK_COLUMNS {
+        if !has_block_column(conn, column)? {
+            conn.execute_batch(&format!("ALTER TABLE blocks ADD COLUMN {} TEXT", column))?;
+        }
+    }
+    Ok(())
+}
+
+/// Remove os blocos que satisfazem `filter`, junto com suas transações e contratos
+fn delete_stale_blocks(
+    tx: &SqlTransaction<'_>,
+    filter: &str,
+    args: impl rusqlite::Params + Copy,
+) -> SqlResult<()> {
+    for table in ["block_transactions", "block_contracts"] {
+        tx.execute(
+            &format!(
+                "DELETE FROM {} WHERE block_hash IN (SELECT hash FROM blocks WHERE {})",
+                table, filter
+            ),
+            args,
+        )?;
+    }
+    tx.execute(&format!("DELETE FROM blocks WHERE {}", filter), args)?;
+    Ok(())
+}
+
+/// Codec das transações, contratos e corpo dos blocos gravados por este nó
+const STORAGE_CODEC: Codec = Codec::Zstd;
+
+/// JSON de uma coluna de bloco comprimido como BLOB, com o ID do codec no início
+fn compress(text: &str) -> SqlResult<Vec<u8>> {
+    compression::encode(STORAGE_CODEC, text.as_bytes())
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+/// Volta ao JSON de uma coluna de bloco; linhas gravadas antes da compressão
+/// guardam o texto puro e são lidas como estão
+fn decompress(column: usize, value: Value) -> SqlResult<String> {
+    let frame = match value {
+        Value::Text(text) => return Ok(text),
+        Value::Blob(frame) => frame,
+        other => {
+            return Err(rusqlite::Error::InvalidColumnType(
+                column,
+                "data".to_string(),
+                other.data_type(),
+            ))
+        }
+    };
+    let failure = |e: Box<dyn std::error::Error + Send + Sync>| {
+        rusqlite::Error::FromSqlConversionFailure(column, Type::Blob, e)
+    };
+    let data = compression::decode(&frame, MAX_BLOCK_SIZE).map_err(|e| failure(Box::new(e)))?;
+    String::from_utf8(data).map_err(|e| failure(Box::new(e)))
+}
+
+fn to_json<T: Serialize>(value: &T) -> SqlResult<String> {
+    serde_json::to_string(value).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+fn from_json<T: DeserializeOwned>(column: usize, text: &str) -> SqlResult<T> {
+    serde_json::from_str(text)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(column, Type::Text, Box::new(e)))
+}
+
+/// Colunas de um bloco como ficam no banco, incluindo transações, contratos e
+/// corpo já serializados; a compressão é aplicada só na gravação
+struct BlockRow {
+    height: u64,
+    timestamp: u64,
+    previous_hash: String,
+    hash: String,
+    proposer: Option<String>,
+    validator_signature: Option<Vec<u8>>,
+    nonce: u64,
+    processed_transactions: String,
+    execution: Option<String>,
+    /// Operações e transferências do bloco, ausentes quando o corpo é vazio
+    body: Option<String>,
+    transactions: Vec<String>,
+    contracts: Vec<String>,
+}
+
+impl BlockRow {
+    fn of(block: &Block) -> SqlResult<Self> {
+        Ok(Self {
+            height: block.index,
+            timestamp: block.timestamp,
+            previous_hash: block.previous_hash.clone(),
+            hash: block.hash.clone(),
+            proposer: block.proposer.clone(),
+            validator_signature: block.validator_signature.clone(),
+            nonce: block.nonce,
+            processed_transactions: to_json(&block.processed_transactions)?,
+            execution: block.execution.as_ref().map(to_json).transpose()?,
+            body: if block.operations.is_empty() && block.transfers.is_empty() {
+                None
+            } else {
+                Some(to_json(&(&block.operations, &block.transfers))?)
+            },
+            transactions: block
+                .transactions
+                .iter()
+                .map(to_json)
+                .collect::<SqlResult<_>>()?,
+            contracts: block
+                .contracts
+                .iter()
+                .map(to_json)
+                .collect::<SqlResult<_>>()?,
+        })
+    }
+
+    /// SHA3-256 dos campos gravados (antes da compressão, para que somas antigas
+    /// confiram), cada um prefixado pelo tamanho para que mover bytes de uma
+    /// coluna para outra mude a soma
+    fn checksum(&self) -> String {
+        let mut hasher = Sha3_256::new();
+        let mut field = |bytes: Option<&[u8]>| match bytes {
+            Some(bytes) => {
+                hasher.update([1]);
+                hasher.update((bytes.len() as u64).to_le_bytes());
+                hasher.update(bytes);
+            }
+            None => hasher.update([0]),
+        };
+        field(Some(&self.height.to_le_bytes()));
+        field(Some(&self.timestamp.to_le_bytes()));
+        field(Some(self.previous_hash.as_bytes()));
+        field(Some(self.hash.as_bytes()));
+        field(self.proposer.as_deref().map(str::as_bytes));
+        field(self.validator_signature.as_deref());
+        field(Some(&self.nonce.to_le_bytes()));
+        field(Some(self.processed_transactions.as_bytes()));
+        field(self.execution.as_deref().map(str::as_bytes));
+        field(Some(&(self.transactions.len() as u64).to_le_bytes()));
+        for transaction in &self.transactions {
+            field(Some(transaction.as_bytes()));
+        }
+        field(Some(&(self.contracts.len() as u64).to_le_bytes()));
+        for contract in &self.contracts {
+            field(Some(contract.as_bytes()));
+        }
+        // Só entra quando presente, para que somas gravadas antes do corpo confiram
+        if let Some(body) = &self.body {
+            field(Some(body.as_bytes()));
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    fn into_block(self) -> SqlResult<Block> {
+        let (operations, transfers) = match &self.body {
+            Some(body) => from_json(10, body)?,
+            None => (Vec::new(), Vec::new()),
+        };
+        Ok(Block {
+            index: self.height,
+            timestamp: self.timestamp,
+            transactions: self
+                .transactions
+                .iter()
+                .map(|data| from_json(0, data))
+                .collect::<SqlResult<_>>()?,
+            contracts: self
+                .contracts
+                .iter()
+                .map(|data| from_json(0, data))
+                .collect::<SqlResult<_>>()?,
+            previous_hash: self.previous_hash,
+            hash: self.hash,
+            proposer: self.proposer,
+            validator_signature: self.validator_signature,
+            nonce: self.nonce,
+            processed_transactions: from_json(7, &self.processed_transactions)?,
+            execution: self
+                .execution
+                .map(|execution| from_json(8, &execution))
+                .transpose()?,
+            operations,
+            transfers,
+        })
+    }
+}
+
+/// Grava (ou atualiza) os blocos de `chain` com índice a partir de `from_height`.
+/// Reexecutar com os mesmos blocos não cria linhas novas.
+fn write_blocks(tx: &SqlTransaction<'_>, chain: &[Block], from_height: u64) -> SqlResult<usize> {
+    let mut written = 0;
+    for block in chain.iter().filter(|block| block.index >= from_height) {
+        delete_stale_blocks(tx, "height = ?1 AND hash <> ?2", (block.index, &block.hash))?;
+        let row = BlockRow::of(block)?;
+        tx.execute(
+            "INSERT INTO blocks (height, timestamp, previous_hash, hash, proposer, validator_signature, nonce, processed_transactions, execution, checksum, body)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+             ON CONFLICT (height, hash) DO UPDATE SET
+                 timestamp = excluded.timestamp,
+                 previous_hash = excluded.previous_hash,
+                 proposer = excluded.proposer,
+                 validator_signature = excluded.validator_signature,
+                 nonce = excluded.nonce,
+                 processed_transactions = excluded.processed_transactions,
+                 execution = excluded.execution,
+                 checksum = excluded.checksum,
+                 body = excluded.body",
+            params![
+                row.height,
+                row.timestamp,
+                &row.previous_hash,
+                &row.hash,
+                &row.proposer,
+                &row.validator_signature,
+                row.nonce,
+                &row.processed_transactions,
+                &row.execution,
+                row.checksum(),
+                row.body.as_deref().map(compress).transpose()?,
+            ],
+        )?;
+        for (position, data) in row.transactions.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO block_transactions (block_hash, position, data) VALUES (?1, ?2, ?3)
+                 ON CONFLICT (block_hash, position) DO UPDATE SET data = excluded.data",
+                params![&row.hash, position, compress(data)?],
+            )?;
+        }
+        for (position, data) in row.contracts.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO block_contracts (block_hash, position, data) VALUES (?1, ?2, ?3)
+                 ON CONFLICT (block_hash, position) DO UPDATE SET data = excluded.data",
+                params![&row.hash, position, compress(data)?],
+            )?;
+        }
+        written += 1;
+    }
+
+    // Blocos acima do topo atual foram revertidos
+    let next_height = chain.last().map_or(0, |block| block.index + 1);
+    delete_stale_blocks(tx, "height >= ?1", [next_height])?;
+
+    Ok(written)
+}
+
+/// Regrava as tabelas de estado; o conteúdo anterior é substituído por inteiro para
+/// que saldos e chaves removidos não sobrevivam no banco
+fn write_state(tx: &SqlTransaction<'_>, blockchain: &Blockchain) -> SqlResult<()> {
+    tx.execute_batch(
+        "DELETE FROM chain_tokens;
+         DELETE FROM token_balances;
+         DELETE FROM stakers;
+         DELETE FROM nonces;
+         DELETE FROM public_keys;
+         DELETE FROM chain_state;
+         DELETE FROM transaction_status;",
+    )?;
+
+    for (id, token) in &blockchain.tokens {
+        tx.execute(
+            "INSERT INTO chain_tokens (id, token_id, name, symbol, total_supply, creator, quantum_crypto)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                id,
+                token.id,
+                &token.name,
+                &token.symbol,
+                token.total_supply,
+                &token.creator,
+                to_json(&token.quantum_crypto)?,
+            ],
+        )?;
+        for (address, balance) in &token.balances {
+            tx.execute(
+                "INSERT INTO token_balances (token_id, address, balance) VALUES (?1, ?2, ?3)",
+                params![id, address, balance],
+            )?;
+        }
+    }
+    for (address, amount) in &blockchain.stakers {
+        tx.execute(
+            "INSERT INTO stakers (address, amount) VALUES (?1, ?2)",
+            params![address, amount],
+        )?;
+    }
+    for (address, nonce) in &blockchain.nonces {
+        tx.execute(
+            "INSERT INTO nonces (address, nonce) VALUES (?1, ?2)",
+            params![address, nonce],
+        )?;
+    }
+    for (address, public_key) in &blockchain.public_keys {
+        tx.execute(
+            "INSERT INTO public_keys (address, public_key) VALUES (?1, ?2)",
+            params![address, public_key],
+        )?;
+    }
+    for entry in blockchain.transaction_statuses.iter() {
+        tx.execute(
+            "INSERT INTO transaction_status (txid, state, updated_at) VALUES (?1, ?2, ?3)",
+            params![&entry.txid, to_json(&entry.state)?, entry.updated_at],
+        )?;
+    }
+
+    // Demais campos, no mesmo formato do arquivo JSON
+    let state = [
+        ("format_version", to_json(&blockchain.format_version)?),
+        ("next_token_id", to_json(&blockchain.next_token_id)?),
+        (
+            "pending_transactions",
+            to_json(&blockchain.pending_transactions)?,
+        ),
+        (
+            "committed_transactions",
+            to_json(&blockchain.committed_transactions)?,
+        ),
+        (
+            "pending_operations",
+            to_json(&blockchain.pending_operations)?,
+        ),
+        ("operations", to_json(&blockchain.operations)?),
+        ("index", to_json(&blockchain.index)?),
+        ("checkpoint", to_json(&blockchain.checkpoint)?),
+        ("pruned_blocks", to_json(&blockchain.pruned_blocks)?),
+        ("storage_mode", to_json(&blockchain.storage_mode)?),
+        ("archive", to_json(&blockchain.archive)?),
+        ("validator_keys", to_json(&blockchain.validator_keys)?),
+        ("signed_from", to_json(&blockchain.signed_from)?),
+        ("view_keys", to_json(&blockchain.view_keys)?),
+        ("auditor_view_keys", to_json(&blockchain.auditor_view_keys)?),
+        (
+            "confidential_balances",
+            to_json(&blockchain.confidential_balances)?,
+        ),
+        ("roles", to_json(&blockchain.roles)?),
+        ("transfer_policies", to_json(&blockchain.transfer_policies)?),
+        ("kyc_verified", to_json(&blockchain.kyc_verified)?),
+        ("vesting_schedules", to_json(&blockchain.vesting_schedules)?),
+        ("proposals", to_json(&blockchain.proposals)?),
+        ("halt_approvals", to_json(&blockchain.halt_approvals)?),
+        ("halt_history", to_json(&blockchain.halt_history)?),
+        ("upgrade_signals", to_json(&blockchain.upgrade_signals)?),
+        ("oracle_feeds", to_json(&blockchain.oracle_feeds)?),
+        ("names", to_json(&blockchain.names)?),
+        ("account_guards", to_json(&blockchain.account_guards)?),
+        ("recoveries", to_json(&blockchain.recoveries)?),
+        ("key_rotations", to_json(&blockchain.key_rotations)?),
+        (
+            "pending_token_owners",
+            to_json(&blockchain.pending_token_owners)?,
+        ),
+        ("token_admins", to_json(&blockchain.token_admins)?),
+        ("paused_tokens", to_json(&blockchain.paused_tokens)?),
+        ("supply_history", to_json(&blockchain.supply_history)?),
+        (
+            "execution_receipts",
+            to_json(&blockchain.execution_receipts)?,
+        ),
+        ("interchain", to_json(&blockchain.interchain)?),
+    ];
+    for (key, value) in state {
+        tx.execute(
+            "INSERT INTO chain_state (key, value) VALUES (?1, ?2)",
+            params![key, value],
+        )?;
+    }
+    Ok(())
+}
+
+/// Entrega os blocos a `visit` um de cada vez, na ordem da cadeia, conferindo o
+/// checksum de cada um; blocos gravados antes das somas de verificação não têm
+/// checksum e são aceitos como estão
+fn for_each_block<E: From<rusqlite::Error>>(
+    conn: &Connection,
+    mut visit: impl FnMut(Block) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut transactions = conn
+        .prepare("SELECT data FROM block_transactions WHERE block_hash = ?1 ORDER BY position")?;
+    let mut contracts =
+        conn.prepare("SELECT data FROM block_contracts WHERE block_hash = ?1 ORDER BY position")?;
+    // Sem `ensure_schema` (leitura só), colunas ausentes são lidas como NULL
+    let mut optional = HashMap::new();
+    for column in OPTIONAL_BLOCK_COLUMNS {
+        let expression = match has_block_column(conn, column)? {
+            true => column.to_string(),
+            false => format!("NULL AS {}", column),
+        };
+        optional.insert(column, expression);
+    }
+    let mut blocks = conn.prepare(&format!(
+        "SELECT height, timestamp, previous_hash, hash, {}, validator_signature, nonce, processed_transactions, {}, {}, {}
+         FROM blocks ORDER BY height, id",
+        optional["proposer"], optional["execution"], optional["checksum"], optional["body"]
+    ))?;
+
+    let headers = blocks.query_map([], |row| {
+        let block = BlockRow {
+            height: row.get(0)?,
+            timestamp: row.get(1)?,
+            previous_hash: row.get(2)?,
+            hash: row.get(3)?,
+            proposer: row.get(4)?,
+            validator_signature: row.get(5)?,
+            nonce: row.get(6)?,
+            processed_transactions: row.get(7)?,
+            execution: row.get(8)?,
+            body: row
+                .get::<_, Option<Value>>(10)?
+                .map(|body| decompress(10, body))
+                .transpose()?,
+            transactions: Vec::new(),
+            contracts: Vec::new(),
+        };
+        Ok((block, row.get::<_, Option<String>>(9)?))
+    })?;
+
+    for header in headers {
+        let (mut block, checksum) = header?;
+        block.transactions = transactions
+            .query_map([&block.hash], |row| decompress(0, row.get(0)?))?
+            .collect::<SqlResult<_>>()?;
+        block.contracts = contracts
+            .query_map([&block.hash], |row| decompress(0, row.get(0)?))?
+            .collect::<SqlResult<_>>()?;
+        if checksum.is_some_and(|checksum| checksum != block.checksum()) {
+            return Err(rusqlite::Error::FromSqlConversionFailure(
+                9,
+                Type::Text,
+                format!(
+                    "Checksum do bloco {} ({}) não confere; o banco de dados está corrompido",
+                    block.height, block.hash
+                )
+                .into(),
+            )
+            .into());
+        }
+        visit(block.into_block()?)?;
+    }
+    Ok(())
+}
+
+fn read_blocks(conn: &Connection) -> SqlResult<Vec<Block>> {
+    let mut chain = Vec::new();
+    for_each_block(conn, |block| {
+        chain.push(block);
+        Ok::<_, rusqlite::Error>(())
+    })?;
+    Ok(chain)
+}
+
+fn read_tokens(conn: &Connection) -> SqlResult<HashMap<String, Token>> {
+    let mut balances: HashMap<String, HashMap<String, u64>> = HashMap::new();
+    let mut stmt = conn.prepare("SELECT token_id, address, balance FROM token_balances")?;
+    for row in stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, u64>(2)?,
+        ))
+    })? {
+        let (token_id, address, balance) = row?;
+        balances
+            .entry(token_id)
+            .or_default()
+            .insert(address, balance);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT id, token_id, name, symbol, total_supply, creator, quantum_crypto FROM chain_tokens",
+    )?;
+    let tokens = stmt.query_map([], |row| {
+        let id: String = row.get(0)?;
+        let quantum_crypto: String = row.get(6)?;
+        Ok((
+            id.clone(),
+            Token {
+                id: row.get(1)?,
+                name: row.get(2)?,
+                symbol: row.get(3)?,
+                total_supply: row.get(4)?,
+                balances: balances.remove(&id).unwrap_or_default(),
+                creator: row.get(5)?,
+                quantum_crypto: from_json(6, &quantum_crypto)?,
+            },
+        ))
+    })?;
+    tokens.collect()
+}
+
+fn read_statuses(conn: &Connection) -> SqlResult<TransactionStatusStore> {
+    let mut stmt = conn.prepare("SELECT txid, state, updated_at FROM transaction_status")?;
+    let rows = stmt.query_map([], |row| {
+        Ok(TransactionLifecycle {
+            txid: row.get(0)?,
+            state: from_json(1, &row.get::<_, String>(1)?)?,
+            updated_at: row.get(2)?,
+        })
+    })?;
+
+    let mut statuses = TransactionStatusStore::default();
+    for entry in rows {
+        statuses.restore(entry?);
+    }
+    Ok(statuses)
+}
+
+fn read_map<V: rusqlite::types::FromSql>(
+    conn: &Connection,
+    sql: &str,
+) -> SqlResult<HashMap<String, V>> {
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+}
+
+/// Campo de `chain_state`; chaves ausentes (bancos mais antigos) assumem o padrão,
+/// como `#[serde(default)]` no formato JSON
+fn state_field<T: DeserializeOwned + Default>(
+    state: &HashMap<String, String>,
+    key: &str,
+) -> SqlResult<T> {
+    state
+        .get(key)
+        .map_or_else(|| Ok(T::default()), |value| from_json(1, value))
+}
+
+impl Blockchain {
+    /// Salva a blockchain em um banco de dados SQLite: blocos com transações e
+    /// contratos, tokens e saldos, stakers, nonces, chaves públicas e o restante do
+    /// estado. A gravação é atômica e pode ser repetida sem duplicar linhas.
+    pub fn save_to_db(&self, db_path: &str) -> SqlResult<()> {
+        self.persist_new_blocks_since(db_path, 0).map(|_| ())
+    }
+
+    /// Grava os blocos com índice a partir de `height` e regrava o estado, sem tocar
+    /// nos blocos anteriores já persistidos. Retorna quantos blocos foram gravados.
+    ///
+    /// Blocos de outra bifurcação nas alturas gravadas, ou acima do topo atual, são
+    /// removidos do banco.
+    pub fn persist_new_blocks_since(&self, db_path: &str, height: u64) -> SqlResult<usize> {
+        if self.detached_blocks.range(height..).next().is_some() {
+            let incomplete = self.ensure_complete().unwrap_err();
+            return Err(rusqlite::Error::ToSqlConversionFailure(Box::new(
+                incomplete,
+            )));
+        }
+        let mut conn = Connection::open(db_path)?;
+        ensure_schema(&conn)?;
+
+        let tx = conn.transaction()?;
+        let written = write_blocks(&tx, &self.chain, height)?;
+        write_state(&tx, self)?;
+        tx.commit()?;
+        Ok(written)
+    }
+
+    /// Carrega a blockchain de um banco de dados SQLite gravado por `save_to_db`
+    pub fn load_from_db(db_path: &str) -> SqlResult<Self> {
+        let conn = Connection::open(db_path)?;
+        ensure_schema(&conn)?;
+
+        let state = read_chain_state(&conn)?;
+        let chain = read_blocks(&conn)?;
+        assemble(&conn, &state, chain)
+    }
+
+    /// Percorre os blocos gravados por `save_to_db`, lidos do banco um de cada vez
+    pub fn for_each_block_in_db(
+        db_path: &str,
+        visit: impl FnMut(Block) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let conn = Connection::open(db_path)?;
+        ensure_schema(&conn)?;
+        for_each_block(&conn, visit)
+    }
+
+    /// Como `load_recent_from_file`, para um banco gravado por `save_to_db`: o
+    /// estado é lido por inteiro e os blocos antigos ficam só com o cabeçalho
+    pub fn load_recent_from_db(db_path: &str, keep_blocks: usize) -> Result<Self, Error> {
+        let conn = Connection::open(db_path)?;
+        ensure_schema(&conn)?;
+
+        let state = read_chain_state(&conn)?;
+        let mut window = RecentWindow::new(keep_blocks);
+        for_each_block(&conn, |block| window.push(block))?;
+        let mut blockchain = assemble(&conn, &state, Vec::new())?;
+        window.finish(&mut blockchain)?;
+        Ok(blockchain)
+    }
+}
+
+/// Lê um banco sem alterá-lo: aberto só para leitura, sem criar tabelas nem
+/// colunas, e dentro de uma transação de leitura para que um nó gravando ao
+/// mesmo tempo não deixe a foto pela metade
+pub(super) fn read_only(db_path: &Path) -> SqlResult<Blockchain> {
+    let mut conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let tx = conn.transaction()?;
+    let state = read_chain_state(&tx)?;
+    let chain = read_blocks(&tx)?;
+    assemble(&tx, &state, chain)
+}
+
+/// Lê `chain_state`, recusando bancos de formato mais novo que o suportado
+fn read_chain_state(conn: &Connection) -> SqlResult<HashMap<String, String>> {
+    let state: HashMap<String, String> = read_map(conn, "SELECT key, value FROM chain_state")?;
+    // As mudanças de formato até aqui só acrescentaram campos, que assumem o
+    // padrão quando ausentes; um banco de versão mais nova pode ter mudado o
+    // significado de algum e não é aberto
+    let format_version: u32 = state_field(&state, "format_version")?;
+    if format_version > BLOCKCHAIN_FORMAT_VERSION {
+        return Err(rusqlite::Error::FromSqlConversionFailure(
+            1,
+            Type::Integer,
+            format!(
+                "Banco no formato {} mais novo que o suportado ({})",
+                format_version, BLOCKCHAIN_FORMAT_VERSION
+            )
+            .into(),
+        ));
+    }
+    Ok(state)
+}
+
+/// Monta a blockchain com `chain` e o estado gravado no banco
+fn assemble(
+    conn: &Connection,
+    state: &HashMap<String, String>,
+    chain: Vec<Block>,
+) -> SqlResult<Blockchain> {
+    Ok(Blockchain {
+        format_version: BLOCKCHAIN_FORMAT_VERSION,
+        chain,
+        base_height: 0,
+        tokens: read_tokens(conn)?,
+        stakers: read_map(conn, "SELECT address, amount FROM stakers")?,
+        nonces: read_map(conn, "SELECT address, nonce FROM nonces")?,
+        pending_transactions: state_field(state, "pending_transactions")?,
+        committed_transactions: state_field(state, "committed_transactions")?,
+        pending_operations: state_field(state, "pending_operations")?,
+        operations: state_field(state, "operations")?,
+        index: state_field(state, "index")?,
+        transaction_statuses: read_statuses(conn)?,
+        next_token_id: state_field(state, "next_token_id")?,
+        public_keys: read_map(conn, "SELECT address, public_key FROM public_keys")?,
+        validator: Validator::new(MAX_BLOCK_SIZE, 300),
+        limits: Limits::default(),
+        throttle: SubmissionThrottle::default(),
+        confirmation_depth: DEFAULT_CONFIRMATION_DEPTH,
+        secret_keys: HashMap::new(),
+        checkpoint: state_field(state, "checkpoint")?,
+        pruned_blocks: state_field(state, "pruned_blocks")?,
+        storage_mode: state_field(state, "storage_mode")?,
+        archive: state_field(state, "archive")?,
+        validator_keys: state_field(state, "validator_keys")?,
+        signed_from: state_field(state, "signed_from")?,
+        view_keys: state_field(state, "view_keys")?,
+        auditor_view_keys: state_field(state, "auditor_view_keys")?,
+        confidential_balances: state_field(state, "confidential_balances")?,
+        roles: state_field(state, "roles")?,
+        transfer_policies: state_field(state, "transfer_policies")?,
+        kyc_verified: state_field(state, "kyc_verified")?,
+        vesting_schedules: state_field(state, "vesting_schedules")?,
+        proposals: state_field(state, "proposals")?,
+        halt_approvals: state_field(state, "halt_approvals")?,
+        halt_history: state_field(state, "halt_history")?,
+        upgrade_signals: state_field(state, "upgrade_signals")?,
+        client_version: ClientVersion::current(),
+        oracle_feeds: state_field(state, "oracle_feeds")?,
+        names: state_field(state, "names")?,
+        account_guards: state_field(state, "account_guards")?,
+        recoveries: state_field(state, "recoveries")?,
+        key_rotations: state_field(state, "key_rotations")?,
+        pending_token_owners: state_field(state, "pending_token_owners")?,
+        token_admins: state_field(state, "token_admins")?,
+        paused_tokens: state_field(state, "paused_tokens")?,
+        supply_history: state_field(state, "supply_history")?,
+        execution_receipts: state_field(state, "execution_receipts")?,
+        revert_points: BTreeMap::new(),
+        interchain: state_field(state, "interchain")?,
+        detached_blocks: BTreeMap::new(),
+        signer: None,
+        clock: None,
+        events: EventBus::default(),
+    })
+}
@@ -0,0 +1,51 @@
+use super::blockchain::Blockchain;
+use crate::error::Error;
+use crate::token::BalanceSnapshot;
+
+impl Blockchain {
+    /// Adiciona um staker à blockchain.
+    ///
+    /// O stake passa a contar a partir do próximo bloco: snapshots da altura atual
+    /// e anteriores não o enxergam.
+    pub fn add_staker(&mut self, address: String, amount: u64) {
+        let current = self.stakers.entry(address).or_insert(0);
+        *current += amount;
+        self.index.record_stakes(self.height(), &self.stakers);
+    }
+
+    /// Desvincula `amount` do stake de `address`; quem fica sem stake sai da lista
+    pub fn unbond_stake(&mut self, address: &str, amount: u64) -> Result<(), Error> {
+        let bonded = self.stakers.get(address).copied().unwrap_or(0);
+        let remaining = bonded
+            .checked_sub(amount)
+            .ok_or(Error::InsufficientBalance)?;
+        if remaining == 0 {
+            self.stakers.remove(address);
+        } else {
+            self.stakers.insert(address.to_string(), remaining);
+        }
+        self.index.record_stakes(self.height(), &self.stakers);
+        Ok(())
+    }
+
+    /// Stake vinculado após o bloco em `height`, como snapshot do token nativo em
+    /// que ele é denominado
+    pub fn stake_snapshot(&self, height: u64) -> Result<BalanceSnapshot, Error> {
+        if self.chain.last().is_none_or(|block| height > block.index) {
+            return Err(Error::InvalidInput(format!(
+                "Altura {} ainda não foi confirmada",
+                height
+            )));
+        }
+        self.stakes_at(height).ok_or_else(|| {
+            Error::InvalidInput(format!("Histórico de stake não cobre a altura {}", height))
+        })
+    }
+
+    /// Como `stake_snapshot`, sem exigir que `height` já esteja confirmada; usado
+    /// enquanto o bloco que a confirma ainda está sendo aplicado
+    pub(super) fn stakes_at(&self, height: u64) -> Option<BalanceSnapshot> {
+        let stakes = self.index.stakes_at(height)?;
+        Some(BalanceSnapshot::new(0, height, stakes))
+    }
+}
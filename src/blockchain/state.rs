@@ -0,0 +1,268 @@
+use super::account_guard::AccountGuard;
+use super::block::Block;
+use super::blockchain::{Address, Blockchain};
+use super::governance::Proposal;
+use super::halt::HaltRecord;
+use super::key_rotation::KeyRotation;
+use super::names::NameRecord;
+use super::oracle::OracleFeed;
+use super::recovery::AccountRecovery;
+use super::upgrade::UpgradeSignal;
+use crate::error::Error;
+use crate::interchain::InterchainState;
+use crate::rbac::AdminRole;
+use crate::token::{Token, VestingSchedule};
+use crate::transaction::{Operation, Transaction, TransferRule};
+use log::warn;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Estado que a execução de blocos lê e altera, com o mempool, cujos nonces e IDs
+/// de token já estão reservados nele, mas sem o histórico (blocos, índice,
+/// recibos) nem a configuração do nó.
+///
+/// Copiá-lo custa o tamanho do estado, não o da cadeia. Guarda também o tamanho
+/// dos históricos que a execução só estende, para que `restore_state` os corte
+/// no ponto em que o estado foi tomado.
+pub struct ChainState {
+    tokens: HashMap<String, Token>,
+    stakers: HashMap<Address, u64>,
+    nonces: HashMap<Address, u64>,
+    next_token_id: u64,
+    public_keys: HashMap<String, Vec<u8>>,
+    validator_keys: HashMap<Address, Vec<u8>>,
+    signed_from: Option<u64>,
+    view_keys: HashMap<Address, Vec<u8>>,
+    auditor_view_keys: Vec<Vec<u8>>,
+    confidential_balances: HashMap<String, HashMap<Address, Vec<u8>>>,
+    roles: HashMap<Address, BTreeSet<AdminRole>>,
+    transfer_policies: HashMap<String, Vec<TransferRule>>,
+    kyc_verified: BTreeSet<Address>,
+    vesting_schedules: Vec<VestingSchedule>,
+    proposals: Vec<Proposal>,
+    halt_approvals: BTreeMap<Address, String>,
+    halt_history: Vec<HaltRecord>,
+    upgrade_signals: Vec<UpgradeSignal>,
+    oracle_feeds: BTreeMap<u64, OracleFeed>,
+    names: BTreeMap<String, NameRecord>,
+    account_guards: BTreeMap<Address, AccountGuard>,
+    recoveries: BTreeMap<Address, AccountRecovery>,
+    key_rotations: BTreeMap<Address, Vec<KeyRotation>>,
+    pending_token_owners: HashMap<String, Address>,
+    token_admins: HashMap<String, BTreeSet<Address>>,
+    paused_tokens: BTreeSet<String>,
+    interchain: InterchainState,
+    pending_operations: Vec<Operation>,
+    pending_transactions: Vec<Transaction>,
+    operations: usize,
+    committed_transactions: usize,
+    supply_history: usize,
+}
+
+impl ChainState {
+    /// Cópia do estado guardado; ver `Token::try_clone`
+    pub(super) fn try_clone(&self) -> Result<Self, Error> {
+        Ok(ChainState {
+            tokens: clone_tokens(&self.tokens)?,
+            stakers: self.stakers.clone(),
+            nonces: self.nonces.clone(),
+            next_token_id: self.next_token_id,
+            public_keys: self.public_keys.clone(),
+            validator_keys: self.validator_keys.clone(),
+            signed_from: self.signed_from,
+            view_keys: self.view_keys.clone(),
+            auditor_view_keys: self.auditor_view_keys.clone(),
+            confidential_balances: self.confidential_balances.clone(),
+            roles: self.roles.clone(),
+            transfer_policies: self.transfer_policies.clone(),
+            kyc_verified: self.kyc_verified.clone(),
+            vesting_schedules: self.vesting_schedules.clone(),
+            proposals: self.proposals.clone(),
+            halt_approvals: self.halt_approvals.clone(),
+            halt_history: self.halt_history.clone(),
+            upgrade_signals: self.upgrade_signals.clone(),
+            oracle_feeds: self.oracle_feeds.clone(),
+            names: self.names.clone(),
+            account_guards: self.account_guards.clone(),
+            recoveries: self.recoveries.clone(),
+            key_rotations: self.key_rotations.clone(),
+            pending_token_owners: self.pending_token_owners.clone(),
+            token_admins: self.token_admins.clone(),
+            paused_tokens: self.paused_tokens.clone(),
+            interchain: self.interchain.clone(),
+            pending_operations: self.pending_operations.clone(),
+            pending_transactions: self.pending_transactions.clone(),
+            operations: self.operations,
+            committed_transactions: self.committed_transactions,
+            supply_history: self.supply_history,
+        })
+    }
+}
+
+fn clone_tokens(tokens: &HashMap<String, Token>) -> Result<HashMap<String, Token>, Error> {
+    tokens
+        .iter()
+        .map(|(id, token)| Ok((id.clone(), token.try_clone()?)))
+        .collect()
+}
+
+impl Blockchain {
+    /// Cópia do estado atual; falha só se o liboqs não instanciar os algoritmos
+    /// dos tokens (ver `Token::try_clone`)
+    pub(super) fn chain_state(&self) -> Result<ChainState, Error> {
+        Ok(ChainState {
+            tokens: clone_tokens(&self.tokens)?,
+            stakers: self.stakers.clone(),
+            nonces: self.nonces.clone(),
+            next_token_id: self.next_token_id,
+            public_keys: self.public_keys.clone(),
+            validator_keys: self.validator_keys.clone(),
+            signed_from: self.signed_from,
+            view_keys: self.view_keys.clone(),
+            auditor_view_keys: self.auditor_view_keys.clone(),
+            confidential_balances: self.confidential_balances.clone(),
+            roles: self.roles.clone(),
+            transfer_policies: self.transfer_policies.clone(),
+            kyc_verified: self.kyc_verified.clone(),
+            vesting_schedules: self.vesting_schedules.clone(),
+            proposals: self.proposals.clone(),
+            halt_approvals: self.halt_approvals.clone(),
+            halt_history: self.halt_history.clone(),
+            upgrade_signals: self.upgrade_signals.clone(),
+            oracle_feeds: self.oracle_feeds.clone(),
+            names: self.names.clone(),
+            account_guards: self.account_guards.clone(),
+            recoveries: self.recoveries.clone(),
+            key_rotations: self.key_rotations.clone(),
+            pending_token_owners: self.pending_token_owners.clone(),
+            token_admins: self.token_admins.clone(),
+            paused_tokens: self.paused_tokens.clone(),
+            interchain: self.interchain.clone(),
+            pending_operations: self.pending_operations.clone(),
+            pending_transactions: self.pending_transactions.clone(),
+            operations: self.operations.len(),
+            committed_transactions: self.committed_transactions.len(),
+            supply_history: self.supply_history.len(),
+        })
+    }
+
+    /// Substitui o estado atual e o mempool por `state`, sem tocar no histórico
+    pub(super) fn set_chain_state(&mut self, state: ChainState) {
+        self.tokens = state.tokens;
+        self.stakers = state.stakers;
+        self.nonces = state.nonces;
+        self.next_token_id = state.next_token_id;
+        self.public_keys = state.public_keys;
+        self.validator_keys = state.validator_keys;
+        self.signed_from = state.signed_from;
+        self.view_keys = state.view_keys;
+        self.auditor_view_keys = state.auditor_view_keys;
+        self.confidential_balances = state.confidential_balances;
+        self.roles = state.roles;
+        self.transfer_policies = state.transfer_policies;
+        self.kyc_verified = state.kyc_verified;
+        self.vesting_schedules = state.vesting_schedules;
+        self.proposals = state.proposals;
+        self.halt_approvals = state.halt_approvals;
+        self.halt_history = state.halt_history;
+        self.upgrade_signals = state.upgrade_signals;
+        self.oracle_feeds = state.oracle_feeds;
+        self.names = state.names;
+        self.account_guards = state.account_guards;
+        self.recoveries = state.recoveries;
+        self.key_rotations = state.key_rotations;
+        self.pending_token_owners = state.pending_token_owners;
+        self.token_admins = state.token_admins;
+        self.paused_tokens = state.paused_tokens;
+        self.interchain = state.interchain;
+        self.pending_operations = state.pending_operations;
+        self.pending_transactions = state.pending_transactions;
+    }
+
+    /// Volta ao estado `state`, tomado antes do bloco em `height` ou de um bloco
+    /// acima dele sem blocos de corpo executado no meio, para executar a cadeia
+    /// de novo a partir de `height`.
+    ///
+    /// Os históricos que a execução estende voltam ao tamanho que tinham então, e
+    /// o índice esquece os saldos e stakes registrados a partir de `height`, com
+    /// o stake restaurado valendo a partir dela. Blocos, recibos e registros de
+    /// itens ficam com `truncate_chain`.
+    pub(super) fn restore_state(&mut self, state: ChainState, height: u64) {
+        self.operations.truncate(state.operations);
+        self.committed_transactions
+            .truncate(state.committed_transactions);
+        self.supply_history.truncate(state.supply_history);
+        self.set_chain_state(state);
+        self.index.truncate_history(height);
+        self.index.record_stakes(height, &self.stakers);
+    }
+
+    /// Guarda o estado atual como ponto de retorno de `block`, prestes a ter o
+    /// corpo executado; blocos sem corpo não precisam de um
+    pub(super) fn save_revert_point(&mut self, block: &Block) {
+        if block.operations.is_empty() && block.transfers.is_empty() {
+            return;
+        }
+        let state = self.chain_state();
+        self.keep_revert_point(block.index, state);
+    }
+
+    /// Guarda `state` como ponto de retorno do bloco em `height` e descarta os de
+    /// blocos que já terão `confirmation_depth` blocos por cima
+    pub(super) fn keep_revert_point(&mut self, height: u64, state: Result<ChainState, Error>) {
+        match state {
+            Ok(state) => {
+                self.revert_points.insert(height, state);
+            }
+            Err(e) => warn!(
+                "Estado anterior ao bloco {} não foi guardado; ele não poderá ser revertido: {}",
+                height, e
+            ),
+        }
+        let oldest = (height + 1).saturating_sub(self.confirmation_depth);
+        self.revert_points = self.revert_points.split_off(&oldest);
+    }
+
+    /// Ponto de retorno guardado para o bloco em `height`
+    pub(super) fn revert_point(&self, height: u64) -> Result<ChainState, Error> {
+        self.revert_points
+            .get(&height)
+            .ok_or_else(|| {
+                Error::InvalidBlock(format!(
+                    "Estado anterior ao bloco {} não está mais disponível para revertê-lo",
+                    height
+                ))
+            })?
+            .try_clone()
+    }
+
+    /// Cópia descartável para executar blocos a partir da altura `height`, que
+    /// não passa do topo.
+    ///
+    /// Leva o estado atual, o mempool, o checkpoint e a configuração do nó; da
+    /// cadeia, só o bloco anterior a `height`, e do índice, só o histórico de
+    /// stake. Repetições de transações legadas dos blocos omitidos não são vistas
+    /// pela cópia e ficam a cargo de quem a usa (ver `includes_transaction`).
+    pub(super) fn scratch_at(&self, height: u64) -> Result<Self, Error> {
+        let mut scratch = Self::empty();
+        scratch.set_chain_state(self.chain_state()?);
+        let parent = height
+            .checked_sub(1)
+            .and_then(|parent| parent.checked_sub(self.base_height))
+            .and_then(|position| self.chain.get(position as usize));
+        scratch.base_height = height.saturating_sub(u64::from(parent.is_some()));
+        scratch.chain = parent.cloned().into_iter().collect();
+        scratch.format_version = self.format_version;
+        scratch.index = self.index.stake_history();
+        scratch.checkpoint = self.checkpoint.clone();
+        scratch.limits = self.limits;
+        scratch.confirmation_depth = self.confirmation_depth;
+        scratch.client_version = self.client_version;
+        scratch.clock = self.clock.clone();
+        Ok(scratch)
+    }
+
+    /// `scratch_at` no topo: cópia para reexecutar o próximo bloco
+    pub(super) fn scratch_copy(&self) -> Result<Self, Error> {
+        self.scratch_at(self.height())
+    }
+}
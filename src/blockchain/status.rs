@@ -0,0 +1,112 @@
+use super::blockchain::Blockchain;
+use crate::utils::clock::clock;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Etapa do ciclo de vida de uma transação no nó
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum LifecycleState {
+    /// Admitida no mempool
+    Pending,
+    /// Aplicada no bloco em `height`
+    Included { height: u64 },
+    /// O bloco em `height` está coberto por um checkpoint
+    Finalized { height: u64 },
+    /// Saiu do mempool ou da cadeia sem efeito: descartada ao fechar o bloco ou
+    /// revertida por uma reorganização
+    Dropped {
+        /// Código estável do erro que a descartou, quando houve um
+        code: Option<u32>,
+        reason: String,
+    },
+}
+
+/// Situação atual de uma transação e quando ela mudou pela última vez
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionLifecycle {
+    pub txid: String,
+    pub state: LifecycleState,
+    /// Segundos desde a época, no relógio do nó
+    pub updated_at: i64,
+}
+
+/// Ciclo de vida de todas as transações vistas pelo nó, por txid.
+///
+/// Persistido com a cadeia (tabela `transaction_status` no SQLite); cada
+/// transição substitui a anterior, sem histórico.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransactionStatusStore {
+    entries: HashMap<String, TransactionLifecycle>,
+}
+
+impl TransactionStatusStore {
+    pub fn set(&mut self, txid: String, state: LifecycleState) {
+        let entry = TransactionLifecycle {
+            txid: txid.clone(),
+            state,
+            updated_at: clock().now_secs(),
+        };
+        self.entries.insert(txid, entry);
+    }
+
+    /// Insere uma entrada como foi gravada, preservando `updated_at`
+    pub fn restore(&mut self, entry: TransactionLifecycle) {
+        self.entries.insert(entry.txid.clone(), entry);
+    }
+
+    pub fn get(&self, txid: &str) -> Option<&TransactionLifecycle> {
+        self.entries.get(txid)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &TransactionLifecycle> {
+        self.entries.values()
+    }
+
+    /// Marca como finalizadas as transações incluídas até `height`
+    pub fn finalize_through(&mut self, height: u64) -> usize {
+        let now = clock().now_secs();
+        let mut finalized = 0;
+        for entry in self.entries.values_mut() {
+            if let LifecycleState::Included { height: included } = entry.state {
+                if included <= height {
+                    entry.state = LifecycleState::Finalized { height: included };
+                    entry.updated_at = now;
+                    finalized += 1;
+                }
+            }
+        }
+        finalized
+    }
+
+    /// Dá como descartadas as transações incluídas a partir de `height`
+    pub fn revert_from(&mut self, height: u64, reason: &str) {
+        let now = clock().now_secs();
+        for entry in self.entries.values_mut() {
+            if let LifecycleState::Included { height: included } = entry.state {
+                if included >= height {
+                    entry.state = LifecycleState::Dropped {
+                        code: None,
+                        reason: reason.to_string(),
+                    };
+                    entry.updated_at = now;
+                }
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Blockchain {
+    pub fn transaction_status(&self, txid: &str) -> Option<&TransactionLifecycle> {
+        self.transaction_statuses.get(txid)
+    }
+}
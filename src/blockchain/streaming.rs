@@ -0,0 +1,269 @@
+use super::block::Block;
+use super::blockchain::Blockchain;
+use super::export;
+use super::format::BLOCKCHAIN_FORMAT_VERSION;
+use super::merkle::MerkleHash;
+use super::pruning::signatures_digest;
+use crate::error::Error;
+use crate::transaction::VerificationService;
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// O que se guarda de um bloco mantido só com o cabeçalho para que o resumo da
+/// cadeia e a raiz dos cabeçalhos continuem iguais aos do bloco inteiro
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetachedBlock {
+    pub signatures_digest: String,
+    pub transactions_root: MerkleHash,
+    /// Raiz dos contratos descartados, coberta pela assinatura do proponente
+    pub contracts_root: MerkleHash,
+}
+
+/// Monta a cadeia a partir de blocos lidos em ordem, mantendo inteiros só os
+/// `keep` mais recentes. Dos anteriores fica o cabeçalho; como o corpo é
+/// descartado, encadeamento, hash e assinaturas das transações de cada um são
+/// conferidos na passagem, no lugar de `is_chain_valid`.
+pub(super) struct RecentWindow {
+    keep: usize,
+    headers: Vec<Block>,
+    recent: VecDeque<Block>,
+    detached: BTreeMap<u64, DetachedBlock>,
+    previous_hash: Option<String>,
+    // Blocos descartados com assinaturas vazias; só valem se estiverem podados
+    unsigned: Vec<u64>,
+}
+
+impl RecentWindow {
+    pub(super) fn new(keep: usize) -> Self {
+        Self {
+            keep,
+            headers: Vec::new(),
+            recent: VecDeque::new(),
+            detached: BTreeMap::new(),
+            previous_hash: None,
+            unsigned: Vec::new(),
+        }
+    }
+
+    pub(super) fn push(&mut self, block: Block) -> Result<(), Error> {
+        // Como em `is_chain_valid`, o gênesis não tem hash a recalcular
+        if let Some(previous_hash) = &self.previous_hash {
+            if block.previous_hash != *previous_hash {
+                return Err(Error::InvalidBlock(format!(
+                    "Bloco {} não se encadeia ao anterior",
+                    block.index
+                )));
+            }
+            if block.compute_hash()? != block.hash {
+                return Err(Error::InvalidBlock(format!(
+                    "Hash do bloco {} não confere com o conteúdo",
+                    block.index
+                )));
+            }
+        }
+        self.previous_hash = Some(block.hash.clone());
+
+        self.recent.push_back(block);
+        if self.recent.len() > self.keep {
+            if let Some(mut old) = self.recent.pop_front() {
+                self.verify_signatures(&old)?;
+                let detached = DetachedBlock {
+                    signatures_digest: signatures_digest(&old),
+                    transactions_root: old.transactions_root(),
+                    contracts_root: old.contracts_root(),
+                };
+                self.detached.insert(old.index, detached);
+                old.transactions = Vec::new();
+                old.contracts = Vec::new();
+                self.headers.push(old);
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_signatures(&mut self, block: &Block) -> Result<(), Error> {
+        let (unsigned, signed): (Vec<_>, Vec<_>) = block
+            .transactions
+            .iter()
+            .cloned()
+            .partition(|tx| tx.signature.is_empty());
+        if !unsigned.is_empty() {
+            self.unsigned.push(block.index);
+        }
+        for verified in VerificationService::global().verify_secure_batch(&signed) {
+            if !verified.unwrap_or(false) {
+                return Err(Error::InvalidBlock(format!(
+                    "Bloco {} traz transação com assinatura inválida",
+                    block.index
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn finish(self, blockchain: &mut Blockchain) -> Result<(), Error> {
+        if let Some(height) = self
+            .unsigned
+            .iter()
+            .find(|height| !blockchain.pruned_blocks.contains_key(height))
+        {
+            return Err(Error::InvalidBlock(format!(
+                "Bloco {} traz transações sem assinatura e não foi podado",
+                height
+            )));
+        }
+        let mut chain = self.headers;
+        chain.extend(self.recent);
+        blockchain.chain = chain;
+        blockchain.detached_blocks = self.detached;
+        Ok(())
+    }
+}
+
+/// Percorre o array `chain` do documento sem materializá-lo
+struct Blocks<'a, F> {
+    visit: &'a mut F,
+    failure: &'a mut Option<Error>,
+}
+
+impl<'de, F: FnMut(Block) -> Result<(), Error>> DeserializeSeed<'de> for Blocks<'_, F> {
+    type Value = ();
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, F: FnMut(Block) -> Result<(), Error>> Visitor<'de> for Blocks<'_, F> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("lista de blocos")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(block) = seq.next_element::<Block>()? {
+            if let Err(e) = (self.visit)(block) {
+                let message = e.to_string();
+                *self.failure = Some(e);
+                return Err(de::Error::custom(message));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Documento da blockchain: os blocos vão para `visit`, o resto fica em `state`
+struct Document<'a, F> {
+    visit: &'a mut F,
+    failure: &'a mut Option<Error>,
+}
+
+impl<'de, F: FnMut(Block) -> Result<(), Error>> Visitor<'de> for Document<'_, F> {
+    type Value = Map<String, Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("objeto da blockchain")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut state = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if key != "chain" {
+                state.insert(key, map.next_value()?);
+                continue;
+            }
+            // `save_to_file` grava a versão antes da cadeia; formatos antigos
+            // precisam da migração de `from_json`, que lê o documento inteiro
+            let version = state.get("format_version").and_then(Value::as_u64);
+            if version != Some(BLOCKCHAIN_FORMAT_VERSION as u64) {
+                let message = format!(
+                    "Leitura em fluxo exige o formato {}; use load_from_file para migrar o arquivo",
+                    BLOCKCHAIN_FORMAT_VERSION
+                );
+                *self.failure = Some(Error::InvalidFormat(message.clone()));
+                return Err(de::Error::custom(message));
+            }
+            map.next_value_seed(Blocks {
+                visit: &mut *self.visit,
+                failure: &mut *self.failure,
+            })?;
+        }
+        Ok(state)
+    }
+}
+
+/// Lê o arquivo JSON da blockchain entregando cada bloco a `visit`, na ordem da
+/// cadeia; devolve os demais campos do documento
+fn stream_file<F: FnMut(Block) -> Result<(), Error>>(
+    path: &Path,
+    mut visit: F,
+) -> Result<Map<String, Value>, Error> {
+    let file = File::open(path)
+        .map_err(|e| Error::Other(format!("Falha ao ler o arquivo {}: {}", path.display(), e)))?;
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(file));
+    let mut failure = None;
+    let result = de::Deserializer::deserialize_map(
+        &mut deserializer,
+        Document {
+            visit: &mut visit,
+            failure: &mut failure,
+        },
+    )
+    .and_then(|state| deserializer.end().map(|_| state));
+    match (result, failure) {
+        (_, Some(e)) => Err(e),
+        (result, None) => Ok(result?),
+    }
+}
+
+impl Blockchain {
+    /// Percorre os blocos de um arquivo gravado por `save_to_file` sem carregá-lo
+    /// inteiro; a memória usada não cresce com o histórico
+    pub fn for_each_block_in_file(
+        filename: &str,
+        visit: impl FnMut(Block) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        stream_file(Path::new(filename), visit).map(|_| ())
+    }
+
+    /// Carrega um arquivo gravado por `save_to_file` mantendo em memória o corpo
+    /// só dos `keep_blocks` blocos mais recentes; dos demais fica o cabeçalho.
+    ///
+    /// O arquivo é lido em fluxo, inclusive para conferir a assinatura, se houver.
+    /// A cadeia resultante não pode ser regravada por inteiro: ver `detached_blocks`.
+    pub fn load_recent_from_file(filename: &str, keep_blocks: usize) -> Result<Self, Error> {
+        let path = Path::new(filename);
+        export::verify_file(path, None)?;
+
+        let mut window = RecentWindow::new(keep_blocks);
+        let mut state = stream_file(path, |block| window.push(block))?;
+        state.insert("chain".to_string(), Value::Array(Vec::new()));
+        let mut blockchain = Blockchain::from_value(Value::Object(state))?;
+        window.finish(&mut blockchain)?;
+        Ok(blockchain)
+    }
+
+    /// Blocos mantidos só com o cabeçalho, cujo corpo ficou no disco
+    pub fn is_detached(&self, height: u64) -> bool {
+        self.detached_blocks.contains_key(&height)
+    }
+
+    /// Falha se a cadeia em memória não tiver o corpo de todos os blocos: gravá-la
+    /// por inteiro substituiria os blocos no disco por cabeçalhos vazios
+    pub(super) fn ensure_complete(&self) -> Result<(), Error> {
+        match self.detached_blocks.keys().next_back() {
+            Some(height) => Err(Error::Other(format!(
+                "Cadeia carregada só com os blocos recentes (corpos até a altura {} ficaram no disco); \
+                 não pode ser gravada por inteiro",
+                height
+            ))),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use super::blockchain::Blockchain;
+use crate::transaction::OperationKind;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Natureza de uma mudança no supply de um token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum SupplyChangeKind {
+    /// Supply inicial, na criação do token
+    Issue,
+    Mint,
+    Burn,
+}
+
+/// Entrada do histórico de emissão e queima
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SupplyChange {
+    pub token_id: String,
+    pub height: u64,
+    pub kind: SupplyChangeKind,
+    pub amount: u64,
+    pub reason: String,
+}
+
+/// Resultado da conferência de supply de um token.
+///
+/// `circulating` soma saldos públicos, saldo blindado e o que está preso em
+/// cronogramas de liberação; deve bater com `total_supply`, que por sua vez deve
+/// bater com o histórico (`expected_supply`). Tokens criados antes do histórico
+/// não têm `expected_supply`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SupplyReport {
+    pub token_id: String,
+    pub symbol: String,
+    pub total_supply: u64,
+    pub public_balances: u64,
+    /// Blindado por `Shield` e ainda não devolvido por `Unshield`
+    pub shielded: u64,
+    pub vesting_locked: u64,
+    pub circulating: u64,
+    pub issued: u64,
+    pub minted: u64,
+    pub burned: u64,
+    pub expected_supply: Option<u64>,
+}
+
+impl SupplyReport {
+    pub fn is_consistent(&self) -> bool {
+        self.circulating == self.total_supply
+            && self
+                .expected_supply
+                .is_none_or(|expected| expected == self.total_supply)
+    }
+}
+
+fn saturating_sum(values: impl Iterator<Item = u64>) -> u64 {
+    values.fold(0, u64::saturating_add)
+}
+
+impl Blockchain {
+    /// Registra emissão ou queima de `token_id` na altura atual
+    pub fn record_supply_change(
+        &mut self,
+        token_id: &str,
+        kind: SupplyChangeKind,
+        amount: u64,
+        reason: &str,
+    ) {
+        self.supply_history.push(SupplyChange {
+            token_id: token_id.to_string(),
+            height: self.height(),
+            kind,
+            amount,
+            reason: reason.to_string(),
+        });
+    }
+
+    /// Recalcula o supply em circulação de cada token a partir dos saldos e o
+    /// compara com `total_supply` e com o histórico de emissão e queima
+    pub fn supply_audit(&self) -> Vec<SupplyReport> {
+        let mut shielded: BTreeMap<String, i128> = BTreeMap::new();
+        for operation in &self.operations {
+            match &operation.kind {
+                OperationKind::Shield {
+                    token_id, amount, ..
+                } => *shielded.entry(token_id.to_string()).or_default() += i128::from(*amount),
+                OperationKind::Unshield {
+                    token_id, amount, ..
+                } => *shielded.entry(token_id.to_string()).or_default() -= i128::from(*amount),
+                _ => {}
+            }
+        }
+
+        let mut reports: Vec<SupplyReport> = self
+            .tokens
+            .iter()
+            .map(|(token_id, token)| {
+                let public_balances = saturating_sum(token.balances.values().copied());
+                let vesting_locked = saturating_sum(
+                    self.vesting_schedules
+                        .iter()
+                        .filter(|schedule| schedule.token_id.to_string() == *token_id)
+                        .map(|schedule| schedule.amount - schedule.claimed),
+                );
+                let shielded = shielded
+                    .get(token_id)
+                    .map_or(0, |amount| u64::try_from(*amount).unwrap_or(0));
+
+                let history = self
+                    .supply_history
+                    .iter()
+                    .filter(|change| change.token_id == *token_id);
+                let (mut issued, mut minted, mut burned) = (0u64, 0u64, 0u64);
+                let mut has_issue = false;
+                for change in history {
+                    match change.kind {
+                        SupplyChangeKind::Issue => {
+                            has_issue = true;
+                            issued = issued.saturating_add(change.amount);
+                        }
+                        SupplyChangeKind::Mint => minted = minted.saturating_add(change.amount),
+                        SupplyChangeKind::Burn => burned = burned.saturating_add(change.amount),
+                    }
+                }
+
+                SupplyReport {
+                    token_id: token_id.clone(),
+                    symbol: token.symbol.clone(),
+                    total_supply: token.total_supply,
+                    public_balances,
+                    shielded,
+                    vesting_locked,
+                    circulating: saturating_sum(
+                        [public_balances, shielded, vesting_locked].into_iter(),
+                    ),
+                    issued,
+                    minted,
+                    burned,
+                    expected_supply: has_issue
+                        .then(|| issued.saturating_add(minted).saturating_sub(burned)),
+                }
+            })
+            .collect();
+        reports.sort_by_key(|report| report.token_id.parse::<u64>().unwrap_or(u64::MAX));
+        reports
+    }
+}
@@ -0,0 +1,50 @@
+use crate::config::Limits;
+use crate::error::TransactionError;
+use std::collections::{HashMap, VecDeque};
+
+/// Submissões recentes admitidas no mempool, por remetente.
+///
+/// Só guarda o instante (em segundos) de cada admissão dentro da janela de
+/// `Limits::submission_window_secs`; é estado do nó, não da cadeia, e recomeça
+/// vazio a cada carga.
+#[derive(Debug, Clone, Default)]
+pub struct SubmissionThrottle {
+    recent: HashMap<String, VecDeque<i64>>,
+}
+
+impl SubmissionThrottle {
+    /// Admissões de `sender` ainda dentro da janela que termina em `now`
+    pub fn recent_submissions(&self, sender: &str, now: i64, limits: &Limits) -> usize {
+        let window_start = now - limits.submission_window_secs as i64;
+        self.recent.get(sender).map_or(0, |times| {
+            times.iter().filter(|&&time| time > window_start).count()
+        })
+    }
+
+    /// Recusa `sender` se ele já atingiu `max_submissions_per_window` na janela
+    pub fn check(&self, sender: &str, now: i64, limits: &Limits) -> Result<(), TransactionError> {
+        if self.recent_submissions(sender, now, limits) >= limits.max_submissions_per_window {
+            return Err(TransactionError::SubmissionRateExceeded(
+                limits.max_submissions_per_window,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Registra uma admissão de `sender` e descarta as que saíram da janela
+    pub fn record(&mut self, sender: &str, now: i64, limits: &Limits) {
+        let window_start = now - limits.submission_window_secs as i64;
+        let times = self.recent.entry(sender.to_string()).or_default();
+        while times.front().is_some_and(|&time| time <= window_start) {
+            times.pop_front();
+        }
+        times.push_back(now);
+    }
+
+    /// Esquece os remetentes sem admissões na janela que termina em `now`
+    pub fn prune(&mut self, now: i64, limits: &Limits) {
+        let window_start = now - limits.submission_window_secs as i64;
+        self.recent
+            .retain(|_, times| times.back().is_some_and(|&time| time > window_start));
+    }
+}
@@ -0,0 +1,107 @@
+use super::blockchain::{Address, Blockchain};
+use crate::error::{Error, TransactionError};
+use crate::rbac::AdminRole;
+use crate::transaction::{Operation, OperationKind};
+use crate::utils::version::ClientVersion;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Versão mínima do cliente exigida a partir de uma altura, agendada na cadeia
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct UpgradeSignal {
+    pub min_version: ClientVersion,
+    pub activation_height: u64,
+    /// Bloco que registrou o sinal
+    pub scheduled_at: u64,
+    pub scheduled_by: Address,
+}
+
+impl Blockchain {
+    /// Maior versão exigida pelos sinais já ativos no bloco em `height`
+    pub fn required_version(&self, height: u64) -> Option<ClientVersion> {
+        self.upgrade_signals
+            .iter()
+            .filter(|signal| signal.activation_height <= height)
+            .map(|signal| signal.min_version)
+            .max()
+    }
+
+    /// Sinal ainda não ativo no bloco em `height` que este binário não atende,
+    /// o de ativação mais próxima; serve para avisar o operador com antecedência
+    pub fn pending_upgrade(&self, height: u64) -> Option<&UpgradeSignal> {
+        self.upgrade_signals
+            .iter()
+            .filter(|signal| {
+                signal.activation_height > height && signal.min_version > self.client_version
+            })
+            .min_by_key(|signal| signal.activation_height)
+    }
+
+    /// Recusa propor ou votar no bloco em `height` com um binário anterior à
+    /// versão exigida pela cadeia; a mensagem diz o que o operador precisa fazer
+    pub fn check_client_version(&self, height: u64) -> Result<(), Error> {
+        match self.required_version(height) {
+            Some(required) if required > self.client_version => {
+                Err(Error::UpgradeRequired(format!(
+                    "a cadeia exige a versão {} ou superior desde o bloco {} e este nó \
+                     executa a {}; o nó deixou de propor e votar. Atualize o binário para \
+                     {} ou superior e reinicie o nó",
+                    required, height, self.client_version, required
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Só admins, assinando com a chave do próprio endereço, e com ativação
+    /// posterior ao bloco em `height`, que registra o sinal
+    pub(super) fn check_upgrade_operation(
+        &self,
+        operation: &Operation,
+        height: u64,
+    ) -> Result<(), TransactionError> {
+        let OperationKind::ScheduleUpgrade {
+            activation_height, ..
+        } = &operation.kind
+        else {
+            return Ok(());
+        };
+
+        if !self.is_account_key(&operation.author, &operation.public_key)
+            || !self.has_role(&operation.author, AdminRole::Admin)
+        {
+            return Err(TransactionError::InvalidParameter(format!(
+                "{} não pode agendar atualizações",
+                operation.author
+            )));
+        }
+        if *activation_height <= height {
+            return Err(TransactionError::InvalidParameter(format!(
+                "Ativação no bloco {} precisa ser posterior ao bloco {}",
+                activation_height, height
+            )));
+        }
+        Ok(())
+    }
+
+    pub(super) fn apply_upgrade_operation(
+        &mut self,
+        operation: &Operation,
+        height: u64,
+    ) -> Result<(), TransactionError> {
+        self.check_upgrade_operation(operation, height)?;
+        if let OperationKind::ScheduleUpgrade {
+            min_version,
+            activation_height,
+        } = &operation.kind
+        {
+            self.upgrade_signals.push(UpgradeSignal {
+                min_version: *min_version,
+                activation_height: *activation_height,
+                scheduled_at: height,
+                scheduled_by: operation.author.clone(),
+            });
+        }
+        Ok(())
+    }
+}
@@ -4,10 +4,11 @@ use crate::constants::{MAX_TIME_DRIFT, MAX_TRANSACTION_SIZE};
 use crate::error::Error;
 use crate::error::TransactionError;
 use crate::transaction::Transaction;
+use crate::utils::address::Address;
+use crate::utils::timestamp_policy::{TimestampContext, TimestampPolicy, TimestampViolation};
 use pqcrypto_dilithium::dilithium5;
 use pqcrypto_traits::sign::{DetachedSignature, PublicKey};
 use rand::Rng;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 impl Default for Validator {
     fn default() -> Self {
@@ -53,9 +54,8 @@ impl Validator {
         current_nonce: u64,
     ) -> Result<(), Error> {
         // Validar formato dos endereços
-        if !self.validate_address_format(&tx.from) || !self.validate_address_format(&tx.to) {
-            return Err(Error::InvalidInput("Endereço inválido".to_string()));
-        }
+        Address::parse(&tx.from)?;
+        Address::parse(&tx.to)?;
 
         // Validar token
         let _token = blockchain
@@ -73,17 +73,8 @@ impl Validator {
         }
 
         // Validar timestamp
-        let current_time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|_| Error::InvalidTimestamp("Erro ao obter timestamp".to_string()))?
-            .as_secs() as i64;
-
-        if tx.timestamp < current_time - 86400 || tx.timestamp > current_time + self.max_time_drift
-        {
-            return Err(Error::InvalidTimestamp(
-                "Timestamp fora do intervalo permitido".to_string(),
-            ));
-        }
+        TimestampPolicy::for_context(TimestampContext::IncludedTransaction)
+            .validate_secs(tx.timestamp)?;
 
         // Validar tamanho da transação
         let tx_size = bincode::serialize(tx)
@@ -118,17 +109,13 @@ impl Validator {
         }
 
         // Validação de timestamp
-        let current_time = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
-
-        if block.timestamp > (current_time + self.max_time_drift) as u64 {
-            return Err(ValidationError::TimestampTooFar);
-        }
-
-        if block.timestamp < (current_time - self.max_time_drift) as u64 {
-            return Err(ValidationError::TimestampTooOld);
+        match TimestampPolicy::symmetric(self.max_time_drift).validate_secs(block.timestamp as i64)
+        {
+            Err(TimestampViolation::InFuture { .. }) => {
+                return Err(ValidationError::TimestampTooFar)
+            }
+            Err(TimestampViolation::TooOld { .. }) => return Err(ValidationError::TimestampTooOld),
+            Ok(()) => {}
         }
 
         // Validação de hash anterior
@@ -143,22 +130,9 @@ impl Validator {
     }
 
     pub fn is_timestamp_valid(&self, timestamp: i64, current_time: i64) -> bool {
-        let future_drift = current_time + self.max_time_drift;
-        let past_drift = current_time - self.max_time_drift;
-
-        timestamp <= future_drift && timestamp >= past_drift
-    }
-
-    fn validate_address_format(&self, address: &str) -> bool {
-        if !address.starts_with("0x") {
-            return false;
-        }
-
-        if address.len() != 42 {
-            return false;
-        }
-
-        address[2..].chars().all(|c| c.is_ascii_hexdigit())
+        TimestampPolicy::symmetric(self.max_time_drift)
+            .check_secs(timestamp, current_time)
+            .is_ok()
     }
 
     fn verify_signature_with_delay(&self, tx: &Transaction) -> Result<bool, Error> {
@@ -191,7 +165,9 @@ impl Validator {
 
 // Funções utilitárias
 pub fn validate_timestamp(timestamp: i64, current_time: i64) -> bool {
-    timestamp <= (current_time + MAX_TIME_DRIFT) && timestamp >= (current_time - MAX_TIME_DRIFT)
+    TimestampPolicy::symmetric(MAX_TIME_DRIFT)
+        .check_secs(timestamp, current_time)
+        .is_ok()
 }
 
 pub fn validate_transaction_size(tx: &Transaction) -> Result<(), TransactionError> {
@@ -201,13 +177,9 @@ pub fn validate_transaction_size(tx: &Transaction) -> Result<(), TransactionErro
     Ok(())
 }
 
+/// Mesmo formato exigido por `Transaction::validate_address` (ver `Address`)
 pub fn validate_address_format(address: &str) -> bool {
-    if address.len() < 32 || address.len() > 64 {
-        return false;
-    }
-
-    // Verifica se o endereço contém apenas caracteres válidos
-    address.chars().all(|c| c.is_alphanumeric())
+    Address::is_valid(address)
 }
 
 // Corrigir para usar o tipo TransactionError correto
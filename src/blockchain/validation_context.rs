@@ -0,0 +1,270 @@
+use super::blockchain::Blockchain;
+use crate::error::TransactionError;
+use crate::transaction::{SecureTransaction, Transaction};
+use hashlink::LruCache;
+use pqcrypto_dilithium::dilithium5;
+use pqcrypto_traits::sign::PublicKey as _;
+use std::collections::{HashMap, HashSet};
+
+/// Capacidade padrão de cada cache (tokens, saldos e nonces)
+pub const DEFAULT_CACHE_CAPACITY: usize = 1024;
+
+type TransactionKey = (String, u64, i64);
+
+/// Metadados de token usados na validação, sem o mapa de saldos
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenMetadata {
+    pub id: String,
+    pub name: String,
+    pub symbol: String,
+    pub total_supply: u64,
+    pub creator: String,
+}
+
+/// Acertos e faltas acumulados nos caches do contexto
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Contexto de validação de um bloco.
+///
+/// Consultas a tokens, saldos e nonces passam por caches LRU, e os efeitos das
+/// transações já validadas ficam em uma camada própria que nunca é descartada,
+/// de modo que transações seguintes do mesmo remetente enxergam o estado atualizado.
+/// O índice de transações confirmadas é montado uma única vez por contexto.
+pub struct ValidationContext<'a> {
+    blockchain: &'a Blockchain,
+    tokens: LruCache<String, Option<TokenMetadata>>,
+    balances: LruCache<(String, String), u64>,
+    nonces: LruCache<String, u64>,
+    pending_balances: HashMap<(String, String), u64>,
+    pending_nonces: HashMap<String, u64>,
+    confirmed: Option<HashSet<TransactionKey>>,
+    in_block: HashSet<TransactionKey>,
+    stats: CacheStats,
+}
+
+impl<'a> ValidationContext<'a> {
+    pub fn new(blockchain: &'a Blockchain) -> Self {
+        Self::with_capacity(blockchain, DEFAULT_CACHE_CAPACITY)
+    }
+
+    pub fn with_capacity(blockchain: &'a Blockchain, capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            blockchain,
+            tokens: LruCache::new(capacity),
+            balances: LruCache::new(capacity),
+            nonces: LruCache::new(capacity),
+            pending_balances: HashMap::new(),
+            pending_nonces: HashMap::new(),
+            confirmed: None,
+            in_block: HashSet::new(),
+            stats: CacheStats::default(),
+        }
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Metadados do token, consultando o mapa da blockchain apenas na primeira vez
+    pub fn token(&mut self, token_id: &str) -> Option<TokenMetadata> {
+        if let Some(cached) = self.tokens.get(token_id) {
+            self.stats.hits += 1;
+            return cached.clone();
+        }
+
+        self.stats.misses += 1;
+        let metadata = self
+            .blockchain
+            .get_token(token_id)
+            .map(|token| TokenMetadata {
+                id: token_id.to_string(),
+                name: token.name.clone(),
+                symbol: token.symbol.clone(),
+                total_supply: token.total_supply,
+                creator: token.creator.clone(),
+            });
+        self.tokens.insert(token_id.to_string(), metadata.clone());
+        metadata
+    }
+
+    /// Saldo considerando as transações já validadas neste contexto
+    pub fn balance(&mut self, token_id: &str, address: &str) -> u64 {
+        let key = (token_id.to_string(), address.to_string());
+        if let Some(balance) = self.pending_balances.get(&key) {
+            self.stats.hits += 1;
+            return *balance;
+        }
+        if let Some(balance) = self.balances.get(&key) {
+            self.stats.hits += 1;
+            return *balance;
+        }
+
+        self.stats.misses += 1;
+        let balance = self
+            .blockchain
+            .get_token(token_id)
+            .and_then(|token| token.balances.get(address).copied())
+            .unwrap_or(0);
+        self.balances.insert(key, balance);
+        balance
+    }
+
+    /// Último nonce do endereço considerando as transações já validadas neste contexto
+    pub fn nonce(&mut self, address: &str) -> u64 {
+        if let Some(nonce) = self.pending_nonces.get(address) {
+            self.stats.hits += 1;
+            return *nonce;
+        }
+        if let Some(nonce) = self.nonces.get(address) {
+            self.stats.hits += 1;
+            return *nonce;
+        }
+
+        self.stats.misses += 1;
+        let nonce = self.blockchain.nonces.get(address).copied().unwrap_or(0);
+        self.nonces.insert(address.to_string(), nonce);
+        nonce
+    }
+
+    fn is_duplicate(&mut self, key: &TransactionKey) -> bool {
+        if self.in_block.contains(key) {
+            return true;
+        }
+
+        let blockchain = self.blockchain;
+        self.confirmed
+            .get_or_insert_with(|| {
+                blockchain
+                    .chain
+                    .iter()
+                    .flat_map(|block| block.transactions.iter())
+                    .map(|t| (t.from.clone(), t.nonce, t.timestamp))
+                    .collect()
+            })
+            .contains(key)
+    }
+
+    fn check_nonce(&mut self, from: &str, nonce: u64) -> Result<(), TransactionError> {
+        let nonce_atual = self.nonce(from);
+        if nonce != nonce_atual + 1 {
+            return Err(TransactionError::InvalidSignature(format!(
+                "Nonce inválido: esperado {}, recebido {}",
+                nonce_atual + 1,
+                nonce
+            )));
+        }
+        Ok(())
+    }
+
+    fn public_key(bytes: &[u8]) -> Result<dilithium5::PublicKey, TransactionError> {
+        dilithium5::PublicKey::from_bytes(bytes)
+            .map_err(|_| TransactionError::InvalidSignature("Chave pública inválida".to_string()))
+    }
+
+    /// Valida uma transação do bloco e aplica seus efeitos ao contexto
+    pub fn validate_transaction(
+        &mut self,
+        transaction: &Transaction,
+    ) -> Result<(), TransactionError> {
+        self.check_nonce(&transaction.from, transaction.nonce)?;
+        self.check_key(
+            &transaction.from,
+            &transaction.public_key,
+            transaction.nonce,
+        )?;
+
+        // Verifica a assinatura
+        let pk = Self::public_key(&transaction.public_key)?;
+        if transaction.verify(&pk).is_err() {
+            return Err(TransactionError::InvalidSignature(
+                "Assinatura inválida".to_string(),
+            ));
+        }
+
+        self.apply_transaction(transaction)
+    }
+
+    /// Como `validate_transaction`, para transações no formato guardado nos blocos;
+    /// a assinatura é a da `SecureTransaction`, que a conversão para `Transaction` perde
+    pub fn validate_secure_transaction(
+        &mut self,
+        transaction: &SecureTransaction,
+    ) -> Result<(), TransactionError> {
+        self.check_nonce(&transaction.from, transaction.nonce)?;
+        self.check_key(
+            &transaction.from,
+            &transaction.public_key,
+            transaction.nonce,
+        )?;
+
+        let pk = Self::public_key(&transaction.public_key)?;
+        if !transaction.verify(&pk, &transaction.signature)? {
+            return Err(TransactionError::InvalidSignature(
+                "Assinatura inválida".to_string(),
+            ));
+        }
+
+        self.apply_transaction(&transaction.clone().into())
+    }
+
+    /// A chave precisa ser a vigente da conta no nonce: depois de uma rotação, pelo
+    /// dono ou pelos guardiões, a chave substituída deixa de assinar também em blocos
+    /// recebidos de outros nós
+    fn check_key(&self, from: &str, public_key: &[u8], nonce: u64) -> Result<(), TransactionError> {
+        self.blockchain
+            .check_historical_key(from, public_key, nonce)
+    }
+
+    /// Duplicação, token e saldos; com sucesso, registra os efeitos no contexto
+    fn apply_transaction(&mut self, transaction: &Transaction) -> Result<(), TransactionError> {
+        // Verifica duplicação, na cadeia e dentro do próprio bloco
+        let key = (
+            transaction.from.clone(),
+            transaction.nonce,
+            transaction.timestamp,
+        );
+        if self.is_duplicate(&key) {
+            return Err(TransactionError::InvalidSignature(
+                "Transação duplicada".to_string(),
+            ));
+        }
+
+        // Verifica token e saldo do remetente
+        let token_id = transaction.token_id.to_string();
+        if self.token(&token_id).is_none() {
+            return Err(TransactionError::TokenNaoEncontrado);
+        }
+
+        let saldo_remetente = self.balance(&token_id, &transaction.from);
+        let novo_saldo_remetente = saldo_remetente
+            .checked_sub(transaction.amount)
+            .ok_or(TransactionError::InsufficientFunds)?;
+        // O crédito é lido depois do débito: numa transferência para si mesmo o
+        // saldo volta ao original, como em `move_balance`
+        let saldo_destinatario = if transaction.to == transaction.from {
+            novo_saldo_remetente
+        } else {
+            self.balance(&token_id, &transaction.to)
+        };
+        let novo_saldo_destinatario = saldo_destinatario
+            .checked_add(transaction.amount)
+            .ok_or(TransactionError::ValorInvalido)?;
+
+        self.pending_balances.insert(
+            (token_id.clone(), transaction.from.clone()),
+            novo_saldo_remetente,
+        );
+        self.pending_balances
+            .insert((token_id, transaction.to.clone()), novo_saldo_destinatario);
+        self.pending_nonces
+            .insert(transaction.from.clone(), transaction.nonce);
+        self.in_block.insert(key);
+
+        Ok(())
+    }
+}
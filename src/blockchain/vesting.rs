@@ -0,0 +1,145 @@
+use super::blockchain::Blockchain;
+use crate::error::TransactionError;
+use crate::token::VestingSchedule;
+use crate::transaction::{Operation, OperationKind};
+
+impl Blockchain {
+    pub fn vesting_schedule(&self, schedule_id: u64) -> Option<&VestingSchedule> {
+        usize::try_from(schedule_id)
+            .ok()
+            .and_then(|index| self.vesting_schedules.get(index))
+    }
+
+    /// Cronogramas de `beneficiary`, com seus identificadores
+    pub fn vesting_for<'a>(
+        &'a self,
+        beneficiary: &'a str,
+    ) -> impl Iterator<Item = (u64, &'a VestingSchedule)> + 'a {
+        self.vesting_schedules
+            .iter()
+            .enumerate()
+            .filter(move |(_, schedule)| schedule.beneficiary == beneficiary)
+            .map(|(id, schedule)| (id as u64, schedule))
+    }
+
+    /// Tokens de `address` ainda presos em cronogramas no instante `now`; não
+    /// fazem parte do saldo e por isso não podem ser transferidos
+    pub fn locked_balance(&self, token_id: u64, address: &str, now: u64) -> u64 {
+        self.vesting_for(address)
+            .filter(|(_, schedule)| schedule.token_id == token_id)
+            .map(|(_, schedule)| schedule.locked_at(now))
+            .sum()
+    }
+
+    /// Cronogramas só para tokens existentes e beneficiários admitidos pelas
+    /// restrições do autor; resgate só pelo beneficiário
+    pub(super) fn check_vesting_operation(
+        &self,
+        operation: &Operation,
+    ) -> Result<(), TransactionError> {
+        match &operation.kind {
+            OperationKind::CreateVesting { token_id, .. }
+                if self.get_token(&token_id.to_string()).is_none() =>
+            {
+                return Err(TransactionError::TokenNaoEncontrado);
+            }
+            OperationKind::CreateVesting { beneficiary, .. } => {
+                self.check_account_guard(&operation.author, beneficiary, self.height())?;
+            }
+            OperationKind::ClaimVested { schedule_id } => {
+                let schedule = self.vesting_schedule(*schedule_id).ok_or_else(|| {
+                    TransactionError::InvalidParameter(format!(
+                        "Cronograma de liberação {} inexistente",
+                        schedule_id
+                    ))
+                })?;
+                if schedule.beneficiary != operation.author {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "{} não é beneficiário do cronograma {}",
+                        operation.author, schedule_id
+                    )));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Aplica criação ou resgate de cronograma no bloco com timestamp `now`
+    pub(super) fn apply_vesting_operation(
+        &mut self,
+        operation: &Operation,
+        now: u64,
+    ) -> Result<(), TransactionError> {
+        match &operation.kind {
+            OperationKind::CreateVesting {
+                token_id,
+                beneficiary,
+                amount,
+                start,
+                cliff_secs,
+                duration_secs,
+            } => {
+                self.check_account_guard(&operation.author, beneficiary, self.height())?;
+                let token = self
+                    .tokens
+                    .get_mut(&token_id.to_string())
+                    .ok_or(TransactionError::TokenNaoEncontrado)?;
+                let balance = token.balances.get(&operation.author).copied().unwrap_or(0);
+                let remaining = balance
+                    .checked_sub(*amount)
+                    .ok_or(TransactionError::InsufficientFunds)?;
+                token.balances.insert(operation.author.clone(), remaining);
+
+                self.vesting_schedules.push(VestingSchedule {
+                    token_id: *token_id,
+                    grantor: operation.author.clone(),
+                    beneficiary: beneficiary.clone(),
+                    amount: *amount,
+                    claimed: 0,
+                    start: *start,
+                    cliff_secs: *cliff_secs,
+                    duration_secs: *duration_secs,
+                });
+            }
+            OperationKind::ClaimVested { schedule_id } => {
+                let index = usize::try_from(*schedule_id)
+                    .ok()
+                    .filter(|index| *index < self.vesting_schedules.len())
+                    .ok_or_else(|| {
+                        TransactionError::InvalidParameter(format!(
+                            "Cronograma de liberação {} inexistente",
+                            schedule_id
+                        ))
+                    })?;
+                let schedule = &self.vesting_schedules[index];
+                let claimable = schedule.claimable_at(now);
+                if claimable == 0 {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "Nada liberado no cronograma {}",
+                        schedule_id
+                    )));
+                }
+
+                let token = self
+                    .tokens
+                    .get_mut(&schedule.token_id.to_string())
+                    .ok_or(TransactionError::TokenNaoEncontrado)?;
+                let balance = token
+                    .balances
+                    .get(&schedule.beneficiary)
+                    .copied()
+                    .unwrap_or(0);
+                let credited = balance
+                    .checked_add(claimable)
+                    .ok_or(TransactionError::ValorInvalido)?;
+                token
+                    .balances
+                    .insert(schedule.beneficiary.clone(), credited);
+                self.vesting_schedules[index].claimed += claimable;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::BTreeMap;
+
+/// Rótulo que separa o hash do gênesis de outros hashes SHA3 do projeto
+const GENESIS_DOMAIN: &[u8] = b"kybelith-genesis-v1";
+
+/// Estado inicial da cadeia: o token nativo (ID 0) e as alocações do lançamento.
+///
+/// Nós de uma mesma rede precisam do mesmo gênesis; o hash em `hash` é o que
+/// eles comparam no handshake P2P.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GenesisConfig {
+    /// Instante de lançamento da rede (Unix, segundos); só distingue redes de
+    /// parâmetros iguais
+    pub timestamp: i64,
+
+    pub token_name: String,
+
+    pub token_symbol: String,
+
+    /// Supply emitido para `creator` no gênesis
+    pub initial_supply: u64,
+
+    pub creator: String,
+
+    /// Saldos cunhados no gênesis, além do supply inicial
+    pub allocations: BTreeMap<String, u64>,
+}
+
+impl Default for GenesisConfig {
+    fn default() -> Self {
+        let mut allocations = BTreeMap::new();
+        // Saldo inicial de desenvolvimento
+        allocations.insert("0x123...".to_string(), 1_000_000);
+        Self {
+            timestamp: 0,
+            token_name: "Kybelith".to_string(),
+            token_symbol: "KYBL".to_string(),
+            initial_supply: 10_000_000,
+            creator: "system".to_string(),
+            allocations,
+        }
+    }
+}
+
+impl GenesisConfig {
+    /// Hash canônico do gênesis da cadeia `chain_id`. Cada campo entra com o
+    /// tamanho como prefixo e as alocações em ordem de endereço, de modo que o
+    /// resultado não depende da ordem nem da formatação do arquivo de configuração.
+    pub fn hash(&self, chain_id: &str) -> [u8; 32] {
+        let mut hasher = Sha3_256::new();
+        let mut field = |bytes: &[u8]| {
+            hasher.update((bytes.len() as u64).to_be_bytes());
+            hasher.update(bytes);
+        };
+        field(GENESIS_DOMAIN);
+        field(chain_id.as_bytes());
+        field(&self.timestamp.to_be_bytes());
+        field(self.token_name.as_bytes());
+        field(self.token_symbol.as_bytes());
+        field(&self.initial_supply.to_be_bytes());
+        field(self.creator.as_bytes());
+        field(&(self.allocations.len() as u64).to_be_bytes());
+        for (address, amount) in &self.allocations {
+            field(address.as_bytes());
+            field(&amount.to_be_bytes());
+        }
+        hasher.finalize().into()
+    }
+}
@@ -0,0 +1,98 @@
+use crate::constants::{
+    MAX_BLOCK_SIZE, MAX_BLOCK_WEIGHT, MAX_PENDING_PER_SENDER, MAX_SIGNATURE_SIZE,
+    MAX_SUBMISSIONS_PER_WINDOW, MAX_TRANSACTION_SIZE, SUBMISSION_WINDOW_SECS,
+};
+use crate::error::TransactionError;
+use crate::transaction::{Operation, Transaction, WeightSchedule};
+use serde::{Deserialize, Serialize};
+
+/// Limites de tamanho aplicados na admissão ao mempool, na montagem de blocos e
+/// na validação de blocos recebidos, e limites por remetente contra spam no
+/// mempool.
+///
+/// Os padrões são as constantes de `constants`, que continuam sendo o teto da
+/// decodificação de bytes vindos da rede: valores maiores que elas só valem para
+/// transações e blocos montados pelo próprio nó.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Limits {
+    /// Tamanho máximo de uma transação codificada (`Transaction::encoded_size`)
+    pub max_transaction_size: usize,
+
+    /// Tamanho máximo de uma assinatura
+    pub max_signature_size: usize,
+
+    /// Tamanho máximo de um bloco (`Block::size`)
+    pub max_block_size: usize,
+
+    /// Peso máximo somado dos itens de um bloco
+    pub max_block_weight: u64,
+
+    /// Peso de cada tipo de item
+    pub weights: WeightSchedule,
+
+    /// Transações pendentes simultâneas de um mesmo remetente
+    pub max_pending_per_sender: usize,
+
+    /// Submissões admitidas de um mesmo remetente a cada `submission_window_secs`
+    pub max_submissions_per_window: usize,
+
+    /// Duração da janela de contagem de submissões, em segundos
+    pub submission_window_secs: u64,
+
+    /// Piso de admissão: transferências cuja taxa (`Blockchain::transfer_fee`)
+    /// fica abaixo dele não entram no mempool. Política local do nó, sem efeito
+    /// na validação de blocos; zero aceita qualquer taxa
+    pub fee_floor: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_transaction_size: MAX_TRANSACTION_SIZE,
+            max_signature_size: MAX_SIGNATURE_SIZE,
+            max_block_size: MAX_BLOCK_SIZE,
+            max_block_weight: MAX_BLOCK_WEIGHT,
+            weights: WeightSchedule::default(),
+            max_pending_per_sender: MAX_PENDING_PER_SENDER,
+            max_submissions_per_window: MAX_SUBMISSIONS_PER_WINDOW,
+            submission_window_secs: SUBMISSION_WINDOW_SECS,
+            fee_floor: 0,
+        }
+    }
+}
+
+impl Limits {
+    /// Confere assinatura e tamanho codificado de uma transação, e que ela cabe
+    /// sozinha no peso de um bloco
+    pub fn check_transaction(&self, transaction: &Transaction) -> Result<(), TransactionError> {
+        if transaction.signature.len() > self.max_signature_size {
+            return Err(TransactionError::SignatureSizeExceeded);
+        }
+        if transaction.encoded_size() > self.max_transaction_size {
+            return Err(TransactionError::DataSizeExceeded);
+        }
+        self.check_weight(self.weights.transfer_weight(transaction.encoded_size()))
+    }
+
+    /// Os limites de `check_transaction` para uma operação, com o tamanho
+    /// codificado em bincode
+    pub fn check_operation(&self, operation: &Operation) -> Result<(), TransactionError> {
+        if operation.signature.len() > self.max_signature_size {
+            return Err(TransactionError::SignatureSizeExceeded);
+        }
+        let encoded_size =
+            bincode::serialized_size(operation).map_or(usize::MAX, |size| size as usize);
+        if encoded_size > self.max_transaction_size {
+            return Err(TransactionError::DataSizeExceeded);
+        }
+        self.check_weight(self.weights.operation_weight(operation))
+    }
+
+    fn check_weight(&self, weight: u64) -> Result<(), TransactionError> {
+        if weight > self.max_block_weight {
+            return Err(TransactionError::DataSizeExceeded);
+        }
+        Ok(())
+    }
+}
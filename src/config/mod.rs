@@ -1,12 +1,23 @@
 // Exporta o módulo de configurações
+pub mod genesis;
+pub mod limits;
+pub mod reload;
 pub mod settings;
 
 // Re-exporta os tipos principais para facilitar o uso
+pub use genesis::GenesisConfig;
+pub use limits::Limits;
+pub use reload::{reloadable_changes, RuntimeSettings};
+pub use settings::ClockConfig;
 pub use settings::ConsensusConfig;
 pub use settings::InteroperabilityConfig;
+pub use settings::LogFormat;
+pub use settings::LogRotation;
+pub use settings::LoggingConfig;
 pub use settings::NodeConfig;
 pub use settings::P2PConfig;
 pub use settings::QuantumSecurityConfig;
+pub use settings::RpcConfig;
 pub use settings::Settings;
 
 // Re-exporta funções úteis
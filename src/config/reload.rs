@@ -0,0 +1,81 @@
+use super::settings::{RpcConfig, Settings};
+use log::LevelFilter;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Configurações que o nó aplica sem reiniciar. Nenhuma delas afeta o consenso:
+/// nível de log, limite de chamadas RPC, limites de conexões e piso de taxa
+/// para admissão no mempool.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct RuntimeSettings {
+    pub log_level: String,
+    pub rpc: RpcConfig,
+    pub max_incoming_connections: u32,
+    pub max_outgoing_connections: u32,
+    pub fee_floor: u64,
+}
+
+impl RuntimeSettings {
+    pub fn of(settings: &Settings) -> Self {
+        Self {
+            log_level: settings.node.log_level.clone(),
+            rpc: settings.rpc,
+            max_incoming_connections: settings.p2p.max_incoming_connections,
+            max_outgoing_connections: settings.p2p.max_outgoing_connections,
+            fee_floor: settings.limits.fee_floor,
+        }
+    }
+
+    /// Copia os campos recarregáveis para `settings`
+    pub fn write_to(&self, settings: &mut Settings) {
+        settings.node.log_level = self.log_level.clone();
+        settings.rpc = self.rpc;
+        settings.p2p.max_incoming_connections = self.max_incoming_connections;
+        settings.p2p.max_outgoing_connections = self.max_outgoing_connections;
+        settings.limits.fee_floor = self.fee_floor;
+    }
+
+    pub fn log_level_filter(&self) -> Result<LevelFilter, String> {
+        self.log_level
+            .parse()
+            .map_err(|_| format!("Nível de log desconhecido: {}", self.log_level))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.log_level_filter()?;
+        if self.rpc.max_requests_per_window > 0 && self.rpc.window_secs == 0 {
+            return Err("Janela do limite RPC deve ter ao menos 1 segundo".to_string());
+        }
+        if self.max_outgoing_connections == 0 {
+            return Err("O nó precisa de ao menos uma conexão de saída".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Confere `next` contra as configurações em uso: os campos recarregáveis
+/// precisam ser válidos e todo o resto precisa continuar igual, já que só muda
+/// com o nó reiniciado.
+pub fn reloadable_changes(current: &Settings, next: &Settings) -> Result<RuntimeSettings, String> {
+    let runtime = RuntimeSettings::of(next);
+    runtime.validate()?;
+
+    let mut masked = next.clone();
+    RuntimeSettings::of(current).write_to(&mut masked);
+    let as_json = |settings: &Settings| {
+        serde_json::to_value(settings)
+            .map_err(|e| format!("Falha ao comparar configurações: {}", e))
+    };
+    let (before, after) = (as_json(current)?, as_json(&masked)?);
+    if let (Some(before), Some(after)) = (before.as_object(), after.as_object()) {
+        for (section, value) in after {
+            if before.get(section) != Some(value) {
+                return Err(format!(
+                    "Seção `{}` alterada; essa mudança exige reiniciar o nó",
+                    section
+                ));
+            }
+        }
+    }
+    Ok(runtime)
+}
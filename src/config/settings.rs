@@ -1,7 +1,12 @@
+use super::genesis::GenesisConfig;
+use super::limits::Limits;
+use crate::utils::timestamp_policy::TimestampPolicy;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 /// Estrutura principal de configuração contendo todos os parâmetros do sistema
@@ -21,6 +26,35 @@ pub struct Settings {
 
     /// Configurações para interoperabilidade com outras blockchains
     pub interoperability: InteroperabilityConfig,
+
+    /// Sincronização do relógio e tolerâncias de timestamp
+    #[serde(default)]
+    pub clock: ClockConfig,
+
+    /// Limites de tamanho de transações, assinaturas e blocos
+    #[serde(default)]
+    pub limits: Limits,
+
+    /// Limite de chamadas da interface RPC
+    #[serde(default)]
+    pub rpc: RpcConfig,
+
+    /// Saídas do logger do nó: formato, rotação do arquivo e níveis por módulo
+    #[serde(default)]
+    pub logging: LoggingConfig,
+
+    /// Estado inicial da cadeia; define, com `node.chain_id`, a rede do nó
+    #[serde(default)]
+    pub genesis: GenesisConfig,
+
+    /// Arquivos de configuração de outras cadeias servidas pelo mesmo processo,
+    /// como uma testnet ao lado da mainnet; cada uma com seu `chain_id` e `data_dir`
+    #[serde(default)]
+    pub additional_chains: Vec<PathBuf>,
+
+    /// Arquivo de onde as configurações foram lidas; o nó o relê ao recarregar
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
 }
 
 /// Configurações específicas do nó
@@ -29,6 +63,11 @@ pub struct NodeConfig {
     /// ID do nó na rede
     pub node_id: String,
 
+    /// Identificador da cadeia servida, usado para rotear chamadas RPC quando o
+    /// processo hospeda mais de uma
+    #[serde(default = "default_chain_id")]
+    pub chain_id: String,
+
     /// Diretório de dados para armazenamento
     pub data_dir: String,
 
@@ -37,6 +76,51 @@ pub struct NodeConfig {
 
     /// Flag indicando se o nó é um validador
     pub is_validator: bool,
+
+    /// Arquivo, relativo ao `data_dir`, onde o nó grava o estado da blockchain
+    #[serde(default = "default_blockchain_file")]
+    pub blockchain_file: String,
+
+    /// Intervalo entre gravações periódicas da blockchain (em segundos)
+    #[serde(default = "default_persist_interval_sec")]
+    pub persist_interval_sec: u64,
+
+    /// Arquivo, relativo ao `data_dir`, com o par de chaves Dilithium5 que identifica
+    /// o nó; criado na primeira execução
+    #[serde(default = "default_identity_file")]
+    pub identity_file: String,
+
+    /// Arquivo, relativo ao `data_dir`, do log de auditoria das ações administrativas
+    #[serde(default = "default_audit_log_file")]
+    pub audit_log_file: String,
+
+    /// Banco SQLite do nó, relativo ao `data_dir`
+    #[serde(default = "default_database_file")]
+    pub database_file: String,
+}
+
+fn default_chain_id() -> String {
+    crate::constants::DEFAULT_CHAIN_ID.to_string()
+}
+
+fn default_blockchain_file() -> String {
+    crate::BLOCKCHAIN_FILE.to_string()
+}
+
+fn default_persist_interval_sec() -> u64 {
+    60
+}
+
+fn default_identity_file() -> String {
+    "node_identity.key".to_string()
+}
+
+fn default_audit_log_file() -> String {
+    "audit.log".to_string()
+}
+
+fn default_database_file() -> String {
+    crate::DB_PATH.to_string()
 }
 
 /// Configurações relacionadas à rede P2P
@@ -65,6 +149,26 @@ pub struct P2PConfig {
 
     /// Intervalo para tentar descobrir novos pares (em segundos)
     pub peer_discovery_interval_sec: u64,
+
+    /// Habilita descoberta via mDNS na rede local (devnets; exige a feature `mdns`)
+    #[serde(default)]
+    pub enable_mdns: bool,
+
+    /// Arquivo, relativo ao `data_dir`, onde o livro de endereços de peers é persistido
+    #[serde(default = "default_peer_store_file")]
+    pub peer_store_file: String,
+
+    /// Arquivo, relativo ao `data_dir`, com os hosts banidos por mau comportamento
+    #[serde(default = "default_ban_list_file")]
+    pub ban_list_file: String,
+}
+
+fn default_peer_store_file() -> String {
+    "peers.json".to_string()
+}
+
+fn default_ban_list_file() -> String {
+    "banned_peers.json".to_string()
 }
 
 /// Configurações do sistema de consenso QuantumFlex
@@ -91,6 +195,11 @@ pub struct ConsensusConfig {
     /// Número de blocos para finalidade (não pode ser revertido após)
     pub finality_blocks: u64,
 
+    /// Blocos por cima de uma transação para as consultas a marcarem como `safe`;
+    /// `finalized` depende de checkpoint assinado, não de profundidade
+    #[serde(default = "default_confirmation_depth")]
+    pub confirmation_depth: u64,
+
     /// Percentual de votação necessário para finalidade (0-100)
     pub finality_threshold_percentage: f32,
 
@@ -99,6 +208,18 @@ pub struct ConsensusConfig {
 
     /// Habilita sistema de reputação para validadores
     pub enable_reputation_system: bool,
+
+    /// Máximo de transações do mempool incluídas em cada bloco produzido
+    #[serde(default = "default_max_block_transactions")]
+    pub max_block_transactions: usize,
+}
+
+fn default_confirmation_depth() -> u64 {
+    crate::constants::DEFAULT_CONFIRMATION_DEPTH
+}
+
+fn default_max_block_transactions() -> usize {
+    1000
 }
 
 /// Configurações de segurança quântica
@@ -136,18 +257,154 @@ pub struct InteroperabilityConfig {
     pub min_external_confirmations: u64,
 }
 
+/// Limite de chamadas RPC externas, contadas em janelas fixas para todo o nó
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct RpcConfig {
+    /// Chamadas aceitas por janela; zero desliga o limite
+    pub max_requests_per_window: u32,
+
+    /// Duração da janela, em segundos
+    pub window_secs: u64,
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            max_requests_per_window: 600,
+            window_secs: 60,
+        }
+    }
+}
+
+/// Formato das linhas gravadas no arquivo de log
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    /// Um objeto JSON por linha, para coletores de log
+    Json,
+}
+
+/// Quando o arquivo de log é rotacionado independentemente do tamanho
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    Never,
+    Hourly,
+    #[default]
+    Daily,
+}
+
+/// Saídas do logger do nó. O terminal usa `node.log_level`; o arquivo tem nível
+/// próprio, e `modules` sobrepõe ambos para alvos específicos (ex.: `kybelith::network`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Arquivo de log; ausente desliga a gravação em arquivo
+    pub file: Option<PathBuf>,
+
+    /// Nível mínimo das mensagens gravadas no arquivo
+    pub file_level: String,
+
+    pub format: LogFormat,
+
+    /// Tamanho a partir do qual o arquivo é rotacionado; zero desliga o limite
+    pub max_file_bytes: u64,
+
+    pub rotation: LogRotation,
+
+    /// Arquivos rotacionados mantidos (`blockchain.log.1` é o mais recente)
+    pub max_files: usize,
+
+    /// Nível por prefixo de alvo; vale o prefixo mais longo que casar
+    pub modules: BTreeMap<String, String>,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            file: Some(PathBuf::from("blockchain.log")),
+            file_level: "debug".to_string(),
+            format: LogFormat::Text,
+            max_file_bytes: 50 * 1024 * 1024,
+            rotation: LogRotation::Daily,
+            max_files: 7,
+            modules: BTreeMap::new(),
+        }
+    }
+}
+
+/// Sincronização do relógio e tolerâncias de timestamp, num só lugar
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClockConfig {
+    /// Servidores NTP consultados em ordem (host:porta)
+    pub ntp_servers: Vec<String>,
+
+    /// Timeout de cada consulta NTP (em milissegundos)
+    pub ntp_timeout_ms: u64,
+
+    /// Intervalo entre sincronizações NTP (em segundos)
+    pub ntp_sync_interval_sec: u64,
+
+    /// Peers necessários antes de usar a mediana dos relógios deles
+    pub min_peer_samples: usize,
+
+    /// Desvio a partir do qual um aviso é emitido (em milissegundos)
+    pub drift_warning_ms: i64,
+
+    /// Maior correção aplicada automaticamente ao relógio (em milissegundos)
+    pub max_offset_adjustment_ms: i64,
+
+    /// Timestamps de transações avulsas (mempool, RPC)
+    pub transaction_timestamps: TimestampPolicy,
+
+    /// Timestamps de transações já incluídas em blocos
+    pub included_transaction_timestamps: TimestampPolicy,
+
+    /// Timestamps de blocos recebidos
+    pub block_timestamps: TimestampPolicy,
+
+    /// Timestamps de propostas de bloco do consenso
+    pub proposal_timestamps: TimestampPolicy,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            ntp_servers: vec![
+                "pool.ntp.org:123".to_string(),
+                "time.google.com:123".to_string(),
+            ],
+            ntp_timeout_ms: 2_000,
+            ntp_sync_interval_sec: 3600,
+            min_peer_samples: 5,
+            drift_warning_ms: 2_000,
+            max_offset_adjustment_ms: 120_000,
+            transaction_timestamps: TimestampPolicy::symmetric(300),
+            included_transaction_timestamps: TimestampPolicy::new(300, 86_400),
+            block_timestamps: TimestampPolicy::symmetric(300),
+            proposal_timestamps: TimestampPolicy::symmetric(300),
+        }
+    }
+}
+
 impl Settings {
     /// Carrega as configurações de um arquivo
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
-        let mut file = File::open(path)
+        let mut file = File::open(&path)
             .map_err(|e| format!("Falha ao abrir arquivo de configuração: {}", e))?;
 
         let mut contents = String::new();
         file.read_to_string(&mut contents)
             .map_err(|e| format!("Falha ao ler arquivo de configuração: {}", e))?;
 
-        serde_json::from_str(&contents)
-            .map_err(|e| format!("Falha ao deserializar configuração: {}", e))
+        let mut settings: Self = serde_json::from_str(&contents)
+            .map_err(|e| format!("Falha ao deserializar configuração: {}", e))?;
+        settings.source = Some(path.as_ref().to_path_buf());
+        Ok(settings)
     }
 
     /// Cria configurações padrão
@@ -155,9 +412,15 @@ impl Settings {
         Self {
             node: NodeConfig {
                 node_id: uuid::Uuid::new_v4().to_string(),
+                chain_id: default_chain_id(),
                 data_dir: "./data".to_string(),
                 log_level: "info".to_string(),
                 is_validator: false,
+                blockchain_file: default_blockchain_file(),
+                persist_interval_sec: default_persist_interval_sec(),
+                identity_file: default_identity_file(),
+                audit_log_file: default_audit_log_file(),
+                database_file: default_database_file(),
             },
             p2p: P2PConfig {
                 listen_address: "0.0.0.0:8000".to_string(),
@@ -171,6 +434,9 @@ impl Settings {
                 ping_interval_sec: 30,
                 enable_sybil_protection: true,
                 peer_discovery_interval_sec: 300,
+                enable_mdns: false,
+                peer_store_file: default_peer_store_file(),
+                ban_list_file: default_ban_list_file(),
             },
             consensus: ConsensusConfig {
                 initial_consensus_type: "ADAPTIVE".to_string(),
@@ -180,9 +446,11 @@ impl Settings {
                 min_stake_percentage: 5.0,
                 consensus_timeout_sec: 30,
                 finality_blocks: 20,
+                confirmation_depth: default_confirmation_depth(),
                 finality_threshold_percentage: 67.0,
                 adaptation_interval_blocks: 50,
                 enable_reputation_system: true,
+                max_block_transactions: default_max_block_transactions(),
             },
             quantum_security: QuantumSecurityConfig {
                 kyber_variant: 512,
@@ -201,6 +469,13 @@ impl Settings {
                 bridge_sync_interval_sec: 60,
                 min_external_confirmations: 20,
             },
+            clock: ClockConfig::default(),
+            limits: Limits::default(),
+            rpc: RpcConfig::default(),
+            logging: LoggingConfig::default(),
+            genesis: GenesisConfig::default(),
+            additional_chains: Vec::new(),
+            source: None,
         }
     }
 
@@ -227,4 +502,49 @@ impl Settings {
     pub fn block_interval(&self) -> Duration {
         Duration::from_secs(self.consensus.block_interval_sec)
     }
+
+    /// Caminho completo do arquivo de estado da blockchain
+    pub fn blockchain_path(&self) -> PathBuf {
+        Path::new(&self.node.data_dir).join(&self.node.blockchain_file)
+    }
+
+    /// Caminho completo do arquivo de identidade do nó
+    pub fn identity_path(&self) -> PathBuf {
+        Path::new(&self.node.data_dir).join(&self.node.identity_file)
+    }
+
+    /// Caminho completo do log de auditoria
+    pub fn audit_log_path(&self) -> PathBuf {
+        Path::new(&self.node.data_dir).join(&self.node.audit_log_file)
+    }
+
+    /// Hash do gênesis desta cadeia, exigido dos peers no handshake
+    pub fn genesis_hash(&self) -> [u8; 32] {
+        self.genesis.hash(&self.node.chain_id)
+    }
+
+    /// Caminho completo do banco SQLite do nó
+    pub fn database_path(&self) -> PathBuf {
+        Path::new(&self.node.data_dir).join(&self.node.database_file)
+    }
+
+    /// Intervalo entre gravações periódicas da blockchain
+    pub fn persist_interval(&self) -> Duration {
+        Duration::from_secs(self.node.persist_interval_sec)
+    }
+
+    /// Caminho completo do livro de endereços de peers
+    pub fn peer_store_path(&self) -> PathBuf {
+        Path::new(&self.node.data_dir).join(&self.p2p.peer_store_file)
+    }
+
+    /// Caminho completo da lista de peers banidos
+    pub fn ban_list_path(&self) -> PathBuf {
+        Path::new(&self.node.data_dir).join(&self.p2p.ban_list_file)
+    }
+
+    /// Intervalo entre rodadas de descoberta de peers
+    pub fn peer_discovery_interval(&self) -> Duration {
+        Duration::from_secs(self.p2p.peer_discovery_interval_sec)
+    }
 }
@@ -0,0 +1,281 @@
+use crate::consensus::types::ConsensusError;
+use crate::network::NodeIdentity;
+use log::{debug, warn};
+use pqcrypto_dilithium::dilithium5;
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::{BTreeMap, HashMap};
+
+const BEACON_DOMAIN: &str = "kybelith-beacon-v1";
+
+/// Valor de 32 bytes produzido a cada rodada do beacon
+pub type BeaconValue = [u8; 32];
+
+fn verify_signed(
+    message: &[u8],
+    signature: &[u8],
+    public_key: &[u8],
+    validator_id: &str,
+) -> Result<(), ConsensusError> {
+    let invalid = || {
+        ConsensusError::ValidationFailed(format!(
+            "Assinatura inválida na mensagem do beacon de {}",
+            validator_id
+        ))
+    };
+    let public_key = dilithium5::PublicKey::from_bytes(public_key).map_err(|_| invalid())?;
+    let signature = dilithium5::DetachedSignature::from_bytes(signature).map_err(|_| invalid())?;
+    dilithium5::verify_detached_signature(&signature, message, &public_key).map_err(|_| invalid())
+}
+
+/// Compromisso de um validador com o segredo que revelará na rodada
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BeaconCommit {
+    pub round: u64,
+    pub validator_id: String,
+    /// SHA3-256 do segredo
+    pub commitment: BeaconValue,
+    pub signature: Vec<u8>,
+}
+
+impl BeaconCommit {
+    /// Compromete-se com `secret` na rodada `round`, assinado pela chave de consenso
+    pub fn new(round: u64, validator_id: String, secret: &[u8], identity: &NodeIdentity) -> Self {
+        let mut commit = Self {
+            round,
+            validator_id,
+            commitment: Sha3_256::digest(secret).into(),
+            signature: Vec::new(),
+        };
+        commit.signature = identity.sign_message(&commit.signing_bytes());
+        commit
+    }
+
+    fn signing_bytes(&self) -> Vec<u8> {
+        format!(
+            "{}:commit:{}:{}:{}",
+            BEACON_DOMAIN,
+            self.round,
+            self.validator_id,
+            hex::encode(self.commitment)
+        )
+        .into_bytes()
+    }
+
+    pub fn verify(&self, public_key: &[u8]) -> Result<(), ConsensusError> {
+        verify_signed(
+            &self.signing_bytes(),
+            &self.signature,
+            public_key,
+            &self.validator_id,
+        )
+    }
+}
+
+/// Revelação do segredo comprometido em `BeaconCommit`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BeaconReveal {
+    pub round: u64,
+    pub validator_id: String,
+    pub secret: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+impl BeaconReveal {
+    pub fn new(round: u64, validator_id: String, secret: Vec<u8>, identity: &NodeIdentity) -> Self {
+        let mut reveal = Self {
+            round,
+            validator_id,
+            secret,
+            signature: Vec::new(),
+        };
+        reveal.signature = identity.sign_message(&reveal.signing_bytes());
+        reveal
+    }
+
+    fn signing_bytes(&self) -> Vec<u8> {
+        format!(
+            "{}:reveal:{}:{}:{}",
+            BEACON_DOMAIN,
+            self.round,
+            self.validator_id,
+            hex::encode(&self.secret)
+        )
+        .into_bytes()
+    }
+
+    pub fn verify(&self, public_key: &[u8]) -> Result<(), ConsensusError> {
+        verify_signed(
+            &self.signing_bytes(),
+            &self.signature,
+            public_key,
+            &self.validator_id,
+        )
+    }
+}
+
+/// Fonte de aleatoriedade compartilhada entre os validadores, por commit-reveal.
+///
+/// Cada rodada encadeia o valor anterior com os segredos revelados, em ordem de
+/// validador. Sem revelações, o valor segue a cadeia de hashes apenas; basta um
+/// validador honesto revelar para que ninguém consiga prever o resultado, e nenhum
+/// participante escolhe o próprio segredo depois de ver os demais.
+#[derive(Debug, Clone)]
+pub struct RandomnessBeacon {
+    round: u64,
+    value: BeaconValue,
+    commits: HashMap<String, BeaconValue>,
+    reveals: BTreeMap<String, Vec<u8>>,
+}
+
+impl RandomnessBeacon {
+    /// Inicia o beacon a partir de um valor conhecido por todos (ex.: hash do gênesis)
+    pub fn new(genesis: BeaconValue) -> Self {
+        Self {
+            round: 0,
+            value: genesis,
+            commits: HashMap::new(),
+            reveals: BTreeMap::new(),
+        }
+    }
+
+    /// Rodada que aceita compromissos e revelações
+    pub fn round(&self) -> u64 {
+        self.round
+    }
+
+    /// Valor da última rodada encerrada
+    pub fn value(&self) -> BeaconValue {
+        self.value
+    }
+
+    fn check_round(&self, round: u64) -> Result<(), ConsensusError> {
+        if round != self.round {
+            return Err(ConsensusError::ValidationFailed(format!(
+                "Mensagem do beacon para a rodada {}; rodada atual {}",
+                round, self.round
+            )));
+        }
+        Ok(())
+    }
+
+    /// Registra um compromisso assinado por `public_key`. Um segundo compromisso
+    /// diferente do mesmo validador na rodada é rejeitado.
+    pub fn add_commit(
+        &mut self,
+        commit: &BeaconCommit,
+        public_key: &[u8],
+    ) -> Result<(), ConsensusError> {
+        self.check_round(commit.round)?;
+        commit.verify(public_key)?;
+
+        match self.commits.get(&commit.validator_id) {
+            Some(existing) if *existing != commit.commitment => {
+                Err(ConsensusError::ValidationFailed(format!(
+                    "Validador {} já se comprometeu com outro segredo",
+                    commit.validator_id
+                )))
+            }
+            Some(_) => Ok(()),
+            None => {
+                self.commits
+                    .insert(commit.validator_id.clone(), commit.commitment);
+                Ok(())
+            }
+        }
+    }
+
+    /// Registra uma revelação assinada, que precisa corresponder ao compromisso
+    pub fn add_reveal(
+        &mut self,
+        reveal: &BeaconReveal,
+        public_key: &[u8],
+    ) -> Result<(), ConsensusError> {
+        self.check_round(reveal.round)?;
+        reveal.verify(public_key)?;
+
+        let commitment = self.commits.get(&reveal.validator_id).ok_or_else(|| {
+            ConsensusError::ValidationFailed(format!(
+                "Revelação de {} sem compromisso na rodada",
+                reveal.validator_id
+            ))
+        })?;
+        let digest: BeaconValue = Sha3_256::digest(&reveal.secret).into();
+        if digest != *commitment {
+            return Err(ConsensusError::ValidationFailed(format!(
+                "Segredo revelado por {} não corresponde ao compromisso",
+                reveal.validator_id
+            )));
+        }
+
+        self.reveals
+            .insert(reveal.validator_id.clone(), reveal.secret.clone());
+        Ok(())
+    }
+
+    /// Encerra a rodada, calcula o novo valor e abre a rodada seguinte.
+    /// Retorna o valor e os validadores que se comprometeram sem revelar.
+    pub fn finalize_round(&mut self) -> (BeaconValue, Vec<String>) {
+        let mut hasher = Sha3_256::new();
+        hasher.update(BEACON_DOMAIN.as_bytes());
+        hasher.update(self.round.to_le_bytes());
+        hasher.update(self.value);
+        for (validator_id, secret) in &self.reveals {
+            hasher.update((validator_id.len() as u64).to_le_bytes());
+            hasher.update(validator_id.as_bytes());
+            hasher.update((secret.len() as u64).to_le_bytes());
+            hasher.update(secret);
+        }
+        self.value = hasher.finalize().into();
+
+        let mut missing: Vec<String> = self
+            .commits
+            .keys()
+            .filter(|id| !self.reveals.contains_key(*id))
+            .cloned()
+            .collect();
+        missing.sort();
+        if !missing.is_empty() {
+            warn!(
+                "Rodada {} do beacon encerrada sem revelação de {:?}",
+                self.round, missing
+            );
+        }
+        debug!(
+            "Rodada {} do beacon encerrada com {} revelações",
+            self.round,
+            self.reveals.len()
+        );
+
+        self.round += 1;
+        self.commits.clear();
+        self.reveals.clear();
+        (self.value, missing)
+    }
+}
+
+/// Semente derivada do beacon para um uso específico (`label`) e uma altura
+pub fn derive_seed(value: &BeaconValue, label: &str, height: u64) -> u64 {
+    let mut hasher = Sha3_256::new();
+    hasher.update(BEACON_DOMAIN.as_bytes());
+    hasher.update(label.as_bytes());
+    hasher.update(value);
+    hasher.update(height.to_le_bytes());
+    let digest = hasher.finalize();
+    let mut seed = [0u8; 8];
+    seed.copy_from_slice(&digest[..8]);
+    u64::from_le_bytes(seed)
+}
+
+/// Chave de desempate de um validador: imprevisível antes do valor do beacon e
+/// igual em todos os nós
+pub fn tie_break_key(value: &BeaconValue, validator_id: &str, height: u64) -> BeaconValue {
+    let mut hasher = Sha3_256::new();
+    hasher.update(BEACON_DOMAIN.as_bytes());
+    hasher.update(b"tie-break");
+    hasher.update(value);
+    hasher.update(height.to_le_bytes());
+    hasher.update(validator_id.as_bytes());
+    hasher.finalize().into()
+}
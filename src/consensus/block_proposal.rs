@@ -1,10 +1,41 @@
+use crate::blockchain::Block;
 use crate::consensus::reputation::{ReputationAction, ReputationSystem};
 use crate::consensus::types::{ConsensusError, VerificationResult};
 use crate::consensus::validator::ValidatorSet;
+use crate::network::NodeIdentity;
+use crate::utils::clock::{clock, Clock, SharedClock};
+use crate::utils::timestamp_policy::{TimestampContext, TimestampPolicy};
+use bincode::Options;
 use log::{debug, warn};
+use pqcrypto_dilithium::dilithium5;
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _};
+use serde::{Deserialize, Serialize};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
+/// Tamanho máximo de uma proposta codificada (bytes)
+pub const MAX_PROPOSAL_SIZE: usize = 256 * 1024;
+
+/// Distância máxima, em blocos, entre a altura proposta e a próxima altura local
+/// antes que a proposta conte contra a reputação do proponente
+pub const MAX_PROPOSAL_HEIGHT_GAP: u64 = 10;
+
+/// Tempo sem votos novos após o qual o coordenador abandona uma proposta
+pub const VOTE_EXPIRY: Duration = Duration::from_secs(120);
+
+/// Propostas acompanhadas por altura; acima disso a menos votada é descartada
+pub const MAX_PROPOSALS_PER_HEIGHT: usize = 8;
+
+/// Topo da cadeia local, referência para a continuidade das propostas
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainTip {
+    /// Altura do último bloco confirmado
+    pub height: u64,
+
+    /// Hash do último bloco confirmado
+    pub hash: String,
+}
+
 /// Representa uma proposta de bloco no sistema de consenso
 #[derive(Debug, Clone)]
 pub struct BlockProposal {
@@ -26,7 +57,7 @@ pub struct BlockProposal {
     /// Assinatura Dilithium do proposer
     pub signature: Vec<u8>,
 
-    /// Lista de transações incluídas (apenas hashes)
+    /// Identificadores dos itens incluídos, na ordem do corpo (`Block::item_ids`)
     pub transaction_hashes: Vec<String>,
 
     /// Dados extras do consenso (específicos para cada tipo)
@@ -51,32 +82,131 @@ impl BlockProposal {
             block_hash,
             block_height,
             parent_hash,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis() as u64,
+            timestamp: clock().now_millis().max(0) as u64,
             proposer_id,
             signature,
             transaction_hashes,
             consensus_data,
-            received_at: Instant::now(),
+            received_at: clock().now_instant(),
         }
     }
 
+    /// Refaz o timestamp e o instante de recebimento pelo relógio dado, no lugar
+    /// do relógio do nó
+    pub fn with_clock(mut self, clock: &dyn Clock) -> Self {
+        self.timestamp = clock.unix_millis().max(0) as u64;
+        self.received_at = clock.instant();
+        self
+    }
+
+    /// Proposta para gossip de um bloco já assinado pelo proponente, levando a
+    /// assinatura do bloco (`Block::validator_signature`)
+    pub fn for_block(block: &Block) -> Result<Self, ConsensusError> {
+        let (Some(proposer_id), Some(signature)) = (&block.proposer, &block.validator_signature)
+        else {
+            return Err(ConsensusError::InvalidBlock(format!(
+                "Bloco {} sem assinatura do proponente",
+                block.index
+            )));
+        };
+
+        Ok(Self::new(
+            block.hash.clone(),
+            block.index,
+            block.previous_hash.clone(),
+            proposer_id.clone(),
+            block.item_ids(),
+            signature.clone(),
+            Vec::new(),
+        ))
+    }
+
     /// Verifica se a proposta expirou
     pub fn is_expired(&self, timeout: Duration) -> bool {
-        self.received_at.elapsed() > timeout
+        self.is_expired_at(timeout, clock().now_instant())
     }
 
-    /// Calcula a idade da proposta
+    pub fn is_expired_at(&self, timeout: Duration, now: Instant) -> bool {
+        self.age_at(now) > timeout
+    }
+
+    /// Calcula a idade da proposta pelo relógio do nó
     pub fn age(&self) -> Duration {
-        self.received_at.elapsed()
+        self.age_at(clock().now_instant())
+    }
+
+    /// Idade da proposta em `now`
+    pub fn age_at(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.received_at)
     }
 
     /// Obtém o número de transações incluídas na proposta
     pub fn transaction_count(&self) -> usize {
         self.transaction_hashes.len()
     }
+
+    /// Codifica a proposta no formato usado na rede
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ConsensusError> {
+        let wire = ProposalWire {
+            block_hash: self.block_hash.clone(),
+            block_height: self.block_height,
+            parent_hash: self.parent_hash.clone(),
+            timestamp: self.timestamp,
+            proposer_id: self.proposer_id.clone(),
+            signature: self.signature.clone(),
+            transaction_hashes: self.transaction_hashes.clone(),
+            consensus_data: self.consensus_data.clone(),
+        };
+
+        bincode::serialize(&wire)
+            .map_err(|e| ConsensusError::InternalError(format!("Falha ao codificar proposta: {}", e)))
+    }
+
+    /// Decodifica uma proposta recebida da rede (entrada não confiável)
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ConsensusError> {
+        Self::from_bytes_at(data, clock().now_instant())
+    }
+
+    /// Como `from_bytes`, registrando o recebimento em `received_at`
+    pub fn from_bytes_at(data: &[u8], received_at: Instant) -> Result<Self, ConsensusError> {
+        if data.len() > MAX_PROPOSAL_SIZE {
+            return Err(ConsensusError::InvalidBlock(format!(
+                "Proposta excede o tamanho máximo: {} bytes",
+                data.len()
+            )));
+        }
+
+        let wire: ProposalWire = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .with_limit(MAX_PROPOSAL_SIZE as u64)
+            .deserialize(data)
+            .map_err(|e| ConsensusError::InvalidBlock(format!("Proposta malformada: {}", e)))?;
+
+        Ok(Self {
+            block_hash: wire.block_hash,
+            block_height: wire.block_height,
+            parent_hash: wire.parent_hash,
+            timestamp: wire.timestamp,
+            proposer_id: wire.proposer_id,
+            signature: wire.signature,
+            transaction_hashes: wire.transaction_hashes,
+            consensus_data: wire.consensus_data,
+            received_at,
+        })
+    }
+}
+
+/// Formato de rede da proposta (sem campos locais como `received_at`)
+#[derive(Serialize, Deserialize)]
+struct ProposalWire {
+    block_hash: String,
+    block_height: u64,
+    parent_hash: String,
+    timestamp: u64,
+    proposer_id: String,
+    signature: Vec<u8>,
+    transaction_hashes: Vec<String>,
+    consensus_data: Vec<u8>,
 }
 
 /// Verifica propostas de blocos
@@ -86,6 +216,9 @@ pub struct ProposalVerifier {
 
     /// Sistema de reputação
     reputation: Arc<RwLock<ReputationSystem>>,
+
+    /// Topo local; sem ele, altura e bloco pai não são conferidos
+    tip: Option<ChainTip>,
 }
 
 impl ProposalVerifier {
@@ -97,7 +230,58 @@ impl ProposalVerifier {
         Self {
             validators,
             reputation,
+            tip: None,
+        }
+    }
+
+    /// Exige que as propostas estendam `tip`: altura `tip.height + 1` e bloco pai `tip.hash`
+    pub fn with_tip(mut self, tip: ChainTip) -> Self {
+        self.tip = Some(tip);
+        self
+    }
+
+    /// Confere altura e bloco pai da proposta contra o topo local. Alturas muito
+    /// distantes da esperada penalizam o proponente.
+    fn check_continuity(
+        &self,
+        proposal: &BlockProposal,
+        tip: &ChainTip,
+    ) -> Result<Option<VerificationResult>, ConsensusError> {
+        let expected = tip.height + 1;
+        let reason = if proposal.block_height < expected {
+            format!(
+                "Altura {} já confirmada (topo local {})",
+                proposal.block_height, tip.height
+            )
+        } else if proposal.block_height > expected {
+            format!(
+                "Altura {} à frente da próxima altura local {}",
+                proposal.block_height, expected
+            )
+        } else if proposal.parent_hash != tip.hash {
+            format!(
+                "Bloco pai {} diverge do topo local {}",
+                proposal.parent_hash, tip.hash
+            )
+        } else {
+            return Ok(None);
+        };
+
+        warn!("Proposta de {} rejeitada: {}", proposal.proposer_id, reason);
+        if proposal.block_height.abs_diff(expected) > MAX_PROPOSAL_HEIGHT_GAP {
+            let mut reputation = self.reputation.write().map_err(|_| {
+                ConsensusError::InternalError(
+                    "Falha ao obter lock do sistema de reputação".to_string(),
+                )
+            })?;
+            if let Err(e) = reputation.update_reputation(
+                &proposal.proposer_id,
+                ReputationAction::InvalidBlockProposed,
+            ) {
+                debug!("Não foi possível atualizar reputação: {}", e);
+            }
         }
+        Ok(Some(VerificationResult::Invalid(reason)))
     }
 
     /// Verifica uma proposta de bloco
@@ -145,24 +329,18 @@ impl ProposalVerifier {
         // Aqui seria inserida a verificação criptográfica da assinatura Dilithium
         // usando a chave pública do validador
 
-        // Verifica o timestamp
-        let current_time = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64;
-
-        let time_diff = if current_time > proposal.timestamp {
-            current_time - proposal.timestamp
-        } else {
-            proposal.timestamp - current_time
-        };
+        // Verifica se a proposta estende o topo local
+        if let Some(tip) = &self.tip {
+            if let Some(invalid) = self.check_continuity(proposal, tip)? {
+                return Ok(invalid);
+            }
+        }
 
-        // Rejeita propostas com timestamp muito divergente (mais de 5 minutos)
-        if time_diff > 300_000 {
-            warn!(
-                "Proposta rejeitada: timestamp divergente por {} ms",
-                time_diff
-            );
+        // Verifica o timestamp (em milissegundos)
+        if let Err(violation) = TimestampPolicy::for_context(TimestampContext::Proposal)
+            .validate_millis(proposal.timestamp as i64)
+        {
+            warn!("Proposta rejeitada: {}", violation);
             return Ok(VerificationResult::Invalid(
                 "Timestamp inválido".to_string(),
             ));
@@ -230,12 +408,58 @@ impl ProposalVote {
             validator_id,
             is_in_favor,
             signature,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis() as u64,
+            timestamp: clock().now_millis().max(0) as u64,
         }
     }
+
+    /// Refaz o timestamp do voto pelo relógio dado, no lugar do relógio do nó
+    pub fn with_clock(mut self, clock: &dyn Clock) -> Self {
+        self.timestamp = clock.unix_millis().max(0) as u64;
+        self
+    }
+
+    /// Cria um voto assinado com a chave de consenso do nó
+    pub fn signed(
+        block_hash: String,
+        block_height: u64,
+        validator_id: String,
+        is_in_favor: bool,
+        identity: &NodeIdentity,
+    ) -> Self {
+        let mut vote = Self::new(
+            block_hash,
+            block_height,
+            validator_id,
+            is_in_favor,
+            Vec::new(),
+        );
+        vote.signature = identity.sign_message(&vote.signing_bytes());
+        vote
+    }
+
+    /// Conteúdo coberto pela assinatura do voto
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        format!(
+            "kybelith-vote-v1:{}:{}:{}:{}",
+            self.block_hash, self.block_height, self.validator_id, self.is_in_favor
+        )
+        .into_bytes()
+    }
+
+    /// Confere a assinatura do voto com a chave pública do validador
+    pub fn verify_signature(&self, public_key: &[u8]) -> Result<(), ConsensusError> {
+        let invalid = || {
+            ConsensusError::ValidationFailed(format!(
+                "Assinatura inválida no voto de {}",
+                self.validator_id
+            ))
+        };
+        let public_key = dilithium5::PublicKey::from_bytes(public_key).map_err(|_| invalid())?;
+        let signature =
+            dilithium5::DetachedSignature::from_bytes(&self.signature).map_err(|_| invalid())?;
+        dilithium5::verify_detached_signature(&signature, &self.signing_bytes(), &public_key)
+            .map_err(|_| invalid())
+    }
 }
 
 /// Resultado da votação em uma proposta
@@ -266,6 +490,13 @@ pub struct VotingResult {
     pub is_approved: bool,
 }
 
+/// Votos de uma proposta acompanhada pelo coordenador
+struct TrackedProposal {
+    block_height: u64,
+    last_vote: Instant,
+    votes: Vec<ProposalVote>,
+}
+
 /// Coordena a votação em propostas de blocos
 pub struct VotingCoordinator {
     /// Conjunto de validadores
@@ -274,11 +505,20 @@ pub struct VotingCoordinator {
     /// Sistema de reputação
     reputation: Arc<RwLock<ReputationSystem>>,
 
-    /// Votos recebidos para a proposta atual (hash do bloco -> voto)
-    current_votes: HashMap<String, Vec<ProposalVote>>,
+    /// Votos recebidos por proposta (hash do bloco -> votos)
+    current_votes: HashMap<String, TrackedProposal>,
 
     /// Limiar de aprovação (porcentagem necessária para aprovar)
     approval_threshold: f32,
+
+    /// Tempo sem votos novos até a proposta ser abandonada
+    vote_expiry: Duration,
+
+    /// Máximo de propostas acompanhadas na mesma altura
+    max_proposals_per_height: usize,
+
+    /// Relógio da expiração das propostas
+    clock: SharedClock,
 }
 
 impl VotingCoordinator {
@@ -293,6 +533,74 @@ impl VotingCoordinator {
             reputation,
             current_votes: HashMap::new(),
             approval_threshold,
+            vote_expiry: VOTE_EXPIRY,
+            max_proposals_per_height: MAX_PROPOSALS_PER_HEIGHT,
+            clock: clock().source(),
+        }
+    }
+
+    /// Substitui a expiração de votos e o limite de propostas por altura
+    pub fn with_limits(mut self, vote_expiry: Duration, max_proposals_per_height: usize) -> Self {
+        self.vote_expiry = vote_expiry;
+        self.max_proposals_per_height = max_proposals_per_height.max(1);
+        self
+    }
+
+    /// Substitui o relógio que mede a inatividade das propostas
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Número de propostas com votos em acompanhamento
+    pub fn tracked_proposals(&self) -> usize {
+        self.current_votes.len()
+    }
+
+    /// Descarta as propostas até `height`, inclusive, após a finalização dessa altura.
+    /// Retorna quantas foram removidas.
+    pub fn prune_through(&mut self, height: u64) -> usize {
+        let before = self.current_votes.len();
+        self.current_votes
+            .retain(|_, proposal| proposal.block_height > height);
+        before - self.current_votes.len()
+    }
+
+    /// Descarta propostas abandonadas, sem votos novos dentro de `vote_expiry`.
+    /// Retorna quantas foram removidas.
+    pub fn expire_stale(&mut self) -> usize {
+        let expiry = self.vote_expiry;
+        let now = self.clock.instant();
+        let before = self.current_votes.len();
+        self.current_votes
+            .retain(|_, proposal| now.saturating_duration_since(proposal.last_vote) <= expiry);
+        before - self.current_votes.len()
+    }
+
+    /// Abre espaço para uma nova proposta em `height`, descartando a menos votada
+    /// (e, no empate, a mais antiga) quando a altura já está no limite
+    fn make_room(&mut self, height: u64) {
+        let at_height = self
+            .current_votes
+            .values()
+            .filter(|proposal| proposal.block_height == height)
+            .count();
+        if at_height < self.max_proposals_per_height {
+            return;
+        }
+
+        let weakest = self
+            .current_votes
+            .iter()
+            .filter(|(_, proposal)| proposal.block_height == height)
+            .min_by_key(|(_, proposal)| (proposal.votes.len(), proposal.last_vote))
+            .map(|(hash, _)| hash.clone());
+        if let Some(hash) = weakest {
+            debug!(
+                "Limite de propostas na altura {} atingido; descartando {}",
+                height, hash
+            );
+            self.current_votes.remove(&hash);
         }
     }
 
@@ -316,16 +624,31 @@ impl VotingCoordinator {
             )));
         }
 
+        drop(validators);
+
         // Verifica a assinatura do voto (simplificado)
         // Aqui seria inserida a verificação criptográfica usando Dilithium
 
+        if !self.current_votes.contains_key(&vote.block_hash) {
+            self.make_room(vote.block_height);
+        }
+
         // Verifica se o validador já votou nesta proposta (evita double voting)
-        let votes = self
+        let now = self.clock.instant();
+        let proposal = self
             .current_votes
             .entry(vote.block_hash.clone())
-            .or_insert_with(Vec::new);
-
-        if votes.iter().any(|v| v.validator_id == vote.validator_id) {
+            .or_insert_with(|| TrackedProposal {
+                block_height: vote.block_height,
+                last_vote: now,
+                votes: Vec::new(),
+            });
+
+        if proposal
+            .votes
+            .iter()
+            .any(|v| v.validator_id == vote.validator_id)
+        {
             warn!(
                 "Tentativa de double-voting detectada: {} para bloco {}",
                 vote.validator_id, vote.block_hash
@@ -350,7 +673,8 @@ impl VotingCoordinator {
         }
 
         // Adiciona o voto
-        votes.push(vote.clone());
+        proposal.votes.push(vote.clone());
+        proposal.last_vote = now;
 
         // Atualiza a reputação com base no voto
         // (a correção do voto seria avaliada após a finalização do bloco)
@@ -371,7 +695,7 @@ impl VotingCoordinator {
 
     /// Conta os votos para uma proposta específica
     pub fn tally_votes(&self, block_hash: &str) -> Option<VotingResult> {
-        let votes = self.current_votes.get(block_hash)?;
+        let votes = &self.current_votes.get(block_hash)?.votes;
 
         if votes.is_empty() {
             return None;
@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::time::Instant;
 
+use crate::utils::clock::{clock, SharedClock};
+
 /// Configuração para gerenciamento de épocas
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EpochConfig {
@@ -109,21 +111,52 @@ pub struct EpochManager {
 
     /// Estatísticas da época atual
     current_stats: EpochStats,
+
+    /// Relógio usado para medir a duração das épocas
+    clock: SharedClock,
 }
 
 impl EpochManager {
     /// Cria um novo gerenciador de épocas
     pub fn new(config: EpochConfig) -> Self {
+        Self::with_clock(config, clock().source())
+    }
+
+    /// Cria o gerenciador medindo o tempo das épocas com `clock`
+    pub fn with_clock(config: EpochConfig, clock: SharedClock) -> Self {
         Self {
             config,
             current_epoch: 0,
             current_epoch_start: 0,
-            current_epoch_start_time: Instant::now(),
+            current_epoch_start_time: clock.instant(),
             epoch_history: Vec::new(),
             current_stats: EpochStats::default(),
+            clock,
         }
     }
 
+    /// Segundos decorridos desde o início da época atual
+    fn epoch_elapsed_secs(&self) -> u64 {
+        self.clock
+            .instant()
+            .saturating_duration_since(self.current_epoch_start_time)
+            .as_secs()
+    }
+
+    /// Início da época atual no relógio de parede
+    fn epoch_start_datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        let start_millis = self
+            .clock
+            .unix_millis()
+            .saturating_sub(self.epoch_elapsed_secs() as i64 * 1000);
+        chrono::DateTime::from_timestamp_millis(start_millis).unwrap_or_else(chrono::Utc::now)
+    }
+
+    fn now_datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::from_timestamp_millis(self.clock.unix_millis())
+            .unwrap_or_else(chrono::Utc::now)
+    }
+
     /// Atualiza o gerenciador com um novo bloco
     pub fn process_new_block(&mut self, block_height: u64) -> Option<EpochTransition> {
     // Atualiza estatísticas da época atual
@@ -140,8 +173,9 @@ impl EpochManager {
     /// Verifica se é hora de transicionar para uma nova época
     fn should_transition(&self, block_height: u64) -> bool {
     let blocks_condition = block_height >= self.current_epoch_start + self.config.epoch_length;
-    let min_time_condition = self.current_epoch_start_time.elapsed().as_secs() >= self.config.min_epoch_time_secs;
-    let max_time_condition = self.current_epoch_start_time.elapsed().as_secs() >= self.config.max_epoch_time_secs;
+    let elapsed_secs = self.epoch_elapsed_secs();
+    let min_time_condition = elapsed_secs >= self.config.min_epoch_time_secs;
+    let max_time_condition = elapsed_secs >= self.config.max_epoch_time_secs;
     println!("blocks_condition: {}, min_time: {}, max_time: {}", blocks_condition, min_time_condition, max_time_condition);
 
 
@@ -156,16 +190,8 @@ impl EpochManager {
         epoch_number: self.current_epoch,
         start_block: self.current_epoch_start,
         end_block: block_height - 1,
-        start_time: chrono::DateTime::from_timestamp(
-            (std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs()
-                - self.current_epoch_start_time.elapsed().as_secs()) as i64,
-            0,
-        )
-        .unwrap_or_else(|| chrono::Utc::now()),
-        expected_end_time: chrono::Utc::now(),
+        start_time: self.epoch_start_datetime(),
+        expected_end_time: self.now_datetime(),
         is_completed: true,
         stats: self.current_stats.clone(),
     };
@@ -176,7 +202,7 @@ impl EpochManager {
     // Incrementa para a nova época
     self.current_epoch += 1;
     self.current_epoch_start = block_height;
-    self.current_epoch_start_time = Instant::now();
+    self.current_epoch_start_time = self.clock.instant();
     self.current_stats = EpochStats::default();
 
     // Cria o objeto de transição
@@ -195,16 +221,8 @@ impl EpochManager {
             epoch_number: self.current_epoch,
             start_block: self.current_epoch_start,
             end_block: self.current_epoch_start + self.config.epoch_length - 1,
-            start_time: chrono::DateTime::from_timestamp(
-                (std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs()
-                    - self.current_epoch_start_time.elapsed().as_secs()) as i64,
-                0,
-            )
-            .unwrap_or_else(|| chrono::Utc::now()),
-            expected_end_time: chrono::Utc::now()
+            start_time: self.epoch_start_datetime(),
+            expected_end_time: self.now_datetime()
                 + chrono::Duration::seconds(
                     (self.config.epoch_length - self.current_stats.blocks_produced) as i64
                         * self.config.max_epoch_time_secs as i64
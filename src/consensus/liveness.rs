@@ -0,0 +1,197 @@
+use crate::consensus::reputation::ReputationAction;
+use crate::utils::clock::{clock, SharedClock};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::Instant;
+
+/// Dever de um validador em um slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LivenessDuty {
+    /// Propor o bloco do slot, quando escolhido
+    Proposal,
+    /// Votar na proposta do slot
+    Vote,
+}
+
+/// Parâmetros da janela de disponibilidade
+#[derive(Debug, Clone)]
+pub struct LivenessConfig {
+    /// Quantos deveres recentes de cada validador compõem a janela
+    pub window: usize,
+
+    /// Faltas dentro da janela a partir das quais o validador é dado como offline
+    pub max_missed: usize,
+
+    /// Deveres cumpridos em sequência para um validador offline voltar
+    pub recovery_streak: usize,
+}
+
+impl Default for LivenessConfig {
+    fn default() -> Self {
+        Self {
+            window: 20,
+            max_missed: 5,
+            recovery_streak: 3,
+        }
+    }
+}
+
+/// Desempenho recente de um validador na janela
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct LivenessStats {
+    pub missed_proposals: usize,
+    pub missed_votes: usize,
+    /// Deveres na janela, cumpridos ou não
+    pub duties: usize,
+    /// Faltas seguidas até o último dever registrado
+    pub consecutive_missed: u64,
+    pub offline: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+struct LivenessRecord {
+    /// Deveres mais recentes e se foram cumpridos, do mais antigo ao mais novo
+    recent: VecDeque<(LivenessDuty, bool)>,
+    consecutive_missed: u64,
+    streak: usize,
+    /// Desde quando o validador está dado como offline
+    offline_since: Option<Instant>,
+}
+
+impl LivenessRecord {
+    fn missed(&self) -> usize {
+        self.recent
+            .iter()
+            .filter(|(_, fulfilled)| !fulfilled)
+            .count()
+    }
+}
+
+/// Acompanha faltas de proposta e voto de cada validador em uma janela
+/// deslizante e decide as transições entre online e offline.
+///
+/// A transição para offline acontece uma única vez, ao atingir `max_missed`
+/// faltas na janela; a volta exige `recovery_streak` deveres cumpridos em
+/// sequência. Cada transição vira a `ReputationAction` correspondente.
+#[derive(Debug, Clone)]
+pub struct LivenessTracker {
+    config: LivenessConfig,
+    records: HashMap<String, LivenessRecord>,
+    clock: SharedClock,
+}
+
+impl Default for LivenessTracker {
+    fn default() -> Self {
+        Self::new(LivenessConfig::default())
+    }
+}
+
+impl LivenessTracker {
+    pub fn new(config: LivenessConfig) -> Self {
+        Self {
+            config,
+            records: HashMap::new(),
+            clock: clock().source(),
+        }
+    }
+
+    /// Relógio que marca o início dos períodos offline
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    pub fn config(&self) -> &LivenessConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: LivenessConfig) {
+        self.config = config;
+    }
+
+    /// Registra um dever do validador; retorna a transição que ele provocou
+    pub fn record(
+        &mut self,
+        validator_id: &str,
+        duty: LivenessDuty,
+        fulfilled: bool,
+    ) -> Option<ReputationAction> {
+        let config = &self.config;
+        let record = self.records.entry(validator_id.to_string()).or_default();
+        record.recent.push_back((duty, fulfilled));
+        while record.recent.len() > config.window {
+            record.recent.pop_front();
+        }
+
+        if fulfilled {
+            record.consecutive_missed = 0;
+            record.streak += 1;
+            if record.offline_since.is_some() && record.streak >= config.recovery_streak {
+                record.offline_since = None;
+                return Some(ReputationAction::BackOnline);
+            }
+        } else {
+            record.consecutive_missed += 1;
+            record.streak = 0;
+            if record.offline_since.is_none() && record.missed() >= config.max_missed {
+                record.offline_since = Some(self.clock.instant());
+                return Some(ReputationAction::Offline);
+            }
+        }
+        None
+    }
+
+    /// Dá o validador como offline por inatividade; `false` se já estava
+    pub fn mark_offline(&mut self, validator_id: &str) -> bool {
+        let record = self.records.entry(validator_id.to_string()).or_default();
+        if record.offline_since.is_some() {
+            return false;
+        }
+        record.offline_since = Some(self.clock.instant());
+        record.streak = 0;
+        true
+    }
+
+    /// Traz de volta um validador offline que foi visto depois de `seen_at`,
+    /// desde que as faltas na janela já estejam abaixo de `max_missed`
+    pub fn mark_seen(&mut self, validator_id: &str, seen_at: Instant) -> bool {
+        let max_missed = self.config.max_missed;
+        let Some(record) = self.records.get_mut(validator_id) else {
+            return false;
+        };
+        match record.offline_since {
+            Some(since) if seen_at > since && record.missed() < max_missed => {
+                record.offline_since = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_offline(&self, validator_id: &str) -> bool {
+        self.records
+            .get(validator_id)
+            .is_some_and(|record| record.offline_since.is_some())
+    }
+
+    pub fn stats(&self, validator_id: &str) -> LivenessStats {
+        let Some(record) = self.records.get(validator_id) else {
+            return LivenessStats::default();
+        };
+        let missed = |duty: LivenessDuty| {
+            record
+                .recent
+                .iter()
+                .filter(|(kind, fulfilled)| *kind == duty && !fulfilled)
+                .count()
+        };
+        LivenessStats {
+            missed_proposals: missed(LivenessDuty::Proposal),
+            missed_votes: missed(LivenessDuty::Vote),
+            duties: record.recent.len(),
+            consecutive_missed: record.consecutive_missed,
+            offline: record.offline_since.is_some(),
+        }
+    }
+}
@@ -1,17 +1,33 @@
+pub mod beacon;
 pub mod block_proposal;
 pub mod epoch;
+pub mod liveness;
+pub mod payload;
+pub mod performance;
 pub mod quantum_flex;
 pub mod reputation;
+pub mod simulation;
 pub mod threat_detection;
 pub mod types;
 pub mod validator;
 
 // Reexporta os itens principais para uso externo
-pub use block_proposal::{BlockProposal, ProposalVerifier, ProposalVote, VotingCoordinator};
+pub use beacon::{BeaconCommit, BeaconReveal, BeaconValue, RandomnessBeacon};
+pub use block_proposal::{
+    BlockProposal, ChainTip, ProposalVerifier, ProposalVote, VotingCoordinator,
+};
 pub use epoch::{EpochConfig, EpochManager, EpochTransition};
+pub use liveness::{LivenessConfig, LivenessDuty, LivenessStats, LivenessTracker};
+pub use payload::{validate_payload, vote_on_proposal, BlockSource};
+pub use performance::{
+    PerformanceLedger, PerformanceReport, SlashRecord, ValidatorEpochPerformance,
+};
 pub use quantum_flex::QuantumFlexConsensus as OtherQuantumFlexConsensus;
 pub use quantum_flex::{ConsensusMetrics, ValidatorInfo}; // Reexporta de quantum_flex, onde estão definidos
-pub use reputation::{ReputationAction, ReputationSystem};
+pub use reputation::{
+    ReputationAction, ReputationObservation, ReputationSummary, ReputationSystem,
+};
+pub use simulation::{Simulation, SimulationConfig, SimulationReport};
 pub use threat_detection::{
     detect_threats, evaluate_threat_level, ThreatInfo, ThreatLevel, ThreatType,
 };
@@ -22,6 +38,8 @@ pub use types::{
 pub use validator::{Validator, ValidatorSet};
 
 use crate::config::Settings;
+use crate::events::EventBus;
+use crate::utils::clock::{clock, SharedClock};
 use log::{debug, error, info, warn};
 use std::sync::{Arc, RwLock};
 use tokio::sync::mpsc::{self, Receiver, Sender};
@@ -48,6 +66,9 @@ pub enum ConsensusMessage {
     /// Notificação de nova época
     NewEpoch(EpochTransition),
 
+    /// Resumo de reputação recebido de outro validador
+    ReputationSummary(ReputationSummary),
+
     /// Notificação de novo bloco finalizado
     BlockFinalized {
         block_hash: String,
@@ -83,6 +104,9 @@ pub struct QuantumFlexConsensus {
 
     /// Flag indicando se o consenso está em execução
     is_running: RwLock<bool>,
+
+    /// Relógio repassado ao estado, à reputação, às épocas e à votação
+    clock: SharedClock,
 }
 
 impl QuantumFlexConsensus {
@@ -100,8 +124,12 @@ impl QuantumFlexConsensus {
             validator_config,
         )));
 
-        // Configura o sistema de reputação
-        let reputation = Arc::new(RwLock::new(ReputationSystem::new()));
+        // Configura o sistema de reputação, já acompanhando os validadores iniciais
+        let mut reputation = ReputationSystem::new();
+        for validator in validators.read().unwrap().active_validators() {
+            reputation.add_validator(validator.id.clone());
+        }
+        let reputation = Arc::new(RwLock::new(reputation));
 
         // Configura o gerenciador de épocas
         let epoch_config = epoch::EpochConfig::new(config.consensus.epoch_length);
@@ -129,9 +157,26 @@ impl QuantumFlexConsensus {
             network_metrics: RwLock::new(NetworkMetrics::default()),
             message_sender: None,
             is_running: RwLock::new(false),
+            clock: clock().source(),
         }
     }
 
+    /// Substitui o relógio do consenso antes do início: adaptação, métricas de
+    /// rede, reputação, épocas e votação passam a medir o tempo por `clock`
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        let consensus_type = self.state.get_mut().unwrap().consensus_type;
+        self.state = RwLock::new(ConsensusState::with_clock(consensus_type, clock.clone()));
+        self.network_metrics = RwLock::new(NetworkMetrics::new_at(clock.instant()));
+        self.reputation.write().unwrap().set_clock(clock.clone());
+        let epoch_config = epoch::EpochConfig::new(self.config.consensus.epoch_length);
+        self.epoch_manager = Arc::new(RwLock::new(EpochManager::with_clock(
+            epoch_config,
+            clock.clone(),
+        )));
+        self.clock = clock;
+        self
+    }
+
     /// Inicia o sistema de consenso (em background)
     // Apenas o trecho relevante com o problema de delimitadores
 
@@ -161,6 +206,7 @@ impl QuantumFlexConsensus {
         let validators = Arc::clone(&self.validators);
         let reputation = Arc::clone(&self.reputation);
         let epoch_manager = Arc::clone(&self.epoch_manager);
+        let clock = Arc::clone(&self.clock);
         let is_running = Arc::new(RwLock::new(*self.is_running.read().unwrap()));
 
         // Inicia o worker em uma tarefa separada
@@ -170,6 +216,7 @@ impl QuantumFlexConsensus {
                 validators,
                 reputation,
                 epoch_manager,
+                clock,
                 rx,
                 is_running,
             )
@@ -258,6 +305,28 @@ impl QuantumFlexConsensus {
         }
     }
 
+    /// Encaminha um resumo de reputação recebido de um par; é conferido com a
+    /// chave do validador observador antes de ser incorporado
+    pub async fn process_reputation_summary(
+        &self,
+        summary: ReputationSummary,
+    ) -> Result<(), ConsensusError> {
+        if let Some(tx) = &self.message_sender {
+            tx.send(ConsensusMessage::ReputationSummary(summary))
+                .await
+                .map_err(|_| {
+                    ConsensusError::InternalError(
+                        "Falha ao enviar resumo de reputação para processamento".to_string(),
+                    )
+                })?;
+            Ok(())
+        } else {
+            Err(ConsensusError::InternalError(
+                "Consenso não está em execução".to_string(),
+            ))
+        }
+    }
+
     /// Solicita a criação de uma proposta de bloco (para validadores)
     pub async fn request_block_proposal(&self) -> Result<(), ConsensusError> {
         if let Some(tx) = &self.message_sender {
@@ -272,6 +341,30 @@ impl QuantumFlexConsensus {
         }
     }
 
+    /// Informa ao consenso um bloco confirmado localmente; propostas seguintes
+    /// precisam estendê-lo
+    pub async fn notify_block_finalized(
+        &self,
+        block_hash: String,
+        block_height: u64,
+    ) -> Result<(), ConsensusError> {
+        if let Some(tx) = &self.message_sender {
+            tx.send(ConsensusMessage::BlockFinalized {
+                block_hash,
+                block_height,
+            })
+            .await
+            .map_err(|_| {
+                ConsensusError::InternalError("Falha ao notificar bloco finalizado".to_string())
+            })?;
+            Ok(())
+        } else {
+            Err(ConsensusError::InternalError(
+                "Consenso não está em execução".to_string(),
+            ))
+        }
+    }
+
     /// Atualiza as métricas de rede
     pub fn update_network_metrics(&self, metrics: NetworkMetrics) {
         let mut current = self.network_metrics.write().unwrap();
@@ -325,11 +418,13 @@ impl QuantumFlexConsensus {
         validators: Arc<RwLock<ValidatorSet>>,
         reputation: Arc<RwLock<ReputationSystem>>,
         epoch_manager: Arc<RwLock<EpochManager>>,
+        clock: SharedClock,
         mut rx: Receiver<ConsensusMessage>,
         is_running: Arc<RwLock<bool>>,
     ) -> () {
         // Estado local do worker
         let mut current_block_height = 0u64;
+        let mut chain_tip: Option<ChainTip> = None;
         let mut current_consensus_type =
             ConsensusType::from_str(&config.consensus.initial_consensus_type)
                 .unwrap_or(ConsensusType::Adaptive);
@@ -339,7 +434,8 @@ impl QuantumFlexConsensus {
             Arc::clone(&validators),
             Arc::clone(&reputation),
             config.consensus.finality_threshold_percentage,
-        );
+        )
+        .with_clock(clock);
 
         // Loop principal do worker
         while {
@@ -355,9 +451,12 @@ impl QuantumFlexConsensus {
                             proposal.block_hash, proposal.block_height
                         );
 
-                        // Verifica a proposta
-                        let verifier =
+                        // Verifica a proposta, contra o topo local quando já conhecido
+                        let mut verifier =
                             ProposalVerifier::new(Arc::clone(&validators), Arc::clone(&reputation));
+                        if let Some(tip) = &chain_tip {
+                            verifier = verifier.with_tip(tip.clone());
+                        }
 
                         match verifier.verify(&proposal) {
                             Ok(VerificationResult::Valid) => {
@@ -394,6 +493,10 @@ impl QuantumFlexConsensus {
                                             "Transição de época detectada: {} -> {}",
                                             transition.previous_epoch, transition.new_epoch
                                         );
+                                        reputation
+                                            .write()
+                                            .unwrap()
+                                            .begin_epoch(transition.new_epoch);
 
                                         // Envia uma mensagem de nova época
                                         // (em uma implementação real, isso seria feito através do canal)
@@ -436,6 +539,12 @@ impl QuantumFlexConsensus {
                             }
                         );
 
+                        // Descarta propostas abandonadas antes de acompanhar novos votos
+                        let expired = voting_coordinator.expire_stale();
+                        if expired > 0 {
+                            debug!("{} propostas sem votos recentes descartadas", expired);
+                        }
+
                         // Processa o voto
                         match voting_coordinator.process_vote(vote.clone()) {
                             Ok(_) => {
@@ -493,8 +602,32 @@ impl QuantumFlexConsensus {
                             transition.new_epoch, transition.activation_block
                         );
 
-                        // Implementa lógica de transição de época
-                        // Por exemplo, poderia recalcular distribuição de stake, ajustar parâmetros, etc.
+                        // O desempenho dos validadores passa a contar para a nova época
+                        reputation
+                            .write()
+                            .unwrap()
+                            .begin_epoch(transition.new_epoch);
+                    }
+                    ConsensusMessage::ReputationSummary(summary) => {
+                        let public_key = validators
+                            .read()
+                            .unwrap()
+                            .get_validator(&summary.observer)
+                            .map(|validator| validator.public_key.clone());
+                        let Some(public_key) = public_key else {
+                            warn!(
+                                "Resumo de reputação de validador desconhecido: {}",
+                                summary.observer
+                            );
+                            continue;
+                        };
+                        if let Err(e) = reputation
+                            .write()
+                            .unwrap()
+                            .import_summary(&summary, &public_key)
+                        {
+                            warn!("Resumo de reputação rejeitado: {}", e);
+                        }
                     }
                     ConsensusMessage::BlockFinalized {
                         block_hash,
@@ -504,6 +637,13 @@ impl QuantumFlexConsensus {
 
                         // Atualiza estado interno com o novo bloco finalizado
                         current_block_height = block_height;
+                        chain_tip = Some(ChainTip {
+                            height: block_height,
+                            hash: block_hash,
+                        });
+
+                        // Votos de alturas já finalizadas não serão mais contados
+                        voting_coordinator.prune_through(block_height);
 
                         // Atualiza reputação dos validadores com base nos votos corretos/incorretos
                         // (em uma implementação real, isto seria mais complexo)
@@ -534,6 +674,50 @@ impl QuantumFlexConsensus {
         *self.is_running.read().unwrap()
     }
 
+    /// Liga o sistema de reputação ao barramento de eventos do nó
+    pub fn set_event_bus(&self, events: EventBus) {
+        self.reputation.write().unwrap().set_event_bus(events);
+    }
+
+    /// Aplica o valor de uma rodada encerrada do beacon à seleção de proponentes
+    pub fn apply_beacon(&self, value: BeaconValue) {
+        self.validators.write().unwrap().set_beacon(value);
+    }
+
+    /// Registra se um validador cumpriu seu dever de proposta ou voto no slot
+    /// (ver `ReputationSystem::record_duty`)
+    pub fn record_duty(
+        &self,
+        validator_id: &str,
+        duty: LivenessDuty,
+        fulfilled: bool,
+    ) -> Result<Option<ReputationAction>, String> {
+        self.reputation
+            .write()
+            .unwrap()
+            .record_duty(validator_id, duty, fulfilled)
+    }
+
+    /// Registra uma recompensa paga a um validador na época corrente, para os
+    /// relatórios de desempenho
+    pub fn record_reward(&self, validator_id: &str, amount: u64) -> Result<(), String> {
+        self.reputation
+            .write()
+            .unwrap()
+            .record_reward(validator_id, amount)
+    }
+
+    /// Lê o conjunto de validadores e as reputações de uma só vez, para
+    /// consultas de monitoramento
+    pub fn inspect_validators<R>(
+        &self,
+        f: impl FnOnce(&ValidatorSet, &ReputationSystem) -> R,
+    ) -> R {
+        let validators = self.validators.read().unwrap();
+        let reputation = self.reputation.read().unwrap();
+        f(&validators, &reputation)
+    }
+
     /// Obtém as métricas de rede atuais
     pub fn get_network_metrics(&self) -> NetworkMetrics {
         self.network_metrics.read().unwrap().clone()
@@ -0,0 +1,102 @@
+use crate::blockchain::{verify_assembly, Block, Blockchain};
+use crate::consensus::block_proposal::{BlockProposal, ProposalVote};
+use crate::consensus::types::ConsensusError;
+use crate::network::NodeIdentity;
+use log::{debug, warn};
+use std::collections::HashMap;
+
+/// Origem dos corpos dos blocos propostos: pares da rede ou um cache local
+pub trait BlockSource {
+    /// Corpo do bloco `block_hash`, se disponível
+    fn fetch_block(&self, block_hash: &str) -> Option<Block>;
+}
+
+impl BlockSource for HashMap<String, Block> {
+    fn fetch_block(&self, block_hash: &str) -> Option<Block> {
+        self.get(block_hash).cloned()
+    }
+}
+
+/// Valida o corpo de um bloco proposto: cabeçalho igual ao da proposta, bloco
+/// idêntico ao remontado por `assemble_block` com os itens listados na proposta e, via
+/// `validate_new_block`, a assinatura de cada transação e a transição de estado
+/// reexecutada sobre `blockchain`, sem alterá-la. Operações e transferências do
+/// corpo passam por `replay_body` (assinatura, chave, nonce, saldo) sobre uma
+/// cópia do estado, que deve reproduzir o resumo de execução do proponente.
+pub fn validate_payload(
+    blockchain: &Blockchain,
+    proposal: &BlockProposal,
+    block: &Block,
+) -> Result<(), ConsensusError> {
+    if block.hash != proposal.block_hash
+        || block.index != proposal.block_height
+        || block.previous_hash != proposal.parent_hash
+    {
+        return Err(ConsensusError::InvalidBlock(
+            "Cabeçalho do bloco não corresponde à proposta".to_string(),
+        ));
+    }
+    if block
+        .proposer
+        .as_ref()
+        .is_some_and(|proposer| *proposer != proposal.proposer_id)
+    {
+        return Err(ConsensusError::InvalidProposer(format!(
+            "Bloco assinado por outro proponente que não {}",
+            proposal.proposer_id
+        )));
+    }
+
+    verify_assembly(block, &proposal.transaction_hashes)
+        .map_err(|e| ConsensusError::InvalidBlock(e.to_string()))?;
+
+    blockchain
+        .validate_new_block(block)
+        .map_err(|e| ConsensusError::ValidationFailed(e.to_string()))
+}
+
+/// Busca o corpo do bloco proposto, valida-o com `validate_payload` e devolve o voto
+/// assinado por `identity`: a favor só se o corpo for válido.
+///
+/// Sem o corpo não há voto; o chamador pode tentar de novo quando ele chegar. Um nó
+/// com binário anterior à versão exigida na altura da proposta também não vota.
+pub fn vote_on_proposal(
+    blockchain: &Blockchain,
+    proposal: &BlockProposal,
+    source: &dyn BlockSource,
+    validator_id: String,
+    identity: &NodeIdentity,
+) -> Result<ProposalVote, ConsensusError> {
+    blockchain
+        .check_client_version(proposal.block_height)
+        .map_err(|e| ConsensusError::InternalError(e.to_string()))?;
+
+    let block = source.fetch_block(&proposal.block_hash).ok_or_else(|| {
+        ConsensusError::ValidationFailed(format!(
+            "Corpo do bloco {} indisponível",
+            proposal.block_hash
+        ))
+    })?;
+
+    let in_favor = match validate_payload(blockchain, proposal, &block) {
+        Ok(()) => {
+            debug!(
+                "Bloco {} validado; voto a favor de {}",
+                block.index, validator_id
+            );
+            true
+        }
+        Err(e) => {
+            warn!("Bloco {} rejeitado na validação: {}", block.index, e);
+            false
+        }
+    };
+
+    Ok(ProposalVote::signed(
+        proposal.block_hash.clone(),
+        proposal.block_height,
+        validator_id,
+        in_favor,
+        identity,
+    ))
+}
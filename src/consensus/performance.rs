@@ -0,0 +1,231 @@
+use crate::consensus::liveness::LivenessDuty;
+use crate::consensus::reputation::ReputationAction;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Épocas mantidas pelo livro de desempenho; as mais antigas são descartadas
+pub const RETAINED_EPOCHS: u64 = 256;
+
+/// Penalidade de um validador em uma época: a infração, os pontos de reputação
+/// perdidos e o banimento que ela provocou, se houve
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SlashRecord {
+    pub offence: String,
+    pub penalty: f32,
+    pub ban_secs: Option<u64>,
+}
+
+/// Desempenho de um validador em uma época
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ValidatorEpochPerformance {
+    pub epoch: u64,
+    pub validator_id: String,
+    pub blocks_proposed: u64,
+    pub invalid_proposals: u64,
+    /// Slots em que o validador foi escolhido e não propôs
+    pub blocks_missed: u64,
+    pub votes_cast: u64,
+    pub votes_missed: u64,
+    /// Reputação antes do primeiro registro da época e depois do último
+    pub reputation_start: f32,
+    pub reputation_end: f32,
+    pub reputation_min: f32,
+    pub reputation_max: f32,
+    /// Recompensas creditadas ao validador na época (`record_reward`)
+    pub rewards: u64,
+    pub slashes: Vec<SlashRecord>,
+}
+
+impl ValidatorEpochPerformance {
+    fn new(epoch: u64, validator_id: &str, score: f32) -> Self {
+        Self {
+            epoch,
+            validator_id: validator_id.to_string(),
+            blocks_proposed: 0,
+            invalid_proposals: 0,
+            blocks_missed: 0,
+            votes_cast: 0,
+            votes_missed: 0,
+            reputation_start: score,
+            reputation_end: score,
+            reputation_min: score,
+            reputation_max: score,
+            rewards: 0,
+            slashes: Vec::new(),
+        }
+    }
+
+    fn observe_score(&mut self, score: f32) {
+        self.reputation_end = score;
+        self.reputation_min = self.reputation_min.min(score);
+        self.reputation_max = self.reputation_max.max(score);
+    }
+}
+
+/// Infrações que contam como penalidade mesmo sem levar ao banimento
+fn is_offence(action: ReputationAction) -> bool {
+    matches!(
+        action,
+        ReputationAction::InvalidBlockProposed
+            | ReputationAction::DoubleVote
+            | ReputationAction::UnauthorizedProposal
+    )
+}
+
+/// Relatório de desempenho por época, para delegadores e para a governança
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PerformanceReport {
+    pub from_epoch: u64,
+    pub to_epoch: u64,
+    /// Ordenados por época e, dentro dela, por validador
+    pub records: Vec<ValidatorEpochPerformance>,
+}
+
+const CSV_HEADER: &str = "epoch,validator_id,blocks_proposed,invalid_proposals,blocks_missed,\
+votes_cast,votes_missed,reputation_start,reputation_end,reputation_min,reputation_max,\
+rewards,slashes,slash_penalty,ban_secs";
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+impl PerformanceReport {
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Uma linha por validador e época; as penalidades aparecem somadas
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(CSV_HEADER);
+        csv.push('\n');
+        for record in &self.records {
+            // `sum` de f32 vazio dá -0.0, que sairia como "-0.00"
+            let penalty = record
+                .slashes
+                .iter()
+                .fold(0.0_f32, |total, slash| total + slash.penalty);
+            let ban_secs: u64 = record
+                .slashes
+                .iter()
+                .filter_map(|slash| slash.ban_secs)
+                .sum();
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{:.2},{:.2},{:.2},{:.2},{},{},{:.2},{}\n",
+                record.epoch,
+                csv_field(&record.validator_id),
+                record.blocks_proposed,
+                record.invalid_proposals,
+                record.blocks_missed,
+                record.votes_cast,
+                record.votes_missed,
+                record.reputation_start,
+                record.reputation_end,
+                record.reputation_min,
+                record.reputation_max,
+                record.rewards,
+                record.slashes.len(),
+                penalty,
+                ban_secs
+            ));
+        }
+        csv
+    }
+}
+
+/// Livro do desempenho de cada validador por época, alimentado pelo sistema de
+/// reputação. Só as últimas `RETAINED_EPOCHS` épocas são mantidas.
+#[derive(Debug, Clone, Default)]
+pub struct PerformanceLedger {
+    current_epoch: u64,
+    records: BTreeMap<(u64, String), ValidatorEpochPerformance>,
+}
+
+impl PerformanceLedger {
+    pub fn current_epoch(&self) -> u64 {
+        self.current_epoch
+    }
+
+    /// Passa a registrar em `epoch`, descartando o que saiu da retenção
+    pub fn begin_epoch(&mut self, epoch: u64) {
+        self.current_epoch = self.current_epoch.max(epoch);
+        let oldest = self.current_epoch.saturating_sub(RETAINED_EPOCHS - 1);
+        self.records.retain(|(epoch, _), _| *epoch >= oldest);
+    }
+
+    fn entry(&mut self, validator_id: &str, score: f32) -> &mut ValidatorEpochPerformance {
+        let epoch = self.current_epoch;
+        self.records
+            .entry((epoch, validator_id.to_string()))
+            .or_insert_with(|| ValidatorEpochPerformance::new(epoch, validator_id, score))
+    }
+
+    /// Registra uma ação aplicada à reputação, com a pontuação antes e depois
+    pub fn observe(
+        &mut self,
+        validator_id: &str,
+        action: ReputationAction,
+        score_before: f32,
+        score_after: f32,
+        banned_for: Option<Duration>,
+    ) {
+        let record = self.entry(validator_id, score_before);
+        match action {
+            ReputationAction::ValidBlockProposed => record.blocks_proposed += 1,
+            ReputationAction::InvalidBlockProposed => record.invalid_proposals += 1,
+            ReputationAction::CorrectVote | ReputationAction::IncorrectVote => {
+                record.votes_cast += 1
+            }
+            _ => {}
+        }
+        if is_offence(action) || banned_for.is_some() {
+            record.slashes.push(SlashRecord {
+                offence: format!("{:?}", action),
+                penalty: (score_before - score_after).max(0.0),
+                ban_secs: banned_for.map(|duration| duration.as_secs()),
+            });
+        }
+        record.observe_score(score_after);
+    }
+
+    /// Registra um dever de proposta ou voto não cumprido
+    pub fn record_missed(&mut self, validator_id: &str, duty: LivenessDuty, score: f32) {
+        let record = self.entry(validator_id, score);
+        match duty {
+            LivenessDuty::Proposal => record.blocks_missed += 1,
+            LivenessDuty::Vote => record.votes_missed += 1,
+        }
+    }
+
+    /// Soma uma recompensa creditada ao validador na época corrente
+    pub fn record_reward(&mut self, validator_id: &str, amount: u64, score: f32) {
+        let record = self.entry(validator_id, score);
+        record.rewards = record.rewards.saturating_add(amount);
+    }
+
+    /// Desempenho nas épocas `from_epoch..=to_epoch`, de um validador ou de todos
+    pub fn report(
+        &self,
+        validator_id: Option<&str>,
+        from_epoch: u64,
+        to_epoch: u64,
+    ) -> PerformanceReport {
+        let records = self
+            .records
+            .range((from_epoch, String::new())..)
+            .take_while(|((epoch, _), _)| *epoch <= to_epoch)
+            .filter(|((_, id), _)| validator_id.is_none_or(|wanted| wanted == id))
+            .map(|(_, record)| record.clone())
+            .collect();
+        PerformanceReport {
+            from_epoch,
+            to_epoch,
+            records,
+        }
+    }
+}
@@ -328,7 +328,7 @@ impl QuantumFlexConsensus {
 
             debug!(
                 "⏱️ Tempo desde última adaptação: {:.2}s",
-                state.last_adaptation.elapsed().as_secs_f32()
+                state.since_adaptation().as_secs_f32()
             );
         } else {
             debug!(
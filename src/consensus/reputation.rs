@@ -3,7 +3,17 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
+use crate::consensus::liveness::{LivenessConfig, LivenessDuty, LivenessTracker};
+use crate::consensus::performance::{PerformanceLedger, PerformanceReport};
+use crate::consensus::types::ConsensusError;
+use crate::events::{AppEvent, EventBus};
+use crate::network::NodeIdentity;
+use crate::utils::clock::{clock, SharedClock};
 use crate::utils::serde_helpers::SerializableInstant;
+use pqcrypto_dilithium::dilithium5;
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _};
+
+const SUMMARY_DOMAIN: &str = "kybelith-reputation-v1";
 
 /// Ações que podem afetar a reputação de um validador
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -74,11 +84,20 @@ pub struct ValidatorReputation {
 
     /// Até quando o validador está banido (se aplicável)
     pub banned_until: Option<SerializableInstant>,
+
+    /// Banimentos recentes, antes do decaimento; define o degrau do próximo
+    #[serde(default)]
+    pub recent_bans: u32,
+
+    /// Fim do último banimento, de onde conta o decaimento das reincidências
+    #[serde(default)]
+    pub last_ban_end: Option<SerializableInstant>,
 }
 
 impl ValidatorReputation {
-    /// Cria um novo registro de reputação para um validador
-    pub fn new(validator_id: String) -> Self {
+    /// Cria o registro de um validador visto pela última vez em `now`; o instante
+    /// vem do relógio de quem mantém o registro (`ReputationSystem::set_clock`)
+    pub fn new_at(validator_id: String, now: Instant) -> Self {
         Self {
             validator_id,
             score: 50.0, // Inicia com reputação neutra
@@ -88,33 +107,60 @@ impl ValidatorReputation {
             incorrect_votes: 0,
             timeouts: 0,
             double_votes: 0,
-            last_seen: Instant::now().into(),
+            last_seen: now.into(),
             is_banned: false,
             banned_until: None,
+            recent_bans: 0,
+            last_ban_end: None,
         }
     }
 
     /// Atualiza o momento em que o validador foi visto pela última vez
-    pub fn update_last_seen(&mut self) {
-        self.last_seen = Instant::now().into();
+    pub fn update_last_seen_at(&mut self, now: Instant) {
+        self.last_seen = now.into();
     }
 
     /// Verifica se o validador está offline com base em um limite de tempo
-    pub fn is_offline(&self, threshold: Duration) -> bool {
-        self.last_seen.elapsed() > threshold
+    pub fn is_offline_at(&self, threshold: Duration, now: Instant) -> bool {
+        self.last_seen.elapsed_at(now) > threshold
     }
 
-    /// Bane o validador por um período específico
-    pub fn ban(&mut self, duration: Duration) {
+    /// Bane o validador por `duration` a partir de `now`
+    pub fn ban_at(&mut self, duration: Duration, now: Instant) {
         self.is_banned = true;
-        self.banned_until = Some((Instant::now() + duration).into());
+        self.banned_until = Some((now + duration).into());
+        self.last_ban_end = self.banned_until.clone();
         self.score = self.score.max(10.0); // Reduz a reputação, mas mantém um mínimo
     }
 
+    /// Reincidências que ainda pesam no próximo banimento: cada `decay` passado
+    /// desde o fim do último banimento desconta uma
+    pub fn recidivism_at(&self, decay: Duration, now: Instant) -> u32 {
+        let Some(last_ban_end) = &self.last_ban_end else {
+            return 0;
+        };
+        if decay.is_zero() {
+            return 0;
+        }
+        let decayed = last_ban_end.elapsed_at(now).as_nanos() / decay.as_nanos();
+        self.recent_bans
+            .saturating_sub(u32::try_from(decayed).unwrap_or(u32::MAX))
+    }
+
+    /// Bane pelo degrau da escada de `config` que corresponde às reincidências
+    /// recentes e retorna a duração aplicada
+    pub fn ban_escalated_at(&mut self, config: &ReputationConfig, now: Instant) -> Duration {
+        let recidivism = self.recidivism_at(config.recidivism_decay, now);
+        let duration = config.ban_duration(recidivism);
+        self.ban_at(duration, now);
+        self.recent_bans = recidivism.saturating_add(1);
+        duration
+    }
+
     /// Verifica se o banimento expirou e atualiza o status
-    pub fn check_ban_status(&mut self) -> bool {
+    pub fn check_ban_status_at(&mut self, now: Instant) -> bool {
         if let Some(until) = &self.banned_until {
-            if Instant::now() >= until.to_instant() {
+            if now >= until.to_instant() {
                 self.is_banned = false;
                 self.banned_until = None;
                 return true; // Ban expirou
@@ -132,6 +178,21 @@ pub struct ReputationSystem {
 
     /// Configurações de ajuste de reputação
     config: ReputationConfig,
+
+    /// Destino dos eventos de banimento, quando ligado a um nó
+    events: Option<EventBus>,
+
+    /// `issued_at` do último resumo aceito de cada observador
+    last_summaries: HashMap<String, u64>,
+
+    /// Faltas recentes de proposta e voto de cada validador
+    liveness: LivenessTracker,
+
+    /// Desempenho de cada validador por época
+    performance: PerformanceLedger,
+
+    /// Relógio dos banimentos e da última atividade
+    clock: SharedClock,
 }
 
 /// Configurações para o sistema de reputação
@@ -170,8 +231,25 @@ pub struct ReputationConfig {
     /// Duração do banimento inicial (aumenta com reincidências)
     pub initial_ban_duration: Duration,
 
+    /// Multiplicadores de `initial_ban_duration` a cada reincidência recente,
+    /// em ordem; o último se repete
+    pub ban_escalation: Vec<u32>,
+
+    /// Tempo sem banimento após o qual uma reincidência deixa de contar
+    pub recidivism_decay: Duration,
+
     /// Fator de decaimento para ajustes negativos repetidos
     pub decay_factor: f32,
+
+    /// Peso (0 a 1) das observações recebidas de um par sem entrada em
+    /// `peer_trust`; 0 ignora os resumos, 1 substitui a visão local
+    pub default_peer_trust: f32,
+
+    /// Peso por nó observador, sobrepondo `default_peer_trust`
+    pub peer_trust: HashMap<String, f32>,
+
+    /// Janela de faltas que decide as transições `Offline`/`BackOnline`
+    pub liveness: LivenessConfig,
 }
 
 impl Default for ReputationConfig {
@@ -188,17 +266,40 @@ impl Default for ReputationConfig {
             suspicious_threshold: 30.0,
             ban_threshold: 15.0,
             initial_ban_duration: Duration::from_secs(3600), // 1 hora
+            ban_escalation: vec![1, 6, 24, 168], // 1h, 6h, 24h, 7 dias
+            recidivism_decay: Duration::from_secs(7 * 24 * 3600),
             decay_factor: 0.9,
+            default_peer_trust: 0.2,
+            peer_trust: HashMap::new(),
+            liveness: LivenessConfig::default(),
         }
     }
 }
 
+impl ReputationConfig {
+    /// Duração do banimento de quem tem `recidivism` reincidências recentes
+    pub fn ban_duration(&self, recidivism: u32) -> Duration {
+        let step = self
+            .ban_escalation
+            .get(recidivism as usize)
+            .or(self.ban_escalation.last())
+            .copied()
+            .unwrap_or(1);
+        self.initial_ban_duration.saturating_mul(step)
+    }
+}
+
 impl ReputationSystem {
     /// Cria um novo sistema de reputação com configurações padrão
     pub fn new() -> Self {
         Self {
             reputations: HashMap::new(),
             config: ReputationConfig::default(),
+            events: None,
+            last_summaries: HashMap::new(),
+            liveness: LivenessTracker::default(),
+            performance: PerformanceLedger::default(),
+            clock: clock().source(),
         }
     }
 
@@ -207,9 +308,30 @@ impl ReputationSystem {
     }
 
     pub fn set_config(&mut self, config: ReputationConfig) {
+        self.liveness.set_config(config.liveness.clone());
         self.config = config;
     }
 
+    /// Troca o relógio dos banimentos, da última atividade e da disponibilidade
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.liveness.set_clock(clock.clone());
+        self.clock = clock;
+    }
+
+    /// Publica `AppEvent::ValidatorBanned` no barramento a cada banimento
+    pub fn set_event_bus(&mut self, events: EventBus) {
+        self.events = Some(events);
+    }
+
+    fn notify_ban(&self, validator_id: &str, duration: Duration) {
+        if let Some(events) = &self.events {
+            events.emit(AppEvent::ValidatorBanned {
+                validator_id: validator_id.to_string(),
+                duration_secs: duration.as_secs(),
+            });
+        }
+    }
+
     pub fn ban_validator(&mut self, validator_id: &str, duration: Duration) -> Result<(), String> {
         // Obtém a reputação do validador
         let reputation = self
@@ -218,7 +340,8 @@ impl ReputationSystem {
             .ok_or_else(|| format!("Validador não encontrado: {}", validator_id))?;
 
         // Aplica o banimento
-        reputation.ban(duration);
+        reputation.ban_at(duration, self.clock.instant());
+        self.notify_ban(validator_id, duration);
         Ok(())
     }
 
@@ -231,14 +354,20 @@ impl ReputationSystem {
     pub fn with_config(config: ReputationConfig) -> Self {
         Self {
             reputations: HashMap::new(),
+            liveness: LivenessTracker::new(config.liveness.clone()),
             config,
+            events: None,
+            last_summaries: HashMap::new(),
+            performance: PerformanceLedger::default(),
+            clock: clock().source(),
         }
     }
 
     /// Adiciona um novo validador ao sistema
     pub fn add_validator(&mut self, validator_id: String) {
         if !self.reputations.contains_key(&validator_id) {
-            let reputation = ValidatorReputation::new(validator_id.clone());
+            let reputation =
+                ValidatorReputation::new_at(validator_id.clone(), self.clock.instant());
             self.reputations.insert(validator_id.clone(), reputation);
             debug!(
                 "Adicionado novo validador ao sistema de reputação: {}",
@@ -258,12 +387,18 @@ impl ReputationSystem {
             .get_mut(validator_id)
             .ok_or_else(|| format!("Validador não encontrado: {}", validator_id))?;
 
-        // Atualiza o timestamp de última atividade
-        reputation.update_last_seen();
+        // Atualiza o timestamp de última atividade; ser dado como offline não
+        // conta como atividade
+        let now = self.clock.instant();
+        if action != ReputationAction::Offline {
+            reputation.update_last_seen_at(now);
+        }
+
+        let score_before = reputation.score;
 
         // Se estiver banido, verifica se o ban expirou
         if reputation.is_banned {
-            reputation.check_ban_status();
+            reputation.check_ban_status_at(now);
             if reputation.is_banned {
                 return Err(format!("Validador está banido: {}", validator_id));
             }
@@ -305,14 +440,11 @@ impl ReputationSystem {
         reputation.score = (reputation.score + adjustment).max(0.0).min(100.0);
 
         // Verifica se o validador deve ser banido
+        let mut banned_for = None;
         if adjustment < 0.0 && reputation.score < self.config.ban_threshold {
-            // Calcula um multiplicador baseado nas infrações
-            let multiplier = (1.0 + reputation.double_votes as f32)
-                * (1.0 + reputation.invalid_proposals as f32);
-
-            let mult_factor = multiplier as u32;
-            let ban_duration = self.config.initial_ban_duration * mult_factor;
-            reputation.ban(ban_duration);
+            // Reincidências recentes sobem a escada de durações
+            let ban_duration = reputation.ban_escalated_at(&self.config, now);
+            banned_for = Some(ban_duration);
 
             info!(
                 "Validador {} banido por {:?} devido a pontuação baixa ({:.2})",
@@ -333,7 +465,13 @@ impl ReputationSystem {
             );
         }
 
-        Ok(reputation.score)
+        let score = reputation.score;
+        self.performance
+            .observe(validator_id, action, score_before, score, banned_for);
+        if let Some(duration) = banned_for {
+            self.notify_ban(validator_id, duration);
+        }
+        Ok(score)
     }
 
     pub fn set_reputation(&mut self, validator_id: &str, score: f32) -> Result<(), String> {
@@ -380,26 +518,101 @@ impl ReputationSystem {
             .unwrap_or(false)
     }
 
-    /// Atualiza o status de validadores offline com base em um limiar de tempo
-    pub fn update_offline_status(&mut self, offline_threshold: Duration) {
-        // Primeiro, coletamos os IDs dos validadores offline
-        let ids_to_update: Vec<String> = self
-            .reputations
-            .keys()
-            .filter(|id| {
-                if let Some(rep) = self.reputations.get(*id) {
-                    rep.is_offline(offline_threshold) && !rep.is_banned
-                } else {
-                    false
+    /// Atualiza o status de validadores pela última atividade.
+    ///
+    /// Quem passou de `offline_threshold` sem ser visto é dado como offline uma
+    /// única vez, e quem estava offline e voltou a ser visto, sem faltas acima do
+    /// limite na janela, volta. Retorna as transições aplicadas, em ordem de ID.
+    pub fn update_offline_status(
+        &mut self,
+        offline_threshold: Duration,
+    ) -> Vec<(String, ReputationAction)> {
+        let mut transitions = Vec::new();
+        let now = self.clock.instant();
+        for (id, rep) in &self.reputations {
+            if rep.is_banned {
+                continue;
+            }
+            if rep.is_offline_at(offline_threshold, now) {
+                if self.liveness.mark_offline(id) {
+                    transitions.push((id.clone(), ReputationAction::Offline));
                 }
-            })
-            .cloned()
-            .collect();
+            } else if self.liveness.mark_seen(id, rep.last_seen.to_instant()) {
+                transitions.push((id.clone(), ReputationAction::BackOnline));
+            }
+        }
+        transitions.sort_by(|a, b| a.0.cmp(&b.0));
 
-        // Depois, processamos cada ID separadamente
-        for id in ids_to_update {
-            let _ = self.update_reputation(&id, ReputationAction::Offline);
+        for (id, action) in &transitions {
+            let _ = self.update_reputation(id, *action);
         }
+        transitions
+    }
+
+    /// Registra se o validador cumpriu um dever de proposta ou voto e aplica a
+    /// transição de disponibilidade que isso provocar; a penalidade de
+    /// `Offline` passa pelo mesmo limiar de banimento das demais infrações
+    pub fn record_duty(
+        &mut self,
+        validator_id: &str,
+        duty: LivenessDuty,
+        fulfilled: bool,
+    ) -> Result<Option<ReputationAction>, String> {
+        let reputation = self
+            .reputations
+            .get_mut(validator_id)
+            .ok_or_else(|| format!("Validador não encontrado: {}", validator_id))?;
+        if fulfilled {
+            reputation.update_last_seen_at(self.clock.instant());
+        } else {
+            let score = reputation.score;
+            self.performance.record_missed(validator_id, duty, score);
+        }
+
+        let transition = self.liveness.record(validator_id, duty, fulfilled);
+        if let Some(action) = transition {
+            info!("Validador {} agora {:?}", validator_id, action);
+            if let Err(e) = self.update_reputation(validator_id, action) {
+                debug!("Transição de disponibilidade sem efeito na reputação: {}", e);
+            }
+        }
+        Ok(transition)
+    }
+
+    /// Faltas recentes e status de disponibilidade dos validadores
+    pub fn liveness(&self) -> &LivenessTracker {
+        &self.liveness
+    }
+
+    /// Desempenho dos validadores por época
+    pub fn performance(&self) -> &PerformanceLedger {
+        &self.performance
+    }
+
+    /// Passa a atribuir o desempenho dos validadores à época `epoch`
+    pub fn begin_epoch(&mut self, epoch: u64) {
+        self.performance.begin_epoch(epoch);
+    }
+
+    /// Registra uma recompensa paga ao validador na época corrente
+    pub fn record_reward(&mut self, validator_id: &str, amount: u64) -> Result<(), String> {
+        let score = self
+            .reputations
+            .get(validator_id)
+            .map(|reputation| reputation.score)
+            .ok_or_else(|| format!("Validador não encontrado: {}", validator_id))?;
+        self.performance.record_reward(validator_id, amount, score);
+        Ok(())
+    }
+
+    /// Relatório de desempenho por época (ver `PerformanceLedger::report`)
+    pub fn performance_report(
+        &self,
+        validator_id: Option<&str>,
+        from_epoch: u64,
+        to_epoch: u64,
+    ) -> PerformanceReport {
+        self.performance.report(validator_id, from_epoch, to_epoch)
     }
 
     /// Retorna a pontuação média de reputação de todos os validadores
@@ -412,4 +625,175 @@ impl ReputationSystem {
 
         sum / self.reputations.len() as f32
     }
+
+    /// Resumo das observações locais, assinado com a chave do nó `observer`
+    pub fn export_summary(&self, observer: String, identity: &NodeIdentity) -> ReputationSummary {
+        let mut observations: Vec<ReputationObservation> = self
+            .reputations
+            .values()
+            .map(|rep| ReputationObservation {
+                validator_id: rep.validator_id.clone(),
+                score: rep.score,
+                invalid_proposals: rep.invalid_proposals,
+                incorrect_votes: rep.incorrect_votes,
+                timeouts: rep.timeouts,
+                double_votes: rep.double_votes,
+            })
+            .collect();
+        observations.sort_by(|a, b| a.validator_id.cmp(&b.validator_id));
+
+        let issued_at = self.clock.unix_millis().max(0) as u64;
+        ReputationSummary::signed(observer, issued_at, observations, identity)
+    }
+
+    /// Peso dado às observações de `observer`, entre 0 e 1
+    pub fn peer_trust(&self, observer: &str) -> f32 {
+        self.config
+            .peer_trust
+            .get(observer)
+            .copied()
+            .unwrap_or(self.config.default_peer_trust)
+            .clamp(0.0, 1.0)
+    }
+
+    /// Incorpora o resumo de outro nó, conferido com `public_key`.
+    ///
+    /// Cada validador conhecido passa a `local * (1 - w) + remota * w`, com `w` de
+    /// `peer_trust`. Validadores que este nó não acompanha e a nota do observador
+    /// sobre si mesmo são ignorados, e nenhum banimento decorre de um resumo:
+    /// banir exige infração vista localmente. Resumos não mais novos que o último
+    /// aceito do mesmo observador são recusados. Retorna quantas pontuações
+    /// foram ajustadas.
+    pub fn import_summary(
+        &mut self,
+        summary: &ReputationSummary,
+        public_key: &[u8],
+    ) -> Result<usize, ConsensusError> {
+        summary.verify(public_key)?;
+        if self
+            .last_summaries
+            .get(&summary.observer)
+            .is_some_and(|last| summary.issued_at <= *last)
+        {
+            return Err(ConsensusError::ValidationFailed(format!(
+                "Resumo de reputação antigo de {}",
+                summary.observer
+            )));
+        }
+        if let Some(observation) = summary
+            .observations
+            .iter()
+            .find(|observation| !(0.0..=100.0).contains(&observation.score))
+        {
+            return Err(ConsensusError::ValidationFailed(format!(
+                "Pontuação fora do intervalo para {} no resumo de {}",
+                observation.validator_id, summary.observer
+            )));
+        }
+        self.last_summaries
+            .insert(summary.observer.clone(), summary.issued_at);
+
+        let weight = self.peer_trust(&summary.observer);
+        if weight == 0.0 {
+            return Ok(0);
+        }
+
+        let mut merged = 0;
+        for observation in &summary.observations {
+            if observation.validator_id == summary.observer {
+                continue;
+            }
+            if let Some(reputation) = self.reputations.get_mut(&observation.validator_id) {
+                reputation.score = (reputation.score * (1.0 - weight) + observation.score * weight)
+                    .clamp(0.0, 100.0);
+                merged += 1;
+            }
+        }
+        debug!(
+            "Resumo de reputação de {} incorporado com peso {:.2}: {} validadores",
+            summary.observer, weight, merged
+        );
+        Ok(merged)
+    }
+}
+
+/// Observação de um nó sobre um validador, como entra no resumo trocado entre
+/// pares
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReputationObservation {
+    pub validator_id: String,
+    pub score: f32,
+    pub invalid_proposals: u64,
+    pub incorrect_votes: u64,
+    pub timeouts: u64,
+    pub double_votes: u64,
+}
+
+/// Resumo assinado das observações de reputação de um nó.
+///
+/// Cada nó só vê as falhas que chegam até ele; trocando resumos, um validador
+/// que falha com parte da rede perde pontuação também no resto dela, na medida
+/// da confiança dada a quem observou (ver `ReputationSystem::import_summary`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReputationSummary {
+    /// ID de validador do nó que observou
+    pub observer: String,
+
+    /// Momento da emissão, em milissegundos
+    pub issued_at: u64,
+
+    /// Observações em ordem de validador
+    pub observations: Vec<ReputationObservation>,
+
+    pub signature: Vec<u8>,
+}
+
+impl ReputationSummary {
+    pub fn signed(
+        observer: String,
+        issued_at: u64,
+        observations: Vec<ReputationObservation>,
+        identity: &NodeIdentity,
+    ) -> Self {
+        let mut summary = Self {
+            observer,
+            issued_at,
+            observations,
+            signature: Vec::new(),
+        };
+        summary.signature = identity.sign_message(&summary.signing_bytes());
+        summary
+    }
+
+    /// Conteúdo coberto pela assinatura do resumo
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut message = format!("{}:{}:{}", SUMMARY_DOMAIN, self.observer, self.issued_at);
+        for observation in &self.observations {
+            message.push_str(&format!(
+                ":{}:{}:{}:{}:{}:{}",
+                observation.validator_id,
+                observation.score.to_bits(),
+                observation.invalid_proposals,
+                observation.incorrect_votes,
+                observation.timeouts,
+                observation.double_votes
+            ));
+        }
+        message.into_bytes()
+    }
+
+    /// Confere a assinatura com a chave pública do observador
+    pub fn verify(&self, public_key: &[u8]) -> Result<(), ConsensusError> {
+        let invalid = || {
+            ConsensusError::ValidationFailed(format!(
+                "Assinatura inválida no resumo de reputação de {}",
+                self.observer
+            ))
+        };
+        let public_key = dilithium5::PublicKey::from_bytes(public_key).map_err(|_| invalid())?;
+        let signature =
+            dilithium5::DetachedSignature::from_bytes(&self.signature).map_err(|_| invalid())?;
+        dilithium5::verify_detached_signature(&signature, &self.signing_bytes(), &public_key)
+            .map_err(|_| invalid())
+    }
 }
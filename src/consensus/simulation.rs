@@ -0,0 +1,748 @@
+use crate::consensus::block_proposal::{
+    BlockProposal, ProposalVerifier, ProposalVote, VotingCoordinator,
+};
+use crate::consensus::reputation::ReputationSystem;
+use crate::consensus::types::VerificationResult;
+use crate::consensus::validator::{Validator, ValidatorSet, ValidatorSetConfig};
+use log::{debug, info, warn};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use sha3::{Digest, Sha3_256};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashSet};
+use std::sync::{Arc, RwLock};
+
+/// Hash usado como pai do primeiro bloco simulado
+pub const SIM_GENESIS_HASH: &str = "genesis";
+
+/// Configuração de uma simulação determinística de consenso
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    /// Número de validadores em processo
+    pub num_validators: usize,
+
+    /// Semente do gerador aleatório (mesma semente => mesma execução)
+    pub seed: u64,
+
+    /// Latência mínima de entrega de mensagens (ms virtuais)
+    pub base_latency_ms: u64,
+
+    /// Variação máxima adicionada à latência (ms virtuais)
+    pub latency_jitter_ms: u64,
+
+    /// Probabilidade de descartar uma mensagem (0.0 - 1.0)
+    pub drop_rate: f64,
+
+    /// Intervalo entre a finalização de um bloco e o início da próxima altura
+    pub block_interval_ms: u64,
+
+    /// Tempo de espera de uma rodada antes de trocar de proposer
+    pub round_timeout_ms: u64,
+
+    /// Stake atribuído a cada validador
+    pub stake_per_validator: u64,
+
+    /// Limiar de aprovação repassado ao VotingCoordinator
+    pub approval_threshold: f32,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            num_validators: 4,
+            seed: 42,
+            base_latency_ms: 20,
+            latency_jitter_ms: 30,
+            drop_rate: 0.0,
+            block_interval_ms: 1_000,
+            round_timeout_ms: 3_000,
+            stake_per_validator: 5_000,
+            approval_threshold: 67.0,
+        }
+    }
+}
+
+/// Mensagens trocadas entre os nós simulados
+#[derive(Debug, Clone)]
+pub enum SimMessage {
+    /// Proposta de bloco para uma altura/rodada
+    Proposal(BlockProposal),
+
+    /// Voto em uma proposta
+    Vote(ProposalVote),
+
+    /// Anúncio de bloco finalizado (sincronização simplificada de nós atrasados)
+    Commit {
+        height: u64,
+        block_hash: String,
+        parent_hash: String,
+    },
+
+    /// Pedido de blocos finalizados a partir de uma altura (nó atrasado)
+    SyncRequest { from_height: u64 },
+}
+
+/// Eventos agendados na fila de tempo virtual
+#[derive(Debug, Clone)]
+enum SimEvent {
+    Deliver {
+        from: usize,
+        to: usize,
+        message: SimMessage,
+    },
+    Timer {
+        node: usize,
+        height: u64,
+        round: u32,
+    },
+}
+
+/// Relógio virtual controlado pela simulação
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VirtualClock {
+    now_ms: u64,
+}
+
+impl VirtualClock {
+    /// Tempo virtual atual em milissegundos
+    pub fn now_ms(&self) -> u64 {
+        self.now_ms
+    }
+
+    fn advance_to(&mut self, time_ms: u64) {
+        if time_ms > self.now_ms {
+            self.now_ms = time_ms;
+        }
+    }
+}
+
+/// Rede simulada com latência, partições e perda de mensagens controláveis
+pub struct MockNetwork {
+    rng: StdRng,
+    base_latency_ms: u64,
+    latency_jitter_ms: u64,
+    drop_rate: f64,
+    partitions: Option<Vec<HashSet<usize>>>,
+    messages_sent: u64,
+    messages_dropped: u64,
+}
+
+impl MockNetwork {
+    fn new(config: &SimulationConfig) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(config.seed),
+            base_latency_ms: config.base_latency_ms,
+            latency_jitter_ms: config.latency_jitter_ms,
+            drop_rate: config.drop_rate.clamp(0.0, 1.0),
+            partitions: None,
+            messages_sent: 0,
+            messages_dropped: 0,
+        }
+    }
+
+    /// Verifica se dois nós conseguem se comunicar na topologia atual
+    pub fn can_reach(&self, from: usize, to: usize) -> bool {
+        if from == to {
+            return true;
+        }
+        match &self.partitions {
+            Some(groups) => groups
+                .iter()
+                .any(|group| group.contains(&from) && group.contains(&to)),
+            None => true,
+        }
+    }
+
+    /// Decide se a mensagem será entregue e, em caso afirmativo, com qual latência
+    fn route(&mut self, from: usize, to: usize) -> Option<u64> {
+        self.messages_sent += 1;
+
+        if from == to {
+            return Some(0);
+        }
+
+        if !self.can_reach(from, to) || (self.drop_rate > 0.0 && self.rng.gen_bool(self.drop_rate))
+        {
+            self.messages_dropped += 1;
+            return None;
+        }
+
+        let jitter = if self.latency_jitter_ms > 0 {
+            self.rng.gen_range(0..=self.latency_jitter_ms)
+        } else {
+            0
+        };
+        Some(self.base_latency_ms + jitter)
+    }
+}
+
+/// Validador executando em processo dentro da simulação
+pub struct SimulatedNode {
+    /// Índice do nó na simulação
+    pub index: usize,
+
+    /// ID do validador
+    pub id: String,
+
+    /// Blocos finalizados por este nó (posição = altura - 1)
+    pub finalized: Vec<String>,
+
+    validators: Arc<RwLock<ValidatorSet>>,
+    reputation: Arc<RwLock<ReputationSystem>>,
+    coordinator: VotingCoordinator,
+    round: u32,
+    crashed: bool,
+    known_proposals: HashSet<String>,
+    voted_rounds: HashSet<(u64, u32)>,
+    pending_commits: BTreeMap<u64, (String, String)>,
+}
+
+impl SimulatedNode {
+    fn new(index: usize, validators: Vec<Validator>, approval_threshold: f32) -> Self {
+        let count = validators.len();
+        let ids: Vec<String> = validators.iter().map(|v| v.id.clone()).collect();
+
+        let validator_set = Arc::new(RwLock::new(ValidatorSet::with_config(
+            validators,
+            ValidatorSetConfig {
+                min_validators: count,
+                min_stake: 0,
+                proposer_cooldown: 0,
+            },
+        )));
+
+        let mut reputation_system = ReputationSystem::new();
+        for id in ids {
+            reputation_system.add_validator(id);
+        }
+        let reputation = Arc::new(RwLock::new(reputation_system));
+
+        let coordinator = VotingCoordinator::new(
+            Arc::clone(&validator_set),
+            Arc::clone(&reputation),
+            approval_threshold,
+        );
+
+        Self {
+            index,
+            id: sim_validator_id(index),
+            finalized: Vec::new(),
+            validators: validator_set,
+            reputation,
+            coordinator,
+            round: 0,
+            crashed: false,
+            known_proposals: HashSet::new(),
+            voted_rounds: HashSet::new(),
+            pending_commits: BTreeMap::new(),
+        }
+    }
+
+    /// Altura do último bloco finalizado
+    pub fn height(&self) -> u64 {
+        self.finalized.len() as u64
+    }
+
+    /// Hash do último bloco finalizado
+    pub fn last_hash(&self) -> &str {
+        self.finalized
+            .last()
+            .map(|h| h.as_str())
+            .unwrap_or(SIM_GENESIS_HASH)
+    }
+
+    /// Indica se o nó está parado (crash)
+    pub fn is_crashed(&self) -> bool {
+        self.crashed
+    }
+}
+
+/// Resumo de uma execução da simulação
+#[derive(Debug, Clone)]
+pub struct SimulationReport {
+    /// Tempo virtual final
+    pub virtual_time_ms: u64,
+
+    /// Altura finalizada por cada nó
+    pub heights: Vec<u64>,
+
+    /// Total de mensagens enviadas
+    pub messages_sent: u64,
+
+    /// Total de mensagens descartadas pela rede
+    pub messages_dropped: u64,
+
+    /// Violações de segurança encontradas (alturas com blocos divergentes)
+    pub safety_violations: Vec<String>,
+}
+
+/// Harness de simulação determinística para o consenso
+pub struct Simulation {
+    config: SimulationConfig,
+    clock: VirtualClock,
+    network: MockNetwork,
+    nodes: Vec<SimulatedNode>,
+    queue: BinaryHeap<Reverse<(u64, u64)>>,
+    events: BTreeMap<u64, SimEvent>,
+    next_seq: u64,
+}
+
+impl Simulation {
+    /// Cria uma simulação com N validadores e agenda a primeira rodada
+    pub fn new(config: SimulationConfig) -> Self {
+        let validators: Vec<Validator> = (0..config.num_validators)
+            .map(|i| {
+                Validator::new(
+                    sim_validator_id(i),
+                    format!("sim://{}", i),
+                    (i as u64 + 1).to_be_bytes().to_vec(),
+                    config.stake_per_validator,
+                )
+            })
+            .collect();
+
+        let nodes = (0..config.num_validators)
+            .map(|i| SimulatedNode::new(i, validators.clone(), config.approval_threshold))
+            .collect();
+
+        let mut simulation = Self {
+            network: MockNetwork::new(&config),
+            config,
+            clock: VirtualClock::default(),
+            nodes,
+            queue: BinaryHeap::new(),
+            events: BTreeMap::new(),
+            next_seq: 0,
+        };
+
+        for node in 0..simulation.nodes.len() {
+            simulation.schedule(
+                0,
+                SimEvent::Timer {
+                    node,
+                    height: 1,
+                    round: 0,
+                },
+            );
+        }
+
+        simulation
+    }
+
+    /// Tempo virtual atual
+    pub fn now_ms(&self) -> u64 {
+        self.clock.now_ms()
+    }
+
+    /// Nós da simulação
+    pub fn nodes(&self) -> &[SimulatedNode] {
+        &self.nodes
+    }
+
+    /// Rede simulada
+    pub fn network(&self) -> &MockNetwork {
+        &self.network
+    }
+
+    /// Divide a rede em grupos isolados; nós fora de qualquer grupo ficam isolados
+    pub fn partition(&mut self, groups: Vec<Vec<usize>>) {
+        info!("Simulação: particionando rede em {:?}", groups);
+        self.network.partitions = Some(
+            groups
+                .into_iter()
+                .map(|group| group.into_iter().collect())
+                .collect(),
+        );
+    }
+
+    /// Remove todas as partições da rede
+    pub fn heal(&mut self) {
+        info!("Simulação: partições removidas");
+        self.network.partitions = None;
+    }
+
+    /// Altera a taxa de perda de mensagens durante a execução
+    pub fn set_drop_rate(&mut self, drop_rate: f64) {
+        self.network.drop_rate = drop_rate.clamp(0.0, 1.0);
+    }
+
+    /// Para um nó: ele deixa de processar mensagens e timers
+    pub fn crash(&mut self, node: usize) {
+        if let Some(n) = self.nodes.get_mut(node) {
+            warn!("Simulação: nó {} parado", n.id);
+            n.crashed = true;
+        }
+    }
+
+    /// Religa um nó parado e reinicia seu timer de rodada
+    pub fn recover(&mut self, node: usize) {
+        let height = match self.nodes.get_mut(node) {
+            Some(n) if n.crashed => {
+                info!("Simulação: nó {} religado", n.id);
+                n.crashed = false;
+                n.round = 0;
+                n.height() + 1
+            }
+            _ => return,
+        };
+        let now = self.now_ms();
+        self.schedule(
+            now,
+            SimEvent::Timer {
+                node,
+                height,
+                round: 0,
+            },
+        );
+    }
+
+    /// Processa o próximo evento; retorna false quando a fila está vazia
+    pub fn step(&mut self) -> bool {
+        let Some(Reverse((time, seq))) = self.queue.pop() else {
+            return false;
+        };
+        let Some(event) = self.events.remove(&seq) else {
+            return true;
+        };
+
+        self.clock.advance_to(time);
+
+        match event {
+            SimEvent::Deliver { from, to, message } => self.handle_message(from, to, message),
+            SimEvent::Timer {
+                node,
+                height,
+                round,
+            } => self.handle_timer(node, height, round),
+        }
+
+        true
+    }
+
+    /// Executa eventos até o tempo virtual informado (inclusive)
+    pub fn run_until(&mut self, time_ms: u64) {
+        while let Some(Reverse((next, _))) = self.queue.peek() {
+            if *next > time_ms {
+                break;
+            }
+            self.step();
+        }
+        self.clock.advance_to(time_ms);
+    }
+
+    /// Avança o tempo virtual por `duration_ms`
+    pub fn run_for(&mut self, duration_ms: u64) {
+        let target = self.now_ms() + duration_ms;
+        self.run_until(target);
+    }
+
+    /// Executa até que todos os nós ativos atinjam a altura ou o tempo limite seja atingido
+    pub fn run_until_height(&mut self, height: u64, deadline_ms: u64) -> bool {
+        loop {
+            if self.min_active_height() >= height {
+                return true;
+            }
+            match self.queue.peek() {
+                Some(Reverse((next, _))) if *next <= deadline_ms => {
+                    self.step();
+                }
+                _ => return self.min_active_height() >= height,
+            }
+        }
+    }
+
+    /// Menor altura finalizada entre os nós que não estão parados
+    pub fn min_active_height(&self) -> u64 {
+        self.nodes
+            .iter()
+            .filter(|n| !n.crashed)
+            .map(|n| n.height())
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Verifica a propriedade de segurança: nenhum par de nós finalizou blocos diferentes na mesma altura
+    pub fn check_safety(&self) -> Result<(), Vec<String>> {
+        let max_height = self.nodes.iter().map(|n| n.finalized.len()).max().unwrap_or(0);
+        let mut violations = Vec::new();
+
+        for position in 0..max_height {
+            let hashes: HashSet<&String> = self
+                .nodes
+                .iter()
+                .filter_map(|n| n.finalized.get(position))
+                .collect();
+            if hashes.len() > 1 {
+                violations.push(format!(
+                    "Altura {}: {} blocos finalizados divergentes",
+                    position + 1,
+                    hashes.len()
+                ));
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Gera um resumo da execução
+    pub fn report(&self) -> SimulationReport {
+        SimulationReport {
+            virtual_time_ms: self.now_ms(),
+            heights: self.nodes.iter().map(|n| n.height()).collect(),
+            messages_sent: self.network.messages_sent,
+            messages_dropped: self.network.messages_dropped,
+            safety_violations: self.check_safety().err().unwrap_or_default(),
+        }
+    }
+
+    fn schedule(&mut self, time_ms: u64, event: SimEvent) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.events.insert(seq, event);
+        self.queue.push(Reverse((time_ms, seq)));
+    }
+
+    fn send(&mut self, from: usize, to: usize, message: SimMessage) {
+        if let Some(latency) = self.network.route(from, to) {
+            let at = self.now_ms() + latency;
+            self.schedule(at, SimEvent::Deliver { from, to, message });
+        } else {
+            debug!("Simulação: mensagem {} -> {} descartada", from, to);
+        }
+    }
+
+    fn broadcast(&mut self, from: usize, message: SimMessage) {
+        for to in 0..self.nodes.len() {
+            self.send(from, to, message.clone());
+        }
+    }
+
+    fn proposer_for(&self, height: u64, round: u32) -> usize {
+        ((height + round as u64) % self.nodes.len() as u64) as usize
+    }
+
+    fn handle_timer(&mut self, node: usize, height: u64, round: u32) {
+        {
+            let n = &mut self.nodes[node];
+            if n.crashed || n.height() + 1 != height || round < n.round {
+                return;
+            }
+            n.round = round;
+        }
+
+        if self.proposer_for(height, round) == node {
+            let parent_hash = self.nodes[node].last_hash().to_string();
+            let proposer_id = self.nodes[node].id.clone();
+            let block_hash = sim_block_hash(height, round, &parent_hash, &proposer_id);
+
+            debug!(
+                "Simulação: {} propõe {} na altura {} (rodada {})",
+                proposer_id, block_hash, height, round
+            );
+
+            let proposal = BlockProposal::new(
+                block_hash,
+                height,
+                parent_hash,
+                proposer_id.clone(),
+                Vec::new(),
+                proposer_id.into_bytes(),
+                round.to_le_bytes().to_vec(),
+            );
+            self.broadcast(node, SimMessage::Proposal(proposal));
+        }
+
+        let at = self.now_ms() + self.config.round_timeout_ms;
+        self.schedule(
+            at,
+            SimEvent::Timer {
+                node,
+                height,
+                round: round + 1,
+            },
+        );
+    }
+
+    fn handle_message(&mut self, from: usize, to: usize, message: SimMessage) {
+        if self.nodes[to].crashed {
+            return;
+        }
+
+        match message {
+            SimMessage::Proposal(proposal) => self.handle_proposal(to, proposal),
+            SimMessage::Vote(vote) => self.handle_vote(to, vote),
+            SimMessage::Commit {
+                height,
+                block_hash,
+                parent_hash,
+            } => {
+                self.nodes[to]
+                    .pending_commits
+                    .insert(height, (block_hash, parent_hash));
+                self.apply_pending_commits(to);
+
+                // Ainda faltam alturas intermediárias: pede ao remetente
+                let next = self.nodes[to].height() + 1;
+                if height > next {
+                    self.send(to, from, SimMessage::SyncRequest { from_height: next });
+                }
+            }
+            SimMessage::SyncRequest { from_height } => {
+                let n = &self.nodes[to];
+                let commits: Vec<SimMessage> = (from_height.max(1)..=n.height())
+                    .map(|height| SimMessage::Commit {
+                        height,
+                        block_hash: n.finalized[(height - 1) as usize].clone(),
+                        parent_hash: if height == 1 {
+                            SIM_GENESIS_HASH.to_string()
+                        } else {
+                            n.finalized[(height - 2) as usize].clone()
+                        },
+                    })
+                    .collect();
+                for commit in commits {
+                    self.send(to, from, commit);
+                }
+            }
+        }
+    }
+
+    fn handle_proposal(&mut self, node: usize, proposal: BlockProposal) {
+        let round = proposal
+            .consensus_data
+            .get(..4)
+            .and_then(|b| b.try_into().ok())
+            .map(u32::from_le_bytes)
+            .unwrap_or(0);
+
+        let expected_proposer = sim_validator_id(self.proposer_for(proposal.block_height, round));
+        let n = &mut self.nodes[node];
+        if proposal.block_height != n.height() + 1
+            || proposal.parent_hash != n.last_hash()
+            || proposal.proposer_id != expected_proposer
+        {
+            return;
+        }
+
+        let verifier = ProposalVerifier::new(Arc::clone(&n.validators), Arc::clone(&n.reputation));
+        match verifier.verify(&proposal) {
+            Ok(VerificationResult::Valid) => {}
+            other => {
+                debug!("Simulação: {} rejeitou proposta: {:?}", n.id, other);
+                return;
+            }
+        }
+
+        n.known_proposals.insert(proposal.block_hash.clone());
+
+        // Votos podem ter chegado antes da proposta
+        if self.try_finalize(node, &proposal.block_hash) {
+            return;
+        }
+
+        let n = &mut self.nodes[node];
+        if !n.voted_rounds.insert((proposal.block_height, round)) {
+            return;
+        }
+
+        let vote = ProposalVote::new(
+            proposal.block_hash,
+            proposal.block_height,
+            n.id.clone(),
+            true,
+            n.id.clone().into_bytes(),
+        );
+        self.broadcast(node, SimMessage::Vote(vote));
+    }
+
+    fn handle_vote(&mut self, node: usize, vote: ProposalVote) {
+        let n = &mut self.nodes[node];
+        if vote.block_height != n.height() + 1 {
+            return;
+        }
+
+        let block_hash = vote.block_hash.clone();
+        if n.coordinator.process_vote(vote).is_err() {
+            return;
+        }
+
+        self.try_finalize(node, &block_hash);
+    }
+
+    /// Finaliza o bloco se a proposta é conhecida e os votos atingiram finalidade
+    fn try_finalize(&mut self, node: usize, block_hash: &str) -> bool {
+        let n = &self.nodes[node];
+        if !n.known_proposals.contains(block_hash) || !n.coordinator.has_reached_finality(block_hash)
+        {
+            return false;
+        }
+
+        let parent_hash = n.last_hash().to_string();
+        self.finalize(node, block_hash.to_string());
+        let height = self.nodes[node].height();
+        self.broadcast(
+            node,
+            SimMessage::Commit {
+                height,
+                block_hash: block_hash.to_string(),
+                parent_hash,
+            },
+        );
+        true
+    }
+
+    fn apply_pending_commits(&mut self, node: usize) {
+        loop {
+            let n = &mut self.nodes[node];
+            let next = n.height() + 1;
+            // Descarta anúncios de alturas já finalizadas
+            n.pending_commits = n.pending_commits.split_off(&next);
+            let Some((block_hash, parent_hash)) = n.pending_commits.get(&next).cloned() else {
+                return;
+            };
+            if parent_hash != n.last_hash() {
+                n.pending_commits.remove(&next);
+                return;
+            }
+            debug!(
+                "Simulação: {} sincronizou o bloco {} na altura {}",
+                n.id, block_hash, next
+            );
+            self.finalize(node, block_hash);
+        }
+    }
+
+    fn finalize(&mut self, node: usize, block_hash: String) {
+        let height = {
+            let n = &mut self.nodes[node];
+            n.coordinator.clear_votes(&block_hash);
+            n.finalized.push(block_hash);
+            n.round = 0;
+            n.height()
+        };
+
+        let at = self.now_ms() + self.config.block_interval_ms;
+        self.schedule(
+            at,
+            SimEvent::Timer {
+                node,
+                height: height + 1,
+                round: 0,
+            },
+        );
+    }
+}
+
+fn sim_validator_id(index: usize) -> String {
+    format!("sim-validator-{}", index)
+}
+
+fn sim_block_hash(height: u64, round: u32, parent_hash: &str, proposer_id: &str) -> String {
+    let mut hasher = Sha3_256::new();
+    hasher.update(format!("{}:{}:{}:{}", height, round, parent_hash, proposer_id).as_bytes());
+    hex::encode(hasher.finalize())
+}
@@ -1,4 +1,5 @@
 use crate::consensus::types::NetworkMetrics;
+use crate::utils::clock::clock;
 use log::{info, warn};
 
 /// Níveis de ameaça para o sistema de consenso
@@ -94,6 +95,7 @@ pub fn detect_threats(
     validator_behaviors: &[(String, f32)],
 ) -> Vec<ThreatInfo> {
     let mut threats = Vec::new();
+    let detected_at = clock().now_instant();
 
     // Detecta possível DoS
     if metrics.message_loss_rate > 0.3 || metrics.average_latency_ms > 2000.0 {
@@ -110,7 +112,7 @@ pub fn detect_threats(
                 metrics.average_latency_ms
             ),
             involved_validators: Vec::new(),
-            detected_at: std::time::Instant::now(),
+            detected_at,
         });
     }
 
@@ -134,7 +136,7 @@ pub fn detect_threats(
                 suspicious_validators.len()
             ),
             involved_validators: suspicious_validators,
-            detected_at: std::time::Instant::now(),
+            detected_at,
         });
     }
 
@@ -150,7 +152,7 @@ pub fn detect_threats(
                 metrics.active_validators, metrics.connected_peers
             ),
             involved_validators: Vec::new(),
-            detected_at: std::time::Instant::now(),
+            detected_at,
         });
     }
 
@@ -169,7 +171,7 @@ pub fn detect_threats(
                 metrics.orphan_rate * 100.0
             ),
             involved_validators: Vec::new(),
-            detected_at: std::time::Instant::now(),
+            detected_at,
         });
     }
 
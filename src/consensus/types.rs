@@ -1,3 +1,6 @@
+use crate::error::{ErrorCategory, ErrorCode};
+use crate::utils::clock::{clock, SharedClock};
+use crate::utils::i18n;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::time::{Duration, Instant};
@@ -69,31 +72,47 @@ pub struct ConsensusState {
 
     /// Status do consenso para o bloco atual
     pub status: ConsensusStatus,
+
+    /// Relógio que mede o intervalo entre adaptações
+    clock: SharedClock,
 }
 
 impl ConsensusState {
     /// Cria um novo estado de consenso com o tipo especificado
     pub fn new(consensus_type: ConsensusType) -> Self {
+        Self::with_clock(consensus_type, clock().source())
+    }
+
+    /// Como `new`, medindo o tempo pelo relógio dado
+    pub fn with_clock(consensus_type: ConsensusType, clock: SharedClock) -> Self {
         Self {
             consensus_type,
-            last_adaptation: Instant::now(),
+            last_adaptation: clock.instant(),
             current_block_height: 0,
             current_proposer: None,
             current_epoch: 0,
             status: ConsensusStatus::Idle,
+            clock,
         }
     }
 
+    /// Tempo decorrido desde a última adaptação
+    pub fn since_adaptation(&self) -> Duration {
+        self.clock
+            .instant()
+            .saturating_duration_since(self.last_adaptation)
+    }
+
     /// Verifica se é hora de considerar uma adaptação no tipo de consenso
     pub fn should_adapt(&self, adaptation_interval: Duration) -> bool {
-        self.last_adaptation.elapsed() >= adaptation_interval
+        self.since_adaptation() >= adaptation_interval
     }
 
     /// Atualiza o tipo de consenso e marca o tempo da adaptação
     pub fn adapt_to(&mut self, new_type: ConsensusType) {
         if self.consensus_type != new_type {
             self.consensus_type = new_type;
-            self.last_adaptation = Instant::now();
+            self.last_adaptation = self.clock.instant();
         }
     }
 }
@@ -169,6 +188,13 @@ pub struct NetworkMetrics {
 
 impl Default for NetworkMetrics {
     fn default() -> Self {
+        Self::new_at(clock().now_instant())
+    }
+}
+
+impl NetworkMetrics {
+    /// Métricas zeradas, com a última atualização em `now`
+    pub fn new_at(now: Instant) -> Self {
         Self {
             message_loss_rate: 0.0,
             average_latency_ms: 0.0,
@@ -177,12 +203,10 @@ impl Default for NetworkMetrics {
             suspicious_validators: 0,
             orphan_rate: 0.0,
             fork_count: 0,
-            last_update: Instant::now(),
+            last_update: now,
         }
     }
-}
 
-impl NetworkMetrics {
     /// Avalia a condição atual da rede com base nas métricas
     pub fn network_condition(&self) -> NetworkCondition {
         // Alta perda de mensagens ou muitos validadores suspeitos indica problemas
@@ -222,28 +246,72 @@ pub enum ConsensusDecision {
 /// Erros que podem ocorrer durante o processo de consenso
 #[derive(Debug, thiserror::Error)]
 pub enum ConsensusError {
-    #[error("Proposer inválido: {0}")]
     InvalidProposer(String),
 
-    #[error("Bloco proposto inválido: {0}")]
     InvalidBlock(String),
 
-    #[error("Validação falhou: {0}")]
     ValidationFailed(String),
 
-    #[error("Timeout de consenso")]
     ConsensusTimeout,
 
-    #[error("Votos insuficientes: requerido {required}, recebido {received}")]
     InsufficientVotes { required: usize, received: usize },
 
-    #[error("Configuração inválida: {0}")]
     ConfigurationError(String),
 
-    #[error("Erro interno: {0}")]
     InternalError(String),
 }
 
+impl fmt::Display for ConsensusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let detail: Option<&dyn fmt::Display> = match self {
+            ConsensusError::InsufficientVotes { required, received } => {
+                return i18n::write_error(
+                    f,
+                    self.code(),
+                    Some(&format_args!("{}/{}", received, required)),
+                )
+            }
+            ConsensusError::InvalidProposer(detail)
+            | ConsensusError::InvalidBlock(detail)
+            | ConsensusError::ValidationFailed(detail)
+            | ConsensusError::ConfigurationError(detail)
+            | ConsensusError::InternalError(detail) => Some(detail),
+            ConsensusError::ConsensusTimeout => None,
+        };
+        i18n::write_error(f, self.code(), detail)
+    }
+}
+
+impl ErrorCode for ConsensusError {
+    fn code(&self) -> u32 {
+        match self {
+            ConsensusError::InternalError(_) => 3000,
+            ConsensusError::InvalidProposer(_) => 3001,
+            ConsensusError::InvalidBlock(_) => 3002,
+            ConsensusError::ValidationFailed(_) => 3003,
+            ConsensusError::ConsensusTimeout => 3004,
+            ConsensusError::InsufficientVotes { .. } => 3005,
+            ConsensusError::ConfigurationError(_) => 3006,
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            ConsensusError::InvalidProposer(_) => ErrorCategory::Unauthorized,
+            ConsensusError::InvalidBlock(_) | ConsensusError::ValidationFailed(_) => {
+                ErrorCategory::Validation
+            }
+            // A rodada pode fechar na próxima tentativa, com mais votos
+            ConsensusError::ConsensusTimeout | ConsensusError::InsufficientVotes { .. } => {
+                ErrorCategory::Unavailable
+            }
+            ConsensusError::ConfigurationError(_) | ConsensusError::InternalError(_) => {
+                ErrorCategory::Internal
+            }
+        }
+    }
+}
+
 /// Resultado da verificação de uma proposta
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum VerificationResult {
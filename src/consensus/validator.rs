@@ -1,3 +1,4 @@
+use crate::consensus::beacon::{derive_seed, tie_break_key, BeaconValue};
 use crate::consensus::reputation::ReputationSystem;
 use crate::consensus::ConsensusError;
 use log::{debug, info, warn};
@@ -72,6 +73,9 @@ pub struct ValidatorSet {
 
     /// Configuração do conjunto de validadores
     config: ValidatorSetConfig,
+
+    /// Último valor do beacon de aleatoriedade, usado na seleção de proponentes
+    beacon: Option<BeaconValue>,
 }
 
 /// Configuração para o conjunto de validadores
@@ -104,6 +108,7 @@ impl ValidatorSet {
             validators: HashMap::new(),
             validators_by_stake: Vec::new(),
             config: ValidatorSetConfig::default(),
+            beacon: None,
         };
 
         // Adiciona os validadores iniciais
@@ -120,6 +125,7 @@ impl ValidatorSet {
             validators: HashMap::new(),
             validators_by_stake: Vec::new(),
             config,
+            beacon: None,
         };
 
         // Adiciona os validadores iniciais
@@ -295,7 +301,7 @@ impl ValidatorSet {
         }
 
         // Filtra validadores elegíveis
-        let eligible: Vec<&Validator> = self
+        let mut eligible: Vec<&Validator> = self
             .validators
             .values()
             .filter(|v| {
@@ -313,8 +319,9 @@ impl ValidatorSet {
         let total_stake: u64 = eligible.iter().map(|v| v.stake).sum();
 
         // Escolhe um validador com probabilidade proporcional ao stake
-        // Usa o bloco como seed para determinismo
-        let mut rng = StdRng::seed_from_u64(block_height);
+        // A semente vem do beacon, ou do bloco, para determinismo
+        let seed = self.selection_seed(&mut eligible, block_height);
+        let mut rng = StdRng::seed_from_u64(seed);
         let threshold = rng.gen_range(0..total_stake);
 
         let mut cumulative = 0;
@@ -357,6 +364,24 @@ impl ValidatorSet {
         Ok(active[index].clone())
     }
 
+    /// Passa a usar `value`, saída do beacon de aleatoriedade, na seleção de proponentes
+    pub fn set_beacon(&mut self, value: BeaconValue) {
+        self.beacon = Some(value);
+    }
+
+    /// Ordena os candidatos e devolve a semente da seleção em `block_height`. Com o
+    /// beacon, ordem e semente vêm do seu valor e não podem ser previstas antes dele;
+    /// sem beacon, a semente é a própria altura.
+    fn selection_seed(&self, candidates: &mut [&Validator], block_height: u64) -> u64 {
+        match &self.beacon {
+            Some(value) => {
+                candidates.sort_by_cached_key(|v| tie_break_key(value, &v.id, block_height));
+                derive_seed(value, "proposer", block_height)
+            }
+            None => block_height,
+        }
+    }
+
     /// Seleciona um proposer usando ponderação combinada (stake e reputação)
     pub fn select_proposer_weighted(
         &self,
@@ -373,7 +398,7 @@ impl ValidatorSet {
         }
 
         // Filtra validadores elegíveis
-        let eligible: Vec<&Validator> = self
+        let mut eligible: Vec<&Validator> = self
             .validators
             .values()
             .filter(|v| {
@@ -406,7 +431,8 @@ impl ValidatorSet {
         }
 
         // Escolhe um validador com probabilidade proporcional à pontuação combinada
-        let mut rng = StdRng::seed_from_u64(block_height);
+        let seed = self.selection_seed(&mut eligible, block_height);
+        let mut rng = StdRng::seed_from_u64(seed);
         let threshold = rng.gen_range(0.0..total_score);
 
         let mut cumulative = 0.0;
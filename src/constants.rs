@@ -1,8 +1,13 @@
 // Constantes de segurança centralizadas
 pub const MAX_TRANSACTION_SIZE: usize = 128 * 1024; // 128KB
-pub const MAX_SIGNATURE_SIZE: usize = 4627; // Tamanho da assinatura Dilithium5
+pub const MAX_SIGNATURE_SIZE: usize = 4627; // Maior assinatura entre os esquemas aceitos (Dilithium5)
 pub const MAX_TIME_DRIFT: i64 = 300; // 5 minutos
 pub const MAX_BLOCK_SIZE: usize = 1024 * 1024; // 1MB
+pub const MAX_BLOCK_WEIGHT: u64 = 4_000_000; // Unidades de `WeightSchedule`
+pub const MAX_PENDING_PER_SENDER: usize = 64;
+pub const MAX_SUBMISSIONS_PER_WINDOW: usize = 120;
+pub const SUBMISSION_WINDOW_SECS: u64 = 60;
+pub const MAX_SNAPSHOT_SIZE: usize = 512 * 1024 * 1024; // 512MB descomprimidos
 pub const TIMESTAMP_WINDOW: i64 = 300; // 5 minutos para janela de timestamp
 pub const MIN_ADDRESS_LENGTH: usize = 32;
 pub const MAX_ADDRESS_LENGTH: usize = 64;
@@ -30,3 +35,9 @@ pub const LIQUIDITY_PERCENTAGE: u64 = 10;
 // Taxa de transferência (percentual do valor, dividido por este número)
 pub const TRANSFER_FEE_DIVISOR: u64 = 1000; // 0.1%
 pub const TRANSFER_FEE_MINIMUM: u64 = 1; // Mínimo de 1 KYBL
+
+// Blocos por cima de um bloco para as consultas o marcarem como `safe`
+pub const DEFAULT_CONFIRMATION_DEPTH: u64 = 6;
+
+// Cadeia servida por um nó cuja configuração não informa `chain_id`
+pub const DEFAULT_CHAIN_ID: &str = "kybelith-mainnet";
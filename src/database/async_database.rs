@@ -0,0 +1,86 @@
+use super::Database;
+use crate::error::Error;
+use rusqlite::Connection;
+use std::sync::{Arc, Mutex};
+use tokio::task;
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Acesso assíncrono ao banco SQLite.
+///
+/// Cada operação roda em `spawn_blocking` com a conexão protegida por um mutex,
+/// de modo que consultas lentas não bloqueiem as threads do runtime tokio.
+#[derive(Clone)]
+pub struct AsyncDatabase {
+    inner: Arc<Mutex<Database>>,
+}
+
+impl AsyncDatabase {
+    pub async fn open(db_path: &str) -> Result<Self> {
+        let db_path = db_path.to_string();
+        let database = task::spawn_blocking(move || Database::new(&db_path))
+            .await
+            .map_err(|e| {
+                Error::Other(format!(
+                    "Tarefa de abertura do banco de dados falhou: {}",
+                    e
+                ))
+            })??;
+        Ok(Self::from(database))
+    }
+
+    /// Executa `f` com acesso exclusivo à conexão, fora das threads do runtime
+    pub async fn call<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        task::spawn_blocking(move || {
+            let mut database = inner.lock().map_err(|_| Error::LockError)?;
+            f(database.get_connection_mut()?)
+        })
+        .await
+        .map_err(|e| Error::Other(format!("Tarefa do banco de dados falhou: {}", e)))?
+    }
+
+    pub async fn insert_transaction(
+        &self,
+        from: String,
+        to: String,
+        amount: u64,
+        timestamp: i64,
+        signature: Vec<u8>,
+        public_key: Vec<u8>,
+    ) -> Result<()> {
+        let inner = Arc::clone(&self.inner);
+        task::spawn_blocking(move || {
+            let database = inner.lock().map_err(|_| Error::LockError)?;
+            database.insert_transaction(&from, &to, amount, timestamp, &signature, &public_key)
+        })
+        .await
+        .map_err(|e| Error::Other(format!("Tarefa de inserção de transação falhou: {}", e)))?
+    }
+
+    pub async fn get_transactions_by_address(
+        &self,
+        address: &str,
+    ) -> Result<Vec<(String, String, u64, i64)>> {
+        let inner = Arc::clone(&self.inner);
+        let address = address.to_string();
+        task::spawn_blocking(move || {
+            let database = inner.lock().map_err(|_| Error::LockError)?;
+            database.get_transactions_by_address(&address)
+        })
+        .await
+        .map_err(|e| Error::Other(format!("Tarefa de consulta de transações falhou: {}", e)))?
+    }
+}
+
+impl From<Database> for AsyncDatabase {
+    fn from(database: Database) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(database)),
+        }
+    }
+}
@@ -1,8 +1,29 @@
-use anyhow::{Context, Result};
+mod async_database;
+
+pub use async_database::AsyncDatabase;
+
+use crate::error::Error;
 use log::info;
-use rusqlite::Connection;
+use rusqlite::{Connection, OpenFlags};
 use std::path::Path;
 
+type Result<T> = std::result::Result<T, Error>;
+
+/// Executa `PRAGMA integrity_check` no banco em `db_path`, aberto só para
+/// leitura; devolve as linhas do relatório, `["ok"]` quando íntegro
+pub fn integrity_check(db_path: &Path) -> Result<Vec<String>> {
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| Error::database("Falha ao abrir banco de dados para verificação", e))?;
+    let mut stmt = conn
+        .prepare("PRAGMA integrity_check")
+        .map_err(|e| Error::database("Falha ao preparar verificação de integridade", e))?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| Error::database("Falha na verificação de integridade", e))?;
+    rows.collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| Error::database("Falha na verificação de integridade", e))
+}
+
 pub struct Database {
     conn: Connection,
 }
@@ -12,14 +33,13 @@ impl Database {
         let path = Path::new(db_path);
         let create_new = !path.exists();
 
-        let conn =
-            Connection::open(db_path).context("Falha ao abrir conexão com banco de dados")?;
+        let conn = Connection::open(db_path)
+            .map_err(|e| Error::database("Falha ao abrir conexão com banco de dados", e))?;
 
         let db = Database { conn };
 
         if create_new {
-            db.initialize_tables()
-                .context("Falha ao inicializar tabelas")?;
+            db.initialize_tables()?;
         }
 
         Ok(db)
@@ -40,7 +60,7 @@ impl Database {
             )",
                 [],
             )
-            .context("Falha ao criar tabela transactions")?;
+            .map_err(|e| Error::database("Falha ao criar tabela transactions", e))?;
 
         info!("Criando tabela tokens...");
         self.conn
@@ -54,7 +74,7 @@ impl Database {
             )",
                 [],
             )
-            .context("Falha ao criar tabela tokens")?;
+            .map_err(|e| Error::database("Falha ao criar tabela tokens", e))?;
 
         info!("Criando tabela fee_distributions...");
         self.conn
@@ -69,7 +89,7 @@ impl Database {
         )",
                 [],
             )
-            .context("Falha ao criar tabela fee_distributions")?;
+            .map_err(|e| Error::database("Falha ao criar tabela fee_distributions", e))?;
 
         info!("Criando tabela transfers...");
         self.conn
@@ -85,7 +105,7 @@ impl Database {
             )",
                 [],
             )
-            .context("Falha ao criar tabela transfers")?;
+            .map_err(|e| Error::database("Falha ao criar tabela transfers", e))?;
 
         info!("Criando índices...");
         self.conn
@@ -93,14 +113,16 @@ impl Database {
                 "CREATE INDEX IF NOT EXISTS idx_transactions_from ON transactions (from_address)",
                 [],
             )
-            .context("Falha ao criar índice em transactions.from_address")?;
+            .map_err(|e| {
+                Error::database("Falha ao criar índice em transactions.from_address", e)
+            })?;
 
         self.conn
             .execute(
                 "CREATE INDEX IF NOT EXISTS idx_transactions_to ON transactions (to_address)",
                 [],
             )
-            .context("Falha ao criar índice em transactions.to_address")?;
+            .map_err(|e| Error::database("Falha ao criar índice em transactions.to_address", e))?;
 
         Ok(())
     }
@@ -125,7 +147,7 @@ impl Database {
         self.conn.execute(
             "INSERT INTO transactions (from_address, to_address, amount, timestamp, signature, public_key) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
             rusqlite::params![from, to, amount, timestamp, signature, public_key],
-        ).context("Falha ao inserir transação")?;
+        ).map_err(|e| Error::database("Falha ao inserir transação", e))?;
         Ok(())
     }
 
@@ -141,9 +163,8 @@ impl Database {
             Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
         })?;
 
-        // Mapeia rusqlite::Error para anyhow::Error
         let transactions: Vec<_> = rows
-            .map(|result| result.map_err(|e| anyhow::anyhow!("Erro ao acessar linha: {}", e)))
+            .map(|result| result.map_err(|e| Error::database("Erro ao acessar linha", e)))
             .collect::<Result<Vec<_>>>()?;
 
         Ok(transactions)
@@ -1,16 +1,70 @@
+use crate::utils::i18n;
 use bincode::ErrorKind;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use serde_json::Error as SerdeError;
+use std::fmt;
 use std::time::SystemTimeError;
 
-#[derive(Debug)]
+/// Categoria de um erro, para que clientes RPC e a CLI decidam como reagir
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// Dados enviados pelo cliente não passam na validação
+    Validation,
+    /// Conteúdo malformado ou que não (des)serializa
+    Format,
+    /// Recurso inexistente
+    NotFound,
+    /// Conflito com o estado atual (duplicata, nonce reutilizado, bloco antigo)
+    Conflict,
+    /// Credenciais ausentes ou insuficientes
+    Unauthorized,
+    /// Recurso temporariamente indisponível; repetir a chamada pode funcionar
+    Unavailable,
+    /// Falha das primitivas criptográficas do nó
+    Crypto,
+    /// Falha interna do nó
+    Internal,
+}
+
+/// Classificação estável de um erro.
+///
+/// Os códigos são parte da interface pública: uma vez publicados não mudam de
+/// significado nem são reaproveitados. Faixas: 1xxx `Error`, 2xxx
+/// `TransactionError`, 3xxx `ConsensusError`.
+pub trait ErrorCode: std::error::Error {
+    fn code(&self) -> u32;
+
+    fn category(&self) -> ErrorCategory;
+
+    /// Se a mesma operação pode ter sucesso ao ser repetida sem alterações
+    fn is_retryable(&self) -> bool {
+        self.category() == ErrorCategory::Unavailable
+    }
+
+    /// Se o erro foi causado pela requisição, e não pelo nó
+    fn is_client_error(&self) -> bool {
+        matches!(
+            self.category(),
+            ErrorCategory::Validation
+                | ErrorCategory::Format
+                | ErrorCategory::NotFound
+                | ErrorCategory::Conflict
+                | ErrorCategory::Unauthorized
+        )
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
 pub enum Error {
     TokenNotFound,
     InsufficientBalance,
     InvalidNonce,
     DuplicateTransaction,
     InvalidPublicKey,
-    TransactionError(Box<TransactionError>),
-    SerializationError(SerdeError),
+    TransactionError(#[source] Box<TransactionError>),
+    SerializationError(#[from] SerdeError),
     InvalidFormat(String),
     InvalidInput(String),
     TransactionTooLarge,
@@ -23,19 +77,34 @@ pub enum Error {
     InvalidAmount,
     DoubleSpending,
     InvalidAddress,
-    BincodeSerializationError(Box<ErrorKind>),
-    SystemTimeError(SystemTimeError),
+    BincodeSerializationError(#[from] Box<ErrorKind>),
+    SystemTimeError(#[from] SystemTimeError),
     StaleBlock,
     CryptoError(String),
     TimeError(String),
     InvalidBlock(String),
     Other(String),
-    OqsError(oqs::Error),
+    #[cfg(feature = "node")]
+    OqsError(#[from] oqs::Error),
+    UnsupportedCodec(u8),
+    CompressionError(String),
+    Unauthorized(String),
+    /// Chamadas RPC acima do limite por janela; carrega o limite
+    RateLimited(u32),
+    /// O binário do nó é anterior à versão exigida pela cadeia na altura
+    UpgradeRequired(String),
+    #[cfg(feature = "node")]
+    Database {
+        context: String,
+        #[source]
+        source: rusqlite::Error,
+    },
 }
 
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum TransactionError {
-    OqsError(Box<oqs::Error>),
+    #[cfg(feature = "node")]
+    OqsError(#[source] Box<oqs::Error>),
     InvalidTransaction,
     InvalidDataFormat,
     InsufficientFunds,
@@ -60,83 +129,252 @@ pub enum TransactionError {
     LockError,
     NonceReused,
     InvalidSignatures(String),
+    Busy,
+    /// O remetente já tem o máximo de transações pendentes no mempool
+    TooManyPending(usize),
+    /// O remetente excedeu as submissões admitidas por janela
+    SubmissionRateExceeded(usize),
+    /// As transferências do token estão suspensas pelo dono ou administradores
+    TokenPaused(u64),
+    /// A taxa da transferência fica abaixo do piso de admissão do nó
+    FeeBelowFloor(u64),
+    /// A cadeia está parada por emergência; só aprovações de retomada são aceitas
+    ChainHalted,
+    /// As restrições de saída da conta de origem não admitem o destino
+    DestinationBlocked(String),
+    /// A chave da operação foi substituída por uma recuperação da conta
+    KeyRotated(String),
+}
+
+/// As mensagens vêm do catálogo em `utils::i18n`, no idioma do processo; o detalhe
+/// carregado pela variante é anexado após `: `.
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let detail: Option<&dyn fmt::Display> = match self {
+            // O código é o do erro embutido; a mensagem indica o embrulho
+            Error::TransactionError(e) => return i18n::write_error(f, 1006, Some(e)),
+            #[cfg(feature = "node")]
+            Error::Database { context, source } => {
+                return i18n::write_error(
+                    f,
+                    self.code(),
+                    Some(&format_args!("{}: {}", context, source)),
+                )
+            }
+            Error::SerializationError(e) => Some(e),
+            Error::BincodeSerializationError(e) => Some(e),
+            Error::SystemTimeError(e) => Some(e),
+            #[cfg(feature = "node")]
+            Error::OqsError(e) => Some(e),
+            Error::UnsupportedCodec(id) => Some(id),
+            Error::RateLimited(limit) => Some(limit),
+            Error::InvalidFormat(detail)
+            | Error::InvalidInput(detail)
+            | Error::InvalidTimestamp(detail)
+            | Error::CryptoError(detail)
+            | Error::TimeError(detail)
+            | Error::InvalidBlock(detail)
+            | Error::Other(detail)
+            | Error::CompressionError(detail)
+            | Error::Unauthorized(detail)
+            | Error::UpgradeRequired(detail) => Some(detail),
+            _ => None,
+        };
+        i18n::write_error(f, self.code(), detail)
+    }
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let detail: Option<&dyn fmt::Display> = match self {
+            #[cfg(feature = "node")]
+            TransactionError::OqsError(e) => Some(e),
+            TransactionError::InvalidTimestamp(detail)
+            | TransactionError::InvalidSignature(detail)
+            | TransactionError::InvalidPublicKey(detail)
+            | TransactionError::InvalidData(detail)
+            | TransactionError::Other(detail)
+            | TransactionError::InvalidParameter(detail)
+            | TransactionError::InvalidInput(detail)
+            | TransactionError::InvalidFormat(detail)
+            | TransactionError::InvalidSignatures(detail)
+            | TransactionError::DestinationBlocked(detail)
+            | TransactionError::KeyRotated(detail) => Some(detail),
+            TransactionError::TooManyPending(limit)
+            | TransactionError::SubmissionRateExceeded(limit) => Some(limit),
+            TransactionError::TokenPaused(value) | TransactionError::FeeBelowFloor(value) => {
+                Some(value)
+            }
+            _ => None,
+        };
+        i18n::write_error(f, self.code(), detail)
+    }
 }
 
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl ErrorCode for Error {
+    fn code(&self) -> u32 {
         match self {
-            Error::TokenNotFound => write!(f, "Token não encontrado"),
-            Error::InsufficientBalance => write!(f, "Saldo insuficiente"),
-            Error::InvalidNonce => write!(f, "Nonce inválido"),
-            Error::DuplicateTransaction => write!(f, "Transação duplicada"),
-            Error::InvalidPublicKey => write!(f, "Chave pública inválida"),
-            Error::TransactionError(e) => write!(f, "Erro de transação: {}", e),
-            Error::SerializationError(e) => write!(f, "Erro de serialização: {}", e),
-            Error::InvalidFormat(e) => write!(f, "Formato inválido: {}", e),
-            Error::InvalidInput(e) => write!(f, "Entrada inválida: {}", e),
-            Error::TransactionTooLarge => write!(f, "Transação muito grande"),
-            Error::InvalidTimestamp(e) => write!(f, "Timestamp inválido: {}", e),
-            Error::InvalidSignature => write!(f, "Assinatura inválida"),
-            Error::LockError => write!(f, "Erro de bloqueio"),
-            Error::NonceReused => write!(f, "Nonce reutilizado"),
-            Error::BlockTooLarge => write!(f, "Bloco muito grande"),
-            Error::InvalidPreviousHash => write!(f, "Hash anterior inválido"),
-            Error::InvalidAmount => write!(f, "Quantidade inválida"),
-            Error::DoubleSpending => write!(f, "Tentativa de gasto duplo"),
-            Error::InvalidAddress => write!(f, "Endereço inválido"),
-            Error::BincodeSerializationError(e) => write!(f, "Erro de serialização bincode: {}", e),
-            Error::SystemTimeError(e) => write!(f, "Erro de tempo do sistema: {}", e),
-            Error::OqsError(e) => write!(f, "Erro OQS: {}", e),
-            Error::StaleBlock => write!(f, "Bloco antigo"),
-            Error::CryptoError(e) => write!(f, "Erro criptográfico: {}", e),
-            Error::TimeError(e) => write!(f, "Erro de tempo: {}", e),
-            Error::InvalidBlock(s) => write!(f, "Bloco inválido: {}", s),
-            Error::Other(s) => write!(f, "Outro erro: {}", s),
+            Error::Other(_) => 1000,
+            Error::TokenNotFound => 1001,
+            Error::InsufficientBalance => 1002,
+            Error::InvalidNonce => 1003,
+            Error::DuplicateTransaction => 1004,
+            Error::InvalidPublicKey => 1005,
+            // O erro de transação embutido carrega o próprio código
+            Error::TransactionError(e) => e.code(),
+            Error::SerializationError(_) => 1007,
+            Error::InvalidFormat(_) => 1008,
+            Error::InvalidInput(_) => 1009,
+            Error::TransactionTooLarge => 1010,
+            Error::InvalidTimestamp(_) => 1011,
+            Error::InvalidSignature => 1012,
+            Error::LockError => 1013,
+            Error::NonceReused => 1014,
+            Error::BlockTooLarge => 1015,
+            Error::InvalidPreviousHash => 1016,
+            Error::InvalidAmount => 1017,
+            Error::DoubleSpending => 1018,
+            Error::InvalidAddress => 1019,
+            Error::BincodeSerializationError(_) => 1020,
+            Error::SystemTimeError(_) => 1021,
+            Error::StaleBlock => 1022,
+            Error::CryptoError(_) => 1023,
+            Error::TimeError(_) => 1024,
+            Error::InvalidBlock(_) => 1025,
+            #[cfg(feature = "node")]
+            Error::OqsError(_) => 1026,
+            Error::UnsupportedCodec(_) => 1027,
+            Error::CompressionError(_) => 1028,
+            Error::Unauthorized(_) => 1029,
+            Error::RateLimited(_) => 1031,
+            Error::UpgradeRequired(_) => 1032,
+            #[cfg(feature = "node")]
+            Error::Database { .. } => 1030,
         }
     }
-}
 
-impl std::fmt::Display for TransactionError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn category(&self) -> ErrorCategory {
         match self {
-            TransactionError::OqsError(e) => write!(f, "Erro OQS: {}", e),
-            TransactionError::InvalidTransaction => write!(f, "Transação inválida"),
-            TransactionError::InvalidDataFormat => write!(f, "Formato de dados inválido"),
-            TransactionError::InsufficientFunds => write!(f, "Fundos insuficientes"),
-            TransactionError::NonceInvalido => write!(f, "Nonce inválido"),
-            TransactionError::InvalidTimestamp(e) => write!(f, "Timestamp inválido: {}", e),
-            TransactionError::EnderecoInvalido => write!(f, "Endereço inválido"),
-            TransactionError::TokenNaoEncontrado => write!(f, "Token não encontrado"),
-            TransactionError::TransacaoRepetida => write!(f, "Transação repetida"),
-            TransactionError::ValorInvalido => write!(f, "Valor inválido"),
-            TransactionError::InvalidSignature(e) => write!(f, "Assinatura inválida: {}", e),
-            TransactionError::InvalidPublicKey(e) => write!(f, "Chave pública inválida: {}", e),
-            TransactionError::InvalidData(e) => write!(f, "Dados inválidos: {}", e),
-            TransactionError::Other(e) => write!(f, "Outro erro: {}", e),
-            TransactionError::InvalidParameter(e) => write!(f, "Parâmetro inválido: {}", e),
-            TransactionError::SignatureSizeExceeded => write!(f, "Tamanho da assinatura excedido"),
-            TransactionError::DataSizeExceeded => write!(f, "Tamanho dos dados excedido"),
-            TransactionError::NonceOverflow => write!(f, "Overflow de nonce"),
-            TransactionError::AddressFormatInvalid => write!(f, "Formato de endereço inválido"),
-            TransactionError::TimestampInvalid => write!(f, "Timestamp inválido"),
-            TransactionError::InvalidInput(e) => write!(f, "Entrada inválida: {}", e),
-            TransactionError::InvalidFormat(e) => write!(f, "Formato inválido: {}", e),
-            TransactionError::LockError => write!(f, "Erro de bloqueio"),
-            TransactionError::NonceReused => write!(f, "Nonce reutilizado"),
-            TransactionError::InvalidSignatures(e) => write!(f, "Assinaturas inválidas: {}", e),
+            Error::TransactionError(e) => e.category(),
+            Error::TokenNotFound => ErrorCategory::NotFound,
+            Error::InsufficientBalance
+            | Error::InvalidNonce
+            | Error::InvalidPublicKey
+            | Error::InvalidInput(_)
+            | Error::TransactionTooLarge
+            | Error::InvalidTimestamp(_)
+            | Error::InvalidSignature
+            | Error::BlockTooLarge
+            | Error::InvalidPreviousHash
+            | Error::InvalidAmount
+            | Error::InvalidAddress
+            | Error::InvalidBlock(_) => ErrorCategory::Validation,
+            Error::SerializationError(_)
+            | Error::InvalidFormat(_)
+            | Error::BincodeSerializationError(_)
+            | Error::UnsupportedCodec(_)
+            | Error::CompressionError(_) => ErrorCategory::Format,
+            Error::DuplicateTransaction
+            | Error::NonceReused
+            | Error::DoubleSpending
+            | Error::StaleBlock => ErrorCategory::Conflict,
+            Error::Unauthorized(_) => ErrorCategory::Unauthorized,
+            Error::LockError | Error::RateLimited(_) => ErrorCategory::Unavailable,
+            #[cfg(feature = "node")]
+            Error::OqsError(_) => ErrorCategory::Crypto,
+            Error::CryptoError(_) => ErrorCategory::Crypto,
+            #[cfg(feature = "node")]
+            Error::Database { .. } => ErrorCategory::Internal,
+            Error::SystemTimeError(_)
+            | Error::TimeError(_)
+            | Error::Other(_)
+            | Error::UpgradeRequired(_) => ErrorCategory::Internal,
         }
     }
 }
 
-impl std::error::Error for Error {}
-impl std::error::Error for TransactionError {}
+impl ErrorCode for TransactionError {
+    fn code(&self) -> u32 {
+        match self {
+            TransactionError::Other(_) => 2000,
+            #[cfg(feature = "node")]
+            TransactionError::OqsError(_) => 2001,
+            TransactionError::InvalidTransaction => 2002,
+            TransactionError::InvalidDataFormat => 2003,
+            TransactionError::InsufficientFunds => 2004,
+            TransactionError::NonceInvalido => 2005,
+            TransactionError::InvalidTimestamp(_) => 2006,
+            TransactionError::EnderecoInvalido => 2007,
+            TransactionError::TokenNaoEncontrado => 2008,
+            TransactionError::TransacaoRepetida => 2009,
+            TransactionError::ValorInvalido => 2010,
+            TransactionError::InvalidSignature(_) => 2011,
+            TransactionError::InvalidPublicKey(_) => 2012,
+            TransactionError::InvalidData(_) => 2013,
+            TransactionError::InvalidParameter(_) => 2014,
+            TransactionError::SignatureSizeExceeded => 2015,
+            TransactionError::DataSizeExceeded => 2016,
+            TransactionError::NonceOverflow => 2017,
+            TransactionError::AddressFormatInvalid => 2018,
+            TransactionError::TimestampInvalid => 2019,
+            TransactionError::InvalidInput(_) => 2020,
+            TransactionError::InvalidFormat(_) => 2021,
+            TransactionError::LockError => 2022,
+            TransactionError::NonceReused => 2023,
+            TransactionError::InvalidSignatures(_) => 2024,
+            TransactionError::Busy => 2025,
+            TransactionError::TooManyPending(_) => 2026,
+            TransactionError::SubmissionRateExceeded(_) => 2027,
+            TransactionError::TokenPaused(_) => 2028,
+            TransactionError::FeeBelowFloor(_) => 2029,
+            TransactionError::ChainHalted => 2030,
+            TransactionError::DestinationBlocked(_) => 2031,
+            TransactionError::KeyRotated(_) => 2032,
+        }
+    }
 
-impl From<SerdeError> for Error {
-    fn from(err: SerdeError) -> Self {
-        Error::SerializationError(err)
+    fn category(&self) -> ErrorCategory {
+        match self {
+            TransactionError::InvalidTransaction
+            | TransactionError::InsufficientFunds
+            | TransactionError::NonceInvalido
+            | TransactionError::InvalidTimestamp(_)
+            | TransactionError::EnderecoInvalido
+            | TransactionError::ValorInvalido
+            | TransactionError::InvalidSignature(_)
+            | TransactionError::InvalidPublicKey(_)
+            | TransactionError::InvalidData(_)
+            | TransactionError::InvalidParameter(_)
+            | TransactionError::SignatureSizeExceeded
+            | TransactionError::DataSizeExceeded
+            | TransactionError::NonceOverflow
+            | TransactionError::AddressFormatInvalid
+            | TransactionError::TimestampInvalid
+            | TransactionError::InvalidInput(_)
+            | TransactionError::InvalidSignatures(_)
+            | TransactionError::KeyRotated(_)
+            | TransactionError::FeeBelowFloor(_) => ErrorCategory::Validation,
+            TransactionError::InvalidDataFormat | TransactionError::InvalidFormat(_) => {
+                ErrorCategory::Format
+            }
+            TransactionError::TokenNaoEncontrado => ErrorCategory::NotFound,
+            TransactionError::TransacaoRepetida
+            | TransactionError::NonceReused
+            | TransactionError::TokenPaused(_)
+            | TransactionError::DestinationBlocked(_) => ErrorCategory::Conflict,
+            TransactionError::LockError
+            | TransactionError::Busy
+            | TransactionError::TooManyPending(_)
+            | TransactionError::SubmissionRateExceeded(_)
+            | TransactionError::ChainHalted => ErrorCategory::Unavailable,
+            #[cfg(feature = "node")]
+            TransactionError::OqsError(_) => ErrorCategory::Crypto,
+            TransactionError::Other(_) => ErrorCategory::Internal,
+        }
     }
 }
 
+#[cfg(feature = "node")]
 impl From<oqs::Error> for TransactionError {
     fn from(err: oqs::Error) -> Self {
         TransactionError::OqsError(Box::new(err))
@@ -149,26 +387,20 @@ impl From<TransactionError> for Error {
     }
 }
 
-impl From<oqs::Error> for Error {
-    fn from(err: oqs::Error) -> Self {
-        Error::OqsError(err)
-    }
-}
-
-impl From<Box<ErrorKind>> for Error {
-    fn from(err: Box<ErrorKind>) -> Self {
-        Error::BincodeSerializationError(err)
-    }
-}
-
-impl From<anyhow::Error> for Error {
-    fn from(err: anyhow::Error) -> Self {
-        Error::Other(err.to_string())
+#[cfg(feature = "node")]
+impl Error {
+    /// Erro do SQLite acompanhado da operação que falhou
+    pub fn database(context: impl Into<String>, source: rusqlite::Error) -> Self {
+        Error::Database {
+            context: context.into(),
+            source,
+        }
     }
 }
 
-impl From<SystemTimeError> for Error {
-    fn from(err: SystemTimeError) -> Self {
-        Error::SystemTimeError(err)
+#[cfg(feature = "node")]
+impl From<rusqlite::Error> for Error {
+    fn from(err: rusqlite::Error) -> Self {
+        Error::database("Erro de banco de dados", err)
     }
 }
@@ -0,0 +1,174 @@
+use crate::rbac::AdminRole;
+use parking_lot::RwLock;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Capacidade do canal de broadcast; assinantes mais lentos que isso perdem eventos
+/// antigos e recebem `RecvError::Lagged`
+pub const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// Eventos publicados pelo nó para integrações externas
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum AppEvent {
+    /// Bloco anexado à cadeia; emitido depois dos eventos dos itens que ele aplicou
+    BlockCommitted {
+        height: u64,
+        hash: String,
+        transactions: usize,
+        operations: usize,
+    },
+    TokenCreated {
+        token_id: u64,
+        symbol: String,
+        owner: String,
+        total_supply: u64,
+        height: u64,
+    },
+    /// Operação de estado sem evento próprio (chaves de visualização, movimentos
+    /// confidenciais, cujo valor não é publicado, políticas de token, KYC,
+    /// cronogramas de liberação e administração de tokens)
+    OperationApplied {
+        author: String,
+        nonce: u64,
+        height: u64,
+    },
+    TransferApplied {
+        txid: String,
+        token_id: u64,
+        from: String,
+        to: String,
+        amount: u64,
+        height: u64,
+    },
+    ValidatorBanned {
+        validator_id: String,
+        duration_secs: u64,
+    },
+    /// Papel administrativo atribuído ou revogado por `by`
+    RoleAssigned {
+        subject: String,
+        role: AdminRole,
+        granted: bool,
+        by: String,
+        height: u64,
+    },
+    /// A cadeia trocou os blocos a partir de `fork_height` por um ramo mais longo;
+    /// seguido de um `BlockCommitted` para cada bloco do novo ramo.
+    ///
+    /// Um txid presente nas duas listas foi reincluído em outra altura, e suas
+    /// confirmações recomeçam a contar.
+    ChainReorganized {
+        fork_height: u64,
+        old_tip: String,
+        new_tip: String,
+        /// Itens dos blocos revertidos: transações, operações e transferências
+        /// (ver `Block::item_ids`)
+        reverted: Vec<String>,
+        /// Itens dos blocos do novo ramo, na mesma forma
+        applied: Vec<String>,
+    },
+    /// A maioria dos admins parou ou retomou a cadeia; `by` deu a aprovação que
+    /// completou a maioria
+    ChainHaltChanged {
+        halted: bool,
+        reason: String,
+        approvers: Vec<String>,
+        by: String,
+        height: u64,
+    },
+}
+
+/// Identificador devolvido por `EventBus::on`, usado para cancelar o callback
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+type Callback = Arc<dyn Fn(&AppEvent) + Send + Sync>;
+
+struct Inner {
+    sender: broadcast::Sender<AppEvent>,
+    callbacks: RwLock<Vec<(SubscriptionId, Callback)>>,
+    next_id: AtomicU64,
+}
+
+/// Registro de callbacks e canal de broadcast para `AppEvent`.
+///
+/// Clonar é barato e todos os clones publicam para os mesmos assinantes. Callbacks
+/// rodam de forma síncrona na thread que emitiu o evento, com os locks de estado
+/// ainda tomados: devem ser rápidos e não podem chamar de volta a blockchain ou o
+/// consenso. Para trabalho assíncrono, use `subscribe`.
+#[derive(Clone)]
+pub struct EventBus {
+    inner: Arc<Inner>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self {
+            inner: Arc::new(Inner {
+                sender,
+                callbacks: RwLock::new(Vec::new()),
+                next_id: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Receptor que recebe todos os eventos emitidos a partir de agora
+    pub fn subscribe(&self) -> broadcast::Receiver<AppEvent> {
+        self.inner.sender.subscribe()
+    }
+
+    /// Registra um callback chamado para cada evento emitido
+    pub fn on(&self, callback: impl Fn(&AppEvent) + Send + Sync + 'static) -> SubscriptionId {
+        let id = SubscriptionId(self.inner.next_id.fetch_add(1, Ordering::Relaxed));
+        self.inner.callbacks.write().push((id, Arc::new(callback)));
+        id
+    }
+
+    /// Remove um callback; retorna `false` se ele já não estava registrado
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        let mut callbacks = self.inner.callbacks.write();
+        let before = callbacks.len();
+        callbacks.retain(|(registered, _)| *registered != id);
+        callbacks.len() != before
+    }
+
+    pub fn emit(&self, event: AppEvent) {
+        // Cópia da lista para que um callback possa registrar ou remover outros
+        let callbacks: Vec<Callback> = self
+            .inner
+            .callbacks
+            .read()
+            .iter()
+            .map(|(_, callback)| Arc::clone(callback))
+            .collect();
+        for callback in callbacks {
+            callback(&event);
+        }
+
+        // Sem receptores ativos o envio falha, o que não é um erro aqui
+        let _ = self.inner.sender.send(event);
+    }
+
+    /// Quantidade de callbacks e receptores ativos
+    pub fn subscriber_count(&self) -> usize {
+        self.inner.callbacks.read().len() + self.inner.sender.receiver_count()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBus")
+            .field("subscribers", &self.subscriber_count())
+            .finish()
+    }
+}
@@ -0,0 +1,234 @@
+// Interface C para carteiras e serviços que não são escritos em Rust.
+//
+// Todas as funções retornam `QstStatus`; buffers devolvidos pela biblioteca
+// pertencem ao chamador e devem ser liberados com `qst_buffer_free`, que os
+// zera antes de devolver a memória. As assinaturas cobrem a mesma codificação
+// canônica de `Transaction::serialize_for_signing`, incluindo o `token_id`.
+// O cabeçalho correspondente fica em `include/kybelith.h`.
+
+use crate::transaction::TransactionView;
+use crate::utils::address::{derive_address, DERIVED_ADDRESS_LEN};
+use crate::utils::dilithium;
+use pqcrypto_dilithium::dilithium5::{self, PublicKey, SecretKey};
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _, SecretKey as _};
+use std::ffi::{c_char, CStr};
+use std::panic::{catch_unwind, UnwindSafe};
+use std::ptr;
+use zeroize::Zeroize;
+
+/// Códigos de retorno da API C
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QstStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    InvalidKey = 3,
+    InvalidSignature = 4,
+    BufferTooSmall = 5,
+    Panic = 6,
+}
+
+/// Bytes alocados pela biblioteca; liberar com `qst_buffer_free`
+#[repr(C)]
+#[derive(Debug)]
+pub struct QstBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+impl QstBuffer {
+    fn empty() -> Self {
+        QstBuffer {
+            data: ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    fn from_vec(bytes: Vec<u8>) -> Self {
+        let mut bytes = bytes.into_boxed_slice();
+        let buffer = QstBuffer {
+            data: bytes.as_mut_ptr(),
+            len: bytes.len(),
+        };
+        std::mem::forget(bytes);
+        buffer
+    }
+}
+
+/// Campos assinados de uma transação; as strings são UTF-8 terminadas em NUL e
+/// `public_key` aponta para os `public_key_len` bytes da chave do remetente
+#[repr(C)]
+#[derive(Debug)]
+pub struct QstTransaction {
+    pub token_id: u64,
+    pub from: *const c_char,
+    pub to: *const c_char,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub nonce: u64,
+    pub public_key: *const u8,
+    pub public_key_len: usize,
+}
+
+/// Tamanho do buffer exigido por `qst_derive_address`, incluindo o NUL
+pub const QST_ADDRESS_BUFFER_LEN: usize = DERIVED_ADDRESS_LEN + 1;
+
+fn guard(f: impl FnOnce() -> Result<(), QstStatus> + UnwindSafe) -> QstStatus {
+    match catch_unwind(f) {
+        Ok(Ok(())) => QstStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => QstStatus::Panic,
+    }
+}
+
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> Result<&'a [u8], QstStatus> {
+    if data.is_null() {
+        return Err(QstStatus::NullPointer);
+    }
+    Ok(std::slice::from_raw_parts(data, len))
+}
+
+unsafe fn text<'a>(value: *const c_char) -> Result<&'a str, QstStatus> {
+    if value.is_null() {
+        return Err(QstStatus::NullPointer);
+    }
+    CStr::from_ptr(value)
+        .to_str()
+        .map_err(|_| QstStatus::InvalidUtf8)
+}
+
+unsafe fn signing_data(tx: *const QstTransaction) -> Result<Vec<u8>, QstStatus> {
+    let tx = tx.as_ref().ok_or(QstStatus::NullPointer)?;
+    TransactionView {
+        token_id: tx.token_id,
+        from: text(tx.from)?,
+        to: text(tx.to)?,
+        amount: tx.amount,
+        timestamp: tx.timestamp,
+        nonce: tx.nonce,
+        public_key: bytes(tx.public_key, tx.public_key_len)?,
+        signature: &[],
+        transaction_hash: &[],
+        hash: "",
+    }
+    .serialize_for_signing()
+    .map_err(|_| QstStatus::InvalidKey)
+}
+
+/// Gera um par de chaves Dilithium5.
+///
+/// # Safety
+/// `out_public_key` e `out_secret_key` devem apontar para `QstBuffer` graváveis.
+#[no_mangle]
+pub unsafe extern "C" fn qst_generate_keypair(
+    out_public_key: *mut QstBuffer,
+    out_secret_key: *mut QstBuffer,
+) -> QstStatus {
+    guard(|| {
+        if out_public_key.is_null() || out_secret_key.is_null() {
+            return Err(QstStatus::NullPointer);
+        }
+        let (public_key, secret_key) = dilithium::keypair();
+        *out_public_key = QstBuffer::from_vec(public_key.as_bytes().to_vec());
+        *out_secret_key = QstBuffer::from_vec(secret_key.as_bytes().to_vec());
+        Ok(())
+    })
+}
+
+/// Assina a codificação canônica da transação com a chave secreta Dilithium5.
+///
+/// # Safety
+/// `tx` deve apontar para um `QstTransaction` válido, `secret_key` para
+/// `secret_key_len` bytes legíveis e `out_signature` para um `QstBuffer` gravável.
+#[no_mangle]
+pub unsafe extern "C" fn qst_sign_transaction(
+    tx: *const QstTransaction,
+    secret_key: *const u8,
+    secret_key_len: usize,
+    out_signature: *mut QstBuffer,
+) -> QstStatus {
+    guard(|| {
+        if out_signature.is_null() {
+            return Err(QstStatus::NullPointer);
+        }
+        *out_signature = QstBuffer::empty();
+
+        let data = signing_data(tx)?;
+        let secret_key = SecretKey::from_bytes(bytes(secret_key, secret_key_len)?)
+            .map_err(|_| QstStatus::InvalidKey)?;
+        let signature = dilithium5::detached_sign(&data, &secret_key);
+        *out_signature = QstBuffer::from_vec(signature.as_bytes().to_vec());
+        Ok(())
+    })
+}
+
+/// Verifica a assinatura dos campos da transação.
+///
+/// Retorna `QST_STATUS_OK` apenas quando a assinatura confere.
+///
+/// # Safety
+/// `tx` deve apontar para um `QstTransaction` válido e `public_key`/`signature`
+/// para a quantidade de bytes informada.
+#[no_mangle]
+pub unsafe extern "C" fn qst_verify_transaction(
+    tx: *const QstTransaction,
+    public_key: *const u8,
+    public_key_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+) -> QstStatus {
+    guard(|| {
+        let data = signing_data(tx)?;
+        let public_key = PublicKey::from_bytes(bytes(public_key, public_key_len)?)
+            .map_err(|_| QstStatus::InvalidKey)?;
+        let signature = dilithium5::DetachedSignature::from_bytes(bytes(signature, signature_len)?)
+            .map_err(|_| QstStatus::InvalidSignature)?;
+
+        dilithium5::verify_detached_signature(&signature, &data, &public_key)
+            .map_err(|_| QstStatus::InvalidSignature)
+    })
+}
+
+/// Escreve em `out` o endereço da chave pública, terminado em NUL.
+///
+/// # Safety
+/// `public_key` deve apontar para `public_key_len` bytes legíveis e `out` para
+/// `out_len` bytes graváveis (ao menos `QST_ADDRESS_BUFFER_LEN`).
+#[no_mangle]
+pub unsafe extern "C" fn qst_derive_address(
+    public_key: *const u8,
+    public_key_len: usize,
+    out: *mut c_char,
+    out_len: usize,
+) -> QstStatus {
+    guard(|| {
+        if out.is_null() {
+            return Err(QstStatus::NullPointer);
+        }
+        if out_len < QST_ADDRESS_BUFFER_LEN {
+            return Err(QstStatus::BufferTooSmall);
+        }
+        let public_key = PublicKey::from_bytes(bytes(public_key, public_key_len)?)
+            .map_err(|_| QstStatus::InvalidKey)?;
+
+        let address = derive_address(public_key.as_bytes());
+        ptr::copy_nonoverlapping(address.as_ptr(), out as *mut u8, address.len());
+        *out.add(address.len()) = 0;
+        Ok(())
+    })
+}
+
+/// Zera e libera um buffer devolvido pela biblioteca. Aceita buffers vazios.
+///
+/// # Safety
+/// `buffer` deve ter sido produzido por esta biblioteca e não pode ser liberado
+/// duas vezes.
+#[no_mangle]
+pub unsafe extern "C" fn qst_buffer_free(buffer: QstBuffer) {
+    if !buffer.data.is_null() {
+        // Chaves secretas também passam por aqui: não deixar os bytes no heap
+        let mut bytes = Box::from_raw(ptr::slice_from_raw_parts_mut(buffer.data, buffer.len));
+        bytes.zeroize();
+    }
+}
@@ -0,0 +1,213 @@
+// Pontos de entrada para fuzzing dos parsers expostos à rede (feature `fuzzing`).
+// Cada função `fuzz_*` recebe bytes arbitrários e nunca deve entrar em pânico;
+// as funções `arbitrary_*` geram entradas estruturadas para o corpus inicial.
+
+use crate::blockchain::Block;
+use crate::consensus::BlockProposal;
+use crate::smart_contract::SmartContract;
+use crate::transaction::{NonceRegistry, SecureTransaction, Transaction};
+use arbitrary::{Arbitrary, Unstructured};
+use once_cell::sync::Lazy;
+use pqcrypto_dilithium::dilithium5;
+use std::collections::HashSet;
+
+/// Chave usada na validação de blocos durante o fuzzing (gerada uma única vez)
+static FUZZ_PUBLIC_KEY: Lazy<dilithium5::PublicKey> = Lazy::new(|| dilithium5::keypair().0);
+
+/// Decodifica e valida uma transação a partir de bytes não confiáveis
+pub fn fuzz_transaction_decode(data: &[u8]) {
+    if let Ok(tx) = Transaction::deserialize_from_bytes(data) {
+        let _ = tx.size();
+        let _ = tx.validate_address();
+        let _ = tx.validate_timestamp();
+        let _ = tx.serialize_for_signing();
+
+        let mut registry = NonceRegistry::new();
+        let _ = tx.validate(&mut registry);
+    }
+}
+
+/// Decodifica e valida um bloco a partir de bytes não confiáveis
+pub fn fuzz_block_decode(data: &[u8]) {
+    if let Ok(block) = Block::from_bytes(data) {
+        let _ = block.size();
+        let _ = block.validate_block(&FUZZ_PUBLIC_KEY);
+        let _ = block.compute_hash();
+    }
+}
+
+/// Decodifica uma proposta e verifica que a recodificação é estável
+pub fn fuzz_proposal_decode(data: &[u8]) {
+    if let Ok(proposal) = BlockProposal::from_bytes(data) {
+        let _ = proposal.transaction_count();
+
+        let encoded = proposal
+            .to_bytes()
+            .expect("proposta decodificada deve ser recodificável");
+        let decoded = BlockProposal::from_bytes(&encoded)
+            .expect("proposta recodificada deve ser decodificável");
+        assert_eq!(
+            decoded.to_bytes().ok(),
+            Some(encoded),
+            "codificação da proposta não é estável"
+        );
+    }
+}
+
+/// Transação estruturada para geração de corpus
+#[derive(Debug, Arbitrary)]
+pub struct FuzzTransaction {
+    pub token_id: u64,
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub nonce: u64,
+    pub public_key: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+impl From<FuzzTransaction> for Transaction {
+    fn from(f: FuzzTransaction) -> Self {
+        Transaction {
+            token_id: f.token_id,
+            from: f.from,
+            to: f.to,
+            amount: f.amount,
+            timestamp: f.timestamp,
+            nonce: f.nonce,
+            public_key: f.public_key,
+            signature: f.signature,
+            transaction_hash: Vec::new(),
+            hash: String::new(),
+        }
+    }
+}
+
+/// Transação segura estruturada para geração de corpus
+#[derive(Debug, Arbitrary)]
+pub struct FuzzSecureTransaction {
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub nonce: u64,
+    pub signature: Vec<u8>,
+    pub public_key: Vec<u8>,
+    pub encrypted_data: Vec<u8>,
+}
+
+impl From<FuzzSecureTransaction> for SecureTransaction {
+    fn from(f: FuzzSecureTransaction) -> Self {
+        SecureTransaction {
+            from: f.from,
+            to: f.to,
+            amount: f.amount,
+            timestamp: f.timestamp,
+            nonce: f.nonce,
+            signature: f.signature,
+            public_key: f.public_key,
+            cipher_key: Vec::new(),
+            encrypted_data: f.encrypted_data,
+            iv: Vec::new(),
+            salt: Vec::new(),
+            mac: Vec::new(),
+            payload_grants: Vec::new(),
+        }
+    }
+}
+
+/// Bloco estruturado para geração de corpus
+#[derive(Debug, Arbitrary)]
+pub struct FuzzBlock {
+    pub index: u64,
+    pub timestamp: u64,
+    pub transactions: Vec<FuzzSecureTransaction>,
+    pub contract_code: Option<Vec<u8>>,
+    pub previous_hash: String,
+    pub hash: String,
+    pub validator_signature: Option<Vec<u8>>,
+    pub nonce: u64,
+}
+
+impl From<FuzzBlock> for Block {
+    fn from(f: FuzzBlock) -> Self {
+        let contracts = f
+            .contract_code
+            .map(|code| {
+                vec![SmartContract::new(
+                    code,
+                    Vec::new(),
+                    "fuzz-contract".to_string(),
+                    "fuzz-creator".to_string(),
+                    f.timestamp as i64,
+                    false,
+                )]
+            })
+            .unwrap_or_default();
+
+        Block {
+            index: f.index,
+            timestamp: f.timestamp,
+            transactions: f.transactions.into_iter().map(Into::into).collect(),
+            contracts,
+            previous_hash: f.previous_hash,
+            hash: f.hash,
+            proposer: None,
+            validator_signature: f.validator_signature,
+            nonce: f.nonce,
+            processed_transactions: HashSet::new(),
+            execution: None,
+            operations: Vec::new(),
+            transfers: Vec::new(),
+        }
+    }
+}
+
+/// Proposta estruturada para geração de corpus
+#[derive(Debug, Arbitrary)]
+pub struct FuzzProposal {
+    pub block_hash: String,
+    pub block_height: u64,
+    pub parent_hash: String,
+    pub proposer_id: String,
+    pub transaction_hashes: Vec<String>,
+    pub signature: Vec<u8>,
+    pub consensus_data: Vec<u8>,
+}
+
+impl From<FuzzProposal> for BlockProposal {
+    fn from(f: FuzzProposal) -> Self {
+        BlockProposal::new(
+            f.block_hash,
+            f.block_height,
+            f.parent_hash,
+            f.proposer_id,
+            f.transaction_hashes,
+            f.signature,
+            f.consensus_data,
+        )
+    }
+}
+
+/// Gera a codificação de rede de uma transação arbitrária
+pub fn arbitrary_transaction_bytes(u: &mut Unstructured) -> arbitrary::Result<Vec<u8>> {
+    let tx: Transaction = FuzzTransaction::arbitrary(u)?.into();
+    bincode::serialize(&tx).map_err(|_| arbitrary::Error::IncorrectFormat)
+}
+
+/// Gera a codificação de rede de um bloco arbitrário
+pub fn arbitrary_block_bytes(u: &mut Unstructured) -> arbitrary::Result<Vec<u8>> {
+    let block: Block = FuzzBlock::arbitrary(u)?.into();
+    block
+        .to_bytes()
+        .map_err(|_| arbitrary::Error::IncorrectFormat)
+}
+
+/// Gera a codificação de rede de uma proposta arbitrária
+pub fn arbitrary_proposal_bytes(u: &mut Unstructured) -> arbitrary::Result<Vec<u8>> {
+    let proposal: BlockProposal = FuzzProposal::arbitrary(u)?.into();
+    proposal
+        .to_bytes()
+        .map_err(|_| arbitrary::Error::IncorrectFormat)
+}
@@ -0,0 +1,152 @@
+use super::types::{ChainHeader, InterchainError, Packet, SignedHeader};
+use crate::blockchain::merkle::MerkleProof;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Cliente leve de uma cadeia estrangeira.
+///
+/// Aceita cabeçalhos assinados por ao menos `threshold` validadores confiáveis e
+/// verifica provas de inclusão contra as raízes desses cabeçalhos.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightClient {
+    chain_id: String,
+    validators: BTreeSet<Vec<u8>>,
+    threshold: usize,
+    headers: BTreeMap<u64, ChainHeader>,
+}
+
+impl LightClient {
+    pub fn new(
+        chain_id: impl Into<String>,
+        validators: Vec<Vec<u8>>,
+        threshold: usize,
+    ) -> Result<Self, InterchainError> {
+        let validators: BTreeSet<Vec<u8>> = validators.into_iter().collect();
+        if threshold == 0 || threshold > validators.len() {
+            return Err(InterchainError::InvalidHeader(format!(
+                "Limite de assinaturas {} inválido para {} validadores",
+                threshold,
+                validators.len()
+            )));
+        }
+
+        Ok(Self {
+            chain_id: chain_id.into(),
+            validators,
+            threshold,
+            headers: BTreeMap::new(),
+        })
+    }
+
+    pub fn chain_id(&self) -> &str {
+        &self.chain_id
+    }
+
+    pub fn latest_height(&self) -> Option<u64> {
+        self.headers.keys().next_back().copied()
+    }
+
+    pub fn header(&self, height: u64) -> Option<&ChainHeader> {
+        self.headers.get(&height)
+    }
+
+    /// Valida e armazena um cabeçalho da cadeia estrangeira
+    pub fn update(&mut self, signed: SignedHeader) -> Result<(), InterchainError> {
+        self.check_header(&signed)?;
+        if !self.headers.contains_key(&signed.header.height) {
+            debug!(
+                "Cliente leve de {} aceitou cabeçalho da altura {}",
+                self.chain_id, signed.header.height
+            );
+            self.headers.insert(signed.header.height, signed.header);
+        }
+        Ok(())
+    }
+
+    /// Verificações de `update` sem alterar o cliente; um cabeçalho idêntico ao
+    /// já armazenado na mesma altura é aceito
+    pub fn check_header(&self, signed: &SignedHeader) -> Result<(), InterchainError> {
+        let header = &signed.header;
+        if header.chain_id != self.chain_id {
+            return Err(InterchainError::InvalidHeader(format!(
+                "Cabeçalho de {} enviado ao cliente de {}",
+                header.chain_id, self.chain_id
+            )));
+        }
+
+        if let Some(existing) = self.headers.get(&header.height) {
+            if existing == header {
+                return Ok(());
+            }
+            return Err(InterchainError::InvalidHeader(format!(
+                "Cabeçalho conflitante na altura {}",
+                header.height
+            )));
+        }
+
+        // Quando a altura anterior é conhecida, o encadeamento precisa conferir
+        if let Some(parent) = header
+            .height
+            .checked_sub(1)
+            .and_then(|h| self.headers.get(&h))
+        {
+            if parent.block_hash != header.previous_hash {
+                return Err(InterchainError::InvalidHeader(format!(
+                    "Hash anterior não confere na altura {}",
+                    header.height
+                )));
+            }
+        }
+
+        let signers: HashSet<&[u8]> = signed
+            .valid_signers()
+            .into_iter()
+            .filter(|pk| self.validators.contains(*pk))
+            .collect();
+        if signers.len() < self.threshold {
+            return Err(InterchainError::InsufficientSignatures {
+                required: self.threshold,
+                received: signers.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Verifica que `packet` foi comprometido pela cadeia estrangeira em `height`
+    pub fn verify_packet(
+        &self,
+        packet: &Packet,
+        proof: &MerkleProof,
+        height: u64,
+    ) -> Result<(), InterchainError> {
+        let header = self
+            .headers
+            .get(&height)
+            .ok_or(InterchainError::UntrustedHeight(height))?;
+        self.check_packet(packet, proof, header)
+    }
+
+    /// Verifica que `packet` está na raiz de pacotes de `header`, um cabeçalho
+    /// desta cadeia já conferido por `check_header`
+    pub fn check_packet(
+        &self,
+        packet: &Packet,
+        proof: &MerkleProof,
+        header: &ChainHeader,
+    ) -> Result<(), InterchainError> {
+        if packet.source_chain != self.chain_id {
+            return Err(InterchainError::InvalidPacket(format!(
+                "Pacote de {} verificado pelo cliente de {}",
+                packet.source_chain, self.chain_id
+            )));
+        }
+
+        if proof.verify(&packet.commitment(), &header.packets_root) {
+            Ok(())
+        } else {
+            Err(InterchainError::InvalidProof)
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod light_client;
+pub mod state;
+pub mod types;
+
+pub use light_client::LightClient;
+pub use state::{escrow_address, InterchainState};
+pub use types::{ChainHeader, HeaderSignature, InterchainError, Packet, SignedHeader};
@@ -0,0 +1,63 @@
+use super::light_client::LightClient;
+use super::types::Packet;
+use crate::blockchain::merkle::{merkle_root, MerkleHash, MerkleProof};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Conta que custodia tokens nativos enviados para `destination`
+pub fn escrow_address(destination: &str) -> String {
+    format!("ibc-escrow-{}", destination)
+}
+
+/// Estado entre cadeias da blockchain local, alterado só pelas operações
+/// `RegisterChainClient`, `SendPacket` e `ReceivePacket` aplicadas nos blocos.
+///
+/// Tokens nativos enviados são custodiados em uma conta de escrow; vouchers
+/// recebidos de outra cadeia são emitidos como tokens locais cujo rastro
+/// (`<origem>/<denominação>`) é lembrado para que possam ser queimados na volta.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InterchainState {
+    /// Identificador desta cadeia nos pacotes e cabeçalhos; vazio desliga o envio
+    /// e o recebimento
+    pub chain_id: String,
+    /// Clientes leves das cadeias contraparte, por identificador
+    pub clients: BTreeMap<String, LightClient>,
+    /// Próxima sequência de saída, por cadeia de destino
+    pub next_sequence: BTreeMap<String, u64>,
+    /// Pacotes enviados, pela altura do bloco que os aplicou; a raiz de cada
+    /// altura vai no cabeçalho entregue às contrapartes
+    pub sent: BTreeMap<u64, Vec<Packet>>,
+    /// Pacotes já recebidos, por cadeia de origem e sequência
+    pub received: BTreeSet<(String, u64)>,
+    /// Rastro do voucher -> ID do token local
+    pub vouchers: BTreeMap<String, u64>,
+}
+
+impl InterchainState {
+    /// Rastro do voucher representado pelo token local `token_id`
+    pub fn trace_of(&self, token_id: u64) -> Option<&str> {
+        self.vouchers
+            .iter()
+            .find(|(_, local)| **local == token_id)
+            .map(|(trace, _)| trace.as_str())
+    }
+
+    /// Raiz dos pacotes enviados no bloco da altura `height`
+    pub fn packets_root(&self, height: u64) -> MerkleHash {
+        let leaves: Vec<_> = self
+            .sent
+            .get(&height)
+            .map(|packets| packets.iter().map(Packet::commitment).collect())
+            .unwrap_or_default();
+        merkle_root(&leaves)
+    }
+
+    /// Altura e prova de inclusão de um pacote já enviado
+    pub fn packet_proof(&self, packet: &Packet) -> Option<(u64, MerkleProof)> {
+        self.sent.iter().find_map(|(height, packets)| {
+            let position = packets.iter().position(|p| p == packet)?;
+            let leaves: Vec<_> = packets.iter().map(Packet::commitment).collect();
+            MerkleProof::build(&leaves, position).map(|proof| (*height, proof))
+        })
+    }
+}
@@ -0,0 +1,138 @@
+use crate::blockchain::merkle::{leaf_hash, MerkleHash};
+use crate::error::TransactionError;
+use pqcrypto_dilithium::dilithium5::{self, PublicKey, SecretKey};
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Erros da camada de mensagens entre cadeias
+#[derive(Debug, thiserror::Error)]
+pub enum InterchainError {
+    #[error("Cadeia desconhecida: {0}")]
+    UnknownChain(String),
+
+    #[error("Cabeçalho inválido: {0}")]
+    InvalidHeader(String),
+
+    #[error("Assinaturas insuficientes: requerido {required}, recebido {received}")]
+    InsufficientSignatures { required: usize, received: usize },
+
+    #[error("Cabeçalho não confiável para a altura {0}")]
+    UntrustedHeight(u64),
+
+    #[error("Prova de inclusão inválida")]
+    InvalidProof,
+
+    #[error("Pacote já recebido: {chain}#{sequence}")]
+    PacketReplayed { chain: String, sequence: u64 },
+
+    #[error("Pacote inválido: {0}")]
+    InvalidPacket(String),
+
+    #[error("Token não encontrado: {0}")]
+    TokenNotFound(String),
+
+    #[error("Saldo insuficiente")]
+    InsufficientBalance,
+}
+
+impl From<InterchainError> for TransactionError {
+    fn from(err: InterchainError) -> Self {
+        match err {
+            InterchainError::TokenNotFound(_) => TransactionError::TokenNaoEncontrado,
+            InterchainError::InsufficientBalance => TransactionError::InsufficientFunds,
+            InterchainError::PacketReplayed { .. } => TransactionError::TransacaoRepetida,
+            other => TransactionError::InvalidParameter(other.to_string()),
+        }
+    }
+}
+
+/// Cabeçalho rastreado por clientes leves de outras cadeias
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ChainHeader {
+    pub chain_id: String,
+    pub height: u64,
+    pub block_hash: String,
+    pub previous_hash: String,
+    pub transactions_root: MerkleHash,
+    /// Raiz dos pacotes de saída comprometidos nesta altura
+    pub packets_root: MerkleHash,
+}
+
+impl ChainHeader {
+    fn signing_payload(&self) -> Vec<u8> {
+        bincode::serialize(self).unwrap_or_default()
+    }
+}
+
+/// Assinatura de um validador sobre um cabeçalho
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct HeaderSignature {
+    pub public_key: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+/// Cabeçalho acompanhado das assinaturas dos validadores da cadeia de origem
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SignedHeader {
+    pub header: ChainHeader,
+    pub signatures: Vec<HeaderSignature>,
+}
+
+impl SignedHeader {
+    pub fn new(header: ChainHeader) -> Self {
+        Self {
+            header,
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Adiciona a assinatura Dilithium5 de um validador
+    pub fn sign(&mut self, public_key: &PublicKey, secret_key: &SecretKey) {
+        let signature = dilithium5::detached_sign(&self.header.signing_payload(), secret_key);
+        self.signatures.push(HeaderSignature {
+            public_key: public_key.as_bytes().to_vec(),
+            signature: signature.as_bytes().to_vec(),
+        });
+    }
+
+    /// Chaves públicas cujas assinaturas conferem com o cabeçalho
+    pub fn valid_signers(&self) -> Vec<&[u8]> {
+        let payload = self.header.signing_payload();
+        self.signatures
+            .iter()
+            .filter(|entry| {
+                let public_key = match PublicKey::from_bytes(&entry.public_key) {
+                    Ok(pk) => pk,
+                    Err(_) => return false,
+                };
+                let signature = match dilithium5::DetachedSignature::from_bytes(&entry.signature) {
+                    Ok(sig) => sig,
+                    Err(_) => return false,
+                };
+                dilithium5::verify_detached_signature(&signature, &payload, &public_key).is_ok()
+            })
+            .map(|entry| entry.public_key.as_slice())
+            .collect()
+    }
+}
+
+/// Pacote de transferência de tokens entre cadeias
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Packet {
+    pub sequence: u64,
+    pub source_chain: String,
+    pub destination_chain: String,
+    pub sender: String,
+    pub receiver: String,
+    /// Denominação vista pela cadeia de origem: ID de token nativo ou rastro de voucher
+    pub denom: String,
+    pub amount: u64,
+}
+
+impl Packet {
+    /// Folha Merkle que compromete o pacote no cabeçalho da cadeia de origem
+    pub fn commitment(&self) -> MerkleHash {
+        leaf_hash(&bincode::serialize(self).unwrap_or_default())
+    }
+}
@@ -1,11 +1,13 @@
-use crate::error::TransactionError;
-use anyhow::{Context, Result};
+use crate::audit::{AuditAction, AuditLog};
+use crate::error::{Error, TransactionError};
+use crate::utils::entropy;
 use oqs::kem::PublicKeyRef;
 use oqs::kem::{Algorithm as KemAlgorithm, Kem};
 use oqs::sig::{Algorithm as SigAlgorithm, Sig};
 use rusqlite::params;
 use rusqlite::{Connection, TransactionBehavior};
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Serialize, Deserialize)]
@@ -30,10 +32,14 @@ pub struct KeyManager {
 }
 
 impl KeyManager {
-    pub fn new() -> Result<Self> {
-        let kem = Kem::new(KemAlgorithm::Kyber512).context("Falha ao inicializar Kyber512")?;
-        let sig = Sig::new(SigAlgorithm::Dilithium5) // Atualizado para Dilithium5
-            .context("Falha ao inicializar Dilithium5")?;
+    pub fn new() -> Result<Self, Error> {
+        let kem = entropy::kem(KemAlgorithm::Kyber512)
+            .map_err(|e| Error::CryptoError(format!("Falha ao inicializar Kyber512: {}", e)))?;
+        let sig =
+            entropy::sig(SigAlgorithm::Dilithium5) // Atualizado para Dilithium5
+                .map_err(|e| {
+                    Error::CryptoError(format!("Falha ao inicializar Dilithium5: {}", e))
+                })?;
 
         Ok(Self { kem, sig })
     }
@@ -64,7 +70,24 @@ impl KeyManager {
         std::thread::sleep(std::time::Duration::from_millis(delay));
     }
 
-    pub fn rotate_keys(&mut self, conn: &mut Connection) -> Result<()> {
+    pub fn rotate_keys(&mut self, conn: &mut Connection) -> Result<(), Error> {
+        self.rotate(conn).map(|_| ())
+    }
+
+    /// Rotaciona as chaves e registra a rotação no log de auditoria
+    pub fn rotate_keys_audited(
+        &mut self,
+        conn: &mut Connection,
+        audit_log: &mut AuditLog,
+        actor: &str,
+    ) -> Result<(), Error> {
+        let new_public_key = self.rotate(conn)?;
+        let key_id = hex::encode(&Sha3_256::digest(&new_public_key)[..16]);
+        audit_log.append(actor, AuditAction::KeyRotated { key_id })?;
+        Ok(())
+    }
+
+    fn rotate(&mut self, conn: &mut Connection) -> Result<Vec<u8>, Error> {
         let (new_public_key, _new_secret_key) = self.generate_quantum_keys()?;
 
         conn.execute(
@@ -80,10 +103,10 @@ impl KeyManager {
             ],
         )?;
 
-        Ok(())
+        Ok(new_public_key)
     }
 
-    pub fn backup_keys(&self, backup_path: &str) -> Result<()> {
+    pub fn backup_keys(&self, backup_path: &str) -> Result<(), Error> {
         // Primeiro, obtenha um par de chaves
         let (public_key, _) = self.kem.keypair()?;
         let public_key_ref = PublicKeyRef::from(&public_key);
@@ -103,24 +126,35 @@ impl KeyManager {
 
         // Serialize para string e salve no arquivo
         let json_string = serde_json::to_string(&serializable_backup)?;
-        std::fs::write(backup_path, json_string)?;
+        std::fs::write(backup_path, json_string).map_err(|e| {
+            Error::Other(format!("Falha ao gravar backup em {}: {}", backup_path, e))
+        })?;
 
         Ok(())
     }
 
-    pub fn validate_transaction_params(&self, from: &str, to: &str, amount: u64) -> Result<()> {
+    pub fn validate_transaction_params(
+        &self,
+        from: &str,
+        to: &str,
+        amount: u64,
+    ) -> Result<(), TransactionError> {
         if from.len() < 32 || to.len() < 32 {
-            return Err(anyhow::anyhow!("Endereços inválidos"));
+            return Err(TransactionError::InvalidParameter(
+                "Endereços inválidos".to_string(),
+            ));
         }
 
         if amount == 0 {
-            return Err(anyhow::anyhow!("Quantidade inválida"));
+            return Err(TransactionError::InvalidParameter(
+                "Quantidade inválida".to_string(),
+            ));
         }
 
         Ok(())
     }
 
-    pub fn log_key_operation(&self, conn: &mut Connection, operation: &str) -> Result<()> {
+    pub fn log_key_operation(&self, conn: &mut Connection, operation: &str) -> Result<(), Error> {
         let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
 
         conn.execute(
@@ -131,20 +165,18 @@ impl KeyManager {
         Ok(())
     }
 
-    pub fn generate_quantum_keys(&self) -> Result<(Vec<u8>, Vec<u8>)> {
-        let (public_key, secret_key) = self
-            .kem
-            .keypair()
-            .context("Falha ao gerar par de chaves Kyber")?;
+    pub fn generate_quantum_keys(&self) -> Result<(Vec<u8>, Vec<u8>), Error> {
+        let (public_key, secret_key) = self.kem.keypair().map_err(|e| {
+            Error::CryptoError(format!("Falha ao gerar par de chaves Kyber: {}", e))
+        })?;
 
         Ok((public_key.into_vec(), secret_key.into_vec()))
     }
 
-    pub fn generate_signing_keys(&self) -> Result<(Vec<u8>, Vec<u8>)> {
-        let (public_key, secret_key) = self
-            .sig
-            .keypair()
-            .context("Falha ao gerar par de chaves Dilithium")?;
+    pub fn generate_signing_keys(&self) -> Result<(Vec<u8>, Vec<u8>), Error> {
+        let (public_key, secret_key) = self.sig.keypair().map_err(|e| {
+            Error::CryptoError(format!("Falha ao gerar par de chaves Dilithium: {}", e))
+        })?;
 
         Ok((public_key.into_vec(), secret_key.into_vec()))
     }
@@ -155,7 +187,7 @@ impl KeyManager {
         to: String,
         amount: u64,
         conn: &'a mut Connection,
-    ) -> Result<rusqlite::Transaction<'a>> {
+    ) -> Result<rusqlite::Transaction<'a>, Error> {
         let (pub_key, secret_key_bytes) = self.generate_signing_keys()?;
 
         let transaction = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
@@ -172,7 +204,7 @@ impl KeyManager {
             &self
                 .sig
                 .secret_key_from_bytes(&secret_key_bytes)
-                .ok_or_else(|| anyhow::anyhow!("Falha ao criar chave secreta"))?,
+                .ok_or_else(|| Error::CryptoError("Falha ao criar chave secreta".to_string()))?,
         )?;
 
         transaction.execute(
@@ -1,30 +1,71 @@
+#[cfg(feature = "node")]
 pub mod app;
+#[cfg(feature = "node")]
+pub mod audit;
+#[cfg(feature = "bench")]
+pub mod bench;
+#[cfg(feature = "node")]
 pub mod blockchain;
 pub mod config;
+#[cfg(feature = "node")]
 pub mod consensus;
 pub mod constants;
+#[cfg(feature = "node")]
 pub mod database;
 pub mod error;
+#[cfg(feature = "node")]
+pub mod events;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+#[cfg(feature = "node")]
+pub mod interchain;
+#[cfg(feature = "node")]
 pub mod key_manager;
+#[cfg(feature = "node")]
+pub mod logging;
+#[cfg(feature = "node")]
+pub mod network;
+#[cfg(feature = "node")]
 pub mod quantum_crypto;
+pub mod rbac;
+#[cfg(feature = "node")]
+pub mod reload;
+#[cfg(feature = "node")]
+pub mod rpc;
+#[cfg(feature = "node")]
 pub mod smart_contract;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+#[cfg(feature = "node")]
 pub mod token;
 pub mod transaction;
 pub mod utils;
+pub mod wallet;
 
 // Re-exports principais
-pub use app::QuantumBlockchainApp;
-pub use blockchain::Blockchain;
-pub use database::Database;
+#[cfg(feature = "node")]
+pub use app::{ChainHost, NodeHandle, QuantumBlockchainApp};
+#[cfg(feature = "node")]
+pub use blockchain::{Blockchain, SharedBlockchain};
+#[cfg(feature = "node")]
+pub use database::{AsyncDatabase, Database};
 pub use error::TransactionError;
+#[cfg(feature = "node")]
 pub use key_manager::KeyManager;
+#[cfg(feature = "node")]
 pub use quantum_crypto::quantum_crypto::OqsError;
+#[cfg(feature = "node")]
 pub use quantum_crypto::QuantumCrypto;
+#[cfg(feature = "node")]
 pub use smart_contract::SmartContract;
+#[cfg(feature = "node")]
 pub use token::Token;
 pub use transaction::{
     NonceRegistry, TransactionProcessor, TransactionSigner, TransactionVerifier,
 };
+pub use utils::address::Address;
 
 // Constantes globais
 pub const BLOCKCHAIN_FILE: &str = "blockchain.json";
@@ -0,0 +1,282 @@
+use crate::config::{LogFormat, LogRotation, LoggingConfig};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
+use serde_json::json;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::UNIX_EPOCH;
+
+static LOGGER: OnceCell<NodeLogger> = OnceCell::new();
+
+fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    level
+        .parse()
+        .map_err(|_| format!("Nível de log desconhecido: {}", level))
+}
+
+fn level_from_usize(value: usize) -> LevelFilter {
+    LevelFilter::iter().nth(value).unwrap_or(LevelFilter::Trace)
+}
+
+/// Níveis por prefixo de alvo; o prefixo mais longo que casar decide
+#[derive(Clone, Debug, Default)]
+pub struct ModuleLevels {
+    // Ordenados do prefixo mais longo para o mais curto
+    levels: Vec<(String, LevelFilter)>,
+}
+
+impl ModuleLevels {
+    pub fn parse<'a, I>(modules: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = (&'a String, &'a String)>,
+    {
+        let mut levels = modules
+            .into_iter()
+            .map(|(module, level)| Ok((module.clone(), parse_level(level)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+        levels.sort_by_key(|(module, _)| std::cmp::Reverse(module.len()));
+        Ok(Self { levels })
+    }
+
+    /// Nível configurado para `target`, se algum prefixo casar com ele
+    pub fn level_for(&self, target: &str) -> Option<LevelFilter> {
+        self.levels
+            .iter()
+            .find(|(module, _)| {
+                target == module
+                    || target
+                        .strip_prefix(module.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .map(|(_, level)| *level)
+    }
+
+    fn max(&self) -> LevelFilter {
+        self.levels
+            .iter()
+            .map(|(_, level)| *level)
+            .max()
+            .unwrap_or(LevelFilter::Off)
+    }
+}
+
+/// Arquivo de log que rotaciona por tamanho e por hora/dia. Ao rotacionar,
+/// `arquivo.1` passa a `arquivo.2` e assim por diante, descartando o que
+/// passar de `max_files`.
+pub struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    period_secs: Option<i64>,
+    max_files: usize,
+    file: File,
+    size: u64,
+    period: i64,
+}
+
+impl RotatingFile {
+    pub fn open(path: impl AsRef<Path>, config: &LoggingConfig) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let period_secs = match config.rotation {
+            LogRotation::Never => None,
+            LogRotation::Hourly => Some(3600),
+            LogRotation::Daily => Some(86_400),
+        };
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let metadata = file.metadata()?;
+        // Um arquivo herdado de outra execução pertence ao período da última escrita
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or_else(|| chrono::Utc::now().timestamp());
+        Ok(Self {
+            period: period_secs.map_or(0, |secs| modified / secs),
+            path,
+            max_bytes: config.max_file_bytes,
+            period_secs,
+            max_files: config.max_files,
+            file,
+            size: metadata.len(),
+        })
+    }
+
+    pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+        self.write_line_at(line, chrono::Utc::now().timestamp())
+    }
+
+    /// Grava `line` como se fosse o instante `now` (segundos Unix), rotacionando antes
+    /// se o período mudou ou se a linha estouraria o tamanho máximo
+    pub fn write_line_at(&mut self, line: &str, now: i64) -> io::Result<()> {
+        let len = line.len() as u64 + 1;
+        let period = self.period_secs.map_or(0, |secs| now / secs);
+        let too_big = self.max_bytes > 0 && self.size > 0 && self.size + len > self.max_bytes;
+        if period != self.period || too_big {
+            self.rotate()?;
+            self.period = period;
+        }
+        writeln!(self.file, "{}", line)?;
+        self.size += len;
+        Ok(())
+    }
+
+    fn rotated(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.max_files == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            let _ = fs::remove_file(self.rotated(self.max_files));
+            for index in (1..self.max_files).rev() {
+                let from = self.rotated(index);
+                if from.exists() {
+                    fs::rename(&from, self.rotated(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated(1))?;
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+/// Formata uma linha de log no formato configurado
+pub fn format_line(format: LogFormat, level: Level, target: &str, message: &str) -> String {
+    let now = chrono::Utc::now();
+    match format {
+        LogFormat::Text => format!(
+            "{} [{}] {}: {}",
+            now.format("%Y-%m-%d %H:%M:%S"),
+            level,
+            target,
+            message
+        ),
+        LogFormat::Json => json!({
+            "timestamp": now.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            "level": level.as_str(),
+            "target": target,
+            "message": message,
+        })
+        .to_string(),
+    }
+}
+
+/// Logger do nó: terminal em texto e, opcionalmente, arquivo rotativo em texto
+/// ou JSON, com níveis próprios e sobreposições por módulo.
+pub struct NodeLogger {
+    terminal_level: AtomicUsize,
+    file_level: LevelFilter,
+    modules: ModuleLevels,
+    format: LogFormat,
+    file: Option<Mutex<RotatingFile>>,
+}
+
+impl NodeLogger {
+    pub fn new(log_level: &str, config: &LoggingConfig) -> Result<Self, String> {
+        let file = match &config.file {
+            Some(path) => Some(Mutex::new(RotatingFile::open(path, config).map_err(
+                |e| format!("Falha ao abrir arquivo de log {}: {}", path.display(), e),
+            )?)),
+            None => None,
+        };
+        Ok(Self {
+            terminal_level: AtomicUsize::new(parse_level(log_level)? as usize),
+            file_level: parse_level(&config.file_level)?,
+            modules: ModuleLevels::parse(&config.modules)?,
+            format: config.format,
+            file,
+        })
+    }
+
+    fn terminal_level(&self) -> LevelFilter {
+        level_from_usize(self.terminal_level.load(Ordering::Relaxed))
+    }
+
+    /// Maior nível que alguma saída aceita; usado como `log::max_level`
+    pub fn max_level(&self) -> LevelFilter {
+        let file = if self.file.is_some() {
+            self.file_level
+        } else {
+            LevelFilter::Off
+        };
+        self.terminal_level().max(file).max(self.modules.max())
+    }
+
+    fn levels_for(&self, target: &str) -> (LevelFilter, LevelFilter) {
+        match self.modules.level_for(target) {
+            Some(level) => (level, level),
+            None => (self.terminal_level(), self.file_level),
+        }
+    }
+}
+
+impl Log for NodeLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        let (terminal, file) = self.levels_for(metadata.target());
+        metadata.level() <= terminal || (self.file.is_some() && metadata.level() <= file)
+    }
+
+    fn log(&self, record: &Record) {
+        let (terminal, file_level) = self.levels_for(record.target());
+        let message = record.args().to_string();
+        if record.level() <= terminal {
+            let line = format_line(LogFormat::Text, record.level(), record.target(), &message);
+            if record.level() == Level::Error {
+                eprintln!("{}", line);
+            } else {
+                println!("{}", line);
+            }
+        }
+        if let Some(file) = &self.file {
+            if record.level() <= file_level {
+                let line = format_line(self.format, record.level(), record.target(), &message);
+                // Falhar ao gravar o log não pode derrubar o nó
+                let _ = file.lock().write_line(&line);
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(file) = &self.file {
+            let _ = file.lock().file.flush();
+        }
+    }
+}
+
+/// Instala o logger do nó. `log_level` vale para o terminal; o arquivo e os
+/// módulos seguem `config`.
+pub fn init(log_level: &str, config: &LoggingConfig) -> Result<(), String> {
+    let logger = NodeLogger::new(log_level, config)?;
+    let logger = LOGGER
+        .try_insert(logger)
+        .map_err(|_| "Logger já inicializado".to_string())?;
+    log::set_logger(logger).map_err(|e| format!("Falha ao instalar logger: {}", e))?;
+    log::set_max_level(logger.max_level());
+    Ok(())
+}
+
+/// Troca o nível do terminal com o nó em execução, sem perder os níveis do
+/// arquivo e dos módulos
+pub fn set_level(level: LevelFilter) {
+    match LOGGER.get() {
+        Some(logger) => {
+            logger
+                .terminal_level
+                .store(level as usize, Ordering::Relaxed);
+            log::set_max_level(logger.max_level());
+        }
+        None => log::set_max_level(level),
+    }
+}
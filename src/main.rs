@@ -1,124 +1,747 @@
-use anyhow::{Context, Result};
-use log::{error, info};
-use pqcrypto_dilithium::dilithium5;
-use pqcrypto_traits::sign::PublicKey as PublicKeyTrait;
-use simplelog::*;
-use std::error::Error;
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use log::info;
+use serde::Serialize;
+use serde_json::{json, Value};
 use std::fs;
-use time::macros::format_description;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use kybelith::QuantumBlockchainApp;
+use kybelith::blockchain::{
+    Blockchain, LifecycleState, SharedBlockchain, TransactionLifecycle, TransactionRecord,
+};
+use kybelith::config::Settings;
+use kybelith::consensus::{QuantumFlexConsensus, Validator};
+use kybelith::network::NodeIdentity;
+use kybelith::rpc::{
+    BlockResponse, HeightResponse, NameResponse, RpcService, SubmitResult, TokenResponse,
+    ValidatorSetResponse,
+};
+use kybelith::transaction::{Operation, OperationKind};
+use kybelith::wallet::{export_public_key, import_key, ImportedKey, Recipient, SignedMessage};
+use kybelith::{ChainHost, QuantumBlockchainApp};
+use pqcrypto_traits::sign::PublicKey as _;
+use zeroize::Zeroizing;
 
-fn setup_logging() -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
-    let config = ConfigBuilder::new()
-        .set_time_format_custom(format_description!("%Y-%m-%d %H:%M:%S"))
-        .build();
+/// Arquivo de configuração opcional lido do diretório atual
+const CONFIG_FILE: &str = "config.json";
 
-    CombinedLogger::init(vec![
-        TermLogger::new(
-            LevelFilter::Info,
-            config.clone(),
-            TerminalMode::Mixed,
-            ColorChoice::Auto,
-        ),
-        WriteLogger::new(
-            LevelFilter::Debug,
-            config,
-            fs::File::create("blockchain.log")?,
-        ),
-    ])?;
+/// Sem subcomando, executa o nó
+#[derive(Parser)]
+#[command(name = "kybelith")]
+struct Cli {
+    /// Emite o documento OpenAPI da superfície RPC para geração de SDKs
+    #[arg(long)]
+    openapi: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Operações de carteira que não dependem do nó
+    #[command(subcommand)]
+    Wallet(WalletCommand),
+    /// Consulta a cadeia gravada no diretório de dados, sem iniciar o nó
+    Chain(ChainArgs),
+    /// Administração de tokens: monta e assina a operação e a admite no mempool
+    /// da cadeia gravada no diretório de dados (com o nó parado); ela é aplicada
+    /// no próximo bloco que o nó produzir
+    Token(TokenArgs),
+    /// Registro de nomes (`alice.qst`): monta e assina a operação e a admite no
+    /// mempool da cadeia gravada no diretório de dados, como em `token`
+    Name(NameArgs),
+}
+
+#[derive(Args)]
+struct ChainArgs {
+    /// Imprime a resposta da consulta em JSON
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: ChainCommand,
+}
+
+#[derive(Subcommand)]
+enum ChainCommand {
+    /// Altura e hash do topo da cadeia
+    Head,
+    /// Bloco pela altura ou pelo hash
+    Block {
+        /// Altura (número) ou hash hexadecimal do bloco
+        id: String,
+    },
+    /// Transação pelo txid, com a etapa do seu ciclo de vida
+    Tx { txid: String },
+    /// Validadores registrados na cadeia, do maior stake para o menor
+    Validators,
+    /// Dono atual de um nome do registro
+    Resolve { name: String },
+}
+
+#[derive(Args)]
+struct TokenArgs {
+    /// Imprime a resposta RPC em JSON
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: TokenCommand,
+}
+
+#[derive(Subcommand)]
+enum TokenCommand {
+    /// Cria um token no próximo ID livre, com todo o supply creditado ao autor
+    Create {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        symbol: String,
+        #[arg(long)]
+        supply: u64,
+        #[command(flatten)]
+        signer: SignerArgs,
+    },
+    /// Emite unidades novas do token para um endereço
+    Mint {
+        token_id: u64,
+        /// Endereço ou nome registrado (`alice.qst`)
+        #[arg(long)]
+        to: String,
+        #[arg(long)]
+        amount: u64,
+        #[command(flatten)]
+        signer: SignerArgs,
+    },
+    /// Queima unidades do saldo público do autor
+    Burn {
+        token_id: u64,
+        #[arg(long)]
+        amount: u64,
+        #[command(flatten)]
+        signer: SignerArgs,
+    },
+    /// Suspende as transferências do token, ou as retoma com `--resume`
+    Pause {
+        token_id: u64,
+        #[arg(long)]
+        resume: bool,
+        #[command(flatten)]
+        signer: SignerArgs,
+    },
+    /// Metadados, supply e situação do token
+    Info { token_id: u64 },
+}
+
+#[derive(Args)]
+struct NameArgs {
+    /// Imprime a resposta RPC em JSON
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: NameCommand,
+}
+
+#[derive(Subcommand)]
+enum NameCommand {
+    /// Registra um nome livre ou expirado para o autor; a taxa é de
+    /// `NAME_FEE_PER_BLOCK` do token nativo por bloco de validade
+    Register {
+        name: String,
+        /// Validade, em blocos
+        #[arg(long)]
+        blocks: u64,
+        #[command(flatten)]
+        signer: SignerArgs,
+    },
+    /// Estende a validade de um nome do autor
+    Renew {
+        name: String,
+        #[arg(long)]
+        blocks: u64,
+        #[command(flatten)]
+        signer: SignerArgs,
+    },
+    /// Passa um nome do autor para outro dono
+    Transfer {
+        name: String,
+        /// Endereço ou nome registrado do novo dono
+        #[arg(long)]
+        to: String,
+        #[command(flatten)]
+        signer: SignerArgs,
+    },
+}
+
+#[derive(Args)]
+struct SignerArgs {
+    /// Bloco armado com a chave secreta do autor (ver `wallet export-key`)
+    #[arg(long)]
+    keystore: PathBuf,
+    /// Arquivo com a senha, se a chave secreta estiver cifrada
+    #[arg(long)]
+    passphrase_file: Option<PathBuf>,
+}
+
+impl SignerArgs {
+    fn load(self) -> Result<ImportedKey> {
+        let armored = fs::read_to_string(&self.keystore)
+            .with_context(|| format!("Falha ao ler {}", self.keystore.display()))?;
+        let passphrase = read_passphrase(self.passphrase_file)?;
+        import_key(&armored, passphrase.as_deref().map(String::as_str))
+            .map_err(|e| anyhow::anyhow!("Chave inválida: {}", e))
+    }
+}
+
+#[derive(Subcommand)]
+enum WalletCommand {
+    /// Assina uma mensagem e imprime o envelope JSON
+    SignMessage {
+        /// Arquivo de chaves (chave pública seguida da secreta)
+        #[arg(long)]
+        key: PathBuf,
+        #[command(flatten)]
+        message: MessageInput,
+    },
+    /// Confere um envelope contra a mensagem
+    VerifyMessage {
+        /// Envelope JSON produzido por `sign-message`
+        #[arg(long)]
+        envelope: PathBuf,
+        #[command(flatten)]
+        message: MessageInput,
+    },
+    /// Exporta as chaves em bloco armado, com soma de verificação e esquema
+    ExportKey {
+        /// Arquivo de chaves (chave pública seguida da secreta)
+        #[arg(long)]
+        key: PathBuf,
+        /// Exporta só a chave pública
+        #[arg(long)]
+        public: bool,
+        /// Arquivo cuja primeira linha é a senha que cifra a chave secreta
+        #[arg(long, conflicts_with = "public")]
+        passphrase_file: Option<PathBuf>,
+    },
+    /// Importa um bloco armado para um arquivo de chaves novo
+    ImportKey {
+        #[arg(long)]
+        input: PathBuf,
+        #[arg(long)]
+        out: PathBuf,
+        /// Arquivo com a senha, se a chave secreta estiver cifrada
+        #[arg(long)]
+        passphrase_file: Option<PathBuf>,
+    },
+}
 
+#[derive(Args)]
+#[group(required = true, multiple = false)]
+struct MessageInput {
+    /// Mensagem em texto
+    #[arg(long)]
+    message: Option<String>,
+    /// Arquivo com a mensagem, lido como bytes
+    #[arg(long)]
+    file: Option<PathBuf>,
+}
+
+impl MessageInput {
+    fn read(self) -> Result<Vec<u8>> {
+        match (self.message, self.file) {
+            (Some(message), _) => Ok(message.into_bytes()),
+            (None, Some(file)) => {
+                fs::read(&file).with_context(|| format!("Falha ao ler {}", file.display()))
+            }
+            (None, None) => bail!("Informe --message ou --file"),
+        }
+    }
+}
+
+fn read_passphrase(path: Option<PathBuf>) -> Result<Option<Zeroizing<String>>> {
+    let Some(path) = path else {
+        return Ok(None);
+    };
+    let contents = Zeroizing::new(
+        fs::read_to_string(&path).with_context(|| format!("Falha ao ler {}", path.display()))?,
+    );
+    let passphrase = contents.lines().next().unwrap_or_default();
+    if passphrase.is_empty() {
+        bail!("Senha vazia em {}", path.display());
+    }
+    Ok(Some(Zeroizing::new(passphrase.to_string())))
+}
+
+fn run_wallet(command: WalletCommand) -> Result<()> {
+    match command {
+        WalletCommand::SignMessage { key, message } => {
+            let identity = NodeIdentity::load(&key)
+                .with_context(|| format!("Falha ao carregar chaves de {}", key.display()))?;
+            let signed = SignedMessage::sign_with(
+                &message.read()?,
+                identity.public_key.as_bytes(),
+                |data| identity.sign_message(data),
+            );
+            println!("{}", serde_json::to_string_pretty(&signed)?);
+        }
+        WalletCommand::VerifyMessage { envelope, message } => {
+            let contents = fs::read_to_string(&envelope)
+                .with_context(|| format!("Falha ao ler {}", envelope.display()))?;
+            let signed: SignedMessage =
+                serde_json::from_str(&contents).context("Envelope inválido")?;
+            signed
+                .verify(&message.read()?)
+                .map_err(|e| anyhow::anyhow!("Assinatura inválida: {}", e))?;
+            println!("Assinatura válida de {}", signed.address);
+        }
+        WalletCommand::ExportKey {
+            key,
+            public,
+            passphrase_file,
+        } => {
+            let identity = NodeIdentity::load(&key)
+                .with_context(|| format!("Falha ao carregar chaves de {}", key.display()))?;
+            let armored = if public {
+                export_public_key(&identity.public_key)
+            } else {
+                let passphrase = read_passphrase(passphrase_file)?;
+                identity
+                    .export_armored(passphrase.as_deref().map(String::as_str))
+                    .map_err(|e| anyhow::anyhow!("{}", e))?
+            };
+            print!("{}", armored);
+        }
+        WalletCommand::ImportKey {
+            input,
+            out,
+            passphrase_file,
+        } => {
+            let armored = fs::read_to_string(&input)
+                .with_context(|| format!("Falha ao ler {}", input.display()))?;
+            let passphrase = read_passphrase(passphrase_file)?;
+            let imported = import_key(&armored, passphrase.as_deref().map(String::as_str))
+                .map_err(|e| anyhow::anyhow!("Chave inválida: {}", e))?;
+            let secret_key = imported
+                .secret_key
+                .context("O bloco contém só a chave pública")?;
+            NodeIdentity::new(imported.public_key, secret_key)
+                .save(&out)
+                .with_context(|| format!("Falha ao gravar {}", out.display()))?;
+            println!(
+                "Chaves de {} gravadas em {}",
+                imported.address,
+                out.display()
+            );
+        }
+    }
     Ok(())
 }
 
-fn main() -> Result<()> {
-    if let Err(e) = setup_logging() {
-        eprintln!("Erro ao configurar logging: {}", e);
+/// Nome de um valor serializado como texto (variantes de enum em snake_case)
+fn label<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(Value::String(text)) => text,
+        Ok(other) => other.to_string(),
+        Err(_) => "?".to_string(),
     }
+}
 
-    info!("Iniciando aplicação blockchain quântica");
+/// Chama `method` no serviço RPC local, convertendo o erro para a CLI
+fn query(service: &RpcService, method: &str, request: Value) -> Result<Value> {
+    service
+        .handle(method, request)
+        .map_err(|e| anyhow::anyhow!("{}", e))
+}
 
-    // Gerar par de chaves para o nó
-    let (public_key, secret_key) = dilithium5::keypair();
+fn typed<T: serde::de::DeserializeOwned>(value: &Value) -> Result<T> {
+    serde_json::from_value(value.clone()).context("Resposta RPC inesperada")
+}
 
-    // Inicializar a aplicação
-    let mut app = QuantumBlockchainApp::new().context("Falha ao inicializar aplicação")?;
-
-    // Registrar as chaves do administrador na blockchain
-    let admin_address = "0x123...".to_string();
-
-    // Usando o método as_bytes() através do trait
-    app.blockchain
-        .public_keys
-        .insert(admin_address.clone(), public_key.as_bytes().to_vec());
-
-    // Se necessário, também registrar a chave secreta
-    app.blockchain
-        .secret_keys
-        .insert(admin_address.clone(), secret_key.clone());
-
-    // ADICIONAR SALDO INICIAL DE KYBL PARA O ADMINISTRADOR
-    // Este é o código que adicionamos para garantir que o administrador tenha KYBL suficiente
-    if let Some(kybl_token) = app.blockchain.tokens.get_mut(&0.to_string()) {
-        // Adicionar 1 milhão de KYBL para o administrador usar durante desenvolvimento
-        *kybl_token
-            .balances
-            .entry(admin_address.clone())
-            .or_insert(0) += 1_000_000;
-        info!(
-            "Adicionado saldo inicial de 1,000,000 KYBL ao administrador: {}",
-            admin_address
+fn load_chain(settings: &Settings) -> Result<Blockchain> {
+    let path = settings.blockchain_path();
+    if !path.exists() {
+        bail!("Nenhuma blockchain em {}", path.display());
+    }
+    Blockchain::load_from_file(&path.to_string_lossy())
+        .with_context(|| format!("Falha ao carregar {}", path.display()))
+}
+
+/// Serviço RPC sobre a cadeia do diretório de dados, aberta só para leitura para
+/// não interferir com um nó em execução. O conjunto de validadores é montado a
+/// partir das chaves de consenso registradas na cadeia e dos stakes.
+fn chain_service(settings: Settings) -> Result<RpcService> {
+    let path = settings.blockchain_path();
+    let blockchain = Blockchain::open_read_only(&path)
+        .with_context(|| format!("Falha ao abrir {}", path.display()))?
+        .into_inner();
+
+    let validators = blockchain
+        .validator_keys
+        .iter()
+        .map(|(address, public_key)| {
+            let stake = blockchain.stakers.get(address).copied().unwrap_or(0);
+            Validator::new(address.clone(), address.clone(), public_key.clone(), stake)
+        })
+        .collect();
+    let consensus = QuantumFlexConsensus::new(Arc::new(settings), validators);
+    Ok(RpcService::new(SharedBlockchain::new(blockchain)).with_consensus(Arc::new(consensus)))
+}
+
+fn run_chain(args: ChainArgs, settings: Settings) -> Result<()> {
+    let service = chain_service(settings)?;
+    let output = match &args.command {
+        ChainCommand::Head => query(&service, "get_height", Value::Null)?,
+        ChainCommand::Block { id } => match id.parse::<u64>() {
+            Ok(height) => query(&service, "get_block", json!({ "height": height }))?,
+            Err(_) => query(&service, "get_block_by_hash", json!({ "hash": id }))?,
+        },
+        ChainCommand::Tx { txid } => {
+            let record = query(&service, "get_transaction", json!({ "txid": txid })).ok();
+            let status = query(&service, "get_transaction_status", json!({ "txid": txid })).ok();
+            if record.is_none() && status.is_none() {
+                bail!("Transação {} não encontrada", txid);
+            }
+            json!({ "transaction": record, "status": status })
+        }
+        ChainCommand::Validators => query(&service, "get_validators", Value::Null)?,
+        ChainCommand::Resolve { name } => query(&service, "resolve_name", json!({ "name": name }))?,
+    };
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    match args.command {
+        ChainCommand::Head => {
+            let head: HeightResponse = typed(&output)?;
+            println!("Blocos: {}", head.height);
+            println!(
+                "Topo:   {}",
+                head.latest_hash.as_deref().unwrap_or("(cadeia vazia)")
+            );
+        }
+        ChainCommand::Block { .. } => {
+            let BlockResponse { block, finality } = typed(&output)?;
+            println!("Bloco {} ({})", block.index, label(&finality));
+            println!("Hash:        {}", block.hash);
+            println!("Anterior:    {}", block.previous_hash);
+            println!("Timestamp:   {}", block.timestamp);
+            println!(
+                "Proponente:  {}",
+                block.proposer.as_deref().unwrap_or("(não assinado)")
+            );
+            println!("Transações:  {}", block.transactions.len());
+            if let Some(execution) = &block.execution {
+                println!(
+                    "Execução:    {} transferências, {} operações, {} descartados, taxas {}",
+                    execution.transactions,
+                    execution.operations,
+                    execution.discarded,
+                    execution.total_fees
+                );
+            }
+        }
+        ChainCommand::Tx { txid } => {
+            println!("Transação {}", txid);
+            if !output["transaction"].is_null() {
+                let record: TransactionRecord = typed(&output["transaction"])?;
+                println!("De:          {}", record.from);
+                println!("Para:        {}", record.to);
+                println!("Valor:       {} (token {})", record.amount, record.token_id);
+                println!("Nonce:       {}", record.nonce);
+                println!("Finalidade:  {}", label(&record.finality));
+            }
+            if !output["status"].is_null() {
+                let status: TransactionLifecycle = typed(&output["status"])?;
+                let stage = match status.state {
+                    LifecycleState::Pending => "pendente no mempool".to_string(),
+                    LifecycleState::Included { height } => format!("incluída no bloco {}", height),
+                    LifecycleState::Finalized { height } => {
+                        format!("finalizada no bloco {}", height)
+                    }
+                    LifecycleState::Dropped { reason, .. } => format!("descartada: {}", reason),
+                };
+                println!("Etapa:       {}", stage);
+            }
+        }
+        ChainCommand::Validators => {
+            let set: ValidatorSetResponse = typed(&output)?;
+            println!(
+                "{} validadores ativos, stake total {}",
+                set.validators.len(),
+                set.total_active_stake
+            );
+            for validator in set.validators {
+                let ban = if validator.is_banned { " banido" } else { "" };
+                println!(
+                    "  {}  stake {}  reputação {:.1}{}",
+                    validator.address, validator.stake, validator.score, ban
+                );
+            }
+        }
+        ChainCommand::Resolve { .. } => {
+            let name: NameResponse = typed(&output)?;
+            println!("{} → {}", name.name, name.owner);
+            println!("Válido até o bloco {}", name.expires_at);
+        }
+    }
+    Ok(())
+}
+
+/// Endereço de um destinatário digitado como endereço ou nome (`alice.qst`),
+/// resolvido no registro da cadeia local
+fn resolve_recipient(blockchain: &SharedBlockchain, input: &str) -> Result<String> {
+    let recipient = Recipient::parse(input).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let address = recipient
+        .resolve(|name| blockchain.read(|blockchain| blockchain.resolve_name(name).cloned()))
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    Ok(address.into_inner())
+}
+
+fn run_token(args: TokenArgs, settings: Settings) -> Result<()> {
+    if let TokenCommand::Info { token_id } = args.command {
+        let service = chain_service(settings)?;
+        let output = query(
+            &service,
+            "get_token",
+            json!({ "token_id": token_id.to_string() }),
+        )?;
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&output)?);
+            return Ok(());
+        }
+        let token: TokenResponse = typed(&output)?;
+        println!("Token {} ({})", token.metadata.id, token.metadata.symbol);
+        println!("Nome:    {}", token.metadata.name);
+        println!("Dono:    {}", token.metadata.owner);
+        println!("Supply:  {}", token.total_supply);
+        let state = if token.paused {
+            "suspensas"
+        } else {
+            "liberadas"
+        };
+        println!("Transferências {}", state);
+        return Ok(());
+    }
+
+    let blockchain = SharedBlockchain::new(load_chain(&settings)?);
+    let (kind, signer) = match args.command {
+        TokenCommand::Create {
+            name,
+            symbol,
+            supply,
+            signer,
+        } => {
+            let token_id = blockchain.read(|blockchain| blockchain.next_token_id);
+            let kind = OperationKind::CreateToken {
+                token_id,
+                name,
+                symbol,
+                total_supply: supply,
+            };
+            (kind, signer)
+        }
+        TokenCommand::Mint {
+            token_id,
+            to,
+            amount,
+            signer,
+        } => (
+            OperationKind::MintTokens {
+                token_id,
+                to: resolve_recipient(&blockchain, &to)?,
+                amount,
+            },
+            signer,
+        ),
+        TokenCommand::Burn {
+            token_id,
+            amount,
+            signer,
+        } => (OperationKind::BurnTokens { token_id, amount }, signer),
+        TokenCommand::Pause {
+            token_id,
+            resume,
+            signer,
+        } => (
+            OperationKind::SetTokenPaused {
+                token_id,
+                paused: !resume,
+            },
+            signer,
+        ),
+        TokenCommand::Info { .. } => unreachable!("consulta atendida acima"),
+    };
+    admit_operation(&blockchain, &settings, kind, signer, args.json)
+}
+
+fn run_name(args: NameArgs, settings: Settings) -> Result<()> {
+    let blockchain = SharedBlockchain::new(load_chain(&settings)?);
+    let (kind, signer) = match args.command {
+        NameCommand::Register {
+            name,
+            blocks,
+            signer,
+        } => (
+            OperationKind::RegisterName {
+                name: name.to_ascii_lowercase(),
+                blocks,
+            },
+            signer,
+        ),
+        NameCommand::Renew {
+            name,
+            blocks,
+            signer,
+        } => (
+            OperationKind::RenewName {
+                name: name.to_ascii_lowercase(),
+                blocks,
+            },
+            signer,
+        ),
+        NameCommand::Transfer { name, to, signer } => (
+            OperationKind::TransferName {
+                name: name.to_ascii_lowercase(),
+                to: resolve_recipient(&blockchain, &to)?,
+            },
+            signer,
+        ),
+    };
+    admit_operation(&blockchain, &settings, kind, signer, args.json)
+}
+
+/// Monta e assina a operação com a chave de `signer`, admite no mempool da cadeia
+/// local e grava a cadeia
+fn admit_operation(
+    blockchain: &SharedBlockchain,
+    settings: &Settings,
+    kind: OperationKind,
+    signer: SignerArgs,
+    json: bool,
+) -> Result<()> {
+    let key = signer.load()?;
+    let secret_key = key
+        .secret_key
+        .context("O bloco contém só a chave pública")?;
+    let nonce = blockchain.nonce_of(&key.address) + 1;
+    let mut operation = Operation::new(
+        kind,
+        key.address.clone(),
+        nonce,
+        key.public_key.as_bytes().to_vec(),
+    )
+    .map_err(|e| anyhow::anyhow!("Operação inválida: {}", e))?;
+    operation
+        .sign(&secret_key)
+        .map_err(|e| anyhow::anyhow!("Falha ao assinar: {}", e))?;
+
+    let service = RpcService::new(blockchain.clone());
+    let output = query(
+        &service,
+        "submit_operation",
+        serde_json::to_value(&operation)?,
+    )?;
+    let result: SubmitResult = typed(&output)?;
+    if !result.accepted {
+        bail!(
+            "Operação rejeitada: {}",
+            result
+                .error
+                .unwrap_or_else(|| "motivo desconhecido".to_string())
         );
-    } else {
-        error!("Token KYBL (ID 0) não encontrado ao tentar adicionar saldo inicial");
     }
+    let path = settings.blockchain_path();
+    blockchain
+        .save_to_file(&path.to_string_lossy())
+        .with_context(|| format!("Falha ao gravar {}", path.display()))?;
 
-    info!(
-        "Chaves do administrador registradas com sucesso: {}",
-        admin_address
+    if json {
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+    if let OperationKind::CreateToken { token_id, .. } = &operation.kind {
+        println!("Token {} reservado", token_id);
+    }
+    println!(
+        "Operação de {} admitida com nonce {}; aplicada no próximo bloco",
+        key.address, nonce
     );
+    Ok(())
+}
 
-    // Criar um token normal
-    let token = app.create_token("MyToken".to_string(), "MTK".to_string(), 1_000_000)?;
-
-    info!("Token criado: {} ({})", token.name, token.symbol);
-
-    // Criar um token personalizado
-    let custom_token_id = 1;
-    let mut custom_token = app
-        .create_custom_token(
-            custom_token_id,
-            "CustomToken".to_string(),
-            "CTK".to_string(),
-            500_000,
-            admin_address.clone(),
-        )
-        .context("Falha ao criar token personalizado")?;
-
-    info!(
-        "Token personalizado criado: {} ({})",
-        custom_token.name, custom_token.symbol
-    );
+fn load_settings() -> Result<Settings> {
+    if Path::new(CONFIG_FILE).exists() {
+        return Settings::from_file(CONFIG_FILE).map_err(anyhow::Error::msg);
+    }
 
-    // Transferir tokens
-    let transaction =
-        app.transfer_token(&mut custom_token, "0x456...".to_string(), 1000, &secret_key)?;
+    // Sem arquivo de configuração, o estado fica no diretório atual
+    let mut settings = Settings::default();
+    settings.node.data_dir = ".".to_string();
+    Ok(settings)
+}
 
-    // Verificar a assinatura
-    if !transaction.verify(&public_key, &transaction.signature)? {
-        error!("Assinatura da transação inválida!");
-        return Err(anyhow::anyhow!("Assinatura inválida"));
+#[tokio::main]
+async fn main() -> Result<()> {
+    // O liboqs passa a sortear pela entropia do nó antes de qualquer chave
+    kybelith::utils::entropy::install();
+    let cli = Cli::parse();
+    if cli.openapi {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&kybelith::rpc::openapi_document())?
+        );
+        return Ok(());
+    }
+    match cli.command {
+        Some(Command::Wallet(command)) => return run_wallet(command),
+        Some(Command::Chain(args)) => return run_chain(args, load_settings()?),
+        Some(Command::Token(args)) => return run_token(args, load_settings()?),
+        Some(Command::Name(args)) => return run_name(args, load_settings()?),
+        None => {}
+    }
+
+    kybelith::utils::i18n::set_locale(kybelith::utils::i18n::Locale::from_env());
+
+    let settings = load_settings()?;
+    if let Err(e) = kybelith::logging::init(&settings.node.log_level, &settings.logging) {
+        eprintln!("Erro ao configurar logging: {}", e);
     }
 
-    info!("Transferência realizada com sucesso e verificada");
+    info!("Iniciando aplicação blockchain quântica");
+
+    // Mede o desvio do relógio local em segundo plano, sem atrasar a inicialização
+    std::thread::spawn(|| loop {
+        let clock = kybelith::utils::clock::clock();
+        if let Ok(offset) = clock.sync_ntp() {
+            info!("Desvio do relógio local em relação ao NTP: {} ms", offset);
+        }
+        let interval = clock.tolerance().ntp_sync_interval_sec.max(60);
+        std::thread::sleep(std::time::Duration::from_secs(interval));
+    });
 
-    // Verificar a integridade da blockchain
-    if !app.verify_chain_integrity()? {
-        error!("Detectada violação de integridade na blockchain!");
-        return Err(anyhow::anyhow!("Violação de integridade detectada"));
+    if !settings.additional_chains.is_empty() {
+        let mut chains = vec![settings.clone()];
+        for path in &settings.additional_chains {
+            let chain = Settings::from_file(path).map_err(|e| {
+                anyhow::anyhow!("Falha ao carregar a cadeia {}: {}", path.display(), e)
+            })?;
+            chains.push(chain);
+        }
+        info!("Servindo {} cadeias; Ctrl+C encerra o nó", chains.len());
+        ChainHost::run(chains)
+            .await
+            .context("Falha durante a execução do nó")?;
+        return Ok(());
     }
 
-    info!("Aplicação iniciada com sucesso");
+    // Inicializar a aplicação
+    let app = QuantumBlockchainApp::open(&settings).context("Falha ao inicializar aplicação")?;
+
+    info!("Aplicação iniciada com sucesso; Ctrl+C encerra o nó");
+
+    app.run(settings)
+        .await
+        .context("Falha durante a execução do nó")?;
 
     Ok(())
 }
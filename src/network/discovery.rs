@@ -0,0 +1,242 @@
+use super::misbehavior::{peer_host, BanList, Misbehavior, PeerScoring, PeerVerdict};
+use super::peer_store::{PeerSource, PeerStore};
+use crate::config::{P2PConfig, Settings};
+use crate::error::Error;
+use crate::utils::i18n::message;
+
+/// Tipo de serviço anunciado via mDNS
+pub const MDNS_SERVICE_TYPE: &str = "_kybelith._tcp.local.";
+
+/// Descoberta de peers: nós bootstrap da configuração, livro de endereços
+/// persistido e, opcionalmente, mDNS na rede local. Hosts banidos por mau
+/// comportamento não são sugeridos nem aceitos.
+pub struct PeerDiscovery {
+    store: PeerStore,
+    scoring: PeerScoring,
+    bootstrap: Vec<String>,
+    max_outgoing: usize,
+    #[cfg(feature = "mdns")]
+    mdns: Option<mdns::MdnsDiscovery>,
+}
+
+impl PeerDiscovery {
+    /// Monta a descoberta sobre um livro já aberto, semeando os nós bootstrap
+    pub fn new(config: &P2PConfig, mut store: PeerStore) -> Self {
+        let bootstrap: Vec<String> = config
+            .bootstrap_nodes
+            .iter()
+            .map(|address| address.trim().to_string())
+            .filter(|address| !address.is_empty())
+            .collect();
+        for address in &bootstrap {
+            store.add(address, PeerSource::Bootstrap);
+        }
+
+        Self {
+            store,
+            scoring: PeerScoring::default(),
+            bootstrap,
+            max_outgoing: config.max_outgoing_connections as usize,
+            #[cfg(feature = "mdns")]
+            mdns: None,
+        }
+    }
+
+    /// Abre o livro em `data_dir` e liga o mDNS se estiver habilitado
+    pub fn from_settings(settings: &Settings) -> Result<Self, Error> {
+        let store = PeerStore::open(settings.peer_store_path())?;
+        let mut discovery = Self::new(&settings.p2p, store);
+        discovery.scoring = PeerScoring::new(BanList::open(settings.ban_list_path())?);
+
+        if settings.p2p.enable_mdns {
+            #[cfg(feature = "mdns")]
+            {
+                discovery.mdns = Some(mdns::MdnsDiscovery::start(
+                    &settings.node.node_id,
+                    &settings.p2p.listen_address,
+                )?);
+            }
+            #[cfg(not(feature = "mdns"))]
+            log::warn!("enable_mdns ativo, mas o nó foi compilado sem a feature `mdns`");
+        }
+
+        Ok(discovery)
+    }
+
+    /// Endereços para as próximas conexões de saída: os peers mais bem pontuados,
+    /// completando com os nós bootstrap caso o livro não tenha candidatos suficientes
+    pub fn dial_candidates(&self) -> Vec<String> {
+        let mut candidates: Vec<String> = self
+            .store
+            .best_peers(usize::MAX)
+            .into_iter()
+            .map(|record| record.address.clone())
+            .filter(|address| self.scoring.allows(address))
+            .take(self.max_outgoing)
+            .collect();
+
+        for address in &self.bootstrap {
+            if candidates.len() >= self.max_outgoing {
+                break;
+            }
+            if !candidates.contains(address) && self.scoring.allows(address) {
+                candidates.push(address.clone());
+            }
+        }
+        candidates
+    }
+
+    /// Endereço recebido de outro peer (troca de endereços)
+    pub fn add_gossiped(&mut self, address: &str) -> bool {
+        self.store.add(address, PeerSource::Gossip)
+    }
+
+    /// Incorpora os peers anunciados via mDNS desde a última chamada
+    pub fn poll_mdns(&mut self) -> usize {
+        #[cfg(feature = "mdns")]
+        if let Some(mdns) = &self.mdns {
+            return mdns
+                .drain()
+                .iter()
+                .filter(|address| self.store.add(address, PeerSource::Mdns))
+                .count();
+        }
+        0
+    }
+
+    pub fn record_success(&mut self, address: &str) {
+        self.store.record_success(address);
+    }
+
+    pub fn record_failure(&mut self, address: &str) {
+        self.store.record_failure(address);
+    }
+
+    /// Se uma conexão (de entrada ou de saída) com o endereço é permitida
+    pub fn allows(&self, address: &str) -> bool {
+        self.scoring.allows(address)
+    }
+
+    /// Registra o comportamento de um peer conectado; o chamador encerra a conexão
+    /// quando o veredito não for `Keep`
+    pub fn report(&mut self, address: &str, behavior: Misbehavior) -> PeerVerdict {
+        let verdict = self.scoring.report(address, behavior);
+        if verdict != PeerVerdict::Keep {
+            self.store.record_failure(address);
+        }
+        if let PeerVerdict::Ban { until } = verdict {
+            log::warn!(
+                "Host {} banido até {}",
+                peer_host(address),
+                chrono::DateTime::from_timestamp(until, 0)
+                    .map(|t| t.to_rfc3339())
+                    .unwrap_or_default()
+            );
+            if let Err(e) = self.scoring.save() {
+                log::error!("{}: {}", message("peer.ban_persist_failed"), e);
+            }
+        }
+        verdict
+    }
+
+    pub fn store(&self) -> &PeerStore {
+        &self.store
+    }
+
+    pub fn scoring(&self) -> &PeerScoring {
+        &self.scoring
+    }
+
+    /// Persiste o livro de endereços e a lista de banimentos
+    pub fn save(&self) -> Result<(), Error> {
+        self.store.save()?;
+        self.scoring.save()
+    }
+}
+
+#[cfg(feature = "mdns")]
+mod mdns {
+    use super::MDNS_SERVICE_TYPE;
+    use crate::error::Error;
+    use crate::utils::i18n::message;
+    use mdns_sd::{Receiver, ServiceDaemon, ServiceEvent, ServiceInfo};
+    use std::net::SocketAddr;
+
+    pub struct MdnsDiscovery {
+        daemon: ServiceDaemon,
+        events: Receiver<ServiceEvent>,
+        own_fullname: String,
+    }
+
+    fn mdns_error(err: mdns_sd::Error) -> Error {
+        Error::Other(format!("Erro mDNS: {}", err))
+    }
+
+    impl MdnsDiscovery {
+        /// Anuncia o nó na rede local e passa a escutar anúncios de outros nós
+        pub fn start(node_id: &str, listen_address: &str) -> Result<Self, Error> {
+            let listen: SocketAddr = listen_address
+                .parse()
+                .map_err(|e| Error::InvalidInput(format!("Endereço de escuta inválido: {}", e)))?;
+
+            let daemon = ServiceDaemon::new().map_err(mdns_error)?;
+            let instance: String = node_id.chars().filter(|c| *c != '.').take(63).collect();
+            let host_name = format!("{}.local.", instance);
+            let info = if listen.ip().is_unspecified() {
+                ServiceInfo::new(
+                    MDNS_SERVICE_TYPE,
+                    &instance,
+                    &host_name,
+                    "",
+                    listen.port(),
+                    None,
+                )
+                .map(ServiceInfo::enable_addr_auto)
+            } else {
+                ServiceInfo::new(
+                    MDNS_SERVICE_TYPE,
+                    &instance,
+                    &host_name,
+                    listen.ip(),
+                    listen.port(),
+                    None,
+                )
+            }
+            .map_err(mdns_error)?;
+            let own_fullname = info.get_fullname().to_string();
+
+            daemon.register(info).map_err(mdns_error)?;
+            let events = daemon.browse(MDNS_SERVICE_TYPE).map_err(mdns_error)?;
+
+            Ok(Self {
+                daemon,
+                events,
+                own_fullname,
+            })
+        }
+
+        /// Endereços resolvidos desde a última chamada, sem bloquear
+        pub fn drain(&self) -> Vec<String> {
+            let mut addresses = Vec::new();
+            while let Ok(event) = self.events.try_recv() {
+                if let ServiceEvent::ServiceResolved(info) = event {
+                    if info.get_fullname() == self.own_fullname {
+                        continue;
+                    }
+                    addresses.extend(
+                        info.get_addresses()
+                            .iter()
+                            .map(|ip| SocketAddr::new(*ip, info.get_port()).to_string()),
+                    );
+                }
+            }
+            addresses
+        }
+    }
+
+    impl Drop for MdnsDiscovery {
+        fn drop(&mut self) {
+            let _ = self.daemon.shutdown();
+        }
+    }
+}
@@ -0,0 +1,359 @@
+use crate::error::Error;
+use crate::utils::clock::{clock, SharedClock};
+use crate::utils::i18n::message;
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Comportamentos de um peer que afetam sua pontuação
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Misbehavior {
+    /// Mensagem útil e válida (recupera um pouco da pontuação)
+    ValidMessage,
+
+    /// Mensagem malformada ou que não desserializa
+    InvalidMessage,
+
+    /// Transação com assinatura ou campos inválidos
+    InvalidTransaction,
+
+    /// Bloco que não passa na validação
+    InvalidBlock,
+
+    /// Excesso de mensagens, duplicatas ou frames grandes demais
+    Spam,
+
+    /// Violação do protocolo (handshake, frames fora de ordem ou adulterados)
+    ProtocolViolation,
+}
+
+/// Decisão após registrar um comportamento
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerVerdict {
+    /// Conexão segue normalmente
+    Keep,
+    /// Encerrar a conexão, sem banir
+    Disconnect,
+    /// Encerrar a conexão e recusar o host até o instante indicado (Unix, segundos)
+    Ban { until: i64 },
+}
+
+/// Parâmetros da pontuação de peers (escala 0-100)
+#[derive(Debug, Clone)]
+pub struct PeerScoringConfig {
+    pub initial_score: f32,
+    pub valid_message_points: f32,
+    pub invalid_message_penalty: f32,
+    pub invalid_transaction_penalty: f32,
+    pub invalid_block_penalty: f32,
+    pub spam_penalty: f32,
+    pub protocol_violation_penalty: f32,
+
+    /// Abaixo deste limiar a conexão é encerrada
+    pub disconnect_threshold: f32,
+
+    /// Abaixo deste limiar o host é banido
+    pub ban_threshold: f32,
+
+    /// Duração do primeiro banimento; dobra a cada reincidência
+    pub initial_ban_duration: Duration,
+
+    /// Teto da duração de um banimento
+    pub max_ban_duration: Duration,
+}
+
+impl Default for PeerScoringConfig {
+    fn default() -> Self {
+        Self {
+            initial_score: 100.0,
+            valid_message_points: 0.5,
+            invalid_message_penalty: 10.0,
+            invalid_transaction_penalty: 5.0,
+            invalid_block_penalty: 25.0,
+            spam_penalty: 5.0,
+            protocol_violation_penalty: 40.0,
+            disconnect_threshold: 50.0,
+            ban_threshold: 20.0,
+            initial_ban_duration: Duration::from_secs(3600), // 1 hora
+            max_ban_duration: Duration::from_secs(7 * 24 * 3600),
+        }
+    }
+}
+
+/// Banimento de um host
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerBan {
+    pub host: String,
+    pub reason: String,
+    /// Fim do banimento (Unix, segundos)
+    pub banned_until: i64,
+    /// Quantas vezes o host já foi banido
+    pub ban_count: u32,
+}
+
+/// Host de um endereço `host:porta`; conexões de entrada usam portas efêmeras,
+/// então o banimento vale para o host inteiro
+pub fn peer_host(address: &str) -> String {
+    match address.parse::<SocketAddr>() {
+        Ok(socket) => socket.ip().to_string(),
+        Err(_) => address
+            .rsplit_once(':')
+            .map(|(host, _)| host)
+            .unwrap_or(address)
+            .to_string(),
+    }
+}
+
+/// Lista de hosts banidos, persistida em JSON
+#[derive(Debug, Default)]
+pub struct BanList {
+    path: Option<PathBuf>,
+    bans: HashMap<String, PeerBan>,
+}
+
+impl BanList {
+    /// Lista apenas em memória
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Abre a lista persistida em `path`; um arquivo inexistente resulta em lista vazia
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let bans = if path.exists() {
+            let contents = std::fs::read(&path).map_err(|e| {
+                Error::Other(format!(
+                    "Falha ao ler banimentos de {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+            let records: Vec<PeerBan> = serde_json::from_slice(&contents)?;
+            records
+                .into_iter()
+                .map(|ban| (ban.host.clone(), ban))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            path: Some(path),
+            bans,
+        })
+    }
+
+    /// Grava a lista no arquivo de origem (sem efeito para listas em memória)
+    pub fn save(&self) -> Result<(), Error> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| Error::Other(format!("Falha ao criar {}: {}", parent.display(), e)))?;
+        }
+
+        let mut records: Vec<&PeerBan> = self.bans.values().collect();
+        records.sort_by(|a, b| a.host.cmp(&b.host));
+        let contents = serde_json::to_vec_pretty(&records)?;
+
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, contents)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| {
+                Error::Other(format!(
+                    "Falha ao gravar banimentos em {}: {}",
+                    path.display(),
+                    e
+                ))
+            })
+    }
+
+    /// Bane `host` até `until`, contando a reincidência
+    pub fn ban(&mut self, host: &str, reason: &str, until: i64) -> &PeerBan {
+        let ban = self
+            .bans
+            .entry(host.to_string())
+            .or_insert_with(|| PeerBan {
+                host: host.to_string(),
+                reason: String::new(),
+                banned_until: 0,
+                ban_count: 0,
+            });
+        ban.reason = reason.to_string();
+        ban.banned_until = ban.banned_until.max(until);
+        ban.ban_count = ban.ban_count.saturating_add(1);
+        ban
+    }
+
+    pub fn unban(&mut self, host: &str) -> bool {
+        self.bans.remove(host).is_some()
+    }
+
+    pub fn get(&self, host: &str) -> Option<&PeerBan> {
+        self.bans.get(host)
+    }
+
+    /// Verifica se o host está banido no instante `now` (Unix, segundos)
+    pub fn is_banned_at(&self, host: &str, now: i64) -> bool {
+        self.bans
+            .get(host)
+            .map(|ban| ban.banned_until > now)
+            .unwrap_or(false)
+    }
+
+    pub fn is_banned(&self, host: &str) -> bool {
+        self.is_banned_at(host, clock().now_secs())
+    }
+
+    /// Banimentos em vigor no instante `now`
+    pub fn active_at(&self, now: i64) -> Vec<&PeerBan> {
+        self.bans
+            .values()
+            .filter(|ban| ban.banned_until > now)
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.bans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bans.is_empty()
+    }
+}
+
+/// Pontuação de mau comportamento por host. A pontuação sobrevive a reconexões
+/// (um peer desconectado não volta zerado) e é reiniciada quando o host é banido;
+/// só o banimento é persistido.
+#[derive(Debug)]
+pub struct PeerScoring {
+    scores: HashMap<String, f32>,
+    bans: BanList,
+    config: PeerScoringConfig,
+    clock: SharedClock,
+}
+
+impl Default for PeerScoring {
+    fn default() -> Self {
+        Self::new(BanList::new())
+    }
+}
+
+impl PeerScoring {
+    pub fn new(bans: BanList) -> Self {
+        Self::with_config(bans, PeerScoringConfig::default())
+    }
+
+    pub fn with_config(bans: BanList, config: PeerScoringConfig) -> Self {
+        Self {
+            scores: HashMap::new(),
+            bans,
+            config,
+            clock: clock().source(),
+        }
+    }
+
+    /// Substitui o relógio que data os banimentos e confere sua expiração
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn now_secs(&self) -> i64 {
+        self.clock.unix_millis() / 1000
+    }
+
+    pub fn config(&self) -> &PeerScoringConfig {
+        &self.config
+    }
+
+    pub fn bans(&self) -> &BanList {
+        &self.bans
+    }
+
+    /// Pontuação atual do peer (peers sem registro têm a pontuação inicial)
+    pub fn score(&self, address: &str) -> f32 {
+        self.scores
+            .get(&peer_host(address))
+            .copied()
+            .unwrap_or(self.config.initial_score)
+    }
+
+    /// Se uma conexão com o endereço pode ser aceita ou iniciada agora
+    pub fn allows(&self, address: &str) -> bool {
+        !self.bans.is_banned_at(&peer_host(address), self.now_secs())
+    }
+
+    /// Registra um comportamento do peer e decide o destino da conexão
+    pub fn report(&mut self, address: &str, behavior: Misbehavior) -> PeerVerdict {
+        self.report_at(address, behavior, self.now_secs())
+    }
+
+    /// Como `report`, com o instante (Unix, segundos) informado pelo chamador
+    pub fn report_at(&mut self, address: &str, behavior: Misbehavior, now: i64) -> PeerVerdict {
+        let adjustment = match behavior {
+            Misbehavior::ValidMessage => self.config.valid_message_points,
+            Misbehavior::InvalidMessage => -self.config.invalid_message_penalty,
+            Misbehavior::InvalidTransaction => -self.config.invalid_transaction_penalty,
+            Misbehavior::InvalidBlock => -self.config.invalid_block_penalty,
+            Misbehavior::Spam => -self.config.spam_penalty,
+            Misbehavior::ProtocolViolation => -self.config.protocol_violation_penalty,
+        };
+
+        let host = peer_host(address);
+        let initial = self.config.initial_score;
+        let score = self.scores.entry(host.clone()).or_insert(initial);
+        *score = (*score + adjustment).clamp(0.0, 100.0);
+        let score = *score;
+
+        if adjustment >= 0.0 {
+            return PeerVerdict::Keep;
+        }
+        debug!(
+            "Peer {} perdeu {:.2} pontos ({:?}), pontuação {:.2}",
+            address, -adjustment, behavior, score
+        );
+
+        if score < self.config.ban_threshold {
+            let previous = self.bans.get(&host).map(|ban| ban.ban_count).unwrap_or(0);
+            let duration = self
+                .config
+                .initial_ban_duration
+                .saturating_mul(2u32.saturating_pow(previous.min(16)))
+                .min(self.config.max_ban_duration);
+            let until = now.saturating_add(duration.as_secs() as i64);
+            self.bans.ban(&host, &format!("{:?}", behavior), until);
+            self.scores.remove(&host);
+
+            info!(
+                "{} {} ({:?}, {:.2})",
+                message("peer.banned"),
+                host,
+                duration,
+                score
+            );
+            return PeerVerdict::Ban { until };
+        }
+
+        if score < self.config.disconnect_threshold {
+            info!(
+                "{} {} ({:.2})",
+                message("peer.disconnected"),
+                address,
+                score
+            );
+            return PeerVerdict::Disconnect;
+        }
+
+        PeerVerdict::Keep
+    }
+
+    /// Persiste a lista de banimentos
+    pub fn save(&self) -> Result<(), Error> {
+        self.bans.save()
+    }
+}
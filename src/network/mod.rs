@@ -0,0 +1,14 @@
+pub mod discovery;
+pub mod misbehavior;
+pub mod peer_store;
+pub mod transport;
+
+pub use discovery::{PeerDiscovery, MDNS_SERVICE_TYPE};
+pub use misbehavior::{
+    peer_host, BanList, Misbehavior, PeerBan, PeerScoring, PeerScoringConfig, PeerVerdict,
+};
+pub use peer_store::{PeerRecord, PeerSource, PeerStore};
+pub use transport::{
+    HandshakeFinish, HandshakeInit, HandshakeResponse, Initiator, NodeIdentity, Responder,
+    SecureChannel, SecureSession, TransportError, MAX_FRAME_SIZE, PROTOCOL_VERSION,
+};
@@ -0,0 +1,236 @@
+use crate::error::Error;
+use crate::utils::clock::{clock, SharedClock};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Pontuação de um peer recém-descoberto
+pub const INITIAL_SCORE: i32 = 0;
+/// Limites da pontuação de qualidade
+pub const MAX_SCORE: i32 = 100;
+pub const MIN_SCORE: i32 = -100;
+/// Abaixo disso o peer não é mais sugerido para conexão
+pub const DIAL_THRESHOLD: i32 = -50;
+
+const SUCCESS_REWARD: i32 = 5;
+const FAILURE_PENALTY: i32 = 10;
+
+/// Capacidade padrão do livro de endereços
+pub const DEFAULT_CAPACITY: usize = 1_000;
+
+/// Como o endereço chegou ao nó
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PeerSource {
+    Bootstrap,
+    Mdns,
+    Gossip,
+    Manual,
+}
+
+/// Entrada do livro de endereços
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerRecord {
+    pub address: String,
+    pub source: PeerSource,
+    pub score: i32,
+    pub successes: u32,
+    pub failures: u32,
+    /// Última conexão bem-sucedida (Unix, segundos)
+    pub last_seen: Option<i64>,
+    pub first_seen: i64,
+}
+
+/// Livro de endereços de peers com pontuação de qualidade, persistido em JSON
+/// para que o nó volte a se conectar aos bons peers após reiniciar.
+#[derive(Debug)]
+pub struct PeerStore {
+    path: Option<PathBuf>,
+    capacity: usize,
+    peers: HashMap<String, PeerRecord>,
+    clock: SharedClock,
+}
+
+impl Default for PeerStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PeerStore {
+    /// Livro apenas em memória
+    pub fn new() -> Self {
+        Self {
+            path: None,
+            capacity: DEFAULT_CAPACITY,
+            peers: HashMap::new(),
+            clock: clock().source(),
+        }
+    }
+
+    /// Abre o livro persistido em `path`; um arquivo inexistente resulta em livro vazio
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let peers = if path.exists() {
+            let contents = std::fs::read(&path).map_err(|e| {
+                Error::Other(format!("Falha ao ler peers de {}: {}", path.display(), e))
+            })?;
+            let records: Vec<PeerRecord> = serde_json::from_slice(&contents)?;
+            records
+                .into_iter()
+                .map(|record| (record.address.clone(), record))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            path: Some(path),
+            capacity: DEFAULT_CAPACITY,
+            peers,
+            clock: clock().source(),
+        })
+    }
+
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self.evict();
+        self
+    }
+
+    /// Substitui o relógio que data a descoberta e a última conexão dos peers
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn now_secs(&self) -> i64 {
+        self.clock.unix_millis() / 1000
+    }
+
+    /// Grava o livro no arquivo de origem (sem efeito para livros em memória)
+    pub fn save(&self) -> Result<(), Error> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| Error::Other(format!("Falha ao criar {}: {}", parent.display(), e)))?;
+        }
+
+        let mut records: Vec<&PeerRecord> = self.peers.values().collect();
+        records.sort_by(|a, b| a.address.cmp(&b.address));
+        let contents = serde_json::to_vec_pretty(&records)?;
+
+        // Grava num arquivo temporário e renomeia, para não deixar o livro truncado
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, contents)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| {
+                Error::Other(format!(
+                    "Falha ao gravar peers em {}: {}",
+                    path.display(),
+                    e
+                ))
+            })
+    }
+
+    /// Registra um endereço; devolve `true` se ele ainda não era conhecido
+    pub fn add(&mut self, address: &str, source: PeerSource) -> bool {
+        let address = address.trim();
+        if address.is_empty() || self.peers.contains_key(address) {
+            return false;
+        }
+
+        let now = self.now_secs();
+        self.peers.insert(
+            address.to_string(),
+            PeerRecord {
+                address: address.to_string(),
+                source,
+                score: INITIAL_SCORE,
+                successes: 0,
+                failures: 0,
+                last_seen: None,
+                first_seen: now,
+            },
+        );
+        self.evict();
+        self.peers.contains_key(address)
+    }
+
+    /// Conexão (handshake) bem-sucedida com o peer
+    pub fn record_success(&mut self, address: &str) {
+        let now = self.now_secs();
+        if let Some(record) = self.peers.get_mut(address) {
+            record.successes = record.successes.saturating_add(1);
+            record.score = (record.score + SUCCESS_REWARD).min(MAX_SCORE);
+            record.last_seen = Some(now);
+        }
+    }
+
+    /// Falha de conexão ou handshake com o peer
+    pub fn record_failure(&mut self, address: &str) {
+        if let Some(record) = self.peers.get_mut(address) {
+            record.failures = record.failures.saturating_add(1);
+            record.score = (record.score - FAILURE_PENALTY).max(MIN_SCORE);
+        }
+    }
+
+    pub fn remove(&mut self, address: &str) -> Option<PeerRecord> {
+        self.peers.remove(address)
+    }
+
+    pub fn get(&self, address: &str) -> Option<&PeerRecord> {
+        self.peers.get(address)
+    }
+
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+
+    /// Até `limit` peers aptos para conexão, dos melhores para os piores;
+    /// empates favorecem quem foi visto mais recentemente
+    pub fn best_peers(&self, limit: usize) -> Vec<&PeerRecord> {
+        let mut candidates: Vec<&PeerRecord> = self
+            .peers
+            .values()
+            .filter(|record| record.score > DIAL_THRESHOLD)
+            .collect();
+        candidates.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then(b.last_seen.cmp(&a.last_seen))
+                .then(a.address.cmp(&b.address))
+        });
+        candidates.truncate(limit);
+        candidates
+    }
+
+    /// Mantém o livro dentro da capacidade descartando as piores entradas;
+    /// nós bootstrap nunca são descartados
+    fn evict(&mut self) {
+        while self.peers.len() > self.capacity {
+            let worst = self
+                .peers
+                .values()
+                .filter(|record| record.source != PeerSource::Bootstrap)
+                .min_by(|a, b| {
+                    a.score
+                        .cmp(&b.score)
+                        .then(a.last_seen.cmp(&b.last_seen))
+                        .then(b.first_seen.cmp(&a.first_seen))
+                })
+                .map(|record| record.address.clone());
+            match worst {
+                Some(address) => {
+                    self.peers.remove(&address);
+                }
+                None => break,
+            }
+        }
+    }
+}
@@ -0,0 +1,673 @@
+use super::misbehavior::Misbehavior;
+use crate::utils::compression::{self, Codec, SUPPORTED_CODECS};
+use crate::utils::dilithium;
+use crate::utils::entropy::{self, entropy};
+use oqs::kem::{Algorithm as KemAlgorithm, Kem};
+use pqcrypto_dilithium::dilithium5::{self, PublicKey, SecretKey};
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _, SecretKey as _};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use sodiumoxide::crypto::secretbox;
+use std::io::Write;
+use std::path::Path;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use zeroize::Zeroize;
+
+/// Versão do protocolo de handshake; peers de versões diferentes não se conectam
+pub const PROTOCOL_VERSION: u8 = 3;
+
+/// Tamanho máximo de um frame na conexão (cabe um bloco máximo serializado com folga)
+pub const MAX_FRAME_SIZE: usize = 4 * 1024 * 1024;
+
+const PROTOCOL_NAME: &[u8] = b"kybelith-p2p-kyber512-dilithium5-v1";
+const COUNTER_LEN: usize = 8;
+
+/// Erros do transporte seguro entre nós
+#[derive(Debug, thiserror::Error)]
+pub enum TransportError {
+    #[error("Erro de E/S: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Handshake inválido: {0}")]
+    Handshake(String),
+
+    #[error("Assinatura de identidade inválida")]
+    InvalidSignature,
+
+    #[error("Peer inesperado: identidade não corresponde à esperada")]
+    UnexpectedPeer,
+
+    #[error("Peer de outra rede: {0}")]
+    NetworkMismatch(String),
+
+    #[error("Falha ao decifrar frame")]
+    Decryption,
+
+    #[error("Frame fora de ordem ou repetido: esperado {expected}, recebido {received}")]
+    Replay { expected: u64, received: u64 },
+
+    #[error("Frame excede o tamanho máximo: {0} bytes")]
+    FrameTooLarge(usize),
+
+    #[error("Erro criptográfico: {0}")]
+    Crypto(String),
+
+    #[error("Payload comprimido inválido: {0}")]
+    Compression(String),
+}
+
+impl TransportError {
+    /// Comportamento do peer que o erro representa, para a pontuação de peers.
+    /// Erros de E/S e locais não são atribuídos ao peer.
+    pub fn misbehavior(&self) -> Option<Misbehavior> {
+        match self {
+            TransportError::Handshake(_)
+            | TransportError::InvalidSignature
+            | TransportError::UnexpectedPeer
+            | TransportError::Decryption
+            | TransportError::Replay { .. }
+            | TransportError::Compression(_) => Some(Misbehavior::ProtocolViolation),
+            TransportError::FrameTooLarge(_) => Some(Misbehavior::Spam),
+            // Um peer honesto de outra rede não é punido; a conexão só não se forma
+            TransportError::NetworkMismatch(_)
+            | TransportError::Io(_)
+            | TransportError::Crypto(_) => None,
+        }
+    }
+}
+
+impl From<oqs::Error> for TransportError {
+    fn from(err: oqs::Error) -> Self {
+        TransportError::Crypto(err.to_string())
+    }
+}
+
+impl From<crate::error::Error> for TransportError {
+    fn from(err: crate::error::Error) -> Self {
+        TransportError::Compression(err.to_string())
+    }
+}
+
+impl From<bincode::Error> for TransportError {
+    fn from(err: bincode::Error) -> Self {
+        TransportError::Handshake(err.to_string())
+    }
+}
+
+/// Identidade de longo prazo do nó (Dilithium5)
+pub struct NodeIdentity {
+    pub public_key: PublicKey,
+    secret_key: SecretKey,
+}
+
+impl NodeIdentity {
+    pub fn new(public_key: PublicKey, secret_key: SecretKey) -> Self {
+        Self {
+            public_key,
+            secret_key,
+        }
+    }
+
+    pub fn generate() -> Self {
+        let (public_key, secret_key) = dilithium::keypair();
+        Self::new(public_key, secret_key)
+    }
+
+    /// Lê a identidade gravada em `path`: chave pública seguida da secreta
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let invalid = || {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Arquivo de identidade inválido",
+            )
+        };
+
+        let mut bytes = std::fs::read(path)?;
+        if bytes.len() != dilithium5::public_key_bytes() + dilithium5::secret_key_bytes() {
+            bytes.zeroize();
+            return Err(invalid());
+        }
+        let (public_key, secret_key) = bytes.split_at(dilithium5::public_key_bytes());
+        let identity = PublicKey::from_bytes(public_key)
+            .and_then(|public_key| Ok(Self::new(public_key, SecretKey::from_bytes(secret_key)?)))
+            .map_err(|_| invalid());
+        bytes.zeroize();
+        identity
+    }
+
+    /// Lê a identidade gravada em `path` ou gera uma nova e a grava, com
+    /// permissão restrita ao dono em sistemas Unix
+    pub fn load_or_generate(path: &Path) -> std::io::Result<Self> {
+        if path.exists() {
+            return Self::load(path);
+        }
+
+        let identity = Self::generate();
+        identity.save(path)?;
+        Ok(identity)
+    }
+
+    /// Grava a identidade em um arquivo novo, no formato lido por `load`; nunca
+    /// sobrescreve um arquivo existente
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let mut bytes = self.public_key.as_bytes().to_vec();
+        bytes.extend_from_slice(self.secret_key.as_bytes());
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let written = options
+            .open(path)
+            .and_then(|mut file| file.write_all(&bytes));
+        bytes.zeroize();
+        written
+    }
+
+    /// Exporta a identidade em bloco armado (ver `wallet::keystore`)
+    pub fn export_armored(&self, passphrase: Option<&str>) -> Result<String, crate::error::Error> {
+        crate::wallet::export_keypair(&self.public_key, &self.secret_key, passphrase)
+    }
+
+    /// Assinatura destacada de uma mensagem arbitrária com a chave do nó
+    pub fn sign_message(&self, message: &[u8]) -> Vec<u8> {
+        dilithium5::detached_sign(message, &self.secret_key)
+            .as_bytes()
+            .to_vec()
+    }
+
+    fn sign(&self, transcript: &[u8; 32]) -> Vec<u8> {
+        self.sign_message(transcript)
+    }
+}
+
+/// Primeira mensagem (iniciador → respondedor)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandshakeInit {
+    pub version: u8,
+    pub genesis_hash: [u8; 32],
+    pub ephemeral_key: Vec<u8>,
+    pub nonce: [u8; 32],
+    /// IDs dos codecs de compressão aceitos pelo iniciador
+    pub codecs: Vec<u8>,
+}
+
+/// Segunda mensagem: encapsula para o efêmero do iniciador, escolhe o codec da
+/// sessão e se identifica
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandshakeResponse {
+    pub version: u8,
+    pub genesis_hash: [u8; 32],
+    /// ID do codec negociado entre os anunciados pelo iniciador
+    pub codec: u8,
+    pub ciphertext: Vec<u8>,
+    pub ephemeral_key: Vec<u8>,
+    pub identity: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+/// Terceira mensagem: encapsula para o efêmero do respondedor e se identifica
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandshakeFinish {
+    pub ciphertext: Vec<u8>,
+    pub identity: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+fn kem() -> Result<Kem, TransportError> {
+    Ok(entropy::kem(KemAlgorithm::Kyber512)?)
+}
+
+fn mix(previous: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(previous);
+    for part in parts {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+/// Recusa peers de outra versão do protocolo ou de outro gênesis
+fn check_network(
+    version: u8,
+    genesis_hash: &[u8; 32],
+    expected_genesis: &[u8; 32],
+) -> Result<(), TransportError> {
+    if version != PROTOCOL_VERSION {
+        return Err(TransportError::NetworkMismatch(format!(
+            "versão de protocolo {} (local {})",
+            version, PROTOCOL_VERSION
+        )));
+    }
+    if genesis_hash != expected_genesis {
+        return Err(TransportError::NetworkMismatch(format!(
+            "gênese {} (local {})",
+            hex::encode(genesis_hash),
+            hex::encode(expected_genesis)
+        )));
+    }
+    Ok(())
+}
+
+fn verify_identity(
+    identity: &[u8],
+    signature: &[u8],
+    transcript: &[u8; 32],
+    expected: Option<&PublicKey>,
+) -> Result<PublicKey, TransportError> {
+    let public_key =
+        PublicKey::from_bytes(identity).map_err(|_| TransportError::InvalidSignature)?;
+    if let Some(expected) = expected {
+        if expected.as_bytes() != identity {
+            return Err(TransportError::UnexpectedPeer);
+        }
+    }
+    let signature = dilithium5::DetachedSignature::from_bytes(signature)
+        .map_err(|_| TransportError::InvalidSignature)?;
+    dilithium5::verify_detached_signature(&signature, transcript, &public_key)
+        .map_err(|_| TransportError::InvalidSignature)?;
+    Ok(public_key)
+}
+
+fn decapsulate(
+    secret_key: &oqs::kem::SecretKey,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, TransportError> {
+    let kem = kem()?;
+    let ciphertext = kem
+        .ciphertext_from_bytes(ciphertext)
+        .ok_or_else(|| TransportError::Handshake("Ciphertext KEM inválido".to_string()))?;
+    Ok(kem.decapsulate(secret_key, ciphertext)?.into_vec())
+}
+
+fn encapsulate(public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>), TransportError> {
+    let kem = kem()?;
+    let public_key = kem
+        .public_key_from_bytes(public_key)
+        .ok_or_else(|| TransportError::Handshake("Chave KEM efêmera inválida".to_string()))?;
+    let (ciphertext, shared) = kem.encapsulate(public_key)?;
+    Ok((ciphertext.into_vec(), shared.into_vec()))
+}
+
+fn derive_key(label: &[u8], transcript: &[u8; 32], first: &[u8], second: &[u8]) -> secretbox::Key {
+    secretbox::Key(mix(label, &[transcript, first, second]))
+}
+
+/// Lado que abre a conexão
+pub struct Initiator {
+    ephemeral_secret: oqs::kem::SecretKey,
+    transcript: [u8; 32],
+    genesis_hash: [u8; 32],
+}
+
+impl Initiator {
+    /// Abre o handshake anunciando a rede do nó pelo hash do gênesis
+    pub fn start(genesis_hash: [u8; 32]) -> Result<(Self, HandshakeInit), TransportError> {
+        let (public_key, secret_key) = kem()?.keypair()?;
+        let init = HandshakeInit {
+            version: PROTOCOL_VERSION,
+            genesis_hash,
+            ephemeral_key: public_key.into_vec(),
+            nonce: entropy().array(),
+            codecs: Codec::supported_ids(),
+        };
+        let transcript = mix(PROTOCOL_NAME, &[&bincode::serialize(&init)?]);
+        Ok((
+            Initiator {
+                ephemeral_secret: secret_key,
+                transcript,
+                genesis_hash,
+            },
+            init,
+        ))
+    }
+
+    /// Autentica o respondedor e conclui o handshake do lado do iniciador
+    pub fn finish(
+        self,
+        identity: &NodeIdentity,
+        response: &HandshakeResponse,
+        expected_peer: Option<&PublicKey>,
+    ) -> Result<(SecureSession, HandshakeFinish), TransportError> {
+        check_network(response.version, &response.genesis_hash, &self.genesis_hash)?;
+        let transcript = mix(
+            &self.transcript,
+            &[
+                &[response.version],
+                &response.genesis_hash,
+                &[response.codec],
+                &response.ciphertext,
+                &response.ephemeral_key,
+                &response.identity,
+            ],
+        );
+        let peer = verify_identity(
+            &response.identity,
+            &response.signature,
+            &transcript,
+            expected_peer,
+        )?;
+        let codec = Codec::from_id(response.codec)
+            .ok()
+            .filter(|codec| SUPPORTED_CODECS.contains(codec))
+            .ok_or_else(|| {
+                TransportError::Handshake(format!("Codec {} não anunciado", response.codec))
+            })?;
+
+        let responder_secret = decapsulate(&self.ephemeral_secret, &response.ciphertext)?;
+        let (ciphertext, initiator_secret) = encapsulate(&response.ephemeral_key)?;
+        let own_identity = identity.public_key.as_bytes().to_vec();
+        let transcript = mix(
+            &transcript,
+            &[&response.signature, &ciphertext, &own_identity],
+        );
+        let finish = HandshakeFinish {
+            ciphertext,
+            identity: own_identity,
+            signature: identity.sign(&transcript),
+        };
+
+        let session = SecureSession::new(
+            derive_key(b"i2r", &transcript, &responder_secret, &initiator_secret),
+            derive_key(b"r2i", &transcript, &responder_secret, &initiator_secret),
+            peer,
+            transcript,
+            codec,
+        );
+        Ok((session, finish))
+    }
+}
+
+/// Lado que aceita a conexão
+pub struct Responder {
+    ephemeral_secret: oqs::kem::SecretKey,
+    responder_secret: Vec<u8>,
+    transcript: [u8; 32],
+    signature: Vec<u8>,
+    codec: Codec,
+}
+
+impl Responder {
+    /// Responde a um handshake vindo da mesma rede (`genesis_hash` local)
+    pub fn respond(
+        identity: &NodeIdentity,
+        genesis_hash: [u8; 32],
+        init: &HandshakeInit,
+    ) -> Result<(Self, HandshakeResponse), TransportError> {
+        check_network(init.version, &init.genesis_hash, &genesis_hash)?;
+
+        let transcript = mix(PROTOCOL_NAME, &[&bincode::serialize(init)?]);
+        let codec = compression::negotiate(SUPPORTED_CODECS, &init.codecs);
+        let (ciphertext, responder_secret) = encapsulate(&init.ephemeral_key)?;
+        let (public_key, secret_key) = kem()?.keypair()?;
+        let ephemeral_key = public_key.into_vec();
+        let own_identity = identity.public_key.as_bytes().to_vec();
+
+        let transcript = mix(
+            &transcript,
+            &[
+                &[PROTOCOL_VERSION],
+                &genesis_hash,
+                &[codec.id()],
+                &ciphertext,
+                &ephemeral_key,
+                &own_identity,
+            ],
+        );
+        let signature = identity.sign(&transcript);
+        let response = HandshakeResponse {
+            version: PROTOCOL_VERSION,
+            genesis_hash,
+            codec: codec.id(),
+            ciphertext,
+            ephemeral_key,
+            identity: own_identity,
+            signature: signature.clone(),
+        };
+
+        Ok((
+            Responder {
+                ephemeral_secret: secret_key,
+                responder_secret,
+                transcript,
+                signature,
+                codec,
+            },
+            response,
+        ))
+    }
+
+    /// Autentica o iniciador e conclui o handshake do lado do respondedor
+    pub fn finish(
+        self,
+        finish: &HandshakeFinish,
+        expected_peer: Option<&PublicKey>,
+    ) -> Result<SecureSession, TransportError> {
+        let transcript = mix(
+            &self.transcript,
+            &[&self.signature, &finish.ciphertext, &finish.identity],
+        );
+        let peer = verify_identity(
+            &finish.identity,
+            &finish.signature,
+            &transcript,
+            expected_peer,
+        )?;
+        let initiator_secret = decapsulate(&self.ephemeral_secret, &finish.ciphertext)?;
+
+        Ok(SecureSession::new(
+            derive_key(
+                b"r2i",
+                &transcript,
+                &self.responder_secret,
+                &initiator_secret,
+            ),
+            derive_key(
+                b"i2r",
+                &transcript,
+                &self.responder_secret,
+                &initiator_secret,
+            ),
+            peer,
+            transcript,
+            self.codec,
+        ))
+    }
+}
+
+/// Chaves de sessão derivadas do handshake. Cada frame leva um contador
+/// crescente, usado como nonce e para rejeitar frames repetidos ou reordenados.
+pub struct SecureSession {
+    send_key: secretbox::Key,
+    recv_key: secretbox::Key,
+    send_counter: u64,
+    recv_counter: u64,
+    peer: PublicKey,
+    session_id: [u8; 32],
+    codec: Codec,
+}
+
+fn frame_nonce(counter: u64) -> secretbox::Nonce {
+    let mut nonce = [0u8; secretbox::NONCEBYTES];
+    nonce[..COUNTER_LEN].copy_from_slice(&counter.to_be_bytes());
+    secretbox::Nonce(nonce)
+}
+
+impl SecureSession {
+    fn new(
+        send_key: secretbox::Key,
+        recv_key: secretbox::Key,
+        peer: PublicKey,
+        session_id: [u8; 32],
+        codec: Codec,
+    ) -> Self {
+        Self {
+            send_key,
+            recv_key,
+            send_counter: 0,
+            recv_counter: 0,
+            peer,
+            session_id,
+            codec,
+        }
+    }
+
+    /// Identidade autenticada do outro nó
+    pub fn peer(&self) -> &PublicKey {
+        &self.peer
+    }
+
+    /// Hash final do transcript, igual nos dois lados
+    pub fn session_id(&self) -> [u8; 32] {
+        self.session_id
+    }
+
+    /// Codec de compressão negociado no handshake
+    pub fn codec(&self) -> Codec {
+        self.codec
+    }
+
+    /// Descomprime um payload já decifrado, recusando codecs diferentes do
+    /// negociado no handshake
+    pub fn decompress(&self, payload: &[u8], max_size: usize) -> Result<Vec<u8>, TransportError> {
+        match payload.first() {
+            Some(&id) if id == self.codec.id() => Ok(compression::decode(payload, max_size)?),
+            Some(&id) => Err(TransportError::Compression(format!(
+                "Codec {} difere do negociado ({})",
+                id,
+                self.codec.id()
+            ))),
+            None => Err(TransportError::Compression("Payload vazio".to_string())),
+        }
+    }
+
+    pub fn seal(&mut self, plaintext: &[u8]) -> Vec<u8> {
+        let counter = self.send_counter;
+        self.send_counter += 1;
+
+        let mut frame = counter.to_be_bytes().to_vec();
+        frame.extend(secretbox::seal(
+            plaintext,
+            &frame_nonce(counter),
+            &self.send_key,
+        ));
+        frame
+    }
+
+    pub fn open(&mut self, frame: &[u8]) -> Result<Vec<u8>, TransportError> {
+        if frame.len() < COUNTER_LEN {
+            return Err(TransportError::Decryption);
+        }
+        let (counter, ciphertext) = frame.split_at(COUNTER_LEN);
+        let counter =
+            u64::from_be_bytes(counter.try_into().map_err(|_| TransportError::Decryption)?);
+        if counter != self.recv_counter {
+            return Err(TransportError::Replay {
+                expected: self.recv_counter,
+                received: counter,
+            });
+        }
+
+        let plaintext = secretbox::open(ciphertext, &frame_nonce(counter), &self.recv_key)
+            .map_err(|_| TransportError::Decryption)?;
+        self.recv_counter += 1;
+        Ok(plaintext)
+    }
+}
+
+async fn write_frame<S: AsyncWrite + Unpin>(
+    stream: &mut S,
+    payload: &[u8],
+) -> Result<(), TransportError> {
+    if payload.len() > MAX_FRAME_SIZE {
+        return Err(TransportError::FrameTooLarge(payload.len()));
+    }
+    stream
+        .write_all(&(payload.len() as u32).to_be_bytes())
+        .await?;
+    stream.write_all(payload).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+async fn read_frame<S: AsyncRead + Unpin>(stream: &mut S) -> Result<Vec<u8>, TransportError> {
+    let mut length = [0u8; 4];
+    stream.read_exact(&mut length).await?;
+    let length = u32::from_be_bytes(length) as usize;
+    if length > MAX_FRAME_SIZE {
+        return Err(TransportError::FrameTooLarge(length));
+    }
+    let mut payload = vec![0u8; length];
+    stream.read_exact(&mut payload).await?;
+    Ok(payload)
+}
+
+/// Conexão cifrada e autenticada sobre um stream (tipicamente TCP)
+pub struct SecureChannel<S> {
+    stream: S,
+    session: SecureSession,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> SecureChannel<S> {
+    /// Executa o handshake como iniciador, exigindo um peer do mesmo gênesis
+    pub async fn connect(
+        mut stream: S,
+        identity: &NodeIdentity,
+        genesis_hash: [u8; 32],
+        expected_peer: Option<&PublicKey>,
+    ) -> Result<Self, TransportError> {
+        let (initiator, init) = Initiator::start(genesis_hash)?;
+        write_frame(&mut stream, &bincode::serialize(&init)?).await?;
+
+        let response: HandshakeResponse = bincode::deserialize(&read_frame(&mut stream).await?)?;
+        let (session, finish) = initiator.finish(identity, &response, expected_peer)?;
+        write_frame(&mut stream, &bincode::serialize(&finish)?).await?;
+
+        Ok(Self { stream, session })
+    }
+
+    /// Executa o handshake como respondedor, exigindo um peer do mesmo gênesis
+    pub async fn accept(
+        mut stream: S,
+        identity: &NodeIdentity,
+        genesis_hash: [u8; 32],
+        expected_peer: Option<&PublicKey>,
+    ) -> Result<Self, TransportError> {
+        let init: HandshakeInit = bincode::deserialize(&read_frame(&mut stream).await?)?;
+        let (responder, response) = Responder::respond(identity, genesis_hash, &init)?;
+        write_frame(&mut stream, &bincode::serialize(&response)?).await?;
+
+        let finish: HandshakeFinish = bincode::deserialize(&read_frame(&mut stream).await?)?;
+        let session = responder.finish(&finish, expected_peer)?;
+
+        Ok(Self { stream, session })
+    }
+
+    pub fn peer(&self) -> &PublicKey {
+        self.session.peer()
+    }
+
+    pub fn session_id(&self) -> [u8; 32] {
+        self.session.session_id()
+    }
+
+    pub fn codec(&self) -> Codec {
+        self.session.codec()
+    }
+
+    /// Comprime `message` com o codec da sessão antes de cifrar
+    pub async fn send(&mut self, message: &[u8]) -> Result<(), TransportError> {
+        let payload = compression::encode(self.session.codec(), message)?;
+        let frame = self.session.seal(&payload);
+        write_frame(&mut self.stream, &frame).await
+    }
+
+    /// Decifra e descomprime com o codec da sessão; nada se expande além de
+    /// `MAX_FRAME_SIZE`
+    pub async fn recv(&mut self) -> Result<Vec<u8>, TransportError> {
+        let frame = read_frame(&mut self.stream).await?;
+        let payload = self.session.open(&frame)?;
+        self.session.decompress(&payload, MAX_FRAME_SIZE)
+    }
+}
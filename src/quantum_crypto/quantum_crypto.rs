@@ -1,4 +1,6 @@
 use crate::error::Error;
+use crate::utils::dilithium;
+use crate::utils::entropy;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use oqs::kem::{Algorithm as KemAlgorithm, Kem, PublicKeyRef};
 use oqs::sig::{Algorithm as SigAlgorithm, Sig};
@@ -47,7 +49,7 @@ pub trait PermissionValidator {
     fn validate_transaction_fee(&self, fee: &TransactionFee) -> Result<bool, OqsError>;
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct QuantumCryptoData {
     signature_scheme: String,
     public_key: String,
@@ -88,6 +90,12 @@ impl From<OqsError> for oqs::Error {
     }
 }
 
+impl From<OqsError> for Error {
+    fn from(err: OqsError) -> Self {
+        Error::CryptoError(err.to_string())
+    }
+}
+
 impl From<oqs::Error> for OqsError {
     fn from(err: oqs::Error) -> Self {
         match err {
@@ -103,14 +111,30 @@ impl<'de> Deserialize<'de> for QuantumCrypto {
         D: Deserializer<'de>,
     {
         let data = QuantumCryptoData::deserialize(deserializer)?;
+        QuantumCrypto::from_data(data).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Debug for QuantumCrypto {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("QuantumCrypto")
+            .field("signature_scheme", &self.data.signature_scheme)
+            .field("public_key", &self.data.public_key)
+            .field("signature", &self.data.signature)
+            .finish()
+    }
+}
 
-        let kem = Kem::new(KemAlgorithm::Kyber512).map_err(serde::de::Error::custom)?;
-        let sig = Sig::new(SigAlgorithm::Dilithium5).map_err(serde::de::Error::custom)?;
+// Implementação principal do QuantumCrypto
 
+impl QuantumCrypto {
+    /// Instância com os dados persistidos; os algoritmos são criados de novo e
+    /// permissões, nonces e logs começam vazios
+    fn from_data(data: QuantumCryptoData) -> Result<Self, OqsError> {
         Ok(QuantumCrypto {
             data,
-            kem,
-            sig,
+            kem: entropy::kem(KemAlgorithm::Kyber512)?,
+            sig: entropy::sig(SigAlgorithm::Dilithium5)?,
             permissions: Vec::new(),
             transaction_fee: TransactionFee {
                 amount: 0,
@@ -121,30 +145,21 @@ impl<'de> Deserialize<'de> for QuantumCrypto {
             logs_seguros: Mutex::new(Vec::new()),
         })
     }
-}
 
-impl Debug for QuantumCrypto {
-    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        f.debug_struct("QuantumCrypto")
-            .field("signature_scheme", &self.data.signature_scheme)
-            .field("public_key", &self.data.public_key)
-            .field("signature", &self.data.signature)
-            .finish()
+    /// Cópia dos dados persistidos, como a obtida ao serializar e desserializar
+    pub fn try_clone(&self) -> Result<Self, OqsError> {
+        Self::from_data(self.data.clone())
     }
-}
-
-// Implementação principal do QuantumCrypto
 
-impl QuantumCrypto {
     pub fn new() -> Result<Self, OqsError> {
-        let (public_key, secret_key) = dilithium5::keypair();
+        let (public_key, secret_key) = dilithium::keypair();
 
         if public_key.as_bytes().is_empty() || secret_key.as_bytes().is_empty() {
             return Err(OqsError::AlgorithmDisabled);
         }
 
-        let kem = Kem::new(KemAlgorithm::Kyber512)?;
-        let sig = Sig::new(SigAlgorithm::Dilithium5)?;
+        let kem = entropy::kem(KemAlgorithm::Kyber512)?;
+        let sig = entropy::sig(SigAlgorithm::Dilithium5)?;
 
         Ok(QuantumCrypto {
             data: QuantumCryptoData {
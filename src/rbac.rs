@@ -0,0 +1,135 @@
+use crate::constants::MAX_SIGNATURE_SIZE;
+use crate::error::Error;
+use crate::utils::address::derive_address;
+use crate::utils::clock::clock;
+use pqcrypto_dilithium::dilithium5::{self, PublicKey, SecretKey};
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Papéis administrativos atribuídos na cadeia.
+///
+/// Ao contrário dos níveis de acesso RPC, não formam uma hierarquia: cada chamada
+/// administrativa exige um papel específico e um endereço pode ter vários.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum AdminRole {
+    /// Atribui e revoga papéis
+    Admin,
+    /// Operação do nó: blocos, checkpoints e manutenção
+    Operator,
+    /// Leitura de logs de auditoria e dados sensíveis, sem poder de escrita
+    Auditor,
+    /// Emissão de tokens
+    Minter,
+}
+
+impl AdminRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AdminRole::Admin => "admin",
+            AdminRole::Operator => "operator",
+            AdminRole::Auditor => "auditor",
+            AdminRole::Minter => "minter",
+        }
+    }
+}
+
+impl fmt::Display for AdminRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Serialize)]
+struct SignableCredential<'a> {
+    subject: &'a str,
+    role: AdminRole,
+    public_key: &'a [u8],
+    issued_at: i64,
+    expires_at: i64,
+}
+
+/// Credencial apresentada para exercer um papel: assinada pela própria chave do
+/// titular, prova o controle de `subject` (endereço derivado de `public_key`).
+///
+/// A credencial sozinha não concede nada; quem a recebe confere também se o papel
+/// está atribuído a `subject` na cadeia.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct RoleCredential {
+    pub subject: String,
+    pub role: AdminRole,
+    pub public_key: Vec<u8>,
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub signature: Vec<u8>,
+}
+
+impl RoleCredential {
+    /// Emite uma credencial para o papel `role`, válida por `ttl_secs` segundos
+    pub fn issue(
+        role: AdminRole,
+        ttl_secs: i64,
+        public_key: &PublicKey,
+        secret_key: &SecretKey,
+    ) -> Result<Self, Error> {
+        let now = clock().now_secs();
+        let public_key = public_key.as_bytes().to_vec();
+        let mut credential = RoleCredential {
+            subject: derive_address(&public_key),
+            role,
+            public_key,
+            issued_at: now,
+            expires_at: now.saturating_add(ttl_secs),
+            signature: Vec::new(),
+        };
+        let data = credential.signing_data()?;
+        credential.signature = dilithium5::detached_sign(&data, secret_key)
+            .as_bytes()
+            .to_vec();
+        Ok(credential)
+    }
+
+    fn signing_data(&self) -> Result<Vec<u8>, Error> {
+        bincode::serialize(&SignableCredential {
+            subject: &self.subject,
+            role: self.role,
+            public_key: &self.public_key,
+            issued_at: self.issued_at,
+            expires_at: self.expires_at,
+        })
+        .map_err(|_| Error::InvalidFormat("Credencial não serializável".to_string()))
+    }
+
+    /// Verifica assinatura, vínculo entre chave e endereço e validade no instante atual
+    pub fn verify(&self) -> Result<(), Error> {
+        self.verify_at(clock().now_secs())
+    }
+
+    pub fn verify_at(&self, now: i64) -> Result<(), Error> {
+        if self.subject != derive_address(&self.public_key) {
+            return Err(Error::Unauthorized(
+                "Credencial não pertence à chave que a assina".to_string(),
+            ));
+        }
+        if now < self.issued_at || now >= self.expires_at {
+            return Err(Error::Unauthorized(format!(
+                "Credencial de {} fora da validade",
+                self.role
+            )));
+        }
+        if self.signature.len() > MAX_SIGNATURE_SIZE {
+            return Err(Error::InvalidSignature);
+        }
+
+        let public_key =
+            PublicKey::from_bytes(&self.public_key).map_err(|_| Error::InvalidPublicKey)?;
+        let signature = dilithium5::DetachedSignature::from_bytes(&self.signature)
+            .map_err(|_| Error::InvalidSignature)?;
+        dilithium5::verify_detached_signature(&signature, &self.signing_data()?, &public_key)
+            .map_err(|_| Error::InvalidSignature)
+    }
+}
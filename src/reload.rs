@@ -0,0 +1,73 @@
+use crate::blockchain::SharedBlockchain;
+use crate::config::{reloadable_changes, RuntimeSettings, Settings};
+use crate::rpc::RpcRateLimiter;
+use log::{info, warn};
+use parking_lot::Mutex;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Recarrega, com o nó em execução, as configurações que não afetam o consenso.
+///
+/// O arquivo é relido e validado por inteiro antes de qualquer efeito: se for
+/// inválido ou mudar algo que exige reinício, o nó segue com a configuração
+/// anterior. Disparado por SIGHUP ou pela chamada RPC `reload_config`.
+pub struct ConfigReloader {
+    current: Mutex<Settings>,
+    blockchain: SharedBlockchain,
+    rpc_limiter: Arc<RpcRateLimiter>,
+}
+
+impl ConfigReloader {
+    pub fn new(
+        settings: Settings,
+        blockchain: SharedBlockchain,
+        rpc_limiter: Arc<RpcRateLimiter>,
+    ) -> Self {
+        Self {
+            current: Mutex::new(settings),
+            blockchain,
+            rpc_limiter,
+        }
+    }
+
+    /// Configurações em uso, com os valores do último recarregamento aceito
+    pub fn settings(&self) -> Settings {
+        self.current.lock().clone()
+    }
+
+    /// Relê o arquivo de onde as configurações vieram
+    pub fn reload(&self) -> Result<RuntimeSettings, String> {
+        let path: PathBuf = self
+            .current
+            .lock()
+            .source
+            .clone()
+            .ok_or("Configurações não vieram de um arquivo; nada a recarregar")?;
+        let next = Settings::from_file(&path)?;
+        self.apply(next)
+            .inspect(|_| info!("Configuração recarregada de {}", path.display()))
+            .inspect_err(|e| {
+                warn!(
+                    "Recarregamento de {} recusado, mantendo a configuração anterior: {}",
+                    path.display(),
+                    e
+                )
+            })
+    }
+
+    /// Valida `next` contra a configuração em uso e aplica os campos recarregáveis
+    pub fn apply(&self, next: Settings) -> Result<RuntimeSettings, String> {
+        let mut current = self.current.lock();
+        let runtime = reloadable_changes(&current, &next)?;
+        let level = runtime.log_level_filter()?;
+
+        crate::logging::set_level(level);
+        if self.rpc_limiter.config() != runtime.rpc {
+            self.rpc_limiter.configure(runtime.rpc);
+        }
+        self.blockchain
+            .write(|blockchain| blockchain.limits.fee_floor = runtime.fee_floor);
+        runtime.write_to(&mut current);
+        Ok(runtime)
+    }
+}
@@ -0,0 +1,176 @@
+use crate::error::Error;
+use crate::utils::clock::{clock, SharedClock};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use pqcrypto_dilithium::dilithium5::{self, PublicKey, SecretKey};
+use pqcrypto_traits::sign::DetachedSignature as _;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Nível de acesso às chamadas RPC, do menos ao mais privilegiado
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Consultas de leitura, sem token
+    Public,
+    /// Submissão de transações assinadas
+    Wallet,
+    /// Operação do nó (blocos, checkpoints)
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::Public => "public",
+            Role::Wallet => "wallet",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+/// Conteúdo assinado de um token de acesso
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub role: Role,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TokenHeader {
+    alg: String,
+    typ: String,
+}
+
+const TOKEN_ALGORITHM: &str = "DILITHIUM5";
+
+/// Emissão e verificação de tokens de acesso no formato `header.claims.assinatura`
+/// (base64url, como um JWT), assinados com a chave Dilithium5 do nó.
+///
+/// Um nó que apenas verifica tokens emitidos por outro não precisa da chave secreta.
+pub struct RpcAuth {
+    public_key: PublicKey,
+    secret_key: Option<SecretKey>,
+    clock: SharedClock,
+}
+
+impl RpcAuth {
+    pub fn new(public_key: PublicKey, secret_key: SecretKey) -> Self {
+        Self {
+            public_key,
+            secret_key: Some(secret_key),
+            clock: clock().source(),
+        }
+    }
+
+    pub fn verifier(public_key: PublicKey) -> Self {
+        Self {
+            public_key,
+            secret_key: None,
+            clock: clock().source(),
+        }
+    }
+
+    /// Substitui o relógio que data os tokens emitidos e confere sua expiração
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn now_secs(&self) -> i64 {
+        self.clock.unix_millis() / 1000
+    }
+
+    /// Emite um token para `subject` válido por `ttl_secs` segundos
+    pub fn issue(&self, subject: &str, role: Role, ttl_secs: i64) -> Result<String, Error> {
+        let secret_key = self
+            .secret_key
+            .as_ref()
+            .ok_or_else(|| Error::Unauthorized("Nó sem chave para emitir tokens".to_string()))?;
+
+        let now = self.now_secs();
+        let header = TokenHeader {
+            alg: TOKEN_ALGORITHM.to_string(),
+            typ: "JWT".to_string(),
+        };
+        let claims = Claims {
+            sub: subject.to_string(),
+            role,
+            iat: now,
+            exp: now.saturating_add(ttl_secs),
+        };
+
+        let signing_input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(serde_json::to_vec(&header)?),
+            URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims)?)
+        );
+        let signature = dilithium5::detached_sign(signing_input.as_bytes(), secret_key);
+        Ok(format!(
+            "{}.{}",
+            signing_input,
+            URL_SAFE_NO_PAD.encode(signature.as_bytes())
+        ))
+    }
+
+    /// Verifica assinatura e validade do token, devolvendo as claims
+    pub fn verify(&self, token: &str) -> Result<Claims, Error> {
+        let invalid = || Error::Unauthorized("Token malformado".to_string());
+
+        let (signing_input, signature) = token.rsplit_once('.').ok_or_else(invalid)?;
+        let (header, claims) = signing_input.split_once('.').ok_or_else(invalid)?;
+
+        let header: TokenHeader =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header).map_err(|_| invalid())?)
+                .map_err(|_| invalid())?;
+        if header.alg != TOKEN_ALGORITHM {
+            return Err(Error::Unauthorized(format!(
+                "Algoritmo de token não suportado: {}",
+                header.alg
+            )));
+        }
+
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| invalid())?;
+        let signature =
+            dilithium5::DetachedSignature::from_bytes(&signature).map_err(|_| invalid())?;
+        dilithium5::verify_detached_signature(
+            &signature,
+            signing_input.as_bytes(),
+            &self.public_key,
+        )
+        .map_err(|_| Error::Unauthorized("Assinatura do token inválida".to_string()))?;
+
+        let claims: Claims =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(claims).map_err(|_| invalid())?)
+                .map_err(|_| invalid())?;
+        if claims.exp <= self.now_secs() {
+            return Err(Error::Unauthorized("Token expirado".to_string()));
+        }
+
+        Ok(claims)
+    }
+
+    /// Confere se o token dá acesso a uma chamada que exige `required`
+    pub fn authorize(&self, token: Option<&str>, required: Role) -> Result<Role, Error> {
+        if required == Role::Public {
+            return Ok(Role::Public);
+        }
+
+        let token = token.ok_or_else(|| {
+            Error::Unauthorized(format!("Chamada exige papel {}", required.as_str()))
+        })?;
+        let claims = self.verify(token)?;
+        if claims.role < required {
+            return Err(Error::Unauthorized(format!(
+                "Papel {} insuficiente; exigido {}",
+                claims.role.as_str(),
+                required.as_str()
+            )));
+        }
+
+        Ok(claims.role)
+    }
+}
@@ -0,0 +1,69 @@
+use super::types::{StorageHealth, SyncState};
+use crate::database;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// Sinais do nó que não estão na cadeia: peers conectados, maior altura anunciada
+/// por eles e o banco SQLite a verificar.
+///
+/// A camada de rede atualiza os contadores; o RPC só os lê em `get_node_status`
+/// e `/health`.
+#[derive(Debug, Default)]
+pub struct NodeMonitor {
+    peers: AtomicUsize,
+    best_peer_height: AtomicU64,
+    storage_path: Option<PathBuf>,
+}
+
+impl NodeMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Passa a verificar a integridade do banco em `path` a cada consulta
+    pub fn with_storage(mut self, path: impl Into<PathBuf>) -> Self {
+        self.storage_path = Some(path.into());
+        self
+    }
+
+    pub fn set_peers(&self, peers: usize) {
+        self.peers.store(peers, Ordering::Relaxed);
+    }
+
+    pub fn peers(&self) -> usize {
+        self.peers.load(Ordering::Relaxed)
+    }
+
+    /// Registra a altura anunciada por um peer; só a maior é mantida
+    pub fn observe_peer_height(&self, height: u64) {
+        self.best_peer_height.fetch_max(height, Ordering::Relaxed);
+    }
+
+    pub fn sync_state(&self, local_height: u64) -> SyncState {
+        let best = self.best_peer_height.load(Ordering::Relaxed);
+        if best > local_height {
+            SyncState::Syncing {
+                target_height: best,
+            }
+        } else {
+            SyncState::Synced
+        }
+    }
+
+    /// `None` sem banco configurado; um banco ilegível conta como não íntegro
+    pub fn storage_health(&self) -> Option<StorageHealth> {
+        self.storage_path.as_deref().map(check_storage)
+    }
+}
+
+fn check_storage(path: &Path) -> StorageHealth {
+    let (healthy, detail) = match database::integrity_check(path) {
+        Ok(report) => (report == ["ok"], report.join("; ")),
+        Err(e) => (false, e.to_string()),
+    };
+    StorageHealth {
+        path: path.display().to_string(),
+        healthy,
+        detail,
+    }
+}
@@ -0,0 +1,22 @@
+pub mod auth;
+pub mod health;
+pub mod openapi;
+pub mod rate_limit;
+pub mod router;
+pub mod service;
+pub mod types;
+
+pub use auth::{Claims, Role, RpcAuth};
+pub use health::NodeMonitor;
+pub use openapi::{openapi_document, RpcMethod, HEALTH_PATH, METHODS, RPC_PATH_PREFIX};
+pub use rate_limit::RpcRateLimiter;
+pub use router::{ChainRouter, CHAIN_PATH_PREFIX};
+pub use service::RpcService;
+pub use types::{
+    AccountRequest, AccountResponse, BalanceRequest, BalanceResponse, BlockHashRequest,
+    BlockResponse, HealthResponse, HeightRequest, HeightResponse, NameRequest, NameResponse,
+    NodeStatus, ProofRequest, ReportFormat, RpcErrorResponse, StorageHealth, SubmitBlockResponse,
+    SubmitResult, SubmitTransactionsRequest, SubmitTransactionsResponse, SyncState, TokenRequest,
+    TokenResponse, TransactionRequest, ValidatorReportRequest, ValidatorReportResponse,
+    ValidatorRequest, ValidatorSetResponse, ValidatorStatus,
+};
@@ -0,0 +1,317 @@
+use super::auth::Role;
+use super::types::{
+    AccountRequest, AccountResponse, BalanceRequest, BalanceResponse, BlockHashRequest,
+    BlockResponse, HealthResponse, HeightRequest, HeightResponse, NameRequest, NameResponse,
+    NodeStatus, ProofRequest, RpcErrorResponse, SubmitBlockResponse, SubmitResult,
+    SubmitTransactionsRequest, SubmitTransactionsResponse, TokenRequest, TokenResponse,
+    TransactionRequest, ValidatorReportRequest, ValidatorReportResponse, ValidatorRequest,
+    ValidatorSetResponse, ValidatorStatus,
+};
+use crate::blockchain::{
+    AccountNonce, Block, HistoricalState, InclusionProof, RejectionReason, SimulationResult,
+    TransactionLifecycle, TransactionReceipt, TransactionRecord,
+};
+use crate::config::RuntimeSettings;
+use crate::rbac::AdminRole;
+use crate::transaction::{Operation, Transaction};
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde_json::{json, Map, Value};
+
+/// Prefixo das rotas HTTP das chamadas RPC
+pub const RPC_PATH_PREFIX: &str = "/rpc/";
+/// Rota de verificação para balanceadores de carga, fora do prefixo RPC e sem
+/// autenticação (ver `RpcService::health`)
+pub const HEALTH_PATH: &str = "/health";
+
+/// Descrição de uma chamada RPC e dos seus tipos de entrada e saída
+pub struct RpcMethod {
+    pub name: &'static str,
+    pub summary: &'static str,
+    /// Papel mínimo exigido do token de acesso
+    pub role: Role,
+    /// Papel administrativo exigido na cadeia quando o serviço confere credenciais
+    pub permission: Option<AdminRole>,
+    request: Option<fn(&mut SchemaGenerator) -> Schema>,
+    response: fn(&mut SchemaGenerator) -> Schema,
+}
+
+impl RpcMethod {
+    pub fn path(&self) -> String {
+        format!("{}{}", RPC_PATH_PREFIX, self.name)
+    }
+
+    pub fn find(name: &str) -> Option<&'static RpcMethod> {
+        METHODS.iter().find(|method| method.name == name)
+    }
+}
+
+fn schema_for<T: JsonSchema>(gen: &mut SchemaGenerator) -> Schema {
+    gen.subschema_for::<T>()
+}
+
+/// Chamadas expostas pelo nó; `RpcService::handle` atende exatamente esta lista
+pub const METHODS: &[RpcMethod] = &[
+    RpcMethod {
+        name: "get_node_status",
+        summary: "Sincronização, topo da cadeia, peers, mempool, finalidade e banco de dados",
+        role: Role::Public,
+        permission: None,
+        request: None,
+        response: schema_for::<NodeStatus>,
+    },
+    RpcMethod {
+        name: "get_height",
+        summary: "Altura atual e hash do último bloco",
+        role: Role::Public,
+        permission: None,
+        request: None,
+        response: schema_for::<HeightResponse>,
+    },
+    RpcMethod {
+        name: "get_block",
+        summary: "Bloco na altura informada, com sua finalidade",
+        role: Role::Public,
+        permission: None,
+        request: Some(schema_for::<HeightRequest>),
+        response: schema_for::<BlockResponse>,
+    },
+    RpcMethod {
+        name: "get_block_by_hash",
+        summary: "Bloco com o hash informado, com sua finalidade",
+        role: Role::Public,
+        permission: None,
+        request: Some(schema_for::<BlockHashRequest>),
+        response: schema_for::<BlockResponse>,
+    },
+    RpcMethod {
+        name: "get_transaction",
+        summary: "Transação pelo txid, confirmada ou no mempool, com sua finalidade",
+        role: Role::Public,
+        permission: None,
+        request: Some(schema_for::<TransactionRequest>),
+        response: schema_for::<TransactionRecord>,
+    },
+    RpcMethod {
+        name: "get_transaction_status",
+        summary:
+            "Etapa do ciclo de vida de uma transação: pendente, incluída, finalizada ou descartada",
+        role: Role::Public,
+        permission: None,
+        request: Some(schema_for::<TransactionRequest>),
+        response: schema_for::<TransactionLifecycle>,
+    },
+    RpcMethod {
+        name: "get_rejection",
+        summary: "Motivo da última rejeição de uma transação pelo nó",
+        role: Role::Admin,
+        permission: Some(AdminRole::Operator),
+        request: Some(schema_for::<TransactionRequest>),
+        response: schema_for::<RejectionReason>,
+    },
+    RpcMethod {
+        name: "get_balance",
+        summary: "Saldo de um endereço em um token",
+        role: Role::Public,
+        permission: None,
+        request: Some(schema_for::<BalanceRequest>),
+        response: schema_for::<BalanceResponse>,
+    },
+    RpcMethod {
+        name: "get_account",
+        summary: "Saldo, nonce e transações pendentes de um endereço, do mesmo instante",
+        role: Role::Public,
+        permission: None,
+        request: Some(schema_for::<BalanceRequest>),
+        response: schema_for::<AccountResponse>,
+    },
+    RpcMethod {
+        name: "get_account_nonce",
+        summary: "Nonces confirmado e pendente de um endereço, com itens a reenviar",
+        role: Role::Public,
+        permission: None,
+        request: Some(schema_for::<AccountRequest>),
+        response: schema_for::<AccountNonce>,
+    },
+    RpcMethod {
+        name: "get_token",
+        summary: "Metadados e supply total de um token",
+        role: Role::Public,
+        permission: None,
+        request: Some(schema_for::<TokenRequest>),
+        response: schema_for::<TokenResponse>,
+    },
+    RpcMethod {
+        name: "resolve_name",
+        summary: "Dono atual de um nome do registro, se ainda não expirou",
+        role: Role::Public,
+        permission: None,
+        request: Some(schema_for::<NameRequest>),
+        response: schema_for::<NameResponse>,
+    },
+    RpcMethod {
+        name: "get_proof",
+        summary: "Prova SPV de inclusão de uma transação",
+        role: Role::Public,
+        permission: None,
+        request: Some(schema_for::<ProofRequest>),
+        response: schema_for::<InclusionProof>,
+    },
+    RpcMethod {
+        name: "get_state_at",
+        summary: "Estado histórico após a altura informada (nós de arquivo)",
+        role: Role::Public,
+        permission: None,
+        request: Some(schema_for::<HeightRequest>),
+        response: schema_for::<HistoricalState>,
+    },
+    RpcMethod {
+        name: "get_receipts",
+        summary: "Recibos das transações do bloco (nós de arquivo)",
+        role: Role::Public,
+        permission: None,
+        request: Some(schema_for::<HeightRequest>),
+        response: schema_for::<Vec<TransactionReceipt>>,
+    },
+    RpcMethod {
+        name: "get_validators",
+        summary: "Validadores ativos com stake, reputação, banimento e desempenho recente",
+        role: Role::Public,
+        permission: None,
+        request: None,
+        response: schema_for::<ValidatorSetResponse>,
+    },
+    RpcMethod {
+        name: "get_validator",
+        summary: "Stake, reputação, banimento e desempenho recente de um validador",
+        role: Role::Public,
+        permission: None,
+        request: Some(schema_for::<ValidatorRequest>),
+        response: schema_for::<ValidatorStatus>,
+    },
+    RpcMethod {
+        name: "get_validator_report",
+        summary: "Desempenho por época: blocos propostos e perdidos, votos, trajetória de \
+                  reputação, recompensas e penalidades, em JSON ou CSV",
+        role: Role::Public,
+        permission: None,
+        request: Some(schema_for::<ValidatorReportRequest>),
+        response: schema_for::<ValidatorReportResponse>,
+    },
+    RpcMethod {
+        name: "submit_transactions",
+        summary: "Submete um lote de transações assinadas",
+        role: Role::Wallet,
+        permission: None,
+        request: Some(schema_for::<SubmitTransactionsRequest>),
+        response: schema_for::<SubmitTransactionsResponse>,
+    },
+    RpcMethod {
+        name: "submit_operation",
+        summary: "Submete uma operação assinada (tokens, papéis, políticas)",
+        role: Role::Wallet,
+        permission: None,
+        request: Some(schema_for::<Operation>),
+        response: schema_for::<SubmitResult>,
+    },
+    RpcMethod {
+        name: "simulate_transaction",
+        summary: "Executa uma transação assinada sobre uma cópia do estado, sem confirmá-la",
+        role: Role::Wallet,
+        permission: None,
+        request: Some(schema_for::<Transaction>),
+        response: schema_for::<SimulationResult>,
+    },
+    RpcMethod {
+        name: "reload_config",
+        summary: "Relê o arquivo de configuração e aplica os ajustes que dispensam reinício",
+        role: Role::Admin,
+        permission: Some(AdminRole::Operator),
+        request: None,
+        response: schema_for::<RuntimeSettings>,
+    },
+    RpcMethod {
+        name: "submit_block",
+        summary: "Anexa um bloco à cadeia",
+        role: Role::Admin,
+        permission: Some(AdminRole::Operator),
+        request: Some(schema_for::<Block>),
+        response: schema_for::<SubmitBlockResponse>,
+    },
+];
+
+/// Documento OpenAPI 3.0 com todas as chamadas de `METHODS`
+pub fn openapi_document() -> Value {
+    let mut gen = SchemaSettings::openapi3().into_generator();
+    let error_schema = schema_for::<RpcErrorResponse>(&mut gen);
+
+    let mut paths = Map::new();
+    for method in METHODS {
+        let mut operation = json!({
+            "operationId": method.name,
+            "summary": method.summary,
+            "responses": {
+                "200": {
+                    "description": "Sucesso",
+                    "content": { "application/json": { "schema": (method.response)(&mut gen) } }
+                },
+                "default": {
+                    "description": "Erro",
+                    "content": { "application/json": { "schema": error_schema.clone() } }
+                }
+            }
+        });
+        if method.role != Role::Public {
+            operation["security"] = json!([{ "bearerAuth": [] }]);
+            operation["x-required-role"] = json!(method.role.as_str());
+        }
+        if let Some(permission) = method.permission {
+            operation["x-required-permission"] = json!(permission.as_str());
+        }
+        if let Some(request) = method.request {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": { "application/json": { "schema": request(&mut gen) } }
+            });
+        }
+        paths.insert(method.path(), json!({ "post": operation }));
+    }
+    let health = schema_for::<HealthResponse>(&mut gen);
+    paths.insert(
+        HEALTH_PATH.to_string(),
+        json!({ "get": {
+            "operationId": "health",
+            "summary": "Verificação de saúde do nó para balanceadores de carga",
+            "responses": {
+                "200": {
+                    "description": "Nó sincronizado e banco íntegro",
+                    "content": { "application/json": { "schema": health.clone() } }
+                },
+                "503": {
+                    "description": "Nó indisponível; `problems` traz os motivos",
+                    "content": { "application/json": { "schema": health } }
+                }
+            }
+        } }),
+    );
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Kybelith RPC",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": {
+            "schemas": gen.take_definitions(),
+            "securitySchemes": {
+                "bearerAuth": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "bearerFormat": "JWT (Dilithium5)",
+                }
+            }
+        },
+    })
+}
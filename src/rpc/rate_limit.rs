@@ -0,0 +1,61 @@
+use crate::config::RpcConfig;
+use crate::error::Error;
+use crate::utils::clock::clock;
+use parking_lot::Mutex;
+
+#[derive(Debug)]
+struct Window {
+    config: RpcConfig,
+    started_at: i64,
+    count: u32,
+}
+
+/// Limite de chamadas RPC externas em janelas fixas, compartilhado por todo o
+/// nó. A configuração pode ser trocada com o nó em execução.
+#[derive(Debug)]
+pub struct RpcRateLimiter {
+    window: Mutex<Window>,
+}
+
+impl RpcRateLimiter {
+    pub fn new(config: RpcConfig) -> Self {
+        Self {
+            window: Mutex::new(Window {
+                config,
+                started_at: clock().now_secs(),
+                count: 0,
+            }),
+        }
+    }
+
+    pub fn config(&self) -> RpcConfig {
+        self.window.lock().config
+    }
+
+    /// Troca o limite e recomeça a contagem
+    pub fn configure(&self, config: RpcConfig) {
+        let mut window = self.window.lock();
+        window.config = config;
+        window.started_at = clock().now_secs();
+        window.count = 0;
+    }
+
+    /// Conta uma chamada, falhando se a janela atual já estiver cheia
+    pub fn check(&self) -> Result<(), Error> {
+        let mut window = self.window.lock();
+        let limit = window.config.max_requests_per_window;
+        if limit == 0 {
+            return Ok(());
+        }
+        let now = clock().now_secs();
+        if now - window.started_at >= window.config.window_secs as i64 {
+            window.started_at = now;
+            window.count = 0;
+        }
+        if window.count >= limit {
+            return Err(Error::RateLimited(limit));
+        }
+        window.count += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use super::openapi::RPC_PATH_PREFIX;
+use super::service::RpcService;
+use crate::error::Error;
+use crate::rbac::RoleCredential;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Prefixo das rotas HTTP que escolhem a cadeia: `/chains/{chain_id}/rpc/{método}`.
+/// As rotas sem esse prefixo vão para a cadeia padrão.
+pub const CHAIN_PATH_PREFIX: &str = "/chains/";
+
+/// Encaminha chamadas RPC para o serviço da cadeia indicada quando o processo
+/// hospeda mais de uma. Cada serviço mantém os próprios limites e autenticação.
+#[derive(Clone)]
+pub struct ChainRouter {
+    services: BTreeMap<String, RpcService>,
+    default_chain: String,
+}
+
+impl ChainRouter {
+    /// Roteador com uma única cadeia, que passa a ser a padrão
+    pub fn new(chain_id: impl Into<String>, service: RpcService) -> Self {
+        let chain_id = chain_id.into();
+        let mut services = BTreeMap::new();
+        services.insert(chain_id.clone(), service);
+        Self {
+            services,
+            default_chain: chain_id,
+        }
+    }
+
+    /// Acrescenta uma cadeia; um `chain_id` repetido substitui o serviço anterior
+    pub fn with_chain(mut self, chain_id: impl Into<String>, service: RpcService) -> Self {
+        self.services.insert(chain_id.into(), service);
+        self
+    }
+
+    pub fn default_chain(&self) -> &str {
+        &self.default_chain
+    }
+
+    pub fn chain_ids(&self) -> impl Iterator<Item = &str> {
+        self.services.keys().map(String::as_str)
+    }
+
+    /// Serviço da cadeia `chain_id`, ou da padrão quando `None`
+    pub fn service(&self, chain_id: Option<&str>) -> Result<&RpcService, Error> {
+        let chain_id = chain_id.unwrap_or(&self.default_chain);
+        self.services
+            .get(chain_id)
+            .ok_or_else(|| Error::InvalidInput(format!("Cadeia desconhecida: {}", chain_id)))
+    }
+
+    /// Resolve uma rota HTTP no serviço da cadeia e no nome do método
+    pub fn route<'a>(&self, path: &'a str) -> Result<(&RpcService, &'a str), Error> {
+        let unknown = || Error::InvalidInput(format!("Rota RPC desconhecida: {}", path));
+        let (chain_id, rest) = match path.strip_prefix(CHAIN_PATH_PREFIX) {
+            Some(rest) => {
+                let slash = rest.find('/').ok_or_else(unknown)?;
+                (Some(&rest[..slash]), &rest[slash..])
+            }
+            None => (None, path),
+        };
+        let method = rest
+            .strip_prefix(RPC_PATH_PREFIX)
+            .filter(|method| !method.is_empty())
+            .ok_or_else(unknown)?;
+        Ok((self.service(chain_id)?, method))
+    }
+
+    pub fn call(
+        &self,
+        chain_id: Option<&str>,
+        method: &str,
+        token: Option<&str>,
+        request: Value,
+    ) -> Result<Value, Error> {
+        self.service(chain_id)?.call(method, token, request)
+    }
+
+    /// Como `call`, apresentando uma credencial de papel administrativo
+    pub fn call_with_credential(
+        &self,
+        chain_id: Option<&str>,
+        method: &str,
+        token: Option<&str>,
+        credential: Option<&RoleCredential>,
+        request: Value,
+    ) -> Result<Value, Error> {
+        self.service(chain_id)?
+            .call_with_credential(method, token, credential, request)
+    }
+}
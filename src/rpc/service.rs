@@ -0,0 +1,441 @@
+use super::auth::{Role, RpcAuth};
+use super::health::NodeMonitor;
+use super::openapi::{openapi_document, RpcMethod, METHODS};
+use super::rate_limit::RpcRateLimiter;
+use super::types::{
+    AccountRequest, AccountResponse, BalanceRequest, BalanceResponse, BlockHashRequest,
+    BlockResponse, HealthResponse, HeightRequest, HeightResponse, NameRequest, NameResponse,
+    NodeStatus, ProofRequest, ReportFormat, SubmitBlockResponse, SubmitResult,
+    SubmitTransactionsRequest, SubmitTransactionsResponse, SyncState, TokenRequest, TokenResponse,
+    TransactionRequest, ValidatorReportRequest, ValidatorReportResponse, ValidatorRequest,
+    ValidatorSetResponse, ValidatorStatus,
+};
+use crate::blockchain::{Block, SharedBlockchain};
+use crate::consensus::QuantumFlexConsensus;
+use crate::error::{Error, ErrorCode};
+use crate::rbac::RoleCredential;
+use crate::reload::ConfigReloader;
+use crate::transaction::{Operation, Transaction};
+use crate::utils::name::validate_name;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Atende as chamadas RPC sobre o handle compartilhado da blockchain.
+///
+/// Parâmetros e respostas trafegam como JSON; os formatos estão descritos no
+/// documento devolvido por `openapi`. Sem `RpcAuth` configurado, apenas as
+/// chamadas públicas são aceitas por `call`.
+///
+/// Com `with_role_checks`, as chamadas administrativas exigem também uma
+/// credencial do papel correspondente atribuído na cadeia.
+///
+/// As consultas de validadores (`get_validators`, `get_validator`) exigem o
+/// consenso do nó, ligado com `with_consensus`.
+#[derive(Clone)]
+pub struct RpcService {
+    blockchain: SharedBlockchain,
+    auth: Option<Arc<RpcAuth>>,
+    role_checks: bool,
+    consensus: Option<Arc<QuantumFlexConsensus>>,
+    monitor: Arc<NodeMonitor>,
+    rate_limiter: Option<Arc<RpcRateLimiter>>,
+    reloader: Option<Arc<ConfigReloader>>,
+}
+
+fn params<T: DeserializeOwned>(value: Value) -> Result<T, Error> {
+    Ok(serde_json::from_value(value)?)
+}
+
+fn reply<T: Serialize>(value: T) -> Result<Value, Error> {
+    Ok(serde_json::to_value(value)?)
+}
+
+impl RpcService {
+    pub fn new(blockchain: SharedBlockchain) -> Self {
+        Self {
+            blockchain,
+            auth: None,
+            role_checks: false,
+            consensus: None,
+            monitor: Arc::default(),
+            rate_limiter: None,
+            reloader: None,
+        }
+    }
+
+    pub fn with_auth(blockchain: SharedBlockchain, auth: RpcAuth) -> Self {
+        Self {
+            blockchain,
+            auth: Some(Arc::new(auth)),
+            role_checks: false,
+            consensus: None,
+            monitor: Arc::default(),
+            rate_limiter: None,
+            reloader: None,
+        }
+    }
+
+    /// Passa a exigir, nas chamadas com `permission`, uma credencial do papel
+    /// atribuído na cadeia (implantações com controle de acesso por papel)
+    pub fn with_role_checks(mut self) -> Self {
+        self.role_checks = true;
+        self
+    }
+
+    /// Liga o consenso do nó, fonte das consultas de validadores
+    pub fn with_consensus(mut self, consensus: Arc<QuantumFlexConsensus>) -> Self {
+        self.consensus = Some(consensus);
+        self
+    }
+
+    /// Liga os sinais de rede e armazenamento do nó, lidos pelo status e pelo `/health`
+    pub fn with_monitor(mut self, monitor: Arc<NodeMonitor>) -> Self {
+        self.monitor = monitor;
+        self
+    }
+
+    /// Limita as chamadas externas (`call`); chamadas internas por `handle` não contam
+    pub fn with_rate_limiter(mut self, limiter: Arc<RpcRateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Habilita `reload_config`
+    pub fn with_reloader(mut self, reloader: Arc<ConfigReloader>) -> Self {
+        self.reloader = Some(reloader);
+        self
+    }
+
+    /// Sincronização, topo da cadeia, peers, mempool, finalidade e integridade do banco
+    pub fn node_status(&self) -> NodeStatus {
+        let (height, latest_hash, mempool_size, finalized_height, halted) =
+            self.blockchain.read(|blockchain| {
+                (
+                    blockchain.height(),
+                    blockchain.latest_block().map(|block| block.hash.clone()),
+                    blockchain.pending_transactions.len() + blockchain.pending_operations.len(),
+                    blockchain.finalized_height(),
+                    blockchain.is_halted(),
+                )
+            });
+        NodeStatus {
+            sync: self.monitor.sync_state(height),
+            height,
+            latest_hash,
+            peers: self.monitor.peers(),
+            mempool_size,
+            finalized_height,
+            storage: self.monitor.storage_health(),
+            halted,
+        }
+    }
+
+    /// Verificação para balanceadores de carga (`GET /health`): a camada HTTP
+    /// responde 200 quando `healthy` e 503 caso contrário
+    pub fn health(&self) -> HealthResponse {
+        let status = self.node_status();
+        let mut problems = Vec::new();
+        if let SyncState::Syncing { target_height } = status.sync {
+            problems.push(format!(
+                "Sincronizando: altura {} de {}",
+                status.height, target_height
+            ));
+        }
+        if let Some(storage) = status.storage.filter(|storage| !storage.healthy) {
+            problems.push(format!(
+                "Banco de dados {} com problemas: {}",
+                storage.path, storage.detail
+            ));
+        }
+        HealthResponse {
+            healthy: problems.is_empty(),
+            height: status.height,
+            problems,
+        }
+    }
+
+    fn consensus(&self) -> Result<&QuantumFlexConsensus, Error> {
+        self.consensus
+            .as_deref()
+            .ok_or_else(|| Error::Other("Consenso não disponível neste nó".to_string()))
+    }
+
+    /// Ponto de entrada para clientes externos: aplica a ACL da chamada antes de executá-la
+    pub fn call(&self, method: &str, token: Option<&str>, request: Value) -> Result<Value, Error> {
+        self.call_with_credential(method, token, None, request)
+    }
+
+    /// Como `call`, apresentando uma credencial de papel administrativo
+    pub fn call_with_credential(
+        &self,
+        method: &str,
+        token: Option<&str>,
+        credential: Option<&RoleCredential>,
+        request: Value,
+    ) -> Result<Value, Error> {
+        let spec = RpcMethod::find(method)
+            .ok_or_else(|| Error::InvalidInput(format!("Método RPC desconhecido: {}", method)))?;
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.check()?;
+        }
+
+        if spec.role != Role::Public {
+            let auth = self.auth.as_ref().ok_or_else(|| {
+                Error::Unauthorized("Autenticação RPC não configurada".to_string())
+            })?;
+            auth.authorize(token, spec.role)?;
+        }
+        if let Some(permission) = spec.permission.filter(|_| self.role_checks) {
+            let credential = credential.ok_or_else(|| {
+                Error::Unauthorized(format!("Chamada exige credencial de {}", permission))
+            })?;
+            self.blockchain
+                .read(|blockchain| blockchain.authorize(credential, permission))?;
+        }
+
+        self.handle(method, request)
+    }
+
+    /// Documento OpenAPI da superfície RPC
+    pub fn openapi(&self) -> Value {
+        openapi_document()
+    }
+
+    /// Nomes das chamadas suportadas
+    pub fn methods(&self) -> impl Iterator<Item = &'static str> {
+        METHODS.iter().map(|method| method.name)
+    }
+
+    /// Executa a chamada `method` com os parâmetros JSON `params`, sem controle de
+    /// acesso (uso interno do nó; clientes externos passam por `call`)
+    pub fn handle(&self, method: &str, request: Value) -> Result<Value, Error> {
+        match method {
+            "get_node_status" => reply(self.node_status()),
+            "get_height" => {
+                let snapshot = self.blockchain.read_snapshot();
+                reply(HeightResponse {
+                    height: snapshot.height(),
+                    latest_hash: snapshot.latest_hash().map(str::to_string),
+                })
+            }
+            "get_block" => {
+                let HeightRequest { height } = params(request)?;
+                let (block, finality) =
+                    self.blockchain.block_with_finality(height).ok_or_else(|| {
+                        Error::InvalidBlock(format!("Bloco {} não encontrado", height))
+                    })?;
+                reply(BlockResponse { block, finality })
+            }
+            "get_block_by_hash" => {
+                let BlockHashRequest { hash } = params(request)?;
+                let (block, finality) = self
+                    .blockchain
+                    .block_by_hash_with_finality(&hash)
+                    .ok_or_else(|| Error::InvalidBlock(format!("Bloco {} não encontrado", hash)))?;
+                reply(BlockResponse { block, finality })
+            }
+            "get_transaction" => {
+                let TransactionRequest { txid } = params(request)?;
+                let record = self.blockchain.transaction_record(&txid).ok_or_else(|| {
+                    Error::InvalidInput(format!("Transação {} não encontrada", txid))
+                })?;
+                reply(record)
+            }
+            "get_transaction_status" => {
+                let TransactionRequest { txid } = params(request)?;
+                let status = self.blockchain.transaction_status(&txid).ok_or_else(|| {
+                    Error::InvalidInput(format!("Transação {} não encontrada", txid))
+                })?;
+                reply(status)
+            }
+            "get_rejection" => {
+                let TransactionRequest { txid } = params(request)?;
+                let reason = self.blockchain.rejection(&txid).ok_or_else(|| {
+                    Error::InvalidInput(format!("Nenhuma rejeição registrada para {}", txid))
+                })?;
+                reply(reason)
+            }
+            "get_balance" => {
+                let BalanceRequest { token_id, address } = params(request)?;
+                let balance = self
+                    .blockchain
+                    .balance_of(&token_id, &address)
+                    .ok_or(Error::TokenNotFound)?;
+                reply(BalanceResponse {
+                    token_id,
+                    address,
+                    balance,
+                })
+            }
+            "get_account" => {
+                let BalanceRequest { token_id, address } = params(request)?;
+                let snapshot = self.blockchain.read_snapshot();
+                let balance = snapshot
+                    .balance_of(&token_id, &address)
+                    .ok_or(Error::TokenNotFound)?;
+                reply(AccountResponse {
+                    balance,
+                    nonce: snapshot.nonce_of(&address),
+                    pending: snapshot.pending_for(&address).cloned().collect(),
+                    height: snapshot.height(),
+                    token_id,
+                    address,
+                })
+            }
+            "get_account_nonce" => {
+                let AccountRequest { address } = params(request)?;
+                reply(self.blockchain.account_nonce(&address))
+            }
+            "get_token" => {
+                let TokenRequest { token_id } = params(request)?;
+                let snapshot = self.blockchain.read_snapshot();
+                let metadata = snapshot
+                    .token_metadata(&token_id)
+                    .ok_or(Error::TokenNotFound)?;
+                let total_supply = snapshot
+                    .token_supply(&token_id)
+                    .ok_or(Error::TokenNotFound)?;
+                reply(TokenResponse {
+                    metadata: metadata.clone(),
+                    total_supply,
+                    paused: snapshot.is_token_paused(&token_id),
+                })
+            }
+            "resolve_name" => {
+                let NameRequest { name } = params(request)?;
+                let name = name.to_ascii_lowercase();
+                validate_name(&name)?;
+                let record = self
+                    .blockchain
+                    .read(|blockchain| {
+                        blockchain
+                            .resolve_name(&name)
+                            .and_then(|_| blockchain.name_record(&name).cloned())
+                    })
+                    .ok_or_else(|| {
+                        Error::InvalidInput(format!("Nome {} não registrado ou expirado", name))
+                    })?;
+                reply(NameResponse {
+                    name,
+                    owner: record.owner,
+                    registered_at: record.registered_at,
+                    expires_at: record.expires_at,
+                })
+            }
+            "get_proof" => {
+                let ProofRequest { txid } = params(request)?;
+                reply(self.blockchain.get_proof(&txid)?)
+            }
+            "get_state_at" => {
+                let HeightRequest { height } = params(request)?;
+                reply(self.blockchain.state_at(height)?)
+            }
+            "get_receipts" => {
+                let HeightRequest { height } = params(request)?;
+                reply(self.blockchain.receipts_at(height)?)
+            }
+            "get_validators" => {
+                let response = self
+                    .consensus()?
+                    .inspect_validators(|validators, reputation| ValidatorSetResponse {
+                        validators: validators
+                            .validators_by_stake()
+                            .iter()
+                            .filter_map(|id| validators.get_validator(id))
+                            .filter(|validator| validator.is_active)
+                            .map(|validator| ValidatorStatus::new(validator, reputation))
+                            .collect(),
+                        total_active_stake: validators.total_active_stake(),
+                    });
+                reply(response)
+            }
+            "get_validator" => {
+                let ValidatorRequest { validator_id } = params(request)?;
+                let status = self
+                    .consensus()?
+                    .inspect_validators(|validators, reputation| {
+                        validators
+                            .get_validator(&validator_id)
+                            .map(|validator| ValidatorStatus::new(validator, reputation))
+                    });
+                reply(status.ok_or_else(|| {
+                    Error::InvalidInput(format!("Validador {} não encontrado", validator_id))
+                })?)
+            }
+            "get_validator_report" => {
+                let ValidatorReportRequest {
+                    validator_id,
+                    from_epoch,
+                    to_epoch,
+                    format,
+                } = params(request)?;
+                let report = self.consensus()?.inspect_validators(|_, reputation| {
+                    let to_epoch = to_epoch.unwrap_or(reputation.performance().current_epoch());
+                    reputation.performance_report(
+                        validator_id.as_deref(),
+                        from_epoch.unwrap_or(0),
+                        to_epoch,
+                    )
+                });
+                reply(match format {
+                    ReportFormat::Json => ValidatorReportResponse::Json { report },
+                    ReportFormat::Csv => ValidatorReportResponse::Csv {
+                        content: report.to_csv(),
+                    },
+                })
+            }
+            "submit_transactions" => {
+                let SubmitTransactionsRequest { transactions } = params(request)?;
+                let txids: Vec<String> = transactions.iter().map(Transaction::txid).collect();
+                let results = self
+                    .blockchain
+                    .submit_batch(transactions)
+                    .into_iter()
+                    .zip(txids)
+                    .map(|(result, txid)| SubmitResult {
+                        accepted: result.is_ok(),
+                        error: result.as_ref().err().map(|e| e.to_string()),
+                        error_code: result.as_ref().err().map(|e| e.code()),
+                        rejection: result.err().and_then(|_| self.blockchain.rejection(&txid)),
+                    })
+                    .collect();
+                reply(SubmitTransactionsResponse { results })
+            }
+            "submit_operation" => {
+                let operation: Operation = params(request)?;
+                let result = self.blockchain.submit_operation(operation);
+                reply(SubmitResult {
+                    accepted: result.is_ok(),
+                    error: result.as_ref().err().map(|e| e.to_string()),
+                    error_code: result.as_ref().err().map(|e| e.code()),
+                    rejection: None,
+                })
+            }
+            "simulate_transaction" => {
+                let transaction: Transaction = params(request)?;
+                reply(self.blockchain.simulate_transaction(&transaction))
+            }
+            "reload_config" => {
+                let reloader = self.reloader.as_ref().ok_or_else(|| {
+                    Error::Other("Recarregamento não disponível neste nó".to_string())
+                })?;
+                reply(reloader.reload().map_err(Error::InvalidInput)?)
+            }
+            "submit_block" => {
+                let block: Block = params(request)?;
+                let response = SubmitBlockResponse {
+                    height: block.index,
+                    hash: block.hash.clone(),
+                };
+                self.blockchain.add_block(block)?;
+                reply(response)
+            }
+            other => Err(Error::InvalidInput(format!(
+                "Método RPC desconhecido: {}",
+                other
+            ))),
+        }
+    }
+}
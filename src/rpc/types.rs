@@ -0,0 +1,299 @@
+use crate::blockchain::{Block, Finality, RejectionReason};
+use crate::consensus::reputation::ValidatorReputation;
+use crate::consensus::{LivenessStats, PerformanceReport, ReputationSystem, Validator};
+use crate::error::{ErrorCategory, ErrorCode};
+use crate::token::TokenMetadata;
+use crate::transaction::Transaction;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// Parâmetros de consultas por altura (`get_block`, `get_state_at`, `get_receipts`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct HeightRequest {
+    pub height: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct BlockHashRequest {
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct HeightResponse {
+    /// Número de blocos da cadeia
+    pub height: u64,
+    pub latest_hash: Option<String>,
+}
+
+/// Bloco com sua finalidade no momento da consulta
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BlockResponse {
+    #[serde(flatten)]
+    pub block: Block,
+    pub finality: Finality,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct BalanceRequest {
+    pub token_id: String,
+    pub address: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct BalanceResponse {
+    pub token_id: String,
+    pub address: String,
+    pub balance: u64,
+}
+
+/// Saldo, nonce e transações pendentes de um endereço, lidos da mesma foto
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AccountResponse {
+    pub token_id: String,
+    pub address: String,
+    pub balance: u64,
+    /// Último nonce admitido, contando o mempool
+    pub nonce: u64,
+    /// Transações do mempool enviadas por ou para o endereço
+    pub pending: Vec<Transaction>,
+    /// Altura da cadeia no momento da leitura
+    pub height: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct AccountRequest {
+    pub address: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TokenRequest {
+    pub token_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TokenResponse {
+    pub metadata: TokenMetadata,
+    pub total_supply: u64,
+    /// Transferências suspensas pelo dono ou administradores
+    #[serde(default)]
+    pub paused: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct NameRequest {
+    /// Nome do registro (`alice.qst`), sem distinção de caixa
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct NameResponse {
+    /// Nome na forma canônica, em minúsculas
+    pub name: String,
+    pub owner: String,
+    pub registered_at: u64,
+    pub expires_at: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionRequest {
+    /// txid da transação (`Transaction::txid`)
+    pub txid: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ProofRequest {
+    /// Hex da folha Merkle da transação
+    pub txid: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SubmitTransactionsRequest {
+    pub transactions: Vec<Transaction>,
+}
+
+/// Resultado da admissão de uma transação, na ordem do pedido
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SubmitResult {
+    pub accepted: bool,
+    pub error: Option<String>,
+    /// Código estável do erro (ver `ErrorCode`)
+    #[serde(default)]
+    pub error_code: Option<u32>,
+    /// Campo culpado e valores esperado e encontrado, quando rejeitada
+    #[serde(default)]
+    pub rejection: Option<RejectionReason>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SubmitTransactionsResponse {
+    pub results: Vec<SubmitResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SubmitBlockResponse {
+    pub height: u64,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ValidatorRequest {
+    pub validator_id: String,
+}
+
+/// Stake, reputação, banimento e desempenho recente de um validador
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ValidatorStatus {
+    pub id: String,
+    pub address: String,
+    pub stake: u64,
+    pub is_active: bool,
+    /// Pontuação de reputação (0-100; 50 é neutro)
+    pub score: f32,
+    pub is_banned: bool,
+    /// Segundos restantes do banimento em curso
+    pub ban_remaining_secs: Option<u64>,
+    pub successful_proposals: u64,
+    pub invalid_proposals: u64,
+    pub correct_votes: u64,
+    pub incorrect_votes: u64,
+    pub timeouts: u64,
+    pub double_votes: u64,
+    /// Faltas na janela de disponibilidade
+    pub liveness: LivenessStats,
+}
+
+impl ValidatorStatus {
+    pub fn new(validator: &Validator, reputation: &ReputationSystem) -> Self {
+        let record = reputation.get_reputation(&validator.id);
+        let ban_remaining = record
+            .filter(|record| record.is_banned)
+            .and_then(|record| record.banned_until.as_ref())
+            .map(|until| until.to_instant().saturating_duration_since(Instant::now()))
+            .filter(|remaining| !remaining.is_zero());
+        let count = |field: fn(&ValidatorReputation) -> u64| record.map(field).unwrap_or(0);
+        Self {
+            id: validator.id.clone(),
+            address: validator.address.clone(),
+            stake: validator.stake,
+            is_active: validator.is_active,
+            score: record.map(|record| record.score).unwrap_or(50.0),
+            is_banned: ban_remaining.is_some(),
+            ban_remaining_secs: ban_remaining.map(|remaining| remaining.as_secs()),
+            successful_proposals: count(|record| record.successful_proposals),
+            invalid_proposals: count(|record| record.invalid_proposals),
+            correct_votes: count(|record| record.correct_votes),
+            incorrect_votes: count(|record| record.incorrect_votes),
+            timeouts: count(|record| record.timeouts),
+            double_votes: count(|record| record.double_votes),
+            liveness: reputation.liveness().stats(&validator.id),
+        }
+    }
+}
+
+/// Formato de exportação de um relatório
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+/// Filtros do relatório de desempenho; sem eles, todos os validadores em todas
+/// as épocas ainda retidas pelo nó
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ValidatorReportRequest {
+    pub validator_id: Option<String>,
+    pub from_epoch: Option<u64>,
+    pub to_epoch: Option<u64>,
+    pub format: ReportFormat,
+}
+
+/// Relatório de desempenho por época, estruturado ou já em CSV
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "format", rename_all = "snake_case")]
+pub enum ValidatorReportResponse {
+    Json { report: PerformanceReport },
+    Csv { content: String },
+}
+
+/// Conjunto ativo de validadores, do maior stake para o menor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ValidatorSetResponse {
+    pub validators: Vec<ValidatorStatus>,
+    pub total_active_stake: u64,
+}
+
+/// Situação da sincronização com os peers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum SyncState {
+    /// Nenhum peer anunciou altura maior que a local
+    Synced,
+    /// Algum peer anunciou `target_height`, acima da altura local
+    Syncing { target_height: u64 },
+}
+
+/// Resultado do `PRAGMA integrity_check` no banco SQLite do nó
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct StorageHealth {
+    pub path: String,
+    pub healthy: bool,
+    /// `ok` ou os problemas relatados pelo SQLite
+    pub detail: String,
+}
+
+/// Resposta de `get_node_status`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct NodeStatus {
+    pub sync: SyncState,
+    /// Número de blocos da cadeia
+    pub height: u64,
+    pub latest_hash: Option<String>,
+    pub peers: usize,
+    /// Transações e operações no mempool
+    pub mempool_size: usize,
+    /// Altura coberta pelo último checkpoint, se houver
+    pub finalized_height: Option<u64>,
+    /// Ausente quando o nó não tem banco SQLite configurado
+    pub storage: Option<StorageHealth>,
+    /// Cadeia parada por emergência: consultas seguem disponíveis, mas nada além
+    /// das aprovações de retomada é admitido
+    #[serde(default)]
+    pub halted: bool,
+}
+
+/// Corpo de `GET /health`: saudável só com o nó sincronizado e o banco íntegro
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct HealthResponse {
+    pub healthy: bool,
+    pub height: u64,
+    /// Motivos da indisponibilidade, vazio quando saudável
+    pub problems: Vec<String>,
+}
+
+/// Corpo de resposta de chamadas que falharam
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct RpcErrorResponse {
+    pub method: String,
+    pub message: String,
+    /// Código estável do erro (ver `ErrorCode`)
+    pub code: u32,
+    pub category: ErrorCategory,
+    /// Se repetir a chamada sem alterações pode ter sucesso
+    pub retryable: bool,
+}
+
+impl RpcErrorResponse {
+    pub fn new<E: ErrorCode>(method: &str, error: &E) -> Self {
+        Self {
+            method: method.to_string(),
+            message: error.to_string(),
+            code: error.code(),
+            category: error.category(),
+            retryable: error.is_retryable(),
+        }
+    }
+}
@@ -1,8 +1,10 @@
+use super::limits::{ContractLimits, ContractProfile};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::error::Error as StdError;
 use wasmer::{imports, Instance, Module, Store, Value};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SmartContract {
     pub code: Vec<u8>,        // Código do contrato (bytecode)
     pub data: Vec<u8>,        // Dados do contrato (estado)
@@ -43,6 +45,11 @@ impl SmartContract {
         Module::validate(store, &self.code).map_err(|e| format!("Bytecode inválido: {}", e))
     }
 
+    /// Confere o bytecode contra os limites de tamanho e complexidade.
+    pub fn check_limits(&self, limits: &ContractLimits) -> Result<ContractProfile, String> {
+        limits.inspect(&self.code)
+    }
+
     /// Atualiza os dados do contrato.
     pub fn update_data(&mut self, new_data: Vec<u8>) {
         self.data = new_data;
@@ -0,0 +1,139 @@
+use crate::transaction::operation::MAX_POLICY_CONTRACT_SIZE;
+use wasmparser::{ImportSectionEntryType, Parser, Payload, Validator};
+
+/// Limites de tamanho e complexidade de um módulo WASM antes de ele entrar na cadeia.
+///
+/// A inspeção lê só a estrutura do módulo, sem compilá-lo; um módulo fora dos limites
+/// é recusado antes de chegar ao compilador.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractLimits {
+    /// Tamanho máximo do código submetido, em bytes (texto WAT ou binário)
+    pub max_code_size: usize,
+    /// Funções definidas e importadas
+    pub max_functions: u32,
+    pub max_tables: u32,
+    /// Soma dos máximos declarados pelas memórias, em páginas de 64 KiB. Memórias
+    /// sem máximo declarado são recusadas
+    pub max_memory_pages: u64,
+    /// Módulos (`wasi_unstable`) ou imports específicos (`env.abort`) proibidos
+    pub banned_imports: Vec<String>,
+}
+
+impl Default for ContractLimits {
+    fn default() -> Self {
+        Self {
+            max_code_size: MAX_POLICY_CONTRACT_SIZE,
+            max_functions: 256,
+            max_tables: 1,
+            max_memory_pages: 16,
+            banned_imports: vec![
+                "env".to_string(),
+                "wasi_snapshot_preview1".to_string(),
+                "wasi_unstable".to_string(),
+            ],
+        }
+    }
+}
+
+/// Estrutura de um módulo aprovado pelos limites
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContractProfile {
+    pub code_size: usize,
+    pub functions: u32,
+    pub tables: u32,
+    pub memory_pages: u64,
+    /// Imports no formato `modulo.campo`
+    pub imports: Vec<String>,
+}
+
+impl ContractLimits {
+    /// Valida o módulo e confere cada limite, devolvendo a estrutura lida
+    pub fn inspect(&self, code: &[u8]) -> Result<ContractProfile, String> {
+        if code.len() > self.max_code_size {
+            return Err(format!(
+                "código com {} bytes, acima do limite de {}",
+                code.len(),
+                self.max_code_size
+            ));
+        }
+        let binary = wat::parse_bytes(code).map_err(|e| format!("texto WAT inválido: {}", e))?;
+        Validator::new()
+            .validate_all(&binary)
+            .map_err(|e| format!("módulo WASM inválido: {}", e))?;
+
+        let profile = Self::profile(code.len(), &binary)?;
+        if profile.functions > self.max_functions {
+            return Err(format!(
+                "{} funções, acima do limite de {}",
+                profile.functions, self.max_functions
+            ));
+        }
+        if profile.tables > self.max_tables {
+            return Err(format!(
+                "{} tabelas, acima do limite de {}",
+                profile.tables, self.max_tables
+            ));
+        }
+        if profile.memory_pages > self.max_memory_pages {
+            return Err(format!(
+                "{} páginas de memória, acima do limite de {}",
+                profile.memory_pages, self.max_memory_pages
+            ));
+        }
+        if let Some(import) = profile.imports.iter().find(|import| self.is_banned(import)) {
+            return Err(format!("import proibido: {}", import));
+        }
+        Ok(profile)
+    }
+
+    fn is_banned(&self, import: &str) -> bool {
+        let module = import.split('.').next().unwrap_or_default();
+        self.banned_imports
+            .iter()
+            .any(|banned| banned == import || banned == module)
+    }
+
+    fn profile(code_size: usize, binary: &[u8]) -> Result<ContractProfile, String> {
+        let mut profile = ContractProfile {
+            code_size,
+            ..ContractProfile::default()
+        };
+        for payload in Parser::new(0).parse_all(binary) {
+            match payload.map_err(|e| format!("módulo WASM inválido: {}", e))? {
+                Payload::ImportSection(reader) => {
+                    for import in reader {
+                        let import = import.map_err(|e| format!("import ilegível: {}", e))?;
+                        match import.ty {
+                            ImportSectionEntryType::Function(_) => profile.functions += 1,
+                            ImportSectionEntryType::Table(_) => profile.tables += 1,
+                            ImportSectionEntryType::Memory(memory) => {
+                                profile.memory_pages += declared_maximum(memory.maximum)?
+                            }
+                            _ => {}
+                        }
+                        profile.imports.push(format!(
+                            "{}.{}",
+                            import.module,
+                            import.field.unwrap_or_default()
+                        ));
+                    }
+                }
+                Payload::FunctionSection(reader) => profile.functions += reader.get_count(),
+                Payload::TableSection(reader) => profile.tables += reader.get_count(),
+                Payload::MemorySection(reader) => {
+                    for memory in reader {
+                        let memory = memory.map_err(|e| format!("memória ilegível: {}", e))?;
+                        profile.memory_pages += declared_maximum(memory.maximum)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(profile)
+    }
+}
+
+/// Memória sem máximo pode crescer até o limite do runtime durante a execução
+fn declared_maximum(maximum: Option<u64>) -> Result<u64, String> {
+    maximum.ok_or_else(|| "memória sem máximo de páginas declarado".to_string())
+}
@@ -1,2 +1,4 @@
 mod contract_impl;
+mod limits;
 pub use contract_impl::SmartContract;
+pub use limits::{ContractLimits, ContractProfile};
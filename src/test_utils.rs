@@ -0,0 +1,303 @@
+// Utilitários de testes baseados em propriedades (feature `test-utils`).
+// `strategies` gera valores válidos para proptest; as implementações de
+// `Arbitrary` geram valores estruturalmente arbitrários (não necessariamente válidos).
+// `fixtures` reúne contas e atalhos usados pelos testes de integração.
+
+use crate::blockchain::Block;
+use crate::consensus::epoch::{EpochInfo, EpochStats, EpochTransition};
+use crate::consensus::{BlockProposal, ConsensusMessage, ProposalVote};
+use crate::smart_contract::SmartContract;
+use crate::token::Token;
+use crate::transaction::{SecureTransaction, Transaction};
+use arbitrary::{Arbitrary, Unstructured};
+use std::collections::HashSet;
+
+pub mod fixtures;
+
+/// Estratégias proptest para os tipos principais
+pub mod strategies {
+    use crate::blockchain::Block;
+    use crate::constants::{MAX_AMOUNT, MIN_AMOUNT};
+    use crate::consensus::{BlockProposal, ProposalVote};
+    use crate::token::Token;
+    use crate::transaction::{SecureTransaction, Transaction};
+    use once_cell::sync::Lazy;
+    use pqcrypto_dilithium::dilithium5::{self, PublicKey, SecretKey};
+    use pqcrypto_traits::sign::PublicKey as _;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    /// Par de chaves compartilhado para estratégias que só precisam de assinaturas válidas
+    static SHARED_KEYS: Lazy<(PublicKey, SecretKey)> = Lazy::new(dilithium5::keypair);
+
+    /// Endereço alfanumérico aceito por `Transaction::validate_address`
+    pub fn address() -> impl Strategy<Value = String> {
+        "(0x)?[a-zA-Z0-9]{32,64}"
+    }
+
+    /// Par de endereços distintos (remetente, destinatário)
+    pub fn address_pair() -> impl Strategy<Value = (String, String)> {
+        (address(), address()).prop_filter("endereços devem ser distintos", |(a, b)| a != b)
+    }
+
+    /// Valor dentro dos limites de transação
+    pub fn amount() -> impl Strategy<Value = u64> {
+        MIN_AMOUNT..=MAX_AMOUNT
+    }
+
+    /// Hash hexadecimal de 64 caracteres
+    pub fn hex_hash() -> impl Strategy<Value = String> {
+        "[0-9a-f]{64}"
+    }
+
+    /// Transação bem formada, com timestamp atual e ainda não assinada
+    pub fn transaction() -> impl Strategy<Value = Transaction> {
+        (address_pair(), amount(), any::<u64>(), 1..1000u64).prop_map(
+            |((from, to), amount, token_id, nonce)| {
+                let mut tx = Transaction::new(from, to, amount, Vec::new())
+                    .expect("endereços gerados devem ser válidos");
+                tx.token_id = token_id;
+                tx.nonce = nonce;
+                tx.update_hash().expect("hash deve ser calculável");
+                tx
+            },
+        )
+    }
+
+    /// Transação assinada com um par de chaves novo (chave pública em `public_key`)
+    pub fn signed_transaction() -> impl Strategy<Value = Transaction> {
+        transaction().prop_map(|mut tx| {
+            let (public_key, secret_key) = dilithium5::keypair();
+            tx.public_key = public_key.as_bytes().to_vec();
+            tx.sign(&secret_key).expect("assinatura deve funcionar");
+            tx
+        })
+    }
+
+    /// Transação segura assinada com o par de chaves compartilhado
+    pub fn secure_transaction() -> impl Strategy<Value = SecureTransaction> {
+        (address_pair(), amount(), any::<u32>(), 1..1000u64).prop_map(
+            |((from, to), amount, timestamp, nonce)| {
+                SecureTransaction::new(
+                    from,
+                    to,
+                    amount,
+                    timestamp as i64,
+                    nonce,
+                    &SHARED_KEYS.1,
+                    &SHARED_KEYS.0,
+                )
+                .expect("transação segura deve ser criada")
+            },
+        )
+    }
+
+    /// Chave pública do par compartilhado usado por `secure_transaction` e `block`
+    pub fn shared_public_key() -> PublicKey {
+        SHARED_KEYS.0
+    }
+
+    /// Bloco com hash consistente e até `max_transactions` transações seguras
+    pub fn block(max_transactions: usize) -> impl Strategy<Value = Block> {
+        (
+            any::<u32>(),
+            hex_hash(),
+            vec(secure_transaction(), 0..=max_transactions),
+        )
+            .prop_map(|(index, previous_hash, transactions)| {
+                Block::new(index as u64, transactions, Vec::new(), previous_hash)
+                    .expect("bloco deve ser criado")
+            })
+    }
+
+    /// Token com nome, símbolo e supply dentro das regras de criação
+    pub fn token() -> impl Strategy<Value = Token> {
+        ("[A-Za-z]{1,64}", "[A-Z]{2,10}", 1..=u64::MAX / 2, address()).prop_map(
+            |(name, symbol, supply, creator)| {
+                Token::new(name, symbol, supply, creator).expect("token deve ser criado")
+            },
+        )
+    }
+
+    /// Proposta de bloco com campos bem formados
+    pub fn block_proposal() -> impl Strategy<Value = BlockProposal> {
+        (
+            hex_hash(),
+            1..u64::MAX / 2,
+            hex_hash(),
+            "validator[0-9]{1,3}",
+            vec(hex_hash(), 0..8),
+            vec(any::<u8>(), 1..64),
+        )
+            .prop_map(|(hash, height, parent, proposer, txs, signature)| {
+                BlockProposal::new(hash, height, parent, proposer, txs, signature, Vec::new())
+            })
+    }
+
+    /// Voto em uma proposta
+    pub fn proposal_vote() -> impl Strategy<Value = ProposalVote> {
+        (
+            hex_hash(),
+            1..u64::MAX / 2,
+            "validator[0-9]{1,3}",
+            any::<bool>(),
+            vec(any::<u8>(), 1..64),
+        )
+            .prop_map(|(hash, height, validator, in_favor, signature)| {
+                ProposalVote::new(hash, height, validator, in_favor, signature)
+            })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Transaction {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Transaction {
+            token_id: u.arbitrary()?,
+            from: u.arbitrary()?,
+            to: u.arbitrary()?,
+            amount: u.arbitrary()?,
+            timestamp: u.arbitrary()?,
+            nonce: u.arbitrary()?,
+            public_key: u.arbitrary()?,
+            signature: u.arbitrary()?,
+            transaction_hash: u.arbitrary()?,
+            hash: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for SecureTransaction {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(SecureTransaction {
+            from: u.arbitrary()?,
+            to: u.arbitrary()?,
+            amount: u.arbitrary()?,
+            timestamp: u.arbitrary()?,
+            nonce: u.arbitrary()?,
+            signature: u.arbitrary()?,
+            public_key: u.arbitrary()?,
+            cipher_key: u.arbitrary()?,
+            encrypted_data: u.arbitrary()?,
+            iv: u.arbitrary()?,
+            salt: u.arbitrary()?,
+            mac: u.arbitrary()?,
+            payload_grants: Vec::new(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for SmartContract {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(SmartContract::new(
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Block {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Block {
+            index: u.arbitrary()?,
+            timestamp: u.arbitrary()?,
+            transactions: u.arbitrary()?,
+            contracts: u.arbitrary()?,
+            previous_hash: u.arbitrary()?,
+            hash: u.arbitrary()?,
+            proposer: u.arbitrary()?,
+            validator_signature: u.arbitrary()?,
+            nonce: u.arbitrary()?,
+            processed_transactions: u.arbitrary::<HashSet<String>>()?,
+            execution: None,
+            operations: Vec::new(),
+            transfers: Vec::new(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Token {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let supply: u64 = u.arbitrary()?;
+        let mut token = Token::new(u.arbitrary()?, u.arbitrary()?, supply, u.arbitrary()?)
+            .map_err(|_| arbitrary::Error::IncorrectFormat)?;
+        token.id = u.arbitrary()?;
+        Ok(token)
+    }
+}
+
+impl<'a> Arbitrary<'a> for BlockProposal {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut proposal = BlockProposal::new(
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+        );
+        proposal.timestamp = u.arbitrary()?;
+        Ok(proposal)
+    }
+}
+
+impl<'a> Arbitrary<'a> for ProposalVote {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut vote = ProposalVote::new(
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+        );
+        vote.timestamp = u.arbitrary()?;
+        Ok(vote)
+    }
+}
+
+impl<'a> Arbitrary<'a> for EpochTransition {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let previous_epoch: u32 = u.arbitrary()?;
+        let transition_block: u32 = u.arbitrary()?;
+        let start_time = chrono::DateTime::from_timestamp(u.arbitrary::<u32>()? as i64, 0)
+            .ok_or(arbitrary::Error::IncorrectFormat)?;
+
+        Ok(EpochTransition {
+            previous_epoch: previous_epoch as u64,
+            new_epoch: previous_epoch as u64 + 1,
+            transition_block: transition_block as u64,
+            activation_block: transition_block as u64 + u.arbitrary::<u8>()? as u64,
+            previous_epoch_info: EpochInfo {
+                epoch_number: previous_epoch as u64,
+                start_block: u.arbitrary::<u32>()? as u64,
+                end_block: transition_block as u64,
+                start_time,
+                expected_end_time: start_time,
+                is_completed: true,
+                stats: EpochStats::default(),
+            },
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for ConsensusMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=7u8)? {
+            0 => ConsensusMessage::NewBlockProposal(u.arbitrary()?),
+            1 => ConsensusMessage::NewVote(u.arbitrary()?),
+            2 => ConsensusMessage::ProposeBlock,
+            3 => ConsensusMessage::ConsensusTimeout,
+            4 => ConsensusMessage::EvaluateAndAdapt,
+            5 => ConsensusMessage::NewEpoch(u.arbitrary()?),
+            6 => ConsensusMessage::BlockFinalized {
+                block_hash: u.arbitrary()?,
+                block_height: u.arbitrary()?,
+            },
+            _ => ConsensusMessage::Shutdown,
+        })
+    }
+}
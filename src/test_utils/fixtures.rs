@@ -0,0 +1,157 @@
+//! Contas, transferências e atalhos de bloco repetidos pelos testes de integração
+
+use crate::blockchain::{Block, Blockchain};
+use crate::transaction::{Operation, OperationKind, Transaction};
+use crate::utils::address::derive_address;
+use pqcrypto_dilithium::dilithium5::{keypair, PublicKey, SecretKey};
+use pqcrypto_traits::sign::PublicKey as _;
+use std::path::PathBuf;
+
+pub fn alice() -> String {
+    "a".repeat(40)
+}
+
+pub fn bob() -> String {
+    "b".repeat(40)
+}
+
+pub fn carol() -> String {
+    "c".repeat(40)
+}
+
+/// Transferência de `from` para `to` no token 0, assinada com `keys`
+pub fn signed_transfer(
+    keys: &(PublicKey, SecretKey),
+    from: &str,
+    to: &str,
+    amount: u64,
+    nonce: u64,
+) -> Transaction {
+    let mut tx = Transaction::new(
+        from.to_string(),
+        to.to_string(),
+        amount,
+        keys.0.as_bytes().to_vec(),
+    )
+    .unwrap();
+    tx.nonce = nonce;
+    tx.sign(&keys.1).unwrap();
+    tx
+}
+
+/// Endereço derivado da chave pública de `keys`
+pub fn address_of(keys: &(PublicKey, SecretKey)) -> String {
+    derive_address(keys.0.as_bytes())
+}
+
+/// Transferência para bob a partir do endereço derivado de `keys`
+pub fn transfer(keys: &(PublicKey, SecretKey), amount: u64, nonce: u64) -> Transaction {
+    signed_transfer(keys, &address_of(keys), &bob(), amount, nonce)
+}
+
+/// Cadeia nova com `amount` do token 0 em `address`
+pub fn funded(address: &str, amount: u64) -> Blockchain {
+    let mut blockchain = Blockchain::new().unwrap();
+    fund(&mut blockchain, address, amount);
+    blockchain
+}
+
+/// Define em `amount` o saldo do token 0 (KYBL, que paga as taxas) de `address`
+pub fn fund(blockchain: &mut Blockchain, address: &str, amount: u64) {
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(address.to_string(), amount);
+}
+
+pub fn balance(blockchain: &Blockchain, token_id: u64, address: &str) -> u64 {
+    blockchain
+        .get_token(&token_id.to_string())
+        .unwrap()
+        .balance_of(&address.to_string())
+}
+
+/// Submete as operações e produz o bloco que as inclui
+pub fn commit(
+    blockchain: &mut Blockchain,
+    operations: impl IntoIterator<Item = Operation>,
+) -> Block {
+    for operation in operations {
+        blockchain.submit_operation(operation).unwrap();
+    }
+    blockchain.produce_block(10).unwrap().unwrap()
+}
+
+/// Submete a transferência e produz o bloco que a inclui
+pub fn commit_transaction(blockchain: &mut Blockchain, tx: Transaction) -> Block {
+    blockchain.submit_transaction(tx).unwrap();
+    blockchain.produce_block(10).unwrap().unwrap()
+}
+
+/// Caminho em `temp_dir` exclusivo deste processo; um arquivo anterior com o
+/// mesmo nome é removido
+pub fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("kybelith-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+/// Conta com par de chaves próprio e contador de nonce
+pub struct Account {
+    pub address: String,
+    pub keys: (PublicKey, SecretKey),
+    pub nonce: u64,
+}
+
+impl Account {
+    /// Conta cujo endereço deriva da chave
+    pub fn new() -> Self {
+        let keys = keypair();
+        Self {
+            address: derive_address(keys.0.as_bytes()),
+            keys,
+            nonce: 0,
+        }
+    }
+
+    pub fn public_key(&self) -> Vec<u8> {
+        self.keys.0.as_bytes().to_vec()
+    }
+
+    /// Operação assinada com o próximo nonce
+    pub fn operation(&mut self, kind: OperationKind) -> Operation {
+        self.nonce += 1;
+        let mut operation =
+            Operation::new(kind, self.address.clone(), self.nonce, self.public_key()).unwrap();
+        operation.sign(&self.keys.1).unwrap();
+        operation
+    }
+
+    /// Transferência do token 0 com o próximo nonce
+    pub fn transfer(&mut self, to: &str, amount: u64) -> Transaction {
+        self.transfer_token(0, to, amount)
+    }
+
+    pub fn transfer_token(&mut self, token_id: u64, to: &str, amount: u64) -> Transaction {
+        self.nonce += 1;
+        let mut tx = Transaction::new(
+            self.address.clone(),
+            to.to_string(),
+            amount,
+            self.public_key(),
+        )
+        .unwrap();
+        tx.token_id = token_id;
+        tx.nonce = self.nonce;
+        tx.sign(&self.keys.1).unwrap();
+        tx
+    }
+}
+
+impl Default for Account {
+    fn default() -> Self {
+        Self::new()
+    }
+}
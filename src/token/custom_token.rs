@@ -1,9 +1,11 @@
-use crate::error::Error;
+use super::fungible::FungibleToken;
+use crate::error::{Error, TransactionError};
 use crate::transaction::Transaction;
-use anyhow::Result;
-use pqcrypto_dilithium::dilithium5::{self, detached_sign, verify_detached_signature};
+use crate::utils::dilithium;
+use pqcrypto_dilithium::dilithium5::{detached_sign, verify_detached_signature};
 use pqcrypto_traits::sign::{DetachedSignature, PublicKey, SecretKey};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CustomToken {
@@ -12,6 +14,9 @@ pub struct CustomToken {
     pub symbol: String,
     pub supply: u64,
     pub owner: String,
+    /// Saldos já distribuídos a partir de `supply`
+    #[serde(default)]
+    pub balances: HashMap<String, u64>,
     pub signature: Vec<u8>,
     #[serde(skip)]
     public_key: Option<Vec<u8>>,
@@ -21,9 +26,15 @@ pub struct CustomToken {
 }
 
 impl CustomToken {
-    pub fn new(id: u32, name: String, symbol: String, supply: u64, owner: String) -> Result<Self> {
+    pub fn new(
+        id: u32,
+        name: String,
+        symbol: String,
+        supply: u64,
+        owner: String,
+    ) -> Result<Self, Error> {
         if id == 0 {
-            return Err(anyhow::anyhow!("ID inválido"));
+            return Err(Error::InvalidInput("ID inválido".to_string()));
         }
 
         // Gerar chaves pública e secreta
@@ -35,6 +46,7 @@ impl CustomToken {
             symbol,
             supply,
             owner,
+            balances: HashMap::new(),
             signature: Vec::new(),
             public_key: Some(public_key),
             secret_key: Some(secret_key),
@@ -42,19 +54,19 @@ impl CustomToken {
         })
     }
 
-    fn generate_keys() -> Result<(Vec<u8>, Vec<u8>)> {
-        let (pk, sk) = dilithium5::keypair();
+    fn generate_keys() -> Result<(Vec<u8>, Vec<u8>), Error> {
+        let (pk, sk) = dilithium::keypair();
         Ok((pk.as_bytes().to_vec(), sk.as_bytes().to_vec()))
     }
 
-    pub fn sign_transaction(&mut self, data: &str) -> Result<()> {
+    pub fn sign_transaction(&mut self, data: &str) -> Result<(), Error> {
         let secret_key = self
             .secret_key
             .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Chave secreta não disponível"))?;
+            .ok_or_else(|| Error::CryptoError("Chave secreta não disponível".to_string()))?;
 
         let sk = SecretKey::from_bytes(secret_key)
-            .map_err(|e| anyhow::anyhow!("Falha ao processar chave secreta: {}", e))?;
+            .map_err(|e| Error::CryptoError(format!("Falha ao processar chave secreta: {}", e)))?;
 
         let signature = detached_sign(data.as_bytes(), &sk);
         self.signature = signature.as_bytes().to_vec();
@@ -62,33 +74,33 @@ impl CustomToken {
         Ok(())
     }
 
-    pub fn transfer(&mut self, _: String, amount: u64) -> Result<()> {
+    /// Transfere `amount` do dono para `to`
+    pub fn transfer(&mut self, to: String, amount: u64) -> Result<(), Error> {
+        let owner = self.owner.clone();
+        self.transfer_between(&owner, &to, amount)
+    }
+
+    fn transfer_between(&mut self, from: &str, to: &str, amount: u64) -> Result<(), Error> {
         if amount == 0 {
-            return Err(anyhow::anyhow!("Quantidade deve ser maior que zero"));
+            return Err(Error::InvalidAmount);
         }
-        if self.supply < amount {
-            return Err(anyhow::anyhow!("Saldo insuficiente"));
-        }
-
-        self.supply -= amount;
-        Ok(())
+        FungibleToken::transfer(self, from, to, amount).map_err(|e| match e {
+            TransactionError::InsufficientFunds => Error::InsufficientBalance,
+            other => Error::TransactionError(Box::new(other)),
+        })
     }
 
-    pub fn verify_signature(&self, data: &str) -> Result<bool> {
+    pub fn verify_signature(&self, data: &str) -> Result<bool, Error> {
         if self.signature.is_empty() {
-            return Err(anyhow::anyhow!("Assinatura não presente"));
+            return Err(Error::InvalidSignature);
         }
 
-        let public_key = self
-            .public_key
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Chave pública não disponível"))?;
+        let public_key = self.public_key.as_ref().ok_or(Error::InvalidPublicKey)?;
 
-        let pk = PublicKey::from_bytes(public_key)
-            .map_err(|e| anyhow::anyhow!("Falha ao processar chave pública: {}", e))?;
+        let pk = PublicKey::from_bytes(public_key).map_err(|_| Error::InvalidPublicKey)?;
 
-        let signature = DetachedSignature::from_bytes(&self.signature)
-            .map_err(|e| anyhow::anyhow!("Falha ao processar assinatura: {}", e))?;
+        let signature =
+            DetachedSignature::from_bytes(&self.signature).map_err(|_| Error::InvalidSignature)?;
 
         Ok(verify_detached_signature(&signature, data.as_bytes(), &pk).is_ok())
     }
@@ -98,15 +110,16 @@ impl CustomToken {
             return Err(Error::DoubleSpending);
         }
 
-        self.transfer(tx.from.clone(), tx.amount)?;
+        self.transfer_between(&tx.from, &tx.to, tx.amount)?;
+        self.processed_transactions.insert(tx.hash.clone());
         Ok(())
     }
 
-    pub fn export_public_key(&self) -> Result<Vec<u8>> {
+    pub fn export_public_key(&self) -> Result<Vec<u8>, Error> {
         self.public_key
             .as_ref()
             .cloned()
-            .ok_or_else(|| anyhow::anyhow!("Chave pública não disponível"))
+            .ok_or(Error::InvalidPublicKey)
     }
 }
 
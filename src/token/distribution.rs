@@ -0,0 +1,161 @@
+use super::BalanceSnapshot;
+use crate::constants::MAX_AMOUNT;
+use crate::error::TransactionError;
+use crate::transaction::builder::TransactionBuilder;
+use crate::transaction::Transaction;
+use pqcrypto_dilithium::dilithium5::SecretKey;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Repartição de `funding` unidades de `payout_token_id` entre os detentores de
+/// `snapshot_token_id` na altura `height`, proporcional aos saldos da foto.
+///
+/// A soma dos valores devidos mais `dust` é sempre igual a `funding`. Os valores
+/// podem ser pagos de uma vez por `transfers` ou ficar disponíveis para `claim`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Distribution {
+    pub snapshot_token_id: u64,
+    pub height: u64,
+    pub payout_token_id: u64,
+    pub funding: u64,
+    /// Valor devido a cada detentor, ainda não reclamado
+    entitlements: BTreeMap<String, u64>,
+    /// Parcelas abaixo do pagamento mínimo, que ficam com quem financia
+    dust: u64,
+}
+
+impl Distribution {
+    /// Reparte `funding` pelo método do maior resto: cada detentor recebe a parte
+    /// inteira da sua fração e as unidades que sobram do arredondamento vão, uma a
+    /// uma, para os maiores restos (empates resolvidos pela ordem de endereço).
+    /// Parcelas menores que `min_payout` viram `dust`.
+    pub fn pro_rata(
+        snapshot: &BalanceSnapshot,
+        payout_token_id: u64,
+        funding: u64,
+        min_payout: u64,
+    ) -> Result<Self, TransactionError> {
+        if funding == 0 {
+            return Err(TransactionError::ValorInvalido);
+        }
+        let total: u128 = snapshot
+            .balances()
+            .values()
+            .map(|balance| u128::from(*balance))
+            .sum();
+        if total == 0 {
+            return Err(TransactionError::InvalidParameter(format!(
+                "Token {} sem detentores na altura {}",
+                snapshot.token_id(),
+                snapshot.height()
+            )));
+        }
+
+        let mut shares: Vec<(&String, u64, u128)> = snapshot
+            .balances()
+            .iter()
+            .map(|(address, balance)| {
+                let weighted = u128::from(funding) * u128::from(*balance);
+                // A parte inteira nunca passa de `funding`
+                (address, (weighted / total) as u64, weighted % total)
+            })
+            .collect();
+
+        let assigned: u64 = shares.iter().map(|(_, amount, _)| amount).sum();
+        let leftover = (funding - assigned) as usize;
+        // Ordenação estável: entre restos iguais vale a ordem de endereço do mapa
+        let mut by_remainder: Vec<usize> = (0..shares.len()).collect();
+        by_remainder.sort_by(|a, b| shares[*b].2.cmp(&shares[*a].2));
+        for index in by_remainder.into_iter().take(leftover) {
+            shares[index].1 += 1;
+        }
+
+        let mut entitlements = BTreeMap::new();
+        let mut dust = 0;
+        for (address, amount, _) in shares {
+            if amount == 0 {
+                continue;
+            }
+            if amount < min_payout {
+                dust += amount;
+            } else {
+                entitlements.insert(address.clone(), amount);
+            }
+        }
+
+        Ok(Self {
+            snapshot_token_id: snapshot.token_id(),
+            height: snapshot.height(),
+            payout_token_id,
+            funding,
+            entitlements,
+            dust,
+        })
+    }
+
+    pub fn entitlement(&self, address: &str) -> u64 {
+        self.entitlements.get(address).copied().unwrap_or(0)
+    }
+
+    /// Valores ainda devidos, por endereço
+    pub fn entitlements(&self) -> &BTreeMap<String, u64> {
+        &self.entitlements
+    }
+
+    pub fn dust(&self) -> u64 {
+        self.dust
+    }
+
+    /// Soma dos valores ainda não reclamados
+    pub fn outstanding(&self) -> u64 {
+        self.entitlements.values().sum()
+    }
+
+    /// Retira o valor devido a `address`, que deixa de constar na distribuição
+    pub fn claim(&mut self, address: &str) -> Result<u64, TransactionError> {
+        self.entitlements.remove(address).ok_or_else(|| {
+            TransactionError::InvalidParameter(format!("Nada a receber para {}", address))
+        })
+    }
+
+    /// Transferências assinadas por `from` pagando todos os valores devidos, com
+    /// nonces consecutivos a partir de `first_nonce`.
+    ///
+    /// A parte do próprio `from` não gera transferência, e valores acima de
+    /// `MAX_AMOUNT` são divididos em várias transferências ao mesmo detentor.
+    pub fn transfers(
+        &self,
+        from: &str,
+        public_key: &[u8],
+        secret_key: &SecretKey,
+        first_nonce: u64,
+    ) -> Result<Vec<Transaction>, TransactionError> {
+        let mut nonce = first_nonce;
+        let mut transfers = Vec::new();
+        for (address, amount) in &self.entitlements {
+            if address == from {
+                continue;
+            }
+            let mut remaining = *amount;
+            while remaining > 0 {
+                let amount = remaining.min(MAX_AMOUNT);
+                let mut tx = TransactionBuilder::new()
+                    .token_id(self.payout_token_id)
+                    .from(from.to_string())
+                    .to(address.clone())
+                    .amount(amount)
+                    .nonce(nonce)
+                    .public_key(public_key.to_vec())
+                    .build()?;
+                tx.sign(secret_key)?;
+                transfers.push(tx);
+                nonce = nonce
+                    .checked_add(1)
+                    .ok_or(TransactionError::NonceOverflow)?;
+                remaining -= amount;
+            }
+        }
+        Ok(transfers)
+    }
+}
@@ -0,0 +1,133 @@
+use super::custom_token::CustomToken;
+use super::Token;
+use crate::error::TransactionError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Dados descritivos comuns a qualquer token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TokenMetadata {
+    pub id: u64,
+    pub name: String,
+    pub symbol: String,
+    pub owner: String,
+}
+
+/// Interface de token fungível usada pela transição de estado, pelo RPC e pela
+/// carteira, sem distinção entre `Token` e `CustomToken`
+pub trait FungibleToken {
+    fn metadata(&self) -> TokenMetadata;
+
+    fn total_supply(&self) -> u64;
+
+    fn balance_of(&self, address: &str) -> u64;
+
+    /// Move `amount` de `from` para `to`; sem saldo suficiente nada muda
+    fn transfer(&mut self, from: &str, to: &str, amount: u64) -> Result<(), TransactionError>;
+}
+
+/// Débito e crédito sobre um mapa de saldos, conferidos antes de qualquer escrita
+pub(crate) fn move_balance(
+    balances: &mut HashMap<String, u64>,
+    from: &str,
+    to: &str,
+    amount: u64,
+) -> Result<(), TransactionError> {
+    let sender = balances.get(from).copied().unwrap_or(0);
+    let remaining = sender
+        .checked_sub(amount)
+        .ok_or(TransactionError::InsufficientFunds)?;
+    if from == to {
+        return Ok(());
+    }
+    let recipient = balances.get(to).copied().unwrap_or(0);
+    let credited = recipient
+        .checked_add(amount)
+        .ok_or(TransactionError::ValorInvalido)?;
+
+    balances.insert(from.to_string(), remaining);
+    balances.insert(to.to_string(), credited);
+    Ok(())
+}
+
+impl FungibleToken for Token {
+    fn metadata(&self) -> TokenMetadata {
+        TokenMetadata {
+            id: self.id,
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            owner: self.creator.clone(),
+        }
+    }
+
+    fn total_supply(&self) -> u64 {
+        self.total_supply
+    }
+
+    fn balance_of(&self, address: &str) -> u64 {
+        self.balances.get(address).copied().unwrap_or(0)
+    }
+
+    fn transfer(&mut self, from: &str, to: &str, amount: u64) -> Result<(), TransactionError> {
+        move_balance(&mut self.balances, from, to, amount)
+    }
+}
+
+/// Em `CustomToken`, `supply` é a parte ainda não distribuída, que pertence ao
+/// dono; o que já saiu dela fica em `balances`
+impl FungibleToken for CustomToken {
+    fn metadata(&self) -> TokenMetadata {
+        TokenMetadata {
+            id: u64::from(self.id),
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            owner: self.owner.clone(),
+        }
+    }
+
+    fn total_supply(&self) -> u64 {
+        self.balances
+            .values()
+            .fold(self.supply, |total, balance| total.saturating_add(*balance))
+    }
+
+    fn balance_of(&self, address: &str) -> u64 {
+        let held = self.balances.get(address).copied().unwrap_or(0);
+        if address == self.owner {
+            held.saturating_add(self.supply)
+        } else {
+            held
+        }
+    }
+
+    fn transfer(&mut self, from: &str, to: &str, amount: u64) -> Result<(), TransactionError> {
+        if from != self.owner {
+            return move_balance(&mut self.balances, from, to, amount);
+        }
+        if self.balance_of(from) < amount {
+            return Err(TransactionError::InsufficientFunds);
+        }
+        if from == to {
+            return Ok(());
+        }
+        let credited = self
+            .balances
+            .get(to)
+            .copied()
+            .unwrap_or(0)
+            .checked_add(amount)
+            .ok_or(TransactionError::ValorInvalido)?;
+
+        // O dono gasta primeiro o que não foi distribuído
+        let from_supply = amount.min(self.supply);
+        self.supply -= from_supply;
+        if amount > from_supply {
+            let held = self.balances.get(from).copied().unwrap_or(0);
+            self.balances
+                .insert(from.to_string(), held - (amount - from_supply));
+        }
+        self.balances.insert(to.to_string(), credited);
+        Ok(())
+    }
+}
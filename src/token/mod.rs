@@ -1,5 +1,15 @@
 pub mod custom_token;
+pub mod distribution;
+pub mod fungible;
+pub mod policy;
+mod snapshot;
 pub mod token_builder;
 mod token_impl;
+pub mod vesting;
 
+pub use distribution::Distribution;
+pub use fungible::{FungibleToken, TokenMetadata};
+pub use policy::{TransferCheck, TransferPolicy};
+pub use snapshot::BalanceSnapshot;
 pub use token_impl::Token;
+pub use vesting::VestingSchedule;
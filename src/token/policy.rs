@@ -0,0 +1,198 @@
+use crate::error::TransactionError;
+use crate::transaction::TransferRule;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use wasmer::{imports, Function, Instance, Module, Store, WasmerEnv};
+
+/// Dados de uma transferência apresentados às políticas do token
+#[derive(Debug, Clone, Copy)]
+pub struct TransferCheck<'a> {
+    pub token_id: u64,
+    pub from: &'a str,
+    pub to: &'a str,
+    pub amount: u64,
+    pub from_kyc: bool,
+    pub to_kyc: bool,
+}
+
+/// Regra avaliada na transição de estado de cada transferência de um token.
+///
+/// Implementações recusam a transferência devolvendo erro; a transação é então
+/// descartada do bloco como qualquer outra que falhe na aplicação.
+pub trait TransferPolicy: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn check(&self, transfer: &TransferCheck<'_>) -> Result<(), TransactionError>;
+}
+
+fn rejected(policy: &dyn TransferPolicy, reason: impl std::fmt::Display) -> TransactionError {
+    TransactionError::InvalidParameter(format!(
+        "Transferência recusada pela política {}: {}",
+        policy.name(),
+        reason
+    ))
+}
+
+pub struct MaxTransferAmount(pub u64);
+
+impl TransferPolicy for MaxTransferAmount {
+    fn name(&self) -> &'static str {
+        "max_transfer_amount"
+    }
+
+    fn check(&self, transfer: &TransferCheck<'_>) -> Result<(), TransactionError> {
+        if transfer.amount > self.0 {
+            return Err(rejected(
+                self,
+                format_args!("valor {} acima do limite {}", transfer.amount, self.0),
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub struct AllowList(pub HashSet<String>);
+
+impl TransferPolicy for AllowList {
+    fn name(&self) -> &'static str {
+        "allow_list"
+    }
+
+    fn check(&self, transfer: &TransferCheck<'_>) -> Result<(), TransactionError> {
+        match [transfer.from, transfer.to]
+            .into_iter()
+            .find(|address| !self.0.contains(*address))
+        {
+            Some(address) => Err(rejected(self, format_args!("{} fora da lista", address))),
+            None => Ok(()),
+        }
+    }
+}
+
+pub struct KycRequired;
+
+impl TransferPolicy for KycRequired {
+    fn name(&self) -> &'static str {
+        "kyc_required"
+    }
+
+    fn check(&self, transfer: &TransferCheck<'_>) -> Result<(), TransactionError> {
+        if !transfer.from_kyc {
+            return Err(rejected(self, format_args!("{} sem KYC", transfer.from)));
+        }
+        if !transfer.to_kyc {
+            return Err(rejected(self, format_args!("{} sem KYC", transfer.to)));
+        }
+        Ok(())
+    }
+}
+
+/// Política delegada a um contrato WASM, que exporta
+/// `check_transfer(amount: i64, from_kyc: i32, to_kyc: i32) -> i32`.
+///
+/// O único import oferecido é `kybelith.oracle_value(feed_id: i64) -> i64`, o valor
+/// agregado do oráculo (saturado em `i64::MAX`) ou -1 se ele ainda não tem valor.
+///
+/// O contrato roda sem medição de combustível; por isso o registro de políticas
+/// com contrato exige o papel de operador.
+pub struct ContractPolicy {
+    pub code: Vec<u8>,
+    /// Valores agregados dos oráculos no momento da transferência
+    pub oracle: BTreeMap<u64, u64>,
+}
+
+#[derive(Clone, WasmerEnv)]
+struct OracleEnv {
+    values: Arc<BTreeMap<u64, u64>>,
+}
+
+fn oracle_value(env: &OracleEnv, feed_id: i64) -> i64 {
+    u64::try_from(feed_id)
+        .ok()
+        .and_then(|feed_id| env.values.get(&feed_id))
+        .map_or(-1, |value| i64::try_from(*value).unwrap_or(i64::MAX))
+}
+
+impl ContractPolicy {
+    fn run(&self, transfer: &TransferCheck<'_>) -> Result<i32, String> {
+        let store = Store::default();
+        let module = Module::new(&store, &self.code)
+            .map_err(|e| format!("Falha ao carregar o módulo Wasm: {}", e))?;
+        let env = OracleEnv {
+            values: Arc::new(self.oracle.clone()),
+        };
+        let imports = imports! {
+            "kybelith" => {
+                "oracle_value" => Function::new_native_with_env(&store, env, oracle_value),
+            },
+        };
+        let instance = Instance::new(&module, &imports)
+            .map_err(|e| format!("Falha ao instanciar o módulo Wasm: {}", e))?;
+        let check = instance
+            .exports
+            .get_native_function::<(i64, i32, i32), i32>("check_transfer")
+            .map_err(|e| format!("Função 'check_transfer' não encontrada: {}", e))?;
+        // Valores acima de i64::MAX chegam ao contrato saturados
+        let amount = i64::try_from(transfer.amount).unwrap_or(i64::MAX);
+        check
+            .call(
+                amount,
+                i32::from(transfer.from_kyc),
+                i32::from(transfer.to_kyc),
+            )
+            .map_err(|e| format!("Falha ao executar 'check_transfer': {}", e))
+    }
+}
+
+impl TransferPolicy for ContractPolicy {
+    fn name(&self) -> &'static str {
+        "contract"
+    }
+
+    fn check(&self, transfer: &TransferCheck<'_>) -> Result<(), TransactionError> {
+        match self.run(transfer) {
+            Ok(0) => Ok(()),
+            Ok(code) => Err(rejected(self, format_args!("contrato devolveu {}", code))),
+            Err(e) => Err(rejected(self, e)),
+        }
+    }
+}
+
+/// Política correspondente a uma regra registrada na cadeia
+pub fn policy_for(rule: &TransferRule) -> Box<dyn TransferPolicy> {
+    match rule {
+        TransferRule::MaxTransferAmount(limit) => Box::new(MaxTransferAmount(*limit)),
+        TransferRule::AllowList(addresses) => {
+            Box::new(AllowList(addresses.iter().cloned().collect()))
+        }
+        TransferRule::RequireKyc => Box::new(KycRequired),
+        TransferRule::Contract { code } => Box::new(ContractPolicy {
+            code: code.clone(),
+            oracle: BTreeMap::new(),
+        }),
+    }
+}
+
+/// Avalia as regras na ordem em que foram registradas, parando na primeira recusa
+pub fn evaluate(
+    rules: &[TransferRule],
+    transfer: &TransferCheck<'_>,
+) -> Result<(), TransactionError> {
+    evaluate_with_oracle(rules, transfer, &BTreeMap::new())
+}
+
+/// Como `evaluate`, com os contratos enxergando os valores de oráculo em `oracle`
+pub fn evaluate_with_oracle(
+    rules: &[TransferRule],
+    transfer: &TransferCheck<'_>,
+    oracle: &BTreeMap<u64, u64>,
+) -> Result<(), TransactionError> {
+    rules.iter().try_for_each(|rule| match rule {
+        TransferRule::Contract { code } => ContractPolicy {
+            code: code.clone(),
+            oracle: oracle.clone(),
+        }
+        .check(transfer),
+        _ => policy_for(rule).check(transfer),
+    })
+}
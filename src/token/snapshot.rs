@@ -0,0 +1,60 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Saldos de um token congelados após o bloco em `height`.
+///
+/// Base para distribuição de dividendos e para o poder de voto na governança;
+/// não muda quando a cadeia avança, e só endereços com saldo aparecem no mapa.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct BalanceSnapshot {
+    token_id: u64,
+    height: u64,
+    balances: BTreeMap<String, u64>,
+    total: u64,
+}
+
+impl BalanceSnapshot {
+    pub(crate) fn new(token_id: u64, height: u64, balances: BTreeMap<String, u64>) -> Self {
+        let total = balances
+            .values()
+            .fold(0u64, |total, balance| total.saturating_add(*balance));
+        Self {
+            token_id,
+            height,
+            balances,
+            total,
+        }
+    }
+
+    pub fn token_id(&self) -> u64 {
+        self.token_id
+    }
+
+    pub fn height(&self) -> u64 {
+        self.height
+    }
+
+    pub fn balance_of(&self, address: &str) -> u64 {
+        self.balances.get(address).copied().unwrap_or(0)
+    }
+
+    /// Saldos por endereço, em ordem de endereço
+    pub fn balances(&self) -> &BTreeMap<String, u64> {
+        &self.balances
+    }
+
+    /// Soma dos saldos, denominador das frações de cada detentor
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Quantidade de endereços com saldo
+    pub fn len(&self) -> usize {
+        self.balances.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.balances.is_empty()
+    }
+}
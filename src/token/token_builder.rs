@@ -1,5 +1,5 @@
 use super::Token;
-use anyhow::Result;
+use crate::error::Error;
 
 pub struct TokenBuilder {
     name: Option<String>,
@@ -38,22 +38,22 @@ impl TokenBuilder {
         self
     }
 
-    pub fn build(self) -> Result<Token> {
+    pub fn build(self) -> Result<Token, Error> {
         let name = self
             .name
-            .ok_or_else(|| anyhow::anyhow!("Nome não definido"))?;
+            .ok_or_else(|| Error::InvalidInput("Nome não definido".to_string()))?;
 
         let symbol = self
             .symbol
-            .ok_or_else(|| anyhow::anyhow!("Símbolo não definido"))?;
+            .ok_or_else(|| Error::InvalidInput("Símbolo não definido".to_string()))?;
 
         let total_supply = self
             .total_supply
-            .ok_or_else(|| anyhow::anyhow!("Fornecimento total não definido"))?;
+            .ok_or_else(|| Error::InvalidInput("Fornecimento total não definido".to_string()))?;
 
         let creator = self
             .creator
-            .ok_or_else(|| anyhow::anyhow!("Criador não definido"))?;
+            .ok_or_else(|| Error::InvalidInput("Criador não definido".to_string()))?;
 
         Ok(Token::new(name, symbol, total_supply, creator)?)
     }
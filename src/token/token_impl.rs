@@ -1,3 +1,5 @@
+use super::BalanceSnapshot;
+use crate::blockchain::TransactionIndex;
 use crate::quantum_crypto::quantum_crypto::Permission;
 use crate::quantum_crypto::OqsError;
 use crate::quantum_crypto::QuantumCrypto;
@@ -60,6 +62,19 @@ impl Token {
         })
     }
 
+    /// Cópia do token, com o `QuantumCrypto` recriado por `try_clone`
+    pub fn try_clone(&self) -> Result<Self, OqsError> {
+        Ok(Token {
+            id: self.id,
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            total_supply: self.total_supply,
+            balances: self.balances.clone(),
+            creator: self.creator.clone(),
+            quantum_crypto: self.quantum_crypto.try_clone()?,
+        })
+    }
+
     pub fn balance_of(&self, address: &String) -> u64 {
         self.balances.get(address).copied().unwrap_or(0)
     }
@@ -122,6 +137,22 @@ impl Token {
         Ok(token)
     }
 
+    /// Saldos deste token após o bloco em `height`, lidos do histórico de saldos do
+    /// índice. Falha se o índice só começou a acompanhar o token depois dessa altura.
+    pub fn snapshot(
+        &self,
+        height: u64,
+        index: &TransactionIndex,
+    ) -> Result<BalanceSnapshot, crate::error::Error> {
+        let balances = index.balances_at(self.id, height).ok_or_else(|| {
+            crate::error::Error::InvalidInput(format!(
+                "Histórico de saldos do token {} não cobre a altura {}",
+                self.id, height
+            ))
+        })?;
+        Ok(BalanceSnapshot::new(self.id, height, balances))
+    }
+
     // Método para verificar a validade do token
     pub fn verify(&self) -> Result<bool, OqsError> {
         let data = format!("{}{}{}", self.name, self.symbol, self.total_supply).into_bytes();
@@ -0,0 +1,46 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Alocação com liberação gradual (cotas de equipe e investidores).
+///
+/// Os tokens saem do saldo de `grantor` na criação e ficam reservados até serem
+/// reclamados; a liberação é linear entre `start` e `start + duration_secs`, mas
+/// nada é liberado antes de `start + cliff_secs`. Os instantes são segundos Unix,
+/// medidos pelo timestamp dos blocos.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct VestingSchedule {
+    pub token_id: u64,
+    pub grantor: String,
+    pub beneficiary: String,
+    pub amount: u64,
+    /// Quanto já foi movido para o saldo do beneficiário
+    pub claimed: u64,
+    pub start: u64,
+    pub cliff_secs: u64,
+    pub duration_secs: u64,
+}
+
+impl VestingSchedule {
+    /// Total liberado no instante `now`, reclamado ou não
+    pub fn released_at(&self, now: u64) -> u64 {
+        let elapsed = now.saturating_sub(self.start);
+        if elapsed < self.cliff_secs {
+            return 0;
+        }
+        if elapsed >= self.duration_secs {
+            return self.amount;
+        }
+        // `elapsed < duration_secs`, então o resultado fica abaixo de `amount`
+        (u128::from(self.amount) * u128::from(elapsed) / u128::from(self.duration_secs)) as u64
+    }
+
+    /// Liberado e ainda não reclamado
+    pub fn claimable_at(&self, now: u64) -> u64 {
+        self.released_at(now).saturating_sub(self.claimed)
+    }
+
+    /// Ainda não liberado
+    pub fn locked_at(&self, now: u64) -> u64 {
+        self.amount - self.released_at(now)
+    }
+}
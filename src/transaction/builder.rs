@@ -1,22 +1,39 @@
 use crate::constants::MAX_SIGNATURE_SIZE;
-use crate::constants::{HASH_SALT, MAX_ADDRESS_LENGTH, MIN_ADDRESS_LENGTH, TIMESTAMP_WINDOW};
+use crate::constants::HASH_SALT;
 use crate::constants::{MAX_AMOUNT, MIN_AMOUNT};
 use crate::error::TransactionError;
+#[cfg(feature = "node")]
 use crate::transaction::secure_transaction::SecureTransaction;
+use crate::transaction::view::TransactionView;
+use crate::utils::address::Address;
+use crate::utils::canonical_json::{hex_field, CanonicalJson};
+use crate::utils::clock::clock;
+use crate::utils::dilithium;
+use crate::utils::versioned::{self, Magic};
+use crate::utils::timestamp_policy::{TimestampContext, TimestampPolicy};
 use bincode::serialize;
 use once_cell::sync::Lazy;
-use pqcrypto_dilithium::dilithium5::verify_detached_signature;
-use pqcrypto_dilithium::dilithium5::{keypair, sign, PublicKey, SecretKey};
+use pqcrypto_dilithium::dilithium5::{detached_sign, sign, PublicKey, SecretKey};
 use pqcrypto_traits::sign::DetachedSignature as PqcDetachedSignature;
 use pqcrypto_traits::sign::SignedMessage;
-use regex::Regex;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
 use std::collections::HashMap;
 use std::sync::Mutex;
 use tracing::warn;
 use zeroize::Zeroize;
 
-#[derive(Debug, Serialize, Deserialize, Zeroize, Clone)]
+/// Versão da codificação gravada por `Transaction::to_versioned_bytes`.
+///
+/// A versão 0 é o bincode puro, sem envelope. Uma mudança de layout (um campo
+/// novo, por exemplo) incrementa a versão e mantém em `from_versioned_bytes` a
+/// decodificação do layout anterior, convertido para a struct atual.
+pub const TRANSACTION_ENCODING_VERSION: u8 = 1;
+
+const TRANSACTION_MAGIC: &Magic = b"KTX";
+
+#[derive(Debug, Serialize, Deserialize, Zeroize, Clone, JsonSchema)]
 pub struct Transaction {
     pub token_id: u64,
     pub from: String,
@@ -24,12 +41,16 @@ pub struct Transaction {
     pub amount: u64,
     pub timestamp: i64,
     pub nonce: u64,
+    #[cfg_attr(feature = "cbor", serde(with = "serde_bytes"), schemars(with = "Vec<u8>"))]
     pub public_key: Vec<u8>,
+    #[cfg_attr(feature = "cbor", serde(with = "serde_bytes"), schemars(with = "Vec<u8>"))]
     pub signature: Vec<u8>,
+    #[cfg_attr(feature = "cbor", serde(with = "serde_bytes"), schemars(with = "Vec<u8>"))]
     pub transaction_hash: Vec<u8>,
     pub hash: String,
 }
 
+#[cfg(feature = "node")]
 impl From<SecureTransaction> for Transaction {
     fn from(st: SecureTransaction) -> Self {
         let mut transaction = Transaction {
@@ -82,10 +103,7 @@ impl NonceRegistry {
         }
 
         // Verificar o intervalo mínimo de atualização
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|_| TransactionError::TimestampInvalid)?
-            .as_secs() as i64;
+        let now = clock().now_secs();
 
         let mut last_update_map = self.last_update.lock().unwrap();
         if let Some(last_time) = last_update_map.get(address) {
@@ -109,18 +127,22 @@ impl Transaction {
         amount: u64,
         public_key: Vec<u8>,
     ) -> Result<Self, TransactionError> {
-        if from.len() < MIN_ADDRESS_LENGTH
-            || from.len() > MAX_ADDRESS_LENGTH
-            || to.len() < MIN_ADDRESS_LENGTH
-            || to.len() > MAX_ADDRESS_LENGTH
-        {
-            return Err(TransactionError::AddressFormatInvalid);
-        }
+        let timestamp = clock().now_secs();
 
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|_| TransactionError::TimestampInvalid)?
-            .as_secs() as i64;
+        Self::with_timestamp(from, to, amount, public_key, timestamp)
+    }
+
+    /// Como `new`, mas com o timestamp informado pelo chamador (carteiras em
+    /// wasm32-unknown-unknown não têm relógio do sistema)
+    pub fn with_timestamp(
+        from: String,
+        to: String,
+        amount: u64,
+        public_key: Vec<u8>,
+        timestamp: i64,
+    ) -> Result<Self, TransactionError> {
+        Address::parse(&from)?;
+        Address::parse(&to)?;
 
         let mut transaction = Transaction {
             token_id: 0,
@@ -162,11 +184,7 @@ impl Transaction {
         nonce_registry.validate_nonce(&self.from, self.nonce)?;
 
         // Verificação de tamanho total da transação
-        if serialize(self)
-            .map_err(|_| TransactionError::InvalidDataFormat)?
-            .len()
-            > MAX_TRANSACTION_SIZE
-        {
+        if self.encoded_size() > MAX_TRANSACTION_SIZE {
             return Err(TransactionError::DataSizeExceeded);
         }
 
@@ -174,15 +192,8 @@ impl Transaction {
     }
 
     pub fn validate_address(&self) -> Result<(), TransactionError> {
-        let re = Regex::new(&format!(
-            "^[a-zA-Z0-9]{{{},{}}}$",
-            MIN_ADDRESS_LENGTH, MAX_ADDRESS_LENGTH
-        ))
-        .map_err(|_| TransactionError::AddressFormatInvalid)?;
-
-        if !re.is_match(&self.from) || !re.is_match(&self.to) {
-            return Err(TransactionError::AddressFormatInvalid);
-        }
+        Address::parse(&self.from)?;
+        Address::parse(&self.to)?;
 
         if self.from == self.to {
             return Err(TransactionError::AddressFormatInvalid);
@@ -210,38 +221,95 @@ impl Transaction {
     }
 
     pub fn validate_timestamp(&self) -> Result<(), TransactionError> {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|_| TransactionError::TimestampInvalid)?
-            .as_secs() as i64;
-
-        match now.checked_sub(self.timestamp) {
-            Some(diff) if diff.abs() <= TIMESTAMP_WINDOW => Ok(()),
-            _ => {
-                warn!("Timestamp inválido detectado. From: {}", self.from);
-                Err(TransactionError::TimestampInvalid)
-            }
-        }
+        TimestampPolicy::for_context(TimestampContext::Transaction)
+            .validate_secs(self.timestamp)
+            .map_err(|violation| {
+                warn!("{}. From: {}", violation, self.from);
+                TransactionError::TimestampInvalid
+            })
     }
 
-    pub fn verify(&self, public_key: &PublicKey) -> Result<(), TransactionError> {
+    /// Assina a transação com Dilithium5 (assinatura destacada) e atualiza o hash
+    pub fn sign(&mut self, secret_key: &SecretKey) -> Result<(), TransactionError> {
+        self.validate_address()?;
         let data = self.serialize_for_signing()?;
 
-        // Validate signature size before attempting conversion
+        let signature = detached_sign(&data, secret_key);
+        self.signature = signature.as_bytes().to_vec();
+
         if self.signature.len() > MAX_SIGNATURE_SIZE {
             return Err(TransactionError::SignatureSizeExceeded);
         }
 
-        let signature =
-            pqcrypto_dilithium::dilithium5::DetachedSignature::from_bytes(&self.signature)
-                .map_err(|_| {
-                    TransactionError::InvalidSignatures("Invalid signature".to_string())
-                })?;
+        self.update_hash()?;
+        Ok(())
+    }
 
-        verify_detached_signature(&signature, &data, public_key)
-            .map_err(|_| TransactionError::InvalidSignatures("Invalid signature".to_string()))?;
+    pub fn verify(&self, public_key: &PublicKey) -> Result<(), TransactionError> {
+        self.view().verify(public_key)
+    }
 
-        Ok(())
+    /// Decodifica uma transação recebida da rede (entrada não confiável)
+    pub fn deserialize_from_bytes(data: &[u8]) -> Result<Self, TransactionError> {
+        use crate::constants::MAX_TRANSACTION_SIZE;
+        use bincode::Options;
+
+        if data.len() > MAX_TRANSACTION_SIZE {
+            warn!("Tamanho de dados excedido na desserialização: {}", data.len());
+            return Err(TransactionError::DataSizeExceeded);
+        }
+
+        bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .with_limit(MAX_TRANSACTION_SIZE as u64)
+            .deserialize(data)
+            .map_err(|_| TransactionError::InvalidDataFormat)
+    }
+
+    /// Codifica em CBOR (ver `utils::cbor`)
+    #[cfg(feature = "cbor")]
+    pub fn to_cbor(&self) -> Result<Vec<u8>, TransactionError> {
+        crate::utils::cbor::encode(self).map_err(TransactionError::InvalidFormat)
+    }
+
+    /// Decodifica uma transação em CBOR, com o mesmo limite de tamanho da forma bincode
+    #[cfg(feature = "cbor")]
+    pub fn from_cbor(data: &[u8]) -> Result<Self, TransactionError> {
+        use crate::constants::MAX_TRANSACTION_SIZE;
+
+        if data.len() > MAX_TRANSACTION_SIZE {
+            return Err(TransactionError::DataSizeExceeded);
+        }
+        crate::utils::cbor::decode(data, MAX_TRANSACTION_SIZE)
+            .map_err(|_| TransactionError::InvalidDataFormat)
+    }
+
+    /// Codifica para armazenamento, com o prefixo de versão
+    pub fn to_versioned_bytes(&self) -> Result<Vec<u8>, TransactionError> {
+        let payload = serialize(self).map_err(|_| TransactionError::InvalidDataFormat)?;
+        Ok(versioned::tag(
+            TRANSACTION_MAGIC,
+            TRANSACTION_ENCODING_VERSION,
+            &payload,
+        ))
+    }
+
+    /// Decodifica qualquer versão conhecida, inclusive bytes sem envelope
+    pub fn from_versioned_bytes(data: &[u8]) -> Result<Self, TransactionError> {
+        match versioned::split(TRANSACTION_MAGIC, data) {
+            None => Self::deserialize_from_bytes(data),
+            Some((1, payload)) => Self::deserialize_from_bytes(payload),
+            Some((version, _)) => Err(TransactionError::InvalidFormat(format!(
+                "Codificação de transação na versão {}; suportadas até {}",
+                version, TRANSACTION_ENCODING_VERSION
+            ))),
+        }
+    }
+
+    /// Tamanho em bytes da codificação bincode da transação, a mesma usada na
+    /// rede e comparada com `Limits::max_transaction_size`
+    pub fn encoded_size(&self) -> usize {
+        bincode::serialized_size(self).map_or(usize::MAX, |size| size as usize)
     }
 
     pub fn size(&self) -> usize {
@@ -257,28 +325,26 @@ impl Transaction {
     }
 
     pub fn serialize_for_signing(&self) -> Result<Vec<u8>, TransactionError> {
-        #[derive(Serialize)]
-        struct SignableData {
-            token_id: u64,
-            from: String,
-            to: String,
-            amount: u64,
-            timestamp: i64,
-            nonce: u64,
-            public_key: Vec<u8>,
-        }
+        self.view().serialize_for_signing()
+    }
 
-        let data = SignableData {
-            token_id: self.token_id,
-            from: self.from.clone(),
-            to: self.to.clone(),
-            amount: self.amount,
-            timestamp: self.timestamp,
-            nonce: self.nonce,
-            public_key: self.public_key.clone(),
-        };
+    /// Codificação canônica de assinatura escrita em um buffer reaproveitável
+    pub fn serialize_for_signing_into(&self, buf: &mut Vec<u8>) -> Result<(), TransactionError> {
+        self.view().serialize_for_signing_into(buf)
+    }
+
+    /// Identificador estável: hex do SHA3-256 da codificação de assinatura seguida
+    /// da assinatura. Diferente de `transaction_hash`, não depende de chaves efêmeras.
+    pub fn txid(&self) -> String {
+        let mut hasher = Sha3_256::new();
+        hasher.update(self.serialize_for_signing().unwrap_or_default());
+        hasher.update(&self.signature);
+        hex::encode(hasher.finalize())
+    }
 
-        bincode::serialize(&data).map_err(|_| TransactionError::InvalidDataFormat)
+    /// Visão emprestada da transação, sem cópia dos campos
+    pub fn view(&self) -> TransactionView<'_> {
+        TransactionView::from(self)
     }
 
     fn calculate_hash(&self) -> Result<Vec<u8>, TransactionError> {
@@ -308,7 +374,7 @@ impl Transaction {
         };
 
         let data = serialize(&hash_data).map_err(|_| TransactionError::InvalidDataFormat)?;
-        static HASH_KEYS: Lazy<(PublicKey, SecretKey)> = Lazy::new(|| keypair());
+        static HASH_KEYS: Lazy<(PublicKey, SecretKey)> = Lazy::new(dilithium::keypair);
         let signature = sign(&data, &HASH_KEYS.1);
         Ok(signature.as_bytes().to_vec())
     }
@@ -384,7 +450,44 @@ impl TransactionBuilder {
         self
     }
 
+    /// Monta a transação; timestamp e nonce não informados (zero) usam os padrões de `Transaction::new`
     pub fn build(self) -> Result<Transaction, TransactionError> {
-        Transaction::new(self.from, self.to, self.amount, self.public_key)
+        let mut transaction = if self.timestamp != 0 {
+            Transaction::with_timestamp(
+                self.from,
+                self.to,
+                self.amount,
+                self.public_key,
+                self.timestamp,
+            )?
+        } else {
+            Transaction::new(self.from, self.to, self.amount, self.public_key)?
+        };
+
+        transaction.token_id = self.token_id;
+        if self.nonce != 0 {
+            transaction.nonce = self.nonce;
+        }
+        transaction.update_hash()?;
+        Ok(transaction)
+    }
+}
+
+impl CanonicalJson for Transaction {
+    const KIND: &'static str = "transaction";
+
+    fn canonical_fields(&self) -> serde_json::Value {
+        serde_json::json!({
+            "token_id": self.token_id,
+            "from": self.from,
+            "to": self.to,
+            "amount": self.amount,
+            "timestamp": self.timestamp,
+            "nonce": self.nonce,
+            "public_key": hex_field(&self.public_key),
+            "signature": hex_field(&self.signature),
+            "transaction_hash": hex_field(&self.transaction_hash),
+            "hash": self.hash,
+        })
     }
 }
@@ -0,0 +1,705 @@
+//! Valores confidenciais: compromissos de Pedersen sobre P-256, provas de intervalo
+//! por decomposição em bits e aberturas cifradas para chaves de visualização Kyber.
+//!
+//! O saldo confidencial de uma conta é um compromisso `v·G + r·H`. Validadores somam
+//! e subtraem compromissos sem conhecer os valores; as provas de intervalo garantem
+//! que nenhum valor (transferido ou restante) seja negativo módulo a ordem do grupo.
+
+use crate::error::TransactionError;
+use crate::transaction::operation::{EncryptedMemo, OperationKind, SealedOpening, MAX_MEMO_SIZE};
+use crate::utils::entropy::{self, entropy};
+use openssl::bn::{BigNum, BigNumContext, BigNumRef};
+use openssl::ec::{EcGroup, EcPoint, EcPointRef, PointConversionForm};
+use openssl::error::ErrorStack;
+use openssl::nid::Nid;
+use oqs::kem::{Algorithm as KemAlgorithm, Kem};
+use secrecy::Secret;
+use sha3::{Digest, Sha3_256};
+use sodiumoxide::crypto::secretbox;
+use zeroize::Zeroizing;
+
+const PEDERSEN_DOMAIN: &[u8] = b"kybelith-pedersen-v1";
+const VIEW_KEY_DOMAIN: &[u8] = b"kybelith-view-key-v1";
+const MEMO_DOMAIN: &[u8] = b"kybelith-memo-v1";
+pub(crate) const PAYLOAD_KEY_DOMAIN: &[u8] = b"kybelith-payload-key-v1";
+const VIEW_KEY_EXPORT_PREFIX: &str = "kvk1";
+
+/// Bits cobertos pelas provas de intervalo: todo valor `u64`
+pub const RANGE_BITS: usize = 64;
+/// Tamanho de um ponto comprimido (compromisso)
+pub const COMMITMENT_SIZE: usize = 33;
+const SCALAR_SIZE: usize = 32;
+const BIT_PROOF_SIZE: usize = COMMITMENT_SIZE + 4 * SCALAR_SIZE;
+/// Tamanho fixo de uma prova de intervalo serializada
+pub const RANGE_PROOF_SIZE: usize = RANGE_BITS * BIT_PROOF_SIZE;
+
+fn crypto_error(e: ErrorStack) -> TransactionError {
+    TransactionError::Other(format!("Falha na operação de curva elíptica: {}", e))
+}
+
+fn invalid(message: &str) -> TransactionError {
+    TransactionError::InvalidData(message.to_string())
+}
+
+/// Grupo P-256 com o segundo gerador `H`, cujo logaritmo em relação a `G` ninguém
+/// conhece: é obtido por hash-to-curve (tentativa e incremento) de um domínio fixo.
+struct Curve {
+    group: EcGroup,
+    order: BigNum,
+    h: EcPoint,
+    ctx: BigNumContext,
+}
+
+impl Curve {
+    fn new() -> Result<Self, TransactionError> {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).map_err(crypto_error)?;
+        let mut ctx = BigNumContext::new().map_err(crypto_error)?;
+        let mut order = BigNum::new().map_err(crypto_error)?;
+        group.order(&mut order, &mut ctx).map_err(crypto_error)?;
+
+        let mut counter = 0u32;
+        let h = loop {
+            let mut encoded = vec![0x02];
+            encoded.extend(
+                Sha3_256::new()
+                    .chain_update(PEDERSEN_DOMAIN)
+                    .chain_update(b"H")
+                    .chain_update(counter.to_be_bytes())
+                    .finalize(),
+            );
+            if let Ok(point) = EcPoint::from_bytes(&group, &encoded, &mut ctx) {
+                break point;
+            }
+            counter += 1;
+        };
+
+        Ok(Self {
+            group,
+            order,
+            h,
+            ctx,
+        })
+    }
+
+    fn scalar(&mut self, bytes: &[u8]) -> Result<BigNum, TransactionError> {
+        let value = BigNum::from_slice(bytes).map_err(crypto_error)?;
+        let mut reduced = BigNum::new().map_err(crypto_error)?;
+        reduced
+            .nnmod(&value, &self.order, &mut self.ctx)
+            .map_err(crypto_error)?;
+        Ok(reduced)
+    }
+
+    /// Escalar sorteado pela entropia do nó; 64 bytes reduzidos pela ordem deixam
+    /// o viés desprezível
+    fn random_scalar(&mut self) -> Result<BigNum, TransactionError> {
+        let bytes = Zeroizing::new(entropy().array::<64>());
+        self.scalar(&*bytes)
+    }
+
+    fn add_mod(&mut self, a: &BigNumRef, b: &BigNumRef) -> Result<BigNum, TransactionError> {
+        let mut r = BigNum::new().map_err(crypto_error)?;
+        r.mod_add(a, b, &self.order, &mut self.ctx)
+            .map_err(crypto_error)?;
+        Ok(r)
+    }
+
+    fn sub_mod(&mut self, a: &BigNumRef, b: &BigNumRef) -> Result<BigNum, TransactionError> {
+        let mut r = BigNum::new().map_err(crypto_error)?;
+        r.mod_sub(a, b, &self.order, &mut self.ctx)
+            .map_err(crypto_error)?;
+        Ok(r)
+    }
+
+    fn mul_mod(&mut self, a: &BigNumRef, b: &BigNumRef) -> Result<BigNum, TransactionError> {
+        let mut r = BigNum::new().map_err(crypto_error)?;
+        r.mod_mul(a, b, &self.order, &mut self.ctx)
+            .map_err(crypto_error)?;
+        Ok(r)
+    }
+
+    fn encode_scalar(value: &BigNumRef) -> Result<Vec<u8>, TransactionError> {
+        value
+            .to_vec_padded(SCALAR_SIZE as i32)
+            .map_err(crypto_error)
+    }
+
+    /// `a·G + b·H`
+    fn commit(&mut self, a: &BigNumRef, b: &BigNumRef) -> Result<EcPoint, TransactionError> {
+        let mut point = EcPoint::new(&self.group).map_err(crypto_error)?;
+        point
+            .mul_full(&self.group, a, &self.h, b, &mut self.ctx)
+            .map_err(crypto_error)?;
+        Ok(point)
+    }
+
+    /// `s·H - e·P`
+    fn schnorr_point(
+        &mut self,
+        s: &BigNumRef,
+        e: &BigNumRef,
+        p: &EcPointRef,
+    ) -> Result<EcPoint, TransactionError> {
+        let zero = BigNum::new().map_err(crypto_error)?;
+        let neg_e = self.sub_mod(&zero, e)?;
+        let mut sh = EcPoint::new(&self.group).map_err(crypto_error)?;
+        sh.mul(&self.group, &self.h, s, &self.ctx)
+            .map_err(crypto_error)?;
+        let mut ep = EcPoint::new(&self.group).map_err(crypto_error)?;
+        ep.mul(&self.group, p, &neg_e, &self.ctx)
+            .map_err(crypto_error)?;
+        self.add(&sh, &ep)
+    }
+
+    fn add(&mut self, a: &EcPointRef, b: &EcPointRef) -> Result<EcPoint, TransactionError> {
+        let mut r = EcPoint::new(&self.group).map_err(crypto_error)?;
+        r.add(&self.group, a, b, &mut self.ctx)
+            .map_err(crypto_error)?;
+        Ok(r)
+    }
+
+    fn sub(&mut self, a: &EcPointRef, b: &EcPointRef) -> Result<EcPoint, TransactionError> {
+        let mut negated = b.to_owned(&self.group).map_err(crypto_error)?;
+        negated
+            .invert(&self.group, &self.ctx)
+            .map_err(crypto_error)?;
+        self.add(a, &negated)
+    }
+
+    fn decode_point(&mut self, bytes: &[u8]) -> Result<EcPoint, TransactionError> {
+        EcPoint::from_bytes(&self.group, bytes, &mut self.ctx)
+            .map_err(|_| invalid("Compromisso inválido"))
+    }
+
+    fn encode_point(&mut self, point: &EcPointRef) -> Result<Vec<u8>, TransactionError> {
+        point
+            .to_bytes(&self.group, PointConversionForm::COMPRESSED, &mut self.ctx)
+            .map_err(crypto_error)
+    }
+
+    fn points_equal(&mut self, a: &EcPointRef, b: &EcPointRef) -> Result<bool, TransactionError> {
+        a.eq(&self.group, b, &mut self.ctx).map_err(crypto_error)
+    }
+
+    fn challenge(
+        &mut self,
+        index: usize,
+        commitment: &[u8],
+        a0: &EcPointRef,
+        a1: &EcPointRef,
+    ) -> Result<BigNum, TransactionError> {
+        let a0 = self.encode_point(a0)?;
+        let a1 = self.encode_point(a1)?;
+        let digest = Sha3_256::new()
+            .chain_update(PEDERSEN_DOMAIN)
+            .chain_update(b"bit")
+            .chain_update((index as u64).to_le_bytes())
+            .chain_update(commitment)
+            .chain_update(a0)
+            .chain_update(a1)
+            .finalize();
+        self.scalar(&digest)
+    }
+
+    fn u64_scalar(value: u64) -> Result<BigNum, TransactionError> {
+        BigNum::from_slice(&value.to_be_bytes()).map_err(crypto_error)
+    }
+}
+
+/// Valor e fator de cegamento que abrem um compromisso; fica com o dono da conta
+#[derive(Clone, PartialEq, Eq, zeroize::Zeroize, zeroize::ZeroizeOnDrop)]
+pub struct Opening {
+    pub value: u64,
+    pub blinding: [u8; 32],
+}
+
+impl Opening {
+    /// Abertura com fator de cegamento aleatório
+    pub fn random(value: u64) -> Result<Self, TransactionError> {
+        let mut curve = Curve::new()?;
+        let blinding = Curve::encode_scalar(&*curve.random_scalar()?)?;
+        Ok(Self {
+            value,
+            blinding: blinding
+                .try_into()
+                .map_err(|_| invalid("Escalar inválido"))?,
+        })
+    }
+
+    /// Compromisso de Pedersen `value·G + blinding·H`, comprimido
+    pub fn commitment(&self) -> Result<Vec<u8>, TransactionError> {
+        let mut curve = Curve::new()?;
+        let value = Curve::u64_scalar(self.value)?;
+        let blinding = curve.scalar(&self.blinding)?;
+        let point = curve.commit(&value, &blinding)?;
+        curve.encode_point(&point)
+    }
+
+    /// Indica se a abertura corresponde a `commitment`
+    pub fn opens(&self, commitment: &[u8]) -> bool {
+        self.commitment()
+            .map(|own| own == commitment)
+            .unwrap_or(false)
+    }
+
+    fn combine(&self, other: &Opening, add: bool) -> Result<Opening, TransactionError> {
+        let mut curve = Curve::new()?;
+        let a = curve.scalar(&self.blinding)?;
+        let b = curve.scalar(&other.blinding)?;
+        let (value, blinding) = if add {
+            (self.value.checked_add(other.value), curve.add_mod(&a, &b)?)
+        } else {
+            (self.value.checked_sub(other.value), curve.sub_mod(&a, &b)?)
+        };
+        Ok(Opening {
+            value: value.ok_or(TransactionError::InsufficientFunds)?,
+            blinding: Curve::encode_scalar(&blinding)?
+                .try_into()
+                .map_err(|_| invalid("Escalar inválido"))?,
+        })
+    }
+
+    /// Abertura da soma dos compromissos (crédito recebido)
+    pub fn add(&self, other: &Opening) -> Result<Opening, TransactionError> {
+        self.combine(other, true)
+    }
+
+    /// Abertura da diferença dos compromissos; falha se o valor ficaria negativo
+    pub fn sub(&self, other: &Opening) -> Result<Opening, TransactionError> {
+        self.combine(other, false)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.value.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&self.blinding);
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, TransactionError> {
+        if bytes.len() != 8 + 32 {
+            return Err(invalid("Abertura cifrada com tamanho inválido"));
+        }
+        let mut value = [0u8; 8];
+        value.copy_from_slice(&bytes[..8]);
+        let mut blinding = [0u8; 32];
+        blinding.copy_from_slice(&bytes[8..]);
+        Ok(Self {
+            value: u64::from_le_bytes(value),
+            blinding,
+        })
+    }
+}
+
+/// Compromisso da soma `a + b`
+pub fn add_commitments(a: &[u8], b: &[u8]) -> Result<Vec<u8>, TransactionError> {
+    let mut curve = Curve::new()?;
+    let a = curve.decode_point(a)?;
+    let b = curve.decode_point(b)?;
+    let sum = curve.add(&a, &b)?;
+    curve.encode_point(&sum)
+}
+
+/// Compromisso da diferença `a - b`
+pub fn sub_commitments(a: &[u8], b: &[u8]) -> Result<Vec<u8>, TransactionError> {
+    let mut curve = Curve::new()?;
+    let a = curve.decode_point(a)?;
+    let b = curve.decode_point(b)?;
+    let difference = curve.sub(&a, &b)?;
+    curve.encode_point(&difference)
+}
+
+/// Compromisso de `commitment - amount·G`: o saldo depois de revelar `amount`
+pub fn sub_public_amount(commitment: &[u8], amount: u64) -> Result<Vec<u8>, TransactionError> {
+    let public = Opening {
+        value: amount,
+        blinding: [0u8; 32],
+    };
+    sub_commitments(commitment, &public.commitment()?)
+}
+
+/// Prova de que o valor comprometido por `opening` está em `[0, 2^64)`.
+///
+/// Cada bit recebe um compromisso próprio e uma prova OU (Cramer-Damgård-Schoenmakers)
+/// de que abre para 0 ou 1; os fatores de cegamento dos bits somam, com pesos `2^i`,
+/// o do compromisso original, de modo que o verificador confere a soma.
+pub fn prove_range(opening: &Opening) -> Result<Vec<u8>, TransactionError> {
+    let mut curve = Curve::new()?;
+    let total_blinding = curve.scalar(&opening.blinding)?;
+
+    let mut blindings = Vec::with_capacity(RANGE_BITS);
+    let mut weighted = BigNum::new().map_err(crypto_error)?;
+    for index in 1..RANGE_BITS {
+        let r = curve.random_scalar()?;
+        let mut weight = BigNum::new().map_err(crypto_error)?;
+        weight.set_bit(index as i32).map_err(crypto_error)?;
+        let term = curve.mul_mod(&r, &weight)?;
+        weighted = curve.add_mod(&weighted, &term)?;
+        blindings.push(r);
+    }
+    blindings.insert(0, curve.sub_mod(&total_blinding, &weighted)?);
+
+    let zero = BigNum::new().map_err(crypto_error)?;
+    let one = BigNum::from_u32(1).map_err(crypto_error)?;
+    let g = curve.commit(&one, &zero)?;
+    let mut proof = Vec::with_capacity(RANGE_PROOF_SIZE);
+    for (index, r) in blindings.iter().enumerate() {
+        let bit = (opening.value >> index) & 1 == 1;
+        let c = curve.commit(if bit { &one } else { &zero }, r)?;
+        let c_minus_g = curve.sub(&c, &g)?;
+        let encoded = curve.encode_point(&c)?;
+
+        // Ramo verdadeiro com nonce `k`; o outro é simulado com desafio e resposta aleatórios
+        let k = curve.random_scalar()?;
+        let fake_e = curve.random_scalar()?;
+        let fake_s = curve.random_scalar()?;
+        let real = curve.commit(&zero, &k)?;
+        let (e0, e1, s0, s1) = if bit {
+            let a0 = curve.schnorr_point(&fake_s, &fake_e, &c)?;
+            let e = curve.challenge(index, &encoded, &a0, &real)?;
+            let e1 = curve.sub_mod(&e, &fake_e)?;
+            let e1r = curve.mul_mod(&e1, r)?;
+            let s1 = curve.add_mod(&k, &e1r)?;
+            (fake_e, e1, fake_s, s1)
+        } else {
+            let a1 = curve.schnorr_point(&fake_s, &fake_e, &c_minus_g)?;
+            let e = curve.challenge(index, &encoded, &real, &a1)?;
+            let e0 = curve.sub_mod(&e, &fake_e)?;
+            let e0r = curve.mul_mod(&e0, r)?;
+            let s0 = curve.add_mod(&k, &e0r)?;
+            (e0, fake_e, s0, fake_s)
+        };
+
+        proof.extend(encoded);
+        for scalar in [&e0, &e1, &s0, &s1] {
+            proof.extend(Curve::encode_scalar(scalar)?);
+        }
+    }
+    Ok(proof)
+}
+
+/// Verifica que `commitment` compromete um valor em `[0, 2^64)`
+pub fn verify_range(commitment: &[u8], proof: &[u8]) -> Result<(), TransactionError> {
+    if proof.len() != RANGE_PROOF_SIZE {
+        return Err(invalid("Prova de intervalo com tamanho inválido"));
+    }
+    let mut curve = Curve::new()?;
+    let expected = curve.decode_point(commitment)?;
+    let zero = BigNum::new().map_err(crypto_error)?;
+    let one = BigNum::from_u32(1).map_err(crypto_error)?;
+    let g = curve.commit(&one, &zero)?;
+
+    let mut sum = EcPoint::new(&curve.group).map_err(crypto_error)?;
+    for (index, chunk) in proof.chunks(BIT_PROOF_SIZE).enumerate() {
+        let (encoded, scalars) = chunk.split_at(COMMITMENT_SIZE);
+        let c = curve.decode_point(encoded)?;
+        let c_minus_g = curve.sub(&c, &g)?;
+        let mut parts = scalars.chunks(SCALAR_SIZE);
+        let mut next =
+            || BigNum::from_slice(parts.next().unwrap_or_default()).map_err(crypto_error);
+        let (e0, e1, s0, s1) = (next()?, next()?, next()?, next()?);
+
+        let a0 = curve.schnorr_point(&s0, &e0, &c)?;
+        let a1 = curve.schnorr_point(&s1, &e1, &c_minus_g)?;
+        let e = curve.challenge(index, encoded, &a0, &a1)?;
+        let e_sum = curve.add_mod(&e0, &e1)?;
+        if e != e_sum {
+            return Err(invalid("Prova de intervalo inválida"));
+        }
+
+        let mut weight = BigNum::new().map_err(crypto_error)?;
+        weight.set_bit(index as i32).map_err(crypto_error)?;
+        let mut weighted = EcPoint::new(&curve.group).map_err(crypto_error)?;
+        weighted
+            .mul(&curve.group, &c, &weight, &curve.ctx)
+            .map_err(crypto_error)?;
+        sum = curve.add(&sum, &weighted)?;
+    }
+
+    if !curve.points_equal(&sum, &expected)? {
+        return Err(invalid("Prova de intervalo não corresponde ao compromisso"));
+    }
+    Ok(())
+}
+
+fn kem() -> Result<Kem, TransactionError> {
+    entropy::kem(KemAlgorithm::Kyber512)
+        .map_err(|e| TransactionError::Other(format!("KEM indisponível: {}", e)))
+}
+
+/// Identificador curto (hex) de uma chave pública de visualização
+pub fn view_key_id(public_key: &[u8]) -> String {
+    hex::encode(&Sha3_256::digest(public_key)[..16])
+}
+
+/// Chave simétrica derivada do segredo do KEM, separada por finalidade (`domain`)
+fn sealing_key(domain: &[u8], shared: &[u8]) -> secretbox::Key {
+    secretbox::Key(
+        Sha3_256::new()
+            .chain_update(domain)
+            .chain_update(shared)
+            .finalize()
+            .into(),
+    )
+}
+
+/// Encapsula para `public_key` e cifra `plaintext`; devolve a chave encapsulada e o
+/// texto cifrado
+pub(crate) fn seal(
+    plaintext: &[u8],
+    public_key: &[u8],
+    domain: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), TransactionError> {
+    let kem = kem()?;
+    let kem_key = kem.public_key_from_bytes(public_key).ok_or_else(|| {
+        TransactionError::InvalidPublicKey("Chave de visualização inválida".to_string())
+    })?;
+    let (encapsulated, shared) = kem
+        .encapsulate(kem_key)
+        .map_err(|e| TransactionError::Other(format!("Falha no encapsulamento: {}", e)))?;
+
+    // Chave nova a cada encapsulamento, então o nonce fixo não se repete
+    let nonce = secretbox::Nonce([0u8; secretbox::NONCEBYTES]);
+    let ciphertext = secretbox::seal(plaintext, &nonce, &sealing_key(domain, shared.as_ref()));
+    Ok((encapsulated.into_vec(), ciphertext))
+}
+
+/// Cifra `opening` para a chave pública de visualização `public_key` (Kyber512)
+pub fn seal_opening(
+    opening: &Opening,
+    public_key: &[u8],
+) -> Result<SealedOpening, TransactionError> {
+    let (encapsulated_key, ciphertext) = seal(&opening.to_bytes(), public_key, VIEW_KEY_DOMAIN)?;
+    Ok(SealedOpening {
+        view_key_id: view_key_id(public_key),
+        encapsulated_key,
+        ciphertext,
+    })
+}
+
+/// Cifra um memorando para a chave de visualização do destinatário
+pub fn seal_memo(memo: &str, public_key: &[u8]) -> Result<EncryptedMemo, TransactionError> {
+    if memo.len() > MAX_MEMO_SIZE {
+        return Err(TransactionError::DataSizeExceeded);
+    }
+    let (encapsulated_key, ciphertext) = seal(memo.as_bytes(), public_key, MEMO_DOMAIN)?;
+    Ok(EncryptedMemo {
+        view_key_id: view_key_id(public_key),
+        encapsulated_key,
+        ciphertext,
+    })
+}
+
+/// Chave de visualização: decifra as aberturas destinadas a ela sem dar poder de
+/// assinatura. Contas a registram na cadeia para receber valores confidenciais e
+/// auditores a usam para ler os valores das transferências.
+pub struct ViewKey {
+    public_key: Vec<u8>,
+    secret_key: oqs::kem::SecretKey,
+}
+
+impl ViewKey {
+    pub fn generate() -> Result<Self, TransactionError> {
+        let (public_key, secret_key) = kem()?
+            .keypair()
+            .map_err(|e| TransactionError::Other(format!("Falha ao gerar chave: {}", e)))?;
+        Ok(Self {
+            public_key: public_key.into_vec(),
+            secret_key,
+        })
+    }
+
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    /// Exporta o par de chaves para entrega a um auditor, no formato
+    /// `kvk1:<chave pública>:<chave secreta>` (hex). Dá apenas leitura: nenhuma
+    /// chave de assinatura da conta é incluída.
+    pub fn export(&self) -> Secret<String> {
+        Secret::new(format!(
+            "{}:{}:{}",
+            VIEW_KEY_EXPORT_PREFIX,
+            hex::encode(&self.public_key),
+            hex::encode(self.secret_key.as_ref())
+        ))
+    }
+
+    /// Reconstrói uma chave exportada com `export`
+    pub fn import(exported: &str) -> Result<Self, TransactionError> {
+        let malformed = || invalid("Chave de visualização exportada inválida");
+        let mut parts = exported.trim().split(':');
+        if parts.next() != Some(VIEW_KEY_EXPORT_PREFIX) {
+            return Err(malformed());
+        }
+        let public_key = parts
+            .next()
+            .and_then(|part| hex::decode(part).ok())
+            .ok_or_else(malformed)?;
+        let secret_bytes = Zeroizing::new(
+            parts
+                .next()
+                .and_then(|part| hex::decode(part).ok())
+                .ok_or_else(malformed)?,
+        );
+        if parts.next().is_some() {
+            return Err(malformed());
+        }
+
+        let kem = kem()?;
+        kem.public_key_from_bytes(&public_key)
+            .ok_or_else(malformed)?;
+        let secret_key = kem
+            .secret_key_from_bytes(&secret_bytes)
+            .ok_or_else(malformed)?
+            .to_owned();
+        Ok(Self {
+            public_key,
+            secret_key,
+        })
+    }
+
+    pub fn id(&self) -> String {
+        view_key_id(&self.public_key)
+    }
+
+    pub(crate) fn unseal(
+        &self,
+        view_key_id: &str,
+        encapsulated_key: &[u8],
+        ciphertext: &[u8],
+        domain: &[u8],
+    ) -> Result<Vec<u8>, TransactionError> {
+        if view_key_id != self.id() {
+            return Err(invalid("Dados cifrados para outra chave de visualização"));
+        }
+        let kem = kem()?;
+        let encapsulated = kem
+            .ciphertext_from_bytes(encapsulated_key)
+            .ok_or_else(|| invalid("Chave encapsulada inválida"))?;
+        let shared = kem
+            .decapsulate(&self.secret_key, encapsulated)
+            .map_err(|e| TransactionError::Other(format!("Falha no desencapsulamento: {}", e)))?;
+        let nonce = secretbox::Nonce([0u8; secretbox::NONCEBYTES]);
+        secretbox::open(ciphertext, &nonce, &sealing_key(domain, shared.as_ref()))
+            .map_err(|_| invalid("Falha ao decifrar dados selados"))
+    }
+
+    /// Decifra uma abertura selada para esta chave
+    pub fn open(&self, sealed: &SealedOpening) -> Result<Opening, TransactionError> {
+        let plaintext = self.unseal(
+            &sealed.view_key_id,
+            &sealed.encapsulated_key,
+            &sealed.ciphertext,
+            VIEW_KEY_DOMAIN,
+        )?;
+        Opening::from_bytes(&plaintext)
+    }
+
+    /// Decifra um memorando endereçado a esta chave
+    pub fn open_memo(&self, memo: &EncryptedMemo) -> Result<String, TransactionError> {
+        let plaintext = self.unseal(
+            &memo.view_key_id,
+            &memo.encapsulated_key,
+            &memo.ciphertext,
+            MEMO_DOMAIN,
+        )?;
+        String::from_utf8(plaintext).map_err(|_| invalid("Memorando não é UTF-8 válido"))
+    }
+
+    /// Memorando de uma transferência confidencial, se houver um para esta chave
+    pub fn read_memo(&self, kind: &OperationKind) -> Option<String> {
+        match kind {
+            OperationKind::ConfidentialTransfer {
+                memo: Some(memo), ..
+            } => self.open_memo(memo).ok(),
+            _ => None,
+        }
+    }
+
+    /// Abre o valor de uma transferência confidencial, se alguma abertura for para
+    /// esta chave e corresponder ao compromisso publicado
+    pub fn open_transfer(&self, kind: &OperationKind) -> Option<Opening> {
+        let OperationKind::ConfidentialTransfer {
+            commitment,
+            openings,
+            ..
+        } = kind
+        else {
+            return None;
+        };
+        openings
+            .iter()
+            .filter(|sealed| sealed.view_key_id == self.id())
+            .filter_map(|sealed| self.open(sealed).ok())
+            .find(|opening| opening.opens(commitment))
+    }
+}
+
+/// Move `amount` do saldo público do autor para o saldo confidencial.
+///
+/// O valor é público nesta operação; a abertura devolvida é o ponto de partida do
+/// saldo confidencial que a carteira acompanha.
+pub fn shield(token_id: u64, amount: u64) -> Result<(OperationKind, Opening), TransactionError> {
+    let opening = Opening::random(amount)?;
+    let kind = OperationKind::Shield {
+        token_id,
+        amount,
+        blinding: opening.blinding.to_vec(),
+    };
+    Ok((kind, opening))
+}
+
+/// Monta uma transferência confidencial de `amount` a partir do saldo `balance`.
+///
+/// A abertura do valor é selada para a chave de visualização do destinatário e para
+/// cada chave de auditor; o memorando opcional só o destinatário lê. Devolve a
+/// operação e a abertura do saldo restante.
+pub fn confidential_transfer(
+    token_id: u64,
+    to: String,
+    amount: u64,
+    balance: &Opening,
+    recipient_view_key: &[u8],
+    auditor_view_keys: &[Vec<u8>],
+    memo: Option<&str>,
+) -> Result<(OperationKind, Opening), TransactionError> {
+    let transferred = Opening::random(amount)?;
+    let remaining = balance.sub(&transferred)?;
+
+    let mut openings = vec![seal_opening(&transferred, recipient_view_key)?];
+    for auditor in auditor_view_keys {
+        openings.push(seal_opening(&transferred, auditor)?);
+    }
+
+    let kind = OperationKind::ConfidentialTransfer {
+        token_id,
+        to,
+        commitment: transferred.commitment()?,
+        range_proof: prove_range(&transferred)?,
+        remaining_proof: prove_range(&remaining)?,
+        openings,
+        memo: memo
+            .map(|memo| seal_memo(memo, recipient_view_key))
+            .transpose()?,
+    };
+    Ok((kind, remaining))
+}
+
+/// Devolve `amount` do saldo confidencial ao saldo público, provando que o restante
+/// não fica negativo
+pub fn unshield(
+    token_id: u64,
+    amount: u64,
+    balance: &Opening,
+) -> Result<(OperationKind, Opening), TransactionError> {
+    let remaining = balance.sub(&Opening {
+        value: amount,
+        blinding: [0u8; 32],
+    })?;
+    let kind = OperationKind::Unshield {
+        token_id,
+        amount,
+        remaining_proof: prove_range(&remaining)?,
+    };
+    Ok((kind, remaining))
+}
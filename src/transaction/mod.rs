@@ -1,12 +1,34 @@
 pub mod builder;
+#[cfg(feature = "node")]
+pub mod confidential;
+pub mod operation;
+#[cfg(feature = "node")]
+pub mod pipeline;
 pub mod processor;
+pub mod scheme;
+#[cfg(feature = "node")]
 pub mod secure_transaction;
 pub mod signer;
+#[cfg(feature = "node")]
+pub mod verification;
 pub mod verifier;
+pub mod view;
+pub mod weight;
 
 // Reexportar os tipos para facilitar o uso externo
-pub use self::builder::{NonceRegistry, Transaction};
+pub use self::builder::{NonceRegistry, Transaction, TRANSACTION_ENCODING_VERSION};
+pub use self::operation::{
+    EncryptedMemo, GuardRule, Operation, OperationKind, SealedOpening, TransferRule, VoteChoice,
+};
+#[cfg(feature = "node")]
+pub use self::pipeline::{PipelineConfig, PipelineMetrics, TransactionPipeline};
 pub use self::processor::TransactionProcessor;
-pub use self::secure_transaction::SecureTransaction;
+pub use self::scheme::{SignatureScheme, SUPPORTED_SCHEMES};
+#[cfg(feature = "node")]
+pub use self::secure_transaction::{PayloadGrant, SecureTransaction};
 pub use self::signer::TransactionSigner;
+#[cfg(feature = "node")]
+pub use self::verification::VerificationService;
 pub use self::verifier::TransactionVerifier;
+pub use self::view::{EncodedTransaction, TransactionView};
+pub use self::weight::WeightSchedule;
@@ -0,0 +1,739 @@
+#[cfg(feature = "node")]
+use crate::blockchain::merkle::MerkleProof;
+use crate::constants::MAX_SIGNATURE_SIZE;
+use crate::error::TransactionError;
+#[cfg(feature = "node")]
+use crate::interchain::{LightClient, Packet, SignedHeader};
+use crate::rbac::AdminRole;
+use crate::transaction::scheme::SignatureScheme;
+use crate::transaction::view::with_signing_buffer;
+use crate::utils::address::Address;
+use crate::utils::clock::clock;
+use crate::utils::name::validate_name;
+use crate::utils::timestamp_policy::{TimestampContext, TimestampPolicy};
+use crate::utils::version::ClientVersion;
+use pqcrypto_dilithium::dilithium5::{self, PublicKey, SecretKey};
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+/// Operações de estado registradas na cadeia além das transferências
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum OperationKind {
+    /// Cria um token; `token_id` precisa ser o próximo ID livre da cadeia e todo o
+    /// supply é creditado ao autor quando o bloco é confirmado
+    CreateToken {
+        token_id: u64,
+        name: String,
+        symbol: String,
+        total_supply: u64,
+    },
+    /// Registra a chave pública de visualização (Kyber512) do autor, necessária para
+    /// receber transferências confidenciais
+    RegisterViewKey { public_key: Vec<u8> },
+    /// Move `amount` do saldo público do autor para o saldo confidencial; o valor é
+    /// público nesta operação e `blinding` abre o compromisso creditado
+    Shield {
+        token_id: u64,
+        amount: u64,
+        blinding: Vec<u8>,
+    },
+    /// Transferência com valor oculto em um compromisso de Pedersen. As provas de
+    /// intervalo mostram que o valor e o saldo restante do autor não são negativos
+    ConfidentialTransfer {
+        token_id: u64,
+        to: String,
+        commitment: Vec<u8>,
+        range_proof: Vec<u8>,
+        remaining_proof: Vec<u8>,
+        /// Abertura do valor cifrada para o destinatário e para cada auditor
+        openings: Vec<SealedOpening>,
+        /// Memorando cifrado para a chave de visualização do destinatário
+        memo: Option<EncryptedMemo>,
+    },
+    /// Devolve `amount` do saldo confidencial ao saldo público
+    Unshield {
+        token_id: u64,
+        amount: u64,
+        remaining_proof: Vec<u8>,
+    },
+    /// Atribui (`granted`) ou revoga um papel administrativo de `subject`; o autor
+    /// precisa ter o papel de admin e assinar com a chave do próprio endereço
+    AssignRole {
+        subject: String,
+        role: AdminRole,
+        granted: bool,
+    },
+    /// Substitui as regras avaliadas em toda transferência do token; só o dono do
+    /// token e seus administradores podem definir. Lista vazia remove a política
+    SetTransferPolicy {
+        token_id: u64,
+        rules: Vec<TransferRule>,
+    },
+    /// Marca (ou desmarca) `subject` como verificado por KYC; exige o papel de
+    /// operador, assinando com a chave do próprio endereço
+    SetKyc { subject: String, verified: bool },
+    /// Reserva `amount` do saldo do autor para `beneficiary`, liberado linearmente
+    /// de `start` a `start + duration_secs` e nada antes de `start + cliff_secs`
+    /// (segundos Unix, pelo timestamp do bloco)
+    CreateVesting {
+        token_id: u64,
+        beneficiary: String,
+        amount: u64,
+        start: u64,
+        cliff_secs: u64,
+        duration_secs: u64,
+    },
+    /// Move para o saldo do autor, beneficiário do cronograma `schedule_id` (a
+    /// posição na ordem de criação), tudo o que já foi liberado
+    ClaimVested { schedule_id: u64 },
+    /// Primeiro passo da troca de dono: o dono atual indica `new_owner`, que só
+    /// assume ao aceitar; uma nova proposta substitui a anterior
+    ProposeTokenOwner { token_id: u64, new_owner: String },
+    /// Segundo passo: o endereço proposto aceita e passa a ser o dono do token
+    AcceptTokenOwner { token_id: u64 },
+    /// Adiciona (`granted`) ou remove um administrador secundário do token; só o
+    /// dono. Nas três operações o autor assina com a chave do próprio endereço
+    SetTokenAdmin {
+        token_id: u64,
+        admin: String,
+        granted: bool,
+    },
+    /// Emite `amount` unidades novas do token para `to`, aumentando o supply; só o
+    /// dono do token e seus administradores
+    MintTokens {
+        token_id: u64,
+        to: String,
+        amount: u64,
+    },
+    /// Queima `amount` do saldo público do autor, dono ou administrador do token
+    BurnTokens { token_id: u64, amount: u64 },
+    /// Suspende (`paused`) ou retoma as transferências públicas do token; dono ou
+    /// administradores, assinando com a chave do próprio endereço
+    SetTokenPaused { token_id: u64, paused: bool },
+    /// Abre uma proposta de governança votada durante `voting_blocks` blocos. O
+    /// poder de voto é o stake vinculado no bloco anterior ao que inclui a
+    /// proposta, e o autor precisa ter stake nessa altura
+    SubmitProposal {
+        description: String,
+        voting_blocks: u64,
+    },
+    /// Voto do autor na proposta `proposal_id` (a posição na ordem de criação); um
+    /// novo voto do mesmo endereço substitui o anterior
+    CastVote {
+        proposal_id: u64,
+        choice: VoteChoice,
+    },
+    /// Aprovação de um admin para parar (`halted`) ou retomar a cadeia em
+    /// emergência; a mudança vale quando a maioria dos admins aprova o mesmo
+    /// sentido. Com a cadeia parada, só essas operações são admitidas e produzidas
+    SetChainHalted { halted: bool, reason: String },
+    /// Sinal de atualização: a partir do bloco `activation_height`, nós com binário
+    /// anterior a `min_version` deixam de propor e votar. Só admins, assinando com
+    /// a chave do próprio endereço, e para uma altura futura
+    ScheduleUpgrade {
+        min_version: ClientVersion,
+        activation_height: u64,
+    },
+    /// Habilita (`allowed`) ou remove `feeder` da lista de alimentadores do
+    /// oráculo `feed_id`; só admins, assinando com a chave do próprio endereço
+    SetOracleFeeder {
+        feed_id: u64,
+        feeder: String,
+        allowed: bool,
+    },
+    /// Ponto de dado do autor (um preço, por exemplo) para o oráculo `feed_id`. A
+    /// assinatura Dilithium da operação autentica o alimentador, que precisa estar
+    /// habilitado e assinar com a chave do próprio endereço
+    SubmitOracleData { feed_id: u64, value: u64 },
+    /// Registra `name` (`alice.qst`) para o autor por `blocks` blocos, pagando a
+    /// taxa de registro em token nativo; nomes expirados podem ser registrados de novo
+    RegisterName { name: String, blocks: u64 },
+    /// Estende por `blocks` blocos um nome do autor ainda não expirado
+    RenewName { name: String, blocks: u64 },
+    /// Passa um nome do autor, ainda não expirado, para `to`
+    TransferName { name: String, to: String },
+    /// Restrições que o autor impõe às saídas da própria conta: com `allow_only`,
+    /// só destinos liberados recebem. Mudanças que afrouxam as restrições só valem
+    /// depois do atraso em vigor; as que apertam valem no bloco que as inclui
+    SetAccountGuard { allow_only: bool, delay_blocks: u64 },
+    /// Libera, bloqueia ou (com `None`) tira da lista um destino das saídas do
+    /// autor, sob a mesma regra de atraso de `SetAccountGuard`
+    SetGuardRule {
+        destination: String,
+        rule: Option<GuardRule>,
+    },
+    /// Define os guardiões que podem trocar a chave do autor: `threshold` deles
+    /// aprovando a mesma chave nova, ela passa a valer `delay_blocks` blocos depois.
+    /// Lista vazia (com `threshold` zero) desliga a recuperação
+    SetGuardians {
+        guardians: Vec<String>,
+        threshold: u32,
+        delay_blocks: u64,
+    },
+    /// Aprovação de um guardião para trocar a chave de `account` por `new_public_key`
+    ApproveRecovery {
+        account: String,
+        new_public_key: Vec<u8>,
+    },
+    /// O dono, ainda com a chave vigente, descarta aprovações e recuperação pendente
+    CancelRecovery,
+    /// Conclui a recuperação pendente do autor depois do prazo; assinada com a
+    /// chave nova, que passa a ser a única aceita para a conta
+    CompleteRecovery,
+    /// Troca a chave do autor por `new_public_key`, assinada pela chave vigente; os
+    /// nonces seguintes só são aceitos com a chave nova
+    RotateKey { new_public_key: Vec<u8> },
+    /// Registra (ou substitui) o cliente leve da cadeia `chain_id`, que aceita
+    /// cabeçalhos assinados por ao menos `threshold` dos `validators`; só admins,
+    /// assinando com a chave do próprio endereço. As operações entre cadeias só
+    /// existem no nó completo, que tem o cliente leve e as provas de Merkle
+    #[cfg(feature = "node")]
+    RegisterChainClient {
+        chain_id: String,
+        validators: Vec<Vec<u8>>,
+        threshold: u32,
+    },
+    /// Envia `amount` do token do autor para `receiver` na cadeia
+    /// `destination_chain`. Vouchers que voltam à cadeia de origem são queimados;
+    /// os demais tokens ficam no escrow da cadeia de destino
+    #[cfg(feature = "node")]
+    SendPacket {
+        destination_chain: String,
+        receiver: String,
+        token_id: u64,
+        amount: u64,
+    },
+    /// Entrega um pacote da cadeia de origem com a prova de inclusão contra
+    /// `header`, que atualiza o cliente leve dela. Para um voucher,
+    /// `voucher_token_id` é o ID já associado ao rastro ou, na primeira recepção,
+    /// o próximo ID livre da cadeia; para um token nativo que volta, `None`
+    #[cfg(feature = "node")]
+    ReceivePacket {
+        packet: Packet,
+        proof: MerkleProof,
+        header: SignedHeader,
+        voucher_token_id: Option<u64>,
+    },
+}
+
+/// Regra de um destino nas restrições de saída de uma conta
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum GuardRule {
+    Allow,
+    Deny,
+}
+
+/// Opção de voto em uma proposta de governança
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum VoteChoice {
+    Yes,
+    No,
+    /// Conta para o quórum, mas não para a aprovação
+    Abstain,
+}
+
+/// Regra de uma política de transferência de token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum TransferRule {
+    /// Valor máximo por transferência
+    MaxTransferAmount(u64),
+    /// Remetente e destinatário precisam estar na lista
+    AllowList(Vec<String>),
+    /// Remetente e destinatário precisam estar verificados por KYC
+    RequireKyc,
+    /// Contrato WASM que exporta `check_transfer(amount: i64, from_kyc: i32,
+    /// to_kyc: i32) -> i32`; zero autoriza a transferência. Pode importar
+    /// `kybelith.oracle_value` para ler os oráculos da cadeia
+    Contract { code: Vec<u8> },
+}
+
+/// Abertura (valor e fator de cegamento) de um compromisso, cifrada para uma chave
+/// de visualização identificada por `view_key_id`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SealedOpening {
+    pub view_key_id: String,
+    pub encapsulated_key: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+}
+
+/// Memorando cifrado para uma chave de visualização: a chave simétrica vem do
+/// encapsulamento Kyber em `encapsulated_key`, e só o dono da chave o lê
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct EncryptedMemo {
+    pub view_key_id: String,
+    pub encapsulated_key: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+}
+
+/// Tamanho máximo do texto de um memorando, em bytes
+pub const MAX_MEMO_SIZE: usize = 256;
+const MEMO_MAC_SIZE: usize = 16;
+/// Tamanho máximo aceito para uma chave de visualização registrada
+pub const MAX_VIEW_KEY_SIZE: usize = 2048;
+/// Aberturas cifradas por transferência confidencial: destinatário e auditores
+pub const MAX_SEALED_OPENINGS: usize = 8;
+/// Regras por política de transferência
+pub const MAX_TRANSFER_RULES: usize = 8;
+/// Endereços em uma lista de permissão
+pub const MAX_ALLOW_LIST_SIZE: usize = 1024;
+/// Tamanho máximo do código de um contrato de política
+pub const MAX_POLICY_CONTRACT_SIZE: usize = 64 * 1024;
+/// Tamanho máximo da descrição de uma proposta de governança, em bytes
+pub const MAX_PROPOSAL_DESCRIPTION_SIZE: usize = 4096;
+/// Tamanho máximo do motivo de uma parada ou retomada de emergência, em bytes
+pub const MAX_HALT_REASON_SIZE: usize = 512;
+/// Validade máxima de um nome a partir do bloco em que é registrado ou renovado
+pub const MAX_NAME_REGISTRATION_BLOCKS: u64 = 5_256_000;
+/// Atraso máximo das restrições de saída de uma conta, em blocos; limita por
+/// quanto tempo um dono pode travar a própria conta
+pub const MAX_GUARD_DELAY_BLOCKS: u64 = 100_000;
+/// Guardiões por conta
+pub const MAX_GUARDIANS: usize = 16;
+/// Tamanho máximo do identificador de uma cadeia contraparte, em bytes
+pub const MAX_CHAIN_ID_SIZE: usize = 64;
+/// Validadores por cliente leve de cadeia contraparte
+pub const MAX_CHAIN_CLIENT_VALIDATORS: usize = 64;
+/// Limites do prazo entre aprovar uma recuperação e poder concluí-la, em blocos;
+/// o mínimo dá ao dono tempo de cancelar uma recuperação que não pediu
+pub const MIN_RECOVERY_DELAY_BLOCKS: u64 = 10;
+pub const MAX_RECOVERY_DELAY_BLOCKS: u64 = 100_000;
+
+/// Campos cobertos pela assinatura, na ordem da codificação canônica
+#[derive(Serialize)]
+struct SignableOperation<'a> {
+    kind: &'a OperationKind,
+    author: &'a str,
+    timestamp: i64,
+    nonce: u64,
+    public_key: &'a [u8],
+    fee: u64,
+}
+
+/// Operação assinada pelo autor com Dilithium5.
+///
+/// Compartilha a sequência de nonces da conta com as transferências, de modo que
+/// uma operação não pode ser reenviada nem reordenada em relação às transações do
+/// mesmo autor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Operation {
+    pub kind: OperationKind,
+    pub author: String,
+    pub timestamp: i64,
+    pub nonce: u64,
+    pub public_key: Vec<u8>,
+    /// Taxa em KYBL paga pelo autor quando a operação é aplicada; coberta pela
+    /// assinatura
+    #[serde(default)]
+    pub fee: u64,
+    pub signature: Vec<u8>,
+}
+
+impl Operation {
+    pub fn new(
+        kind: OperationKind,
+        author: String,
+        nonce: u64,
+        public_key: Vec<u8>,
+    ) -> Result<Self, TransactionError> {
+        let timestamp = clock().now_secs();
+
+        Address::parse(&author)?;
+
+        Ok(Self {
+            kind,
+            author,
+            timestamp,
+            nonce,
+            public_key,
+            fee: 0,
+            signature: Vec::new(),
+        })
+    }
+
+    fn serialize_for_signing_into(&self, buf: &mut Vec<u8>) -> Result<(), TransactionError> {
+        let data = SignableOperation {
+            kind: &self.kind,
+            author: &self.author,
+            timestamp: self.timestamp,
+            nonce: self.nonce,
+            public_key: &self.public_key,
+            fee: self.fee,
+        };
+
+        buf.clear();
+        bincode::serialize_into(&mut *buf, &data).map_err(|_| TransactionError::InvalidDataFormat)
+    }
+
+    pub fn sign(&mut self, secret_key: &SecretKey) -> Result<(), TransactionError> {
+        let mut data = Vec::new();
+        self.serialize_for_signing_into(&mut data)?;
+        self.signature = dilithium5::detached_sign(&data, secret_key)
+            .as_bytes()
+            .to_vec();
+        Ok(())
+    }
+
+    /// Verifica a assinatura com a chave pública embutida na operação
+    pub fn verify(&self) -> Result<(), TransactionError> {
+        if self.signature.len() > MAX_SIGNATURE_SIZE {
+            return Err(TransactionError::SignatureSizeExceeded);
+        }
+        SignatureScheme::check(&self.public_key, &self.signature)?;
+
+        let public_key = PublicKey::from_bytes(&self.public_key).map_err(|_| {
+            TransactionError::InvalidPublicKey("Chave pública inválida".to_string())
+        })?;
+        let signature = dilithium5::DetachedSignature::from_bytes(&self.signature)
+            .map_err(|_| TransactionError::InvalidSignatures("Invalid signature".to_string()))?;
+
+        with_signing_buffer(|buf| {
+            self.serialize_for_signing_into(buf)?;
+            dilithium5::verify_detached_signature(&signature, buf, &public_key)
+                .map_err(|_| TransactionError::InvalidSignatures("Invalid signature".to_string()))
+        })
+    }
+
+    /// Identificador estável: hex do SHA3-256 da codificação de assinatura seguida
+    /// da assinatura, como `Transaction::txid`
+    pub fn id(&self) -> String {
+        let mut data = Vec::new();
+        if self.serialize_for_signing_into(&mut data).is_err() {
+            data.clear();
+        }
+        let mut hasher = Sha3_256::new();
+        hasher.update(&data);
+        hasher.update(&self.signature);
+        hex::encode(hasher.finalize())
+    }
+
+    /// Verificações sem estado: autor, parâmetros da operação, timestamp e assinatura
+    pub fn check(&self) -> Result<(), TransactionError> {
+        Address::parse(&self.author)?;
+
+        match &self.kind {
+            OperationKind::CreateToken {
+                name,
+                symbol,
+                total_supply,
+                ..
+            } => {
+                if name.trim().is_empty() || name.len() > 64 {
+                    return Err(TransactionError::InvalidParameter(
+                        "Nome do token deve ter entre 1 e 64 caracteres".to_string(),
+                    ));
+                }
+                if symbol.len() < 2
+                    || symbol.len() > 10
+                    || !symbol.chars().all(|c| c.is_ascii_uppercase())
+                {
+                    return Err(TransactionError::InvalidParameter(
+                        "Símbolo do token deve ter entre 2 e 10 caracteres maiúsculos".to_string(),
+                    ));
+                }
+                if *total_supply == 0 {
+                    return Err(TransactionError::ValorInvalido);
+                }
+            }
+            OperationKind::RegisterViewKey { public_key } => {
+                if public_key.is_empty() || public_key.len() > MAX_VIEW_KEY_SIZE {
+                    return Err(TransactionError::InvalidParameter(
+                        "Chave de visualização com tamanho inválido".to_string(),
+                    ));
+                }
+            }
+            OperationKind::Shield {
+                amount, blinding, ..
+            } => {
+                if *amount == 0 {
+                    return Err(TransactionError::ValorInvalido);
+                }
+                if blinding.len() != 32 {
+                    return Err(TransactionError::InvalidParameter(
+                        "Fator de cegamento deve ter 32 bytes".to_string(),
+                    ));
+                }
+            }
+            OperationKind::ConfidentialTransfer {
+                to, openings, memo, ..
+            } => {
+                Address::parse(to)?;
+                if to == &self.author {
+                    return Err(TransactionError::EnderecoInvalido);
+                }
+                if openings.is_empty() || openings.len() > MAX_SEALED_OPENINGS {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "Transferência confidencial requer de 1 a {} aberturas cifradas",
+                        MAX_SEALED_OPENINGS
+                    )));
+                }
+                // Texto cifrado carrega o MAC do secretbox além do memorando
+                if memo
+                    .as_ref()
+                    .is_some_and(|memo| memo.ciphertext.len() > MAX_MEMO_SIZE + MEMO_MAC_SIZE)
+                {
+                    return Err(TransactionError::DataSizeExceeded);
+                }
+            }
+            OperationKind::Unshield { amount, .. } => {
+                if *amount == 0 {
+                    return Err(TransactionError::ValorInvalido);
+                }
+            }
+            OperationKind::AssignRole { subject, .. } | OperationKind::SetKyc { subject, .. } => {
+                Address::parse(subject)?;
+            }
+            OperationKind::SetOracleFeeder { feeder, .. } => {
+                Address::parse(feeder)?;
+            }
+            OperationKind::RegisterName { name, blocks }
+            | OperationKind::RenewName { name, blocks } => {
+                validate_name(name)?;
+                if *blocks == 0 || *blocks > MAX_NAME_REGISTRATION_BLOCKS {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "Validade do nome deve ficar entre 1 e {} blocos",
+                        MAX_NAME_REGISTRATION_BLOCKS
+                    )));
+                }
+            }
+            OperationKind::TransferName { name, to } => {
+                validate_name(name)?;
+                Address::parse(to)?;
+            }
+            OperationKind::SetAccountGuard { delay_blocks, .. } => {
+                if *delay_blocks > MAX_GUARD_DELAY_BLOCKS {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "Atraso das restrições acima de {} blocos",
+                        MAX_GUARD_DELAY_BLOCKS
+                    )));
+                }
+            }
+            OperationKind::SetGuardRule { destination, .. } => {
+                Address::parse(destination)?;
+            }
+            OperationKind::SetGuardians {
+                guardians,
+                threshold,
+                delay_blocks,
+            } => {
+                if guardians.len() > MAX_GUARDIANS {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "Mais de {} guardiões",
+                        MAX_GUARDIANS
+                    )));
+                }
+                if (*threshold == 0) != guardians.is_empty()
+                    || *threshold as usize > guardians.len()
+                {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "Limiar {} inválido para {} guardiões",
+                        threshold,
+                        guardians.len()
+                    )));
+                }
+                if !guardians.is_empty()
+                    && !(MIN_RECOVERY_DELAY_BLOCKS..=MAX_RECOVERY_DELAY_BLOCKS)
+                        .contains(delay_blocks)
+                {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "Prazo de recuperação deve ficar entre {} e {} blocos",
+                        MIN_RECOVERY_DELAY_BLOCKS, MAX_RECOVERY_DELAY_BLOCKS
+                    )));
+                }
+                for (i, guardian) in guardians.iter().enumerate() {
+                    Address::parse(guardian)?;
+                    if guardian == &self.author || guardians[..i].contains(guardian) {
+                        return Err(TransactionError::InvalidParameter(format!(
+                            "Guardião {} repetido ou igual à própria conta",
+                            guardian
+                        )));
+                    }
+                }
+            }
+            OperationKind::RotateKey { new_public_key } => {
+                PublicKey::from_bytes(new_public_key).map_err(|_| {
+                    TransactionError::InvalidPublicKey("Chave nova inválida".to_string())
+                })?;
+                if new_public_key == &self.public_key {
+                    return Err(TransactionError::InvalidParameter(
+                        "Chave nova igual à vigente".to_string(),
+                    ));
+                }
+            }
+            OperationKind::ApproveRecovery {
+                account,
+                new_public_key,
+            } => {
+                Address::parse(account)?;
+                PublicKey::from_bytes(new_public_key).map_err(|_| {
+                    TransactionError::InvalidPublicKey(
+                        "Chave nova da recuperação inválida".to_string(),
+                    )
+                })?;
+            }
+            OperationKind::SetTransferPolicy { rules, .. } => {
+                if rules.len() > MAX_TRANSFER_RULES {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "Política com mais de {} regras",
+                        MAX_TRANSFER_RULES
+                    )));
+                }
+                for rule in rules {
+                    match rule {
+                        TransferRule::MaxTransferAmount(0) => {
+                            return Err(TransactionError::ValorInvalido)
+                        }
+                        TransferRule::AllowList(addresses) => {
+                            if addresses.len() > MAX_ALLOW_LIST_SIZE {
+                                return Err(TransactionError::DataSizeExceeded);
+                            }
+                            for address in addresses {
+                                Address::parse(address)?;
+                            }
+                        }
+                        TransferRule::Contract { code } => {
+                            if code.is_empty() || code.len() > MAX_POLICY_CONTRACT_SIZE {
+                                return Err(TransactionError::DataSizeExceeded);
+                            }
+                        }
+                        TransferRule::MaxTransferAmount(_) | TransferRule::RequireKyc => {}
+                    }
+                }
+            }
+            OperationKind::CreateVesting {
+                beneficiary,
+                amount,
+                cliff_secs,
+                duration_secs,
+                ..
+            } => {
+                Address::parse(beneficiary)?;
+                if *amount == 0 {
+                    return Err(TransactionError::ValorInvalido);
+                }
+                if *duration_secs == 0 || cliff_secs > duration_secs {
+                    return Err(TransactionError::InvalidParameter(
+                        "Cronograma requer duração positiva e carência não maior que a duração"
+                            .to_string(),
+                    ));
+                }
+            }
+            OperationKind::ProposeTokenOwner { new_owner, .. } => {
+                Address::parse(new_owner)?;
+                if new_owner == &self.author {
+                    return Err(TransactionError::EnderecoInvalido);
+                }
+            }
+            OperationKind::SetTokenAdmin { admin, .. } => {
+                Address::parse(admin)?;
+            }
+            OperationKind::MintTokens { to, amount, .. } => {
+                Address::parse(to)?;
+                if *amount == 0 {
+                    return Err(TransactionError::ValorInvalido);
+                }
+            }
+            OperationKind::BurnTokens { amount, .. } => {
+                if *amount == 0 {
+                    return Err(TransactionError::ValorInvalido);
+                }
+            }
+            OperationKind::SubmitProposal {
+                description,
+                voting_blocks,
+            } => {
+                if description.trim().is_empty() {
+                    return Err(TransactionError::InvalidParameter(
+                        "Proposta sem descrição".to_string(),
+                    ));
+                }
+                if description.len() > MAX_PROPOSAL_DESCRIPTION_SIZE {
+                    return Err(TransactionError::DataSizeExceeded);
+                }
+                if *voting_blocks == 0 {
+                    return Err(TransactionError::InvalidParameter(
+                        "Período de votação deve ter ao menos um bloco".to_string(),
+                    ));
+                }
+            }
+            OperationKind::SetChainHalted { reason, .. } => {
+                if reason.trim().is_empty() {
+                    return Err(TransactionError::InvalidParameter(
+                        "Parada ou retomada de emergência exige um motivo".to_string(),
+                    ));
+                }
+                if reason.len() > MAX_HALT_REASON_SIZE {
+                    return Err(TransactionError::DataSizeExceeded);
+                }
+            }
+            #[cfg(feature = "node")]
+            OperationKind::RegisterChainClient {
+                chain_id,
+                validators,
+                threshold,
+            } => {
+                check_chain_id(chain_id)?;
+                if validators.len() > MAX_CHAIN_CLIENT_VALIDATORS {
+                    return Err(TransactionError::DataSizeExceeded);
+                }
+                LightClient::new(chain_id.clone(), validators.clone(), *threshold as usize)
+                    .map_err(|e| TransactionError::InvalidParameter(e.to_string()))?;
+            }
+            #[cfg(feature = "node")]
+            OperationKind::SendPacket {
+                destination_chain,
+                receiver,
+                amount,
+                ..
+            } => {
+                check_chain_id(destination_chain)?;
+                Address::parse(receiver)?;
+                if *amount == 0 {
+                    return Err(TransactionError::ValorInvalido);
+                }
+            }
+            #[cfg(feature = "node")]
+            OperationKind::ReceivePacket { packet, header, .. } => {
+                check_chain_id(&packet.source_chain)?;
+                Address::parse(&packet.receiver)?;
+                if packet.amount == 0 {
+                    return Err(TransactionError::ValorInvalido);
+                }
+                if header.header.chain_id != packet.source_chain {
+                    return Err(TransactionError::InvalidParameter(format!(
+                        "Cabeçalho de {} não prova pacote de {}",
+                        header.header.chain_id, packet.source_chain
+                    )));
+                }
+            }
+            OperationKind::ClaimVested { .. }
+            | OperationKind::AcceptTokenOwner { .. }
+            | OperationKind::SetTokenPaused { .. }
+            | OperationKind::CastVote { .. }
+            | OperationKind::ScheduleUpgrade { .. }
+            | OperationKind::SubmitOracleData { .. }
+            | OperationKind::CancelRecovery
+            | OperationKind::CompleteRecovery => {}
+        }
+
+        TimestampPolicy::for_context(TimestampContext::Transaction)
+            .validate_secs(self.timestamp)
+            .map_err(|_| TransactionError::TimestampInvalid)?;
+
+        self.verify()
+    }
+}
+
+#[cfg(feature = "node")]
+fn check_chain_id(chain_id: &str) -> Result<(), TransactionError> {
+    if chain_id.trim().is_empty() {
+        return Err(TransactionError::InvalidParameter(
+            "Identificador de cadeia vazio".to_string(),
+        ));
+    }
+    if chain_id.len() > MAX_CHAIN_ID_SIZE {
+        return Err(TransactionError::DataSizeExceeded);
+    }
+    Ok(())
+}
@@ -0,0 +1,309 @@
+use super::builder::Transaction;
+use super::processor::TransactionProcessor;
+use super::verification::VerificationService;
+use crate::blockchain::SharedBlockchain;
+use crate::error::TransactionError;
+use log::{debug, warn};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot, Semaphore};
+
+/// Resultado entregue ao remetente: admissão no mempool ou motivo da rejeição
+pub type SubmissionResult = Result<(), TransactionError>;
+
+/// Configuração do pipeline de processamento de transações
+#[derive(Debug, Clone)]
+pub struct PipelineConfig {
+    /// Capacidade da fila de bytes recebidos (entrada do pipeline)
+    pub decode_queue: usize,
+
+    /// Capacidade da fila de verificações baratas
+    pub check_queue: usize,
+
+    /// Capacidade da fila de verificação de assinaturas
+    pub verify_queue: usize,
+
+    /// Capacidade da fila de inserção no mempool
+    pub insert_queue: usize,
+
+    /// Número máximo de verificações Dilithium5 em andamento no pool de verificação
+    pub verify_workers: usize,
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        Self {
+            decode_queue: 1024,
+            check_queue: 512,
+            verify_queue: 256,
+            insert_queue: 256,
+            verify_workers: std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(4),
+        }
+    }
+}
+
+/// Fotografia das métricas do pipeline
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PipelineMetrics {
+    pub decode_depth: usize,
+    pub check_depth: usize,
+    pub verify_depth: usize,
+    pub insert_depth: usize,
+    pub accepted: u64,
+    pub rejected: u64,
+    pub busy: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    decode_depth: AtomicUsize,
+    check_depth: AtomicUsize,
+    verify_depth: AtomicUsize,
+    insert_depth: AtomicUsize,
+    accepted: AtomicU64,
+    rejected: AtomicU64,
+    busy: AtomicU64,
+}
+
+impl Counters {
+    fn reject(&self, reply: oneshot::Sender<SubmissionResult>, error: TransactionError) {
+        debug!("Transação rejeitada no pipeline: {}", error);
+        self.rejected.fetch_add(1, Ordering::Relaxed);
+        let _ = reply.send(Err(error));
+    }
+}
+
+struct Job<T> {
+    item: T,
+    reply: oneshot::Sender<SubmissionResult>,
+}
+
+type VerifiedJob = Job<oneshot::Receiver<(Transaction, Result<(), TransactionError>)>>;
+
+/// Pipeline assíncrono de admissão de transações:
+/// decodificação → verificações baratas → verificação de assinatura → inserção no mempool.
+///
+/// Os estágios são ligados por canais limitados; quando a fila de entrada está cheia
+/// a submissão é rejeitada com `TransactionError::Busy` em vez de acumular memória.
+/// A ordem de chegada é preservada até a inserção, de modo que nonces consecutivos
+/// de um mesmo remetente sejam admitidos em sequência.
+pub struct TransactionPipeline {
+    entry: mpsc::Sender<Job<Vec<u8>>>,
+    counters: Arc<Counters>,
+}
+
+impl TransactionPipeline {
+    /// Inicia os estágios no runtime tokio atual
+    pub fn start(blockchain: SharedBlockchain, config: PipelineConfig) -> Self {
+        let counters = Arc::new(Counters::default());
+
+        let (entry, decode_rx) = mpsc::channel(config.decode_queue.max(1));
+        let (check_tx, check_rx) = mpsc::channel(config.check_queue.max(1));
+        let (verify_tx, verify_rx) = mpsc::channel(config.verify_queue.max(1));
+        let (insert_tx, insert_rx) = mpsc::channel(config.insert_queue.max(1));
+
+        tokio::spawn(decode_stage(decode_rx, check_tx, Arc::clone(&counters)));
+        tokio::spawn(check_stage(
+            check_rx,
+            verify_tx,
+            blockchain.clone(),
+            Arc::clone(&counters),
+        ));
+        tokio::spawn(verify_stage(
+            verify_rx,
+            insert_tx,
+            config.verify_workers.max(1),
+            Arc::clone(&counters),
+        ));
+        tokio::spawn(insert_stage(insert_rx, blockchain, Arc::clone(&counters)));
+
+        Self { entry, counters }
+    }
+
+    /// Enfileira uma transação codificada sem esperar; retorna `Busy` se o pipeline
+    /// estiver saturado. O receptor entrega o resultado final da admissão.
+    pub fn try_submit(
+        &self,
+        data: Vec<u8>,
+    ) -> Result<oneshot::Receiver<SubmissionResult>, TransactionError> {
+        let (reply, receiver) = oneshot::channel();
+
+        // Contabiliza antes do envio para que o estágio nunca decremente primeiro
+        self.counters.decode_depth.fetch_add(1, Ordering::Relaxed);
+        match self.entry.try_send(Job { item: data, reply }) {
+            Ok(()) => Ok(receiver),
+            Err(e) => {
+                self.counters.decode_depth.fetch_sub(1, Ordering::Relaxed);
+                match e {
+                    mpsc::error::TrySendError::Full(_) => {
+                        self.counters.busy.fetch_add(1, Ordering::Relaxed);
+                        warn!("Pipeline de transações saturado, rejeitando submissão");
+                        Err(TransactionError::Busy)
+                    }
+                    mpsc::error::TrySendError::Closed(_) => Err(TransactionError::Other(
+                        "Pipeline de transações encerrado".to_string(),
+                    )),
+                }
+            }
+        }
+    }
+
+    /// Submete uma transação codificada e aguarda o resultado da admissão
+    pub async fn submit(&self, data: Vec<u8>) -> SubmissionResult {
+        self.try_submit(data)?.await.map_err(|_| {
+            TransactionError::Other("Pipeline encerrado antes da resposta".to_string())
+        })?
+    }
+
+    /// Submete uma transação já decodificada
+    pub async fn submit_transaction(&self, tx: &Transaction) -> SubmissionResult {
+        let data = bincode::serialize(tx).map_err(|_| TransactionError::InvalidDataFormat)?;
+        self.submit(data).await
+    }
+
+    pub fn metrics(&self) -> PipelineMetrics {
+        let c = &self.counters;
+        PipelineMetrics {
+            decode_depth: c.decode_depth.load(Ordering::Relaxed),
+            check_depth: c.check_depth.load(Ordering::Relaxed),
+            verify_depth: c.verify_depth.load(Ordering::Relaxed),
+            insert_depth: c.insert_depth.load(Ordering::Relaxed),
+            accepted: c.accepted.load(Ordering::Relaxed),
+            rejected: c.rejected.load(Ordering::Relaxed),
+            busy: c.busy.load(Ordering::Relaxed),
+        }
+    }
+}
+
+async fn decode_stage(
+    mut rx: mpsc::Receiver<Job<Vec<u8>>>,
+    next: mpsc::Sender<Job<Transaction>>,
+    counters: Arc<Counters>,
+) {
+    while let Some(job) = rx.recv().await {
+        counters.decode_depth.fetch_sub(1, Ordering::Relaxed);
+
+        match Transaction::deserialize_from_bytes(&job.item) {
+            Ok(tx) => {
+                counters.check_depth.fetch_add(1, Ordering::Relaxed);
+                if next
+                    .send(Job {
+                        item: tx,
+                        reply: job.reply,
+                    })
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            Err(e) => counters.reject(job.reply, e),
+        }
+    }
+}
+
+async fn check_stage(
+    mut rx: mpsc::Receiver<Job<Transaction>>,
+    next: mpsc::Sender<Job<Transaction>>,
+    blockchain: SharedBlockchain,
+    counters: Arc<Counters>,
+) {
+    let processor = TransactionProcessor;
+
+    while let Some(first) = rx.recv().await {
+        // Limites relidos a cada lote para acompanhar recargas de configuração
+        let limits = blockchain.limits();
+        let mut next_job = Some(first);
+
+        while let Some(job) = next_job.take() {
+            counters.check_depth.fetch_sub(1, Ordering::Relaxed);
+
+            match processor.cheap_checks_with(&job.item, &limits) {
+                Ok(()) => {
+                    counters.verify_depth.fetch_add(1, Ordering::Relaxed);
+                    if next.send(job).await.is_err() {
+                        return;
+                    }
+                }
+                Err(e) => {
+                    blockchain.record_rejection(&job.item, &e);
+                    counters.reject(job.reply, e);
+                }
+            }
+
+            next_job = rx.try_recv().ok();
+        }
+    }
+}
+
+async fn verify_stage(
+    mut rx: mpsc::Receiver<Job<Transaction>>,
+    next: mpsc::Sender<VerifiedJob>,
+    workers: usize,
+    counters: Arc<Counters>,
+) {
+    let verifier = VerificationService::global().clone();
+    let permits = Arc::new(Semaphore::new(workers));
+
+    while let Some(job) = rx.recv().await {
+        counters.verify_depth.fetch_sub(1, Ordering::Relaxed);
+
+        let permit = match Arc::clone(&permits).acquire_owned().await {
+            Ok(permit) => permit,
+            Err(_) => break,
+        };
+
+        // A verificação roda no pool dedicado; o receptor segue em ordem para a inserção
+        let tx = job.item;
+        let handle = verifier.spawn(move || {
+            let _permit = permit;
+            let verified = TransactionProcessor.verify_signature(&tx);
+            (tx, verified)
+        });
+
+        counters.insert_depth.fetch_add(1, Ordering::Relaxed);
+        if next
+            .send(Job {
+                item: handle,
+                reply: job.reply,
+            })
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+async fn insert_stage(
+    mut rx: mpsc::Receiver<VerifiedJob>,
+    blockchain: SharedBlockchain,
+    counters: Arc<Counters>,
+) {
+    while let Some(job) = rx.recv().await {
+        let Ok((tx, verified)) = job.item.await else {
+            counters.insert_depth.fetch_sub(1, Ordering::Relaxed);
+            let interrupted = "Verificação de assinatura interrompida".to_string();
+            counters.reject(job.reply, TransactionError::Other(interrupted));
+            continue;
+        };
+        counters.insert_depth.fetch_sub(1, Ordering::Relaxed);
+
+        let admitted = match verified {
+            Ok(()) => blockchain.admit_transaction(tx),
+            Err(e) => {
+                blockchain.record_rejection(&tx, &e);
+                Err(e)
+            }
+        };
+        match admitted {
+            Ok(()) => {
+                counters.accepted.fetch_add(1, Ordering::Relaxed);
+                let _ = job.reply.send(Ok(()));
+            }
+            Err(e) => counters.reject(job.reply, e),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use super::builder::{NonceRegistry, Transaction};
-use crate::constants::{MAX_AMOUNT, MAX_SIGNATURE_SIZE, MIN_AMOUNT, TIMESTAMP_WINDOW};
+use super::scheme::SignatureScheme;
+use crate::config::Limits;
+use crate::constants::{MAX_AMOUNT, MIN_AMOUNT};
 use crate::error::TransactionError;
+use crate::utils::timestamp_policy::{TimestampContext, TimestampPolicy};
 use once_cell::sync::Lazy;
 use std::collections::HashSet;
 use std::sync::Mutex;
@@ -15,6 +18,23 @@ impl TransactionProcessor {
         &self,
         transaction: &Transaction,
         nonce_registry: &mut NonceRegistry,
+    ) -> Result<(), TransactionError> {
+        self.cheap_checks(transaction)?;
+        nonce_registry.validate_nonce(&transaction.from, transaction.nonce)?;
+
+        Ok(())
+    }
+
+    /// Verificações baratas e sem estado, executadas antes da verificação de
+    /// assinatura, com os limites de tamanho padrão
+    pub fn cheap_checks(&self, transaction: &Transaction) -> Result<(), TransactionError> {
+        self.cheap_checks_with(transaction, &Limits::default())
+    }
+
+    pub fn cheap_checks_with(
+        &self,
+        transaction: &Transaction,
+        limits: &Limits,
     ) -> Result<(), TransactionError> {
         if transaction.amount < MIN_AMOUNT || transaction.amount > MAX_AMOUNT {
             return Err(TransactionError::InvalidData(
@@ -24,22 +44,17 @@ impl TransactionProcessor {
 
         transaction.validate_address()?;
 
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|_| TransactionError::TimestampInvalid)?
-            .as_secs() as i64;
-
-        if (now - transaction.timestamp).abs() > TIMESTAMP_WINDOW {
-            return Err(TransactionError::TimestampInvalid);
-        }
-
-        if transaction.signature.len() > MAX_SIGNATURE_SIZE {
-            return Err(TransactionError::SignatureSizeExceeded);
-        }
+        TimestampPolicy::for_context(TimestampContext::Transaction)
+            .validate_secs(transaction.timestamp)
+            .map_err(|_| TransactionError::TimestampInvalid)?;
 
-        nonce_registry.validate_nonce(&transaction.from, transaction.nonce)?;
+        limits.check_transaction(transaction)?;
+        SignatureScheme::check(&transaction.public_key, &transaction.signature).map(|_| ())
+    }
 
-        Ok(())
+    /// Verificação da assinatura Dilithium5 com a chave pública embutida na transação
+    pub fn verify_signature(&self, transaction: &Transaction) -> Result<(), TransactionError> {
+        transaction.view().verify_embedded_key()
     }
 
     pub fn verify_nonce(&self, nonce: &[u8]) -> Result<(), TransactionError> {
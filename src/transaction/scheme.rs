@@ -0,0 +1,95 @@
+use crate::error::TransactionError;
+use pqcrypto_dilithium::{dilithium2, dilithium3, dilithium5};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Esquemas de assinatura conhecidos pelo nó.
+///
+/// Transações e operações não trazem um campo de esquema: ele é declarado pelo
+/// tamanho da chave pública, que é diferente em cada variante do Dilithium. A
+/// assinatura precisa então ter exatamente o tamanho do mesmo esquema.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureScheme {
+    Dilithium2,
+    Dilithium3,
+    Dilithium5,
+}
+
+/// Esquemas cujas assinaturas o nó verifica; os demais são reconhecidos apenas
+/// para recusar a transação com uma mensagem clara
+pub const SUPPORTED_SCHEMES: &[SignatureScheme] = &[SignatureScheme::Dilithium5];
+
+impl SignatureScheme {
+    pub const ALL: [SignatureScheme; 3] = [
+        SignatureScheme::Dilithium2,
+        SignatureScheme::Dilithium3,
+        SignatureScheme::Dilithium5,
+    ];
+
+    pub const fn public_key_size(self) -> usize {
+        match self {
+            SignatureScheme::Dilithium2 => dilithium2::public_key_bytes(),
+            SignatureScheme::Dilithium3 => dilithium3::public_key_bytes(),
+            SignatureScheme::Dilithium5 => dilithium5::public_key_bytes(),
+        }
+    }
+
+    pub const fn signature_size(self) -> usize {
+        match self {
+            SignatureScheme::Dilithium2 => dilithium2::signature_bytes(),
+            SignatureScheme::Dilithium3 => dilithium3::signature_bytes(),
+            SignatureScheme::Dilithium5 => dilithium5::signature_bytes(),
+        }
+    }
+
+    pub fn is_supported(self) -> bool {
+        SUPPORTED_SCHEMES.contains(&self)
+    }
+
+    /// Esquema declarado por uma chave pública com `len` bytes
+    pub fn from_public_key_len(len: usize) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scheme| scheme.public_key_size() == len)
+    }
+
+    /// Identifica o esquema pela chave e confere que a assinatura tem o tamanho
+    /// dele e que o nó sabe verificá-lo, antes de qualquer operação criptográfica
+    pub fn check(public_key: &[u8], signature: &[u8]) -> Result<Self, TransactionError> {
+        let scheme = Self::from_public_key_len(public_key.len()).ok_or_else(|| {
+            TransactionError::InvalidPublicKey(format!(
+                "Chave pública de {} bytes não corresponde a nenhum esquema de assinatura",
+                public_key.len()
+            ))
+        })?;
+        if signature.len() != scheme.signature_size() {
+            return Err(TransactionError::InvalidSignature(format!(
+                "Assinatura de {} bytes para chave {}, que exige {}",
+                signature.len(),
+                scheme,
+                scheme.signature_size()
+            )));
+        }
+        if !scheme.is_supported() {
+            return Err(TransactionError::InvalidSignature(format!(
+                "Esquema de assinatura {} não suportado",
+                scheme
+            )));
+        }
+        Ok(scheme)
+    }
+}
+
+impl fmt::Display for SignatureScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SignatureScheme::Dilithium2 => "dilithium2",
+            SignatureScheme::Dilithium3 => "dilithium3",
+            SignatureScheme::Dilithium5 => "dilithium5",
+        })
+    }
+}
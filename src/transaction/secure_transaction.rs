@@ -1,29 +1,54 @@
 use crate::error::TransactionError;
+use crate::transaction::confidential::{seal, view_key_id, ViewKey, PAYLOAD_KEY_DOMAIN};
+use crate::transaction::view::with_signing_buffer;
+use crate::utils::entropy::entropy;
 use pqcrypto_dilithium::dilithium5;
 use pqcrypto_dilithium::dilithium5::{detached_sign, PublicKey, SecretKey};
 use pqcrypto_traits::sign::{
     DetachedSignature as PqcDetachedSignature, PublicKey as TraitsPublicKey,
 };
 use secrecy::Zeroize;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sodiumoxide::crypto::secretbox;
 
 const MAX_SIGNATURE_SIZE: usize = 4627; // Tamanho da assinatura Dilithium5
 
-#[derive(Debug, Serialize, Deserialize, Clone, Zeroize)]
+/// Chave de cifra do payload selada para uma chave de visualização (Kyber512).
+///
+/// Permite a auditores e reguladores ler os dados da transação sem qualquer acesso
+/// às chaves de assinatura da conta.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Zeroize, JsonSchema)]
+pub struct PayloadGrant {
+    pub view_key_id: String,
+    pub encapsulated_key: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Zeroize, JsonSchema)]
 pub struct SecureTransaction {
     pub from: String,
     pub to: String,
     pub amount: u64,
     pub timestamp: i64,
     pub nonce: u64,
+    #[cfg_attr(feature = "cbor", serde(with = "serde_bytes"), schemars(with = "Vec<u8>"))]
     pub signature: Vec<u8>,
+    #[cfg_attr(feature = "cbor", serde(with = "serde_bytes"), schemars(with = "Vec<u8>"))]
     pub public_key: Vec<u8>,
+    #[cfg_attr(feature = "cbor", serde(with = "serde_bytes"), schemars(with = "Vec<u8>"))]
     pub cipher_key: Vec<u8>,
+    #[cfg_attr(feature = "cbor", serde(with = "serde_bytes"), schemars(with = "Vec<u8>"))]
     pub encrypted_data: Vec<u8>,
+    #[cfg_attr(feature = "cbor", serde(with = "serde_bytes"), schemars(with = "Vec<u8>"))]
     pub iv: Vec<u8>,
+    #[cfg_attr(feature = "cbor", serde(with = "serde_bytes"), schemars(with = "Vec<u8>"))]
     pub salt: Vec<u8>,
+    #[cfg_attr(feature = "cbor", serde(with = "serde_bytes"), schemars(with = "Vec<u8>"))]
     pub mac: Vec<u8>,
+    /// Acessos de leitura ao payload concedidos a chaves de visualização
+    #[serde(default)]
+    pub payload_grants: Vec<PayloadGrant>,
 }
 
 impl SecureTransaction {
@@ -37,11 +62,11 @@ impl SecureTransaction {
         public_key: &PublicKey,
     ) -> Result<Self, TransactionError> {
         // Gera salt e IV aleatórios
-        let salt = secretbox::gen_nonce().0.to_vec();
-        let iv = secretbox::gen_nonce().0.to_vec();
+        let salt = entropy().bytes(secretbox::NONCEBYTES);
+        let iv = entropy().bytes(secretbox::NONCEBYTES);
 
         // Gera chave de cifra
-        let cipher_key = secretbox::gen_key().0.to_vec();
+        let cipher_key = entropy().bytes(secretbox::KEYBYTES);
 
         // Cria a transação inicial
         let mut transaction = SecureTransaction {
@@ -57,6 +82,7 @@ impl SecureTransaction {
             iv,
             salt,
             mac: Vec::new(),
+            payload_grants: Vec::new(),
         };
 
         // Encripta os dados
@@ -80,16 +106,17 @@ impl SecureTransaction {
         public_key: &PublicKey,
         signature: &[u8],
     ) -> Result<bool, TransactionError> {
-        let mac_valid = self.verify_mac()?;
-        let data_valid = self.decrypt_data().is_ok();
-        let sig_valid = dilithium5::verify_detached_signature(
-            &dilithium5::DetachedSignature::from_bytes(signature).map_err(|_| {
-                TransactionError::InvalidSignature("Assinatura inválida".to_string())
-            })?,
-            &self.serialize_data()?,
-            public_key,
-        )
-        .is_ok();
+        // Com a chave de cifra retirada o payload só é verificável por quem tem
+        // uma das chaves de visualização; a assinatura continua cobrindo os campos
+        let sealed = self.is_sealed();
+        let mac_valid = sealed || self.verify_mac()?;
+        let data_valid = sealed || self.decrypt_data().is_ok();
+        let signature = dilithium5::DetachedSignature::from_bytes(signature)
+            .map_err(|_| TransactionError::InvalidSignature("Assinatura inválida".to_string()))?;
+        let sig_valid = with_signing_buffer(|buf| {
+            self.write_signing_data(buf);
+            dilithium5::verify_detached_signature(&signature, buf, public_key).is_ok()
+        });
 
         // Executa todas as verificações mesmo quando falha para prevenir timing attacks
         if mac_valid && data_valid && sig_valid {
@@ -101,6 +128,72 @@ impl SecureTransaction {
         }
     }
 
+    /// Concede leitura do payload às chaves de visualização `view_keys` e retira a
+    /// chave de cifra da transação; a partir daí só os titulares dessas chaves
+    /// conseguem decifrá-lo.
+    pub fn grant_view_keys(&mut self, view_keys: &[Vec<u8>]) -> Result<(), TransactionError> {
+        if self.cipher_key.is_empty() {
+            return Err(TransactionError::InvalidData(
+                "Chave de cifra já retirada da transação".to_string(),
+            ));
+        }
+        if view_keys.is_empty() {
+            return Err(TransactionError::InvalidParameter(
+                "Nenhuma chave de visualização informada".to_string(),
+            ));
+        }
+
+        let mut grants = Vec::with_capacity(view_keys.len());
+        for public_key in view_keys {
+            let (encapsulated_key, ciphertext) =
+                seal(&self.cipher_key, public_key, PAYLOAD_KEY_DOMAIN)?;
+            grants.push(PayloadGrant {
+                view_key_id: view_key_id(public_key),
+                encapsulated_key,
+                ciphertext,
+            });
+        }
+        self.payload_grants.extend(grants);
+        self.cipher_key.zeroize();
+        self.cipher_key.clear();
+        Ok(())
+    }
+
+    /// Indica se a chave de cifra foi retirada e o payload só é legível por
+    /// chaves de visualização
+    pub fn is_sealed(&self) -> bool {
+        self.cipher_key.is_empty() && !self.payload_grants.is_empty()
+    }
+
+    /// Decifra o payload (`from:to:amount:timestamp:nonce`) com uma chave de
+    /// visualização que recebeu acesso, conferindo-o contra os campos assinados
+    pub fn open_payload(&self, view_key: &ViewKey) -> Result<String, TransactionError> {
+        let id = view_key.id();
+        let grant = self
+            .payload_grants
+            .iter()
+            .find(|grant| grant.view_key_id == id)
+            .ok_or_else(|| {
+                TransactionError::InvalidParameter(
+                    "Nenhum acesso concedido a esta chave de visualização".to_string(),
+                )
+            })?;
+        let cipher_key = view_key.unseal(
+            &grant.view_key_id,
+            &grant.encapsulated_key,
+            &grant.ciphertext,
+            PAYLOAD_KEY_DOMAIN,
+        )?;
+        let payload = self.decrypt_with(&cipher_key)?;
+        if payload != self.serialize_data()? {
+            return Err(TransactionError::InvalidData(
+                "Payload não corresponde aos campos assinados".to_string(),
+            ));
+        }
+        String::from_utf8(payload)
+            .map_err(|_| TransactionError::InvalidData("Payload inválido".to_string()))
+    }
+
     pub fn size(&self) -> usize {
         self.from.len() + self.to.len() + 8 + 8 + 8 + self.signature.len() + self.public_key.len()
         // +8 para amount, timestamp e nonce
@@ -127,10 +220,14 @@ impl SecureTransaction {
     }
 
     fn decrypt_data(&self) -> Result<Vec<u8>, TransactionError> {
+        self.decrypt_with(&self.cipher_key)
+    }
+
+    fn decrypt_with(&self, cipher_key: &[u8]) -> Result<Vec<u8>, TransactionError> {
         let nonce = secretbox::Nonce::from_slice(&self.iv)
             .ok_or(TransactionError::InvalidData("Invalid nonce".to_string()))?;
 
-        let key = secretbox::Key::from_slice(&self.cipher_key)
+        let key = secretbox::Key::from_slice(cipher_key)
             .ok_or(TransactionError::InvalidData("Invalid key".to_string()))?;
 
         secretbox::open(&self.encrypted_data, &nonce, &key)
@@ -157,12 +254,22 @@ impl SecureTransaction {
     }
 
     fn serialize_data(&self) -> Result<Vec<u8>, TransactionError> {
-        let data = format!(
+        let mut data = Vec::new();
+        self.write_signing_data(&mut data);
+        Ok(data)
+    }
+
+    /// Escreve `from:to:amount:timestamp:nonce` em `buf`, substituindo o conteúdo
+    fn write_signing_data(&self, buf: &mut Vec<u8>) {
+        use std::io::Write;
+
+        buf.clear();
+        // Escrita em Vec<u8> não falha
+        let _ = write!(
+            buf,
             "{}:{}:{}:{}:{}",
             self.from, self.to, self.amount, self.timestamp, self.nonce
         );
-
-        Ok(data.into_bytes())
     }
 
     fn generate_mac(&mut self, data: &[u8]) -> Result<(), TransactionError> {
@@ -1,4 +1,5 @@
 use crate::error::TransactionError;
+use crate::utils::dilithium;
 use pqcrypto_dilithium::dilithium5::{sign, PublicKey, SecretKey};
 use pqcrypto_traits::sign::SignedMessage;
 
@@ -6,7 +7,7 @@ pub struct TransactionSigner;
 
 impl TransactionSigner {
     pub fn generate_keys() -> Result<(SecretKey, PublicKey), TransactionError> {
-        let (public_key, secret_key) = pqcrypto_dilithium::dilithium5::keypair();
+        let (public_key, secret_key) = dilithium::keypair();
         Ok((secret_key, public_key))
     }
 
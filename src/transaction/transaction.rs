@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::error::Error as StdError;
 use super::secure_transaction::SecureTransaction;
+use crate::utils::clock::clock;
 use pqcrypto_dilithium::dilithium5::{SecretKey, PublicKey, sign, DetachedSignature,verify_detached_signature, keypair};
 use std::collections::HashMap;
 use bincode::{serialize, deserialize};
@@ -153,10 +154,7 @@ impl Transaction {
             return Err(TransactionError::AddressFormatInvalid);
         }
 
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|_| TransactionError::TimestampInvalid)?
-            .as_secs() as i64;
+        let timestamp = clock().now_secs();
 
         let mut transaction = Transaction {
             token_id: 0,
@@ -302,10 +300,7 @@ impl Transaction {
     }
 
     fn validate_timestamp(&self) -> Result<(), TransactionError> {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|_| TransactionError::TimestampInvalid)?
-            .as_secs() as i64;
+        let now = clock().now_secs();
 
         match now.checked_sub(self.timestamp) {
             Some(diff) if diff.abs() <= TIMESTAMP_WINDOW => Ok(()),
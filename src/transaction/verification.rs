@@ -0,0 +1,128 @@
+use super::builder::Transaction;
+use super::processor::TransactionProcessor;
+use super::secure_transaction::SecureTransaction;
+use crate::error::TransactionError;
+use once_cell::sync::Lazy;
+use pqcrypto_dilithium::dilithium5::PublicKey;
+use pqcrypto_traits::sign::PublicKey as _;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::sync::Arc;
+use tokio::sync::oneshot;
+
+/// Serviço global, dimensionado pelo número de núcleos disponíveis
+static GLOBAL_SERVICE: Lazy<VerificationService> = Lazy::new(|| {
+    VerificationService::new(default_threads())
+        .expect("Falha ao criar pool de verificação de assinaturas")
+});
+
+fn default_threads() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+}
+
+/// Serviço de verificação de assinaturas Dilithium5 com pool de threads dedicado.
+///
+/// Mempool e validação de blocos submetem lotes ao pool, que é separado das
+/// threads do runtime tokio e da thread do consenso.
+#[derive(Clone)]
+pub struct VerificationService {
+    pool: Arc<ThreadPool>,
+}
+
+impl VerificationService {
+    pub fn new(threads: usize) -> Result<Self, TransactionError> {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads.max(1))
+            .thread_name(|i| format!("verificacao-{}", i))
+            .build()
+            .map_err(|e| {
+                TransactionError::Other(format!("Falha ao criar pool de verificação: {}", e))
+            })?;
+
+        Ok(Self {
+            pool: Arc::new(pool),
+        })
+    }
+
+    /// Instância compartilhada pelo processo
+    pub fn global() -> &'static VerificationService {
+        &GLOBAL_SERVICE
+    }
+
+    pub fn threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
+    /// Verifica um lote de transações em paralelo; os resultados seguem a ordem de entrada
+    pub fn verify_batch(&self, transactions: &[Transaction]) -> Vec<Result<(), TransactionError>> {
+        self.pool.install(|| {
+            transactions
+                .par_iter()
+                .map(|tx| TransactionProcessor.verify_signature(tx))
+                .collect()
+        })
+    }
+
+    /// Verifica um lote de transações seguras de um bloco (MAC, cifra e assinatura)
+    pub fn verify_secure_batch(
+        &self,
+        transactions: &[SecureTransaction],
+    ) -> Vec<Result<bool, TransactionError>> {
+        self.pool.install(|| {
+            transactions
+                .par_iter()
+                .map(|tx| {
+                    let public_key = PublicKey::from_bytes(&tx.public_key).map_err(|_| {
+                        TransactionError::InvalidSignature("Chave pública inválida".to_string())
+                    })?;
+                    tx.verify(&public_key, &tx.signature)
+                })
+                .collect()
+        })
+    }
+
+    /// Variante assíncrona de `verify_batch`: a tarefa chamadora apenas aguarda o
+    /// resultado, sem ocupar uma thread do runtime durante a verificação
+    pub async fn verify_batch_async(
+        &self,
+        transactions: Vec<Transaction>,
+    ) -> Vec<Result<(), TransactionError>> {
+        let count = transactions.len();
+        let service = self.clone();
+        let receiver = self.spawn(move || service.verify_batch(&transactions));
+
+        // Um resultado por transação, mesmo com o pool encerrado
+        receiver.await.unwrap_or_else(|_| {
+            std::iter::repeat_with(|| {
+                Err(TransactionError::Other(
+                    "Pool de verificação encerrado".to_string(),
+                ))
+            })
+            .take(count)
+            .collect()
+        })
+    }
+
+    /// Executa `f` no pool e entrega o resultado pelo receptor
+    pub(crate) fn spawn<T, F>(&self, f: F) -> oneshot::Receiver<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let (reply, receiver) = oneshot::channel();
+        self.pool.spawn(move || {
+            let _ = reply.send(f());
+        });
+        receiver
+    }
+}
+
+impl std::fmt::Debug for VerificationService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VerificationService")
+            .field("threads", &self.threads())
+            .finish()
+    }
+}
@@ -1,4 +1,5 @@
 use crate::error::TransactionError;
+use crate::utils::address::derive_address;
 use pqcrypto_dilithium::dilithium5::{verify_detached_signature, DetachedSignature, PublicKey};
 use pqcrypto_traits::sign::DetachedSignature as PqcDetachedSignature;
 
@@ -19,4 +20,33 @@ impl TransactionVerifier {
 
         Ok(())
     }
+
+    /// Confere se `public_key` responde por `account`. Enquanto a conta não teve a
+    /// chave trocada (`rotated_key` vazio) vale a chave da qual o endereço deriva
+    /// ou a registrada para ele (`registered_key`); depois de uma rotação
+    /// registrada na cadeia, só a chave nova. Para blocos antigos, `rotated_key` é
+    /// a chave em vigor no nonce verificado. Vale na admissão ao mempool, na
+    /// validação de blocos recebidos e na verificação da cadeia
+    pub fn verify_account_key(
+        &self,
+        account: &str,
+        public_key: &[u8],
+        rotated_key: Option<&[u8]>,
+        registered_key: Option<&[u8]>,
+    ) -> Result<(), TransactionError> {
+        match rotated_key {
+            Some(current) if current != public_key => Err(TransactionError::KeyRotated(format!(
+                "{} agora só aceita a chave registrada na recuperação",
+                account
+            ))),
+            Some(_) => Ok(()),
+            None if derive_address(public_key) == account || registered_key == Some(public_key) => {
+                Ok(())
+            }
+            None => Err(TransactionError::InvalidPublicKey(format!(
+                "Chave pública não pertence a {}",
+                account
+            ))),
+        }
+    }
 }
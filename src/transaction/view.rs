@@ -0,0 +1,192 @@
+use super::builder::Transaction;
+use super::scheme::SignatureScheme;
+use crate::constants::{MAX_SIGNATURE_SIZE, MAX_TRANSACTION_SIZE};
+use crate::error::TransactionError;
+use bytes::Bytes;
+use pqcrypto_dilithium::dilithium5::{self, PublicKey};
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+thread_local! {
+    // Buffer reaproveitado pela codificação canônica em cada thread de verificação
+    static SIGNING_BUFFER: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Empresta o buffer de codificação da thread atual; evita uma alocação por verificação
+pub(crate) fn with_signing_buffer<R>(f: impl FnOnce(&mut Vec<u8>) -> R) -> R {
+    SIGNING_BUFFER.with(|buf| f(&mut buf.borrow_mut()))
+}
+
+/// Campos cobertos pela assinatura, na ordem da codificação canônica
+#[derive(Serialize)]
+struct SignableData<'a> {
+    token_id: u64,
+    from: &'a str,
+    to: &'a str,
+    amount: u64,
+    timestamp: i64,
+    nonce: u64,
+    public_key: &'a [u8],
+}
+
+/// Transação emprestada: os campos apontam para o buffer de origem (bytes da rede
+/// ou uma `Transaction` existente), sem cópias de strings ou chaves.
+///
+/// O layout bincode é idêntico ao de `Transaction`, então os mesmos bytes podem ser
+/// decodificados em qualquer uma das duas formas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionView<'a> {
+    pub token_id: u64,
+    pub from: &'a str,
+    pub to: &'a str,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub nonce: u64,
+    pub public_key: &'a [u8],
+    pub signature: &'a [u8],
+    pub transaction_hash: &'a [u8],
+    pub hash: &'a str,
+}
+
+impl<'a> TransactionView<'a> {
+    /// Decodifica sem alocar, com os mesmos limites de `Transaction::deserialize_from_bytes`
+    pub fn decode(data: &'a [u8]) -> Result<Self, TransactionError> {
+        use bincode::Options;
+
+        if data.len() > MAX_TRANSACTION_SIZE {
+            return Err(TransactionError::DataSizeExceeded);
+        }
+
+        bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .with_limit(MAX_TRANSACTION_SIZE as u64)
+            .deserialize(data)
+            .map_err(|_| TransactionError::InvalidDataFormat)
+    }
+
+    /// Escreve a codificação canônica de assinatura em `buf`, substituindo o conteúdo
+    pub fn serialize_for_signing_into(&self, buf: &mut Vec<u8>) -> Result<(), TransactionError> {
+        let data = SignableData {
+            token_id: self.token_id,
+            from: self.from,
+            to: self.to,
+            amount: self.amount,
+            timestamp: self.timestamp,
+            nonce: self.nonce,
+            public_key: self.public_key,
+        };
+
+        buf.clear();
+        bincode::serialize_into(&mut *buf, &data).map_err(|_| TransactionError::InvalidDataFormat)
+    }
+
+    pub fn serialize_for_signing(&self) -> Result<Vec<u8>, TransactionError> {
+        let mut buf = Vec::new();
+        self.serialize_for_signing_into(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Verifica a assinatura Dilithium5 usando o buffer de codificação da thread atual
+    pub fn verify(&self, public_key: &PublicKey) -> Result<(), TransactionError> {
+        if self.signature.len() > MAX_SIGNATURE_SIZE {
+            return Err(TransactionError::SignatureSizeExceeded);
+        }
+
+        let signature = dilithium5::DetachedSignature::from_bytes(self.signature)
+            .map_err(|_| TransactionError::InvalidSignatures("Invalid signature".to_string()))?;
+
+        with_signing_buffer(|buf| {
+            self.serialize_for_signing_into(buf)?;
+            dilithium5::verify_detached_signature(&signature, buf, public_key)
+                .map_err(|_| TransactionError::InvalidSignatures("Invalid signature".to_string()))
+        })
+    }
+
+    /// Verifica a assinatura com a chave pública embutida na própria transação
+    pub fn verify_embedded_key(&self) -> Result<(), TransactionError> {
+        SignatureScheme::check(self.public_key, self.signature)?;
+        let public_key = PublicKey::from_bytes(self.public_key).map_err(|_| {
+            TransactionError::InvalidPublicKey("Chave pública inválida".to_string())
+        })?;
+        self.verify(&public_key)
+    }
+
+    /// Cópia proprietária, necessária apenas quando a transação entra no mempool
+    pub fn to_transaction(&self) -> Transaction {
+        Transaction {
+            token_id: self.token_id,
+            from: self.from.to_string(),
+            to: self.to.to_string(),
+            amount: self.amount,
+            timestamp: self.timestamp,
+            nonce: self.nonce,
+            public_key: self.public_key.to_vec(),
+            signature: self.signature.to_vec(),
+            transaction_hash: self.transaction_hash.to_vec(),
+            hash: self.hash.to_string(),
+        }
+    }
+}
+
+impl<'a> From<&'a Transaction> for TransactionView<'a> {
+    fn from(tx: &'a Transaction) -> Self {
+        Self {
+            token_id: tx.token_id,
+            from: &tx.from,
+            to: &tx.to,
+            amount: tx.amount,
+            timestamp: tx.timestamp,
+            nonce: tx.nonce,
+            public_key: &tx.public_key,
+            signature: &tx.signature,
+            transaction_hash: &tx.transaction_hash,
+            hash: &tx.hash,
+        }
+    }
+}
+
+/// Transação codificada recebida da rede, apoiada em `Bytes`.
+///
+/// O formato é validado uma única vez na construção; clones compartilham o mesmo
+/// buffer e `view` devolve os campos emprestados sem novas alocações.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedTransaction {
+    bytes: Bytes,
+}
+
+impl EncodedTransaction {
+    pub fn new(bytes: Bytes) -> Result<Self, TransactionError> {
+        TransactionView::decode(&bytes)?;
+        Ok(Self { bytes })
+    }
+
+    pub fn encode(tx: &Transaction) -> Result<Self, TransactionError> {
+        let data = bincode::serialize(tx).map_err(|_| TransactionError::InvalidDataFormat)?;
+        Self::new(Bytes::from(data))
+    }
+
+    pub fn view(&self) -> TransactionView<'_> {
+        TransactionView::decode(&self.bytes).expect("bytes validados na construção")
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Bytes {
+        self.bytes
+    }
+
+    pub fn to_transaction(&self) -> Transaction {
+        self.view().to_transaction()
+    }
+}
+
+impl TryFrom<Bytes> for EncodedTransaction {
+    type Error = TransactionError;
+
+    fn try_from(bytes: Bytes) -> Result<Self, Self::Error> {
+        Self::new(bytes)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::transaction::operation::{Operation, OperationKind};
+use serde::{Deserialize, Serialize};
+
+/// Quanto cada item consome do limite de peso do bloco (`Limits::max_block_weight`).
+///
+/// Transferências, operações administrativas de token e chamadas de contrato
+/// usam a mesma unidade: o peso é uma base pelo tipo do item mais um valor por
+/// byte codificado. Assim a montagem de blocos e a estimativa de custo tratam
+/// todos os itens do mesmo jeito, mesmo antes de os contratos medirem gas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeightSchedule {
+    /// Base de uma transferência
+    pub transfer: u64,
+
+    /// Base de uma operação administrativa: tokens, papéis, políticas e vesting
+    pub operation: u64,
+
+    /// Base de uma operação confidencial, que verifica provas de intervalo
+    pub confidential_operation: u64,
+
+    /// Base de uma chamada de contrato, somada ao gas que ela medir
+    pub contract_call: u64,
+
+    /// Peso por byte codificado, comum a todos os itens
+    pub per_byte: u64,
+}
+
+impl Default for WeightSchedule {
+    fn default() -> Self {
+        Self {
+            transfer: 10_000,
+            operation: 20_000,
+            confidential_operation: 100_000,
+            contract_call: 50_000,
+            per_byte: 1,
+        }
+    }
+}
+
+impl WeightSchedule {
+    fn sized(&self, base: u64, encoded_size: usize) -> u64 {
+        base.saturating_add(self.per_byte.saturating_mul(encoded_size as u64))
+    }
+
+    /// Peso de uma transferência com `encoded_size` bytes codificados
+    pub fn transfer_weight(&self, encoded_size: usize) -> u64 {
+        self.sized(self.transfer, encoded_size)
+    }
+
+    pub fn operation_weight(&self, operation: &Operation) -> u64 {
+        let base = match operation.kind {
+            OperationKind::Shield { .. }
+            | OperationKind::ConfidentialTransfer { .. }
+            | OperationKind::Unshield { .. } => self.confidential_operation,
+            _ => self.operation,
+        };
+        let encoded_size =
+            bincode::serialized_size(operation).map_or(usize::MAX, |size| size as usize);
+        self.sized(base, encoded_size)
+    }
+
+    /// Peso de uma chamada de contrato com `input_size` bytes de entrada; sem
+    /// medição de gas na execução, `gas_used` é 0
+    pub fn contract_call_weight(&self, input_size: usize, gas_used: u64) -> u64 {
+        self.sized(self.contract_call, input_size)
+            .saturating_add(gas_used)
+    }
+}
@@ -0,0 +1,140 @@
+use crate::constants::{MAX_ADDRESS_LENGTH, MIN_ADDRESS_LENGTH};
+use crate::error::{Error, TransactionError};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::fmt;
+use std::str::FromStr;
+
+/// Comprimento, em caracteres hex, de um endereço derivado de chave pública
+pub const DERIVED_ADDRESS_LEN: usize = 40;
+
+/// Prefixo opcional aceito na forma textual do endereço
+pub const ADDRESS_PREFIX: &str = "0x";
+
+/// Deriva o endereço de uma chave pública Dilithium5: os primeiros 20 bytes do
+/// SHA3-256 da chave, em hex minúsculo
+pub fn derive_address(public_key: &[u8]) -> String {
+    let digest = Sha3_256::digest(public_key);
+    hex::encode(&digest[..DERIVED_ADDRESS_LEN / 2])
+}
+
+/// Motivo pelo qual um texto não é um endereço válido
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressError {
+    /// Corpo do endereço (sem o prefixo `0x`) fora dos limites de tamanho
+    InvalidLength(usize),
+    /// Caractere fora de `[A-Za-z0-9]`
+    InvalidCharacter(char),
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressError::InvalidLength(len) => write!(
+                f,
+                "Endereço com {} caracteres; esperado entre {} e {}",
+                len, MIN_ADDRESS_LENGTH, MAX_ADDRESS_LENGTH
+            ),
+            AddressError::InvalidCharacter(c) => {
+                write!(f, "Caractere inválido no endereço: {:?}", c)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AddressError {}
+
+impl From<AddressError> for TransactionError {
+    fn from(_: AddressError) -> Self {
+        TransactionError::AddressFormatInvalid
+    }
+}
+
+impl From<AddressError> for Error {
+    fn from(_: AddressError) -> Self {
+        Error::InvalidAddress
+    }
+}
+
+/// Endereço de conta validado.
+///
+/// Formato único para transações e blockchain: prefixo `0x` opcional seguido de
+/// 32 a 64 caracteres ASCII alfanuméricos (o endereço derivado de chave pública,
+/// 40 caracteres hex, é um caso particular). Como o texto é a chave dos saldos,
+/// ele é guardado na forma canônica, sem prefixo e em minúsculas: grafias
+/// diferentes da mesma conta levam ao mesmo endereço.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Address(String);
+
+impl Address {
+    /// Valida e encapsula um endereço na forma canônica
+    pub fn parse(address: &str) -> Result<Self, AddressError> {
+        Self::check(address)?;
+        let body = address.strip_prefix(ADDRESS_PREFIX).unwrap_or(address);
+        Ok(Self(body.to_ascii_lowercase()))
+    }
+
+    /// Verifica o formato sem alocar o endereço
+    pub fn is_valid(address: &str) -> bool {
+        Self::check(address).is_ok()
+    }
+
+    fn check(address: &str) -> Result<(), AddressError> {
+        let body = address.strip_prefix(ADDRESS_PREFIX).unwrap_or(address);
+        if let Some(c) = body.chars().find(|c| !c.is_ascii_alphanumeric()) {
+            return Err(AddressError::InvalidCharacter(c));
+        }
+        if !(MIN_ADDRESS_LENGTH..=MAX_ADDRESS_LENGTH).contains(&body.len()) {
+            return Err(AddressError::InvalidLength(body.len()));
+        }
+        Ok(())
+    }
+
+    /// Endereço derivado de uma chave pública (ver `derive_address`)
+    pub fn from_public_key(public_key: &[u8]) -> Self {
+        Self(derive_address(public_key))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl FromStr for Address {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for Address {
+    type Error = AddressError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<Address> for String {
+    fn from(address: Address) -> Self {
+        address.0
+    }
+}
+
+impl AsRef<str> for Address {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
@@ -0,0 +1,73 @@
+use serde_json::{Map, Value};
+
+/// Versão do formato canônico. Muda apenas quando campos são renomeados,
+/// removidos ou mudam de codificação; campos novos também exigem nova versão.
+pub const CANONICAL_JSON_VERSION: u32 = 2;
+
+/// Representação JSON estável, independente dos derives serde, destinada a
+/// exploradores e ferramentas de auditoria.
+///
+/// O documento é `{"data": {...}, "type": ..., "version": ...}`, sem espaços e
+/// com as chaves de todos os objetos em ordem lexicográfica. Campos de bytes
+/// vão em hex minúsculo e conjuntos saem ordenados, então o mesmo valor produz
+/// sempre os mesmos bytes.
+pub trait CanonicalJson {
+    /// Valor do campo `type` do documento
+    const KIND: &'static str;
+
+    /// Campos do objeto `data`; também usado quando o valor aparece dentro de
+    /// outro documento (as transações de um bloco, por exemplo)
+    fn canonical_fields(&self) -> Value;
+
+    fn to_canonical_json(&self) -> String {
+        let mut document = Map::new();
+        document.insert("data".to_string(), self.canonical_fields());
+        document.insert("type".to_string(), Value::from(Self::KIND));
+        document.insert("version".to_string(), Value::from(CANONICAL_JSON_VERSION));
+        write(&Value::Object(document))
+    }
+}
+
+/// Bytes codificados como no formato canônico
+pub fn hex_field(bytes: &[u8]) -> Value {
+    Value::String(hex::encode(bytes))
+}
+
+/// Serializa sem espaços, ordenando as chaves de cada objeto
+pub fn write(value: &Value) -> String {
+    let mut out = String::new();
+    write_into(value, &mut out);
+    out
+}
+
+fn write_into(value: &Value, out: &mut String) {
+    match value {
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_into(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(fields) => {
+            // Não depende da ordem interna de `Map`, que muda com a feature
+            // `preserve_order` do serde_json
+            let mut keys: Vec<&String> = fields.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::from(key.as_str()).to_string());
+                out.push(':');
+                write_into(&fields[key], out);
+            }
+            out.push('}');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
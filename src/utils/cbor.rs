@@ -0,0 +1,23 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Codifica `value` em CBOR. Structs viram mapas indexados pelo nome do campo,
+/// então campos novos com `#[serde(default)]` continuam decodificando dados
+/// antigos, e campos desconhecidos são ignorados por versões anteriores.
+pub fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    ciborium::ser::into_writer(value, &mut out).map_err(|e| e.to_string())?;
+    Ok(out)
+}
+
+/// Decodifica CBOR de origem não confiável, recusando entradas acima de `limit` bytes
+pub fn decode<T: DeserializeOwned>(data: &[u8], limit: usize) -> Result<T, String> {
+    if data.len() > limit {
+        return Err(format!(
+            "{} bytes de CBOR, acima do limite de {}",
+            data.len(),
+            limit
+        ));
+    }
+    ciborium::de::from_reader(data).map_err(|e| e.to_string())
+}
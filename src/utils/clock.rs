@@ -0,0 +1,384 @@
+use super::i18n::message;
+use crate::config::ClockConfig;
+use crate::error::Error;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Diferença entre a época NTP (1900) e a época Unix (1970), em segundos
+const NTP_UNIX_OFFSET_SECS: u64 = 2_208_988_800;
+const NTP_PACKET_LEN: usize = 48;
+
+/// Máximo de amostras de peers mantidas; as mais antigas são descartadas
+const MAX_PEER_SAMPLES: usize = 200;
+
+static CLOCK: Lazy<TimeService> = Lazy::new(|| TimeService::new(ClockConfig::default()));
+
+/// Relógio do nó usado pela validação de timestamps
+pub fn clock() -> &'static TimeService {
+    &CLOCK
+}
+
+fn system_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+/// Fonte de tempo: hora Unix para timestamps e instante monotônico para prazos
+/// (banimentos, expiração de propostas, timeouts do consenso)
+pub trait Clock: Send + Sync + fmt::Debug {
+    /// Hora Unix em milissegundos, sem correção de desvio
+    fn unix_millis(&self) -> i64;
+    fn instant(&self) -> Instant;
+}
+
+/// Relógio compartilhado entre os módulos que dependem de tempo
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Relógio do sistema operacional
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn unix_millis(&self) -> i64 {
+        system_millis()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Relógio controlado à mão, para testes: só anda com `advance` ou `set_unix_millis`.
+/// Os clones compartilham o mesmo tempo
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    origin: Instant,
+    state: Arc<Mutex<MockTime>>,
+}
+
+#[derive(Debug)]
+struct MockTime {
+    unix_millis: i64,
+    elapsed: Duration,
+}
+
+impl MockClock {
+    pub fn new(unix_millis: i64) -> Self {
+        Self {
+            origin: Instant::now(),
+            state: Arc::new(Mutex::new(MockTime {
+                unix_millis,
+                elapsed: Duration::ZERO,
+            })),
+        }
+    }
+
+    /// Avança a hora Unix e o instante monotônico
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self.state.lock();
+        state.elapsed += duration;
+        state.unix_millis = state
+            .unix_millis
+            .saturating_add(i64::try_from(duration.as_millis()).unwrap_or(i64::MAX));
+    }
+
+    /// Ajusta só a hora Unix, como um relógio de parede corrigido
+    pub fn set_unix_millis(&self, unix_millis: i64) {
+        self.state.lock().unix_millis = unix_millis;
+    }
+
+    pub fn shared(&self) -> SharedClock {
+        Arc::new(self.clone())
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new(system_millis())
+    }
+}
+
+impl Clock for MockClock {
+    fn unix_millis(&self) -> i64 {
+        self.state.lock().unix_millis
+    }
+
+    fn instant(&self) -> Instant {
+        self.origin + self.state.lock().elapsed
+    }
+}
+
+/// Origem da estimativa de desvio do relógio local
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OffsetSource {
+    /// Sem amostras suficientes: relógio do sistema sem correção
+    None,
+    Ntp,
+    Peers,
+}
+
+#[derive(Debug)]
+struct ClockState {
+    config: ClockConfig,
+    ntp_offset_ms: Option<i64>,
+    peer_offsets: HashMap<String, i64>,
+    peer_order: Vec<String>,
+    drift_warned: bool,
+}
+
+/// Estima o desvio do relógio local a partir de NTP ou da mediana dos relógios
+/// dos peers e concentra as tolerâncias de timestamp configuradas.
+///
+/// A correção só é aplicada enquanto o desvio estimado ficar abaixo de
+/// `max_offset_adjustment_ms`; acima disso um punhado de peers mentindo poderia
+/// empurrar o relógio do nó, então o sistema é mantido e um aviso é emitido.
+///
+/// O tempo vem de um `Clock`, o do sistema por padrão.
+#[derive(Debug)]
+pub struct TimeService {
+    state: RwLock<ClockState>,
+    source: RwLock<SharedClock>,
+}
+
+impl TimeService {
+    pub fn new(config: ClockConfig) -> Self {
+        Self::with_source(config, Arc::new(SystemClock))
+    }
+
+    pub fn with_source(config: ClockConfig, source: SharedClock) -> Self {
+        Self {
+            state: RwLock::new(ClockState {
+                config,
+                ntp_offset_ms: None,
+                peer_offsets: HashMap::new(),
+                peer_order: Vec::new(),
+                drift_warned: false,
+            }),
+            source: RwLock::new(source),
+        }
+    }
+
+    /// Fonte de tempo em uso, para injetar nos módulos que medem prazos
+    pub fn source(&self) -> SharedClock {
+        self.source.read().clone()
+    }
+
+    /// Troca a fonte de tempo; os desvios já estimados continuam valendo
+    pub fn set_source(&self, source: SharedClock) {
+        *self.source.write() = source;
+    }
+
+    /// Instante monotônico da fonte de tempo
+    pub fn now_instant(&self) -> Instant {
+        self.source.read().instant()
+    }
+
+    /// Substitui as tolerâncias e parâmetros de sincronização
+    pub fn configure(&self, config: ClockConfig) {
+        self.state.write().config = config;
+    }
+
+    /// Tolerâncias de timestamp em vigor
+    pub fn tolerance(&self) -> ClockConfig {
+        self.state.read().config.clone()
+    }
+
+    /// Desvio estimado (ms) a somar ao relógio do sistema e a origem da estimativa
+    pub fn estimated_offset(&self) -> (i64, OffsetSource) {
+        let state = self.state.read();
+        if let Some(offset) = state.ntp_offset_ms {
+            return (offset, OffsetSource::Ntp);
+        }
+        if state.peer_offsets.len() < state.config.min_peer_samples.max(1) {
+            return (0, OffsetSource::None);
+        }
+
+        let mut offsets: Vec<i64> = state.peer_offsets.values().copied().collect();
+        offsets.sort_unstable();
+        let mid = offsets.len() / 2;
+        let median = if offsets.len().is_multiple_of(2) {
+            (offsets[mid - 1] + offsets[mid]) / 2
+        } else {
+            offsets[mid]
+        };
+        (median, OffsetSource::Peers)
+    }
+
+    /// Correção efetivamente aplicada ao relógio (0 se o desvio excede o limite)
+    pub fn offset_millis(&self) -> i64 {
+        let (offset, _) = self.estimated_offset();
+        if offset.abs() > self.state.read().config.max_offset_adjustment_ms {
+            0
+        } else {
+            offset
+        }
+    }
+
+    /// Hora da rede em milissegundos Unix
+    pub fn now_millis(&self) -> i64 {
+        self.source.read().unix_millis() + self.offset_millis()
+    }
+
+    /// Hora da rede em segundos Unix
+    pub fn now_secs(&self) -> i64 {
+        self.now_millis().div_euclid(1000)
+    }
+
+    /// Registra o horário informado por um peer (ms Unix) no momento da recepção
+    pub fn record_peer_time(&self, peer_id: &str, peer_time_ms: i64) {
+        let local_ms = self.source.read().unix_millis();
+        self.record_peer_offset(peer_id, peer_time_ms - local_ms);
+    }
+
+    /// Registra diretamente o desvio (ms) observado em relação a um peer
+    pub fn record_peer_offset(&self, peer_id: &str, offset_ms: i64) {
+        {
+            let mut state = self.state.write();
+            if state
+                .peer_offsets
+                .insert(peer_id.to_string(), offset_ms)
+                .is_none()
+            {
+                state.peer_order.push(peer_id.to_string());
+                if state.peer_order.len() > MAX_PEER_SAMPLES {
+                    let oldest = state.peer_order.remove(0);
+                    state.peer_offsets.remove(&oldest);
+                }
+            }
+        }
+        self.check_drift();
+    }
+
+    /// Registra o desvio (ms) medido contra um servidor NTP
+    pub fn record_ntp_offset(&self, offset_ms: i64) {
+        self.state.write().ntp_offset_ms = Some(offset_ms);
+        self.check_drift();
+    }
+
+    /// Emite um aviso quando o desvio estimado cruza o limiar configurado
+    fn check_drift(&self) {
+        let (offset, source) = self.estimated_offset();
+        let mut state = self.state.write();
+        let drifting = source != OffsetSource::None && offset.abs() > state.config.drift_warning_ms;
+
+        if drifting && !state.drift_warned {
+            let key = if offset.abs() > state.config.max_offset_adjustment_ms {
+                "clock.drift_uncorrected"
+            } else {
+                "clock.drift_corrected"
+            };
+            warn!("{} ({} ms, {:?})", message(key), offset, source);
+        } else if !drifting && state.drift_warned {
+            info!("{} ({} ms)", message("clock.drift_normalized"), offset);
+        }
+        state.drift_warned = drifting;
+    }
+
+    /// Consulta os servidores NTP configurados, registrando o primeiro desvio obtido
+    #[cfg(feature = "node")]
+    pub fn sync_ntp(&self) -> Result<i64, Error> {
+        let (servers, timeout) = {
+            let state = self.state.read();
+            (
+                state.config.ntp_servers.clone(),
+                std::time::Duration::from_millis(state.config.ntp_timeout_ms),
+            )
+        };
+
+        let mut last_error = Error::TimeError("Nenhum servidor NTP configurado".to_string());
+        for server in &servers {
+            match query_ntp(server, timeout) {
+                Ok(offset) => {
+                    self.record_ntp_offset(offset);
+                    return Ok(offset);
+                }
+                Err(e) => {
+                    warn!("{} {}: {}", message("clock.ntp_failed"), server, e);
+                    last_error = e;
+                }
+            }
+        }
+        Err(last_error)
+    }
+}
+
+fn to_ntp_timestamp(unix_ms: i64) -> [u8; 8] {
+    let millis = unix_ms.max(0) as u64;
+    let secs = millis / 1000 + NTP_UNIX_OFFSET_SECS;
+    let frac = ((millis % 1000) << 32) / 1000;
+    let mut bytes = [0u8; 8];
+    bytes[..4].copy_from_slice(&(secs as u32).to_be_bytes());
+    bytes[4..].copy_from_slice(&(frac as u32).to_be_bytes());
+    bytes
+}
+
+fn from_ntp_timestamp(bytes: &[u8]) -> i64 {
+    let secs = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as i64;
+    let frac = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as i64;
+    (secs - NTP_UNIX_OFFSET_SECS as i64) * 1000 + ((frac * 1000) >> 32)
+}
+
+/// Requisição SNTP (v4, modo cliente) com o instante local de envio
+pub fn sntp_request(sent_ms: i64) -> [u8; NTP_PACKET_LEN] {
+    let mut packet = [0u8; NTP_PACKET_LEN];
+    packet[0] = 0x23; // LI = 0, VN = 4, modo 3 (cliente)
+    packet[40..48].copy_from_slice(&to_ntp_timestamp(sent_ms));
+    packet
+}
+
+/// Desvio (ms) do relógio local calculado a partir da resposta SNTP:
+/// ((t2 - t1) + (t3 - t4)) / 2
+pub fn sntp_offset(
+    request: &[u8; NTP_PACKET_LEN],
+    response: &[u8],
+    received_ms: i64,
+) -> Result<i64, Error> {
+    if response.len() < NTP_PACKET_LEN {
+        return Err(Error::TimeError("Resposta NTP truncada".to_string()));
+    }
+    if response[0] & 0x07 != 4 {
+        return Err(Error::TimeError(
+            "Resposta NTP não é do modo servidor".to_string(),
+        ));
+    }
+    if response[1] == 0 {
+        return Err(Error::TimeError(
+            "Servidor NTP não sincronizado (kiss-o'-death)".to_string(),
+        ));
+    }
+    if response[24..32] != request[40..48] {
+        return Err(Error::TimeError(
+            "Resposta NTP não corresponde à requisição".to_string(),
+        ));
+    }
+
+    let t1 = from_ntp_timestamp(&request[40..48]);
+    let t2 = from_ntp_timestamp(&response[32..40]);
+    let t3 = from_ntp_timestamp(&response[40..48]);
+    Ok(((t2 - t1) + (t3 - received_ms)) / 2)
+}
+
+/// Mede o desvio do relógio local contra `server` (`host:porta`)
+#[cfg(feature = "node")]
+pub fn query_ntp(server: &str, timeout: std::time::Duration) -> Result<i64, Error> {
+    use std::net::UdpSocket;
+
+    let io_error = |e: std::io::Error| Error::TimeError(format!("NTP {}: {}", server, e));
+    let socket = UdpSocket::bind("0.0.0.0:0").map_err(io_error)?;
+    socket.set_read_timeout(Some(timeout)).map_err(io_error)?;
+    socket.connect(server).map_err(io_error)?;
+
+    let request = sntp_request(system_millis());
+    socket.send(&request).map_err(io_error)?;
+    let mut response = [0u8; NTP_PACKET_LEN];
+    let len = socket.recv(&mut response).map_err(io_error)?;
+
+    sntp_offset(&request, &response[..len], system_millis())
+}
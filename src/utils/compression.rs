@@ -0,0 +1,85 @@
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+
+/// Nível zstd padrão: bom equilíbrio entre taxa e custo de CPU para blocos
+pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+/// Codecs suportados por este nó, em ordem de preferência
+pub const SUPPORTED_CODECS: &[Codec] = &[Codec::Zstd, Codec::None];
+
+/// Codec de compressão identificado por um byte no início de cada payload.
+///
+/// Os IDs fazem parte do formato em disco e na rede e não podem ser reutilizados.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum Codec {
+    None = 0,
+    Zstd = 1,
+}
+
+impl Codec {
+    pub const fn id(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_id(id: u8) -> Result<Self, Error> {
+        match id {
+            0 => Ok(Codec::None),
+            1 => Ok(Codec::Zstd),
+            other => Err(Error::UnsupportedCodec(other)),
+        }
+    }
+
+    /// IDs anunciados a um peer durante o handshake
+    pub fn supported_ids() -> Vec<u8> {
+        SUPPORTED_CODECS.iter().map(|codec| codec.id()).collect()
+    }
+}
+
+/// Escolhe o primeiro codec local, em ordem de preferência, que o peer também
+/// anunciou; sem interseção os dados seguem sem compressão
+pub fn negotiate(local: &[Codec], remote_ids: &[u8]) -> Codec {
+    local
+        .iter()
+        .copied()
+        .find(|codec| remote_ids.contains(&codec.id()))
+        .unwrap_or(Codec::None)
+}
+
+/// Comprime `data` e prefixa o ID do codec
+pub fn encode(codec: Codec, data: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut frame = Vec::with_capacity(data.len() / 2 + 1);
+    frame.push(codec.id());
+
+    match codec {
+        Codec::None => frame.extend_from_slice(data),
+        Codec::Zstd => {
+            let compressed = zstd::bulk::compress(data, DEFAULT_ZSTD_LEVEL)
+                .map_err(|e| Error::CompressionError(e.to_string()))?;
+            frame.extend_from_slice(&compressed);
+        }
+    }
+
+    Ok(frame)
+}
+
+/// Lê o ID do codec e descomprime o payload, recusando resultados maiores que
+/// `max_size` para que um payload pequeno não se expanda sem limite
+pub fn decode(frame: &[u8], max_size: usize) -> Result<Vec<u8>, Error> {
+    let (&id, payload) = frame
+        .split_first()
+        .ok_or_else(|| Error::CompressionError("Payload vazio".to_string()))?;
+
+    match Codec::from_id(id)? {
+        Codec::None => {
+            if payload.len() > max_size {
+                return Err(Error::CompressionError(
+                    "Payload excede o tamanho máximo".to_string(),
+                ));
+            }
+            Ok(payload.to_vec())
+        }
+        Codec::Zstd => zstd::bulk::decompress(payload, max_size)
+            .map_err(|e| Error::CompressionError(e.to_string())),
+    }
+}
@@ -0,0 +1,13 @@
+use pqcrypto_dilithium::dilithium5::{self, PublicKey, SecretKey};
+
+/// Par Dilithium5 do `pqcrypto_dilithium`, ponto único de geração de chaves de
+/// assinatura do nó.
+///
+/// O sorteio é do gerador do sistema operacional, o mesmo da fonte padrão
+/// (`OsEntropy`), e não segue uma fonte injetada: o `pqcrypto_dilithium` não
+/// expõe geração a partir de semente nem troca de gerador, e o Dilithium5 do
+/// liboqs, que segue a entropia do nó, usa outro formato de chave secreta e de
+/// assinatura
+pub fn keypair() -> (PublicKey, SecretKey) {
+    dilithium5::keypair()
+}
@@ -0,0 +1,227 @@
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+#[cfg(feature = "test-utils")]
+use parking_lot::{Mutex, MutexGuard};
+use rand::rngs::OsRng;
+use rand::RngCore;
+#[cfg(feature = "test-utils")]
+use sha3::{Digest, Sha3_256};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+static ENTROPY: Lazy<EntropyService> = Lazy::new(|| {
+    let service = EntropyService::new(Arc::new(OsEntropy));
+    // A partir daqui o liboqs (Kyber e os pares gerados pelo `oqs`) sorteia pela
+    // mesma fonte que o restante do nó
+    #[cfg(feature = "node")]
+    unsafe {
+        oqs::ffi::rand::OQS_randombytes_custom_algorithm(Some(oqs_randombytes));
+    }
+    service
+});
+
+/// Entropia do nó: sais, nonces, IVs, fatores de cegamento e chaves do liboqs.
+/// Os pares Dilithium5 de `utils::dilithium::keypair` sorteiam direto do sistema
+/// operacional.
+pub fn entropy() -> &'static EntropyService {
+    &ENTROPY
+}
+
+/// Registra a entropia do nó no liboqs antes de qualquer sorteio da biblioteca.
+/// Idempotente; chamada na partida do nó e pelos construtores `kem` e `sig`
+pub fn install() {
+    Lazy::force(&ENTROPY);
+}
+
+/// `Kem::new` com a entropia do nó já registrada no liboqs
+#[cfg(feature = "node")]
+pub fn kem(algorithm: oqs::kem::Algorithm) -> oqs::Result<oqs::kem::Kem> {
+    install();
+    oqs::kem::Kem::new(algorithm)
+}
+
+/// `Sig::new` com a entropia do nó já registrada no liboqs
+#[cfg(feature = "node")]
+pub fn sig(algorithm: oqs::sig::Algorithm) -> oqs::Result<oqs::sig::Sig> {
+    install();
+    oqs::sig::Sig::new(algorithm)
+}
+
+/// Gerador de bytes aleatórios. Implementações precisam ser seguras para uso
+/// criptográfico, exceto as de teste como `SeededEntropy`
+pub trait EntropySource: Send + Sync + fmt::Debug {
+    fn fill_bytes(&self, dest: &mut [u8]);
+}
+
+/// Fonte de entropia compartilhada entre os módulos que sorteiam bytes
+pub type SharedEntropy = Arc<dyn EntropySource>;
+
+/// Gerador do sistema operacional (`getrandom`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsEntropy;
+
+impl EntropySource for OsEntropy {
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        OsRng.fill_bytes(dest);
+    }
+}
+
+/// Sequência determinística derivada de uma semente, para vetores de teste:
+/// o bloco `i` é `SHA3-256(semente || i)`. Os clones compartilham a posição.
+/// Só existe com a feature `test-utils`
+#[cfg(feature = "test-utils")]
+#[derive(Debug, Clone)]
+pub struct SeededEntropy {
+    seed: [u8; 32],
+    state: Arc<Mutex<SeededState>>,
+}
+
+#[cfg(feature = "test-utils")]
+#[derive(Debug, Default)]
+struct SeededState {
+    counter: u64,
+    buffer: Vec<u8>,
+}
+
+#[cfg(feature = "test-utils")]
+impl SeededEntropy {
+    pub fn new(seed: [u8; 32]) -> Self {
+        Self {
+            seed,
+            state: Arc::new(Mutex::new(SeededState::default())),
+        }
+    }
+
+    pub fn from_u64(seed: u64) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[..8].copy_from_slice(&seed.to_be_bytes());
+        Self::new(bytes)
+    }
+
+    pub fn shared(&self) -> SharedEntropy {
+        Arc::new(self.clone())
+    }
+}
+
+#[cfg(feature = "test-utils")]
+impl EntropySource for SeededEntropy {
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        let mut state = self.state.lock();
+        let mut written = 0;
+        while written < dest.len() {
+            if state.buffer.is_empty() {
+                let block = Sha3_256::new()
+                    .chain_update(self.seed)
+                    .chain_update(state.counter.to_be_bytes())
+                    .finalize();
+                state.counter += 1;
+                state.buffer = block.to_vec();
+            }
+            let take = state.buffer.len().min(dest.len() - written);
+            dest[written..written + take].copy_from_slice(&state.buffer[..take]);
+            state.buffer.drain(..take);
+            written += take;
+        }
+    }
+}
+
+/// Concentra a fonte de entropia em uso e conta quantos bytes foram sorteados,
+/// para auditoria. A fonte é fixada na criação; trocá-la em execução só é
+/// possível com a feature `test-utils`
+#[derive(Debug)]
+pub struct EntropyService {
+    source: RwLock<SharedEntropy>,
+    drawn: AtomicU64,
+    #[cfg(feature = "test-utils")]
+    scope: Mutex<()>,
+}
+
+impl EntropyService {
+    pub fn new(source: SharedEntropy) -> Self {
+        Self {
+            source: RwLock::new(source),
+            drawn: AtomicU64::new(0),
+            #[cfg(feature = "test-utils")]
+            scope: Mutex::new(()),
+        }
+    }
+
+    pub fn source(&self) -> SharedEntropy {
+        self.source.read().clone()
+    }
+
+    #[cfg(feature = "test-utils")]
+    pub fn set_source(&self, source: SharedEntropy) {
+        *self.source.write() = source;
+    }
+
+    /// Troca a fonte até o guarda sair de escopo, quando a anterior volta. Trocas
+    /// com escopo esperam umas pelas outras, de modo que testes em paralelo que
+    /// dependem de uma sequência fixa não a consomem ao mesmo tempo. Sorteios
+    /// feitos fora de um escopo não esperam e podem consumir a sequência de outro
+    /// teste; quem sorteia da entropia global no mesmo binário abre o seu
+    #[cfg(feature = "test-utils")]
+    pub fn scoped_source(&self, source: SharedEntropy) -> ScopedSource<'_> {
+        let lock = self.scope.lock();
+        let previous = std::mem::replace(&mut *self.source.write(), source);
+        ScopedSource {
+            service: self,
+            previous: Some(previous),
+            _lock: lock,
+        }
+    }
+
+    pub fn fill(&self, dest: &mut [u8]) {
+        let source = self.source();
+        source.fill_bytes(dest);
+        self.drawn.fetch_add(dest.len() as u64, Ordering::Relaxed);
+    }
+
+    pub fn bytes(&self, len: usize) -> Vec<u8> {
+        let mut bytes = vec![0u8; len];
+        self.fill(&mut bytes);
+        bytes
+    }
+
+    pub fn array<const N: usize>(&self) -> [u8; N] {
+        let mut bytes = [0u8; N];
+        self.fill(&mut bytes);
+        bytes
+    }
+
+    /// Total de bytes sorteados desde o início do processo
+    pub fn bytes_drawn(&self) -> u64 {
+        self.drawn.load(Ordering::Relaxed)
+    }
+}
+
+/// Fonte trocada por `EntropyService::scoped_source`
+#[cfg(feature = "test-utils")]
+#[derive(Debug)]
+pub struct ScopedSource<'a> {
+    service: &'a EntropyService,
+    previous: Option<SharedEntropy>,
+    _lock: MutexGuard<'a, ()>,
+}
+
+#[cfg(feature = "test-utils")]
+impl Drop for ScopedSource<'_> {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            self.service.set_source(previous);
+        }
+    }
+}
+
+/// Gerador registrado no liboqs.
+///
+/// # Safety
+/// O liboqs passa um buffer gravável de `len` bytes.
+#[cfg(feature = "node")]
+unsafe extern "C" fn oqs_randombytes(buffer: *mut u8, len: usize) {
+    if buffer.is_null() || len == 0 {
+        return;
+    }
+    entropy().fill(std::slice::from_raw_parts_mut(buffer, len));
+}
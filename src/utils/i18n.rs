@@ -0,0 +1,306 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Idioma das mensagens exibidas a operadores e clientes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    #[serde(rename = "pt-BR")]
+    PtBr,
+    #[serde(rename = "en")]
+    En,
+}
+
+impl Locale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::PtBr => "pt-BR",
+            Locale::En => "en",
+        }
+    }
+
+    /// Idioma de `KYBELITH_LANG` ou, na falta dela, de `LANG`; pt-BR se nenhuma for reconhecida
+    pub fn from_env() -> Self {
+        ["KYBELITH_LANG", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find_map(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    /// Aceita tags como `pt`, `pt-BR`, `pt_BR.UTF-8`, `en` e `en_US.UTF-8`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "pt" => Ok(Locale::PtBr),
+            "en" => Ok(Locale::En),
+            _ => Err(format!("Idioma não suportado: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Idioma em uso pelo processo
+pub fn locale() -> Locale {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Locale::En,
+        _ => Locale::PtBr,
+    }
+}
+
+pub fn set_locale(locale: Locale) {
+    let value = match locale {
+        Locale::PtBr => 0,
+        Locale::En => 1,
+    };
+    CURRENT.store(value, Ordering::Relaxed);
+}
+
+/// Mensagens de erro indexadas pelo código estável (ver `ErrorCode`): (código, pt-BR, en)
+const ERROR_MESSAGES: &[(u32, &str, &str)] = &[
+    (1000, "Outro erro", "Other error"),
+    (1001, "Token não encontrado", "Token not found"),
+    (1002, "Saldo insuficiente", "Insufficient balance"),
+    (1003, "Nonce inválido", "Invalid nonce"),
+    (1004, "Transação duplicada", "Duplicate transaction"),
+    (1005, "Chave pública inválida", "Invalid public key"),
+    (1006, "Erro de transação", "Transaction error"),
+    (1007, "Erro de serialização", "Serialization error"),
+    (1008, "Formato inválido", "Invalid format"),
+    (1009, "Entrada inválida", "Invalid input"),
+    (1010, "Transação muito grande", "Transaction too large"),
+    (1011, "Timestamp inválido", "Invalid timestamp"),
+    (1012, "Assinatura inválida", "Invalid signature"),
+    (1013, "Erro de bloqueio", "Lock error"),
+    (1014, "Nonce reutilizado", "Nonce reused"),
+    (1015, "Bloco muito grande", "Block too large"),
+    (1016, "Hash anterior inválido", "Invalid previous hash"),
+    (1017, "Quantidade inválida", "Invalid amount"),
+    (1018, "Tentativa de gasto duplo", "Double spending attempt"),
+    (1019, "Endereço inválido", "Invalid address"),
+    (
+        1020,
+        "Erro de serialização bincode",
+        "Bincode serialization error",
+    ),
+    (1021, "Erro de tempo do sistema", "System time error"),
+    (1022, "Bloco antigo", "Stale block"),
+    (1023, "Erro criptográfico", "Cryptographic error"),
+    (1024, "Erro de tempo", "Time error"),
+    (1025, "Bloco inválido", "Invalid block"),
+    (1026, "Erro OQS", "OQS error"),
+    (
+        1027,
+        "Codec de compressão não suportado",
+        "Unsupported compression codec",
+    ),
+    (1028, "Erro de compressão", "Compression error"),
+    (1029, "Não autorizado", "Unauthorized"),
+    (1030, "Erro de banco de dados", "Database error"),
+    (
+        1031,
+        "Limite de chamadas RPC excedido",
+        "RPC rate limit exceeded",
+    ),
+    (
+        1032,
+        "Versão do nó desatualizada",
+        "Node version out of date",
+    ),
+    (2000, "Outro erro", "Other error"),
+    (2001, "Erro OQS", "OQS error"),
+    (2002, "Transação inválida", "Invalid transaction"),
+    (2003, "Formato de dados inválido", "Invalid data format"),
+    (2004, "Fundos insuficientes", "Insufficient funds"),
+    (2005, "Nonce inválido", "Invalid nonce"),
+    (2006, "Timestamp inválido", "Invalid timestamp"),
+    (2007, "Endereço inválido", "Invalid address"),
+    (2008, "Token não encontrado", "Token not found"),
+    (2009, "Transação repetida", "Repeated transaction"),
+    (2010, "Valor inválido", "Invalid value"),
+    (2011, "Assinatura inválida", "Invalid signature"),
+    (2012, "Chave pública inválida", "Invalid public key"),
+    (2013, "Dados inválidos", "Invalid data"),
+    (2014, "Parâmetro inválido", "Invalid parameter"),
+    (
+        2015,
+        "Tamanho da assinatura excedido",
+        "Signature size exceeded",
+    ),
+    (2016, "Tamanho dos dados excedido", "Data size exceeded"),
+    (2017, "Overflow de nonce", "Nonce overflow"),
+    (
+        2018,
+        "Formato de endereço inválido",
+        "Invalid address format",
+    ),
+    (2019, "Timestamp inválido", "Invalid timestamp"),
+    (2020, "Entrada inválida", "Invalid input"),
+    (2021, "Formato inválido", "Invalid format"),
+    (2022, "Erro de bloqueio", "Lock error"),
+    (2023, "Nonce reutilizado", "Nonce reused"),
+    (2024, "Assinaturas inválidas", "Invalid signatures"),
+    (
+        2025,
+        "Sistema ocupado, tente novamente",
+        "System busy, try again",
+    ),
+    (
+        2026,
+        "Limite de transações pendentes do remetente atingido",
+        "Sender pending transaction limit reached",
+    ),
+    (
+        2027,
+        "Limite de submissões por janela do remetente atingido",
+        "Sender submission rate limit reached",
+    ),
+    (
+        2028,
+        "Transferências do token suspensas",
+        "Token transfers are paused",
+    ),
+    (
+        2029,
+        "Taxa abaixo do piso de admissão",
+        "Fee below admission floor",
+    ),
+    (
+        2030,
+        "Cadeia parada por emergência",
+        "Chain halted for emergency",
+    ),
+    (
+        2031,
+        "Destino bloqueado pelas restrições da conta",
+        "Destination blocked by account guard",
+    ),
+    (
+        2032,
+        "Chave substituída por recuperação da conta",
+        "Key replaced by account recovery",
+    ),
+    (3000, "Erro interno", "Internal error"),
+    (3001, "Proposer inválido", "Invalid proposer"),
+    (3002, "Bloco proposto inválido", "Invalid proposed block"),
+    (3003, "Validação falhou", "Validation failed"),
+    (3004, "Timeout de consenso", "Consensus timeout"),
+    (3005, "Votos insuficientes", "Insufficient votes"),
+    (3006, "Configuração inválida", "Invalid configuration"),
+];
+
+/// Mensagens de log destinadas a operadores: (chave, pt-BR, en)
+const LOG_MESSAGES: &[(&str, &str, &str)] = &[
+    (
+        "clock.drift_uncorrected",
+        "Relógio local desviado acima do limite de correção",
+        "Local clock drift exceeds the correction limit",
+    ),
+    (
+        "clock.drift_corrected",
+        "Relógio local desviado; aplicando correção",
+        "Local clock drifting; applying correction",
+    ),
+    (
+        "clock.drift_normalized",
+        "Desvio do relógio local normalizado",
+        "Local clock drift back to normal",
+    ),
+    (
+        "clock.ntp_failed",
+        "Falha ao consultar NTP",
+        "NTP query failed",
+    ),
+    ("peer.banned", "Peer banido", "Peer banned"),
+    (
+        "peer.disconnected",
+        "Desconectando peer",
+        "Disconnecting peer",
+    ),
+    (
+        "peer.ban_persist_failed",
+        "Falha ao persistir banimentos",
+        "Failed to persist peer bans",
+    ),
+    ("node.block_produced", "Bloco produzido", "Block produced"),
+    (
+        "node.block_failed",
+        "Falha ao produzir bloco",
+        "Block production failed",
+    ),
+    (
+        "node.persist_failed",
+        "Falha ao gravar a blockchain",
+        "Failed to persist the blockchain",
+    ),
+    (
+        "node.upgrade_scheduled",
+        "A cadeia agendou uma versão mínima acima da deste nó; atualize antes do bloco de ativação",
+        "The chain scheduled a minimum version above this node's; upgrade before the activation block",
+    ),
+];
+
+fn pick(locale: Locale, pt: &'static str, en: &'static str) -> &'static str {
+    match locale {
+        Locale::PtBr => pt,
+        Locale::En => en,
+    }
+}
+
+/// Mensagem do erro com o código informado, no idioma indicado
+pub fn error_message_in(code: u32, locale: Locale) -> &'static str {
+    ERROR_MESSAGES
+        .iter()
+        .find(|(entry, _, _)| *entry == code)
+        .map(|(_, pt, en)| pick(locale, pt, en))
+        .unwrap_or_else(|| pick(locale, "Erro desconhecido", "Unknown error"))
+}
+
+/// Mensagem do erro com o código informado, no idioma do processo
+pub fn error_message(code: u32) -> &'static str {
+    error_message_in(code, locale())
+}
+
+/// Mensagem de log no idioma indicado; chaves desconhecidas são devolvidas como estão
+pub fn message_in(key: &'static str, locale: Locale) -> &'static str {
+    LOG_MESSAGES
+        .iter()
+        .find(|(entry, _, _)| *entry == key)
+        .map(|(_, pt, en)| pick(locale, pt, en))
+        .unwrap_or(key)
+}
+
+/// Mensagem de log no idioma do processo
+pub fn message(key: &'static str) -> &'static str {
+    message_in(key, locale())
+}
+
+/// Escreve a mensagem do código seguida do detalhe, quando houver (`mensagem: detalhe`)
+pub fn write_error(
+    f: &mut fmt::Formatter<'_>,
+    code: u32,
+    detail: Option<&dyn fmt::Display>,
+) -> fmt::Result {
+    f.write_str(error_message(code))?;
+    match detail {
+        Some(detail) => write!(f, ": {}", detail),
+        None => Ok(()),
+    }
+}
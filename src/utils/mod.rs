@@ -1 +1,15 @@
-pub mod serde_helpers;
+pub mod address;
+pub mod canonical_json;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+pub mod clock;
+#[cfg(feature = "node")]
+pub mod compression;
+pub mod dilithium;
+pub mod entropy;
+pub mod i18n;
+pub mod name;
+pub mod serde_helpers;
+pub mod timestamp_policy;
+pub mod version;
+pub mod versioned;
@@ -0,0 +1,71 @@
+use crate::error::{Error, TransactionError};
+use std::fmt;
+
+/// Sufixo obrigatório dos nomes do registro (`alice.qst`)
+pub const NAME_SUFFIX: &str = ".qst";
+/// Limites de tamanho do rótulo, a parte antes do sufixo
+pub const MIN_NAME_LABEL_LEN: usize = 3;
+pub const MAX_NAME_LABEL_LEN: usize = 32;
+
+/// Motivo pelo qual um texto não é um nome registrável
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NameError {
+    MissingSuffix,
+    /// Rótulo fora dos limites de tamanho
+    InvalidLength(usize),
+    /// Caractere fora de `[a-z0-9-]`
+    InvalidCharacter(char),
+    /// Hífen no início ou no fim do rótulo
+    EdgeHyphen,
+}
+
+impl fmt::Display for NameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NameError::MissingSuffix => write!(f, "Nome sem o sufixo {}", NAME_SUFFIX),
+            NameError::InvalidLength(len) => write!(
+                f,
+                "Nome com {} caracteres antes de {}; esperado entre {} e {}",
+                len, NAME_SUFFIX, MIN_NAME_LABEL_LEN, MAX_NAME_LABEL_LEN
+            ),
+            NameError::InvalidCharacter(c) => write!(f, "Caractere inválido no nome: {:?}", c),
+            NameError::EdgeHyphen => write!(f, "Nome não pode começar nem terminar com hífen"),
+        }
+    }
+}
+
+impl std::error::Error for NameError {}
+
+impl From<NameError> for TransactionError {
+    fn from(e: NameError) -> Self {
+        TransactionError::InvalidParameter(e.to_string())
+    }
+}
+
+impl From<NameError> for Error {
+    fn from(e: NameError) -> Self {
+        Error::InvalidInput(e.to_string())
+    }
+}
+
+/// Confere a forma canônica de um nome: rótulo de letras minúsculas, dígitos e
+/// hífens, seguido de `.qst`. Como o ponto não aparece em endereços, um texto com
+/// ponto nunca é confundido com um endereço
+pub fn validate_name(name: &str) -> Result<(), NameError> {
+    let label = name
+        .strip_suffix(NAME_SUFFIX)
+        .ok_or(NameError::MissingSuffix)?;
+    if let Some(c) = label
+        .chars()
+        .find(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '-'))
+    {
+        return Err(NameError::InvalidCharacter(c));
+    }
+    if !(MIN_NAME_LABEL_LEN..=MAX_NAME_LABEL_LEN).contains(&label.len()) {
+        return Err(NameError::InvalidLength(label.len()));
+    }
+    if label.starts_with('-') || label.ends_with('-') {
+        return Err(NameError::EdgeHyphen);
+    }
+    Ok(())
+}
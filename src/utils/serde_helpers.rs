@@ -1,31 +1,33 @@
+use super::clock::clock;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::time::{Duration, Instant};
 
 #[derive(Clone, Debug)]
 pub struct SerializableInstant {
-    elapsed_millis: u64,
+    instant: Instant,
 }
 
 impl From<Instant> for SerializableInstant {
     fn from(instant: Instant) -> Self {
-        // Calcula o tempo decorrido desde o início do programa
-        let elapsed = instant.elapsed();
-        SerializableInstant {
-            elapsed_millis: elapsed.as_millis() as u64,
-        }
+        SerializableInstant { instant }
     }
 }
 
 impl SerializableInstant {
     pub fn to_instant(&self) -> Instant {
-        // Converte de volta para Instant
-        Instant::now() - Duration::from_millis(self.elapsed_millis)
+        self.instant
     }
 }
 
 impl SerializableInstant {
+    /// Tempo decorrido até agora, pelo relógio do nó; zero para instantes no futuro
     pub fn elapsed(&self) -> Duration {
-        Duration::from_millis(self.elapsed_millis)
+        self.elapsed_at(clock().now_instant())
+    }
+
+    /// Tempo decorrido até `now`; zero para instantes no futuro
+    pub fn elapsed_at(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.instant)
     }
 }
 
@@ -35,7 +37,7 @@ impl Serialize for SerializableInstant {
         S: Serializer,
     {
         // Serializa o tempo decorrido em milissegundos
-        serializer.serialize_u64(self.elapsed_millis)
+        serializer.serialize_u64(self.elapsed().as_millis() as u64)
     }
 }
 
@@ -46,8 +48,10 @@ impl<'de> Deserialize<'de> for SerializableInstant {
     {
         // Desserializa o tempo decorrido em milissegundos
         let millis = u64::deserialize(deserializer)?;
-        Ok(SerializableInstant {
-            elapsed_millis: millis,
-        })
+        let now = clock().now_instant();
+        let instant = now
+            .checked_sub(Duration::from_millis(millis))
+            .unwrap_or(now);
+        Ok(SerializableInstant { instant })
     }
 }
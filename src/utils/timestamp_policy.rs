@@ -0,0 +1,122 @@
+use super::clock::clock;
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Contexto em que um timestamp é validado; cada um tem sua própria política
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampContext {
+    /// Admissão de transações avulsas (mempool, RPC)
+    Transaction,
+    /// Transações já incluídas em um bloco, que podem chegar com atraso na sincronização
+    IncludedTransaction,
+    /// Blocos recebidos
+    Block,
+    /// Propostas de bloco do consenso
+    Proposal,
+}
+
+/// Limites aceitos para um timestamp em relação ao relógio do nó
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimestampPolicy {
+    /// Quanto o timestamp pode estar à frente do relógio local (em segundos)
+    pub max_future_skew_sec: i64,
+    /// Idade máxima do timestamp (em segundos)
+    pub max_past_age_sec: i64,
+}
+
+/// Motivo da rejeição de um timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampViolation {
+    /// Timestamp adiante do permitido, em milissegundos além do limite
+    InFuture { excess_ms: i64 },
+    /// Timestamp mais antigo que o permitido, em milissegundos além do limite
+    TooOld { excess_ms: i64 },
+}
+
+impl fmt::Display for TimestampViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimestampViolation::InFuture { excess_ms } => {
+                write!(f, "Timestamp no futuro ({} ms além do limite)", excess_ms)
+            }
+            TimestampViolation::TooOld { excess_ms } => {
+                write!(
+                    f,
+                    "Timestamp antigo demais ({} ms além do limite)",
+                    excess_ms
+                )
+            }
+        }
+    }
+}
+
+impl From<TimestampViolation> for Error {
+    fn from(violation: TimestampViolation) -> Self {
+        Error::InvalidTimestamp(violation.to_string())
+    }
+}
+
+impl TimestampPolicy {
+    pub const fn new(max_future_skew_sec: i64, max_past_age_sec: i64) -> Self {
+        Self {
+            max_future_skew_sec,
+            max_past_age_sec,
+        }
+    }
+
+    /// Mesma tolerância para o futuro e para o passado
+    pub const fn symmetric(max_drift_sec: i64) -> Self {
+        Self::new(max_drift_sec, max_drift_sec)
+    }
+
+    /// Política configurada para o contexto
+    pub fn for_context(context: TimestampContext) -> Self {
+        let tolerance = clock().tolerance();
+        match context {
+            TimestampContext::Transaction => tolerance.transaction_timestamps,
+            TimestampContext::IncludedTransaction => tolerance.included_transaction_timestamps,
+            TimestampContext::Block => tolerance.block_timestamps,
+            TimestampContext::Proposal => tolerance.proposal_timestamps,
+        }
+    }
+
+    /// Verifica um timestamp em milissegundos contra `now_ms`
+    pub fn check_millis(&self, timestamp_ms: i64, now_ms: i64) -> Result<(), TimestampViolation> {
+        let ahead = timestamp_ms.saturating_sub(now_ms);
+        let future_limit = self.max_future_skew_sec.saturating_mul(1000);
+        if ahead > future_limit {
+            return Err(TimestampViolation::InFuture {
+                excess_ms: ahead - future_limit,
+            });
+        }
+
+        let age = now_ms.saturating_sub(timestamp_ms);
+        let past_limit = self.max_past_age_sec.saturating_mul(1000);
+        if age > past_limit {
+            return Err(TimestampViolation::TooOld {
+                excess_ms: age - past_limit,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Verifica um timestamp em segundos contra `now_secs`
+    pub fn check_secs(&self, timestamp: i64, now_secs: i64) -> Result<(), TimestampViolation> {
+        self.check_millis(
+            timestamp.saturating_mul(1000),
+            now_secs.saturating_mul(1000),
+        )
+    }
+
+    /// Verifica um timestamp em segundos contra o relógio do nó
+    pub fn validate_secs(&self, timestamp: i64) -> Result<(), TimestampViolation> {
+        self.check_secs(timestamp, clock().now_secs())
+    }
+
+    /// Verifica um timestamp em milissegundos contra o relógio do nó
+    pub fn validate_millis(&self, timestamp_ms: i64) -> Result<(), TimestampViolation> {
+        self.check_millis(timestamp_ms, clock().now_millis())
+    }
+}
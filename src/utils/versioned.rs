@@ -0,0 +1,19 @@
+/// Bytes de identificação do tipo no início de uma codificação versionada
+pub type Magic = [u8; 3];
+
+/// Prefixa `payload` com `magic` e o byte de versão
+pub fn tag(magic: &Magic, version: u8, payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(magic.len() + 1 + payload.len());
+    out.extend_from_slice(magic);
+    out.push(version);
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Separa versão e conteúdo de uma codificação versionada. `None` indica dados
+/// sem o prefixo, gravados antes da existência do envelope (versão 0).
+pub fn split<'a>(magic: &Magic, data: &'a [u8]) -> Option<(u8, &'a [u8])> {
+    let rest = data.strip_prefix(magic.as_slice())?;
+    let (version, payload) = rest.split_first()?;
+    Some((*version, payload))
+}
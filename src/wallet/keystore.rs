@@ -0,0 +1,336 @@
+use crate::error::Error;
+use crate::transaction::SignatureScheme;
+use crate::utils::address::derive_address;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use pqcrypto_dilithium::dilithium5::{self, PublicKey, SecretKey};
+use pqcrypto_traits::sign::{PublicKey as _, SecretKey as _};
+use sha3::{Digest, Sha3_256};
+use zeroize::Zeroizing;
+
+const PUBLIC_LABEL: &str = "KYBELITH PUBLIC KEY";
+const PRIVATE_LABEL: &str = "KYBELITH PRIVATE KEY";
+/// Largura das linhas de base64, como em PEM
+const LINE_WIDTH: usize = 64;
+
+/// Proteção da chave secreta dentro do bloco armado
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEncryption {
+    None,
+    /// Chave derivada da senha por Argon2id (parâmetros interativos da
+    /// libsodium) e cifrada com XSalsa20-Poly1305
+    Argon2idSecretbox,
+}
+
+impl KeyEncryption {
+    fn as_str(self) -> &'static str {
+        match self {
+            KeyEncryption::None => "none",
+            KeyEncryption::Argon2idSecretbox => "argon2id-xsalsa20poly1305",
+        }
+    }
+
+    fn parse(value: &str) -> Result<Self, Error> {
+        match value {
+            "none" => Ok(KeyEncryption::None),
+            "argon2id-xsalsa20poly1305" => Ok(KeyEncryption::Argon2idSecretbox),
+            other => Err(Error::InvalidFormat(format!(
+                "Cifra de chave desconhecida: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Chave lida de um bloco armado; `secret_key` só existe em blocos privados
+pub struct ImportedKey {
+    pub scheme: SignatureScheme,
+    pub address: String,
+    pub public_key: PublicKey,
+    pub secret_key: Option<SecretKey>,
+    pub encryption: KeyEncryption,
+}
+
+/// Exporta a chave pública em bloco armado
+pub fn export_public_key(public_key: &PublicKey) -> String {
+    armor(
+        PUBLIC_LABEL,
+        public_key.as_bytes(),
+        KeyEncryption::None,
+        public_key.as_bytes(),
+    )
+}
+
+/// Exporta o par de chaves em bloco armado, com a chave secreta cifrada quando
+/// há `passphrase`.
+///
+/// A chave pública vai sempre em claro no início do corpo, então endereço e
+/// esquema podem ser conferidos sem a senha.
+pub fn export_keypair(
+    public_key: &PublicKey,
+    secret_key: &SecretKey,
+    passphrase: Option<&str>,
+) -> Result<String, Error> {
+    let (encryption, sealed) = match passphrase {
+        None => (
+            KeyEncryption::None,
+            Zeroizing::new(secret_key.as_bytes().to_vec()),
+        ),
+        Some(passphrase) => (
+            KeyEncryption::Argon2idSecretbox,
+            Zeroizing::new(seal(secret_key.as_bytes(), passphrase)?),
+        ),
+    };
+    let mut payload = Zeroizing::new(public_key.as_bytes().to_vec());
+    payload.extend_from_slice(&sealed);
+    Ok(armor(
+        PRIVATE_LABEL,
+        public_key.as_bytes(),
+        encryption,
+        &payload,
+    ))
+}
+
+/// Lê um bloco armado público ou privado, conferindo soma de verificação,
+/// esquema e endereço. A senha só é usada se a chave secreta estiver cifrada.
+pub fn import_key(armored: &str, passphrase: Option<&str>) -> Result<ImportedKey, Error> {
+    let block = parse(armored)?;
+    let scheme_name = block.header("Scheme")?;
+    let scheme = SignatureScheme::ALL
+        .into_iter()
+        .find(|scheme| scheme.to_string() == scheme_name)
+        .ok_or_else(|| Error::InvalidFormat("Esquema de assinatura desconhecido".to_string()))?;
+    if !scheme.is_supported() {
+        return Err(Error::InvalidFormat(format!(
+            "Esquema de assinatura {} não suportado",
+            scheme
+        )));
+    }
+    let encryption = KeyEncryption::parse(block.header("Encryption")?)?;
+
+    let payload = Zeroizing::new(
+        STANDARD
+            .decode(block.body.as_str())
+            .map_err(|_| Error::InvalidFormat("Corpo do bloco não é base64".to_string()))?,
+    );
+    let expected = checksum(&block.label, scheme, encryption, &payload);
+    if block.header("Checksum")? != expected {
+        return Err(Error::InvalidFormat(
+            "Soma de verificação do bloco não confere".to_string(),
+        ));
+    }
+
+    let key_len = scheme.public_key_size();
+    if payload.len() < key_len {
+        return Err(Error::InvalidFormat("Bloco sem chave pública".to_string()));
+    }
+    let public_key =
+        PublicKey::from_bytes(&payload[..key_len]).map_err(|_| Error::InvalidPublicKey)?;
+    let address = derive_address(public_key.as_bytes());
+    if block.header("Address")? != address {
+        return Err(Error::InvalidFormat(
+            "Endereço do cabeçalho não corresponde à chave".to_string(),
+        ));
+    }
+
+    let secret_key = match block.label.as_str() {
+        PUBLIC_LABEL if payload.len() == key_len && encryption == KeyEncryption::None => None,
+        PRIVATE_LABEL => {
+            let sealed = &payload[key_len..];
+            let secret = Zeroizing::new(match encryption {
+                KeyEncryption::None => sealed.to_vec(),
+                KeyEncryption::Argon2idSecretbox => {
+                    let passphrase = passphrase.ok_or_else(|| {
+                        Error::Unauthorized("Chave cifrada: informe a senha".to_string())
+                    })?;
+                    open(sealed, passphrase)?
+                }
+            });
+            Some(SecretKey::from_bytes(&secret).map_err(|_| {
+                Error::InvalidFormat("Chave secreta com tamanho inválido".to_string())
+            })?)
+        }
+        _ => {
+            return Err(Error::InvalidFormat("Bloco de chave inválido".to_string()));
+        }
+    };
+
+    // Um par trocado ou corrompido passaria pelas conferências acima
+    if let Some(secret_key) = &secret_key {
+        let probe = dilithium5::detached_sign(address.as_bytes(), secret_key);
+        dilithium5::verify_detached_signature(&probe, address.as_bytes(), &public_key).map_err(
+            |_| Error::InvalidFormat("Chave secreta não corresponde à pública".to_string()),
+        )?;
+    }
+
+    Ok(ImportedKey {
+        scheme,
+        address,
+        public_key,
+        secret_key,
+        encryption,
+    })
+}
+
+/// Primeiros 4 bytes do SHA3-256 sobre rótulo, esquema, cifra e corpo
+fn checksum(
+    label: &str,
+    scheme: SignatureScheme,
+    encryption: KeyEncryption,
+    payload: &[u8],
+) -> String {
+    let mut hasher = Sha3_256::new();
+    for field in [label, &scheme.to_string(), encryption.as_str()] {
+        hasher.update(field.as_bytes());
+        hasher.update([0u8]);
+    }
+    hasher.update(payload);
+    hex::encode(&hasher.finalize()[..4])
+}
+
+fn armor(label: &str, public_key: &[u8], encryption: KeyEncryption, payload: &[u8]) -> String {
+    let scheme = SignatureScheme::Dilithium5;
+    let mut out = format!("-----BEGIN {}-----\n", label);
+    out.push_str(&format!("Scheme: {}\n", scheme));
+    out.push_str(&format!("Address: {}\n", derive_address(public_key)));
+    out.push_str(&format!("Encryption: {}\n", encryption.as_str()));
+    out.push_str(&format!(
+        "Checksum: {}\n\n",
+        checksum(label, scheme, encryption, payload)
+    ));
+    let body = Zeroizing::new(STANDARD.encode(payload));
+    for line in body.as_bytes().chunks(LINE_WIDTH) {
+        // base64 é ASCII
+        out.push_str(std::str::from_utf8(line).unwrap_or_default());
+        out.push('\n');
+    }
+    out.push_str(&format!("-----END {}-----\n", label));
+    out
+}
+
+struct ArmoredBlock {
+    label: String,
+    headers: Vec<(String, String)>,
+    body: Zeroizing<String>,
+}
+
+impl ArmoredBlock {
+    fn header(&self, name: &str) -> Result<&str, Error> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+            .ok_or_else(|| Error::InvalidFormat(format!("Cabeçalho {} ausente", name)))
+    }
+}
+
+fn parse(armored: &str) -> Result<ArmoredBlock, Error> {
+    let invalid = |detail: &str| Error::InvalidFormat(format!("Bloco armado inválido: {}", detail));
+    let mut lines = armored
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty());
+
+    let label = lines
+        .next()
+        .and_then(|line| line.strip_prefix("-----BEGIN "))
+        .and_then(|line| line.strip_suffix("-----"))
+        .ok_or_else(|| invalid("início ausente"))?
+        .to_string();
+
+    let mut headers = Vec::new();
+    for line in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+        let (key, value) = line
+            .split_once(':')
+            .ok_or_else(|| invalid("cabeçalho malformado"))?;
+        headers.push((key.trim().to_string(), value.trim().to_string()));
+    }
+
+    let end = format!("-----END {}-----", label);
+    let mut body = Zeroizing::new(String::new());
+    let mut closed = false;
+    for line in lines {
+        if line == end {
+            closed = true;
+            break;
+        }
+        body.push_str(line);
+    }
+    if !closed {
+        return Err(invalid("fim ausente"));
+    }
+
+    Ok(ArmoredBlock {
+        label,
+        headers,
+        body,
+    })
+}
+
+#[cfg(feature = "node")]
+fn passphrase_key(
+    passphrase: &str,
+    salt: &sodiumoxide::crypto::pwhash::argon2id13::Salt,
+) -> Result<sodiumoxide::crypto::secretbox::Key, Error> {
+    use sodiumoxide::crypto::{pwhash::argon2id13, secretbox};
+
+    let mut key = secretbox::Key([0u8; secretbox::KEYBYTES]);
+    argon2id13::derive_key(
+        &mut key.0,
+        passphrase.as_bytes(),
+        salt,
+        argon2id13::OPSLIMIT_INTERACTIVE,
+        argon2id13::MEMLIMIT_INTERACTIVE,
+    )
+    .map_err(|_| Error::CryptoError("Falha ao derivar a chave da senha".to_string()))?;
+    Ok(key)
+}
+
+/// Corpo cifrado: sal, nonce e texto cifrado com a etiqueta Poly1305
+#[cfg(feature = "node")]
+fn seal(secret: &[u8], passphrase: &str) -> Result<Vec<u8>, Error> {
+    use crate::utils::entropy::entropy;
+    use sodiumoxide::crypto::{pwhash::argon2id13, secretbox};
+
+    let salt = argon2id13::Salt(entropy().array());
+    let nonce = secretbox::Nonce(entropy().array());
+    let key = passphrase_key(passphrase, &salt)?;
+    let mut sealed = salt.0.to_vec();
+    sealed.extend_from_slice(&nonce.0);
+    sealed.extend(secretbox::seal(secret, &nonce, &key));
+    Ok(sealed)
+}
+
+#[cfg(feature = "node")]
+fn open(sealed: &[u8], passphrase: &str) -> Result<Vec<u8>, Error> {
+    use sodiumoxide::crypto::{pwhash::argon2id13, secretbox};
+
+    let nonce_end = argon2id13::SALTBYTES + secretbox::NONCEBYTES;
+    if sealed.len() < nonce_end + secretbox::MACBYTES {
+        return Err(Error::InvalidFormat("Chave cifrada truncada".to_string()));
+    }
+    let salt = argon2id13::Salt::from_slice(&sealed[..argon2id13::SALTBYTES])
+        .ok_or_else(|| Error::InvalidFormat("Sal inválido".to_string()))?;
+    let nonce = secretbox::Nonce::from_slice(&sealed[argon2id13::SALTBYTES..nonce_end])
+        .ok_or_else(|| Error::InvalidFormat("Nonce inválido".to_string()))?;
+    let key = passphrase_key(passphrase, &salt)?;
+    secretbox::open(&sealed[nonce_end..], &nonce, &key)
+        .map_err(|_| Error::Unauthorized("Senha incorreta".to_string()))
+}
+
+// A carteira sem a feature `node` (wasm32) não tem libsodium
+#[cfg(not(feature = "node"))]
+fn seal(_secret: &[u8], _passphrase: &str) -> Result<Vec<u8>, Error> {
+    Err(Error::CryptoError(
+        "Cifrar chaves com senha exige a feature node".to_string(),
+    ))
+}
+
+#[cfg(not(feature = "node"))]
+fn open(_sealed: &[u8], _passphrase: &str) -> Result<Vec<u8>, Error> {
+    Err(Error::CryptoError(
+        "Decifrar chaves com senha exige a feature node".to_string(),
+    ))
+}
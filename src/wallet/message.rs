@@ -0,0 +1,105 @@
+use crate::constants::MAX_SIGNATURE_SIZE;
+use crate::error::TransactionError;
+use crate::transaction::SignatureScheme;
+use crate::utils::address::derive_address;
+use pqcrypto_dilithium::dilithium5::{self, PublicKey, SecretKey};
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+/// Domínio da assinatura; impede que uma mensagem assinada seja reaproveitada
+/// como transação, operação ou bloco
+pub const SIGNED_MESSAGE_DOMAIN: &str = "kybelith-signed-message-v1";
+
+/// Mensagem assinada fora da cadeia, usada para provar o controle de um endereço
+/// (a uma exchange, por exemplo).
+///
+/// O envelope não carrega a mensagem, só o hash dela: quem verifica recebe a
+/// mensagem por outro meio. Chave e assinatura vão em hex.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SignedMessage {
+    pub scheme: SignatureScheme,
+    pub address: String,
+    pub public_key: String,
+    /// SHA3-256 (hex) da mensagem
+    pub payload_hash: String,
+    pub signature: String,
+}
+
+fn payload_hash(message: &[u8]) -> String {
+    hex::encode(Sha3_256::digest(message))
+}
+
+fn signing_bytes(address: &str, payload_hash: &str) -> Vec<u8> {
+    format!("{}:{}:{}", SIGNED_MESSAGE_DOMAIN, address, payload_hash).into_bytes()
+}
+
+impl SignedMessage {
+    pub fn sign(message: &[u8], public_key: &PublicKey, secret_key: &SecretKey) -> Self {
+        Self::sign_with(message, public_key.as_bytes(), |data| {
+            dilithium5::detached_sign(data, secret_key)
+                .as_bytes()
+                .to_vec()
+        })
+    }
+
+    /// Monta o envelope com uma assinatura produzida fora daqui (chave do nó,
+    /// HSM); `sign` recebe os bytes a assinar
+    pub fn sign_with(
+        message: &[u8],
+        public_key: &[u8],
+        sign: impl FnOnce(&[u8]) -> Vec<u8>,
+    ) -> Self {
+        let address = derive_address(public_key);
+        let payload_hash = payload_hash(message);
+        let signature = sign(&signing_bytes(&address, &payload_hash));
+        Self {
+            scheme: SignatureScheme::Dilithium5,
+            address,
+            public_key: hex::encode(public_key),
+            payload_hash,
+            signature: hex::encode(signature),
+        }
+    }
+
+    /// Confere que `message` é a mensagem assinada, que o endereço deriva da
+    /// chave e que a assinatura é válida
+    pub fn verify(&self, message: &[u8]) -> Result<(), TransactionError> {
+        let decode = |field: &str, value: &str| {
+            hex::decode(value)
+                .map_err(|_| TransactionError::InvalidFormat(format!("Campo {} não é hex", field)))
+        };
+        let public_key = decode("public_key", &self.public_key)?;
+        let signature = decode("signature", &self.signature)?;
+        if signature.len() > MAX_SIGNATURE_SIZE {
+            return Err(TransactionError::SignatureSizeExceeded);
+        }
+        if SignatureScheme::check(&public_key, &signature)? != self.scheme {
+            return Err(TransactionError::InvalidSignature(format!(
+                "Chave e assinatura não são do esquema declarado ({})",
+                self.scheme
+            )));
+        }
+        if derive_address(&public_key) != self.address {
+            return Err(TransactionError::EnderecoInvalido);
+        }
+        if payload_hash(message) != self.payload_hash {
+            return Err(TransactionError::InvalidData(
+                "Mensagem diferente da assinada".to_string(),
+            ));
+        }
+
+        let public_key = PublicKey::from_bytes(&public_key).map_err(|_| {
+            TransactionError::InvalidPublicKey("Chave pública inválida".to_string())
+        })?;
+        let signature = dilithium5::DetachedSignature::from_bytes(&signature)
+            .map_err(|_| TransactionError::InvalidSignatures("Invalid signature".to_string()))?;
+        dilithium5::verify_detached_signature(
+            &signature,
+            &signing_bytes(&self.address, &self.payload_hash),
+            &public_key,
+        )
+        .map_err(|_| TransactionError::InvalidSignatures("Invalid signature".to_string()))
+    }
+}
@@ -0,0 +1,12 @@
+//! Funções de carteira independentes do nó: assinatura de mensagens fora da
+//! cadeia, importação/exportação de chaves, pedidos de pagamento e destinatários
+//! por nome
+pub mod keystore;
+pub mod message;
+pub mod payment_request;
+pub mod recipient;
+
+pub use keystore::{export_keypair, export_public_key, import_key, ImportedKey, KeyEncryption};
+pub use message::{SignedMessage, SIGNED_MESSAGE_DOMAIN};
+pub use payment_request::{PaymentRequest, PAYMENT_URI_SCHEME};
+pub use recipient::Recipient;
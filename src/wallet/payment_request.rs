@@ -0,0 +1,186 @@
+use crate::constants::MAX_AMOUNT;
+use crate::error::Error;
+use crate::transaction::operation::MAX_MEMO_SIZE;
+use crate::utils::address::Address;
+use std::fmt;
+use std::str::FromStr;
+
+/// Esquema das URIs de pagamento
+pub const PAYMENT_URI_SCHEME: &str = "qst";
+
+/// Pedido de pagamento no formato `qst:<endereço>?amount=<n>&token=<id>&memo=<texto>`,
+/// para QR codes de carteiras móveis e pontos de venda.
+///
+/// Todos os parâmetros são opcionais; `amount` vai em unidades inteiras do token
+/// e `memo` em UTF-8 com codificação percentual. O esquema é aceito em qualquer
+/// caixa (QR codes alfanuméricos usam maiúsculas), mas o endereço é mantido como
+/// recebido. Parâmetros desconhecidos são ignorados, exceto os com prefixo
+/// `req-`, que o pagador precisa entender e por isso tornam o pedido inválido.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentRequest {
+    pub address: Address,
+    pub amount: Option<u64>,
+    pub token_id: Option<u64>,
+    pub memo: Option<String>,
+}
+
+impl PaymentRequest {
+    pub fn new(address: Address) -> Self {
+        Self {
+            address,
+            amount: None,
+            token_id: None,
+            memo: None,
+        }
+    }
+
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    pub fn token_id(mut self, token_id: u64) -> Self {
+        self.token_id = Some(token_id);
+        self
+    }
+
+    pub fn memo(mut self, memo: impl Into<String>) -> Self {
+        self.memo = Some(memo.into());
+        self
+    }
+
+    /// Confere valor e memorando contra os limites das transações
+    pub fn validate(&self) -> Result<(), Error> {
+        if let Some(amount) = self.amount {
+            if amount == 0 || amount > MAX_AMOUNT {
+                return Err(Error::InvalidInput(format!(
+                    "Valor {} fora do intervalo 1..={}",
+                    amount, MAX_AMOUNT
+                )));
+            }
+        }
+        if let Some(memo) = &self.memo {
+            if memo.len() > MAX_MEMO_SIZE {
+                return Err(Error::InvalidInput(format!(
+                    "Memorando com {} bytes; máximo {}",
+                    memo.len(),
+                    MAX_MEMO_SIZE
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// URI do pedido, com os parâmetros sempre na mesma ordem
+    pub fn to_uri(&self) -> Result<String, Error> {
+        self.validate()?;
+        Ok(self.to_string())
+    }
+
+    pub fn parse(uri: &str) -> Result<Self, Error> {
+        let invalid =
+            |detail: String| Error::InvalidFormat(format!("URI de pagamento: {}", detail));
+
+        let (scheme, rest) = uri
+            .trim()
+            .split_once(':')
+            .ok_or_else(|| invalid("esquema ausente".to_string()))?;
+        if !scheme.eq_ignore_ascii_case(PAYMENT_URI_SCHEME) {
+            return Err(invalid(format!("esquema {} em vez de qst", scheme)));
+        }
+        let (address, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let mut request = Self::new(Address::parse(address)?);
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let number = |value: &str| {
+                value
+                    .parse::<u64>()
+                    .map_err(|_| invalid(format!("{} não é um inteiro: {}", key, value)))
+            };
+            let duplicate = match key {
+                "amount" => request.amount.replace(number(value)?).is_some(),
+                "token" => request.token_id.replace(number(value)?).is_some(),
+                "memo" => request.memo.replace(percent_decode(value)?).is_some(),
+                key if key.starts_with("req-") => {
+                    return Err(invalid(format!(
+                        "parâmetro obrigatório {} não suportado",
+                        key
+                    )))
+                }
+                _ => false,
+            };
+            if duplicate {
+                return Err(invalid(format!("parâmetro {} repetido", key)));
+            }
+        }
+
+        request.validate()?;
+        Ok(request)
+    }
+}
+
+impl fmt::Display for PaymentRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", PAYMENT_URI_SCHEME, self.address.as_str())?;
+        let mut separator = '?';
+        let mut param = |f: &mut fmt::Formatter<'_>, key: &str, value: &dyn fmt::Display| {
+            let written = write!(f, "{}{}={}", separator, key, value);
+            separator = '&';
+            written
+        };
+        if let Some(amount) = self.amount {
+            param(f, "amount", &amount)?;
+        }
+        if let Some(token_id) = self.token_id {
+            param(f, "token", &token_id)?;
+        }
+        if let Some(memo) = &self.memo {
+            param(f, "memo", &percent_encode(memo))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PaymentRequest {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Codifica tudo fora dos caracteres não reservados da RFC 3986
+fn percent_encode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+fn percent_decode(text: &str) -> Result<String, Error> {
+    let invalid = || Error::InvalidFormat(format!("Codificação percentual inválida: {}", text));
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut input = text.bytes();
+    while let Some(byte) = input.next() {
+        if byte != b'%' {
+            bytes.push(byte);
+            continue;
+        }
+        let mut digit = || {
+            input
+                .next()
+                .and_then(|digit| (digit as char).to_digit(16))
+                .ok_or_else(invalid)
+        };
+        let high = digit()?;
+        let low = digit()?;
+        bytes.push((high * 16 + low) as u8);
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
@@ -0,0 +1,58 @@
+use crate::error::Error;
+use crate::utils::address::Address;
+use crate::utils::name::validate_name;
+use std::fmt;
+use std::str::FromStr;
+
+/// Destinatário como o usuário o digita: um endereço ou um nome do registro
+/// (`alice.qst`), que precisa ser resolvido na cadeia antes de montar a transação
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recipient {
+    Address(Address),
+    Name(String),
+}
+
+impl Recipient {
+    /// Texto com ponto é lido como nome, sem distinção de caixa; o resto, como
+    /// endereço
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let input = input.trim();
+        if input.contains('.') {
+            let name = input.to_ascii_lowercase();
+            validate_name(&name)?;
+            return Ok(Recipient::Name(name));
+        }
+        Ok(Recipient::Address(Address::parse(input)?))
+    }
+
+    /// Endereço de destino; nomes passam por `lookup`, que consulta o registro e
+    /// devolve o dono atual de um nome ainda não expirado
+    pub fn resolve(&self, lookup: impl FnOnce(&str) -> Option<String>) -> Result<Address, Error> {
+        match self {
+            Recipient::Address(address) => Ok(address.clone()),
+            Recipient::Name(name) => {
+                let address = lookup(name).ok_or_else(|| {
+                    Error::InvalidInput(format!("Nome {} não registrado ou expirado", name))
+                })?;
+                Ok(Address::parse(&address)?)
+            }
+        }
+    }
+}
+
+impl FromStr for Recipient {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Recipient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Recipient::Address(address) => address.fmt(f),
+            Recipient::Name(name) => f.write_str(name),
+        }
+    }
+}
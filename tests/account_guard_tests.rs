@@ -0,0 +1,114 @@
+use kybelith::blockchain::Blockchain;
+use kybelith::error::{ErrorCode, TransactionError};
+use kybelith::test_utils::fixtures::{balance, commit, fund, Account};
+use kybelith::transaction::{GuardRule, Operation, OperationKind};
+
+fn guard(account: &mut Account, allow_only: bool, delay_blocks: u64) -> Operation {
+    account.operation(OperationKind::SetAccountGuard {
+        allow_only,
+        delay_blocks,
+    })
+}
+
+fn rule(account: &mut Account, destination: &Account, rule: Option<GuardRule>) -> Operation {
+    account.operation(OperationKind::SetGuardRule {
+        destination: destination.address.clone(),
+        rule,
+    })
+}
+
+fn send(blockchain: &mut Blockchain, from: &mut Account, to: &Account, amount: u64) {
+    let tx = from.transfer(&to.address, amount);
+    blockchain.submit_transaction(tx).unwrap();
+    blockchain.produce_block(10).unwrap();
+}
+
+#[test]
+fn test_allow_only_guard_delays_new_destinations_and_denies_at_once() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut owner = Account::new();
+    let cold = Account::new();
+    let thief = Account::new();
+    fund(&mut blockchain, &owner.address, 1_000);
+
+    // Apertar vale no mesmo bloco: só destinos liberados recebem
+    commit(&mut blockchain, [guard(&mut owner, true, 3)]);
+    send(&mut blockchain, &mut owner, &thief, 10);
+    assert_eq!(balance(&blockchain, 0, &thief.address), 0);
+
+    // Liberar um destino espera o atraso vigente
+    let allow = rule(&mut owner, &cold, Some(GuardRule::Allow));
+    commit(&mut blockchain, [allow]);
+    let allowed_at = blockchain.height() - 1 + 3;
+    send(&mut blockchain, &mut owner, &cold, 10);
+    assert_eq!(balance(&blockchain, 0, &cold.address), 0);
+    while blockchain.height() < allowed_at {
+        let filler = guard(&mut owner, true, 3);
+        commit(&mut blockchain, [filler]);
+    }
+    send(&mut blockchain, &mut owner, &cold, 10);
+    assert_eq!(balance(&blockchain, 0, &cold.address), 10);
+
+    // Bloquear vale na hora, também para cronogramas de liberação
+    let deny = rule(&mut owner, &cold, Some(GuardRule::Deny));
+    commit(&mut blockchain, [deny]);
+    send(&mut blockchain, &mut owner, &cold, 10);
+    assert_eq!(balance(&blockchain, 0, &cold.address), 10);
+    let vesting = owner.operation(OperationKind::CreateVesting {
+        token_id: 0,
+        beneficiary: cold.address.clone(),
+        amount: 10,
+        start: 0,
+        cliff_secs: 0,
+        duration_secs: 1,
+    });
+    let err = blockchain.submit_operation(vesting).unwrap_err();
+    assert!(
+        matches!(err, TransactionError::DestinationBlocked(_)),
+        "{}",
+        err
+    );
+    assert_eq!(err.code(), 2031);
+}
+
+#[test]
+fn test_loosening_guard_waits_for_delay_and_survives_reload() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut owner = Account::new();
+    let mut outsider = Account::new();
+    let friend = Account::new();
+    fund(&mut blockchain, &owner.address, 1_000);
+
+    // Só a própria conta define as suas restrições
+    let mut forged = guard(&mut outsider, true, 5);
+    forged.author = owner.address.clone();
+    assert!(blockchain.submit_operation(forged).is_err());
+    outsider.nonce -= 1;
+
+    commit(&mut blockchain, [guard(&mut owner, true, 4)]);
+    let height = blockchain.height();
+    // Desligar o modo restrito e encurtar o atraso só valem após o atraso atual
+    commit(&mut blockchain, [guard(&mut owner, false, 0)]);
+    let guard = blockchain.account_guard(&owner.address).unwrap();
+    assert!(*guard.allow_only.at(height));
+    assert_eq!(guard.delay_at(height), 4);
+    assert!(!*guard.allow_only.at(height + 4));
+    assert_eq!(guard.delay_at(height + 4), 0);
+    send(&mut blockchain, &mut owner, &friend, 10);
+    assert_eq!(balance(&blockchain, 0, &friend.address), 0);
+
+    let path =
+        std::env::temp_dir().join(format!("kybelith-guard-{}-accounts.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    blockchain.save_to_db(path.to_str().unwrap()).unwrap();
+    let mut restored = Blockchain::load_from_db(path.to_str().unwrap()).unwrap();
+    assert_eq!(restored.account_guards, blockchain.account_guards);
+    let _ = std::fs::remove_file(&path);
+
+    while restored.height() < height + 4 {
+        let filler = rule(&mut owner, &friend, None);
+        commit(&mut restored, [filler]);
+    }
+    send(&mut restored, &mut owner, &friend, 10);
+    assert_eq!(balance(&restored, 0, &friend.address), 10);
+}
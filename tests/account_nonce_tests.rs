@@ -0,0 +1,59 @@
+use kybelith::blockchain::{AccountNonce, Blockchain, NonceRepair, SharedBlockchain};
+use kybelith::rpc::RpcService;
+use kybelith::test_utils::fixtures::{address_of, transfer};
+use pqcrypto_dilithium::dilithium5::keypair;
+use serde_json::json;
+
+#[test]
+fn test_account_nonce_reports_pending_and_missing() {
+    let keys = keypair();
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(address_of(&keys), 100);
+
+    let fresh = blockchain.account_nonce(&address_of(&keys));
+    assert_eq!(
+        (fresh.committed, fresh.pending, fresh.next),
+        (0, 0, Some(1))
+    );
+    assert!(fresh.repair.is_none());
+
+    // A segunda transferência passa da admissão, mas é descartada sem saldo
+    blockchain
+        .submit_transaction(transfer(&keys, 40, 1))
+        .unwrap();
+    blockchain
+        .submit_transaction(transfer(&keys, 500, 2))
+        .unwrap();
+    assert_eq!(
+        blockchain.account_nonce(&address_of(&keys)).pending_nonces,
+        vec![1, 2]
+    );
+    blockchain.produce_block(10).unwrap().unwrap();
+    blockchain
+        .submit_transaction(transfer(&keys, 10, 3))
+        .unwrap();
+
+    let service = RpcService::new(SharedBlockchain::new(blockchain));
+    let nonce: AccountNonce = serde_json::from_value(
+        service
+            .handle("get_account_nonce", json!({ "address": address_of(&keys) }))
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(nonce.committed, 2);
+    assert_eq!(nonce.pending, 3);
+    assert_eq!(nonce.pending_nonces, vec![3]);
+    assert_eq!(nonce.next, Some(4));
+    assert_eq!(
+        nonce.repair,
+        Some(NonceRepair {
+            missing: vec![2],
+            resubmit_from: 4,
+        })
+    );
+}
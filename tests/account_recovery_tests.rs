@@ -0,0 +1,237 @@
+use kybelith::blockchain::{Block, Blockchain};
+use kybelith::error::{ErrorCode, TransactionError};
+use kybelith::test_utils::fixtures::{balance, commit, Account};
+use kybelith::transaction::operation::MIN_RECOVERY_DELAY_BLOCKS;
+use kybelith::transaction::{Operation, OperationKind, SecureTransaction, TransactionVerifier};
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+
+fn set_guardians(owner: &mut Account, guardians: &[&Account], threshold: u32) -> Operation {
+    owner.operation(OperationKind::SetGuardians {
+        guardians: guardians.iter().map(|g| g.address.clone()).collect(),
+        threshold,
+        delay_blocks: MIN_RECOVERY_DELAY_BLOCKS,
+    })
+}
+
+fn approve(guardian: &mut Account, account: &Account, new_public_key: Vec<u8>) -> Operation {
+    guardian.operation(OperationKind::ApproveRecovery {
+        account: account.address.clone(),
+        new_public_key,
+    })
+}
+
+#[test]
+fn test_guardians_rotate_key_after_timelock() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut owner = Account::new();
+    let mut guardians = [Account::new(), Account::new(), Account::new()];
+    let mut filler = Account::new();
+    let friend = Account::new();
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(owner.address.clone(), 1_000);
+
+    let setup = set_guardians(&mut owner, &guardians.iter().collect::<Vec<_>>(), 2);
+    commit(&mut blockchain, vec![setup]);
+    assert_eq!(
+        blockchain
+            .account_recovery(&owner.address)
+            .unwrap()
+            .guardians
+            .len(),
+        3
+    );
+
+    // O dono cancela uma recuperação que não pediu
+    let (new_public, new_secret) = keypair();
+    let new_key = new_public.as_bytes().to_vec();
+    let approvals = vec![
+        approve(&mut guardians[0], &owner, new_key.clone()),
+        approve(&mut guardians[1], &owner, new_key.clone()),
+    ];
+    commit(&mut blockchain, approvals);
+    assert!(blockchain
+        .account_recovery(&owner.address)
+        .unwrap()
+        .pending
+        .is_some());
+    commit(
+        &mut blockchain,
+        vec![owner.operation(OperationKind::CancelRecovery)],
+    );
+    assert_eq!(
+        blockchain.account_recovery(&owner.address).unwrap().pending,
+        None
+    );
+
+    let approvals = vec![
+        approve(&mut guardians[1], &owner, new_key.clone()),
+        approve(&mut guardians[2], &owner, new_key.clone()),
+    ];
+    commit(&mut blockchain, approvals);
+    let pending = blockchain
+        .account_recovery(&owner.address)
+        .unwrap()
+        .pending
+        .clone()
+        .unwrap();
+    assert_eq!(pending.executable_at, 3 + MIN_RECOVERY_DELAY_BLOCKS);
+
+    // Com a chave nova, antes do prazo a conclusão é recusada
+    let old_keys = std::mem::replace(&mut owner.keys, (new_public, new_secret));
+    assert!(blockchain
+        .submit_operation(owner.operation(OperationKind::CompleteRecovery))
+        .is_err());
+    owner.nonce -= 1;
+    while blockchain.height() < pending.executable_at {
+        let tick = filler.operation(OperationKind::SetAccountGuard {
+            allow_only: false,
+            delay_blocks: 0,
+        });
+        commit(&mut blockchain, vec![tick]);
+    }
+    commit(
+        &mut blockchain,
+        vec![owner.operation(OperationKind::CompleteRecovery)],
+    );
+    assert_eq!(
+        blockchain.rotated_key(&owner.address),
+        Some(new_key.as_slice())
+    );
+    let rotation = &blockchain.key_rotations(&owner.address)[0];
+    assert_eq!(rotation.height, pending.executable_at);
+    assert_eq!(rotation.approved_by.len(), 2, "{:?}", rotation.approved_by);
+
+    // A chave antiga deixa de valer; a nova movimenta a conta
+    let new_keys = std::mem::replace(&mut owner.keys, old_keys);
+    let stale = owner.transfer(&friend.address, 10);
+    let err = blockchain.submit_transaction(stale).unwrap_err();
+    assert!(matches!(err, TransactionError::KeyRotated(_)), "{}", err);
+    assert_eq!(err.code(), 2032);
+    owner.nonce -= 1;
+
+    // A troca também vale para blocos recebidos de outros nós
+    assert!(blockchain.is_chain_valid().unwrap());
+    let imported = SecureTransaction::new(
+        owner.address.clone(),
+        friend.address.clone(),
+        10,
+        chrono::Utc::now().timestamp(),
+        owner.nonce + 1,
+        &owner.keys.1,
+        &owner.keys.0,
+    )
+    .unwrap();
+    assert!(matches!(
+        blockchain
+            .validation_context()
+            .validate_secure_transaction(&imported),
+        Err(TransactionError::KeyRotated(_))
+    ));
+    let previous_hash = blockchain.chain.last().unwrap().hash.clone();
+    let block = Block::new(
+        blockchain.height(),
+        vec![imported],
+        Vec::new(),
+        previous_hash,
+    )
+    .unwrap();
+    blockchain.chain.push(block);
+    assert!(!blockchain.is_chain_valid().unwrap());
+    blockchain.chain.pop();
+    owner.keys = new_keys;
+    let tx = owner.transfer(&friend.address, 10);
+    blockchain.submit_transaction(tx).unwrap();
+    blockchain.produce_block(10).unwrap().unwrap();
+    assert_eq!(balance(&blockchain, 0, &friend.address), 10);
+    commit(
+        &mut blockchain,
+        vec![set_guardians(&mut owner, &[&guardians[0]], 1)],
+    );
+
+    let path = std::env::temp_dir().join(format!(
+        "kybelith-recovery-{}-rotations.db",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    blockchain.save_to_db(path.to_str().unwrap()).unwrap();
+    let restored = Blockchain::load_from_db(path.to_str().unwrap()).unwrap();
+    assert_eq!(restored.key_rotations, blockchain.key_rotations);
+    assert_eq!(restored.recoveries, blockchain.recoveries);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_recovery_rejects_invalid_schemes_and_outsiders() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut owner = Account::new();
+    let mut guardian = Account::new();
+    let mut outsider = Account::new();
+
+    let invalid = [
+        set_guardians(&mut owner, &[&guardian], 2),
+        set_guardians(&mut owner, &[&guardian, &guardian], 1),
+        owner.operation(OperationKind::SetGuardians {
+            guardians: vec![owner.address.clone()],
+            threshold: 1,
+            delay_blocks: MIN_RECOVERY_DELAY_BLOCKS,
+        }),
+        owner.operation(OperationKind::SetGuardians {
+            guardians: vec![guardian.address.clone()],
+            threshold: 1,
+            delay_blocks: MIN_RECOVERY_DELAY_BLOCKS - 1,
+        }),
+    ];
+    for operation in invalid {
+        assert!(operation.check().is_err(), "{:?}", operation.kind);
+    }
+    owner.nonce = 0;
+
+    commit(
+        &mut blockchain,
+        vec![set_guardians(&mut owner, &[&guardian], 1)],
+    );
+    let outsider_key = outsider.public_key();
+    assert!(blockchain
+        .submit_operation(approve(&mut outsider, &owner, outsider_key))
+        .is_err());
+    outsider.nonce -= 1;
+    assert!(blockchain
+        .submit_operation(approve(&mut guardian, &owner, vec![0; 16]))
+        .is_err());
+    guardian.nonce -= 1;
+    // Sem aprovações não há o que cancelar nem concluir
+    assert!(blockchain
+        .submit_operation(owner.operation(OperationKind::CancelRecovery))
+        .is_err());
+    owner.nonce -= 1;
+    assert!(blockchain
+        .submit_operation(owner.operation(OperationKind::CompleteRecovery))
+        .is_err());
+
+    let verifier = TransactionVerifier;
+    let key = owner.public_key();
+    verifier
+        .verify_account_key(&owner.address, &key, None, None)
+        .unwrap();
+    verifier
+        .verify_account_key(&owner.address, &key, Some(&key), None)
+        .unwrap();
+    assert!(verifier
+        .verify_account_key(&owner.address, &key, Some(&guardian.public_key()), None)
+        .is_err());
+
+    // Sem rotação, só a chave que deriva o endereço ou a registrada para ele
+    let foreign = guardian.public_key();
+    assert!(matches!(
+        verifier.verify_account_key(&owner.address, &foreign, None, None),
+        Err(TransactionError::InvalidPublicKey(_))
+    ));
+    verifier
+        .verify_account_key(&owner.address, &foreign, None, Some(&foreign))
+        .unwrap();
+}
@@ -0,0 +1,88 @@
+use kybelith::error::{Error, TransactionError};
+use kybelith::transaction::Transaction;
+use kybelith::utils::address::{derive_address, AddressError};
+use kybelith::Address;
+
+#[test]
+fn test_address_accepts_prefixed_and_plain_forms() {
+    let hex = format!("0x{}", "ab12".repeat(10));
+    let plain = "a".repeat(32);
+    let derived = derive_address(b"chave publica");
+
+    for address in [&hex, &plain, &derived, &"Z9".repeat(32)] {
+        let parsed: Address = address.parse().unwrap();
+        let canonical = address.trim_start_matches("0x").to_ascii_lowercase();
+        assert_eq!(parsed.as_str(), canonical);
+        assert!(Address::is_valid(address));
+    }
+    assert_eq!(Address::from_public_key(b"chave publica").as_str(), derived);
+
+    assert_eq!(
+        Address::parse("0x1234"),
+        Err(AddressError::InvalidLength(4))
+    );
+    assert_eq!(
+        Address::parse(&"a".repeat(65)),
+        Err(AddressError::InvalidLength(65))
+    );
+    assert_eq!(
+        Address::parse(&format!("{}-", "a".repeat(40))),
+        Err(AddressError::InvalidCharacter('-'))
+    );
+    assert_eq!(
+        Address::parse(&format!("{}é", "a".repeat(40))),
+        Err(AddressError::InvalidCharacter('é'))
+    );
+
+    assert!(matches!(
+        Error::from(AddressError::InvalidLength(0)),
+        Error::InvalidAddress
+    ));
+}
+
+#[test]
+fn test_address_spellings_of_one_account_parse_to_the_same_key() {
+    let canonical = "ab12".repeat(10);
+    let spellings = [
+        canonical.clone(),
+        format!("0x{}", canonical),
+        canonical.to_ascii_uppercase(),
+        format!("0x{}", canonical.to_ascii_uppercase()),
+    ];
+
+    for spelling in &spellings {
+        assert_eq!(Address::parse(spelling).unwrap().as_str(), canonical);
+    }
+    assert_eq!(
+        serde_json::from_str::<Address>(&format!("\"0x{}\"", canonical.to_ascii_uppercase()))
+            .unwrap()
+            .as_str(),
+        canonical
+    );
+}
+
+#[test]
+fn test_address_serde_rejects_invalid_strings() {
+    let address = Address::parse(&"c".repeat(40)).unwrap();
+    let json = serde_json::to_string(&address).unwrap();
+    assert_eq!(json, format!("\"{}\"", "c".repeat(40)));
+    assert_eq!(serde_json::from_str::<Address>(&json).unwrap(), address);
+
+    assert!(serde_json::from_str::<Address>("\"curto\"").is_err());
+}
+
+#[test]
+fn test_transaction_accepts_prefixed_hex_address() -> Result<(), TransactionError> {
+    let from = format!("0x{}", "1".repeat(40));
+    let to = derive_address(b"destinatario");
+
+    let transaction = Transaction::new(from, to, 100, vec![0; 32])?;
+    transaction.validate_address()?;
+
+    let result = Transaction::new("0x12".to_string(), "b".repeat(40), 100, vec![0; 32]);
+    assert!(matches!(
+        result,
+        Err(TransactionError::AddressFormatInvalid)
+    ));
+    Ok(())
+}
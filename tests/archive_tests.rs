@@ -0,0 +1,146 @@
+use kybelith::blockchain::{Block, Blockchain, CheckpointAttestation, StorageMode};
+use kybelith::smart_contract::SmartContract;
+use pqcrypto_dilithium::dilithium5::keypair;
+
+fn next_block(blockchain: &Blockchain, contracts: Vec<SmartContract>) -> Block {
+    let previous_hash = blockchain
+        .chain
+        .last()
+        .map(|block| block.hash.clone())
+        .unwrap_or_else(|| "0".repeat(64));
+    Block::new(
+        blockchain.chain.len() as u64,
+        Vec::new(),
+        contracts,
+        previous_hash,
+    )
+    .unwrap()
+}
+
+fn contract(data: &[u8]) -> SmartContract {
+    SmartContract {
+        code: vec![0x00, 0x61, 0x73, 0x6d],
+        data: data.to_vec(),
+        address: "c".repeat(40),
+        creator: "a".repeat(40),
+        timestamp: chrono::Utc::now().timestamp(),
+        quantum_secure: true,
+    }
+}
+
+fn set_balance(blockchain: &mut Blockchain, address: &str, balance: u64) {
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(address.to_string(), balance);
+}
+
+#[test]
+fn test_state_at_returns_historical_balances_and_contracts() {
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain.enable_archive_mode();
+    let alice = "a".repeat(40);
+
+    set_balance(&mut blockchain, &alice, 100);
+    blockchain
+        .add_block(next_block(&blockchain, vec![contract(b"v1")]))
+        .unwrap();
+
+    set_balance(&mut blockchain, &alice, 40);
+    blockchain.nonces.insert(alice.clone(), 3);
+    blockchain
+        .add_block(next_block(&blockchain, vec![contract(b"v2")]))
+        .unwrap();
+
+    let first = blockchain.state_at(0).unwrap();
+    assert_eq!(first.balance_of("0", &alice), 100);
+    assert_eq!(first.nonces.get(&alice), None);
+    assert_eq!(first.contracts[&"c".repeat(40)], b"v1".to_vec());
+    assert_eq!(first.block_hash, blockchain.chain[0].hash);
+
+    let second = blockchain.state_at(1).unwrap();
+    assert_eq!(second.balance_of("0", &alice), 40);
+    assert_eq!(second.nonces[&alice], 3);
+    assert_eq!(second.contracts[&"c".repeat(40)], b"v2".to_vec());
+    assert_eq!(second.balance_of("0", "system"), 10_000_000);
+
+    assert_eq!(
+        blockchain.balance_history("0", &alice).unwrap(),
+        vec![(0, 100), (1, 40)]
+    );
+    assert!(blockchain.receipts_at(1).unwrap().is_empty());
+    assert!(blockchain.state_at(2).is_err());
+}
+
+#[test]
+fn test_history_queries_require_archive_mode() {
+    let mut blockchain = Blockchain::new().unwrap();
+    assert_eq!(blockchain.storage_mode, StorageMode::Pruned);
+
+    blockchain
+        .add_block(next_block(&blockchain, Vec::new()))
+        .unwrap();
+
+    assert!(blockchain.state_at(0).is_err());
+    assert!(blockchain.receipts_at(0).is_err());
+}
+
+#[test]
+fn test_archive_node_refuses_pruning() {
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain.enable_archive_mode();
+    blockchain
+        .add_block(next_block(&blockchain, Vec::new()))
+        .unwrap();
+
+    let validator = keypair();
+    let attestation =
+        CheckpointAttestation::create(&blockchain, 0, &validator.0, &validator.1).unwrap();
+
+    assert!(blockchain
+        .prune_signatures(attestation, &validator.0)
+        .is_err());
+    assert!(!blockchain.is_pruned(0));
+}
+
+#[test]
+fn test_archive_history_survives_serialization() {
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain.enable_archive_mode();
+    set_balance(&mut blockchain, &"a".repeat(40), 7);
+    blockchain
+        .add_block(next_block(&blockchain, Vec::new()))
+        .unwrap();
+
+    let json = serde_json::to_string(&blockchain).unwrap();
+    let mut restored: Blockchain = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(
+        restored
+            .state_at(0)
+            .unwrap()
+            .balance_of("0", &"a".repeat(40)),
+        7
+    );
+
+    set_balance(&mut restored, &"a".repeat(40), 9);
+    restored
+        .add_block(next_block(&restored, Vec::new()))
+        .unwrap();
+    assert_eq!(
+        restored
+            .state_at(1)
+            .unwrap()
+            .balance_of("0", &"a".repeat(40)),
+        9
+    );
+    assert_eq!(
+        restored
+            .state_at(0)
+            .unwrap()
+            .balance_of("0", &"a".repeat(40)),
+        7
+    );
+}
@@ -0,0 +1,129 @@
+use kybelith::blockchain::{Block, Blockchain, SharedBlockchain};
+use kybelith::error::TransactionError;
+use kybelith::test_utils::fixtures::temp_path;
+use kybelith::test_utils::fixtures::{address_of, bob, signed_transfer, transfer};
+use kybelith::AsyncDatabase;
+use pqcrypto_dilithium::dilithium5::keypair;
+
+fn new_shared() -> SharedBlockchain {
+    SharedBlockchain::new(Blockchain::new().expect("blockchain deve ser criada"))
+}
+
+#[tokio::test]
+async fn test_submit_transaction_async() {
+    let shared = new_shared();
+    let keys = keypair();
+
+    shared
+        .submit_transaction_async(transfer(&keys, 500, 1))
+        .await
+        .unwrap();
+
+    assert_eq!(shared.pending_count(), 1);
+    assert_eq!(shared.nonce_of(&address_of(&keys)), 1);
+}
+
+#[tokio::test]
+async fn test_submit_rejects_bad_nonce_and_signature() {
+    let shared = new_shared();
+
+    let result = shared
+        .submit_transaction_async(transfer(&keypair(), 500, 5))
+        .await;
+    assert!(matches!(result, Err(TransactionError::NonceInvalido)));
+
+    let mut tampered = transfer(&keypair(), 500, 1);
+    tampered.amount += 1;
+    assert!(shared.submit_transaction_async(tampered).await.is_err());
+
+    assert_eq!(shared.pending_count(), 0);
+}
+
+#[tokio::test]
+async fn test_submit_rejects_key_not_bound_to_sender() {
+    let shared = new_shared();
+    let owner = keypair();
+    let thief = keypair();
+
+    // Assinada corretamente pelo ladrão, mas em nome da conta do dono
+    let forged = signed_transfer(&thief, &address_of(&owner), &bob(), 500, 1);
+    let result = shared.submit_transaction_async(forged).await;
+    assert!(matches!(result, Err(TransactionError::InvalidPublicKey(_))));
+    assert_eq!(shared.pending_count(), 0);
+    assert_eq!(shared.nonce_of(&address_of(&owner)), 0);
+}
+
+#[tokio::test]
+async fn test_submit_rejects_duplicate() {
+    let shared = new_shared();
+    let tx = transfer(&keypair(), 500, 1);
+
+    shared.submit_transaction_async(tx.clone()).await.unwrap();
+    let result = shared.submit_transaction_async(tx).await;
+
+    assert!(result.is_err());
+    assert_eq!(shared.pending_count(), 1);
+}
+
+#[tokio::test]
+async fn test_add_block_async() {
+    let shared = new_shared();
+    let block = Block::new(0, Vec::new(), Vec::new(), "0".repeat(64)).unwrap();
+    let hash = block.hash.clone();
+
+    shared.add_block_async(block).await.unwrap();
+
+    assert_eq!(shared.height(), 1);
+    assert_eq!(shared.latest_hash(), Some(hash));
+    assert!(shared.is_chain_valid_async().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_save_and_load_file_async() {
+    let path = temp_path("chain.json");
+    let filename = path.to_str().unwrap();
+
+    let shared = new_shared();
+    let block = Block::new(0, Vec::new(), Vec::new(), "0".repeat(64)).unwrap();
+    shared.add_block_async(block).await.unwrap();
+    shared.save_to_file_async(filename).await.unwrap();
+
+    let loaded = SharedBlockchain::load_from_file_async(filename)
+        .await
+        .unwrap();
+    assert_eq!(loaded.height(), 1);
+    assert_eq!(loaded.latest_hash(), shared.latest_hash());
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_async_database() {
+    let path = temp_path("async.db");
+    let _ = std::fs::remove_file(&path);
+
+    let database = AsyncDatabase::open(path.to_str().unwrap()).await.unwrap();
+    database
+        .insert_transaction(
+            "alice".to_string(),
+            "bob".to_string(),
+            10,
+            1_700_000_000,
+            vec![1, 2, 3],
+            vec![4, 5, 6],
+        )
+        .await
+        .unwrap();
+
+    let transactions = database.get_transactions_by_address("bob").await.unwrap();
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transactions[0].2, 10);
+
+    let count: i64 = database
+        .call(|conn| Ok(conn.query_row("SELECT COUNT(*) FROM transactions", [], |row| row.get(0))?))
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,140 @@
+use kybelith::audit::{self, AuditAction, AuditLog, SharedAuditLog, AUDIT_GENESIS_HASH};
+use kybelith::error::Error;
+use kybelith::events::{AppEvent, EventBus};
+use kybelith::test_utils::fixtures::temp_path;
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+fn mint(token_id: u64) -> AuditAction {
+    AuditAction::TokenMinted {
+        token_id,
+        to: "a".repeat(40),
+        amount: 1_000,
+    }
+}
+
+#[test]
+fn test_hash_chain_detects_tampering() {
+    let mut log = AuditLog::new();
+    assert_eq!(log.head_hash(), AUDIT_GENESIS_HASH);
+    log.append_at("admin", mint(1), 100).unwrap();
+    log.append_at(
+        "governance",
+        AuditAction::GovernanceExecuted {
+            proposal_id: "7".to_string(),
+            description: "aumentar o limite de bloco".to_string(),
+        },
+        200,
+    )
+    .unwrap();
+    log.append_at(
+        "admin",
+        AuditAction::KeyRotated {
+            key_id: "ab".repeat(16),
+        },
+        300,
+    )
+    .unwrap();
+    log.verify().unwrap();
+    assert_eq!(log.entries()[1].previous_hash, log.entries()[0].hash);
+    assert_eq!(
+        AuditLog::verify_entries(log.entries()).unwrap(),
+        log.head_hash()
+    );
+
+    let mut altered = log.entries().to_vec();
+    altered[1].timestamp = 201;
+    assert!(matches!(
+        AuditLog::verify_entries(&altered),
+        Err(Error::InvalidFormat(_))
+    ));
+
+    let mut removed = log.entries().to_vec();
+    removed.remove(1);
+    assert!(AuditLog::verify_entries(&removed).is_err());
+
+    let mut swapped = log.entries().to_vec();
+    swapped.swap(0, 2);
+    assert!(AuditLog::verify_entries(&swapped).is_err());
+
+    // Recalcular só o hash alterado não basta: a entrada seguinte deixa de encadear
+    let mut rehashed = AuditLog::new();
+    rehashed.append_at("admin", mint(2), 100).unwrap();
+    let mut forged = log.entries().to_vec();
+    forged[0] = rehashed.entries()[0].clone();
+    assert!(AuditLog::verify_entries(&forged).is_err());
+}
+
+#[test]
+fn test_persisted_log_reopens_and_rejects_edits() {
+    let path = temp_path("audit-persist").join("audit.log");
+    let _ = std::fs::remove_file(&path);
+
+    let mut log = AuditLog::open(&path).unwrap();
+    log.append("admin", mint(1)).unwrap();
+    log.append(
+        "consensus",
+        AuditAction::ValidatorBanned {
+            validator_id: "v1".to_string(),
+            duration_secs: 60,
+        },
+    )
+    .unwrap();
+    let head = log.head_hash().to_string();
+
+    let reopened = AuditLog::open(&path).unwrap();
+    assert_eq!(reopened.entries(), log.entries());
+    assert_eq!(reopened.head_hash(), head);
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(
+        &path,
+        contents.replace("\"amount\":1000", "\"amount\":9000"),
+    )
+    .unwrap();
+    assert!(AuditLog::open(&path).is_err());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_records_node_events() {
+    let events = EventBus::new();
+    let log: SharedAuditLog = Arc::new(Mutex::new(AuditLog::new()));
+    audit::record_events(&log, &events);
+
+    events.emit(AppEvent::TokenCreated {
+        token_id: 3,
+        symbol: "ABC".to_string(),
+        owner: "a".repeat(40),
+        total_supply: 500,
+        height: 1,
+    });
+    events.emit(AppEvent::BlockCommitted {
+        height: 1,
+        hash: "00".repeat(32),
+        transactions: 0,
+        operations: 1,
+    });
+    events.emit(AppEvent::ValidatorBanned {
+        validator_id: "v2".to_string(),
+        duration_secs: 3_600,
+    });
+
+    let log = log.lock();
+    log.verify().unwrap();
+    let actions: Vec<_> = log.entries().iter().map(|e| e.action.clone()).collect();
+    assert_eq!(
+        actions,
+        vec![
+            AuditAction::TokenMinted {
+                token_id: 3,
+                to: "a".repeat(40),
+                amount: 500,
+            },
+            AuditAction::ValidatorBanned {
+                validator_id: "v2".to_string(),
+                duration_secs: 3_600,
+            },
+        ]
+    );
+}
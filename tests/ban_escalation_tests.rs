@@ -0,0 +1,62 @@
+use kybelith::consensus::reputation::{
+    ReputationAction, ReputationConfig, ReputationSystem, ValidatorReputation,
+};
+use kybelith::events::{AppEvent, EventBus};
+use kybelith::utils::clock::{Clock, MockClock};
+use std::time::Duration;
+
+#[test]
+fn test_ban_duration_climbs_and_decays() {
+    let config = ReputationConfig::default();
+    let hours = |n: u64| Duration::from_secs(n * 3600);
+    assert_eq!(config.ban_duration(0), hours(1));
+    assert_eq!(config.ban_duration(2), hours(24));
+    assert_eq!(config.ban_duration(9), hours(168));
+
+    let clock = MockClock::new(1_700_000_000_000);
+    let mut reputation = ValidatorReputation::new_at("validator1".to_string(), clock.instant());
+    let durations: Vec<Duration> = (0..3)
+        .map(|_| reputation.ban_escalated_at(&config, clock.instant()))
+        .collect();
+    assert_eq!(durations, vec![hours(1), hours(6), hours(24)]);
+
+    // Depois do banimento, cada período de decaimento desconta uma reincidência
+    let config = ReputationConfig {
+        initial_ban_duration: Duration::from_millis(1),
+        recidivism_decay: Duration::from_millis(40),
+        ..ReputationConfig::default()
+    };
+    reputation.ban_at(Duration::from_millis(1), clock.instant());
+    clock.advance(Duration::from_millis(100));
+    assert_eq!(
+        reputation.recidivism_at(config.recidivism_decay, clock.instant()),
+        1
+    );
+    assert_eq!(
+        reputation.ban_escalated_at(&config, clock.instant()),
+        Duration::from_millis(6)
+    );
+}
+
+#[test]
+fn test_first_ban_ignores_lifetime_infractions() {
+    let mut system = ReputationSystem::new();
+    let events = EventBus::new();
+    let mut received = events.subscribe();
+    system.set_event_bus(events);
+    system.add_validator("validator1".to_string());
+    for _ in 0..3 {
+        system
+            .update_reputation("validator1", ReputationAction::InvalidBlockProposed)
+            .unwrap();
+    }
+
+    system
+        .update_reputation("validator1", ReputationAction::DoubleVote)
+        .unwrap();
+    assert!(system.is_banned("validator1"));
+    match received.try_recv().unwrap() {
+        AppEvent::ValidatorBanned { duration_secs, .. } => assert_eq!(duration_secs, 3600),
+        other => panic!("evento inesperado: {:?}", other),
+    }
+}
@@ -0,0 +1,140 @@
+use kybelith::consensus::{BeaconCommit, BeaconReveal, RandomnessBeacon, Validator, ValidatorSet};
+use kybelith::network::NodeIdentity;
+use pqcrypto_traits::sign::PublicKey as _;
+
+struct Participant {
+    id: String,
+    identity: NodeIdentity,
+    secret: Vec<u8>,
+}
+
+fn participants(count: usize) -> Vec<Participant> {
+    (0..count)
+        .map(|i| Participant {
+            id: format!("v{}", i),
+            identity: NodeIdentity::generate(),
+            secret: vec![i as u8; 16],
+        })
+        .collect()
+}
+
+fn run_round(beacon: &mut RandomnessBeacon, participants: &[&Participant], reveal: bool) {
+    let round = beacon.round();
+    for p in participants {
+        let commit = BeaconCommit::new(round, p.id.clone(), &p.secret, &p.identity);
+        beacon
+            .add_commit(&commit, p.identity.public_key.as_bytes())
+            .unwrap();
+    }
+    if reveal {
+        for p in participants {
+            let reveal = BeaconReveal::new(round, p.id.clone(), p.secret.clone(), &p.identity);
+            beacon
+                .add_reveal(&reveal, p.identity.public_key.as_bytes())
+                .unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_round_output_independent_of_message_order() {
+    let group = participants(3);
+    let mut a = RandomnessBeacon::new([7; 32]);
+    let mut b = RandomnessBeacon::new([7; 32]);
+
+    run_round(&mut a, &[&group[0], &group[1], &group[2]], true);
+    run_round(&mut b, &[&group[2], &group[0], &group[1]], true);
+
+    let (value_a, missing) = a.finalize_round();
+    let (value_b, _) = b.finalize_round();
+    assert_eq!(value_a, value_b);
+    assert_ne!(value_a, [7; 32]);
+    assert!(missing.is_empty());
+    assert_eq!(a.round(), 1);
+
+    // Segredos diferentes levam a outro valor
+    let mut c = RandomnessBeacon::new([7; 32]);
+    run_round(&mut c, &[&group[0], &group[1]], true);
+    assert_ne!(c.finalize_round().0, value_a);
+}
+
+#[test]
+fn test_missing_reveals_reported_and_chain_advances() {
+    let group = participants(2);
+    let mut beacon = RandomnessBeacon::new([1; 32]);
+    run_round(&mut beacon, &[&group[0], &group[1]], false);
+
+    let (first, missing) = beacon.finalize_round();
+    assert_eq!(missing, vec!["v0".to_string(), "v1".to_string()]);
+    let (second, _) = beacon.finalize_round();
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_invalid_beacon_messages_rejected() {
+    let group = participants(2);
+    let (p, other) = (&group[0], &group[1]);
+    let mut beacon = RandomnessBeacon::new([0; 32]);
+    let key = p.identity.public_key.as_bytes();
+
+    let commit = BeaconCommit::new(0, p.id.clone(), &p.secret, &p.identity);
+    assert!(beacon
+        .add_commit(&commit, other.identity.public_key.as_bytes())
+        .is_err());
+    assert!(beacon
+        .add_commit(
+            &BeaconCommit::new(1, p.id.clone(), &p.secret, &p.identity),
+            key
+        )
+        .is_err());
+    beacon.add_commit(&commit, key).unwrap();
+    assert!(beacon
+        .add_commit(
+            &BeaconCommit::new(0, p.id.clone(), b"outro", &p.identity),
+            key
+        )
+        .is_err());
+
+    let wrong = BeaconReveal::new(0, p.id.clone(), b"outro".to_vec(), &p.identity);
+    assert!(beacon.add_reveal(&wrong, key).is_err());
+    let mut forged = BeaconReveal::new(0, p.id.clone(), p.secret.clone(), &p.identity);
+    forged.signature[0] ^= 0xff;
+    assert!(beacon.add_reveal(&forged, key).is_err());
+    let uncommitted = BeaconReveal::new(0, other.id.clone(), other.secret.clone(), &other.identity);
+    assert!(beacon
+        .add_reveal(&uncommitted, other.identity.public_key.as_bytes())
+        .is_err());
+}
+
+fn validator_set(order: &[usize], beacon: [u8; 32]) -> ValidatorSet {
+    let mut set = ValidatorSet::new(
+        order
+            .iter()
+            .map(|i| {
+                Validator::new(
+                    format!("v{}", i),
+                    format!("addr-v{}", i),
+                    vec![*i as u8; 32],
+                    1_000,
+                )
+            })
+            .collect(),
+    );
+    set.set_beacon(beacon);
+    set
+}
+
+#[test]
+fn test_beacon_selection_identical_across_nodes() {
+    let a = validator_set(&[0, 1, 2, 3, 4], [9; 32]);
+    let b = validator_set(&[4, 2, 0, 3, 1], [9; 32]);
+    let c = validator_set(&[0, 1, 2, 3, 4], [10; 32]);
+
+    let mut differs = false;
+    for height in 1..50 {
+        let chosen = a.select_proposer_stake_weighted(height).unwrap().id;
+        assert_eq!(chosen, b.select_proposer_stake_weighted(height).unwrap().id);
+        differs |= chosen != c.select_proposer_stake_weighted(height).unwrap().id;
+    }
+    assert!(differs);
+}
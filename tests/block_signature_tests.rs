@@ -0,0 +1,214 @@
+use kybelith::blockchain::{Block, Blockchain};
+use kybelith::consensus::BlockProposal;
+use kybelith::error::Error;
+use kybelith::network::NodeIdentity;
+use kybelith::smart_contract::SmartContract;
+use kybelith::test_utils::fixtures::{bob, commit, commit_transaction, fund, Account};
+use kybelith::transaction::{OperationKind, SecureTransaction};
+use kybelith::utils::compression::Codec;
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+use std::sync::Arc;
+
+fn next_block(blockchain: &Blockchain) -> Block {
+    let previous = blockchain
+        .latest_block()
+        .map(|block| block.hash.clone())
+        .unwrap_or_else(|| "0".repeat(64));
+    Block::new(blockchain.height(), Vec::new(), Vec::new(), previous).unwrap()
+}
+
+#[test]
+fn test_signed_blocks_accepted_and_verified() {
+    let identity = Arc::new(NodeIdentity::generate());
+    let mut blockchain = Blockchain::new().unwrap();
+    let proposer = blockchain.set_signer(Arc::clone(&identity));
+
+    for _ in 0..2 {
+        let mut block = next_block(&blockchain);
+        block.sign(&identity);
+        blockchain.add_block(block).unwrap();
+    }
+
+    let tip = blockchain.latest_block().unwrap();
+    assert_eq!(tip.proposer.as_deref(), Some(proposer.as_str()));
+    assert!(tip.verify_signature(identity.public_key.as_bytes()).is_ok());
+    assert!(blockchain.is_chain_valid().unwrap());
+
+    // A assinatura segue com o bloco no formato de rede e na proposta
+    let received =
+        Block::from_compressed_bytes(&tip.to_compressed_bytes(Codec::Zstd).unwrap()).unwrap();
+    assert!(blockchain.verify_block_signature(&received).is_ok());
+    let proposal = BlockProposal::for_block(tip).unwrap();
+    assert_eq!(proposal.proposer_id, proposer);
+    assert_eq!(Some(&proposal.signature), tip.validator_signature.as_ref());
+}
+
+#[test]
+fn test_unsigned_or_foreign_blocks_rejected() {
+    let identity = Arc::new(NodeIdentity::generate());
+    let outsider = NodeIdentity::generate();
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain.set_signer(Arc::clone(&identity));
+
+    let unsigned = next_block(&blockchain);
+    assert!(BlockProposal::for_block(&unsigned).is_err());
+    assert!(matches!(
+        blockchain.add_block(unsigned),
+        Err(Error::InvalidBlock(_))
+    ));
+
+    let mut foreign = next_block(&blockchain);
+    foreign.sign(&outsider);
+    assert!(matches!(
+        blockchain.add_block(foreign),
+        Err(Error::Unauthorized(_))
+    ));
+
+    // Assinatura de um validador registrado atribuída a outro bloco
+    let mut block = next_block(&blockchain);
+    block.sign(&identity);
+    block.timestamp += 1;
+    block.hash = block.compute_hash().unwrap();
+    assert!(matches!(
+        blockchain.add_block(block),
+        Err(Error::InvalidSignature)
+    ));
+    assert_eq!(blockchain.height(), 0);
+}
+
+#[test]
+fn test_chain_validity_checks_signatures_from_activation() {
+    let identity = Arc::new(NodeIdentity::generate());
+    let mut blockchain = Blockchain::new().unwrap();
+
+    // Blocos anteriores ao registro do validador continuam válidos sem assinatura
+    let block = next_block(&blockchain);
+    blockchain.add_block(block).unwrap();
+    blockchain.set_signer(Arc::clone(&identity));
+    assert_eq!(blockchain.signed_from, Some(1));
+
+    let mut block = next_block(&blockchain);
+    block.sign(&identity);
+    blockchain.add_block(block).unwrap();
+    assert!(blockchain.is_chain_valid().unwrap());
+
+    let restored = Blockchain::from_json(&serde_json::to_vec(&blockchain).unwrap()).unwrap();
+    assert!(restored.is_chain_valid().unwrap());
+
+    let mut stripped = Blockchain::from_json(&serde_json::to_vec(&blockchain).unwrap()).unwrap();
+    stripped.chain[1].validator_signature = None;
+    stripped.chain[1].proposer = None;
+    assert!(!stripped.is_chain_valid().unwrap());
+
+    let mut tampered = restored;
+    if let Some(signature) = tampered.chain[1].validator_signature.as_mut() {
+        signature[0] ^= 0xff;
+    }
+    assert!(!tampered.is_chain_valid().unwrap());
+}
+
+#[test]
+fn test_chain_validity_covers_body_of_unsigned_blocks() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut alice = Account::new();
+    fund(&mut blockchain, &alice.address, 1_000);
+    // O primeiro bloco faz as vezes de gênesis, sem hash a recalcular
+    commit_transaction(&mut blockchain, alice.transfer(&bob(), 1));
+    let create = alice.operation(OperationKind::CreateToken {
+        token_id: blockchain.next_token_id,
+        name: "Token Corpo".to_string(),
+        symbol: "CRP".to_string(),
+        total_supply: 1_000,
+    });
+    let created = commit(&mut blockchain, vec![create]).index as usize;
+    let transferred =
+        commit_transaction(&mut blockchain, alice.transfer(&bob(), 100)).index as usize;
+    assert!(blockchain.signed_from.is_none());
+    assert!(blockchain.is_chain_valid().unwrap());
+
+    let copy = || Blockchain::from_json(&serde_json::to_vec(&blockchain).unwrap()).unwrap();
+
+    // Sem assinatura do proponente, o hash é o que prende o corpo ao bloco
+    let mut edited = copy();
+    if let OperationKind::CreateToken { total_supply, .. } =
+        &mut edited.chain[created].operations[0].kind
+    {
+        *total_supply = 1_000_000;
+    }
+    assert!(!edited.is_chain_valid().unwrap());
+
+    let mut edited = copy();
+    edited.chain[transferred].transfers[0].amount = 900;
+    assert!(!edited.is_chain_valid().unwrap());
+
+    // Com o hash recalculado, a assinatura da transferência ainda denuncia a edição
+    let mut edited = copy();
+    let block = &mut edited.chain[transferred];
+    block.transfers[0].amount = 900;
+    block.hash = block.compute_hash().unwrap();
+    assert!(!edited.is_chain_valid().unwrap());
+}
+
+#[test]
+fn test_block_signature_survives_sqlite() {
+    let identity = Arc::new(NodeIdentity::generate());
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain.set_signer(Arc::clone(&identity));
+    let mut block = next_block(&blockchain);
+    block.sign(&identity);
+    blockchain.add_block(block).unwrap();
+
+    let path = std::env::temp_dir().join(format!(
+        "kybelith-block-signature-{}.db",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    blockchain.save_to_db(&path.to_string_lossy()).unwrap();
+
+    let restored = Blockchain::load_from_db(&path.to_string_lossy()).unwrap();
+    assert_eq!(restored.chain[0].proposer, blockchain.chain[0].proposer);
+    assert_eq!(restored.signed_from, Some(0));
+    assert!(restored.is_chain_valid().unwrap());
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_block_signature_covers_transactions_and_contracts() {
+    let identity = NodeIdentity::generate();
+    let (public_key, secret_key) = keypair();
+    let transaction = SecureTransaction::new(
+        "a".repeat(40),
+        "b".repeat(40),
+        10,
+        1_700_000_000,
+        1,
+        &secret_key,
+        &public_key,
+    )
+    .unwrap();
+    let contract = SmartContract::new(
+        vec![0, 97, 115, 109],
+        Vec::new(),
+        "c".repeat(40),
+        "a".repeat(40),
+        0,
+        true,
+    );
+    let mut block = Block::new(0, vec![transaction], vec![contract], "0".repeat(64)).unwrap();
+    block.sign(&identity);
+    let key = identity.public_key.as_bytes();
+    assert!(block.verify_signature(key).is_ok());
+
+    // Contagens iguais não bastam: o hash e o cabeçalho cobrem as raízes
+    let mut tampered = block.clone();
+    tampered.transactions[0].amount = 1_000;
+    assert_ne!(tampered.compute_hash().unwrap(), block.hash);
+    assert!(tampered.verify_signature(key).is_err());
+
+    let mut tampered = block.clone();
+    tampered.contracts[0].data = vec![1];
+    assert_ne!(tampered.compute_hash().unwrap(), block.hash);
+    assert!(tampered.verify_signature(key).is_err());
+}
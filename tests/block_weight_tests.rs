@@ -0,0 +1,146 @@
+use kybelith::blockchain::Blockchain;
+use kybelith::config::Limits;
+use kybelith::error::{Error, TransactionError};
+use kybelith::test_utils::fixtures::{address_of, funded, transfer};
+use kybelith::transaction::{Operation, OperationKind, WeightSchedule};
+use pqcrypto_dilithium::dilithium5::{keypair, PublicKey, SecretKey};
+use pqcrypto_traits::sign::PublicKey as _;
+
+fn operation(keys: &(PublicKey, SecretKey), kind: OperationKind) -> Operation {
+    let mut operation =
+        Operation::new(kind, address_of(keys), 1, keys.0.as_bytes().to_vec()).unwrap();
+    operation.sign(&keys.1).unwrap();
+    operation
+}
+
+#[test]
+fn test_weight_schedule_prices_every_item_kind() {
+    let keys = keypair();
+    let weights = WeightSchedule::default();
+    let tx = transfer(&keys, 10, 1);
+    assert_eq!(
+        weights.transfer_weight(tx.encoded_size()),
+        weights.transfer + tx.encoded_size() as u64
+    );
+
+    let admin = operation(
+        &keys,
+        OperationKind::SetKyc {
+            subject: "b".repeat(40),
+            verified: true,
+        },
+    );
+    let shield = operation(
+        &keys,
+        OperationKind::Shield {
+            token_id: 0,
+            amount: 10,
+            blinding: vec![0; 32],
+        },
+    );
+    assert!(weights.operation_weight(&admin) > weights.operation);
+    assert!(weights.operation_weight(&shield) > weights.confidential_operation);
+    assert_eq!(
+        weights.contract_call_weight(100, 7),
+        weights.contract_call + 100 + 7
+    );
+}
+
+#[test]
+fn test_block_building_stops_at_weight_limit() {
+    let keys = keypair();
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(address_of(&keys), 1000);
+    for nonce in 1..=3 {
+        blockchain
+            .submit_transaction(transfer(&keys, 10, nonce))
+            .unwrap();
+    }
+
+    let weight = blockchain
+        .limits
+        .weights
+        .transfer_weight(transfer(&keys, 10, 1).encoded_size());
+    blockchain.limits.max_block_weight = weight * 2;
+    blockchain.produce_block(10).unwrap().unwrap();
+    assert_eq!(blockchain.pending_transactions.len(), 1);
+    let simulated = blockchain.simulate_transaction(&transfer(&keys, 10, 4));
+    assert_eq!(simulated.weight, weight);
+
+    let receipts = blockchain.execution_receipts(0).unwrap();
+    assert_eq!(receipts.len(), 2);
+    assert!(receipts.iter().all(|receipt| receipt.gas_used == weight));
+}
+
+#[test]
+fn test_imported_block_is_held_to_weight_and_item_limits() {
+    let keys = keypair();
+    let mut producer = funded(&address_of(&keys), 1000);
+    for nonce in 1..=3 {
+        producer
+            .submit_transaction(transfer(&keys, 10, nonce))
+            .unwrap();
+    }
+    let block = producer.produce_block(10).unwrap().unwrap();
+    assert_eq!(block.transfers.len(), 3);
+
+    // Sem transações legadas no corpo, o peso vem das transferências
+    let mut importer = funded(&address_of(&keys), 1000);
+    let weight = importer
+        .limits
+        .weights
+        .transfer_weight(block.transfers[0].encoded_size());
+    importer.limits.max_block_weight = weight * 2;
+    assert!(matches!(
+        importer.add_block(block.clone()),
+        Err(Error::InvalidBlock(_))
+    ));
+
+    importer.limits = Limits {
+        max_transaction_size: block.transfers[0].encoded_size() - 1,
+        ..Limits::default()
+    };
+    match importer.add_block(block.clone()) {
+        Err(Error::TransactionError(e)) => {
+            assert!(matches!(*e, TransactionError::DataSizeExceeded))
+        }
+        other => panic!("bloco com transferência grande demais aceito: {:?}", other),
+    }
+
+    importer.limits = Limits::default();
+    importer.add_block(block).unwrap();
+    assert_eq!(importer.height(), 1);
+}
+
+#[test]
+fn test_items_that_cannot_fit_a_block_stay_out_of_it() {
+    let keys = keypair();
+    let mut blockchain = funded(&address_of(&keys), 1000);
+    let kyc = operation(
+        &keys,
+        OperationKind::SetKyc {
+            subject: "b".repeat(40),
+            verified: true,
+        },
+    );
+    let weights = blockchain.limits.weights;
+    blockchain.limits.max_block_weight = weights.operation_weight(&kyc) - 1;
+    assert!(matches!(
+        blockchain.submit_operation(kyc),
+        Err(TransactionError::DataSizeExceeded)
+    ));
+
+    // Um item admitido antes de o limite baixar não sai num bloco inválido
+    blockchain.limits = Limits::default();
+    let tx = transfer(&keys, 10, 1);
+    blockchain.submit_transaction(tx.clone()).unwrap();
+    blockchain.limits.max_block_weight = weights.transfer_weight(tx.encoded_size()) - 1;
+    assert!(blockchain.produce_block(10).is_err());
+    assert_eq!(blockchain.pending_transactions.len(), 1);
+    assert_eq!(blockchain.height(), 0);
+}
@@ -0,0 +1,68 @@
+use kybelith::blockchain::{Block, TransactionReceipt};
+use kybelith::transaction::Transaction;
+use kybelith::utils::canonical_json::{CanonicalJson, CANONICAL_JSON_VERSION};
+use serde_json::{json, Value};
+
+#[test]
+fn test_transaction_document_is_sorted_and_hex_encoded() {
+    let mut tx = Transaction::with_timestamp(
+        "a".repeat(40),
+        "b".repeat(40),
+        250,
+        vec![0xab, 0x01],
+        1_700_000_000,
+    )
+    .unwrap();
+    tx.signature = vec![0xff; 3];
+
+    let document = tx.to_canonical_json();
+    assert!(document.starts_with(r#"{"data":{"amount":250,"from":"#));
+    assert!(!document.contains(' '));
+    assert_eq!(document, tx.clone().to_canonical_json());
+
+    let value: Value = serde_json::from_str(&document).unwrap();
+    assert_eq!(value["type"], "transaction");
+    assert_eq!(value["version"], CANONICAL_JSON_VERSION);
+    assert_eq!(value["data"]["public_key"], "ab01");
+    assert_eq!(value["data"]["signature"], "ffffff");
+    assert_eq!(value["data"]["hash"], tx.hash);
+}
+
+#[test]
+fn test_block_and_receipt_documents_are_deterministic() {
+    let mut block = Block::new(3, Vec::new(), Vec::new(), "00".repeat(32)).unwrap();
+    for hash in ["c", "a", "b"] {
+        block.processed_transactions.insert(hash.to_string());
+    }
+    block.validator_signature = Some(vec![1, 2]);
+
+    let document: Value = serde_json::from_str(&block.to_canonical_json()).unwrap();
+    assert_eq!(document["type"], "block");
+    assert_eq!(
+        document["data"]["processed_transactions"],
+        json!(["a", "b", "c"])
+    );
+    assert_eq!(document["data"]["validator_signature"], "0102");
+    assert_eq!(document["data"]["proposer"], Value::Null);
+    assert_eq!(block.to_canonical_json(), block.clone().to_canonical_json());
+
+    let receipt = TransactionReceipt {
+        height: 3,
+        position: 0,
+        block_hash: block.hash.clone(),
+        from: "a".repeat(40),
+        to: "b".repeat(40),
+        amount: 10,
+        nonce: 1,
+        timestamp: 1_700_000_000,
+    };
+    assert_eq!(
+        receipt.to_canonical_json(),
+        format!(
+            r#"{{"data":{{"amount":10,"block_hash":"{}","from":"{}","height":3,"nonce":1,"position":0,"timestamp":1700000000,"to":"{}"}},"type":"receipt","version":2}}"#,
+            block.hash,
+            "a".repeat(40),
+            "b".repeat(40)
+        )
+    );
+}
@@ -0,0 +1,75 @@
+use kybelith::blockchain::Block;
+use kybelith::error::{Error, TransactionError};
+use kybelith::transaction::{SecureTransaction, Transaction};
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+
+#[test]
+fn test_transaction_cbor_round_trip_is_compact() {
+    let keys = keypair();
+    let mut tx = Transaction::new(
+        "a".repeat(40),
+        "b".repeat(40),
+        250,
+        keys.0.as_bytes().to_vec(),
+    )
+    .unwrap();
+    tx.token_id = 3;
+    tx.sign(&keys.1).unwrap();
+
+    let encoded = tx.to_cbor().unwrap();
+    let decoded = Transaction::from_cbor(&encoded).unwrap();
+    assert_eq!(decoded.token_id, 3);
+    assert_eq!(decoded.signature, tx.signature);
+    assert_eq!(decoded.hash, tx.hash);
+    decoded.verify(&keys.0).unwrap();
+
+    // Chave e assinatura viram byte strings: o CBOR fica perto do bincode e
+    // muito abaixo do JSON, que escreve cada byte como número
+    let bincode = bincode::serialize(&tx).unwrap();
+    let json = serde_json::to_vec(&tx).unwrap();
+    assert!(encoded.len() < bincode.len() + 200);
+    assert!(encoded.len() * 2 < json.len());
+
+    assert!(matches!(
+        Transaction::from_cbor(&encoded[..encoded.len() / 2]),
+        Err(TransactionError::InvalidDataFormat)
+    ));
+    assert!(matches!(
+        Transaction::from_cbor(&vec![0; 200 * 1024]),
+        Err(TransactionError::DataSizeExceeded)
+    ));
+}
+
+#[test]
+fn test_block_cbor_round_trip() {
+    let keys = keypair();
+    let tx = SecureTransaction::new(
+        "a".repeat(40),
+        "b".repeat(40),
+        100,
+        chrono::Utc::now().timestamp(),
+        1,
+        &keys.1,
+        &keys.0,
+    )
+    .unwrap();
+    let mut block = Block::new(1, vec![tx], Vec::new(), "0".repeat(64)).unwrap();
+    block.validator_signature = Some(vec![7; 32]);
+    block.processed_transactions.insert("f".repeat(64));
+
+    let decoded = Block::from_cbor(&block.to_cbor().unwrap()).unwrap();
+    assert_eq!(decoded.hash, block.hash);
+    assert_eq!(decoded.validator_signature, block.validator_signature);
+    assert_eq!(decoded.processed_transactions, block.processed_transactions);
+    assert_eq!(
+        decoded.transactions[0].signature,
+        block.transactions[0].signature
+    );
+    assert_eq!(decoded.to_bytes().unwrap(), block.to_bytes().unwrap());
+
+    assert!(matches!(
+        Block::from_cbor(b"nao e cbor"),
+        Err(Error::InvalidFormat(_))
+    ));
+}
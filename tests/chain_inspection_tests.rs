@@ -0,0 +1,40 @@
+use kybelith::blockchain::{Blockchain, Finality, SharedBlockchain};
+use kybelith::rpc::{BlockResponse, RpcService};
+use kybelith::transaction::Transaction;
+use kybelith::utils::address::derive_address;
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+use serde_json::json;
+
+#[test]
+fn test_get_block_by_hash_matches_get_block() {
+    let keys = keypair();
+    let alice = derive_address(keys.0.as_bytes());
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(alice.clone(), 1000);
+    let mut tx = Transaction::new(alice, "b".repeat(40), 10, keys.0.as_bytes().to_vec()).unwrap();
+    tx.nonce = 1;
+    tx.sign(&keys.1).unwrap();
+    blockchain.submit_transaction(tx).unwrap();
+    let hash = blockchain.produce_block(10).unwrap().unwrap().hash;
+
+    let service = RpcService::new(SharedBlockchain::new(blockchain));
+    let by_hash: BlockResponse = serde_json::from_value(
+        service
+            .handle("get_block_by_hash", json!({ "hash": hash }))
+            .unwrap(),
+    )
+    .unwrap();
+    let by_height = service.handle("get_block", json!({ "height": 0 })).unwrap();
+    assert_eq!(by_hash.block.index, 0);
+    assert_eq!(by_hash.finality, Finality::Included);
+    assert_eq!(by_height["hash"], json!(hash));
+    assert!(service
+        .handle("get_block_by_hash", json!({ "hash": "f".repeat(64) }))
+        .is_err());
+}
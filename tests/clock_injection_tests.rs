@@ -0,0 +1,204 @@
+use kybelith::blockchain::{assemble_block, Blockchain, MempoolSelection};
+use kybelith::config::{ClockConfig, Settings};
+use kybelith::consensus::reputation::{ReputationAction, ReputationSystem};
+use kybelith::consensus::{
+    BlockProposal, EpochConfig, EpochManager, ProposalVote, QuantumFlexConsensus, Validator,
+    ValidatorSet, VotingCoordinator,
+};
+use kybelith::error::TransactionError;
+use kybelith::network::{
+    BanList, Misbehavior, PeerScoring, PeerScoringConfig, PeerSource, PeerStore, PeerVerdict,
+};
+use kybelith::rpc::{Role, RpcAuth};
+use kybelith::test_utils::fixtures::{address_of, funded, transfer};
+use kybelith::utils::clock::{Clock, MockClock, TimeService};
+use kybelith::utils::timestamp_policy::TimestampPolicy;
+use pqcrypto_dilithium::dilithium5::keypair;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+const HOUR: Duration = Duration::from_secs(3600);
+
+#[test]
+fn test_mock_clock_drives_bans_and_liveness() {
+    let mock = MockClock::new(1_700_000_000_000);
+    let mut system = ReputationSystem::new();
+    system.set_clock(mock.shared());
+    system.add_validator("validator1".to_string());
+    system.add_validator("validator2".to_string());
+
+    system.ban_validator("validator1", HOUR).unwrap();
+    mock.advance(HOUR - Duration::from_secs(1));
+    assert!(system
+        .update_reputation("validator1", ReputationAction::CorrectVote)
+        .is_err());
+    mock.advance(Duration::from_secs(2));
+    system
+        .update_reputation("validator1", ReputationAction::CorrectVote)
+        .unwrap();
+    assert!(!system.is_banned("validator1"));
+
+    // validator1 acabou de votar; validator2 está parado desde o início
+    let transitions = system.update_offline_status(HOUR);
+    assert_eq!(
+        transitions,
+        vec![("validator2".to_string(), ReputationAction::Offline)]
+    );
+    assert!(system.update_offline_status(HOUR).is_empty());
+}
+
+#[test]
+fn test_mock_clock_drives_proposals_epochs_and_timestamps() {
+    let mock = MockClock::new(1_700_000_000_000);
+    let validators = vec![Validator::new(
+        "v1".to_string(),
+        "addr-v1".to_string(),
+        vec![1; 32],
+        1_000,
+    )];
+    let mut coordinator = VotingCoordinator::new(
+        Arc::new(RwLock::new(ValidatorSet::new(validators))),
+        Arc::new(RwLock::new(ReputationSystem::new())),
+        66.0,
+    )
+    .with_limits(Duration::from_secs(60), 8)
+    .with_clock(mock.shared());
+    let vote = |hash: &str| ProposalVote::new(hash.to_string(), 1, "v1".to_string(), true, vec![1]);
+    coordinator.process_vote(vote("old")).unwrap();
+    mock.advance(Duration::from_secs(61));
+    coordinator.process_vote(vote("new")).unwrap();
+    assert_eq!(coordinator.expire_stale(), 1);
+    assert!(coordinator.tally_votes("new").is_some());
+
+    // O tempo máximo da época passa sem que o teste espere por ele
+    let mut config = EpochConfig::new(100);
+    config.max_epoch_time_secs = 86_400;
+    let mut epochs = EpochManager::with_clock(config, mock.shared());
+    assert!(epochs.process_new_block(1).is_none());
+    mock.advance(Duration::from_secs(86_400));
+    assert!(epochs.process_new_block(2).is_some());
+
+    let service = TimeService::with_source(ClockConfig::default(), mock.shared());
+    let policy = TimestampPolicy {
+        max_future_skew_sec: 30,
+        max_past_age_sec: 300,
+    };
+    let timestamp = service.now_secs();
+    policy.check_secs(timestamp, service.now_secs()).unwrap();
+    mock.advance(Duration::from_secs(301));
+    assert!(policy.check_secs(timestamp, service.now_secs()).is_err());
+    mock.set_unix_millis(timestamp * 1000 - 31_000);
+    assert!(policy.check_secs(timestamp, service.now_secs()).is_err());
+}
+
+#[test]
+fn test_mock_clock_drives_consensus_state_and_proposal_age() {
+    let mock = MockClock::new(1_700_000_000_000);
+    let consensus = QuantumFlexConsensus::new(Arc::new(Settings::default()), Vec::new())
+        .with_clock(mock.shared());
+    assert!(!consensus.get_state().should_adapt(HOUR));
+    assert_eq!(consensus.get_network_metrics().last_update, mock.instant());
+    mock.advance(HOUR);
+    assert!(consensus.get_state().should_adapt(HOUR));
+
+    let proposal = BlockProposal::new(
+        "hash".to_string(),
+        1,
+        "parent".to_string(),
+        "v1".to_string(),
+        Vec::new(),
+        vec![1],
+        Vec::new(),
+    )
+    .with_clock(&mock);
+    assert_eq!(proposal.timestamp, mock.unix_millis() as u64);
+    mock.advance(Duration::from_secs(30));
+    assert_eq!(proposal.age_at(mock.instant()), Duration::from_secs(30));
+    assert!(proposal.is_expired_at(Duration::from_secs(29), mock.instant()));
+
+    let decoded =
+        BlockProposal::from_bytes_at(&proposal.to_bytes().unwrap(), mock.instant()).unwrap();
+    assert_eq!(decoded.age_at(mock.instant()), Duration::ZERO);
+    let vote =
+        ProposalVote::new("hash".to_string(), 1, "v1".to_string(), true, vec![1]).with_clock(&mock);
+    assert_eq!(vote.timestamp, mock.unix_millis() as u64);
+}
+
+#[test]
+fn test_mock_clock_drives_submission_throttle() {
+    let mock = MockClock::default();
+    let keys = keypair();
+    let mut blockchain = funded(&address_of(&keys), 1_000);
+    blockchain.set_clock(mock.shared());
+    blockchain.limits.max_submissions_per_window = 2;
+    blockchain.limits.submission_window_secs = 60;
+
+    for nonce in 1..=2 {
+        blockchain
+            .submit_transaction(transfer(&keys, 10, nonce))
+            .unwrap();
+    }
+    assert!(matches!(
+        blockchain.submit_transaction(transfer(&keys, 10, 3)),
+        Err(TransactionError::SubmissionRateExceeded(2))
+    ));
+
+    // A janela passa sem que o teste espere por ela
+    mock.advance(Duration::from_secs(61));
+    blockchain
+        .submit_transaction(transfer(&keys, 10, 3))
+        .unwrap();
+}
+
+#[test]
+fn test_mock_clock_drives_block_timestamps_and_token_expiry() {
+    let mock = MockClock::new(1_700_000_000_000);
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain.set_clock(mock.shared());
+
+    // O bloco é datado pelo relógio da cadeia, não pelo do sistema
+    let time = (mock.unix_millis() / 1000) as u64;
+    let block = assemble_block(MempoolSelection::default(), &"0".repeat(64), 0, time).unwrap();
+    blockchain.validate_new_block(&block).unwrap();
+    mock.advance(HOUR);
+    assert!(blockchain.validate_new_block(&block).is_err());
+
+    let node = keypair();
+    let auth = RpcAuth::new(node.0, node.1).with_clock(mock.shared());
+    let token = auth.issue("carteira-1", Role::Wallet, 60).unwrap();
+    assert_eq!(auth.verify(&token).unwrap().iat, mock.unix_millis() / 1000);
+    mock.advance(Duration::from_secs(61));
+    assert!(auth.verify(&token).is_err());
+}
+
+#[test]
+fn test_mock_clock_drives_peer_bans_and_address_book() {
+    let mock = MockClock::new(1_700_000_000_000);
+    let config = PeerScoringConfig {
+        initial_ban_duration: Duration::from_secs(60),
+        ..PeerScoringConfig::default()
+    };
+    let mut scoring = PeerScoring::with_config(BanList::new(), config).with_clock(mock.shared());
+    let now = mock.unix_millis() / 1000;
+    let until = loop {
+        if let PeerVerdict::Ban { until } =
+            scoring.report("10.0.0.9:8000", Misbehavior::ProtocolViolation)
+        {
+            break until;
+        }
+    };
+    assert_eq!(until, now + 60);
+    assert!(!scoring.allows("10.0.0.9:8000"));
+    mock.advance(Duration::from_secs(60));
+    assert!(scoring.allows("10.0.0.9:8000"));
+
+    let mut store = PeerStore::new().with_clock(mock.shared());
+    store.add("10.0.0.7:8000", PeerSource::Gossip);
+    assert_eq!(store.get("10.0.0.7:8000").unwrap().first_seen, now + 60);
+    mock.advance(HOUR);
+    store.record_success("10.0.0.7:8000");
+    assert_eq!(
+        store.get("10.0.0.7:8000").unwrap().last_seen,
+        Some(now + 60 + 3600)
+    );
+}
@@ -0,0 +1,86 @@
+use kybelith::config::{ClockConfig, Settings};
+use kybelith::transaction::Transaction;
+use kybelith::utils::clock::{clock, sntp_offset, sntp_request, OffsetSource, TimeService};
+use kybelith::utils::timestamp_policy::TimestampPolicy;
+
+const NTP_UNIX_OFFSET_SECS: u64 = 2_208_988_800;
+
+fn ntp_secs(unix_ms: i64) -> [u8; 8] {
+    let mut bytes = [0u8; 8];
+    bytes[..4]
+        .copy_from_slice(&((unix_ms as u64 / 1000 + NTP_UNIX_OFFSET_SECS) as u32).to_be_bytes());
+    bytes
+}
+
+#[test]
+fn test_peer_median_requires_quorum_and_is_capped() {
+    let service = TimeService::new(ClockConfig {
+        min_peer_samples: 3,
+        max_offset_adjustment_ms: 10_000,
+        ..ClockConfig::default()
+    });
+
+    service.record_peer_offset("a", 4_000);
+    service.record_peer_offset("b", 5_000);
+    assert_eq!(service.estimated_offset(), (0, OffsetSource::None));
+
+    // Um peer mentindo não move a mediana
+    service.record_peer_offset("c", 900_000);
+    assert_eq!(service.estimated_offset(), (5_000, OffsetSource::Peers));
+    assert_eq!(service.offset_millis(), 5_000);
+
+    // Desvios acima do limite de correção não são aplicados
+    service.record_peer_offset("d", 900_000);
+    service.record_peer_offset("e", 900_000);
+    assert_eq!(service.estimated_offset().0, 900_000);
+    assert_eq!(service.offset_millis(), 0);
+
+    // NTP tem precedência sobre os peers
+    service.record_ntp_offset(-250);
+    assert_eq!(service.estimated_offset(), (-250, OffsetSource::Ntp));
+    let drift = service.now_millis() - chrono::Utc::now().timestamp_millis();
+    assert!((-1_000..=0).contains(&drift));
+}
+
+#[test]
+fn test_sntp_offset_from_response() {
+    let t1 = 1_700_000_000_000;
+    let request = sntp_request(t1);
+
+    let mut response = [0u8; 48];
+    response[0] = 0x24; // VN = 4, modo 4 (servidor)
+    response[1] = 2;
+    response[24..32].copy_from_slice(&request[40..48]);
+    response[32..40].copy_from_slice(&ntp_secs(t1 + 2_000));
+    response[40..48].copy_from_slice(&ntp_secs(t1 + 3_000));
+    assert_eq!(sntp_offset(&request, &response, t1 + 1_000).unwrap(), 2_000);
+
+    let mut spoofed = response;
+    spoofed[24..32].copy_from_slice(&ntp_secs(t1 - 5_000));
+    assert!(sntp_offset(&request, &spoofed, t1 + 1_000).is_err());
+    let mut unsynced = response;
+    unsynced[1] = 0;
+    assert!(sntp_offset(&request, &unsynced, t1 + 1_000).is_err());
+    assert!(sntp_offset(&request, &response[..20], t1 + 1_000).is_err());
+}
+
+#[test]
+fn test_tolerance_is_configurable_and_optional_in_settings() {
+    let mut value = serde_json::to_value(Settings::default()).unwrap();
+    value.as_object_mut().unwrap().remove("clock");
+    let settings: Settings = serde_json::from_value(value).unwrap();
+    assert_eq!(settings.clock.transaction_timestamps.max_past_age_sec, 300);
+
+    clock().configure(ClockConfig {
+        transaction_timestamps: TimestampPolicy::symmetric(10),
+        ..settings.clock
+    });
+    let now = chrono::Utc::now().timestamp();
+    let tx = |timestamp| {
+        Transaction::with_timestamp("a".repeat(40), "b".repeat(40), 5, vec![1; 32], timestamp)
+            .unwrap()
+    };
+    assert!(tx(now - 5).validate_timestamp().is_ok());
+    assert!(tx(now - 60).validate_timestamp().is_err());
+    clock().configure(ClockConfig::default());
+}
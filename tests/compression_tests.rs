@@ -0,0 +1,99 @@
+use kybelith::blockchain::{Block, Blockchain};
+use kybelith::error::Error;
+use kybelith::transaction::SecureTransaction;
+use kybelith::utils::compression::{self, Codec, SUPPORTED_CODECS};
+use pqcrypto_dilithium::dilithium5::keypair;
+
+fn block_with_transactions(count: u64) -> Block {
+    let keys = keypair();
+    let timestamp = chrono::Utc::now().timestamp();
+    let transactions: Vec<SecureTransaction> = (1..=count)
+        .map(|nonce| {
+            SecureTransaction::new(
+                "a".repeat(40),
+                "b".repeat(40),
+                100,
+                timestamp,
+                nonce,
+                &keys.1,
+                &keys.0,
+            )
+            .unwrap()
+        })
+        .collect();
+    Block::new(1, transactions, Vec::new(), "0".repeat(64)).unwrap()
+}
+
+#[test]
+fn test_codec_ids_round_trip() {
+    for codec in [Codec::None, Codec::Zstd] {
+        assert_eq!(Codec::from_id(codec.id()).unwrap(), codec);
+    }
+    assert!(matches!(
+        Codec::from_id(42),
+        Err(Error::UnsupportedCodec(42))
+    ));
+}
+
+#[test]
+fn test_negotiation_prefers_local_order() {
+    assert_eq!(
+        compression::negotiate(SUPPORTED_CODECS, &Codec::supported_ids()),
+        Codec::Zstd
+    );
+    assert_eq!(
+        compression::negotiate(SUPPORTED_CODECS, &[Codec::None.id()]),
+        Codec::None
+    );
+    assert_eq!(compression::negotiate(SUPPORTED_CODECS, &[7]), Codec::None);
+}
+
+#[test]
+fn test_block_round_trip_with_each_codec() {
+    let block = block_with_transactions(4);
+    let raw = block.to_bytes().unwrap();
+
+    let plain = block.to_compressed_bytes(Codec::None).unwrap();
+    let compressed = block.to_compressed_bytes(Codec::Zstd).unwrap();
+    assert_eq!(plain.len(), raw.len() + 1);
+    assert!(compressed.len() < raw.len());
+
+    for frame in [plain, compressed] {
+        let decoded = Block::from_compressed_bytes(&frame).unwrap();
+        assert_eq!(decoded.hash, block.hash);
+        assert_eq!(decoded.to_bytes().unwrap(), raw);
+    }
+}
+
+#[test]
+fn test_decode_rejects_oversized_and_malformed_payloads() {
+    let frame = compression::encode(Codec::Zstd, &vec![0u8; 64 * 1024]).unwrap();
+    assert!(compression::decode(&frame, 1024).is_err());
+    assert_eq!(
+        compression::decode(&frame, 64 * 1024).unwrap().len(),
+        64 * 1024
+    );
+
+    assert!(compression::decode(&[], 1024).is_err());
+    assert!(compression::decode(&[Codec::Zstd.id(), 1, 2, 3], 1024).is_err());
+    assert!(matches!(
+        compression::decode(&[9, 0], 1024),
+        Err(Error::UnsupportedCodec(9))
+    ));
+}
+
+#[test]
+fn test_snapshot_round_trip() {
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain.chain.push(block_with_transactions(2));
+    let path = std::env::temp_dir().join(format!("snapshot-{}.zst", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    blockchain.save_snapshot(path, Codec::Zstd).unwrap();
+    let loaded = Blockchain::load_snapshot(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    assert_eq!(loaded.chain.len(), 1);
+    assert_eq!(loaded.chain[0].hash, blockchain.chain[0].hash);
+    assert_eq!(loaded.tokens["0"].total_supply, 11_000_000);
+}
@@ -0,0 +1,167 @@
+use kybelith::blockchain::Blockchain;
+use kybelith::error::TransactionError;
+use kybelith::test_utils::fixtures::{commit, fund, Account};
+use kybelith::transaction::confidential::{
+    confidential_transfer, prove_range, seal_memo, shield, unshield, verify_range, Opening, ViewKey,
+};
+use kybelith::transaction::operation::MAX_MEMO_SIZE;
+use kybelith::transaction::OperationKind;
+
+#[test]
+fn test_range_proof_rejects_other_commitments() {
+    let opening = Opening::random(1_234_567).unwrap();
+    let commitment = opening.commitment().unwrap();
+    let proof = prove_range(&opening).unwrap();
+    verify_range(&commitment, &proof).unwrap();
+
+    let other = Opening::random(1_234_567).unwrap().commitment().unwrap();
+    assert!(verify_range(&other, &proof).is_err());
+
+    let mut tampered = proof.clone();
+    tampered[40] ^= 1;
+    assert!(verify_range(&commitment, &tampered).is_err());
+    assert!(verify_range(&commitment, &proof[..proof.len() - 1]).is_err());
+}
+
+#[test]
+fn test_confidential_transfer_with_auditor() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut alice = Account::new();
+    let mut bob = Account::new();
+    let bob_view = ViewKey::generate().unwrap();
+    let auditor = ViewKey::generate().unwrap();
+    blockchain.add_auditor_view_key(auditor.public_key().to_vec());
+    fund(&mut blockchain, &alice.address, 1_000);
+
+    let register = bob.operation(OperationKind::RegisterViewKey {
+        public_key: bob_view.public_key().to_vec(),
+    });
+    commit(&mut blockchain, [register]);
+
+    let (kind, balance) = shield(0, 600).unwrap();
+    commit(&mut blockchain, [alice.operation(kind)]);
+    assert_eq!(
+        blockchain.get_token("0").unwrap().balances[&alice.address],
+        400
+    );
+    assert!(balance.opens(blockchain.confidential_balance(0, &alice.address).unwrap()));
+
+    // Sem a abertura para o auditor a transferência não é admitida
+    let (partial, _) = confidential_transfer(
+        0,
+        bob.address.clone(),
+        250,
+        &balance,
+        bob_view.public_key(),
+        &[],
+        None,
+    )
+    .unwrap();
+    assert!(matches!(
+        blockchain.submit_operation(alice.operation(partial)),
+        Err(TransactionError::InvalidParameter(_))
+    ));
+    alice.nonce -= 1;
+
+    let (kind, remaining) = confidential_transfer(
+        0,
+        bob.address.clone(),
+        250,
+        &balance,
+        bob_view.public_key(),
+        &[auditor.public_key().to_vec()],
+        Some("aluguel de março"),
+    )
+    .unwrap();
+    assert!(matches!(&kind, OperationKind::ConfidentialTransfer { .. }));
+    let received = bob_view.open_transfer(&kind).unwrap();
+    assert_eq!(received.value, 250);
+    assert_eq!(auditor.open_transfer(&kind).unwrap().value, 250);
+    assert!(ViewKey::generate().unwrap().open_transfer(&kind).is_none());
+    assert!(auditor.read_memo(&kind).is_none());
+    commit(&mut blockchain, [alice.operation(kind)]);
+    assert_eq!(
+        blockchain.memos_for(&bob.address, &bob_view),
+        vec![(alice.address.clone(), "aluguel de março".to_string())]
+    );
+
+    assert_eq!(remaining.value, 350);
+    assert!(remaining.opens(blockchain.confidential_balance(0, &alice.address).unwrap()));
+    assert!(received.opens(blockchain.confidential_balance(0, &bob.address).unwrap()));
+
+    // Bob devolve parte ao saldo público
+    let (kind, left) = unshield(0, 100, &received).unwrap();
+    commit(&mut blockchain, [bob.operation(kind)]);
+    assert_eq!(
+        blockchain.get_token("0").unwrap().balances[&bob.address],
+        100
+    );
+    assert_eq!(left.value, 150);
+    assert!(left.opens(blockchain.confidential_balance(0, &bob.address).unwrap()));
+}
+
+#[test]
+fn test_overspend_cannot_be_proven() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut alice = Account::new();
+    let mut bob = Account::new();
+    let bob_view = ViewKey::generate().unwrap();
+    fund(&mut blockchain, &alice.address, 100);
+    let register = bob.operation(OperationKind::RegisterViewKey {
+        public_key: bob_view.public_key().to_vec(),
+    });
+    commit(&mut blockchain, [register]);
+
+    let (kind, balance) = shield(0, 100).unwrap();
+    commit(&mut blockchain, [alice.operation(kind)]);
+
+    assert!(matches!(
+        confidential_transfer(
+            0,
+            bob.address.clone(),
+            101,
+            &balance,
+            bob_view.public_key(),
+            &[],
+            None
+        ),
+        Err(TransactionError::InsufficientFunds)
+    ));
+
+    // Carteira que mente sobre o saldo: a prova do restante não confere na aplicação
+    let inflated = Opening {
+        value: 1_000,
+        blinding: balance.blinding,
+    };
+    let (kind, _) = confidential_transfer(
+        0,
+        bob.address.clone(),
+        500,
+        &inflated,
+        bob_view.public_key(),
+        &[],
+        None,
+    )
+    .unwrap();
+    commit(&mut blockchain, [alice.operation(kind)]);
+    assert!(blockchain.confidential_balance(0, &bob.address).is_none());
+    assert!(balance.opens(blockchain.confidential_balance(0, &alice.address).unwrap()));
+}
+
+#[test]
+fn test_memo_readable_only_by_recipient() {
+    let recipient = ViewKey::generate().unwrap();
+    let memo = seal_memo("pedido 42", recipient.public_key()).unwrap();
+    assert_eq!(recipient.open_memo(&memo).unwrap(), "pedido 42");
+    assert!(ViewKey::generate().unwrap().open_memo(&memo).is_err());
+
+    let mut tampered = memo.clone();
+    tampered.ciphertext[0] ^= 1;
+    assert!(recipient.open_memo(&tampered).is_err());
+
+    let too_long = "x".repeat(MAX_MEMO_SIZE + 1);
+    assert!(matches!(
+        seal_memo(&too_long, recipient.public_key()),
+        Err(TransactionError::DataSizeExceeded)
+    ));
+}
@@ -0,0 +1,90 @@
+use kybelith::blockchain::{Blockchain, SharedBlockchain};
+use kybelith::config::{reloadable_changes, Settings};
+use kybelith::error::{ErrorCode, TransactionError};
+use kybelith::reload::ConfigReloader;
+use kybelith::rpc::{RpcRateLimiter, RpcService};
+use kybelith::transaction::Transaction;
+use kybelith::utils::address::derive_address;
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+use serde_json::Value;
+use std::sync::Arc;
+
+#[test]
+fn test_only_runtime_settings_are_reloadable() {
+    let current = Settings::default();
+
+    let mut next = current.clone();
+    next.node.log_level = "debug".to_string();
+    next.rpc.max_requests_per_window = 10;
+    next.p2p.max_incoming_connections = 5;
+    next.limits.fee_floor = 3;
+    let runtime = reloadable_changes(&current, &next).unwrap();
+    assert_eq!(runtime.fee_floor, 3);
+    assert_eq!(runtime.rpc.max_requests_per_window, 10);
+
+    let mut consensus = next.clone();
+    consensus.consensus.block_interval_sec += 1;
+    let err = reloadable_changes(&current, &consensus).unwrap_err();
+    assert!(err.contains("consensus"), "{}", err);
+
+    let mut invalid = next;
+    invalid.node.log_level = "verboso".to_string();
+    assert!(reloadable_changes(&current, &invalid).is_err());
+}
+
+#[test]
+fn test_reload_applies_and_keeps_previous_on_error() {
+    let path = std::env::temp_dir().join(format!("kybelith-reload-{}.json", std::process::id()));
+    Settings::default().save_to_file(&path).unwrap();
+    let settings = Settings::from_file(&path).unwrap();
+    assert_eq!(settings.source.as_deref(), Some(path.as_path()));
+
+    let keys = keypair();
+    let alice = derive_address(keys.0.as_bytes());
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(alice.clone(), 100);
+    let shared = SharedBlockchain::new(blockchain);
+    let limiter = Arc::new(RpcRateLimiter::new(settings.rpc));
+    let reloader = ConfigReloader::new(settings.clone(), shared.clone(), Arc::clone(&limiter));
+
+    let mut edited = settings.clone();
+    edited.limits.fee_floor = 5;
+    edited.rpc.max_requests_per_window = 1;
+    edited.save_to_file(&path).unwrap();
+    reloader.reload().unwrap();
+    assert_eq!(reloader.settings().limits.fee_floor, 5);
+
+    // A taxa de uma transferência de 10 é o mínimo (1), abaixo do novo piso
+    let mut tx = Transaction::new(alice, "b".repeat(40), 10, keys.0.as_bytes().to_vec()).unwrap();
+    tx.nonce = 1;
+    tx.sign(&keys.1).unwrap();
+    let err = shared.submit_transaction(tx).unwrap_err();
+    assert!(matches!(err, TransactionError::FeeBelowFloor(5)));
+    assert_eq!(err.code(), 2029);
+
+    let service = RpcService::new(shared.clone()).with_rate_limiter(limiter);
+    service.call("get_height", None, Value::Null).unwrap();
+    assert_eq!(
+        service
+            .call("get_height", None, Value::Null)
+            .unwrap_err()
+            .code(),
+        1031
+    );
+
+    // Configuração inválida não é aplicada
+    let mut invalid = edited;
+    invalid.node.log_level = "verboso".to_string();
+    invalid.limits.fee_floor = 0;
+    invalid.save_to_file(&path).unwrap();
+    assert!(reloader.reload().is_err());
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(reloader.settings().limits.fee_floor, 5);
+    assert_eq!(shared.limits().fee_floor, 5);
+}
@@ -0,0 +1,111 @@
+use kybelith::blockchain::Blockchain;
+use kybelith::rbac::AdminRole;
+use kybelith::smart_contract::{ContractLimits, SmartContract};
+use kybelith::test_utils::fixtures::Account;
+use kybelith::transaction::{OperationKind, TransferRule};
+
+const BOUNDED_CONTRACT: &str = r#"
+(module
+  (import "kybelith" "log" (func $log (param i32)))
+  (memory 1 4)
+  (table 2 funcref)
+  (func (export "check_transfer") (param i64 i32 i32) (result i32)
+    (i32.const 0)))
+"#;
+
+fn module_with_functions(count: usize) -> String {
+    let functions = "(func (result i32) (i32.const 0))".repeat(count);
+    format!("(module {})", functions)
+}
+
+#[test]
+fn test_limits_reject_oversized_and_suspicious_modules() {
+    let limits = ContractLimits::default();
+    let profile = limits.inspect(BOUNDED_CONTRACT.as_bytes()).unwrap();
+    assert_eq!(profile.functions, 2);
+    assert_eq!(profile.tables, 1);
+    assert_eq!(profile.memory_pages, 4);
+    assert_eq!(profile.imports, ["kybelith.log"]);
+
+    let rejected = [
+        (vec![0; limits.max_code_size + 1], "bytes"),
+        (b"(module (func".to_vec(), "WAT"),
+        (vec![0x00, 0x61, 0x73, 0x6d, 0x02], "inválido"),
+        (module_with_functions(257).into_bytes(), "funções"),
+        (
+            b"(module (table 1 funcref) (table 1 funcref))".to_vec(),
+            "tabelas",
+        ),
+        (b"(module (memory 1))".to_vec(), "máximo"),
+        (b"(module (memory 1 17))".to_vec(), "páginas"),
+        (
+            br#"(module (import "wasi_snapshot_preview1" "fd_write" (func)))"#.to_vec(),
+            "wasi_snapshot_preview1.fd_write",
+        ),
+    ];
+    for (code, reason) in rejected {
+        let err = limits.inspect(&code).unwrap_err();
+        assert!(err.contains(reason), "{}", err);
+    }
+    limits
+        .inspect(module_with_functions(256).as_bytes())
+        .unwrap();
+
+    // Um import específico pode ser banido sem banir o módulo inteiro
+    let strict = ContractLimits {
+        banned_imports: vec!["kybelith.log".to_string()],
+        ..ContractLimits::default()
+    };
+    assert!(strict.inspect(BOUNDED_CONTRACT.as_bytes()).is_err());
+    let contract = SmartContract::new(
+        BOUNDED_CONTRACT.as_bytes().to_vec(),
+        Vec::new(),
+        "c".repeat(40),
+        "a".repeat(40),
+        0,
+        true,
+    );
+    assert!(contract.check_limits(&strict).is_err());
+    assert_eq!(contract.check_limits(&limits).unwrap(), profile);
+}
+
+#[test]
+fn test_policy_contract_outside_limits_never_enters_chain() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut operator = Account::new();
+    blockchain
+        .bootstrap_admin(operator.address.clone())
+        .unwrap();
+    let grant = operator.operation(OperationKind::AssignRole {
+        subject: operator.address.clone(),
+        role: AdminRole::Operator,
+        granted: true,
+    });
+    let token_id = blockchain.next_token_id;
+    let create = operator.operation(OperationKind::CreateToken {
+        token_id,
+        name: "Limitado".to_string(),
+        symbol: "LIM".to_string(),
+        total_supply: 1_000,
+    });
+    blockchain.submit_operation(grant).unwrap();
+    blockchain.submit_operation(create).unwrap();
+    blockchain.produce_block(10).unwrap().unwrap();
+
+    let policy = |code: &[u8]| OperationKind::SetTransferPolicy {
+        token_id,
+        rules: vec![TransferRule::Contract {
+            code: code.to_vec(),
+        }],
+    };
+    let unbounded = operator.operation(policy(b"(module (memory 1))"));
+    let err = blockchain.submit_operation(unbounded).unwrap_err();
+    assert!(err.to_string().contains("máximo"), "{}", err);
+    operator.nonce -= 1;
+    assert!(blockchain.transfer_policy(token_id).is_empty());
+
+    let bounded = operator.operation(policy(BOUNDED_CONTRACT.as_bytes()));
+    blockchain.submit_operation(bounded).unwrap();
+    blockchain.produce_block(10).unwrap().unwrap();
+    assert_eq!(blockchain.transfer_policy(token_id).len(), 1);
+}
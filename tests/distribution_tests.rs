@@ -0,0 +1,99 @@
+use kybelith::blockchain::{Blockchain, TransactionIndex};
+use kybelith::error::TransactionError;
+use kybelith::test_utils::fixtures::{balance, fund, Account};
+use kybelith::token::{Distribution, Token};
+use kybelith::transaction::OperationKind;
+use pqcrypto_traits::sign::PublicKey as _;
+use std::collections::HashMap;
+
+fn create_token(blockchain: &mut Blockchain, creator: &mut Account, symbol: &str) -> u64 {
+    let token_id = blockchain.next_token_id;
+    let operation = creator.operation(OperationKind::CreateToken {
+        token_id,
+        name: format!("Token {}", symbol),
+        symbol: symbol.to_string(),
+        total_supply: 10_000,
+    });
+    blockchain.submit_operation(operation).unwrap();
+    blockchain.produce_block(10).unwrap();
+    token_id
+}
+
+#[test]
+fn test_rounding_and_dust() {
+    let mut index = TransactionIndex::default();
+    index.record_balances(
+        0,
+        5,
+        &HashMap::from([
+            ("a".to_string(), 1),
+            ("b".to_string(), 1),
+            ("c".to_string(), 1),
+            ("d".to_string(), 0),
+        ]),
+    );
+    let token = Token::new("Cota".into(), "COTA".into(), 3, "a".into()).unwrap();
+    let snapshot = token.snapshot(5, &index).unwrap();
+
+    // 100 / 3: a unidade que sobra vai para o primeiro endereço entre restos iguais
+    let distribution = Distribution::pro_rata(&snapshot, 0, 100, 1).unwrap();
+    assert_eq!(distribution.entitlement("a"), 34);
+    assert_eq!(distribution.entitlement("b"), 33);
+    assert_eq!(distribution.entitlement("d"), 0);
+    assert_eq!(distribution.outstanding(), 100);
+
+    let mut distribution = Distribution::pro_rata(&snapshot, 0, 100, 34).unwrap();
+    assert_eq!(distribution.entitlements().len(), 1);
+    assert_eq!(distribution.dust(), 66);
+    assert_eq!(distribution.claim("a").unwrap(), 34);
+    assert!(matches!(
+        distribution.claim("a"),
+        Err(TransactionError::InvalidParameter(_))
+    ));
+    assert_eq!(distribution.outstanding(), 0);
+
+    assert!(matches!(
+        Distribution::pro_rata(&snapshot, 0, 0, 1),
+        Err(TransactionError::ValorInvalido)
+    ));
+}
+
+#[test]
+fn test_distribution_paid_by_batch_transfers() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut alice = Account::new();
+    let bob = Account::new();
+    let carol = Account::new();
+    let shares = create_token(&mut blockchain, &mut alice, "COTA");
+    let reward = create_token(&mut blockchain, &mut alice, "RWD");
+    fund(&mut blockchain, &alice.address, 100);
+
+    let tx = alice.transfer_token(shares, &bob.address, 3_000);
+    blockchain.submit_transaction(tx).unwrap();
+    let tx = alice.transfer_token(shares, &carol.address, 1_000);
+    blockchain.submit_transaction(tx).unwrap();
+    let height = blockchain.produce_block(10).unwrap().unwrap().index;
+
+    let snapshot = blockchain.snapshot(shares, height).unwrap();
+    let distribution = Distribution::pro_rata(&snapshot, reward, 1_000, 1).unwrap();
+    assert_eq!(distribution.entitlement(&alice.address), 600);
+
+    // A parte de quem financia fica com ele, sem transferência
+    let transfers = distribution
+        .transfers(
+            &alice.address,
+            alice.keys.0.as_bytes(),
+            &alice.keys.1,
+            alice.nonce + 1,
+        )
+        .unwrap();
+    assert_eq!(transfers.len(), 2);
+    for tx in transfers {
+        blockchain.submit_transaction(tx).unwrap();
+    }
+    blockchain.produce_block(10).unwrap();
+
+    assert_eq!(balance(&blockchain, reward, &bob.address), 300);
+    assert_eq!(balance(&blockchain, reward, &carol.address), 100);
+    assert_eq!(balance(&blockchain, reward, &alice.address), 9_600);
+}
@@ -0,0 +1,72 @@
+use kybelith::blockchain::{SharedBlockchain, SimulationResult, SimulationStage, SimulationStatus};
+use kybelith::events::AppEvent;
+use kybelith::rpc::RpcService;
+use kybelith::test_utils::fixtures::{address_of, bob, funded, transfer};
+use pqcrypto_dilithium::dilithium5::keypair;
+
+#[test]
+fn test_simulate_transaction_reports_outcome_without_mutating_state() {
+    let keys = keypair();
+    let blockchain = funded(&address_of(&keys), 10_000);
+
+    let tx = transfer(&keys, 5_000, 1);
+    let result = blockchain.simulate_transaction(&tx);
+    assert_eq!(result.status, SimulationStatus::Accepted);
+    assert_eq!(result.fee, 5);
+    assert_eq!(
+        result.events,
+        vec![AppEvent::TransferApplied {
+            txid: tx.txid(),
+            token_id: 0,
+            from: address_of(&keys),
+            to: bob(),
+            amount: 5_000,
+            height: 0,
+        }]
+    );
+
+    let broke = blockchain.simulate_transaction(&transfer(&keys, 50_000, 1));
+    assert!(matches!(
+        broke.status,
+        SimulationStatus::Rejected {
+            stage: SimulationStage::Execution,
+            ..
+        }
+    ));
+    assert!(broke.events.is_empty());
+
+    let skipped = blockchain.simulate_transaction(&transfer(&keys, 10, 2));
+    assert!(matches!(
+        skipped.status,
+        SimulationStatus::Rejected {
+            stage: SimulationStage::Admission,
+            ..
+        }
+    ));
+
+    // Nada foi admitido nem movido
+    assert!(blockchain.pending_transactions.is_empty());
+    assert!(!blockchain.nonces.contains_key(&address_of(&keys)));
+    assert_eq!(blockchain.tokens["0"].balances[&address_of(&keys)], 10_000);
+    assert!(!blockchain.tokens["0"].balances.contains_key(&bob()));
+}
+
+#[test]
+fn test_simulate_transaction_over_rpc() {
+    let keys = keypair();
+    let service = RpcService::new(SharedBlockchain::new(funded(&address_of(&keys), 10_000)));
+    let tx = transfer(&keys, 200, 1);
+
+    let result: SimulationResult = serde_json::from_value(
+        service
+            .handle("simulate_transaction", serde_json::to_value(&tx).unwrap())
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(result.status, SimulationStatus::Accepted);
+    assert_eq!(result.fee, 1);
+
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(json["status"], "accepted");
+    assert_eq!(json["fee"], 1);
+}
@@ -0,0 +1,126 @@
+use kybelith::audit::{self, AuditAction, AuditLog, SharedAuditLog};
+use kybelith::blockchain::Blockchain;
+use kybelith::error::{ErrorCode, TransactionError};
+use kybelith::rbac::AdminRole;
+use kybelith::test_utils::fixtures::{balance, commit, Account};
+use kybelith::transaction::{Operation, OperationKind};
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+fn halt(account: &mut Account, halted: bool) -> Operation {
+    account.operation(OperationKind::SetChainHalted {
+        halted,
+        reason: "Falha crítica na validação de blocos".to_string(),
+    })
+}
+
+#[test]
+fn test_majority_of_admins_halts_and_resumes_chain() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let log: SharedAuditLog = Arc::new(Mutex::new(AuditLog::new()));
+    audit::record_events(&log, &blockchain.events);
+    let mut alice = Account::new();
+    let mut bob = Account::new();
+    let mut carol = Account::new();
+    blockchain.bootstrap_admin(alice.address.clone()).unwrap();
+    let grant = alice.operation(OperationKind::AssignRole {
+        subject: bob.address.clone(),
+        role: AdminRole::Admin,
+        granted: true,
+    });
+    commit(&mut blockchain, [grant]);
+    assert_eq!(blockchain.halt_threshold(), 2);
+
+    // Quem não é admin não aprova parada
+    assert!(blockchain.submit_operation(halt(&mut carol, true)).is_err());
+    carol.nonce -= 1;
+
+    commit(&mut blockchain, [halt(&mut alice, true)]);
+    assert!(!blockchain.is_halted());
+    assert_eq!(blockchain.halt_approvers(), vec![alice.address.clone()]);
+    commit(&mut blockchain, [halt(&mut bob, true)]);
+    assert!(blockchain.is_halted());
+
+    let record = blockchain.halt_history.last().unwrap();
+    let mut approvers = vec![alice.address.clone(), bob.address.clone()];
+    approvers.sort();
+    assert_eq!(record.approvers, approvers);
+    assert_eq!(record.height, 2);
+
+    // Admissão fechada para transferências e operações comuns; consultas seguem
+    let err = blockchain
+        .submit_transaction(carol.transfer(&alice.address, 1))
+        .unwrap_err();
+    assert!(matches!(err, TransactionError::ChainHalted));
+    assert_eq!(err.code(), 2030);
+    assert!(err.is_retryable());
+    carol.nonce -= 1;
+    let token_id = blockchain.next_token_id;
+    let create = carol.operation(OperationKind::CreateToken {
+        token_id,
+        name: "Bloqueado".to_string(),
+        symbol: "BLK".to_string(),
+        total_supply: 1,
+    });
+    assert!(matches!(
+        blockchain.submit_operation(create),
+        Err(TransactionError::ChainHalted)
+    ));
+    carol.nonce -= 1;
+    assert!(blockchain.submit_operation(halt(&mut alice, true)).is_err());
+    alice.nonce -= 1;
+    assert_eq!(balance(&blockchain, 0, &carol.address), 0);
+
+    commit(&mut blockchain, [halt(&mut alice, false)]);
+    assert!(blockchain.is_halted());
+    commit(&mut blockchain, [halt(&mut bob, false)]);
+    assert!(!blockchain.is_halted());
+    assert_eq!(blockchain.halt_history.len(), 2);
+    assert!(blockchain.halt_approvals.is_empty());
+
+    let log = log.lock();
+    let halts: Vec<bool> = log
+        .entries()
+        .iter()
+        .filter_map(|entry| match &entry.action {
+            AuditAction::ChainHaltChanged { halted, .. } => Some(*halted),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(halts, [true, false]);
+    assert_eq!(log.entries().last().unwrap().actor, bob.address);
+}
+
+#[test]
+fn test_halt_defers_pending_transfers_until_resume() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut admin = Account::new();
+    let mut payer = Account::new();
+    let payee = Account::new();
+    blockchain.bootstrap_admin(admin.address.clone()).unwrap();
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(payer.address.clone(), 1_000);
+
+    // A transferência entrou antes da parada e fica no mempool enquanto durar
+    blockchain
+        .submit_transaction(payer.transfer(&payee.address, 100))
+        .unwrap();
+    blockchain.submit_operation(halt(&mut admin, true)).unwrap();
+    blockchain.produce_block(10).unwrap().unwrap();
+    assert!(blockchain.is_halted());
+    assert_eq!(blockchain.pending_transactions.len(), 1);
+    assert_eq!(balance(&blockchain, 0, &payee.address), 0);
+    assert!(blockchain.produce_block(10).unwrap().is_none());
+
+    let resume = halt(&mut admin, false);
+    blockchain.submit_operation(resume).unwrap();
+    blockchain.produce_block(10).unwrap().unwrap();
+    assert!(!blockchain.is_halted());
+    blockchain.produce_block(10).unwrap().unwrap();
+    assert_eq!(balance(&blockchain, 0, &payee.address), 100);
+    assert!(blockchain.pending_transactions.is_empty());
+}
@@ -0,0 +1,81 @@
+use kybelith::transaction::confidential::Opening;
+use kybelith::transaction::SecureTransaction;
+use kybelith::utils::dilithium;
+use kybelith::utils::entropy::{entropy, EntropyService, EntropySource, OsEntropy, SeededEntropy};
+use pqcrypto_dilithium::dilithium5::{detached_sign, keypair, verify_detached_signature};
+use pqcrypto_traits::sign::{PublicKey as _, SecretKey as _};
+use std::sync::Arc;
+
+#[test]
+fn test_seeded_entropy_is_reproducible_and_counted() {
+    let whole = SeededEntropy::from_u64(7);
+    let mut expected = [0u8; 80];
+    whole.fill_bytes(&mut expected);
+
+    // O fatiamento dos pedidos não muda a sequência
+    let service = EntropyService::new(SeededEntropy::from_u64(7).shared());
+    let mut chunked = service.bytes(10);
+    chunked.extend(service.array::<33>());
+    chunked.extend(service.bytes(37));
+    assert_eq!(chunked, expected);
+    assert_eq!(service.bytes_drawn(), 80);
+
+    let other = EntropyService::new(SeededEntropy::from_u64(8).shared());
+    assert_ne!(other.bytes(80), expected);
+    let os = EntropyService::new(Arc::new(OsEntropy));
+    assert_ne!(os.array::<32>(), os.array::<32>());
+}
+
+#[test]
+fn test_injected_source_drives_salts_ivs_and_blinding() {
+    let (public_key, secret_key) = keypair();
+    let secure = || {
+        SecureTransaction::new(
+            "alice".to_string(),
+            "bob".to_string(),
+            10,
+            1_700_000_000,
+            1,
+            &secret_key,
+            &public_key,
+        )
+        .unwrap()
+    };
+
+    let before = entropy().bytes_drawn();
+    let seeded = entropy().scoped_source(SeededEntropy::from_u64(42).shared());
+    let first = secure();
+    let first_opening = Opening::random(10).unwrap();
+    drop(seeded);
+    let seeded = entropy().scoped_source(SeededEntropy::from_u64(42).shared());
+    let second = secure();
+    let second_opening = Opening::random(10).unwrap();
+    drop(seeded);
+
+    assert_eq!(
+        (&first.salt, &first.iv, &first.cipher_key),
+        (&second.salt, &second.iv, &second.cipher_key)
+    );
+    assert_ne!(first.salt, first.iv);
+    assert_eq!(first_opening.blinding, second_opening.blinding);
+    assert!(entropy().bytes_drawn() >= before + 2 * (24 + 24 + 32 + 64));
+
+    // Também com escopo, para não consumir a sequência de outro teste
+    let os = entropy().scoped_source(Arc::new(OsEntropy));
+    let fresh = secure();
+    drop(os);
+    assert_ne!(fresh.salt, first.salt);
+}
+
+#[test]
+fn test_dilithium_keypair_signs_with_pqcrypto() {
+    let (public_key, secret_key) = dilithium::keypair();
+    assert_eq!(public_key.as_bytes().len(), 2592);
+    assert_eq!(secret_key.as_bytes().len(), 4896);
+
+    let signature = detached_sign(b"kybelith", &secret_key);
+    verify_detached_signature(&signature, b"kybelith", &public_key).unwrap();
+    let other = dilithium::keypair();
+    assert_ne!(other.0.as_bytes(), public_key.as_bytes());
+    assert!(verify_detached_signature(&signature, b"kybelith", &other.0).is_err());
+}
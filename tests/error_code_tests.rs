@@ -0,0 +1,81 @@
+use kybelith::blockchain::{Blockchain, SharedBlockchain};
+use kybelith::consensus::types::ConsensusError;
+use kybelith::error::{Error, ErrorCategory, ErrorCode, TransactionError};
+use kybelith::rpc::{RpcErrorResponse, RpcService};
+use serde_json::{json, Value};
+use std::error::Error as _;
+
+#[test]
+fn test_error_codes_and_classification() {
+    assert_eq!(Error::TokenNotFound.code(), 1001);
+    assert_eq!(Error::TokenNotFound.category(), ErrorCategory::NotFound);
+    assert!(Error::TokenNotFound.is_client_error());
+    assert!(!Error::TokenNotFound.is_retryable());
+
+    assert!(Error::LockError.is_retryable());
+    assert!(!Error::Other("falha".into()).is_client_error());
+    assert!(TransactionError::Busy.is_retryable());
+    assert_eq!(
+        TransactionError::NonceReused.category(),
+        ErrorCategory::Conflict
+    );
+
+    assert!(ConsensusError::ConsensusTimeout.is_retryable());
+    let votes = ConsensusError::InsufficientVotes {
+        required: 3,
+        received: 1,
+    };
+    assert_eq!(votes.code(), 3005);
+    assert_eq!(
+        ConsensusError::InvalidProposer("x".into()).category(),
+        ErrorCategory::Unauthorized
+    );
+}
+
+#[test]
+fn test_wrapped_transaction_error_keeps_code_and_source() {
+    let error = Error::from(TransactionError::AddressFormatInvalid);
+    assert_eq!(error.code(), TransactionError::AddressFormatInvalid.code());
+    assert_eq!(error.category(), ErrorCategory::Validation);
+
+    let source = error.source().expect("erro de transação como causa");
+    assert_eq!(source.to_string(), "Formato de endereço inválido");
+
+    let json_error = serde_json::from_str::<u32>("x").unwrap_err();
+    let error = Error::from(json_error);
+    assert_eq!(error.category(), ErrorCategory::Format);
+    assert!(error.source().is_some());
+}
+
+#[test]
+fn test_rpc_errors_carry_codes() {
+    let service = RpcService::new(SharedBlockchain::new(Blockchain::new().unwrap()));
+
+    let error = service
+        .call("get_block", None, json!({ "height": 7 }))
+        .unwrap_err();
+    let response = RpcErrorResponse::new("get_block", &error);
+    assert_eq!(response.code, 1025);
+    assert_eq!(response.category, ErrorCategory::Validation);
+    assert!(!response.retryable);
+
+    let body = serde_json::to_value(&response).unwrap();
+    assert_eq!(body["category"], "validation");
+
+    let error = service.call("inexistente", None, Value::Null).unwrap_err();
+    assert!(error.is_client_error());
+}
+
+#[test]
+fn test_library_apis_return_typed_errors() {
+    let error = match kybelith::Database::new("/diretorio-inexistente/kybelith.db") {
+        Err(error) => error,
+        Ok(_) => panic!("banco aberto em diretório inexistente"),
+    };
+    assert!(matches!(error, Error::Database { .. }));
+    assert_eq!(error.code(), 1030);
+    assert!(error.source().is_some());
+
+    let blockchain: Result<Blockchain, Error> = Blockchain::new();
+    assert!(blockchain.is_ok());
+}
@@ -0,0 +1,121 @@
+use kybelith::blockchain::Blockchain;
+use kybelith::consensus::{ReputationAction, ReputationSystem};
+use kybelith::events::{AppEvent, EventBus};
+use kybelith::test_utils::fixtures::{address_of, bob, fund};
+use kybelith::transaction::{Operation, OperationKind, Transaction};
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn test_block_commit_emits_item_events_then_block() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let keys = keypair();
+    let token_id = blockchain.next_token_id;
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&received);
+    blockchain
+        .events
+        .on(move |event| sink.lock().unwrap().push(event.clone()));
+    let mut receiver = blockchain.events.subscribe();
+    // KYBL para a taxa da transferência
+    fund(&mut blockchain, &address_of(&keys), 10);
+
+    let mut operation = Operation::new(
+        OperationKind::CreateToken {
+            token_id,
+            name: "Ouro Digital".to_string(),
+            symbol: "OURO".to_string(),
+            total_supply: 1_000,
+        },
+        address_of(&keys),
+        1,
+        keys.0.as_bytes().to_vec(),
+    )
+    .unwrap();
+    operation.sign(&keys.1).unwrap();
+    blockchain.submit_operation(operation).unwrap();
+
+    let mut tx =
+        Transaction::new(address_of(&keys), bob(), 250, keys.0.as_bytes().to_vec()).unwrap();
+    tx.token_id = token_id;
+    tx.nonce = 2;
+    tx.sign(&keys.1).unwrap();
+    let txid = tx.txid();
+    blockchain.submit_transaction(tx).unwrap();
+
+    let block = blockchain.produce_block(10).unwrap().unwrap();
+
+    let events = received.lock().unwrap().clone();
+    assert_eq!(
+        events,
+        vec![
+            AppEvent::TokenCreated {
+                token_id,
+                symbol: "OURO".to_string(),
+                owner: address_of(&keys),
+                total_supply: 1_000,
+                height: block.index,
+            },
+            AppEvent::TransferApplied {
+                txid,
+                token_id,
+                from: address_of(&keys),
+                to: bob(),
+                amount: 250,
+                height: block.index,
+            },
+            AppEvent::BlockCommitted {
+                height: block.index,
+                hash: block.hash.clone(),
+                transactions: 1,
+                operations: 1,
+            },
+        ]
+    );
+
+    // O canal de broadcast recebe a mesma sequência
+    for expected in events {
+        assert_eq!(receiver.try_recv().unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_unsubscribed_callback_stops_receiving() {
+    let bus = EventBus::new();
+    let count = Arc::new(Mutex::new(0));
+    let counter = Arc::clone(&count);
+    let id = bus.on(move |_| *counter.lock().unwrap() += 1);
+
+    let event = AppEvent::ValidatorBanned {
+        validator_id: "v1".to_string(),
+        duration_secs: 60,
+    };
+    bus.emit(event.clone());
+    assert!(bus.unsubscribe(id));
+    assert!(!bus.unsubscribe(id));
+    bus.emit(event);
+
+    assert_eq!(*count.lock().unwrap(), 1);
+    assert_eq!(bus.subscriber_count(), 0);
+}
+
+#[test]
+fn test_reputation_ban_published() {
+    let bus = EventBus::new();
+    let mut receiver = bus.subscribe();
+
+    let mut reputation = ReputationSystem::new();
+    reputation.set_event_bus(bus.clone());
+    reputation.add_validator("v1".to_string());
+    reputation.set_reputation("v1", 16.0).unwrap();
+    reputation
+        .update_reputation("v1", ReputationAction::DoubleVote)
+        .unwrap();
+
+    match receiver.try_recv().unwrap() {
+        AppEvent::ValidatorBanned { validator_id, .. } => assert_eq!(validator_id, "v1"),
+        other => panic!("evento inesperado: {:?}", other),
+    }
+}
@@ -0,0 +1,191 @@
+use kybelith::blockchain::{Block, BlockHeader, Blockchain};
+use kybelith::network::NodeIdentity;
+use kybelith::test_utils::fixtures::{address_of, balance, bob, fund, temp_path, transfer};
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+use std::sync::Arc;
+
+fn blockchain_with_block(identity: &Arc<NodeIdentity>) -> Blockchain {
+    let keys = keypair();
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain.set_signer(Arc::clone(identity));
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(address_of(&keys), 100);
+
+    // A segunda transferência é descartada por falta de saldo
+    blockchain
+        .submit_transaction(transfer(&keys, 40, 1))
+        .unwrap();
+    blockchain
+        .submit_transaction(transfer(&keys, 500, 2))
+        .unwrap();
+    blockchain.produce_block(10).unwrap().unwrap();
+    blockchain
+}
+
+#[test]
+fn test_block_header_commits_execution_summary() {
+    let identity = Arc::new(NodeIdentity::generate());
+    let blockchain = blockchain_with_block(&identity);
+    let block = blockchain.latest_block().unwrap();
+
+    let summary = block.execution.clone().unwrap();
+    assert_eq!(
+        (summary.transactions, summary.operations, summary.discarded),
+        (1, 0, 1)
+    );
+    assert_eq!(summary.total_fees, Blockchain::transfer_fee(40));
+    // Só o peso do item aplicado entra no resumo
+    let receipts = blockchain.execution_receipts(0).unwrap();
+    assert!(receipts.iter().all(|receipt| receipt.gas_used > 0));
+    assert_eq!(summary.gas_used, receipts[0].gas_used);
+    // A taxa do recibo é a que saiu do saldo do remetente; a descartada não paga
+    assert_eq!(receipts[0].fee, summary.total_fees);
+    assert_eq!(
+        balance(&blockchain, 0, &receipts[0].sender),
+        100 - 40 - receipts[0].fee
+    );
+    assert_eq!(receipts[1].fee, 0);
+
+    // A assinatura do proponente cobre o resumo
+    let public_key = identity.public_key.as_bytes();
+    assert!(block.verify_signature(public_key).is_ok());
+    let mut tampered = block.clone();
+    tampered.execution.as_mut().unwrap().total_fees = 0;
+    assert!(tampered.verify_signature(public_key).is_err());
+    assert_ne!(
+        BlockHeader::from_block(&tampered).leaf(),
+        BlockHeader::from_block(block).leaf()
+    );
+}
+
+#[test]
+fn test_block_body_carries_processed_transfers() {
+    let identity = Arc::new(NodeIdentity::generate());
+    let blockchain = blockchain_with_block(&identity);
+    let block = blockchain.latest_block().unwrap();
+
+    // Aplicada e descartada entram no corpo, na ordem dos recibos
+    let receipts = blockchain.execution_receipts(0).unwrap();
+    let txids: Vec<_> = block.transfers.iter().map(|tx| Some(tx.txid())).collect();
+    let receipt_txids: Vec<_> = receipts
+        .iter()
+        .map(|receipt| receipt.txid.clone())
+        .collect();
+    assert_eq!(txids, receipt_txids);
+    assert_eq!(block.transfers.len(), 2);
+
+    let public_key = identity.public_key.as_bytes();
+    let mut tampered = block.clone();
+    tampered.transfers[0].amount = 1;
+    assert!(tampered.verify_signature(public_key).is_err());
+    let mut tampered = block.clone();
+    tampered.transfers.pop();
+    assert!(tampered.verify_signature(public_key).is_err());
+
+    let decoded = Block::from_bytes(&block.to_bytes().unwrap()).unwrap();
+    assert_eq!(decoded.transfers.len(), 2);
+    assert!(decoded.verify_signature(public_key).is_ok());
+
+    let path = temp_path("block-body.db");
+    blockchain.save_to_db(&path.to_string_lossy()).unwrap();
+    let restored = Blockchain::load_from_db(&path.to_string_lossy()).unwrap();
+    let restored = restored.latest_block().unwrap();
+    assert_eq!(restored.transfers[1].txid(), block.transfers[1].txid());
+    assert!(restored.verify_signature(public_key).is_ok());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_imported_block_summary_must_match_body() {
+    let identity = Arc::new(NodeIdentity::generate());
+    let blockchain = blockchain_with_block(&identity);
+    let block = blockchain.latest_block().unwrap().clone();
+
+    // O importador reexecuta o corpo: precisa do mesmo saldo do remetente
+    let sender = blockchain.execution_receipts(0).unwrap()[0].sender.clone();
+    let mut importer = Blockchain::new().unwrap();
+    importer.set_signer(Arc::clone(&identity));
+    fund(&mut importer, &sender, 100);
+    importer.validate_new_block(&block).unwrap();
+
+    // Mesmo reassinado pelo proponente, um resumo que não bate com o corpo é recusado
+    let mut inflated = block.clone();
+    inflated.execution.as_mut().unwrap().discarded += 1;
+    inflated.sign(&identity);
+    assert!(importer.validate_new_block(&inflated).is_err());
+
+    let mut overcharged = block;
+    overcharged.execution.as_mut().unwrap().total_fees = u64::MAX;
+    overcharged.sign(&identity);
+    assert!(importer.validate_new_block(&overcharged).is_err());
+}
+
+#[test]
+fn test_imported_block_executes_body_like_its_producer() {
+    let identity = Arc::new(NodeIdentity::generate());
+    let keys = keypair();
+    let sender = address_of(&keys);
+    let node = || {
+        let mut blockchain = Blockchain::new().unwrap();
+        blockchain.set_signer(Arc::clone(&identity));
+        blockchain
+            .tokens
+            .get_mut("0")
+            .unwrap()
+            .balances
+            .insert(sender.clone(), 100);
+        blockchain
+    };
+
+    let mut producer = node();
+    producer.submit_transaction(transfer(&keys, 40, 1)).unwrap();
+    producer
+        .submit_transaction(transfer(&keys, 500, 2))
+        .unwrap();
+    let block = producer.produce_block(10).unwrap().unwrap();
+
+    // O importador nunca viu as transferências: elas chegam só no corpo do bloco
+    let mut importer = node();
+    importer.add_block(block).unwrap();
+
+    for address in [&sender, &bob()] {
+        assert_eq!(
+            balance(&importer, 0, address),
+            balance(&producer, 0, address)
+        );
+    }
+    assert_eq!(importer.nonces.get(&sender), Some(&2));
+    assert_eq!(
+        importer.execution_receipts(0),
+        producer.execution_receipts(0)
+    );
+}
+
+#[test]
+fn test_receipt_proof_verifies_against_header() {
+    let identity = Arc::new(NodeIdentity::generate());
+    let blockchain = blockchain_with_block(&identity);
+    let header = BlockHeader::from_block(blockchain.latest_block().unwrap());
+    let summary = header.execution.clone().unwrap();
+
+    let (applied, path) = blockchain.receipt_proof(0, 0).unwrap();
+    assert!(applied.applied());
+    assert_eq!(applied.fee, Blockchain::transfer_fee(40));
+    assert!(summary.verify_receipt(&applied, &path));
+
+    let (discarded, path) = blockchain.receipt_proof(0, 1).unwrap();
+    assert_eq!((discarded.nonce, discarded.fee), (2, 0));
+    assert!(discarded.error_code.is_some());
+    assert!(summary.verify_receipt(&discarded, &path));
+
+    // Um recibo alterado não confere com a raiz do cabeçalho
+    let mut forged = discarded.clone();
+    forged.error_code = None;
+    assert!(!summary.verify_receipt(&forged, &path));
+    assert!(blockchain.receipt_proof(0, 2).is_err());
+}
@@ -0,0 +1,96 @@
+use kybelith::blockchain::{Blockchain, ExportSignature};
+use kybelith::error::Error;
+use kybelith::network::NodeIdentity;
+use kybelith::test_utils::fixtures::temp_path;
+use kybelith::utils::compression::Codec;
+use pqcrypto_traits::sign::PublicKey as _;
+use std::path::PathBuf;
+
+fn cleanup(path: &PathBuf) {
+    let _ = std::fs::remove_file(path);
+    let _ = std::fs::remove_file(ExportSignature::path_for(path));
+}
+
+#[test]
+fn test_signed_export_round_trip() {
+    let identity = NodeIdentity::generate();
+    let blockchain = Blockchain::new().unwrap();
+    let path = temp_path("round-trip.json");
+    let filename = path.to_string_lossy().into_owned();
+
+    let signature = blockchain.save_signed(&filename, &identity).unwrap();
+    assert_eq!(signature.height, 0);
+    assert_eq!(ExportSignature::read_for(&path).unwrap(), Some(signature));
+
+    let restored = Blockchain::load_signed(&filename, &identity.public_key).unwrap();
+    assert_eq!(restored.tokens.len(), blockchain.tokens.len());
+    assert!(Blockchain::load_from_file(&filename).is_ok());
+
+    cleanup(&path);
+}
+
+#[test]
+fn test_tampered_export_rejected() {
+    let identity = NodeIdentity::generate();
+    let blockchain = Blockchain::new().unwrap();
+    let path = temp_path("tampered.json");
+    let filename = path.to_string_lossy().into_owned();
+    blockchain.save_signed(&filename, &identity).unwrap();
+
+    let original = std::fs::read_to_string(&path).unwrap();
+    let tampered = original.replace("\"next_token_id\":1", "\"next_token_id\":9");
+    assert_ne!(tampered, original);
+    std::fs::write(&path, tampered).unwrap();
+
+    assert!(matches!(
+        Blockchain::load_from_file(&filename),
+        Err(Error::InvalidFormat(_))
+    ));
+    assert!(Blockchain::load_signed(&filename, &identity.public_key).is_err());
+
+    cleanup(&path);
+}
+
+#[test]
+fn test_untrusted_or_missing_signature_rejected() {
+    let identity = NodeIdentity::generate();
+    let other = NodeIdentity::generate();
+    let blockchain = Blockchain::new().unwrap();
+    let path = temp_path("untrusted.snapshot");
+    let filename = path.to_string_lossy().into_owned();
+
+    blockchain
+        .save_snapshot_signed(&filename, Codec::Zstd, &identity)
+        .unwrap();
+    assert!(Blockchain::load_snapshot_signed(&filename, &identity.public_key).is_ok());
+    assert!(matches!(
+        Blockchain::load_snapshot_signed(&filename, &other.public_key),
+        Err(Error::Unauthorized(_))
+    ));
+
+    // Uma gravação sem assinatura descarta a assinatura anterior
+    blockchain.save_snapshot(&filename, Codec::Zstd).unwrap();
+    assert!(!ExportSignature::path_for(&path).exists());
+    assert!(Blockchain::load_snapshot(&filename).is_ok());
+    assert!(matches!(
+        Blockchain::load_snapshot_signed(&filename, &identity.public_key),
+        Err(Error::Unauthorized(_))
+    ));
+
+    cleanup(&path);
+}
+
+#[test]
+fn test_node_identity_persisted() {
+    let path = temp_path("identity.key");
+    let _ = std::fs::remove_file(&path);
+
+    let first = NodeIdentity::load_or_generate(&path).unwrap();
+    let second = NodeIdentity::load_or_generate(&path).unwrap();
+    assert_eq!(first.public_key.as_bytes(), second.public_key.as_bytes());
+
+    std::fs::write(&path, b"curto").unwrap();
+    assert!(NodeIdentity::load_or_generate(&path).is_err());
+
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,192 @@
+#![cfg(feature = "ffi")]
+
+use kybelith::ffi::{
+    qst_buffer_free, qst_derive_address, qst_generate_keypair, qst_sign_transaction,
+    qst_verify_transaction, QstBuffer, QstStatus, QstTransaction, QST_ADDRESS_BUFFER_LEN,
+};
+use kybelith::transaction::Transaction;
+use kybelith::utils::address::derive_address;
+use pqcrypto_dilithium::dilithium5::{PublicKey, SecretKey};
+use pqcrypto_traits::sign::{PublicKey as _, SecretKey as _};
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+fn empty() -> QstBuffer {
+    QstBuffer {
+        data: ptr::null_mut(),
+        len: 0,
+    }
+}
+
+fn as_slice(buffer: &QstBuffer) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(buffer.data, buffer.len) }
+}
+
+#[test]
+fn test_sign_and_verify_through_c_api() {
+    let (mut public_key, mut secret_key) = (empty(), empty());
+    unsafe {
+        assert_eq!(
+            qst_generate_keypair(&mut public_key, &mut secret_key),
+            QstStatus::Ok
+        );
+    }
+
+    let from = CString::new("a".repeat(40)).unwrap();
+    let to = CString::new("b".repeat(40)).unwrap();
+    let mut tx = QstTransaction {
+        token_id: 3,
+        from: from.as_ptr(),
+        to: to.as_ptr(),
+        amount: 250,
+        timestamp: 1_700_000_000,
+        nonce: 7,
+        public_key: public_key.data,
+        public_key_len: public_key.len,
+    };
+
+    let mut signature = empty();
+    unsafe {
+        assert_eq!(
+            qst_sign_transaction(&tx, secret_key.data, secret_key.len, &mut signature),
+            QstStatus::Ok
+        );
+        assert_eq!(
+            qst_verify_transaction(
+                &tx,
+                public_key.data,
+                public_key.len,
+                signature.data,
+                signature.len
+            ),
+            QstStatus::Ok
+        );
+
+        // O token faz parte dos dados assinados, assim como o valor
+        tx.token_id = 4;
+        assert_eq!(
+            qst_verify_transaction(
+                &tx,
+                public_key.data,
+                public_key.len,
+                signature.data,
+                signature.len
+            ),
+            QstStatus::InvalidSignature
+        );
+        tx.token_id = 3;
+        tx.amount = 251;
+        assert_eq!(
+            qst_verify_transaction(
+                &tx,
+                public_key.data,
+                public_key.len,
+                signature.data,
+                signature.len
+            ),
+            QstStatus::InvalidSignature
+        );
+
+        qst_buffer_free(signature);
+        qst_buffer_free(public_key);
+        qst_buffer_free(secret_key);
+    }
+}
+
+#[test]
+fn test_c_signature_matches_transaction_format() {
+    let (mut public_key, mut secret_key) = (empty(), empty());
+    unsafe {
+        qst_generate_keypair(&mut public_key, &mut secret_key);
+    }
+    let pk = PublicKey::from_bytes(as_slice(&public_key)).unwrap();
+    let sk = SecretKey::from_bytes(as_slice(&secret_key)).unwrap();
+
+    // Uma transação assinada em Rust verifica pela API C
+    let mut transaction = Transaction::with_timestamp(
+        derive_address(pk.as_bytes()),
+        "b".repeat(40),
+        42,
+        pk.as_bytes().to_vec(),
+        1_700_000_000,
+    )
+    .unwrap();
+    transaction.token_id = 5;
+    transaction.nonce = 3;
+    transaction.sign(&sk).unwrap();
+
+    let from = CString::new(transaction.from.clone()).unwrap();
+    let to = CString::new(transaction.to.clone()).unwrap();
+    let tx = QstTransaction {
+        token_id: transaction.token_id,
+        from: from.as_ptr(),
+        to: to.as_ptr(),
+        amount: transaction.amount,
+        timestamp: transaction.timestamp,
+        nonce: transaction.nonce,
+        public_key: transaction.public_key.as_ptr(),
+        public_key_len: transaction.public_key.len(),
+    };
+
+    let mut signature = empty();
+    unsafe {
+        assert_eq!(
+            qst_verify_transaction(
+                &tx,
+                public_key.data,
+                public_key.len,
+                transaction.signature.as_ptr(),
+                transaction.signature.len()
+            ),
+            QstStatus::Ok
+        );
+
+        // E a assinatura feita pela API C verifica em Rust
+        assert_eq!(
+            qst_sign_transaction(&tx, secret_key.data, secret_key.len, &mut signature),
+            QstStatus::Ok
+        );
+        transaction.signature = as_slice(&signature).to_vec();
+        assert!(transaction.verify(&pk).is_ok());
+
+        qst_buffer_free(signature);
+        qst_buffer_free(public_key);
+        qst_buffer_free(secret_key);
+    }
+}
+
+#[test]
+fn test_derive_address_and_argument_errors() {
+    let (mut public_key, mut secret_key) = (empty(), empty());
+    unsafe {
+        qst_generate_keypair(&mut public_key, &mut secret_key);
+    }
+
+    let mut out = [0 as c_char; QST_ADDRESS_BUFFER_LEN];
+    unsafe {
+        assert_eq!(
+            qst_derive_address(public_key.data, public_key.len, out.as_mut_ptr(), out.len()),
+            QstStatus::Ok
+        );
+        let address = CStr::from_ptr(out.as_ptr()).to_str().unwrap();
+        assert_eq!(address, derive_address(as_slice(&public_key)));
+        assert_eq!(address.len(), 40);
+
+        assert_eq!(
+            qst_derive_address(public_key.data, public_key.len, out.as_mut_ptr(), 40),
+            QstStatus::BufferTooSmall
+        );
+        assert_eq!(
+            qst_derive_address(public_key.data, 10, out.as_mut_ptr(), out.len()),
+            QstStatus::InvalidKey
+        );
+        assert_eq!(
+            qst_sign_transaction(ptr::null(), secret_key.data, secret_key.len, &mut empty()),
+            QstStatus::NullPointer
+        );
+
+        qst_buffer_free(public_key);
+        qst_buffer_free(secret_key);
+        qst_buffer_free(empty());
+    }
+}
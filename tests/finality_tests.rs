@@ -0,0 +1,86 @@
+use kybelith::blockchain::{
+    Blockchain, CheckpointAttestation, Finality, SharedBlockchain, TransactionRecord,
+};
+use kybelith::rpc::{BlockResponse, RpcService};
+use kybelith::test_utils::fixtures::{address_of, transfer};
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+use serde_json::json;
+
+#[test]
+fn test_transaction_finality_follows_depth_and_checkpoint() {
+    let keys = keypair();
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain.confirmation_depth = 2;
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(address_of(&keys), 1000);
+
+    let tx = transfer(&keys, 10, 1);
+    let txid = tx.txid();
+    blockchain.submit_transaction(tx).unwrap();
+    let finality = |blockchain: &Blockchain| blockchain.transaction_record(&txid).unwrap().finality;
+    assert_eq!(finality(&blockchain), Finality::Pending);
+
+    blockchain.produce_block(10).unwrap().unwrap();
+    assert_eq!(finality(&blockchain), Finality::Included);
+    for nonce in 2..=3 {
+        blockchain
+            .submit_transaction(transfer(&keys, 10, nonce))
+            .unwrap();
+        blockchain.produce_block(10).unwrap().unwrap();
+    }
+    assert_eq!(finality(&blockchain), Finality::Safe);
+    assert_eq!(blockchain.finality_at(2), Some(Finality::Included));
+    assert_eq!(
+        blockchain.history(&address_of(&keys), 0, 10)[0].finality,
+        Finality::Included
+    );
+
+    let validator = keypair();
+    blockchain.register_validator_key(validator.0.as_bytes().to_vec());
+    let attestation =
+        CheckpointAttestation::create(&blockchain, 0, &validator.0, &validator.1).unwrap();
+    blockchain
+        .record_checkpoint(attestation, &validator.0)
+        .unwrap();
+    assert_eq!(finality(&blockchain), Finality::Finalized);
+    assert_eq!(blockchain.finality_at(1), Some(Finality::Included));
+    assert_eq!(blockchain.finality_at(3), None);
+}
+
+#[test]
+fn test_rpc_queries_report_finality() {
+    let keys = keypair();
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(address_of(&keys), 1000);
+    let tx = transfer(&keys, 10, 1);
+    let txid = tx.txid();
+    blockchain.submit_transaction(tx).unwrap();
+    blockchain.produce_block(10).unwrap().unwrap();
+
+    let service = RpcService::new(SharedBlockchain::new(blockchain));
+    let block: BlockResponse =
+        serde_json::from_value(service.handle("get_block", json!({ "height": 0 })).unwrap())
+            .unwrap();
+    assert_eq!(block.block.index, 0);
+    assert_eq!(block.finality, Finality::Included);
+
+    let record = service
+        .handle("get_transaction", json!({ "txid": txid }))
+        .unwrap();
+    assert_eq!(record["finality"], "included");
+    let record: TransactionRecord = serde_json::from_value(record).unwrap();
+    assert_eq!(record.txid, txid);
+    assert!(service
+        .handle("get_transaction", json!({ "txid": "0".repeat(64) }))
+        .is_err());
+}
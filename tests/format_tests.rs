@@ -0,0 +1,181 @@
+use kybelith::blockchain::{Block, Blockchain, BLOCKCHAIN_FORMAT_VERSION, BLOCK_ENCODING_VERSION};
+use kybelith::error::{Error, TransactionError};
+use kybelith::transaction::{Transaction, TRANSACTION_ENCODING_VERSION};
+use serde_json::Value;
+
+fn temp_file(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!(
+        "kybelith-format-{}-{}.json",
+        std::process::id(),
+        name
+    ))
+}
+
+/// Documento no formato 0: sem `format_version`, com `blocks` e `transactions`
+fn legacy_document(blockchain: &Blockchain, blocks_only: bool) -> Value {
+    let mut value = serde_json::to_value(blockchain).unwrap();
+    let state = value.as_object_mut().unwrap();
+    state.remove("format_version");
+    let chain = state.get("chain").cloned().unwrap();
+    state.insert("blocks".to_string(), chain);
+    if blocks_only {
+        state.insert("chain".to_string(), Value::Array(Vec::new()));
+    }
+    let committed = state.remove("committed_transactions").unwrap();
+    state.insert("transactions".to_string(), committed);
+    value
+}
+
+fn chain_with_blocks(count: u64) -> Blockchain {
+    let mut blockchain = Blockchain::new().unwrap();
+    for index in 0..count {
+        let previous = blockchain
+            .latest_block()
+            .map(|block| block.hash.clone())
+            .unwrap_or_else(|| "0".repeat(64));
+        let block = Block::new(index, Vec::new(), Vec::new(), previous).unwrap();
+        blockchain.add_block(block).unwrap();
+    }
+    blockchain
+}
+
+#[test]
+fn test_saved_file_carries_current_version() {
+    let blockchain = chain_with_blocks(2);
+    let value = serde_json::to_value(&blockchain).unwrap();
+
+    assert_eq!(value["format_version"], BLOCKCHAIN_FORMAT_VERSION);
+    assert!(value.get("blocks").is_none());
+
+    let path = temp_file("current");
+    blockchain.save_to_file(&path.to_string_lossy()).unwrap();
+    let restored = Blockchain::load_from_file(&path.to_string_lossy()).unwrap();
+    assert_eq!(restored.height(), 2);
+    assert_eq!(restored.format_version(), BLOCKCHAIN_FORMAT_VERSION);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_legacy_document_migrated() {
+    let blockchain = chain_with_blocks(3);
+
+    let legacy = legacy_document(&blockchain, false);
+    let restored = Blockchain::from_json(legacy.to_string().as_bytes()).unwrap();
+    assert_eq!(restored.format_version(), BLOCKCHAIN_FORMAT_VERSION);
+    let hashes = |b: &Blockchain| {
+        b.blocks()
+            .iter()
+            .map(|block| block.hash.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(hashes(&restored), hashes(&blockchain));
+
+    // Arquivos em que só `blocks` foi preenchida
+    let legacy = legacy_document(&blockchain, true);
+    let restored = Blockchain::from_json(legacy.to_string().as_bytes()).unwrap();
+    assert_eq!(restored.height(), 3);
+    assert!(restored.is_chain_valid().unwrap());
+}
+
+#[test]
+fn test_newer_format_rejected_without_overwrite() {
+    let blockchain = chain_with_blocks(1);
+    let mut value = serde_json::to_value(&blockchain).unwrap();
+    value["format_version"] = Value::from(BLOCKCHAIN_FORMAT_VERSION + 1);
+
+    let path = temp_file("newer");
+    std::fs::write(&path, value.to_string()).unwrap();
+    assert!(matches!(
+        Blockchain::load_from_file(&path.to_string_lossy()),
+        Err(Error::InvalidFormat(_))
+    ));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), value.to_string());
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_newer_database_rejected() {
+    let blockchain = chain_with_blocks(1);
+    let path = temp_file("newer-db");
+    let _ = std::fs::remove_file(&path);
+    let db_path = path.to_string_lossy().to_string();
+    blockchain.save_to_db(&db_path).unwrap();
+    assert_eq!(
+        Blockchain::load_from_db(&db_path).unwrap().format_version(),
+        BLOCKCHAIN_FORMAT_VERSION
+    );
+
+    rusqlite::Connection::open(&path)
+        .unwrap()
+        .execute(
+            "UPDATE chain_state SET value = ?1 WHERE key = 'format_version'",
+            [(BLOCKCHAIN_FORMAT_VERSION + 1).to_string()],
+        )
+        .unwrap();
+    assert!(Blockchain::load_from_db(&db_path).is_err());
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_block_encoding_versions() {
+    let block = Block::new(4, Vec::new(), Vec::new(), "0".repeat(64)).unwrap();
+    let bytes = block.to_bytes().unwrap();
+    assert_eq!(&bytes[..3], b"KBK");
+    assert_eq!(bytes[3], BLOCK_ENCODING_VERSION);
+    assert_eq!(Block::from_bytes(&bytes).unwrap().hash, block.hash);
+
+    // Versão 2: sem operações e transferências, os dois últimos campos (vetores
+    // vazios ocupam só os 8 bytes do tamanho)
+    let mut legacy = bincode::serialize(&block).unwrap();
+    legacy.truncate(legacy.len() - 16);
+    let mut tagged = b"KBK\x02".to_vec();
+    tagged.extend_from_slice(&legacy);
+    let decoded = Block::from_bytes(&tagged).unwrap();
+    assert_eq!(decoded.hash, block.hash);
+    assert!(decoded.operations.is_empty() && decoded.transfers.is_empty());
+
+    // Versões 0 e 1: também sem o resumo de execução, que passa a ser o último
+    // campo (um `None` ocupa só o byte da tag)
+    assert_eq!(legacy.pop(), Some(0));
+    let decoded = Block::from_bytes(&legacy).unwrap();
+    assert_eq!(decoded.hash, block.hash);
+    assert!(decoded.execution.is_none());
+
+    let mut tagged = b"KBK\x01".to_vec();
+    tagged.extend_from_slice(&legacy);
+    assert_eq!(Block::from_bytes(&tagged).unwrap().hash, block.hash);
+
+    let mut newer = bytes.clone();
+    newer[3] = BLOCK_ENCODING_VERSION + 1;
+    assert!(matches!(
+        Block::from_bytes(&newer),
+        Err(Error::InvalidFormat(_))
+    ));
+}
+
+#[test]
+fn test_transaction_encoding_versions() {
+    let tx = Transaction::new("a".repeat(40), "b".repeat(40), 10, vec![1; 32]).unwrap();
+    let bytes = tx.to_versioned_bytes().unwrap();
+    assert_eq!(bytes[3], TRANSACTION_ENCODING_VERSION);
+    assert_eq!(
+        Transaction::from_versioned_bytes(&bytes).unwrap().hash,
+        tx.hash
+    );
+
+    let legacy = bincode::serialize(&tx).unwrap();
+    assert_eq!(
+        Transaction::from_versioned_bytes(&legacy).unwrap().hash,
+        tx.hash
+    );
+
+    let mut newer = bytes.clone();
+    newer[3] = TRANSACTION_ENCODING_VERSION + 1;
+    assert!(matches!(
+        Transaction::from_versioned_bytes(&newer),
+        Err(TransactionError::InvalidFormat(_))
+    ));
+}
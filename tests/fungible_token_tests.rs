@@ -0,0 +1,38 @@
+use kybelith::error::TransactionError;
+use kybelith::token::custom_token::CustomToken;
+use kybelith::token::{FungibleToken, Token};
+
+const OWNER: &str = "Endereço1";
+const HOLDER: &str = "Endereço2";
+
+/// Mesmo roteiro para qualquer implementação: o dono distribui, o detentor
+/// repassa parte de volta e ninguém gasta além do saldo
+fn exercise(token: &mut dyn FungibleToken) {
+    assert_eq!(token.metadata().owner, OWNER);
+    assert_eq!(token.balance_of(OWNER), 1_000);
+
+    token.transfer(OWNER, HOLDER, 300).unwrap();
+    token.transfer(HOLDER, OWNER, 100).unwrap();
+    assert_eq!(token.balance_of(OWNER), 800);
+    assert_eq!(token.balance_of(HOLDER), 200);
+
+    assert!(matches!(
+        token.transfer(HOLDER, OWNER, 201),
+        Err(TransactionError::InsufficientFunds)
+    ));
+    assert_eq!(token.balance_of(HOLDER), 200);
+    assert_eq!(token.total_supply(), 1_000);
+}
+
+#[test]
+fn test_token_and_custom_token_share_interface() {
+    let mut token = Token::new("MeuToken".into(), "MTK".into(), 1_000, OWNER.into()).unwrap();
+    exercise(&mut token);
+
+    let mut custom =
+        CustomToken::new(1, "MeuToken".into(), "MTK".into(), 1_000, OWNER.into()).unwrap();
+    exercise(&mut custom);
+    assert_eq!(custom.metadata().id, 1);
+    // O dono gastou primeiro a parte não distribuída
+    assert_eq!(custom.supply, 700);
+}
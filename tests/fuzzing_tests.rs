@@ -0,0 +1,107 @@
+use kybelith::blockchain::Block;
+use kybelith::consensus::BlockProposal;
+use kybelith::transaction::Transaction;
+use kybelith::TransactionError;
+
+#[test]
+fn test_transaction_decode_rejects_garbage() {
+    let garbage = vec![0xFFu8; 64];
+    assert!(Transaction::deserialize_from_bytes(&garbage).is_err());
+    assert!(Transaction::deserialize_from_bytes(&[]).is_err());
+}
+
+#[test]
+fn test_transaction_decode_rejects_oversized_input() {
+    let oversized = vec![0u8; 128 * 1024 + 1];
+    assert!(matches!(
+        Transaction::deserialize_from_bytes(&oversized),
+        Err(TransactionError::DataSizeExceeded)
+    ));
+}
+
+#[test]
+fn test_transaction_decode_roundtrip() {
+    let tx = Transaction::new("a".repeat(32), "b".repeat(32), 100, vec![1, 2, 3])
+        .expect("transação válida");
+    let bytes = bincode::serialize(&tx).unwrap();
+
+    let decoded = Transaction::deserialize_from_bytes(&bytes).expect("deve decodificar");
+    assert_eq!(decoded.from, tx.from);
+    assert_eq!(decoded.amount, tx.amount);
+    assert_eq!(decoded.transaction_hash, tx.transaction_hash);
+}
+
+#[test]
+fn test_block_decode_roundtrip_and_garbage() {
+    let block = Block::new(1, Vec::new(), Vec::new(), "0".repeat(64)).unwrap();
+    let bytes = block.to_bytes().unwrap();
+
+    let decoded = Block::from_bytes(&bytes).expect("deve decodificar");
+    assert_eq!(decoded.hash, block.hash);
+    assert_eq!(decoded.index, 1);
+
+    // Prefixo de comprimento gigante não deve alocar nem entrar em pânico
+    let mut hostile = bytes.clone();
+    hostile[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(Block::from_bytes(&hostile).is_err());
+    assert!(Block::from_bytes(&bytes[..bytes.len() / 2]).is_err());
+}
+
+#[test]
+fn test_proposal_decode_roundtrip() {
+    let proposal = BlockProposal::new(
+        "hash".to_string(),
+        7,
+        "parent".to_string(),
+        "validator1".to_string(),
+        vec!["tx1".to_string()],
+        vec![1, 2, 3],
+        vec![9],
+    );
+    let bytes = proposal.to_bytes().unwrap();
+
+    let decoded = BlockProposal::from_bytes(&bytes).expect("deve decodificar");
+    assert_eq!(decoded.block_height, 7);
+    assert_eq!(decoded.proposer_id, "validator1");
+    assert_eq!(decoded.timestamp, proposal.timestamp);
+    assert!(BlockProposal::from_bytes(&[0xAB; 10]).is_err());
+}
+
+#[cfg(feature = "fuzzing")]
+mod entry_points {
+    use arbitrary::Unstructured;
+    use kybelith::fuzzing::*;
+
+    // Gera entradas pseudoaleatórias determinísticas para os geradores de corpus
+    fn seed_bytes(seed: u8, len: usize) -> Vec<u8> {
+        (0..len)
+            .map(|i| (i as u8).wrapping_mul(31).wrapping_add(seed))
+            .collect()
+    }
+
+    #[test]
+    fn test_entry_points_accept_generated_corpus() {
+        for seed in 0..16u8 {
+            let raw = seed_bytes(seed, 512);
+
+            let mut u = Unstructured::new(&raw);
+            if let Ok(bytes) = arbitrary_transaction_bytes(&mut u) {
+                fuzz_transaction_decode(&bytes);
+            }
+
+            let mut u = Unstructured::new(&raw);
+            if let Ok(bytes) = arbitrary_block_bytes(&mut u) {
+                fuzz_block_decode(&bytes);
+            }
+
+            let mut u = Unstructured::new(&raw);
+            if let Ok(bytes) = arbitrary_proposal_bytes(&mut u) {
+                fuzz_proposal_decode(&bytes);
+            }
+
+            fuzz_transaction_decode(&raw);
+            fuzz_block_decode(&raw);
+            fuzz_proposal_decode(&raw);
+        }
+    }
+}
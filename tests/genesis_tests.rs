@@ -0,0 +1,54 @@
+use kybelith::blockchain::Blockchain;
+use kybelith::config::{GenesisConfig, Settings};
+
+#[test]
+fn test_genesis_hash_is_canonical() {
+    let settings = Settings::default();
+    let hash = settings.genesis_hash();
+
+    // Mesma configuração escrita em outra ordem e formatação dá o mesmo hash
+    let json = serde_json::to_value(&settings.genesis).unwrap();
+    let reordered = format!(
+        r#"{{ "allocations": {}, "creator": "system", "token_symbol": "KYBL",
+            "initial_supply": 10000000, "token_name": "Kybelith", "timestamp": 0 }}"#,
+        json["allocations"]
+    );
+    let parsed: GenesisConfig = serde_json::from_str(&reordered).unwrap();
+    assert_eq!(parsed.hash(&settings.node.chain_id), hash);
+
+    let mut testnet = settings.clone();
+    testnet.node.chain_id = "kybelith-testnet".to_string();
+    assert_ne!(testnet.genesis_hash(), hash);
+
+    let mut other = settings.clone();
+    other.genesis.allocations.insert("b".repeat(40), 1);
+    assert_ne!(other.genesis_hash(), hash);
+}
+
+#[test]
+fn test_blockchain_from_genesis() {
+    let mut genesis = GenesisConfig {
+        token_symbol: "TKYB".to_string(),
+        initial_supply: 500,
+        ..GenesisConfig::default()
+    };
+    genesis.allocations.clear();
+    genesis.allocations.insert("a".repeat(40), 25);
+
+    let blockchain = Blockchain::from_genesis(&genesis).unwrap();
+    let token = &blockchain.tokens["0"];
+    assert_eq!(token.symbol, "TKYB");
+    assert_eq!(token.total_supply, 525);
+    assert_eq!(token.balances[&"a".repeat(40)], 25);
+    assert_eq!(token.balances["system"], 500);
+
+    // O padrão reproduz a cadeia de `Blockchain::new`
+    let default = Blockchain::from_genesis(&GenesisConfig::default()).unwrap();
+    assert_eq!(
+        default.tokens["0"].total_supply,
+        Blockchain::new().unwrap().tokens["0"].total_supply
+    );
+
+    genesis.allocations.insert("b".repeat(40), u64::MAX);
+    assert!(Blockchain::from_genesis(&genesis).is_err());
+}
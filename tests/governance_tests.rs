@@ -0,0 +1,112 @@
+use kybelith::blockchain::{Blockchain, ProposalStatus};
+use kybelith::test_utils::fixtures::{commit, Account};
+use kybelith::transaction::{Operation, OperationKind, VoteChoice};
+
+fn vote(account: &mut Account, proposal_id: u64, choice: VoteChoice) -> Operation {
+    account.operation(OperationKind::CastVote {
+        proposal_id,
+        choice,
+    })
+}
+
+/// Confirma um bloco qualquer: `produce_block` não gera blocos vazios
+fn commit_filler(blockchain: &mut Blockchain, account: &mut Account) {
+    let token_id = blockchain.next_token_id;
+    let create = account.operation(OperationKind::CreateToken {
+        token_id,
+        name: format!("Enchimento {}", token_id),
+        symbol: "FILL".to_string(),
+        total_supply: 1,
+    });
+    commit(blockchain, [create]);
+}
+
+fn propose(account: &mut Account, voting_blocks: u64) -> Operation {
+    account.operation(OperationKind::SubmitProposal {
+        description: "Reduzir a taxa mínima pela metade".to_string(),
+        voting_blocks,
+    })
+}
+
+#[test]
+fn test_votes_count_stake_at_snapshot_height() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut alice = Account::new();
+    let mut bob = Account::new();
+    let mut carol = Account::new();
+    blockchain.add_staker(alice.address.clone(), 600);
+    blockchain.add_staker(bob.address.clone(), 300);
+
+    // Sem bloco confirmado não há altura para o snapshot
+    assert!(blockchain.submit_operation(propose(&mut alice, 3)).is_err());
+    alice.nonce -= 1;
+    commit_filler(&mut blockchain, &mut carol);
+    assert!(blockchain.submit_operation(propose(&mut carol, 3)).is_err());
+    carol.nonce -= 1;
+
+    commit(&mut blockchain, [propose(&mut alice, 3)]);
+    let proposal = blockchain.proposal(0).unwrap();
+    assert_eq!(proposal.snapshot_height, 0);
+    assert_eq!(proposal.voting_ends_at, 4);
+
+    // Stake vinculado ou desvinculado depois do snapshot não muda o poder de voto
+    blockchain.add_staker(carol.address.clone(), 5_000);
+    blockchain.unbond_stake(&bob.address, 300).unwrap();
+    assert!(blockchain.unbond_stake(&bob.address, 1).is_err());
+    assert!(blockchain
+        .submit_operation(vote(&mut carol, 0, VoteChoice::No))
+        .is_err());
+    carol.nonce -= 1;
+
+    commit(&mut blockchain, [vote(&mut bob, 0, VoteChoice::No)]);
+    commit(&mut blockchain, [vote(&mut alice, 0, VoteChoice::Yes)]);
+    let tally = blockchain.proposal_tally(0).unwrap();
+    assert_eq!((tally.yes, tally.no, tally.abstain), (600, 300, 0));
+    assert_eq!(tally.eligible, 900);
+    assert_eq!(tally.status, ProposalStatus::Voting);
+
+    // Um novo voto substitui o anterior
+    commit(&mut blockchain, [vote(&mut bob, 0, VoteChoice::Abstain)]);
+    commit_filler(&mut blockchain, &mut carol);
+    let tally = blockchain.proposal_tally(0).unwrap();
+    assert_eq!((tally.yes, tally.no, tally.abstain), (600, 0, 300));
+    assert_eq!(tally.status, ProposalStatus::Passed);
+    assert!(blockchain
+        .submit_operation(vote(&mut alice, 0, VoteChoice::No))
+        .is_err());
+
+    let snapshot = blockchain.stake_snapshot(0).unwrap();
+    assert_eq!(snapshot.balance_of(&bob.address), 300);
+    let current = blockchain.stake_snapshot(blockchain.height() - 1).unwrap();
+    assert_eq!(current.balance_of(&bob.address), 0);
+    assert_eq!(current.balance_of(&carol.address), 5_000);
+    assert!(blockchain.stake_snapshot(blockchain.height()).is_err());
+}
+
+#[test]
+fn test_proposal_without_quorum_is_rejected_and_persisted() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut alice = Account::new();
+    let mut bob = Account::new();
+    blockchain.add_staker(alice.address.clone(), 100);
+    blockchain.add_staker(bob.address.clone(), 900);
+    commit_filler(&mut blockchain, &mut bob);
+
+    commit(&mut blockchain, [propose(&mut alice, 1)]);
+    commit(&mut blockchain, [vote(&mut alice, 0, VoteChoice::Yes)]);
+    let tally = blockchain.proposal_tally(0).unwrap();
+    assert_eq!(tally.turnout(), 100);
+    assert!(!tally.quorum_reached());
+    assert_eq!(tally.status, ProposalStatus::Rejected);
+
+    let path = std::env::temp_dir().join(format!(
+        "kybelith-governance-{}-quorum.db",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    blockchain.save_to_db(path.to_str().unwrap()).unwrap();
+    let restored = Blockchain::load_from_db(path.to_str().unwrap()).unwrap();
+    assert_eq!(restored.proposals, blockchain.proposals);
+    assert_eq!(restored.proposal_tally(0).unwrap(), tally);
+    let _ = std::fs::remove_file(&path);
+}
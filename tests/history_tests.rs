@@ -0,0 +1,115 @@
+use kybelith::blockchain::{Blockchain, TransactionStatus};
+use kybelith::error::Error;
+use kybelith::test_utils::fixtures::{alice, bob, carol};
+use kybelith::transaction::Transaction;
+use kybelith::{Database, KeyManager, QuantumBlockchainApp};
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+
+fn app(name: &str) -> (QuantumBlockchainApp, std::path::PathBuf) {
+    let dir =
+        std::env::temp_dir().join(format!("kybelith-history-{}-{}", std::process::id(), name));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut app = QuantumBlockchainApp {
+        blockchain: Blockchain::new().unwrap(),
+        key_manager: KeyManager::new().unwrap(),
+        database: Database::new(&dir.join("history.db").to_string_lossy()).unwrap(),
+    };
+    app.blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(alice(), 10_000);
+    (app, dir)
+}
+
+#[test]
+fn test_transaction_lookup_pending_then_committed() {
+    let (mut app, dir) = app("lookup");
+    let keys = keypair();
+    app.blockchain
+        .public_keys
+        .insert(alice(), keys.0.as_bytes().to_vec());
+
+    let token_id = app
+        .create_token(
+            "Prata".to_string(),
+            "PRT".to_string(),
+            1_000,
+            alice(),
+            &keys.1,
+        )
+        .unwrap();
+    app.blockchain.produce_block(10).unwrap();
+
+    let tx = app
+        .transfer_token(token_id, alice(), bob(), 300, &keys.1)
+        .unwrap();
+    let txid = tx.txid();
+
+    let pending = app.get_transaction(&txid).unwrap();
+    assert_eq!(pending.status, TransactionStatus::Pending);
+    assert_eq!(pending.amount, 300);
+
+    let block = app.blockchain.produce_block(10).unwrap().unwrap();
+    let committed = app.get_transaction(&txid).unwrap();
+    assert_eq!(
+        committed.status,
+        TransactionStatus::Committed {
+            height: block.index
+        }
+    );
+    assert_eq!(app.get_balance(&bob(), token_id).unwrap(), 300);
+    assert!(app.get_transaction(&"0".repeat(64)).is_none());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_history_newest_first_and_paginated() {
+    let (mut app, dir) = app("pages");
+    let keys = keypair();
+    app.blockchain
+        .public_keys
+        .insert(alice(), keys.0.as_bytes().to_vec());
+
+    for nonce in 1..=3 {
+        let mut tx =
+            Transaction::new(alice(), bob(), nonce * 10, keys.0.as_bytes().to_vec()).unwrap();
+        tx.nonce = nonce;
+        tx.sign(&keys.1).unwrap();
+        app.blockchain.submit_transaction(tx).unwrap();
+        app.blockchain.produce_block(10).unwrap();
+    }
+    let mut pending = Transaction::new(alice(), carol(), 5, keys.0.as_bytes().to_vec()).unwrap();
+    pending.nonce = 4;
+    pending.sign(&keys.1).unwrap();
+    app.blockchain.submit_transaction(pending).unwrap();
+
+    let history = app.get_history(&alice(), 0).unwrap();
+    let amounts: Vec<u64> = history.iter().map(|r| r.amount).collect();
+    assert_eq!(amounts, vec![5, 30, 20, 10]);
+    assert_eq!(history[0].status, TransactionStatus::Pending);
+
+    // Bob não participa da transação pendente
+    assert_eq!(app.get_history(&bob(), 0).unwrap().len(), 3);
+    assert!(app.get_history(&alice(), 1).unwrap().is_empty());
+    assert!(app.get_history("invalido", 0).is_err());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_balance_of_unknown_token() {
+    let (app, dir) = app("balance");
+
+    assert_eq!(app.get_balance(&alice(), 0).unwrap(), 10_000);
+    assert_eq!(app.get_balance(&carol(), 0).unwrap(), 0);
+    assert!(matches!(
+        app.get_balance(&alice(), 999),
+        Err(Error::TokenNotFound)
+    ));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,44 @@
+use kybelith::consensus::types::ConsensusError;
+use kybelith::error::{Error, ErrorCode, TransactionError};
+use kybelith::utils::i18n::{self, error_message_in, message_in, Locale};
+
+#[test]
+fn test_catalog_covers_both_locales() {
+    assert_eq!("pt_BR.UTF-8".parse::<Locale>(), Ok(Locale::PtBr));
+    assert_eq!("en-US".parse::<Locale>(), Ok(Locale::En));
+    assert!("fr".parse::<Locale>().is_err());
+    assert_eq!(serde_json::to_string(&Locale::PtBr).unwrap(), "\"pt-BR\"");
+
+    let code = Error::InsufficientBalance.code();
+    assert_eq!(error_message_in(code, Locale::PtBr), "Saldo insuficiente");
+    assert_eq!(error_message_in(code, Locale::En), "Insufficient balance");
+    assert_eq!(message_in("peer.banned", Locale::En), "Peer banned");
+    assert_eq!(
+        message_in("chave.inexistente", Locale::En),
+        "chave.inexistente"
+    );
+}
+
+#[test]
+fn test_display_follows_process_locale() {
+    let wrapped = Error::from(TransactionError::InvalidParameter("x".to_string()));
+    let votes = ConsensusError::InsufficientVotes {
+        required: 3,
+        received: 1,
+    };
+    assert_eq!(
+        wrapped.to_string(),
+        "Erro de transação: Parâmetro inválido: x"
+    );
+
+    i18n::set_locale(Locale::En);
+    assert_eq!(
+        wrapped.to_string(),
+        "Transaction error: Invalid parameter: x"
+    );
+    assert_eq!(votes.to_string(), "Insufficient votes: 1/3");
+    assert_eq!(Error::StaleBlock.to_string(), "Stale block");
+
+    i18n::set_locale(Locale::PtBr);
+    assert_eq!(Error::StaleBlock.to_string(), "Bloco antigo");
+}
@@ -0,0 +1,411 @@
+use kybelith::blockchain::merkle::{leaf_hash, merkle_root, MerkleProof};
+use kybelith::blockchain::Blockchain;
+use kybelith::error::TransactionError;
+use kybelith::interchain::{escrow_address, InterchainError, LightClient, Packet, SignedHeader};
+use kybelith::test_utils::fixtures::{balance, commit, fund, temp_path, Account};
+use kybelith::transaction::{Operation, OperationKind, TransferRule};
+use pqcrypto_dilithium::dilithium5::{keypair, PublicKey, SecretKey};
+use pqcrypto_traits::sign::PublicKey as _;
+
+struct Chain {
+    blockchain: Blockchain,
+    admin: Account,
+    validator: (PublicKey, SecretKey),
+}
+
+impl Chain {
+    fn new(chain_id: &str) -> Self {
+        let admin = Account::new();
+        Chain {
+            blockchain: node(chain_id, &admin),
+            admin,
+            validator: keypair(),
+        }
+    }
+
+    fn chain_id(&self) -> String {
+        self.blockchain.chain_id().to_string()
+    }
+
+    /// Cabeçalho da altura `height` assinado pelo validador da cadeia
+    fn header(&self, height: u64) -> SignedHeader {
+        self.blockchain
+            .sign_interchain_header(height, &self.validator.0, &self.validator.1)
+            .unwrap()
+    }
+}
+
+/// Nó da cadeia `chain_id` com `admin` como primeiro administrador
+fn node(chain_id: &str, admin: &Account) -> Blockchain {
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain.set_chain_id(chain_id);
+    blockchain.bootstrap_admin(admin.address.clone()).unwrap();
+    blockchain
+}
+
+/// Registra em `local`, por operação do seu admin, o cliente leve de `remote`
+fn register_client(local: &mut Chain, remote: &Chain) {
+    let register = local.admin.operation(OperationKind::RegisterChainClient {
+        chain_id: remote.chain_id(),
+        validators: vec![remote.validator.0.as_bytes().to_vec()],
+        threshold: 1,
+    });
+    commit(&mut local.blockchain, vec![register]);
+}
+
+fn connect(a: &mut Chain, b: &mut Chain) {
+    register_client(a, b);
+    register_client(b, a);
+}
+
+fn send(
+    sender: &mut Account,
+    destination: &Chain,
+    receiver: &Account,
+    token_id: u64,
+    amount: u64,
+) -> Operation {
+    sender.operation(OperationKind::SendPacket {
+        destination_chain: destination.chain_id(),
+        receiver: receiver.address.clone(),
+        token_id,
+        amount,
+    })
+}
+
+/// Entrega de `packet` com a prova e o cabeçalho da cadeia de origem
+fn receive(
+    relayer: &mut Account,
+    source: &Chain,
+    packet: &Packet,
+    voucher_token_id: Option<u64>,
+) -> Operation {
+    let (height, proof) = source.blockchain.packet_proof(packet).unwrap();
+    relayer.operation(OperationKind::ReceivePacket {
+        packet: packet.clone(),
+        proof,
+        header: source.header(height),
+        voucher_token_id,
+    })
+}
+
+/// Único pacote enviado pelo último bloco da cadeia
+fn last_packet(chain: &Chain) -> Packet {
+    let height = chain.blockchain.latest_block().unwrap().index;
+    let packets = chain.blockchain.sent_packets(height);
+    assert_eq!(packets.len(), 1);
+    packets[0].clone()
+}
+
+#[test]
+fn test_merkle_proofs_for_every_leaf() {
+    for count in 1..=9u8 {
+        let leaves: Vec<_> = (0..count).map(|i| leaf_hash(&[i])).collect();
+        let root = merkle_root(&leaves);
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = MerkleProof::build(&leaves, index).unwrap();
+            assert!(proof.verify(leaf, &root));
+            assert!(!proof.verify(&leaf_hash(b"outra"), &root));
+        }
+        assert!(MerkleProof::build(&leaves, leaves.len()).is_none());
+    }
+}
+
+#[test]
+fn test_token_round_trip_between_two_chains() {
+    let mut a = Chain::new("chain-a");
+    let mut b = Chain::new("chain-b");
+    connect(&mut a, &mut b);
+    let mut alice = Account::new();
+    let mut bob = Account::new();
+    let mut relayer = Account::new();
+    fund(&mut a.blockchain, &alice.address, 1_000);
+
+    // A -> B: o token nativo fica em escrow só quando o bloco aplica o envio
+    let outgoing = send(&mut alice, &b, &bob, 0, 300);
+    a.blockchain.submit_operation(outgoing).unwrap();
+    assert_eq!(balance(&a.blockchain, 0, &alice.address), 1_000);
+    a.blockchain.produce_block(10).unwrap().unwrap();
+    assert_eq!(balance(&a.blockchain, 0, &alice.address), 700);
+    assert_eq!(balance(&a.blockchain, 0, &escrow_address("chain-b")), 300);
+
+    // B emite um voucher com o próximo ID livre da cadeia
+    let packet = last_packet(&a);
+    let voucher = b.blockchain.next_token_id;
+    assert!(b
+        .blockchain
+        .submit_operation(receive(&mut relayer, &a, &packet, Some(voucher + 1)))
+        .is_err());
+    relayer.nonce -= 1;
+    commit(
+        &mut b.blockchain,
+        vec![receive(&mut relayer, &a, &packet, Some(voucher))],
+    );
+    assert_eq!(b.blockchain.voucher_token("chain-a/0"), Some(voucher));
+    assert!(b.blockchain.packet_received("chain-a", packet.sequence));
+    assert_eq!(balance(&b.blockchain, voucher, &bob.address), 300);
+    assert_eq!(
+        b.blockchain
+            .get_token(&voucher.to_string())
+            .unwrap()
+            .total_supply,
+        300
+    );
+
+    // O mesmo pacote não pode ser recebido duas vezes
+    assert!(matches!(
+        b.blockchain
+            .submit_operation(receive(&mut relayer, &a, &packet, Some(voucher))),
+        Err(TransactionError::TransacaoRepetida)
+    ));
+    relayer.nonce -= 1;
+
+    // B -> A: o voucher é queimado e A libera o escrow
+    let back = send(&mut bob, &a, &alice, voucher, 100);
+    commit(&mut b.blockchain, vec![back]);
+    let back = last_packet(&b);
+    assert_eq!(back.denom, "chain-a/0");
+    assert_eq!(
+        b.blockchain
+            .get_token(&voucher.to_string())
+            .unwrap()
+            .total_supply,
+        200
+    );
+
+    // Nonces são por cadeia: em A, o retransmissor é outra conta
+    let mut relayer_a = Account::new();
+    commit(
+        &mut a.blockchain,
+        vec![receive(&mut relayer_a, &b, &back, None)],
+    );
+    assert_eq!(balance(&a.blockchain, 0, &alice.address), 800);
+    assert_eq!(balance(&a.blockchain, 0, &escrow_address("chain-b")), 200);
+}
+
+#[test]
+fn test_send_packet_respects_account_guard_and_token_policy() {
+    let mut a = Chain::new("chain-a");
+    let mut b = Chain::new("chain-b");
+    connect(&mut a, &mut b);
+    let mut alice = Account::new();
+    let mut carol = Account::new();
+    let bob = Account::new();
+    fund(&mut a.blockchain, &alice.address, 1_000);
+    fund(&mut a.blockchain, &carol.address, 1_000);
+
+    // Com as restrições de saída, uma chave roubada não esvazia a conta por outra cadeia
+    let guard = alice.operation(OperationKind::SetAccountGuard {
+        allow_only: true,
+        delay_blocks: 3,
+    });
+    commit(&mut a.blockchain, vec![guard]);
+    let err = a
+        .blockchain
+        .submit_operation(send(&mut alice, &b, &bob, 0, 100))
+        .unwrap_err();
+    assert!(
+        matches!(err, TransactionError::DestinationBlocked(_)),
+        "{}",
+        err
+    );
+    assert_eq!(balance(&a.blockchain, 0, &escrow_address("chain-b")), 0);
+
+    // As regras do token também limitam o envio
+    let token_id = a.blockchain.next_token_id;
+    let create = carol.operation(OperationKind::CreateToken {
+        token_id,
+        name: "Token Limitado".to_string(),
+        symbol: "LIM".to_string(),
+        total_supply: 1_000,
+    });
+    commit(&mut a.blockchain, vec![create]);
+    let limit = carol.operation(OperationKind::SetTransferPolicy {
+        token_id,
+        rules: vec![TransferRule::MaxTransferAmount(50)],
+    });
+    commit(&mut a.blockchain, vec![limit]);
+    assert!(a
+        .blockchain
+        .submit_operation(send(&mut carol, &b, &bob, token_id, 100))
+        .is_err());
+    carol.nonce -= 1;
+    commit(
+        &mut a.blockchain,
+        vec![send(&mut carol, &b, &bob, token_id, 50)],
+    );
+    assert_eq!(
+        balance(&a.blockchain, token_id, &escrow_address("chain-b")),
+        50
+    );
+}
+
+#[test]
+fn test_received_packets_and_vouchers_survive_restart() {
+    let mut a = Chain::new("chain-a");
+    let mut b = Chain::new("chain-b");
+    connect(&mut a, &mut b);
+    let mut alice = Account::new();
+    let bob = Account::new();
+    let mut relayer = Account::new();
+    fund(&mut a.blockchain, &alice.address, 500);
+
+    commit(&mut a.blockchain, vec![send(&mut alice, &b, &bob, 0, 50)]);
+    let packet = last_packet(&a);
+    let voucher = b.blockchain.next_token_id;
+    commit(
+        &mut b.blockchain,
+        vec![receive(&mut relayer, &a, &packet, Some(voucher))],
+    );
+
+    let path = temp_path("interchain.db");
+    b.blockchain.save_to_db(&path.to_string_lossy()).unwrap();
+    let mut restored = Blockchain::load_from_db(&path.to_string_lossy()).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(restored.chain_id(), "chain-b");
+    assert_eq!(restored.voucher_token("chain-a/0"), Some(voucher));
+    assert!(restored.interchain_client("chain-a").is_some());
+    assert!(matches!(
+        restored.submit_operation(receive(&mut relayer, &a, &packet, Some(voucher))),
+        Err(TransactionError::TransacaoRepetida)
+    ));
+}
+
+#[test]
+fn test_imported_blocks_apply_interchain_operations() {
+    let mut a = Chain::new("chain-a");
+    let mut b = Chain::new("chain-b");
+    connect(&mut a, &mut b);
+    let mut alice = Account::new();
+    let bob = Account::new();
+    let mut relayer = Account::new();
+    fund(&mut a.blockchain, &alice.address, 500);
+
+    commit(&mut a.blockchain, vec![send(&mut alice, &b, &bob, 0, 50)]);
+    let packet = last_packet(&a);
+    let voucher = b.blockchain.next_token_id;
+    commit(
+        &mut b.blockchain,
+        vec![receive(&mut relayer, &a, &packet, Some(voucher))],
+    );
+
+    // Outro nó de B recebe só os blocos: registro do cliente e recebimento
+    let mut importer = node("chain-b", &b.admin);
+    for block in b.blockchain.blocks().to_vec() {
+        importer.add_block(block).unwrap();
+    }
+    assert_eq!(importer.voucher_token("chain-a/0"), Some(voucher));
+    assert_eq!(balance(&importer, voucher, &bob.address), 50);
+    assert!(importer.packet_received("chain-a", packet.sequence));
+}
+
+#[test]
+fn test_light_client_rejects_untrusted_headers() {
+    let mut a = Chain::new("chain-a");
+    let mut b = Chain::new("chain-b");
+    connect(&mut a, &mut b);
+    let mut alice = Account::new();
+    let mut relayer = Account::new();
+    fund(&mut a.blockchain, &alice.address, 50);
+
+    commit(
+        &mut a.blockchain,
+        vec![send(&mut alice, &b, &Account::new(), 0, 50)],
+    );
+    let packet = last_packet(&a);
+    let (height, proof) = a.blockchain.packet_proof(&packet).unwrap();
+    let voucher = Some(b.blockchain.next_token_id);
+
+    // Cabeçalho assinado por um validador desconhecido
+    let outsider = keypair();
+    let mut forged = SignedHeader::new(a.header(height).header);
+    forged.sign(&outsider.0, &outsider.1);
+    let mut client =
+        LightClient::new("chain-a", vec![a.validator.0.as_bytes().to_vec()], 1).unwrap();
+    assert!(matches!(
+        client.update(forged.clone()),
+        Err(InterchainError::InsufficientSignatures { .. })
+    ));
+    assert!(b
+        .blockchain
+        .submit_operation(relayer.operation(OperationKind::ReceivePacket {
+            packet: packet.clone(),
+            proof,
+            header: forged,
+            voucher_token_id: voucher,
+        }))
+        .is_err());
+    relayer.nonce -= 1;
+
+    // Pacote de uma cadeia sem cliente registrado em B
+    let mut c = Chain::new("chain-c");
+    let mut carol = Account::new();
+    fund(&mut c.blockchain, &carol.address, 50);
+    register_client(&mut c, &b);
+    commit(
+        &mut c.blockchain,
+        vec![send(&mut carol, &b, &Account::new(), 0, 50)],
+    );
+    let stranger = last_packet(&c);
+    assert!(b
+        .blockchain
+        .submit_operation(receive(&mut relayer, &c, &stranger, voucher))
+        .is_err());
+    assert!(b.blockchain.interchain_client("chain-c").is_none());
+
+    // Cabeçalho que não encadeia com o anterior
+    let genesis = a.header(0);
+    client.update(genesis.clone()).unwrap();
+    let mut broken = genesis.header;
+    broken.height += 1;
+    broken.previous_hash = "f".repeat(64);
+    let mut broken = SignedHeader::new(broken);
+    broken.sign(&a.validator.0, &a.validator.1);
+    assert!(matches!(
+        client.update(broken),
+        Err(InterchainError::InvalidHeader(_))
+    ));
+}
+
+#[test]
+fn test_receive_rejects_packet_not_in_header() {
+    let mut a = Chain::new("chain-a");
+    let mut b = Chain::new("chain-b");
+    connect(&mut a, &mut b);
+    let mut alice = Account::new();
+    let bob = Account::new();
+    let mut relayer = Account::new();
+    fund(&mut a.blockchain, &alice.address, 50);
+
+    commit(&mut a.blockchain, vec![send(&mut alice, &b, &bob, 0, 50)]);
+    let packet = last_packet(&a);
+    let (height, proof) = a.blockchain.packet_proof(&packet).unwrap();
+    let voucher = Some(b.blockchain.next_token_id);
+
+    let mut inflated = packet.clone();
+    inflated.amount = 5_000;
+    assert!(b
+        .blockchain
+        .submit_operation(relayer.operation(OperationKind::ReceivePacket {
+            packet: inflated,
+            proof: proof.clone(),
+            header: a.header(height),
+            voucher_token_id: voucher,
+        }))
+        .is_err());
+    relayer.nonce -= 1;
+
+    // Cabeçalho de outra altura não compromete o pacote
+    assert!(b
+        .blockchain
+        .submit_operation(relayer.operation(OperationKind::ReceivePacket {
+            packet: packet.clone(),
+            proof,
+            header: a.header(height - 1),
+            voucher_token_id: voucher,
+        }))
+        .is_err());
+    assert!(b.blockchain.voucher_token("chain-a/0").is_none());
+    assert!(!b.blockchain.packet_received("chain-a", packet.sequence));
+}
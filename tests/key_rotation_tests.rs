@@ -0,0 +1,162 @@
+use kybelith::blockchain::Blockchain;
+use kybelith::error::{ErrorCode, TransactionError};
+use kybelith::test_utils::fixtures::{balance, funded, Account};
+use kybelith::transaction::{Operation, OperationKind};
+use pqcrypto_dilithium::dilithium5::{keypair, PublicKey, SecretKey};
+use pqcrypto_traits::sign::PublicKey as _;
+
+/// Rotação para um par novo; devolve o par antigo
+fn rotate(account: &mut Account, blockchain: &mut Blockchain) -> (PublicKey, SecretKey) {
+    let keys = keypair();
+    let rotation = account.operation(OperationKind::RotateKey {
+        new_public_key: keys.0.as_bytes().to_vec(),
+    });
+    blockchain.submit_operation(rotation).unwrap();
+    blockchain.produce_block(10).unwrap().unwrap();
+    std::mem::replace(&mut account.keys, keys)
+}
+
+#[test]
+fn test_rotated_key_replaces_old_one_and_history_is_kept() {
+    let mut owner = Account::new();
+    let friend = Account::new();
+    let mut blockchain = funded(&owner.address, 1_000);
+    let original = owner.public_key();
+
+    let first_keys = rotate(&mut owner, &mut blockchain);
+    let second = owner.public_key();
+    assert_eq!(
+        blockchain.rotated_key(&owner.address),
+        Some(second.as_slice())
+    );
+
+    // Nonces seguintes só com a chave nova
+    let current_keys = std::mem::replace(&mut owner.keys, first_keys);
+    let stale = owner.transfer(&friend.address, 10);
+    let err = blockchain.submit_transaction(stale).unwrap_err();
+    assert!(matches!(err, TransactionError::KeyRotated(_)), "{}", err);
+    assert_eq!(err.code(), 2032);
+    owner.nonce -= 1;
+    owner.keys = current_keys;
+    let tx = owner.transfer(&friend.address, 10);
+    blockchain.submit_transaction(tx).unwrap();
+    blockchain.produce_block(10).unwrap().unwrap();
+    assert_eq!(balance(&blockchain, 0, &friend.address), 10);
+
+    // A chave nova também troca a si mesma
+    rotate(&mut owner, &mut blockchain);
+    let third = owner.public_key();
+    let rotations = blockchain.key_rotations(&owner.address);
+    assert_eq!(rotations.len(), 2);
+    assert_eq!((rotations[0].first_nonce, rotations[1].first_nonce), (2, 4));
+    assert!(rotations.iter().all(|r| r.approved_by.is_empty()));
+
+    // Blocos antigos são verificados com a chave em vigor em cada nonce
+    assert_eq!(blockchain.key_for_nonce(&owner.address, 1), None);
+    assert_eq!(
+        blockchain.key_for_nonce(&owner.address, 3),
+        Some(second.as_slice())
+    );
+    assert_eq!(
+        blockchain.key_for_nonce(&owner.address, 4),
+        Some(third.as_slice())
+    );
+    blockchain
+        .check_historical_key(&owner.address, &original, 1)
+        .unwrap();
+    blockchain
+        .check_historical_key(&owner.address, &second, 2)
+        .unwrap();
+    assert!(blockchain
+        .check_historical_key(&owner.address, &second, 4)
+        .is_err());
+    assert!(blockchain.is_chain_valid().unwrap());
+
+    let path = std::env::temp_dir().join(format!(
+        "kybelith-rotation-{}-history.db",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    blockchain.save_to_db(path.to_str().unwrap()).unwrap();
+    let restored = Blockchain::load_from_db(path.to_str().unwrap()).unwrap();
+    assert_eq!(restored.key_rotations, blockchain.key_rotations);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_rotation_requires_current_key_and_drops_stale_pending_transfers() {
+    let mut owner = Account::new();
+    let thief = Account::new();
+    let friend = Account::new();
+    let mut blockchain = funded(&owner.address, 1_000);
+
+    let same = owner.operation(OperationKind::RotateKey {
+        new_public_key: owner.public_key(),
+    });
+    assert!(same.check().is_err());
+    let garbage = owner.operation(OperationKind::RotateKey {
+        new_public_key: vec![1; 32],
+    });
+    assert!(garbage.check().is_err());
+    owner.nonce = 0;
+
+    // Só a chave vigente da conta pede a rotação
+    let mut forged = Operation::new(
+        OperationKind::RotateKey {
+            new_public_key: keypair().0.as_bytes().to_vec(),
+        },
+        owner.address.clone(),
+        1,
+        thief.public_key(),
+    )
+    .unwrap();
+    forged.sign(&thief.keys.1).unwrap();
+    forged.check().unwrap();
+    assert!(blockchain.submit_operation(forged).is_err());
+
+    // Uma transferência com a chave antiga já no mempool cai ao ser aplicada
+    // depois da rotação que entrou antes dela
+    let (new_public, new_secret) = keypair();
+    let rotation = owner.operation(OperationKind::RotateKey {
+        new_public_key: new_public.as_bytes().to_vec(),
+    });
+    blockchain.submit_operation(rotation).unwrap();
+    let stale = owner.transfer(&friend.address, 10);
+    blockchain.submit_transaction(stale).unwrap();
+    blockchain.produce_block(10).unwrap().unwrap();
+    assert_eq!(balance(&blockchain, 0, &friend.address), 0);
+
+    let old_keys = std::mem::replace(&mut owner.keys, (new_public, new_secret));
+    assert!(blockchain.is_account_key(&owner.address, &owner.public_key()));
+    assert!(!blockchain.is_account_key(&owner.address, old_keys.0.as_bytes()));
+    let tx = owner.transfer(&friend.address, 10);
+    blockchain.submit_transaction(tx).unwrap();
+    blockchain.produce_block(10).unwrap().unwrap();
+    assert_eq!(balance(&blockchain, 0, &friend.address), 10);
+}
+
+#[test]
+fn test_unrotated_account_only_accepts_its_own_key() {
+    let owner = Account::new();
+    let mut thief = Account::new();
+    let loot = thief.address.clone();
+    let mut blockchain = funded(&owner.address, 1_000);
+
+    // Assinatura válida, mas de uma chave que não deriva o endereço de origem
+    thief.address = owner.address.clone();
+    let forged = thief.transfer(&loot, 500);
+    let err = blockchain.submit_transaction(forged).unwrap_err();
+    assert!(
+        matches!(err, TransactionError::InvalidPublicKey(_)),
+        "{}",
+        err
+    );
+    assert!(!blockchain.is_account_key(&owner.address, &thief.public_key()));
+
+    // A chave registrada para o endereço também responde por ele
+    blockchain
+        .public_keys
+        .insert(owner.address.clone(), thief.public_key());
+    assert!(blockchain.is_account_key(&owner.address, &thief.public_key()));
+    assert!(blockchain.is_account_key(&owner.address, &owner.public_key()));
+}
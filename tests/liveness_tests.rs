@@ -0,0 +1,92 @@
+use kybelith::consensus::reputation::{ReputationAction, ReputationSystem};
+use kybelith::consensus::LivenessDuty;
+use std::time::Duration;
+
+fn system() -> ReputationSystem {
+    let mut system = ReputationSystem::new();
+    system.add_validator("validator1".to_string());
+    system
+}
+
+#[test]
+fn test_missed_duties_toggle_offline_and_back_online() {
+    let mut system = system();
+    let window = system.config().liveness.clone();
+
+    let mut transitions = Vec::new();
+    for slot in 0..window.max_missed {
+        let duty = if slot % 2 == 0 {
+            LivenessDuty::Vote
+        } else {
+            LivenessDuty::Proposal
+        };
+        transitions.push(system.record_duty("validator1", duty, false).unwrap());
+    }
+    assert_eq!(transitions.pop().unwrap(), Some(ReputationAction::Offline));
+    assert!(transitions.iter().all(Option::is_none));
+
+    let stats = system.liveness().stats("validator1");
+    assert!(stats.offline);
+    assert_eq!(
+        stats.missed_votes + stats.missed_proposals,
+        window.max_missed
+    );
+    assert_eq!(stats.consecutive_missed, window.max_missed as u64);
+    let penalized = 50.0 - system.config().offline_penalty;
+    assert_eq!(
+        system.get_reputation("validator1").unwrap().score,
+        penalized
+    );
+
+    // Mais faltas não repetem a penalidade
+    assert_eq!(
+        system
+            .record_duty("validator1", LivenessDuty::Vote, false)
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        system.get_reputation("validator1").unwrap().score,
+        penalized
+    );
+
+    let recovered: Vec<_> = (0..window.recovery_streak)
+        .map(|_| {
+            system
+                .record_duty("validator1", LivenessDuty::Vote, true)
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(
+        recovered.last().unwrap(),
+        &Some(ReputationAction::BackOnline)
+    );
+    assert!(!system.liveness().is_offline("validator1"));
+    assert_eq!(
+        system.get_reputation("validator1").unwrap().score,
+        penalized + system.config().back_online_points
+    );
+}
+
+#[test]
+fn test_update_offline_status_emits_transitions_once() {
+    let mut system = system();
+    std::thread::sleep(Duration::from_millis(20));
+
+    let threshold = Duration::from_millis(10);
+    assert_eq!(
+        system.update_offline_status(threshold),
+        vec![("validator1".to_string(), ReputationAction::Offline)]
+    );
+    assert!(system.update_offline_status(threshold).is_empty());
+
+    // Voltar a ser visto tira o validador do estado offline
+    system
+        .update_reputation("validator1", ReputationAction::CorrectVote)
+        .unwrap();
+    assert_eq!(
+        system.update_offline_status(threshold),
+        vec![("validator1".to_string(), ReputationAction::BackOnline)]
+    );
+    assert!(!system.liveness().is_offline("validator1"));
+}
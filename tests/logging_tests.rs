@@ -0,0 +1,78 @@
+use kybelith::config::{LogFormat, LogRotation, LoggingConfig, Settings};
+use kybelith::logging::{format_line, ModuleLevels, RotatingFile};
+use log::{Level, LevelFilter};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[test]
+fn test_rotating_file_by_size_and_period() {
+    let dir = std::env::temp_dir().join(format!("kybelith-logs-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("node.log");
+    let config = LoggingConfig {
+        max_file_bytes: 20,
+        rotation: LogRotation::Daily,
+        max_files: 2,
+        ..LoggingConfig::default()
+    };
+
+    let day = 19_000 * 86_400;
+    let mut file = RotatingFile::open(&path, &config).unwrap();
+    // Cada linha tem 10 bytes: duas cabem, a terceira rotaciona
+    for line in ["linha-0001", "linha-0002", "linha-0003"] {
+        file.write_line_at(line, day).unwrap();
+    }
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "linha-0003\n");
+    assert!(std::fs::read_to_string(dir.join("node.log.1"))
+        .unwrap()
+        .ends_with("linha-0002\n"));
+
+    // A virada do dia rotaciona mesmo abaixo do tamanho, e só dois antigos ficam
+    file.write_line_at("linha-0004", day + 86_400).unwrap();
+    file.write_line_at("linha-0005", day + 2 * 86_400).unwrap();
+    let files = std::fs::read_dir(&dir).unwrap().count();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "linha-0005\n");
+    assert_eq!(
+        std::fs::read_to_string(dir.join("node.log.2")).unwrap(),
+        "linha-0003\n"
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(files, 3);
+}
+
+#[test]
+fn test_module_levels_and_json_format() {
+    let mut modules = BTreeMap::new();
+    modules.insert("kybelith::network".to_string(), "warn".to_string());
+    modules.insert("kybelith::network::sync".to_string(), "trace".to_string());
+    let levels = ModuleLevels::parse(&modules).unwrap();
+    assert_eq!(
+        levels.level_for("kybelith::network::peer"),
+        Some(LevelFilter::Warn)
+    );
+    assert_eq!(
+        levels.level_for("kybelith::network::sync"),
+        Some(LevelFilter::Trace)
+    );
+    assert_eq!(levels.level_for("kybelith::networking"), None);
+
+    modules.insert("kybelith::rpc".to_string(), "verboso".to_string());
+    assert!(ModuleLevels::parse(&modules).is_err());
+
+    let line = format_line(
+        LogFormat::Json,
+        Level::Warn,
+        "kybelith::rpc",
+        "limite \"atingido\"",
+    );
+    let record: Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(record["level"], "WARN");
+    assert_eq!(record["message"], "limite \"atingido\"");
+
+    // A seção é opcional no arquivo de configuração
+    let mut json = serde_json::to_value(Settings::default()).unwrap();
+    json.as_object_mut().unwrap().remove("logging");
+    let settings: Settings = serde_json::from_value(json).unwrap();
+    assert_eq!(settings.logging, LoggingConfig::default());
+}
@@ -0,0 +1,56 @@
+use kybelith::error::{ErrorCode, TransactionError};
+use kybelith::test_utils::fixtures::{address_of, funded, transfer};
+use pqcrypto_dilithium::dilithium5::keypair;
+
+#[test]
+fn test_pending_cap_per_sender() {
+    let keys = keypair();
+    let mut blockchain = funded(&address_of(&keys), 1000);
+    blockchain.limits.max_pending_per_sender = 2;
+
+    for nonce in 1..=2 {
+        blockchain
+            .submit_transaction(transfer(&keys, 10, nonce))
+            .unwrap();
+    }
+    let err = blockchain
+        .submit_transaction(transfer(&keys, 10, 3))
+        .unwrap_err();
+    assert!(matches!(err, TransactionError::TooManyPending(2)));
+    assert_eq!(err.code(), 2026);
+    assert!(err.is_retryable());
+    assert_eq!(blockchain.pending_transactions.len(), 2);
+
+    // Com o mempool esvaziado pelo bloco, o remetente volta a ser aceito
+    blockchain.produce_block(10).unwrap().unwrap();
+    blockchain
+        .submit_transaction(transfer(&keys, 10, 3))
+        .unwrap();
+}
+
+#[test]
+fn test_submission_rate_per_sender() {
+    let keys = keypair();
+    let mut blockchain = funded(&address_of(&keys), 1000);
+    blockchain.limits.max_submissions_per_window = 3;
+
+    for nonce in 1..=3 {
+        blockchain
+            .submit_transaction(transfer(&keys, 10, nonce))
+            .unwrap();
+    }
+    blockchain.produce_block(10).unwrap().unwrap();
+
+    // O bloco libera o mempool, mas não zera a contagem da janela
+    let err = blockchain
+        .submit_transaction(transfer(&keys, 10, 4))
+        .unwrap_err();
+    assert!(matches!(err, TransactionError::SubmissionRateExceeded(3)));
+    assert_eq!(err.code(), 2027);
+
+    // Submissões fora da janela não contam mais
+    blockchain.limits.submission_window_secs = 0;
+    blockchain
+        .submit_transaction(transfer(&keys, 10, 4))
+        .unwrap();
+}
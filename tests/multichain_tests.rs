@@ -0,0 +1,89 @@
+use kybelith::config::Settings;
+use kybelith::rpc::BalanceResponse;
+use kybelith::ChainHost;
+use serde_json::json;
+
+fn chain_settings(name: &str, chain_id: &str) -> Settings {
+    let dir = std::env::temp_dir().join(format!(
+        "kybelith-multichain-{}-{}",
+        std::process::id(),
+        name
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut settings = Settings::default();
+    settings.node.chain_id = chain_id.to_string();
+    settings.node.data_dir = dir.to_string_lossy().into_owned();
+    settings
+}
+
+#[tokio::test]
+async fn test_chain_host_rejects_shared_ids_and_files() {
+    let mainnet = chain_settings("dup-main", "mainnet");
+    let mut testnet = chain_settings("dup-test", "mainnet");
+    let err = ChainHost::open(vec![mainnet.clone(), testnet.clone()])
+        .await
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("mais de uma vez"), "{}", err);
+
+    // Cadeias distintas no mesmo diretório gravariam no mesmo arquivo
+    testnet.node.chain_id = "testnet".to_string();
+    testnet.node.data_dir = mainnet.node.data_dir.clone();
+    let err = ChainHost::open(vec![mainnet.clone(), testnet])
+        .await
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("mesmo arquivo"), "{}", err);
+    assert!(!std::path::Path::new(&mainnet.node.data_dir).exists());
+}
+
+#[tokio::test]
+async fn test_chain_host_routes_rpc_to_isolated_chains() {
+    let mainnet = chain_settings("main", "mainnet");
+    let mut testnet = chain_settings("test", "testnet");
+    testnet.node.blockchain_file = "testnet.json".to_string();
+
+    let host = ChainHost::open(vec![mainnet.clone(), testnet.clone()])
+        .await
+        .unwrap();
+    assert_eq!(host.default_chain(), "mainnet");
+    assert_eq!(host.chain_ids().collect::<Vec<_>>(), ["mainnet", "testnet"]);
+
+    // Um saldo creditado só na testnet não aparece na mainnet
+    let address = "a".repeat(40);
+    host.node("testnet")
+        .unwrap()
+        .blockchain()
+        .write(|blockchain| {
+            let token = blockchain.tokens.get_mut("0").unwrap();
+            token.balances.insert(address.clone(), 42);
+        });
+
+    let router = host.rpc_router();
+    let request = json!({ "token_id": "0", "address": address });
+    let balance = |chain_id: Option<&str>| -> u64 {
+        let reply = router
+            .call(chain_id, "get_balance", None, request.clone())
+            .unwrap();
+        serde_json::from_value::<BalanceResponse>(reply)
+            .unwrap()
+            .balance
+    };
+    assert_eq!(balance(Some("testnet")), 42);
+    assert_eq!(balance(Some("mainnet")), 0);
+    assert_eq!(balance(None), 0);
+    assert!(router
+        .call(Some("devnet"), "get_height", None, json!({}))
+        .is_err());
+
+    let (service, method) = router.route("/chains/testnet/rpc/get_balance").unwrap();
+    assert_eq!(method, "get_balance");
+    assert_eq!(service.handle(method, request).unwrap()["balance"], 42);
+    assert!(router.route("/chains/testnet").is_err());
+
+    host.shutdown().await.unwrap();
+    assert!(testnet.blockchain_path().exists());
+    assert!(mainnet.blockchain_path().exists());
+    let _ = std::fs::remove_dir_all(&mainnet.node.data_dir);
+    let _ = std::fs::remove_dir_all(&testnet.node.data_dir);
+}
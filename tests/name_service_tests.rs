@@ -0,0 +1,132 @@
+use kybelith::blockchain::{Blockchain, SharedBlockchain, NAME_FEE_PER_BLOCK};
+use kybelith::rpc::{NameResponse, RpcService};
+use kybelith::test_utils::fixtures::{balance, commit, fund, Account};
+use kybelith::transaction::{Operation, OperationKind};
+use kybelith::utils::name::{validate_name, NameError};
+use kybelith::wallet::Recipient;
+use serde_json::json;
+
+fn register(account: &mut Account, name: &str, blocks: u64) -> Operation {
+    account.operation(OperationKind::RegisterName {
+        name: name.to_string(),
+        blocks,
+    })
+}
+
+#[test]
+fn test_recipient_parses_names_and_addresses() {
+    validate_name("alice.qst").unwrap();
+    validate_name("cofre-01.qst").unwrap();
+    assert_eq!(validate_name("alice"), Err(NameError::MissingSuffix));
+    assert_eq!(validate_name("al.qst"), Err(NameError::InvalidLength(2)));
+    assert_eq!(
+        validate_name("Alice.qst"),
+        Err(NameError::InvalidCharacter('A'))
+    );
+    assert_eq!(validate_name("-alice.qst"), Err(NameError::EdgeHyphen));
+    assert!(validate_name(&format!("{}.qst", "a".repeat(33))).is_err());
+
+    let address = "ab".repeat(20);
+    assert_eq!(
+        Recipient::parse(&address).unwrap(),
+        Recipient::Address(address.parse().unwrap())
+    );
+    let recipient = Recipient::parse(" Alice.QST ").unwrap();
+    assert_eq!(recipient, Recipient::Name("alice.qst".to_string()));
+    assert_eq!(recipient.to_string(), "alice.qst");
+    assert!(Recipient::parse("alice.eth").is_err());
+
+    let resolved = recipient
+        .resolve(|name| (name == "alice.qst").then(|| address.clone()))
+        .unwrap();
+    assert_eq!(resolved.as_str(), address);
+    let err = Recipient::parse("bob.qst")
+        .unwrap()
+        .resolve(|_| None)
+        .unwrap_err();
+    assert!(err.to_string().contains("bob.qst"), "{}", err);
+}
+
+#[test]
+fn test_names_register_transfer_renew_and_expire() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut alice = Account::new();
+    let mut bob = Account::new();
+    fund(&mut blockchain, &alice.address, 100);
+    fund(&mut blockchain, &bob.address, 100);
+    let supply = blockchain.get_token("0").unwrap().total_supply;
+
+    // Sem saldo para a taxa o registro é recusado
+    assert!(blockchain
+        .submit_operation(register(&mut alice, "alice.qst", 101))
+        .is_err());
+    alice.nonce -= 1;
+
+    commit(&mut blockchain, [register(&mut alice, "alice.qst", 3)]);
+    assert_eq!(
+        balance(&blockchain, 0, &alice.address),
+        100 - 3 * NAME_FEE_PER_BLOCK
+    );
+    assert_eq!(
+        blockchain.get_token("0").unwrap().total_supply,
+        supply - 3 * NAME_FEE_PER_BLOCK
+    );
+    assert_eq!(blockchain.resolve_name("alice.qst"), Some(&alice.address));
+    assert_eq!(blockchain.names_of(&alice.address), ["alice.qst"]);
+    assert!(blockchain
+        .submit_operation(register(&mut bob, "alice.qst", 5))
+        .is_err());
+    bob.nonce -= 1;
+
+    // Renovação soma à validade restante; só o dono renova ou transfere
+    let renew = OperationKind::RenewName {
+        name: "alice.qst".to_string(),
+        blocks: 2,
+    };
+    assert!(blockchain
+        .submit_operation(bob.operation(renew.clone()))
+        .is_err());
+    bob.nonce -= 1;
+    commit(&mut blockchain, [alice.operation(renew)]);
+    assert_eq!(blockchain.name_record("alice.qst").unwrap().expires_at, 5);
+
+    let transfer = alice.operation(OperationKind::TransferName {
+        name: "alice.qst".to_string(),
+        to: bob.address.clone(),
+    });
+    commit(&mut blockchain, [transfer]);
+    assert_eq!(blockchain.resolve_name("alice.qst"), Some(&bob.address));
+    assert!(blockchain.names_of(&alice.address).is_empty());
+
+    // Na altura de expiração o nome deixa de resolver e fica livre
+    commit(&mut blockchain, [register(&mut bob, "bob.qst", 10)]);
+    commit(&mut blockchain, [register(&mut bob, "cofre.qst", 10)]);
+    assert_eq!(blockchain.height(), 5);
+    assert_eq!(blockchain.resolve_name("alice.qst"), None);
+    commit(&mut blockchain, [register(&mut alice, "alice.qst", 4)]);
+    let record = blockchain.name_record("alice.qst").unwrap();
+    assert_eq!((record.registered_at, record.expires_at), (5, 9));
+    assert_eq!(record.owner, alice.address);
+
+    let path =
+        std::env::temp_dir().join(format!("kybelith-names-{}-registry.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    blockchain.save_to_db(path.to_str().unwrap()).unwrap();
+    let restored = Blockchain::load_from_db(path.to_str().unwrap()).unwrap();
+    assert_eq!(restored.names, blockchain.names);
+    let _ = std::fs::remove_file(&path);
+
+    let service = RpcService::new(SharedBlockchain::new(restored));
+    let response: NameResponse = serde_json::from_value(
+        service
+            .handle("resolve_name", json!({ "name": "ALICE.qst" }))
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(response.name, "alice.qst");
+    assert_eq!(response.owner, alice.address);
+    assert_eq!((response.registered_at, response.expires_at), (5, 9));
+    assert!(service
+        .handle("resolve_name", json!({ "name": "ninguem.qst" }))
+        .is_err());
+}
@@ -0,0 +1,93 @@
+use kybelith::blockchain::{Blockchain, SharedBlockchain};
+use kybelith::config::Settings;
+use kybelith::test_utils::fixtures::{address_of, transfer};
+use kybelith::{Database, KeyManager, QuantumBlockchainApp};
+use pqcrypto_dilithium::dilithium5::keypair;
+use std::time::Duration;
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("kybelith-node-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn test_produce_block_drains_mempool_in_order() {
+    let keys = keypair();
+    let mut blockchain = Blockchain::new().unwrap();
+    assert!(blockchain.produce_block(10).unwrap().is_none());
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(address_of(&keys), 1_000);
+
+    for nonce in 1..=3 {
+        blockchain
+            .submit_transaction(transfer(&keys, 100, nonce))
+            .unwrap();
+    }
+
+    let first = blockchain
+        .produce_block(2)
+        .unwrap()
+        .expect("bloco esperado");
+    assert_eq!(first.index, 0);
+    assert_eq!(blockchain.pending_transactions.len(), 1);
+    assert_eq!(blockchain.pending_transactions[0].nonce, 3);
+
+    let second = blockchain
+        .produce_block(2)
+        .unwrap()
+        .expect("bloco esperado");
+    assert_eq!(second.previous_hash, first.hash);
+    assert!(blockchain.pending_transactions.is_empty());
+    assert_eq!(blockchain.committed_transactions().len(), 3);
+    assert!(blockchain.is_chain_valid().unwrap());
+}
+
+#[tokio::test]
+async fn test_node_produces_blocks_and_persists_on_shutdown() {
+    let dir = temp_dir("run");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let app = QuantumBlockchainApp {
+        blockchain: Blockchain::new().unwrap(),
+        key_manager: KeyManager::new().unwrap(),
+        database: Database::new(&dir.join("node.db").to_string_lossy()).unwrap(),
+    };
+
+    let mut settings = Settings::default();
+    settings.node.data_dir = dir.to_string_lossy().into_owned();
+    settings.consensus.block_interval_sec = 1;
+
+    let node = app.start(settings.clone()).await.unwrap();
+    assert!(node.consensus().is_running());
+
+    node.pipeline()
+        .submit_transaction(&transfer(&keypair(), 100, 1))
+        .await
+        .unwrap();
+
+    let shared: SharedBlockchain = node.blockchain().clone();
+    let mut waited = Duration::ZERO;
+    while shared.height() == 0 && waited < Duration::from_secs(10) {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        waited += Duration::from_millis(100);
+    }
+    assert_eq!(shared.height(), 1);
+    assert_eq!(shared.pending_count(), 0);
+
+    let identity = *node.identity_public_key();
+    node.shutdown().await.unwrap();
+
+    let restored =
+        Blockchain::load_from_file(&settings.blockchain_path().to_string_lossy()).unwrap();
+    assert_eq!(restored.chain.len(), 1);
+    let signed =
+        Blockchain::load_signed(&settings.blockchain_path().to_string_lossy(), &identity).unwrap();
+    assert_eq!(signed.chain.len(), 1);
+    assert!(signed.chain[0].validator_signature.is_some());
+    assert!(signed.is_chain_valid().unwrap());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
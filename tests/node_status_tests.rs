@@ -0,0 +1,81 @@
+use kybelith::blockchain::{Blockchain, SharedBlockchain};
+use kybelith::rpc::{NodeMonitor, NodeStatus, RpcService, SyncState, HEALTH_PATH};
+use kybelith::test_utils::fixtures::address_of;
+use kybelith::transaction::Transaction;
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+use serde_json::Value;
+use std::sync::Arc;
+
+#[test]
+fn test_node_status_reports_sync_and_mempool() {
+    let keys = keypair();
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(address_of(&keys), 100);
+    let mut tx = Transaction::new(
+        address_of(&keys),
+        "b".repeat(40),
+        10,
+        keys.0.as_bytes().to_vec(),
+    )
+    .unwrap();
+    tx.nonce = 1;
+    tx.sign(&keys.1).unwrap();
+    blockchain.submit_transaction(tx).unwrap();
+
+    let monitor = Arc::new(NodeMonitor::new());
+    let service =
+        RpcService::new(SharedBlockchain::new(blockchain)).with_monitor(Arc::clone(&monitor));
+    let status: NodeStatus =
+        serde_json::from_value(service.handle("get_node_status", Value::Null).unwrap()).unwrap();
+    assert_eq!(status.sync, SyncState::Synced);
+    assert_eq!(status.mempool_size, 1);
+    assert_eq!(status.finalized_height, None);
+    assert!(status.storage.is_none());
+    assert!(service.health().healthy);
+
+    monitor.set_peers(3);
+    monitor.observe_peer_height(status.height + 5);
+    let status = service.node_status();
+    assert_eq!(status.peers, 3);
+    assert_eq!(
+        status.sync,
+        SyncState::Syncing {
+            target_height: status.height + 5
+        }
+    );
+    let health = service.health();
+    assert!(!health.healthy);
+    assert_eq!(health.problems.len(), 1);
+}
+
+#[test]
+fn test_health_checks_sqlite_integrity() {
+    let dir = std::env::temp_dir();
+    let good = dir.join(format!("kybelith-health-ok-{}.db", std::process::id()));
+    let bad = dir.join(format!("kybelith-health-bad-{}.db", std::process::id()));
+    let blockchain = Blockchain::new().unwrap();
+    let _ = std::fs::remove_file(&good);
+    blockchain.save_to_db(&good.to_string_lossy()).unwrap();
+    std::fs::write(&bad, vec![0x5a; 4096]).unwrap();
+
+    let health = |path: &std::path::Path| {
+        let monitor = Arc::new(NodeMonitor::new().with_storage(path));
+        RpcService::new(SharedBlockchain::new(Blockchain::new().unwrap()))
+            .with_monitor(monitor)
+            .health()
+    };
+    let ok = health(&good);
+    let corrupt = health(&bad);
+    let _ = std::fs::remove_file(&good);
+    let _ = std::fs::remove_file(&bad);
+
+    assert!(ok.healthy, "{:?}", ok.problems);
+    assert!(!corrupt.healthy);
+    assert!(kybelith::rpc::openapi_document()["paths"][HEALTH_PATH]["get"].is_object());
+}
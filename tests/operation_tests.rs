@@ -0,0 +1,206 @@
+use kybelith::blockchain::Blockchain;
+use kybelith::error::TransactionError;
+use kybelith::test_utils::fixtures::{address_of, alice, bob, fund};
+use kybelith::transaction::{Operation, OperationKind, Transaction};
+use kybelith::{Database, KeyManager, QuantumBlockchainApp};
+use pqcrypto_dilithium::dilithium5::{keypair, PublicKey, SecretKey};
+use pqcrypto_traits::sign::PublicKey as _;
+
+fn create_token(keys: &(PublicKey, SecretKey), token_id: u64, nonce: u64) -> Operation {
+    let mut operation = Operation::new(
+        OperationKind::CreateToken {
+            token_id,
+            name: "Ouro Digital".to_string(),
+            symbol: "OURO".to_string(),
+            total_supply: 5_000,
+        },
+        address_of(keys),
+        nonce,
+        keys.0.as_bytes().to_vec(),
+    )
+    .unwrap();
+    operation.sign(&keys.1).unwrap();
+    operation
+}
+
+fn transfer(keys: &(PublicKey, SecretKey), token_id: u64, amount: u64, nonce: u64) -> Transaction {
+    let mut tx =
+        Transaction::new(address_of(keys), bob(), amount, keys.0.as_bytes().to_vec()).unwrap();
+    tx.token_id = token_id;
+    tx.nonce = nonce;
+    tx.sign(&keys.1).unwrap();
+    tx
+}
+
+#[test]
+fn test_token_creation_applied_on_block_commit() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let keys = keypair();
+    let token_id = blockchain.next_token_id;
+
+    blockchain
+        .submit_operation(create_token(&keys, token_id, 1))
+        .unwrap();
+    assert!(blockchain.get_token(&token_id.to_string()).is_none());
+    assert_eq!(blockchain.next_token_id, token_id + 1);
+
+    // ID já reservado por uma criação pendente
+    let stale = create_token(&keys, token_id, 2);
+    assert!(matches!(
+        blockchain.submit_operation(stale),
+        Err(TransactionError::InvalidParameter(_))
+    ));
+
+    // Transferência do token criado no mesmo bloco; a taxa sai do KYBL
+    fund(&mut blockchain, &address_of(&keys), 10);
+    blockchain
+        .submit_transaction(transfer(&keys, token_id, 1_200, 2))
+        .unwrap();
+    blockchain
+        .produce_block(10)
+        .unwrap()
+        .expect("bloco esperado");
+
+    let token = blockchain.get_token(&token_id.to_string()).unwrap();
+    assert_eq!(token.id, token_id);
+    assert_eq!(token.balances[&address_of(&keys)], 3_800);
+    assert_eq!(token.balances[&bob()], 1_200);
+    assert_eq!(blockchain.operations.len(), 1);
+    assert_eq!(blockchain.committed_transactions().len(), 1);
+    assert!(blockchain.pending_operations.is_empty());
+}
+
+#[test]
+fn test_operation_signature_and_nonce_checked() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let keys = keypair();
+
+    let mut tampered = create_token(&keys, blockchain.next_token_id, 1);
+    tampered.timestamp -= 1;
+    assert!(blockchain.submit_operation(tampered).is_err());
+
+    let replayed = create_token(&keys, blockchain.next_token_id, 5);
+    assert!(matches!(
+        blockchain.submit_operation(replayed),
+        Err(TransactionError::NonceInvalido)
+    ));
+}
+
+#[test]
+fn test_transfer_without_funds_dropped_at_commit() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let keys = keypair();
+
+    blockchain
+        .submit_transaction(transfer(&keys, 0, 100, 1))
+        .unwrap();
+    blockchain
+        .produce_block(10)
+        .unwrap()
+        .expect("bloco esperado");
+
+    let kybl = blockchain.get_token("0").unwrap();
+    assert_eq!(kybl.balances.get(&bob()), None);
+    assert!(blockchain.committed_transactions().is_empty());
+    assert!(blockchain.pending_transactions.is_empty());
+}
+
+#[test]
+fn test_app_routes_token_operations_through_mempool() {
+    let dir = std::env::temp_dir().join(format!("kybelith-ops-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let keys = keypair();
+
+    let mut app = QuantumBlockchainApp {
+        blockchain: Blockchain::new().unwrap(),
+        key_manager: KeyManager::new().unwrap(),
+        database: Database::new(&dir.join("ops.db").to_string_lossy()).unwrap(),
+    };
+    app.blockchain
+        .public_keys
+        .insert(alice(), keys.0.as_bytes().to_vec());
+    app.blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(alice(), 1_000);
+
+    let token_id = app
+        .create_token(
+            "Prata".to_string(),
+            "PRT".to_string(),
+            10_000,
+            alice(),
+            &keys.1,
+        )
+        .unwrap();
+    assert!(app.blockchain.get_token(&token_id.to_string()).is_none());
+    app.blockchain.produce_block(10).unwrap();
+
+    let tx = app
+        .transfer_token(token_id, alice(), bob(), 2_500, &keys.1)
+        .unwrap();
+    assert_eq!(tx.nonce, 2);
+    assert_eq!(app.blockchain.pending_transactions.len(), 1);
+    app.blockchain.produce_block(10).unwrap();
+
+    let token = app.blockchain.get_token(&token_id.to_string()).unwrap();
+    assert_eq!(token.balances[&alice()], 7_500);
+    assert_eq!(token.balances[&bob()], 2_500);
+    assert!(app.verify_chain_integrity().unwrap());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_custom_tokens_receive_chain_assigned_ids() {
+    let dir = std::env::temp_dir().join(format!("kybelith-custom-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let keys = keypair();
+
+    let mut app = QuantumBlockchainApp {
+        blockchain: Blockchain::new().unwrap(),
+        key_manager: KeyManager::new().unwrap(),
+        database: Database::new(&dir.join("custom.db").to_string_lossy()).unwrap(),
+    };
+    app.blockchain
+        .public_keys
+        .insert(alice(), keys.0.as_bytes().to_vec());
+    app.blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(alice(), 1_000_000);
+    let first_id = app.blockchain.next_token_id;
+
+    let first = app
+        .create_custom_token(
+            "Cobre".to_string(),
+            "COB".to_string(),
+            800,
+            alice(),
+            &keys.1,
+        )
+        .unwrap();
+    let second = app
+        .create_custom_token(
+            "Zinco".to_string(),
+            "ZNC".to_string(),
+            900,
+            alice(),
+            &keys.1,
+        )
+        .unwrap();
+    assert_eq!(u64::from(first.id), first_id);
+    assert_eq!(u64::from(second.id), first_id + 1);
+
+    app.blockchain.produce_block(10).unwrap();
+    let registered = app.blockchain.get_token(&second.id.to_string()).unwrap();
+    assert_eq!(registered.symbol, "ZNC");
+    assert_eq!(registered.balances[&alice()], 900);
+    assert_eq!(app.blockchain.next_token_id, first_id + 2);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
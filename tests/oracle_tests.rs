@@ -0,0 +1,179 @@
+use kybelith::blockchain::{Blockchain, ORACLE_MAX_AGE_BLOCKS};
+use kybelith::rbac::AdminRole;
+use kybelith::test_utils::fixtures::{commit, fund, Account};
+use kybelith::transaction::{Operation, OperationKind, TransferRule};
+
+fn set_feeder(account: &mut Account, feed_id: u64, feeder: &Account, allowed: bool) -> Operation {
+    account.operation(OperationKind::SetOracleFeeder {
+        feed_id,
+        feeder: feeder.address.clone(),
+        allowed,
+    })
+}
+
+fn feed(account: &mut Account, feed_id: u64, value: u64) -> Operation {
+    account.operation(OperationKind::SubmitOracleData { feed_id, value })
+}
+
+/// Recusa transferências acima do valor do oráculo 7, ou todas enquanto ele não
+/// tem valor (-1)
+const ORACLE_CAP_CONTRACT: &str = r#"
+(module
+  (import "kybelith" "oracle_value" (func $oracle (param i64) (result i64)))
+  (func (export "check_transfer") (param i64 i32 i32) (result i32)
+    (i64.gt_s (local.get 0) (call $oracle (i64.const 7)))))
+"#;
+
+fn sorted(mut addresses: Vec<String>) -> Vec<String> {
+    addresses.sort();
+    addresses
+}
+
+#[test]
+fn test_whitelisted_feeders_aggregate_median_without_outliers() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut admin = Account::new();
+    let mut outsider = Account::new();
+    let mut feeders = [Account::new(), Account::new(), Account::new()];
+    blockchain.bootstrap_admin(admin.address.clone()).unwrap();
+
+    assert!(blockchain
+        .submit_operation(set_feeder(&mut outsider, 1, &feeders[0], true))
+        .is_err());
+    outsider.nonce -= 1;
+    let grants: Vec<_> = feeders
+        .iter()
+        .map(|feeder| set_feeder(&mut admin, 1, feeder, true))
+        .collect();
+    commit(&mut blockchain, grants);
+    assert_eq!(blockchain.oracle_feed(1).unwrap().feeders.len(), 3);
+
+    assert!(blockchain
+        .submit_operation(feed(&mut outsider, 1, 1))
+        .is_err());
+    outsider.nonce -= 1;
+
+    // Com menos de três pontos recentes não há valor agregado
+    let first = vec![
+        feed(&mut feeders[0], 1, 1_000),
+        feed(&mut feeders[1], 1, 1_010),
+    ];
+    commit(&mut blockchain, first);
+    assert_eq!(blockchain.oracle_value(1), None);
+
+    let outlier = feed(&mut feeders[2], 1, 5_000);
+    commit(&mut blockchain, vec![outlier]);
+    let aggregate = blockchain
+        .oracle_feed(1)
+        .unwrap()
+        .aggregate
+        .clone()
+        .unwrap();
+    assert_eq!(aggregate.value, 1_005);
+    assert_eq!(aggregate.height, 2);
+    assert_eq!(
+        aggregate.sources,
+        sorted(vec![feeders[0].address.clone(), feeders[1].address.clone()])
+    );
+    assert_eq!(aggregate.outliers, [feeders[2].address.clone()]);
+
+    // Quem perde a habilitação sai da agregação e não envia mais pontos
+    let revoke = set_feeder(&mut admin, 1, &feeders[2], false);
+    commit(&mut blockchain, vec![revoke]);
+    assert!(!blockchain
+        .oracle_feed(1)
+        .unwrap()
+        .points
+        .contains_key(&feeders[2].address));
+    assert!(blockchain
+        .submit_operation(feed(&mut feeders[2], 1, 1_000))
+        .is_err());
+    feeders[2].nonce -= 1;
+
+    let path =
+        std::env::temp_dir().join(format!("kybelith-oracle-{}-feeds.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    blockchain.save_to_db(path.to_str().unwrap()).unwrap();
+    let restored = Blockchain::load_from_db(path.to_str().unwrap()).unwrap();
+    assert_eq!(restored.oracle_feeds, blockchain.oracle_feeds);
+    let _ = std::fs::remove_file(&path);
+
+    // O ponto de altura 1 envelhece: com só dois pontos recentes o valor anterior
+    // é mantido
+    for value in 0..ORACLE_MAX_AGE_BLOCKS {
+        let refresh = feed(&mut feeders[0], 1, 1_000 + value);
+        commit(&mut blockchain, vec![refresh]);
+    }
+    let regrant = set_feeder(&mut admin, 1, &feeders[2], true);
+    commit(&mut blockchain, vec![regrant]);
+    let late = feed(&mut feeders[2], 1, 2_000);
+    commit(&mut blockchain, vec![late]);
+    assert_eq!(blockchain.oracle_feed(1).unwrap().points.len(), 3);
+    assert_eq!(blockchain.oracle_value(1), Some(1_005));
+    assert_eq!(blockchain.oracle_values().get(&1), Some(&1_005));
+}
+
+#[test]
+fn test_contract_policy_reads_oracle_through_host_function() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut admin = Account::new();
+    let holder = Account::new();
+    let mut feeders = [Account::new(), Account::new(), Account::new()];
+    blockchain.bootstrap_admin(admin.address.clone()).unwrap();
+    fund(&mut blockchain, &admin.address, 100);
+
+    let token_id = blockchain.next_token_id;
+    let mut setup = vec![
+        admin.operation(OperationKind::AssignRole {
+            subject: admin.address.clone(),
+            role: AdminRole::Operator,
+            granted: true,
+        }),
+        admin.operation(OperationKind::CreateToken {
+            token_id,
+            name: "Lastreado".to_string(),
+            symbol: "ORC".to_string(),
+            total_supply: 10_000,
+        }),
+    ];
+    setup.extend(
+        feeders
+            .iter()
+            .map(|feeder| set_feeder(&mut admin, 7, feeder, true)),
+    );
+    commit(&mut blockchain, setup);
+    let policy = admin.operation(OperationKind::SetTransferPolicy {
+        token_id,
+        rules: vec![TransferRule::Contract {
+            code: ORACLE_CAP_CONTRACT.as_bytes().to_vec(),
+        }],
+    });
+    commit(&mut blockchain, vec![policy]);
+
+    // Sem valor no oráculo o contrato recusa tudo
+    let blocked = admin.transfer_token(token_id, &holder.address, 1);
+    blockchain.submit_transaction(blocked).unwrap();
+    blockchain.produce_block(10).unwrap();
+    let balance = |blockchain: &Blockchain| {
+        blockchain
+            .get_token(&token_id.to_string())
+            .unwrap()
+            .balance_of(&holder.address)
+    };
+    assert_eq!(balance(&blockchain), 0);
+
+    let points = vec![
+        feed(&mut feeders[0], 7, 100),
+        feed(&mut feeders[1], 7, 104),
+        feed(&mut feeders[2], 7, 900),
+    ];
+    commit(&mut blockchain, points);
+    assert_eq!(blockchain.oracle_value(7), Some(102));
+
+    for amount in [103, 102] {
+        let tx = admin.transfer_token(token_id, &holder.address, amount);
+        blockchain.submit_transaction(tx).unwrap();
+        blockchain.produce_block(10).unwrap();
+    }
+    assert_eq!(balance(&blockchain), 102);
+}
@@ -0,0 +1,181 @@
+use kybelith::blockchain::{Block, Blockchain};
+use kybelith::consensus::types::ConsensusError;
+use kybelith::consensus::{validate_payload, vote_on_proposal, BlockProposal};
+use kybelith::network::NodeIdentity;
+use kybelith::test_utils::fixtures::{address_of, balance, fund, transfer};
+use kybelith::transaction::SecureTransaction;
+use kybelith::utils::address::derive_address;
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+struct Fixture {
+    blockchain: Blockchain,
+    proposer: Arc<NodeIdentity>,
+    block: Block,
+}
+
+fn fixture(amount: u64) -> Fixture {
+    let keys = keypair();
+    let sender = derive_address(keys.0.as_bytes());
+    let proposer = Arc::new(NodeIdentity::generate());
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain.set_signer(Arc::clone(&proposer));
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(sender.clone(), 500);
+
+    let transaction = SecureTransaction::new(
+        sender,
+        "b".repeat(40),
+        amount,
+        chrono::Utc::now().timestamp(),
+        1,
+        &keys.1,
+        &keys.0,
+    )
+    .unwrap();
+    let mut block = Block::new(0, vec![transaction], Vec::new(), "0".repeat(64)).unwrap();
+    block.sign(&proposer);
+
+    Fixture {
+        blockchain,
+        proposer,
+        block,
+    }
+}
+
+fn body(block: &Block) -> HashMap<String, Block> {
+    HashMap::from([(block.hash.clone(), block.clone())])
+}
+
+#[test]
+fn test_valid_payload_gets_signed_favorable_vote() {
+    let f = fixture(100);
+    let proposal = BlockProposal::for_block(&f.block).unwrap();
+    let voter = NodeIdentity::generate();
+
+    validate_payload(&f.blockchain, &proposal, &f.block).unwrap();
+    let vote = vote_on_proposal(
+        &f.blockchain,
+        &proposal,
+        &body(&f.block),
+        "v1".to_string(),
+        &voter,
+    )
+    .unwrap();
+
+    assert!(vote.is_in_favor);
+    assert_eq!(vote.block_hash, f.block.hash);
+    assert!(vote.verify_signature(voter.public_key.as_bytes()).is_ok());
+    assert!(vote
+        .verify_signature(f.proposer.public_key.as_bytes())
+        .is_err());
+}
+
+#[test]
+fn test_missing_body_produces_no_vote() {
+    let f = fixture(100);
+    let proposal = BlockProposal::for_block(&f.block).unwrap();
+
+    let result = vote_on_proposal(
+        &f.blockchain,
+        &proposal,
+        &HashMap::new(),
+        "v1".to_string(),
+        &NodeIdentity::generate(),
+    );
+    assert!(matches!(result, Err(ConsensusError::ValidationFailed(_))));
+}
+
+#[test]
+fn test_invalid_payloads_get_unfavorable_votes() {
+    let voter = NodeIdentity::generate();
+
+    // Transações diferentes das listadas na proposta
+    let f = fixture(100);
+    let mut proposal = BlockProposal::for_block(&f.block).unwrap();
+    proposal.transaction_hashes = vec!["00".repeat(32)];
+    assert!(matches!(
+        validate_payload(&f.blockchain, &proposal, &f.block),
+        Err(ConsensusError::InvalidBlock(_))
+    ));
+    let vote = vote_on_proposal(
+        &f.blockchain,
+        &proposal,
+        &body(&f.block),
+        "v1".to_string(),
+        &voter,
+    )
+    .unwrap();
+    assert!(!vote.is_in_favor);
+    assert!(vote.verify_signature(voter.public_key.as_bytes()).is_ok());
+
+    // Assinatura de transação adulterada
+    let mut f = fixture(100);
+    f.block.transactions[0].signature[0] ^= 0xff;
+    let proposal = BlockProposal::for_block(&f.block).unwrap();
+    assert!(matches!(
+        validate_payload(&f.blockchain, &proposal, &f.block),
+        Err(ConsensusError::ValidationFailed(_))
+    ));
+
+    // Transição de estado inválida: saldo insuficiente
+    let f = fixture(10_000);
+    let proposal = BlockProposal::for_block(&f.block).unwrap();
+    let vote = vote_on_proposal(
+        &f.blockchain,
+        &proposal,
+        &body(&f.block),
+        "v1".to_string(),
+        &voter,
+    )
+    .unwrap();
+    assert!(!vote.is_in_favor);
+}
+
+#[test]
+fn test_block_body_is_reexecuted_before_voting() {
+    let keys = keypair();
+    let sender = address_of(&keys);
+    let proposer = Arc::new(NodeIdentity::generate());
+    let node = |funds: u64| {
+        let mut blockchain = Blockchain::new().unwrap();
+        blockchain.set_signer(Arc::clone(&proposer));
+        fund(&mut blockchain, &sender, funds);
+        blockchain
+    };
+
+    let mut producer = node(100);
+    producer.submit_transaction(transfer(&keys, 40, 1)).unwrap();
+    let block = producer.produce_block(10).unwrap().unwrap();
+    let proposal = BlockProposal::for_block(&block).unwrap();
+
+    // A reexecução usa uma cópia: o estado do validador não muda
+    let validator = node(100);
+    validate_payload(&validator, &proposal, &block).unwrap();
+    assert_eq!(balance(&validator, 0, &sender), 100);
+    assert!(!validator.nonces.contains_key(&sender));
+
+    // Sem saldo para a transferência, o resumo do proponente não se confirma
+    let poorer = node(10);
+    assert!(matches!(
+        validate_payload(&poorer, &proposal, &block),
+        Err(ConsensusError::ValidationFailed(_))
+    ));
+
+    // Transferência adulterada e reassinada pelo proponente: a assinatura da
+    // transação não confere e o bloco é recusado
+    let mut forged = block.clone();
+    forged.transfers[0].amount = 41;
+    forged.sign(&proposer);
+    let proposal = BlockProposal::for_block(&forged).unwrap();
+    assert!(matches!(
+        validate_payload(&validator, &proposal, &forged),
+        Err(ConsensusError::ValidationFailed(_))
+    ));
+}
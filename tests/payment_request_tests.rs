@@ -0,0 +1,57 @@
+use kybelith::constants::MAX_AMOUNT;
+use kybelith::utils::address::{derive_address, Address};
+use kybelith::wallet::PaymentRequest;
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+
+fn address() -> Address {
+    Address::parse(&derive_address(keypair().0.as_bytes())).unwrap()
+}
+
+#[test]
+fn test_payment_request_round_trip() {
+    let address = address();
+    let request = PaymentRequest::new(address.clone())
+        .amount(1_500)
+        .token_id(2)
+        .memo("Café & pão #42");
+    let uri = request.to_uri().unwrap();
+    assert_eq!(
+        uri,
+        format!(
+            "qst:{}?amount=1500&token=2&memo=Caf%C3%A9%20%26%20p%C3%A3o%20%2342",
+            address.as_str()
+        )
+    );
+    assert_eq!(PaymentRequest::parse(&uri).unwrap(), request);
+
+    // Só o endereço, e esquema em maiúsculas como em QR codes alfanuméricos
+    let bare: PaymentRequest = format!("QST:{}", address.as_str()).parse().unwrap();
+    assert_eq!(bare, PaymentRequest::new(address.clone()));
+    assert_eq!(bare.to_uri().unwrap(), format!("qst:{}", address.as_str()));
+
+    // Parâmetros desconhecidos opcionais são ignorados
+    let extra = format!("qst:{}?label=loja&amount=7", address.as_str());
+    assert_eq!(PaymentRequest::parse(&extra).unwrap().amount, Some(7));
+}
+
+#[test]
+fn test_payment_request_rejects_invalid_uris() {
+    let address = address();
+    let address = address.as_str();
+    for uri in [
+        format!("bitcoin:{}", address),
+        "qst:endereço-inválido".to_string(),
+        format!("qst:{}?amount=0", address),
+        format!("qst:{}?amount={}", address, MAX_AMOUNT + 1),
+        format!("qst:{}?amount=-5", address),
+        format!("qst:{}?amount=1&amount=2", address),
+        format!("qst:{}?token=x", address),
+        format!("qst:{}?memo=%E2%28", address),
+        format!("qst:{}?memo=%G1", address),
+        format!("qst:{}?memo={}", address, "a".repeat(300)),
+        format!("qst:{}?req-expires=10", address),
+    ] {
+        assert!(PaymentRequest::parse(&uri).is_err(), "{}", uri);
+    }
+}
@@ -0,0 +1,88 @@
+use kybelith::config::Settings;
+use kybelith::network::{PeerDiscovery, PeerSource, PeerStore};
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("kybelith-peers-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn test_peer_store_scores_and_persists() {
+    let path = temp_dir("store").join("peers.json");
+    let _ = std::fs::remove_file(&path);
+
+    let mut store = PeerStore::open(&path).unwrap();
+    assert!(store.is_empty());
+    assert!(store.add("10.0.0.1:8000", PeerSource::Gossip));
+    assert!(store.add("10.0.0.2:8000", PeerSource::Gossip));
+    assert!(store.add("10.0.0.3:8000", PeerSource::Manual));
+    assert!(!store.add("10.0.0.1:8000", PeerSource::Mdns));
+
+    store.record_success("10.0.0.2:8000");
+    store.record_success("10.0.0.2:8000");
+    for _ in 0..6 {
+        store.record_failure("10.0.0.3:8000");
+    }
+    store.save().unwrap();
+
+    // Após reiniciar, o melhor peer continua na frente e o ruim fica de fora
+    let reopened = PeerStore::open(&path).unwrap();
+    assert_eq!(reopened.len(), 3);
+    let best: Vec<&str> = reopened
+        .best_peers(10)
+        .iter()
+        .map(|record| record.address.as_str())
+        .collect();
+    assert_eq!(best, vec!["10.0.0.2:8000", "10.0.0.1:8000"]);
+    let record = reopened.get("10.0.0.2:8000").unwrap();
+    assert_eq!(record.successes, 2);
+    assert!(record.last_seen.is_some());
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn test_peer_store_capacity_keeps_bootstrap_nodes() {
+    let mut store = PeerStore::new().with_capacity(2);
+    store.add("seed:8000", PeerSource::Bootstrap);
+    store.add("a:8000", PeerSource::Gossip);
+    store.record_success("a:8000");
+    store.add("b:8000", PeerSource::Gossip);
+
+    assert_eq!(store.len(), 2);
+    assert!(store.get("seed:8000").is_some());
+    assert!(store.get("a:8000").is_some());
+    assert!(store.get("b:8000").is_none());
+}
+
+#[test]
+fn test_discovery_prefers_known_peers_and_falls_back_to_bootstrap() {
+    let dir = temp_dir("discovery");
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut settings = Settings::default();
+    settings.node.data_dir = dir.to_string_lossy().into_owned();
+    settings.p2p.bootstrap_nodes = vec!["seed1:8000".to_string(), "seed2:8000".to_string()];
+    settings.p2p.max_outgoing_connections = 4;
+
+    let mut discovery = PeerDiscovery::from_settings(&settings).unwrap();
+    assert_eq!(discovery.store().len(), 2);
+    assert_eq!(discovery.poll_mdns(), 0);
+
+    assert!(discovery.add_gossiped("10.0.0.7:8000"));
+    discovery.record_success("10.0.0.7:8000");
+    discovery.add_gossiped("10.0.0.8:8000");
+    for _ in 0..6 {
+        discovery.record_failure("seed2:8000");
+    }
+    discovery.save().unwrap();
+
+    // O nó reinicia com o mesmo data_dir e reconecta primeiro ao peer conhecido
+    let restarted = PeerDiscovery::from_settings(&settings).unwrap();
+    let candidates = restarted.dial_candidates();
+    assert_eq!(candidates[0], "10.0.0.7:8000");
+    // seed2 saiu do ranking, mas bootstrap completa as vagas restantes
+    assert_eq!(candidates.len(), 4);
+    assert_eq!(candidates[3], "seed2:8000");
+    assert!(settings.peer_store_path().exists());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
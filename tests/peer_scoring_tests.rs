@@ -0,0 +1,104 @@
+use kybelith::config::Settings;
+use kybelith::network::{
+    peer_host, BanList, Misbehavior, PeerDiscovery, PeerScoring, PeerScoringConfig, PeerVerdict,
+    TransportError,
+};
+use std::time::Duration;
+
+#[test]
+fn test_misbehavior_disconnects_then_bans_with_escalation() {
+    let config = PeerScoringConfig {
+        initial_ban_duration: Duration::from_secs(60),
+        ..PeerScoringConfig::default()
+    };
+    let mut scoring = PeerScoring::with_config(BanList::new(), config);
+    let now = 1_700_000_000;
+
+    assert_eq!(
+        scoring.report_at("10.0.0.9:8000", Misbehavior::ValidMessage, now),
+        PeerVerdict::Keep
+    );
+    assert_eq!(
+        scoring.report_at("10.0.0.9:8000", Misbehavior::InvalidBlock, now),
+        PeerVerdict::Keep
+    );
+    scoring.report_at("10.0.0.9:8000", Misbehavior::InvalidBlock, now);
+    assert_eq!(scoring.score("10.0.0.9:8000"), 50.0);
+    assert_eq!(
+        scoring.report_at("10.0.0.9:8000", Misbehavior::InvalidBlock, now),
+        PeerVerdict::Disconnect
+    );
+    // A reconexão por outra porta mantém a pontuação do host
+    assert_eq!(
+        scoring.report_at("10.0.0.9:51234", Misbehavior::ProtocolViolation, now),
+        PeerVerdict::Ban { until: now + 60 }
+    );
+    assert!(scoring.bans().is_banned_at("10.0.0.9", now + 59));
+    assert!(!scoring.bans().is_banned_at("10.0.0.9", now + 60));
+
+    // Após o banimento a pontuação recomeça; a reincidência dobra a duração
+    assert_eq!(scoring.score("10.0.0.9:8000"), 100.0);
+    let later = now + 120;
+    scoring.report_at("10.0.0.9:8000", Misbehavior::ProtocolViolation, later);
+    scoring.report_at("10.0.0.9:8000", Misbehavior::ProtocolViolation, later);
+    assert_eq!(
+        scoring.report_at("10.0.0.9:8000", Misbehavior::Spam, later),
+        PeerVerdict::Ban { until: later + 120 }
+    );
+    assert_eq!(scoring.bans().get("10.0.0.9").unwrap().ban_count, 2);
+}
+
+#[test]
+fn test_ban_list_persists_and_expires() {
+    let path = std::env::temp_dir().join(format!("kybelith-bans-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut bans = BanList::open(&path).unwrap();
+    bans.ban("10.0.0.1", "Spam", 2_000);
+    bans.ban("10.0.0.2", "ProtocolViolation", 1_000);
+    bans.save().unwrap();
+
+    let reopened = BanList::open(&path).unwrap();
+    assert_eq!(reopened.len(), 2);
+    assert!(reopened.is_banned_at("10.0.0.1", 1_500));
+    assert!(!reopened.is_banned_at("10.0.0.2", 1_500));
+    assert_eq!(reopened.active_at(1_500).len(), 1);
+    assert_eq!(reopened.get("10.0.0.1").unwrap().reason, "Spam");
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_discovery_skips_banned_peers_across_restarts() {
+    let dir = std::env::temp_dir().join(format!("kybelith-peer-bans-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut settings = Settings::default();
+    settings.node.data_dir = dir.to_string_lossy().into_owned();
+    settings.p2p.bootstrap_nodes = vec!["192.168.1.10:8000".to_string()];
+
+    let mut discovery = PeerDiscovery::from_settings(&settings).unwrap();
+    discovery.add_gossiped("192.168.1.20:8000");
+    assert!(discovery.allows("192.168.1.20:8000"));
+
+    let violation = TransportError::Decryption.misbehavior().unwrap();
+    let mut verdict = PeerVerdict::Keep;
+    while verdict == PeerVerdict::Keep || verdict == PeerVerdict::Disconnect {
+        verdict = discovery.report("192.168.1.20:40000", violation);
+    }
+    assert!(matches!(verdict, PeerVerdict::Ban { .. }));
+    assert!(!discovery.allows("192.168.1.20:8000"));
+    assert!(!discovery
+        .dial_candidates()
+        .contains(&"192.168.1.20:8000".to_string()));
+
+    // A lista de banimentos sobrevive ao reinício do nó
+    let restarted = PeerDiscovery::from_settings(&settings).unwrap();
+    assert!(!restarted.allows("192.168.1.20:8000"));
+    assert_eq!(restarted.dial_candidates(), vec!["192.168.1.10:8000"]);
+    assert!(TransportError::Io(std::io::ErrorKind::UnexpectedEof.into())
+        .misbehavior()
+        .is_none());
+    assert_eq!(peer_host("[::1]:8000"), "::1");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
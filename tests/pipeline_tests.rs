@@ -0,0 +1,122 @@
+use kybelith::blockchain::{Blockchain, SharedBlockchain};
+use kybelith::error::TransactionError;
+use kybelith::test_utils::fixtures::{address_of, transfer};
+use kybelith::transaction::{PipelineConfig, TransactionPipeline};
+use pqcrypto_dilithium::dilithium5::keypair;
+
+fn new_shared() -> SharedBlockchain {
+    SharedBlockchain::new(Blockchain::new().expect("blockchain deve ser criada"))
+}
+
+#[tokio::test]
+async fn test_pipeline_accepts_valid_transaction() {
+    let shared = new_shared();
+    let pipeline = TransactionPipeline::start(shared.clone(), PipelineConfig::default());
+    let tx = transfer(&keypair(), 100, 1);
+
+    pipeline.submit_transaction(&tx).await.unwrap();
+
+    assert_eq!(shared.pending_count(), 1);
+
+    let metrics = pipeline.metrics();
+    assert_eq!(metrics.accepted, 1);
+    assert_eq!(metrics.rejected, 0);
+    assert_eq!(metrics.decode_depth + metrics.check_depth, 0);
+    assert_eq!(metrics.verify_depth + metrics.insert_depth, 0);
+}
+
+#[tokio::test]
+async fn test_pipeline_rejects_at_each_stage() {
+    let shared = new_shared();
+    let pipeline = TransactionPipeline::start(shared.clone(), PipelineConfig::default());
+    let keys = keypair();
+
+    // Decodificação
+    let result = pipeline.submit(vec![0xff; 8]).await;
+    assert!(matches!(result, Err(TransactionError::InvalidDataFormat)));
+
+    // Verificações baratas
+    let mut tx = transfer(&keys, 100, 1);
+    tx.amount = 0;
+    assert!(pipeline.submit_transaction(&tx).await.is_err());
+
+    // Assinatura
+    let mut tx = transfer(&keys, 100, 1);
+    tx.amount += 1;
+    assert!(pipeline.submit_transaction(&tx).await.is_err());
+
+    // Mempool (nonce fora de sequência)
+    let tx = transfer(&keys, 100, 3);
+    let result = pipeline.submit_transaction(&tx).await;
+    assert!(matches!(result, Err(TransactionError::NonceInvalido)));
+
+    assert_eq!(pipeline.metrics().rejected, 4);
+    assert_eq!(shared.pending_count(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_pipeline_preserves_nonce_order() {
+    let shared = new_shared();
+    let config = PipelineConfig {
+        verify_workers: 4,
+        ..PipelineConfig::default()
+    };
+    let pipeline = TransactionPipeline::start(shared.clone(), config);
+    let keys = keypair();
+
+    let receivers: Vec<_> = (1..=6)
+        .map(|nonce| {
+            let data = bincode::serialize(&transfer(&keys, 100, nonce)).unwrap();
+            pipeline.try_submit(data).unwrap()
+        })
+        .collect();
+
+    for receiver in receivers {
+        let result = receiver.await.unwrap();
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    assert_eq!(shared.pending_count(), 6);
+    assert_eq!(shared.nonce_of(&address_of(&keys)), 6);
+}
+
+#[tokio::test]
+async fn test_pipeline_reports_busy_when_saturated() {
+    let shared = new_shared();
+    let config = PipelineConfig {
+        decode_queue: 2,
+        ..PipelineConfig::default()
+    };
+    let pipeline = TransactionPipeline::start(shared, config);
+    let data = bincode::serialize(&transfer(&keypair(), 100, 1)).unwrap();
+
+    // No runtime de thread única os estágios só avançam quando o teste cede a vez
+    let first = pipeline.try_submit(data.clone()).unwrap();
+    let _second = pipeline.try_submit(data.clone()).unwrap();
+    let third = pipeline.try_submit(data);
+
+    assert!(matches!(third, Err(TransactionError::Busy)));
+    assert_eq!(pipeline.metrics().busy, 1);
+    assert_eq!(pipeline.metrics().decode_depth, 2);
+
+    assert!(first.await.unwrap().is_ok());
+}
+
+#[tokio::test]
+async fn test_pipeline_follows_reloaded_limits() {
+    let shared = new_shared();
+    let pipeline = TransactionPipeline::start(shared.clone(), PipelineConfig::default());
+    let keys = keypair();
+
+    pipeline
+        .submit_transaction(&transfer(&keys, 100, 1))
+        .await
+        .unwrap();
+
+    // Recarga de configuração com o pipeline já em execução
+    shared.write(|blockchain| blockchain.limits.max_transaction_size = 16);
+
+    let result = pipeline.submit_transaction(&transfer(&keys, 100, 2)).await;
+    assert!(result.is_err());
+    assert_eq!(shared.pending_count(), 1);
+}
@@ -0,0 +1,103 @@
+use arbitrary::{Arbitrary, Unstructured};
+use kybelith::blockchain::Block;
+use kybelith::consensus::{BlockProposal, ConsensusMessage};
+use kybelith::test_utils::strategies;
+use kybelith::transaction::{NonceRegistry, Transaction};
+use pqcrypto_dilithium::dilithium5::PublicKey;
+use pqcrypto_traits::sign::PublicKey as _;
+use proptest::prelude::*;
+
+proptest! {
+    // Assinaturas Dilithium5 são caras: poucos casos por propriedade
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn prop_signed_transaction_validates_and_verifies(tx in strategies::signed_transaction()) {
+        let public_key = PublicKey::from_bytes(&tx.public_key).unwrap();
+        let mut registry = NonceRegistry::new();
+
+        prop_assert!(tx.verify(&public_key).is_ok());
+        prop_assert!(tx.validate(&mut registry).is_ok());
+    }
+
+    #[test]
+    fn prop_tampered_amount_breaks_signature(tx in strategies::signed_transaction()) {
+        let public_key = PublicKey::from_bytes(&tx.public_key).unwrap();
+        let mut tampered = tx.clone();
+        tampered.amount = tx.amount.wrapping_add(1);
+
+        prop_assert!(tampered.verify(&public_key).is_err());
+    }
+
+    #[test]
+    fn prop_transaction_encoding_roundtrip(tx in strategies::transaction()) {
+        let bytes = bincode::serialize(&tx).unwrap();
+        let decoded = Transaction::deserialize_from_bytes(&bytes).unwrap();
+
+        prop_assert_eq!(decoded.from, tx.from.clone());
+        prop_assert_eq!(decoded.to, tx.to.clone());
+        prop_assert_eq!(decoded.amount, tx.amount);
+        prop_assert_eq!(decoded.nonce, tx.nonce);
+    }
+
+    #[test]
+    fn prop_block_hash_is_consistent(block in strategies::block(2)) {
+        let recalculated = block.compute_hash().unwrap();
+        prop_assert_eq!(&recalculated, &block.hash);
+
+        let decoded = Block::from_bytes(&block.to_bytes().unwrap()).unwrap();
+        prop_assert_eq!(decoded.hash, block.hash);
+        prop_assert_eq!(decoded.transactions.len(), block.transactions.len());
+    }
+
+    #[test]
+    fn prop_token_transfer_preserves_supply(
+        token in strategies::token(),
+        to in strategies::address(),
+        fraction in 1u64..=100,
+    ) {
+        let mut token = token;
+        let amount = (token.total_supply / 100).max(1) * fraction;
+        prop_assume!(amount <= token.total_supply);
+        prop_assume!(to != token.creator);
+
+        token.transfer(&to, amount).unwrap();
+
+        let sum: u64 = token.balances.values().sum();
+        prop_assert_eq!(sum, token.total_supply);
+        prop_assert_eq!(token.balance_of(&to), amount);
+    }
+}
+
+proptest! {
+    #[test]
+    fn prop_proposal_encoding_roundtrip(proposal in strategies::block_proposal()) {
+        let decoded = BlockProposal::from_bytes(&proposal.to_bytes().unwrap()).unwrap();
+
+        prop_assert_eq!(decoded.block_hash, proposal.block_hash.clone());
+        prop_assert_eq!(decoded.block_height, proposal.block_height);
+        prop_assert_eq!(decoded.transaction_hashes, proposal.transaction_hashes.clone());
+    }
+
+    #[test]
+    fn prop_arbitrary_values_survive_encoding(raw in proptest::collection::vec(any::<u8>(), 0..2048)) {
+        let mut u = Unstructured::new(&raw);
+
+        if let Ok(block) = Block::arbitrary(&mut u) {
+            if let Ok(bytes) = block.to_bytes() {
+                // Blocos arbitrários podem exceder o limite, mas nunca causar pânico
+                let _ = Block::from_bytes(&bytes);
+            }
+        }
+
+        if let Ok(tx) = Transaction::arbitrary(&mut u) {
+            let bytes = bincode::serialize(&tx).unwrap();
+            let _ = Transaction::deserialize_from_bytes(&bytes);
+        }
+
+        if let Ok(ConsensusMessage::NewBlockProposal(proposal)) = ConsensusMessage::arbitrary(&mut u) {
+            let decoded = BlockProposal::from_bytes(&proposal.to_bytes().unwrap()).unwrap();
+            prop_assert_eq!(decoded.timestamp, proposal.timestamp);
+        }
+    }
+}
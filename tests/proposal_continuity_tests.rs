@@ -0,0 +1,107 @@
+use kybelith::consensus::types::VerificationResult;
+use kybelith::consensus::{
+    BlockProposal, ChainTip, ProposalVerifier, ReputationSystem, Validator, ValidatorSet,
+};
+use std::sync::{Arc, RwLock};
+
+fn verifier() -> (ProposalVerifier, Arc<RwLock<ReputationSystem>>) {
+    let validators = ValidatorSet::new(vec![Validator::new(
+        "v1".to_string(),
+        "addr-v1".to_string(),
+        vec![1; 32],
+        1_000,
+    )]);
+    let mut reputation = ReputationSystem::new();
+    reputation.add_validator("v1".to_string());
+    let reputation = Arc::new(RwLock::new(reputation));
+    let verifier =
+        ProposalVerifier::new(Arc::new(RwLock::new(validators)), Arc::clone(&reputation)).with_tip(
+            ChainTip {
+                height: 20,
+                hash: "tip".to_string(),
+            },
+        );
+    (verifier, reputation)
+}
+
+fn proposal(height: u64, parent: &str) -> BlockProposal {
+    BlockProposal::new(
+        format!("hash-{}", height),
+        height,
+        parent.to_string(),
+        "v1".to_string(),
+        Vec::new(),
+        vec![1],
+        Vec::new(),
+    )
+}
+
+fn score(reputation: &Arc<RwLock<ReputationSystem>>) -> f32 {
+    reputation
+        .read()
+        .unwrap()
+        .get_reputation("v1")
+        .unwrap()
+        .score
+}
+
+fn rejection(verifier: &ProposalVerifier, proposal: &BlockProposal) -> String {
+    match verifier.verify(proposal).unwrap() {
+        VerificationResult::Invalid(reason) => reason,
+        other => panic!("proposta deveria ser rejeitada: {:?}", other),
+    }
+}
+
+#[test]
+fn test_proposal_extending_tip_accepted() {
+    let (verifier, _) = verifier();
+    assert!(matches!(
+        verifier.verify(&proposal(21, "tip")).unwrap(),
+        VerificationResult::Valid
+    ));
+}
+
+#[test]
+fn test_stale_forked_and_future_proposals_rejected() {
+    let (verifier, reputation) = verifier();
+    let initial = score(&reputation);
+
+    let stale = rejection(&verifier, &proposal(20, "tip"));
+    let forked = rejection(&verifier, &proposal(21, "outro"));
+    let ahead = rejection(&verifier, &proposal(23, "tip"));
+    assert!(stale.contains("já confirmada"));
+    assert!(forked.contains("Bloco pai"));
+    assert!(ahead.contains("à frente"));
+
+    // Desvios pequenos podem ser atraso da rede e não afetam a reputação
+    assert_eq!(score(&reputation), initial);
+}
+
+#[test]
+fn test_grossly_invalid_height_penalized() {
+    let (verifier, reputation) = verifier();
+    let initial = score(&reputation);
+
+    rejection(&verifier, &proposal(21 + 500, "tip"));
+    let after_future = score(&reputation);
+    assert!(after_future < initial);
+
+    rejection(&verifier, &proposal(1, "tip"));
+    assert!(score(&reputation) < after_future);
+}
+
+#[test]
+fn test_without_tip_continuity_not_checked() {
+    let (_, reputation) = verifier();
+    let validators = ValidatorSet::new(vec![Validator::new(
+        "v1".to_string(),
+        "addr-v1".to_string(),
+        vec![1; 32],
+        1_000,
+    )]);
+    let verifier = ProposalVerifier::new(Arc::new(RwLock::new(validators)), reputation);
+    assert!(matches!(
+        verifier.verify(&proposal(500, "qualquer")).unwrap(),
+        VerificationResult::Valid
+    ));
+}
@@ -0,0 +1,179 @@
+use kybelith::blockchain::{Block, Blockchain, CheckpointAttestation, SignatureArchive};
+use kybelith::error::Error;
+use kybelith::transaction::SecureTransaction;
+use kybelith::utils::address::derive_address;
+use pqcrypto_dilithium::dilithium5::{keypair, PublicKey, SecretKey};
+use pqcrypto_traits::sign::PublicKey as _;
+
+fn build_chain(blocks: u64, keys: &(PublicKey, SecretKey)) -> Blockchain {
+    let timestamp = chrono::Utc::now().timestamp();
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .chain
+        .push(Block::new(0, Vec::new(), Vec::new(), "0".repeat(64)).unwrap());
+
+    for index in 1..=blocks {
+        let transactions: Vec<SecureTransaction> = (1..=2)
+            .map(|nonce| {
+                SecureTransaction::new(
+                    derive_address(keys.0.as_bytes()),
+                    "b".repeat(40),
+                    100,
+                    timestamp,
+                    index * 10 + nonce,
+                    &keys.1,
+                    &keys.0,
+                )
+                .unwrap()
+            })
+            .collect();
+        let previous_hash = blockchain.chain.last().unwrap().hash.clone();
+        blockchain
+            .chain
+            .push(Block::new(index, transactions, Vec::new(), previous_hash).unwrap());
+    }
+
+    blockchain
+}
+
+#[test]
+fn test_prune_below_checkpoint_keeps_chain_valid() {
+    let tx_keys = keypair();
+    let validator = keypair();
+    let mut blockchain = build_chain(3, &tx_keys);
+
+    blockchain.register_validator_key(validator.0.as_bytes().to_vec());
+
+    let attestation =
+        CheckpointAttestation::create(&blockchain, 2, &validator.0, &validator.1).unwrap();
+    let pruned = blockchain
+        .prune_signatures(attestation, &validator.0)
+        .unwrap();
+
+    assert_eq!(pruned, 4);
+    assert!(blockchain.is_pruned(1) && blockchain.is_pruned(2));
+    assert!(!blockchain.is_pruned(3));
+    assert!(blockchain.chain[1].transactions[0].signature.is_empty());
+    assert!(!blockchain.chain[3].transactions[0].signature.is_empty());
+    assert!(blockchain.is_chain_valid().unwrap());
+}
+
+#[test]
+fn test_prune_rejects_foreign_or_mismatched_attestation() {
+    let tx_keys = keypair();
+    let validator = keypair();
+    let mut blockchain = build_chain(2, &tx_keys);
+
+    blockchain.register_validator_key(validator.0.as_bytes().to_vec());
+    let other = keypair();
+    blockchain.register_validator_key(other.0.as_bytes().to_vec());
+
+    let attestation =
+        CheckpointAttestation::create(&blockchain, 2, &validator.0, &validator.1).unwrap();
+    assert!(matches!(
+        blockchain.prune_signatures(attestation.clone(), &other.0),
+        Err(Error::InvalidPublicKey)
+    ));
+
+    let mut forged = attestation;
+    forged.chain_digest = "00".repeat(32);
+    assert!(blockchain.prune_signatures(forged, &validator.0).is_err());
+    assert!(!blockchain.is_pruned(1));
+}
+
+#[test]
+fn test_pruned_chain_requires_validator_checkpoint() {
+    let tx_keys = keypair();
+    let validator = keypair();
+    let outsider = keypair();
+    let mut blockchain = build_chain(3, &tx_keys);
+    blockchain.register_validator_key(validator.0.as_bytes().to_vec());
+
+    // Atestado autoassinado por uma chave que não é validadora da cadeia
+    let forged = CheckpointAttestation::create(&blockchain, 2, &outsider.0, &outsider.1).unwrap();
+    assert!(matches!(
+        blockchain.prune_signatures(forged.clone(), &outsider.0),
+        Err(Error::Unauthorized(_))
+    ));
+    assert!(!blockchain.is_pruned(1));
+
+    let attestation =
+        CheckpointAttestation::create(&blockchain, 2, &validator.0, &validator.1).unwrap();
+    blockchain
+        .prune_signatures(attestation.clone(), &validator.0)
+        .unwrap();
+    assert!(blockchain.is_chain_valid().unwrap());
+
+    // Nem um checkpoint de fora gravado direto no estado sustenta a poda
+    blockchain.checkpoint = Some(forged);
+    assert!(!blockchain.is_chain_valid().unwrap());
+
+    blockchain.checkpoint = Some(attestation);
+    assert!(blockchain.is_chain_valid().unwrap());
+
+    // O resumo registrado na poda deixa de corresponder ao checkpoint
+    blockchain.pruned_blocks.insert(1, "00".repeat(32));
+    assert!(!blockchain.is_chain_valid().unwrap());
+}
+
+#[test]
+fn test_checkpoint_for_missing_height_is_rejected() {
+    let validator = keypair();
+    let blockchain = build_chain(1, &keypair());
+
+    assert!(matches!(
+        CheckpointAttestation::create(&blockchain, 5, &validator.0, &validator.1),
+        Err(Error::InvalidBlock(_))
+    ));
+}
+
+#[test]
+fn test_pruned_chain_without_checkpoint_is_invalid() {
+    let tx_keys = keypair();
+    let validator = keypair();
+    let mut blockchain = build_chain(2, &tx_keys);
+    blockchain.register_validator_key(validator.0.as_bytes().to_vec());
+
+    let attestation =
+        CheckpointAttestation::create(&blockchain, 1, &validator.0, &validator.1).unwrap();
+    blockchain
+        .prune_signatures(attestation, &validator.0)
+        .unwrap();
+    blockchain.checkpoint = None;
+
+    assert!(!blockchain.is_chain_valid().unwrap());
+}
+
+#[test]
+fn test_restore_signatures_from_archive() {
+    let tx_keys = keypair();
+    let validator = keypair();
+    let archive = build_chain(2, &tx_keys);
+    let mut blockchain = Blockchain {
+        chain: archive.chain.clone(),
+        ..Blockchain::new().unwrap()
+    };
+    blockchain.register_validator_key(validator.0.as_bytes().to_vec());
+
+    let attestation =
+        CheckpointAttestation::create(&blockchain, 2, &validator.0, &validator.1).unwrap();
+    blockchain
+        .prune_signatures(attestation, &validator.0)
+        .unwrap();
+
+    // Um nó podado não serve como arquivo
+    assert!(blockchain.fetch_signatures(1).is_err());
+
+    blockchain.restore_signatures(1, &archive).unwrap();
+    assert!(!blockchain.is_pruned(1));
+    assert_eq!(
+        blockchain.chain[1].transactions[0].signature,
+        archive.chain[1].transactions[0].signature
+    );
+
+    // Assinaturas adulteradas não conferem com o resumo registrado
+    let mut tampered = build_chain(2, &tx_keys);
+    tampered.chain[2].transactions[0].signature[0] ^= 0xff;
+    assert!(blockchain.restore_signatures(2, &tampered).is_err());
+    assert!(blockchain.is_pruned(2));
+}
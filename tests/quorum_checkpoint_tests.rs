@@ -0,0 +1,149 @@
+use kybelith::blockchain::{
+    transaction_id, Block, Blockchain, CheckpointAttestation, QuorumCheckpoint,
+};
+use kybelith::error::Error;
+use kybelith::transaction::SecureTransaction;
+use pqcrypto_dilithium::dilithium5::{keypair, PublicKey, SecretKey};
+use pqcrypto_traits::sign::PublicKey as _;
+
+fn build_chain(blocks: u64) -> Blockchain {
+    let keys = keypair();
+    let timestamp = chrono::Utc::now().timestamp();
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .chain
+        .push(Block::new(0, Vec::new(), Vec::new(), "0".repeat(64)).unwrap());
+
+    for index in 1..=blocks {
+        let transaction = SecureTransaction::new(
+            "a".repeat(40),
+            "b".repeat(40),
+            100,
+            timestamp,
+            index,
+            &keys.1,
+            &keys.0,
+        )
+        .unwrap();
+        let previous_hash = blockchain.chain.last().unwrap().hash.clone();
+        blockchain
+            .chain
+            .push(Block::new(index, vec![transaction], Vec::new(), previous_hash).unwrap());
+    }
+    blockchain
+}
+
+struct Committee {
+    keys: Vec<(PublicKey, SecretKey)>,
+    public_keys: Vec<Vec<u8>>,
+}
+
+fn committee(size: usize) -> Committee {
+    let keys: Vec<_> = (0..size).map(|_| keypair()).collect();
+    let public_keys = keys.iter().map(|k| k.0.as_bytes().to_vec()).collect();
+    Committee { keys, public_keys }
+}
+
+fn attest(
+    blockchain: &Blockchain,
+    committee: &Committee,
+    signers: &[usize],
+) -> Vec<CheckpointAttestation> {
+    signers
+        .iter()
+        .map(|i| {
+            let (pk, sk) = &committee.keys[*i];
+            CheckpointAttestation::create(blockchain, 2, pk, sk).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_threshold_attestation_round_trip() {
+    let blockchain = build_chain(3);
+    let committee = committee(10);
+    let attestations = attest(&blockchain, &committee, &[0, 3, 9, 3, 5, 7, 8]);
+
+    let quorum = QuorumCheckpoint::aggregate(&attestations, &committee.public_keys, 6).unwrap();
+    assert_eq!(quorum.signer_count(), 6);
+    assert_eq!(quorum.signer_indices(), vec![0, 3, 5, 7, 8, 9]);
+    assert_eq!(quorum.signers.len(), 2);
+    assert_eq!(quorum.signatures.len(), 6);
+
+    let json = serde_json::to_string(&quorum).unwrap();
+    let received: QuorumCheckpoint = serde_json::from_str(&json).unwrap();
+    received.verify(&committee.public_keys, 6).unwrap();
+    assert!(matches!(
+        received.verify(&committee.public_keys, 7),
+        Err(Error::Unauthorized(_))
+    ));
+    assert!(QuorumCheckpoint::aggregate(&attestations, &committee.public_keys, 7).is_err());
+}
+
+#[test]
+fn test_tampered_quorum_rejected() {
+    let blockchain = build_chain(3);
+    let committee = committee(4);
+    let attestations = attest(&blockchain, &committee, &[0, 1, 2]);
+    let quorum = QuorumCheckpoint::aggregate(&attestations, &committee.public_keys, 3).unwrap();
+
+    // Mapa de bits atribuindo uma assinatura a outro validador
+    let mut moved = quorum.clone();
+    moved.signers[0] = 0b1011;
+    assert!(matches!(
+        moved.verify(&committee.public_keys, 3),
+        Err(Error::InvalidSignature)
+    ));
+
+    let mut extra_bit = quorum.clone();
+    extra_bit.signers[0] |= 0b1000;
+    assert!(matches!(
+        extra_bit.verify(&committee.public_keys, 3),
+        Err(Error::InvalidFormat(_))
+    ));
+
+    let mut other_height = quorum.clone();
+    other_height.height = 1;
+    assert!(other_height.verify(&committee.public_keys, 3).is_err());
+
+    // Outro conjunto de validadores, mesmo que contenha os signatários
+    let mut reordered = committee.public_keys.clone();
+    reordered.swap(0, 3);
+    assert!(quorum.verify(&reordered, 3).is_err());
+
+    // Atestado de quem não é membro não entra no contêiner
+    let outsider = keypair();
+    let mut foreign = attestations.clone();
+    foreign.push(CheckpointAttestation::create(&blockchain, 2, &outsider.0, &outsider.1).unwrap());
+    assert!(matches!(
+        QuorumCheckpoint::aggregate(&foreign, &committee.public_keys, 3),
+        Err(Error::InvalidPublicKey)
+    ));
+}
+
+#[test]
+fn test_inclusion_proof_against_quorum() {
+    let mut blockchain = build_chain(3);
+    let committee = committee(4);
+    let attestations = attest(&blockchain, &committee, &[1, 2, 3]);
+    let quorum = QuorumCheckpoint::aggregate(&attestations, &committee.public_keys, 3).unwrap();
+    blockchain.register_validator_key(committee.public_keys[1].clone());
+    blockchain
+        .record_checkpoint(attestations[0].clone(), &committee.keys[1].0)
+        .unwrap();
+
+    let txid = transaction_id(&blockchain.chain[1].transactions[0]);
+    let proof = blockchain.get_proof(&txid).unwrap();
+    proof
+        .verify_quorum(&quorum, &committee.public_keys, 3)
+        .unwrap();
+    assert!(proof
+        .verify_quorum(&quorum, &committee.public_keys, 4)
+        .is_err());
+
+    let mut forged = proof.clone();
+    forged.checkpoint.headers_root = "00".repeat(32);
+    assert!(forged
+        .verify_quorum(&quorum, &committee.public_keys, 3)
+        .is_err());
+}
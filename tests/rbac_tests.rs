@@ -0,0 +1,193 @@
+use kybelith::blockchain::{Block, Blockchain, SharedBlockchain};
+use kybelith::error::{Error, TransactionError};
+use kybelith::rbac::{AdminRole, RoleCredential};
+use kybelith::rpc::{Role, RpcAuth, RpcService};
+use kybelith::transaction::{Operation, OperationKind};
+use kybelith::utils::address::derive_address;
+use pqcrypto_dilithium::dilithium5::{keypair, PublicKey, SecretKey};
+use pqcrypto_traits::sign::PublicKey as _;
+use serde_json::Value;
+
+struct Member {
+    address: String,
+    keys: (PublicKey, SecretKey),
+}
+
+impl Member {
+    fn new() -> Self {
+        let keys = keypair();
+        Self {
+            address: derive_address(keys.0.as_bytes()),
+            keys,
+        }
+    }
+
+    fn credential(&self, role: AdminRole) -> RoleCredential {
+        RoleCredential::issue(role, 60, &self.keys.0, &self.keys.1).unwrap()
+    }
+}
+
+fn assign(
+    blockchain: &mut Blockchain,
+    admin: &Member,
+    subject: &str,
+    role: AdminRole,
+    granted: bool,
+) -> Result<(), TransactionError> {
+    let nonce = blockchain.nonces.get(&admin.address).copied().unwrap_or(0) + 1;
+    let mut operation = Operation::new(
+        OperationKind::AssignRole {
+            subject: subject.to_string(),
+            role,
+            granted,
+        },
+        admin.address.clone(),
+        nonce,
+        admin.keys.0.as_bytes().to_vec(),
+    )?;
+    operation.sign(&admin.keys.1)?;
+    blockchain.submit_operation(operation)?;
+    blockchain.produce_block(10).unwrap();
+    Ok(())
+}
+
+#[test]
+fn test_role_assignments_on_chain() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let admin = Member::new();
+    let auditor = Member::new();
+    blockchain.bootstrap_admin(admin.address.clone()).unwrap();
+    assert!(blockchain.bootstrap_admin(auditor.address.clone()).is_err());
+
+    assign(
+        &mut blockchain,
+        &admin,
+        &auditor.address,
+        AdminRole::Auditor,
+        true,
+    )
+    .unwrap();
+    assert!(blockchain.has_role(&auditor.address, AdminRole::Auditor));
+    assert!(!blockchain.has_role(&auditor.address, AdminRole::Operator));
+
+    // Quem não é admin não atribui papéis, nem a si mesmo
+    assert!(matches!(
+        assign(
+            &mut blockchain,
+            &auditor,
+            &auditor.address,
+            AdminRole::Minter,
+            true
+        ),
+        Err(TransactionError::InvalidParameter(_))
+    ));
+    assert!(assign(
+        &mut blockchain,
+        &admin,
+        &admin.address,
+        AdminRole::Admin,
+        false
+    )
+    .is_err());
+
+    let credential = auditor.credential(AdminRole::Auditor);
+    assert_eq!(
+        blockchain
+            .authorize(&credential, AdminRole::Auditor)
+            .unwrap(),
+        auditor.address
+    );
+    assert!(blockchain
+        .authorize(&credential, AdminRole::Operator)
+        .is_err());
+    assert!(blockchain
+        .authorize(
+            &auditor.credential(AdminRole::Operator),
+            AdminRole::Operator
+        )
+        .is_err());
+
+    assign(
+        &mut blockchain,
+        &admin,
+        &auditor.address,
+        AdminRole::Auditor,
+        false,
+    )
+    .unwrap();
+    assert!(matches!(
+        blockchain.authorize(&credential, AdminRole::Auditor),
+        Err(Error::Unauthorized(_))
+    ));
+}
+
+#[test]
+fn test_credential_bound_to_signing_key() {
+    let member = Member::new();
+    let credential = member.credential(AdminRole::Operator);
+    credential.verify().unwrap();
+
+    let mut borrowed = credential.clone();
+    borrowed.subject = Member::new().address;
+    assert!(borrowed.verify().is_err());
+
+    let mut escalated = credential.clone();
+    escalated.role = AdminRole::Admin;
+    assert!(matches!(escalated.verify(), Err(Error::InvalidSignature)));
+
+    assert!(credential.verify_at(credential.expires_at).is_err());
+    let expired =
+        RoleCredential::issue(AdminRole::Operator, -1, &member.keys.0, &member.keys.1).unwrap();
+    assert!(expired.verify().is_err());
+}
+
+#[test]
+fn test_rpc_requires_operator_credential() {
+    let node = keypair();
+    let issuer = RpcAuth::new(node.0, node.1);
+    let token = issuer.issue("operador", Role::Admin, 60).unwrap();
+
+    let mut blockchain = Blockchain::new().unwrap();
+    let admin = Member::new();
+    let operator = Member::new();
+    blockchain.bootstrap_admin(admin.address.clone()).unwrap();
+    assign(
+        &mut blockchain,
+        &admin,
+        &operator.address,
+        AdminRole::Operator,
+        true,
+    )
+    .unwrap();
+    let height = blockchain.height();
+
+    let chain = SharedBlockchain::new(blockchain);
+    let service = RpcService::with_auth(chain.clone(), issuer).with_role_checks();
+    let previous_hash = chain.latest_hash().unwrap();
+    let block =
+        serde_json::to_value(Block::new(height, Vec::new(), Vec::new(), previous_hash).unwrap())
+            .unwrap();
+
+    assert!(matches!(
+        service.call("submit_block", Some(&token), block.clone()),
+        Err(Error::Unauthorized(_))
+    ));
+    assert!(service
+        .call_with_credential(
+            "submit_block",
+            Some(&token),
+            Some(&admin.credential(AdminRole::Operator)),
+            block.clone()
+        )
+        .is_err());
+    service
+        .call_with_credential(
+            "submit_block",
+            Some(&token),
+            Some(&operator.credential(AdminRole::Operator)),
+            block,
+        )
+        .unwrap();
+    assert_eq!(chain.height(), height + 1);
+    assert!(service.call("get_height", None, Value::Null).is_ok());
+}
@@ -0,0 +1,56 @@
+use kybelith::blockchain::Blockchain;
+use kybelith::test_utils::fixtures::temp_path;
+
+#[test]
+fn test_open_read_only_never_creates_or_repairs() {
+    let missing = temp_path("missing.json");
+    assert!(Blockchain::open_read_only(&missing).is_err());
+    assert!(!missing.exists());
+
+    // Sem o token QST no arquivo, a inspeção mostra o arquivo como está
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain.tokens.remove("0");
+    let path = temp_path("chain.json");
+    blockchain.save_to_file(&path.to_string_lossy()).unwrap();
+    let before = std::fs::read(&path).unwrap();
+
+    let opened = Blockchain::open_read_only(&path).unwrap();
+    assert!(opened.tokens.is_empty());
+    assert_eq!(opened.path(), path.as_path());
+    assert_eq!(opened.snapshot().height(), blockchain.height());
+    drop(opened);
+    assert_eq!(std::fs::read(&path).unwrap(), before);
+
+    // Um arquivo ilegível é erro, não uma cadeia nova
+    std::fs::write(&path, b"{ corrompido").unwrap();
+    assert!(Blockchain::open_read_only(&path).is_err());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_open_read_only_reads_database_without_writing() {
+    let path = temp_path("chain.db");
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert("a".repeat(40), 42);
+    blockchain.save_to_db(&path.to_string_lossy()).unwrap();
+
+    // Um banco anterior à coluna de checksum não ganha a coluna ao ser inspecionado
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    conn.execute_batch("ALTER TABLE blocks DROP COLUMN checksum")
+        .unwrap();
+    drop(conn);
+    let before = std::fs::read(&path).unwrap();
+
+    let opened = Blockchain::open_read_only(&path).unwrap();
+    assert_eq!(opened.snapshot().balance_of("0", &"a".repeat(40)), Some(42));
+    assert_eq!(opened.height(), blockchain.height());
+    drop(opened);
+    let after = std::fs::read(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(after, before);
+}
@@ -0,0 +1,60 @@
+use kybelith::blockchain::SharedBlockchain;
+use kybelith::rpc::{AccountResponse, RpcService};
+use kybelith::test_utils::fixtures::{address_of, funded, transfer};
+use pqcrypto_dilithium::dilithium5::keypair;
+use serde_json::json;
+use std::sync::Arc;
+
+#[test]
+fn test_read_snapshot_is_shared_until_next_write() {
+    let keys = keypair();
+    let shared = SharedBlockchain::new(funded(&address_of(&keys), 1000));
+    shared.submit_transaction(transfer(&keys, 10, 1)).unwrap();
+
+    let before = shared.read_snapshot();
+    assert!(Arc::ptr_eq(&before, &shared.read_snapshot()));
+    assert_eq!(before.pending_for(&address_of(&keys)).count(), 1);
+
+    // O bloco aplicado depois da foto não aparece nela
+    shared.produce_block(10).unwrap().unwrap();
+    assert_eq!(before.height(), 0);
+    assert_eq!(before.balance_of("0", &address_of(&keys)), Some(1000));
+    assert_eq!(before.nonce_of(&address_of(&keys)), 1);
+
+    let after = shared.read_snapshot();
+    assert!(after.version() > before.version());
+    assert_eq!(after.height(), 1);
+    // O valor transferido e a taxa mínima de 1 KYBL
+    assert_eq!(after.balance_of("0", &address_of(&keys)), Some(989));
+    assert_eq!(after.pending_for(&address_of(&keys)).count(), 0);
+    assert_eq!(after.latest_hash(), shared.latest_hash().as_deref());
+}
+
+#[test]
+fn test_get_account_reads_one_snapshot() {
+    let keys = keypair();
+    let shared = SharedBlockchain::new(funded(&address_of(&keys), 1000));
+    shared.submit_transaction(transfer(&keys, 10, 1)).unwrap();
+
+    let service = RpcService::new(shared);
+    let account: AccountResponse = serde_json::from_value(
+        service
+            .handle(
+                "get_account",
+                json!({ "token_id": "0", "address": address_of(&keys) }),
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        (account.balance, account.nonce, account.height),
+        (1000, 1, 0)
+    );
+    assert_eq!(account.pending.len(), 1);
+    assert!(service
+        .handle(
+            "get_account",
+            json!({ "token_id": "99", "address": address_of(&keys) })
+        )
+        .is_err());
+}
@@ -0,0 +1,71 @@
+use kybelith::blockchain::SharedBlockchain;
+use kybelith::error::ErrorCategory;
+use kybelith::rpc::{RpcService, SubmitTransactionsResponse};
+use kybelith::test_utils::fixtures::{address_of, funded, transfer};
+use pqcrypto_dilithium::dilithium5::keypair;
+use serde_json::json;
+
+#[test]
+fn test_rejection_reason_points_at_field() {
+    let keys = keypair();
+    let mut blockchain = funded(&address_of(&keys), 1000);
+    blockchain
+        .submit_transaction(transfer(&keys, 10, 1))
+        .unwrap();
+
+    let skipped = transfer(&keys, 10, 5);
+    let txid = skipped.txid();
+    assert!(blockchain.submit_transaction(skipped).is_err());
+    let reason = blockchain.rejection(&txid).unwrap();
+    assert_eq!(reason.code, 2005);
+    assert_eq!(reason.category, ErrorCategory::Validation);
+    assert_eq!(reason.field.as_deref(), Some("nonce"));
+    assert_eq!(reason.expected.as_deref(), Some("2"));
+    assert_eq!(reason.actual.as_deref(), Some("5"));
+
+    blockchain.limits.max_signature_size = 16;
+    let oversized = transfer(&keys, 10, 2);
+    let txid = oversized.txid();
+    assert!(blockchain.submit_transaction(oversized).is_err());
+    let reason = blockchain.rejection(&txid).unwrap();
+    assert_eq!(reason.field.as_deref(), Some("signature"));
+    assert_eq!(reason.expected.as_deref(), Some("<= 16"));
+}
+
+#[test]
+fn test_submit_transactions_returns_rejection() {
+    let keys = keypair();
+    let service = RpcService::new(SharedBlockchain::new(funded(&address_of(&keys), 1000)));
+
+    let mut forged = transfer(&keys, 10, 2);
+    forged.amount += 1;
+    let transactions = vec![transfer(&keys, 10, 1), forged, transfer(&keys, 10, 7)];
+    let response: SubmitTransactionsResponse = serde_json::from_value(
+        service
+            .handle(
+                "submit_transactions",
+                json!({ "transactions": transactions }),
+            )
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert!(response.results[0].accepted);
+    assert!(response.results[0].rejection.is_none());
+    let forged = response.results[1].rejection.as_ref().unwrap();
+    assert_eq!(forged.field.as_deref(), Some("signature"));
+    let gap = response.results[2].rejection.as_ref().unwrap();
+    assert_eq!(
+        (gap.expected.as_deref(), gap.actual.as_deref()),
+        (Some("2"), Some("7"))
+    );
+
+    // O operador consulta a mesma rejeição depois
+    let recorded = service
+        .handle("get_rejection", json!({ "txid": gap.txid }))
+        .unwrap();
+    assert_eq!(recorded["field"], "nonce");
+    assert!(service
+        .handle("get_rejection", json!({ "txid": "0".repeat(64) }))
+        .is_err());
+}
@@ -0,0 +1,288 @@
+use kybelith::blockchain::{transaction_id, Block, Blockchain, LifecycleState};
+use kybelith::error::Error;
+use kybelith::events::AppEvent;
+use kybelith::test_utils::fixtures::{
+    address_of, balance, bob, commit_transaction, fund, funded, transfer,
+};
+use kybelith::transaction::SecureTransaction;
+use pqcrypto_dilithium::dilithium5::{keypair, PublicKey, SecretKey};
+
+fn secure_transfer(keys: &(PublicKey, SecretKey), amount: u64) -> SecureTransaction {
+    let timestamp = chrono::Utc::now().timestamp();
+    SecureTransaction::new(
+        address_of(keys),
+        "b".repeat(40),
+        amount,
+        timestamp,
+        1,
+        &keys.1,
+        &keys.0,
+    )
+    .unwrap()
+}
+
+fn block_on(parent: &Block, transactions: Vec<SecureTransaction>) -> Block {
+    Block::new(
+        parent.index + 1,
+        transactions,
+        Vec::new(),
+        parent.hash.clone(),
+    )
+    .unwrap()
+}
+
+fn funded_chain(address: &str) -> Blockchain {
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(address.to_string(), 1000);
+    blockchain
+        .add_block(Block::new(0, Vec::new(), Vec::new(), "0".repeat(64)).unwrap())
+        .unwrap();
+    blockchain
+}
+
+#[test]
+fn test_reorg_reports_reverted_and_applied_txids() {
+    let keys = keypair();
+    let mut blockchain = funded_chain(&address_of(&keys));
+    let genesis = blockchain.chain[0].clone();
+    let original = secure_transfer(&keys, 10);
+    blockchain
+        .add_block(block_on(&genesis, vec![original.clone()]))
+        .unwrap();
+    let mut events = blockchain.events.subscribe();
+
+    // Ramo concorrente mais longo, com outra transferência do mesmo nonce
+    let replacement = secure_transfer(&keys, 20);
+    let fork = Block::new(1, Vec::new(), Vec::new(), genesis.hash.clone()).unwrap();
+    let tip = block_on(&fork, vec![replacement.clone()]);
+    let branch = vec![genesis.clone(), fork, tip.clone()];
+    assert!(blockchain.reorganize(branch).unwrap());
+
+    assert_eq!(blockchain.height(), 3);
+    assert!(blockchain.index.get(&transaction_id(&original)).is_none());
+    assert!(blockchain
+        .index
+        .get(&transaction_id(&replacement))
+        .is_some());
+
+    match events.try_recv().unwrap() {
+        AppEvent::ChainReorganized {
+            fork_height,
+            new_tip,
+            reverted,
+            applied,
+            ..
+        } => {
+            assert_eq!(fork_height, 1);
+            assert_eq!(new_tip, tip.hash);
+            assert_eq!(reverted, vec![transaction_id(&original)]);
+            assert_eq!(applied, vec![transaction_id(&replacement)]);
+        }
+        other => panic!("evento inesperado: {:?}", other),
+    }
+    let committed: Vec<u64> = std::iter::from_fn(|| events.try_recv().ok())
+        .map(|event| match event {
+            AppEvent::BlockCommitted { height, .. } => height,
+            other => panic!("evento inesperado: {:?}", other),
+        })
+        .collect();
+    assert_eq!(committed, vec![1, 2]);
+}
+
+#[test]
+fn test_reorg_keeps_chain_for_shorter_or_invalid_branch() {
+    let keys = keypair();
+    let mut blockchain = funded_chain(&address_of(&keys));
+    let genesis = blockchain.chain[0].clone();
+    let current = block_on(&genesis, vec![secure_transfer(&keys, 10)]);
+    blockchain.add_block(current.clone()).unwrap();
+
+    // Empate mantém a cadeia atual
+    let rival = Block::new(1, Vec::new(), Vec::new(), genesis.hash.clone()).unwrap();
+    assert!(!blockchain.reorganize(vec![rival.clone()]).unwrap());
+
+    // Ramo mais longo com transferência sem saldo: a cadeia anterior volta
+    let invalid = block_on(&rival, vec![secure_transfer(&keys, 5000)]);
+    let mut events = blockchain.events.subscribe();
+    assert!(blockchain.reorganize(vec![rival, invalid]).is_err());
+    assert_eq!(blockchain.height(), 2);
+    assert_eq!(blockchain.chain[1].hash, current.hash);
+    assert!(blockchain
+        .index
+        .get(&transaction_id(&current.transactions[0]))
+        .is_some());
+    assert!(events.try_recv().is_err());
+
+    let detached = Block::new(2, Vec::new(), Vec::new(), "f".repeat(64)).unwrap();
+    assert!(matches!(
+        blockchain.reorganize(vec![detached]),
+        Err(Error::InvalidBlock(_))
+    ));
+}
+
+#[test]
+fn test_invalid_branch_leaves_no_body_effects() {
+    let keys = keypair();
+    let sender = address_of(&keys);
+    let mut blockchain = funded_chain(&sender);
+    let genesis = blockchain.chain[0].clone();
+    let current = Block::new(1, Vec::new(), Vec::new(), genesis.hash.clone()).unwrap();
+    blockchain.add_block(current.clone()).unwrap();
+
+    // Ramo com um bloco de corpo válido seguido de um bloco inválido
+    let mut rival = Blockchain::new().unwrap();
+    rival
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(sender.clone(), 1000);
+    rival.add_block(genesis).unwrap();
+    rival.submit_transaction(transfer(&keys, 40, 1)).unwrap();
+    let with_body = rival.produce_block(10).unwrap().unwrap();
+    let invalid = block_on(&with_body, vec![secure_transfer(&keys, 5000)]);
+
+    assert!(blockchain.reorganize(vec![with_body, invalid]).is_err());
+    assert_eq!(blockchain.chain[1].hash, current.hash);
+    assert_eq!(balance(&blockchain, 0, &sender), 1000);
+    assert_eq!(balance(&blockchain, 0, &bob()), 0);
+    assert!(!blockchain.nonces.contains_key(&sender));
+    assert!(blockchain.execution_receipts(1).is_none());
+}
+
+#[test]
+fn test_reorg_reports_body_transfers() {
+    let keys = keypair();
+    let sender = address_of(&keys);
+    let mut blockchain = funded_chain(&sender);
+    let genesis = blockchain.chain[0].clone();
+    blockchain
+        .add_block(Block::new(1, Vec::new(), Vec::new(), genesis.hash.clone()).unwrap())
+        .unwrap();
+
+    let mut rival = Blockchain::new().unwrap();
+    rival
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(sender.clone(), 1000);
+    rival.add_block(genesis).unwrap();
+    let tx = transfer(&keys, 40, 1);
+    rival.submit_transaction(tx.clone()).unwrap();
+    let with_body = rival.produce_block(10).unwrap().unwrap();
+    let tip = Block::new(2, Vec::new(), Vec::new(), with_body.hash.clone()).unwrap();
+
+    let mut events = blockchain.events.subscribe();
+    assert!(blockchain.reorganize(vec![with_body, tip]).unwrap());
+    assert!(blockchain.index.get(&tx.txid()).is_some());
+    match events.try_recv().unwrap() {
+        AppEvent::ChainReorganized {
+            reverted, applied, ..
+        } => {
+            assert!(reverted.is_empty());
+            assert_eq!(applied, vec![tx.txid()]);
+        }
+        other => panic!("evento inesperado: {:?}", other),
+    }
+}
+
+#[test]
+fn test_reorg_reverts_executed_body_and_returns_its_items_to_mempool() {
+    let keys = keypair();
+    let other = keypair();
+    let sender = address_of(&keys);
+    let mut blockchain = funded_chain(&sender);
+    fund(&mut blockchain, &address_of(&other), 1000);
+    let genesis = blockchain.chain[0].clone();
+    let produced = transfer(&keys, 40, 1);
+    commit_transaction(&mut blockchain, produced.clone());
+    assert_eq!(balance(&blockchain, 0, &bob()), 40);
+
+    let mut rival = funded(&sender, 1000);
+    fund(&mut rival, &address_of(&other), 1000);
+    rival.add_block(genesis).unwrap();
+    let with_body = commit_transaction(&mut rival, transfer(&other, 70, 1));
+    let tip = Block::new(2, Vec::new(), Vec::new(), with_body.hash.clone()).unwrap();
+
+    assert!(blockchain.reorganize(vec![with_body, tip]).unwrap());
+    assert_eq!(blockchain.height(), 3);
+    assert_eq!(balance(&blockchain, 0, &bob()), 70);
+    assert_eq!(balance(&blockchain, 0, &sender), 1000);
+    assert_eq!(
+        balance(&blockchain, 0, &address_of(&other)),
+        balance(&rival, 0, &address_of(&other))
+    );
+    assert_eq!(
+        blockchain.execution_receipts(1),
+        rival.execution_receipts(1)
+    );
+    assert!(blockchain.index.get(&produced.txid()).is_none());
+
+    // A transferência do bloco revertido volta a esperar no mempool
+    let pending: Vec<String> = blockchain
+        .pending_transactions
+        .iter()
+        .map(|tx| tx.txid())
+        .collect();
+    assert_eq!(pending, vec![produced.txid()]);
+    assert_eq!(
+        blockchain
+            .transaction_status(&produced.txid())
+            .unwrap()
+            .state,
+        LifecycleState::Pending
+    );
+    let block = blockchain.produce_block(10).unwrap().unwrap();
+    assert_eq!(block.index, 3);
+    assert_eq!(balance(&blockchain, 0, &bob()), 110);
+}
+
+#[test]
+fn test_reorg_drops_reverted_items_the_branch_conflicts_with() {
+    let keys = keypair();
+    let sender = address_of(&keys);
+    let mut blockchain = funded_chain(&sender);
+    let genesis = blockchain.chain[0].clone();
+
+    let mut producer = funded(&sender, 1000);
+    producer.add_block(genesis.clone()).unwrap();
+    let original = transfer(&keys, 40, 1);
+    blockchain
+        .add_block(commit_transaction(&mut producer, original.clone()))
+        .unwrap();
+
+    // O ramo gasta o mesmo nonce em outra transferência
+    let mut rival = funded(&sender, 1000);
+    rival.add_block(genesis).unwrap();
+    let replacement = transfer(&keys, 70, 1);
+    let with_body = commit_transaction(&mut rival, replacement.clone());
+    let tip = Block::new(2, Vec::new(), Vec::new(), with_body.hash.clone()).unwrap();
+
+    let mut events = blockchain.events.subscribe();
+    assert!(blockchain.reorganize(vec![with_body, tip]).unwrap());
+    assert_eq!(balance(&blockchain, 0, &bob()), 70);
+    assert_eq!(blockchain.nonces.get(&sender), Some(&1));
+    assert!(blockchain.pending_transactions.is_empty());
+    assert!(matches!(
+        blockchain
+            .transaction_status(&original.txid())
+            .unwrap()
+            .state,
+        LifecycleState::Dropped { .. }
+    ));
+    match events.try_recv().unwrap() {
+        AppEvent::ChainReorganized {
+            reverted, applied, ..
+        } => {
+            assert_eq!(reverted, vec![original.txid()]);
+            assert_eq!(applied, vec![replacement.txid()]);
+        }
+        other => panic!("evento inesperado: {:?}", other),
+    }
+}
@@ -0,0 +1,72 @@
+use kybelith::consensus::reputation::{ReputationAction, ReputationSystem};
+use kybelith::network::NodeIdentity;
+use pqcrypto_traits::sign::PublicKey as _;
+
+fn observer_with_offender() -> ReputationSystem {
+    let mut system = ReputationSystem::new();
+    system.add_validator("observer".to_string());
+    system.add_validator("offender".to_string());
+    system
+        .update_reputation("offender", ReputationAction::InvalidBlockProposed)
+        .unwrap();
+    system
+}
+
+#[test]
+fn test_import_summary_blends_scores_by_trust() {
+    let identity = NodeIdentity::generate();
+    let remote = observer_with_offender();
+    let summary = remote.export_summary("observer".to_string(), &identity);
+
+    let mut local = ReputationSystem::new();
+    local.add_validator("offender".to_string());
+    local.add_validator("observer".to_string());
+    local.set_reputation("observer", 80.0).unwrap();
+    let mut config = local.config().clone();
+    config.peer_trust.insert("observer".to_string(), 0.5);
+    local.set_config(config);
+
+    let merged = local
+        .import_summary(&summary, identity.public_key.as_bytes())
+        .unwrap();
+    assert_eq!(merged, 1);
+    assert_eq!(local.get_reputation("offender").unwrap().score, 45.0);
+    // A nota do observador sobre si mesmo não conta
+    assert_eq!(local.get_reputation("observer").unwrap().score, 80.0);
+
+    // O mesmo resumo não é aceito duas vezes
+    assert!(local
+        .import_summary(&summary, identity.public_key.as_bytes())
+        .is_err());
+}
+
+#[test]
+fn test_import_summary_rejects_forged_or_untrusted() {
+    let identity = NodeIdentity::generate();
+    let mut summary = observer_with_offender().export_summary("observer".to_string(), &identity);
+
+    let mut local = ReputationSystem::new();
+    local.add_validator("offender".to_string());
+    let other = NodeIdentity::generate();
+    assert!(local
+        .import_summary(&summary, other.public_key.as_bytes())
+        .is_err());
+
+    summary.observations[1].score = 0.0;
+    assert!(local
+        .import_summary(&summary, identity.public_key.as_bytes())
+        .is_err());
+    assert_eq!(local.get_reputation("offender").unwrap().score, 50.0);
+
+    let mut config = local.config().clone();
+    config.default_peer_trust = 0.0;
+    local.set_config(config);
+    let summary = observer_with_offender().export_summary("observer".to_string(), &identity);
+    assert_eq!(
+        local
+            .import_summary(&summary, identity.public_key.as_bytes())
+            .unwrap(),
+        0
+    );
+    assert_eq!(local.get_reputation("offender").unwrap().score, 50.0);
+}
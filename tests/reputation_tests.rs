@@ -78,7 +78,7 @@ fn test_reputation_ban_expires() {
     let now = std::time::Instant::now();
     let banned_until = reputation.banned_until.as_ref().map(|u| u.to_instant());
     println!("Agora: {:?}, Banned até: {:?}", now, banned_until);
-    let expired = reputation.check_ban_status();
+    let expired = reputation.check_ban_status_at(now);
     println!("Expired: {}, is_banned: {}", expired, reputation.is_banned);
     assert!(expired, "check_ban_status deve indicar que o ban expirou");
     assert!(!reputation.is_banned, "is_banned interno deve ser false após expiração");
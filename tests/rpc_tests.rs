@@ -0,0 +1,224 @@
+use kybelith::blockchain::{
+    transaction_id, Block, Blockchain, CheckpointAttestation, InclusionProof, SharedBlockchain,
+};
+use kybelith::error::Error;
+use kybelith::rpc::{
+    openapi_document, BalanceResponse, HeightResponse, Role, RpcAuth, RpcService, TokenResponse,
+    METHODS,
+};
+use kybelith::transaction::SecureTransaction;
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+use serde_json::{json, Value};
+
+fn collect_refs(value: &Value, refs: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, inner) in map {
+                match (key.as_str(), inner) {
+                    ("$ref", Value::String(reference)) => refs.push(reference.clone()),
+                    _ => collect_refs(inner, refs),
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|item| collect_refs(item, refs)),
+        _ => {}
+    }
+}
+
+#[test]
+fn test_openapi_document_covers_every_method() {
+    let document = openapi_document();
+    assert_eq!(document["openapi"], "3.0.3");
+
+    for method in METHODS {
+        let operation = &document["paths"][method.path()]["post"];
+        assert_eq!(operation["operationId"], method.name);
+        assert!(operation["responses"]["200"].is_object());
+    }
+
+    // Toda referência aponta para um schema definido em components
+    let mut refs = Vec::new();
+    collect_refs(&document, &mut refs);
+    assert!(!refs.is_empty());
+    for reference in refs {
+        let name = reference
+            .strip_prefix("#/components/schemas/")
+            .unwrap_or_else(|| panic!("referência fora de components: {}", reference));
+        assert!(
+            document["components"]["schemas"][name].is_object(),
+            "schema ausente: {}",
+            name
+        );
+    }
+    for name in [
+        "Block",
+        "InclusionProof",
+        "Transaction",
+        "SecureTransaction",
+    ] {
+        assert!(document["components"]["schemas"][name].is_object());
+    }
+}
+
+#[test]
+fn test_service_dispatches_queries() {
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert("a".repeat(40), 42);
+    let service = RpcService::new(SharedBlockchain::new(blockchain));
+
+    let height: HeightResponse =
+        serde_json::from_value(service.handle("get_height", Value::Null).unwrap()).unwrap();
+    assert_eq!(height.height, 0);
+    assert_eq!(height.latest_hash, None);
+
+    let balance: BalanceResponse = serde_json::from_value(
+        service
+            .handle(
+                "get_balance",
+                json!({ "token_id": "0", "address": "a".repeat(40) }),
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(balance.balance, 42);
+
+    let token: TokenResponse = serde_json::from_value(
+        service
+            .handle("get_token", json!({ "token_id": "0" }))
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(token.metadata.symbol, "KYBL");
+    assert_eq!(token.total_supply, 11_000_000);
+
+    assert!(service
+        .handle("get_balance", json!({ "token_id": "0" }))
+        .is_err());
+    assert!(service.handle("get_block", json!({ "height": 5 })).is_err());
+    assert!(service.handle("drop_chain", Value::Null).is_err());
+    assert_eq!(service.methods().count(), METHODS.len());
+}
+
+#[test]
+fn test_get_proof_over_json_verifies() {
+    let tx_keys = keypair();
+    let validator = keypair();
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .chain
+        .push(Block::new(0, Vec::new(), Vec::new(), "0".repeat(64)).unwrap());
+    let tx = SecureTransaction::new(
+        "a".repeat(40),
+        "b".repeat(40),
+        10,
+        chrono::Utc::now().timestamp(),
+        1,
+        &tx_keys.1,
+        &tx_keys.0,
+    )
+    .unwrap();
+    let txid = transaction_id(&tx);
+    let previous_hash = blockchain.chain[0].hash.clone();
+    blockchain
+        .chain
+        .push(Block::new(1, vec![tx], Vec::new(), previous_hash).unwrap());
+    blockchain.register_validator_key(validator.0.as_bytes().to_vec());
+    let attestation =
+        CheckpointAttestation::create(&blockchain, 1, &validator.0, &validator.1).unwrap();
+    blockchain
+        .record_checkpoint(attestation, &validator.0)
+        .unwrap();
+
+    let service = RpcService::new(SharedBlockchain::new(blockchain));
+    let response = service
+        .handle("get_proof", json!({ "txid": txid }))
+        .unwrap();
+    let proof: InclusionProof = serde_json::from_value(response).unwrap();
+    proof.verify(&validator.0).unwrap();
+}
+
+#[test]
+fn test_tokens_are_signed_and_scoped() {
+    let node = keypair();
+    let auth = RpcAuth::new(node.0, node.1);
+
+    let token = auth.issue("carteira-1", Role::Wallet, 60).unwrap();
+    let claims = auth.verify(&token).unwrap();
+    assert_eq!(claims.sub, "carteira-1");
+    assert_eq!(claims.role, Role::Wallet);
+
+    assert!(auth.authorize(Some(&token), Role::Wallet).is_ok());
+    assert!(matches!(
+        auth.authorize(Some(&token), Role::Admin),
+        Err(Error::Unauthorized(_))
+    ));
+    assert!(auth.authorize(None, Role::Wallet).is_err());
+    assert_eq!(auth.authorize(None, Role::Public).unwrap(), Role::Public);
+
+    // Tokens de outro nó, expirados ou adulterados são recusados
+    let other = keypair();
+    let foreign = RpcAuth::new(other.0, other.1)
+        .issue("intruso", Role::Admin, 60)
+        .unwrap();
+    assert!(auth.verify(&foreign).is_err());
+    let expired = auth.issue("carteira-1", Role::Wallet, -1).unwrap();
+    assert!(auth.verify(&expired).is_err());
+    let mut parts: Vec<String> = token.split('.').map(str::to_string).collect();
+    parts[1] = parts[1].chars().rev().collect();
+    assert!(auth.verify(&parts.join(".")).is_err());
+
+    // Um verificador sem chave secreta aceita o token, mas não emite novos
+    let verifier = RpcAuth::verifier(node.0);
+    assert!(verifier.verify(&token).is_ok());
+    assert!(verifier.issue("x", Role::Admin, 60).is_err());
+}
+
+#[test]
+fn test_call_enforces_method_roles() {
+    let node = keypair();
+    let issuer = RpcAuth::new(node.0, node.1);
+    let wallet = issuer.issue("carteira", Role::Wallet, 60).unwrap();
+    let admin = issuer.issue("operador", Role::Admin, 60).unwrap();
+
+    let chain = SharedBlockchain::new(Blockchain::new().unwrap());
+    let service = RpcService::with_auth(chain.clone(), issuer);
+    let genesis =
+        serde_json::to_value(Block::new(0, Vec::new(), Vec::new(), "0".repeat(64)).unwrap())
+            .unwrap();
+
+    assert!(service.call("get_height", None, Value::Null).is_ok());
+    let batch = json!({ "transactions": [] });
+    assert!(service
+        .call("submit_transactions", None, batch.clone())
+        .is_err());
+    assert!(service
+        .call("submit_transactions", Some(&wallet), batch)
+        .is_ok());
+
+    assert!(matches!(
+        service.call("submit_block", Some(&wallet), genesis.clone()),
+        Err(Error::Unauthorized(_))
+    ));
+    assert_eq!(chain.height(), 0);
+    service
+        .call("submit_block", Some(&admin), genesis.clone())
+        .unwrap();
+    assert_eq!(chain.height(), 1);
+
+    // Sem autenticação configurada só as consultas públicas passam
+    let open = RpcService::new(chain);
+    assert!(open.call("get_height", None, Value::Null).is_ok());
+    assert!(open.call("submit_block", Some(&admin), genesis).is_err());
+
+    let document = openapi_document();
+    let submit_block = &document["paths"]["/rpc/submit_block"]["post"];
+    assert_eq!(submit_block["x-required-role"], "admin");
+    assert!(submit_block["security"].is_array());
+    assert!(document["paths"]["/rpc/get_height"]["post"]["security"].is_null());
+}
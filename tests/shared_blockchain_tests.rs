@@ -0,0 +1,86 @@
+use kybelith::blockchain::{Block, Blockchain, SharedBlockchain};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+fn new_shared() -> SharedBlockchain {
+    SharedBlockchain::new(Blockchain::new().expect("blockchain deve ser criada"))
+}
+
+fn next_block(shared: &SharedBlockchain) -> Block {
+    let previous_hash = shared.latest_hash().unwrap_or_else(|| "0".repeat(64));
+    Block::new(shared.height(), Vec::new(), Vec::new(), previous_hash)
+        .expect("bloco deve ser criado")
+}
+
+#[test]
+fn test_clones_share_state() {
+    let shared = new_shared();
+    let other = shared.clone();
+
+    shared.add_block(next_block(&shared)).unwrap();
+
+    assert_eq!(other.height(), 1);
+    assert_eq!(other.latest_hash(), shared.latest_hash());
+    assert_eq!(other.block(0).unwrap().hash, shared.latest_hash().unwrap());
+}
+
+#[test]
+fn test_token_reads() {
+    let shared = new_shared();
+
+    assert_eq!(shared.token_supply("0"), Some(11_000_000));
+    assert_eq!(shared.balance_of("0", "system"), Some(10_000_000));
+    assert_eq!(shared.balance_of("0", "desconhecido"), Some(0));
+    assert_eq!(shared.balance_of("999", "system"), None);
+}
+
+#[test]
+fn test_concurrent_reads_during_writes() {
+    let shared = new_shared();
+    let done = Arc::new(AtomicBool::new(false));
+
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = shared.clone();
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                let mut last_height = 0;
+                while !done.load(Ordering::Acquire) {
+                    // Leituras nunca observam a altura regredir nem um bloco pela metade
+                    let height = shared.height();
+                    assert!(height >= last_height);
+                    if height > 0 {
+                        assert!(shared.block(height - 1).is_some());
+                    }
+                    last_height = height;
+                }
+            })
+        })
+        .collect();
+
+    for _ in 0..5 {
+        let block = next_block(&shared);
+        shared.add_block(block).unwrap();
+    }
+    done.store(true, Ordering::Release);
+
+    for reader in readers {
+        reader.join().unwrap();
+    }
+
+    assert_eq!(shared.height(), 5);
+    assert!(shared.is_chain_valid().unwrap());
+}
+
+#[test]
+fn test_try_into_inner() {
+    let shared = new_shared();
+    let other = shared.clone();
+
+    let shared = shared.try_into_inner().unwrap_err();
+    drop(other);
+
+    let blockchain = shared.try_into_inner().unwrap();
+    assert!(blockchain.tokens.contains_key("0"));
+}
@@ -0,0 +1,60 @@
+use kybelith::blockchain::Blockchain;
+use kybelith::constants::MAX_SIGNATURE_SIZE;
+use kybelith::error::TransactionError;
+use kybelith::test_utils::fixtures::transfer;
+use kybelith::transaction::{SignatureScheme, TransactionProcessor};
+use pqcrypto_dilithium::{dilithium2, dilithium5};
+use pqcrypto_traits::sign::PublicKey as _;
+
+#[test]
+fn test_scheme_sizes_come_from_the_key() {
+    assert_eq!(
+        SignatureScheme::Dilithium5.signature_size(),
+        MAX_SIGNATURE_SIZE
+    );
+    assert!(SignatureScheme::ALL
+        .iter()
+        .all(|scheme| scheme.signature_size() <= MAX_SIGNATURE_SIZE));
+
+    let tx = transfer(&dilithium5::keypair(), 10, 1);
+    assert_eq!(
+        SignatureScheme::check(&tx.public_key, &tx.signature).unwrap(),
+        SignatureScheme::Dilithium5
+    );
+    assert_eq!(
+        SignatureScheme::from_public_key_len(dilithium2::public_key_bytes()),
+        Some(SignatureScheme::Dilithium2)
+    );
+    assert_eq!(SignatureScheme::from_public_key_len(32), None);
+}
+
+#[test]
+fn test_mismatched_sizes_rejected_before_verification() {
+    let processor = TransactionProcessor;
+
+    let mut truncated = transfer(&dilithium5::keypair(), 10, 1);
+    truncated.signature.pop();
+    assert!(matches!(
+        processor.cheap_checks(&truncated),
+        Err(TransactionError::InvalidSignature(_))
+    ));
+
+    let mut odd_key = transfer(&dilithium5::keypair(), 10, 1);
+    odd_key.public_key.truncate(1000);
+    assert!(matches!(
+        processor.cheap_checks(&odd_key),
+        Err(TransactionError::InvalidPublicKey(_))
+    ));
+
+    // Chave e assinatura coerentes, mas de um esquema que o nó não verifica
+    let mut dilithium2_tx = transfer(&dilithium5::keypair(), 10, 1);
+    dilithium2_tx.public_key = dilithium2::keypair().0.as_bytes().to_vec();
+    dilithium2_tx.signature = vec![0; dilithium2::signature_bytes()];
+    let mut blockchain = Blockchain::new().unwrap();
+    match blockchain.submit_transaction(dilithium2_tx) {
+        Err(TransactionError::InvalidSignature(message)) => {
+            assert!(message.contains("dilithium2"), "{}", message)
+        }
+        other => panic!("esperava recusa do esquema, obteve {:?}", other),
+    }
+}
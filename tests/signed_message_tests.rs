@@ -0,0 +1,57 @@
+use kybelith::network::NodeIdentity;
+use kybelith::transaction::SignatureScheme;
+use kybelith::utils::address::derive_address;
+use kybelith::wallet::SignedMessage;
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+
+#[test]
+fn test_signed_message_round_trip() {
+    let (public_key, secret_key) = keypair();
+    let message = b"prova de controle para a exchange #4821";
+
+    let signed = SignedMessage::sign(message, &public_key, &secret_key);
+    assert_eq!(signed.scheme, SignatureScheme::Dilithium5);
+    assert_eq!(signed.address, derive_address(public_key.as_bytes()));
+    signed.verify(message).unwrap();
+
+    let json = serde_json::to_string(&signed).unwrap();
+    let decoded: SignedMessage = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, signed);
+    decoded.verify(message).unwrap();
+
+    assert!(signed.verify(b"outra mensagem").is_err());
+
+    let mut forged = signed.clone();
+    forged.address = derive_address(keypair().0.as_bytes());
+    assert!(forged.verify(message).is_err());
+
+    // Outra chave não pode reaproveitar a assinatura, mesmo ajustando o endereço
+    let mut swapped = signed;
+    let other = keypair().0;
+    swapped.public_key = hex::encode(other.as_bytes());
+    swapped.address = derive_address(other.as_bytes());
+    assert!(swapped.verify(message).is_err());
+}
+
+#[test]
+fn test_signed_message_with_node_identity_file() {
+    let path = std::env::temp_dir().join(format!(
+        "kybelith-signed-message-{}.key",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    let identity = NodeIdentity::load_or_generate(&path).unwrap();
+
+    let loaded = NodeIdentity::load(&path).unwrap();
+    assert_eq!(loaded.public_key.as_bytes(), identity.public_key.as_bytes());
+
+    let signed = SignedMessage::sign_with(b"mensagem", loaded.public_key.as_bytes(), |data| {
+        loaded.sign_message(data)
+    });
+    signed.verify(b"mensagem").unwrap();
+
+    std::fs::write(&path, b"curto").unwrap();
+    assert!(NodeIdentity::load(&path).is_err());
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,103 @@
+use kybelith::consensus::simulation::{Simulation, SimulationConfig};
+
+// Cria uma simulação com parâmetros padrão e a semente informada
+fn create_test_simulation(seed: u64, drop_rate: f64) -> Simulation {
+    let config = SimulationConfig {
+        seed,
+        drop_rate,
+        ..SimulationConfig::default()
+    };
+    Simulation::new(config)
+}
+
+#[test]
+fn test_simulation_progresses_without_faults() {
+    let mut sim = create_test_simulation(7, 0.0);
+
+    assert!(
+        sim.run_until_height(5, 60_000),
+        "Todos os nós devem finalizar 5 blocos sem falhas"
+    );
+    assert!(sim.check_safety().is_ok(), "Não deve haver blocos divergentes");
+}
+
+#[test]
+fn test_simulation_is_deterministic() {
+    let mut first = create_test_simulation(1234, 0.1);
+    let mut second = create_test_simulation(1234, 0.1);
+
+    first.run_until(30_000);
+    second.run_until(30_000);
+
+    let report_a = first.report();
+    let report_b = second.report();
+
+    assert_eq!(report_a.heights, report_b.heights, "Alturas devem ser idênticas");
+    assert_eq!(report_a.messages_sent, report_b.messages_sent);
+    assert_eq!(report_a.messages_dropped, report_b.messages_dropped);
+    for (a, b) in first.nodes().iter().zip(second.nodes()) {
+        assert_eq!(a.finalized, b.finalized, "Cadeias finalizadas devem ser idênticas");
+    }
+}
+
+#[test]
+fn test_simulation_minority_partition_stays_safe_and_recovers() {
+    let mut sim = create_test_simulation(99, 0.0);
+    assert!(sim.run_until_height(2, 20_000));
+
+    // Isola um nó: a maioria continua, o nó isolado não finaliza sozinho
+    sim.partition(vec![vec![0, 1, 2], vec![3]]);
+    sim.run_for(20_000);
+
+    let isolated_height = sim.nodes()[3].height();
+    let majority_height = sim.nodes()[0].height();
+    assert!(majority_height > isolated_height, "A maioria deve continuar avançando");
+    assert!(sim.check_safety().is_ok());
+
+    // Após curar a partição, o nó isolado alcança os demais
+    sim.heal();
+    let target = majority_height + 2;
+    let deadline = sim.now_ms() + 60_000;
+    assert!(sim.run_until_height(target, deadline), "Todos devem voltar a progredir");
+    assert!(sim.check_safety().is_ok());
+}
+
+#[test]
+fn test_simulation_survives_crashed_proposer() {
+    let mut sim = create_test_simulation(5, 0.0);
+    assert!(sim.run_until_height(1, 10_000));
+
+    sim.crash(2);
+    let deadline = sim.now_ms() + 60_000;
+    assert!(
+        sim.run_until_height(4, deadline),
+        "A troca de rodada deve contornar o proposer parado"
+    );
+
+    sim.recover(2);
+    assert!(sim.nodes().iter().all(|n| !n.is_crashed()));
+    assert!(sim.check_safety().is_ok());
+}
+
+#[test]
+fn test_simulation_with_message_loss() {
+    let mut sim = create_test_simulation(2024, 0.2);
+    sim.run_until(120_000);
+
+    let report = sim.report();
+    assert!(report.messages_dropped > 0, "A rede deve descartar mensagens");
+    assert!(report.safety_violations.is_empty());
+    assert!(
+        report.heights.iter().all(|h| *h >= 1),
+        "Com 20% de perda a rede ainda deve progredir"
+    );
+}
+
+#[test]
+fn test_simulation_accepts_more_than_255_validators() {
+    let sim = Simulation::new(SimulationConfig {
+        num_validators: 300,
+        ..SimulationConfig::default()
+    });
+    assert_eq!(sim.nodes().len(), 300);
+}
@@ -0,0 +1,63 @@
+use kybelith::blockchain::Blockchain;
+use kybelith::config::{Limits, Settings};
+use kybelith::error::TransactionError;
+use kybelith::test_utils::fixtures::Account;
+
+#[test]
+fn test_limits_check_encoded_size_and_signature() {
+    let mut alice = Account::new();
+    let tx = alice.transfer(&"b".repeat(40), 10);
+    assert_eq!(tx.encoded_size(), bincode::serialize(&tx).unwrap().len());
+    Limits::default().check_transaction(&tx).unwrap();
+
+    let tight = Limits {
+        max_transaction_size: tx.encoded_size() - 1,
+        ..Limits::default()
+    };
+    assert!(matches!(
+        tight.check_transaction(&tx),
+        Err(TransactionError::DataSizeExceeded)
+    ));
+    let short_signatures = Limits {
+        max_signature_size: tx.signature.len() - 1,
+        ..Limits::default()
+    };
+    assert!(matches!(
+        short_signatures.check_transaction(&tx),
+        Err(TransactionError::SignatureSizeExceeded)
+    ));
+
+    // Configurações antigas, sem a seção de limites, assumem os padrões
+    let mut value = serde_json::to_value(Settings::default()).unwrap();
+    value.as_object_mut().unwrap().remove("limits");
+    let settings: Settings = serde_json::from_value(value).unwrap();
+    assert_eq!(settings.limits, Limits::default());
+}
+
+#[test]
+fn test_mempool_and_block_building_follow_configured_limits() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut alice = Account::new();
+    let first = alice.transfer(&"b".repeat(40), 10);
+    let size = first.encoded_size();
+
+    blockchain.limits.max_transaction_size = size - 1;
+    assert!(matches!(
+        blockchain.submit_transaction(first.clone()),
+        Err(TransactionError::DataSizeExceeded)
+    ));
+    blockchain.limits = Limits::default();
+    blockchain.submit_transaction(first).unwrap();
+    for _ in 0..3 {
+        blockchain
+            .submit_transaction(alice.transfer(&"b".repeat(40), 10))
+            .unwrap();
+    }
+
+    // Cabem duas transferências por bloco; as demais esperam o próximo
+    blockchain.limits.max_block_size = 2 * size + size / 2;
+    blockchain.produce_block(10).unwrap();
+    assert_eq!(blockchain.pending_transactions.len(), 2);
+    blockchain.produce_block(10).unwrap();
+    assert!(blockchain.pending_transactions.is_empty());
+}
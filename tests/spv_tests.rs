@@ -0,0 +1,118 @@
+use kybelith::blockchain::{
+    transaction_id, Block, Blockchain, CheckpointAttestation, SharedBlockchain,
+};
+use kybelith::transaction::SecureTransaction;
+use pqcrypto_dilithium::dilithium5::{keypair, PublicKey, SecretKey};
+use pqcrypto_traits::sign::PublicKey as _;
+
+fn build_chain(blocks: u64, keys: &(PublicKey, SecretKey)) -> Blockchain {
+    let timestamp = chrono::Utc::now().timestamp();
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .chain
+        .push(Block::new(0, Vec::new(), Vec::new(), "0".repeat(64)).unwrap());
+
+    for index in 1..=blocks {
+        let transactions: Vec<SecureTransaction> = (1..=3)
+            .map(|nonce| {
+                SecureTransaction::new(
+                    "a".repeat(40),
+                    "b".repeat(40),
+                    100 + nonce,
+                    timestamp,
+                    index * 10 + nonce,
+                    &keys.1,
+                    &keys.0,
+                )
+                .unwrap()
+            })
+            .collect();
+        let previous_hash = blockchain.chain.last().unwrap().hash.clone();
+        blockchain
+            .chain
+            .push(Block::new(index, transactions, Vec::new(), previous_hash).unwrap());
+    }
+
+    blockchain
+}
+
+#[test]
+fn test_proof_verifies_without_the_chain() {
+    let tx_keys = keypair();
+    let validator = keypair();
+    let mut blockchain = build_chain(4, &tx_keys);
+    blockchain.register_validator_key(validator.0.as_bytes().to_vec());
+    let attestation =
+        CheckpointAttestation::create(&blockchain, 3, &validator.0, &validator.1).unwrap();
+    blockchain
+        .record_checkpoint(attestation, &validator.0)
+        .unwrap();
+
+    let txid = transaction_id(&blockchain.chain[2].transactions[1]);
+    let shared = SharedBlockchain::new(blockchain);
+    let proof = shared.get_proof(&txid).unwrap();
+
+    // O verificador recebe apenas a prova serializada e a chave do validador
+    let json = serde_json::to_string(&proof).unwrap();
+    let received: kybelith::blockchain::InclusionProof = serde_json::from_str(&json).unwrap();
+    assert_eq!(received.header.index, 2);
+    received.verify(&validator.0).unwrap();
+
+    assert!(received.verify(&keypair().0).is_err());
+}
+
+#[test]
+fn test_tampered_proof_is_rejected() {
+    let tx_keys = keypair();
+    let validator = keypair();
+    let mut blockchain = build_chain(3, &tx_keys);
+    blockchain.register_validator_key(validator.0.as_bytes().to_vec());
+    let attestation =
+        CheckpointAttestation::create(&blockchain, 3, &validator.0, &validator.1).unwrap();
+    blockchain
+        .record_checkpoint(attestation, &validator.0)
+        .unwrap();
+
+    let txid = transaction_id(&blockchain.chain[1].transactions[0]);
+    let proof = blockchain.get_proof(&txid).unwrap();
+
+    let mut inflated = proof.clone();
+    inflated.transaction.amount = 1_000_000;
+    inflated.txid = transaction_id(&inflated.transaction);
+    assert!(inflated.verify(&validator.0).is_err());
+
+    let mut moved = proof.clone();
+    moved.header.index = 2;
+    assert!(moved.verify(&validator.0).is_err());
+
+    let mut forged_root = proof;
+    forged_root.checkpoint.headers_root = "00".repeat(32);
+    assert!(forged_root.verify(&validator.0).is_err());
+}
+
+#[test]
+fn test_proof_requires_checkpoint_coverage() {
+    let tx_keys = keypair();
+    let validator = keypair();
+    let mut blockchain = build_chain(3, &tx_keys);
+    let txid = transaction_id(&blockchain.chain[3].transactions[2]);
+
+    assert!(blockchain.get_proof(&txid).is_err());
+
+    blockchain.register_validator_key(validator.0.as_bytes().to_vec());
+    let attestation =
+        CheckpointAttestation::create(&blockchain, 2, &validator.0, &validator.1).unwrap();
+    blockchain
+        .prune_signatures(attestation, &validator.0)
+        .unwrap();
+    assert!(blockchain.get_proof(&txid).is_err());
+
+    // Transações podadas continuam provando inclusão
+    let pruned = transaction_id(&blockchain.chain[1].transactions[0]);
+    blockchain
+        .get_proof(&pruned)
+        .unwrap()
+        .verify(&validator.0)
+        .unwrap();
+    assert!(blockchain.get_proof(&"ff".repeat(32)).is_err());
+}
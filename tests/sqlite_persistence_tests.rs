@@ -0,0 +1,257 @@
+use kybelith::blockchain::{Block, Blockchain};
+use kybelith::constants::MAX_BLOCK_SIZE;
+use kybelith::smart_contract::SmartContract;
+use kybelith::transaction::{SecureTransaction, Transaction};
+use kybelith::utils::compression::{self, Codec};
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+
+fn temp_db(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "kybelith-sqlite-{}-{}.db",
+        std::process::id(),
+        name
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn populated_chain() -> Blockchain {
+    let keys = keypair();
+    let timestamp = chrono::Utc::now().timestamp();
+    let mut blockchain = Blockchain::new().unwrap();
+
+    let contract = SmartContract {
+        code: vec![0, 97, 115, 109],
+        data: vec![1, 2, 3],
+        address: "c".repeat(40),
+        creator: "a".repeat(40),
+        timestamp,
+        quantum_secure: true,
+    };
+    let transactions: Vec<SecureTransaction> = (1..=2)
+        .map(|nonce| {
+            SecureTransaction::new(
+                "a".repeat(40),
+                "b".repeat(40),
+                10 * nonce,
+                timestamp,
+                10 + nonce,
+                &keys.1,
+                &keys.0,
+            )
+            .unwrap()
+        })
+        .collect();
+    blockchain
+        .chain
+        .push(Block::new(0, transactions, vec![contract], "0".repeat(64)).unwrap());
+
+    blockchain.add_staker("d".repeat(40), 5_000);
+    blockchain
+        .public_keys
+        .insert("a".repeat(40), keys.0.as_bytes().to_vec());
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert("a".repeat(40), 700);
+
+    let mut tx = Transaction::new(
+        "a".repeat(40),
+        "b".repeat(40),
+        50,
+        keys.0.as_bytes().to_vec(),
+    )
+    .unwrap();
+    tx.nonce = 1;
+    tx.sign(&keys.1).unwrap();
+    blockchain.submit_transaction(tx).unwrap();
+    blockchain.produce_block(10).unwrap();
+
+    blockchain
+}
+
+#[test]
+fn test_db_round_trip_matches_json() {
+    let blockchain = populated_chain();
+    let path = temp_db("round-trip");
+
+    blockchain.save_to_db(&path.to_string_lossy()).unwrap();
+    let from_db = Blockchain::load_from_db(&path.to_string_lossy()).unwrap();
+    let from_json = Blockchain::from_json(&serde_json::to_vec(&blockchain).unwrap()).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&from_db).unwrap(),
+        serde_json::to_value(&from_json).unwrap()
+    );
+    assert_eq!(from_db.chain[0].transactions.len(), 2);
+    assert_eq!(from_db.chain[0].contracts[0].data, vec![1, 2, 3]);
+    assert_eq!(from_db.stakers[&"d".repeat(40)], 5_000);
+    assert_eq!(from_db.nonces[&"a".repeat(40)], 1);
+    assert_eq!(from_db.committed_transactions().len(), 1);
+    assert!(from_db.is_chain_valid().unwrap());
+
+    let _ = std::fs::remove_file(&path);
+}
+
+fn count(path: &std::path::Path, table: &str) -> i64 {
+    let conn = rusqlite::Connection::open(path).unwrap();
+    conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+        row.get(0)
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_repeated_saves_do_not_duplicate_rows() {
+    let blockchain = populated_chain();
+    let path = temp_db("idempotent");
+
+    blockchain.save_to_db(&path.to_string_lossy()).unwrap();
+    blockchain.save_to_db(&path.to_string_lossy()).unwrap();
+
+    assert_eq!(count(&path, "blocks"), blockchain.chain.len() as i64);
+    assert_eq!(count(&path, "block_transactions"), 2);
+    assert_eq!(count(&path, "block_contracts"), 1);
+    let restored = Blockchain::load_from_db(&path.to_string_lossy()).unwrap();
+    assert_eq!(restored.chain.len(), blockchain.chain.len());
+    assert!(restored.is_chain_valid().unwrap());
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_persist_new_blocks_since_writes_only_the_tail() {
+    let mut blockchain = populated_chain();
+    let path = temp_db("incremental");
+    let db = path.to_string_lossy().into_owned();
+
+    assert_eq!(blockchain.persist_new_blocks_since(&db, 0).unwrap(), 2);
+    let persisted = blockchain.height();
+
+    let previous = blockchain.latest_block().unwrap().hash.clone();
+    let block = Block::new(persisted, Vec::new(), Vec::new(), previous).unwrap();
+    blockchain.add_block(block).unwrap();
+    blockchain.stakers.clear();
+
+    assert_eq!(
+        blockchain.persist_new_blocks_since(&db, persisted).unwrap(),
+        1
+    );
+    assert_eq!(count(&path, "blocks"), 3);
+
+    let restored = Blockchain::load_from_db(&db).unwrap();
+    assert_eq!(restored.height(), 3);
+    assert!(restored.stakers.is_empty());
+    assert!(restored.is_chain_valid().unwrap());
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_legacy_duplicate_rows_removed() {
+    let blockchain = populated_chain();
+    let path = temp_db("legacy");
+    blockchain.save_to_db(&path.to_string_lossy()).unwrap();
+
+    // Banco gravado antes das restrições de unicidade, com blocos repetidos
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    conn.execute_batch(
+        "DROP INDEX blocks_height_hash;
+         DROP INDEX block_transactions_position;
+         DROP INDEX block_contracts_position;
+         INSERT INTO blocks (height, timestamp, previous_hash, hash, validator_signature, nonce, processed_transactions)
+             SELECT height, timestamp, previous_hash, hash, validator_signature, nonce, processed_transactions FROM blocks;
+         INSERT INTO block_transactions SELECT * FROM block_transactions;",
+    )
+    .unwrap();
+    drop(conn);
+    assert_eq!(count(&path, "blocks"), 2 * blockchain.chain.len() as i64);
+
+    blockchain.save_to_db(&path.to_string_lossy()).unwrap();
+    assert_eq!(count(&path, "blocks"), blockchain.chain.len() as i64);
+    assert_eq!(count(&path, "block_transactions"), 2);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_block_checksum_detects_silent_corruption() {
+    let blockchain = populated_chain();
+    let path = temp_db("checksum");
+    let db = path.to_string_lossy().into_owned();
+    blockchain.save_to_db(&db).unwrap();
+    let conn = rusqlite::Connection::open(&path).unwrap();
+
+    // O conteúdo alterado continua um JSON válido; só o checksum denuncia a troca
+    let frame: Vec<u8> = conn
+        .query_row(
+            "SELECT data FROM block_transactions WHERE position = 0",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    let data = String::from_utf8(compression::decode(&frame, MAX_BLOCK_SIZE).unwrap()).unwrap();
+    let data = data.replace(&"b".repeat(40), &"e".repeat(40));
+    conn.execute(
+        "UPDATE block_transactions SET data = ?1 WHERE position = 0",
+        [compression::encode(Codec::Zstd, data.as_bytes()).unwrap()],
+    )
+    .unwrap();
+    let err = Blockchain::load_from_db(&db).unwrap_err();
+    assert!(err.to_string().contains("Checksum do bloco 0"), "{}", err);
+
+    // Bancos anteriores às somas de verificação carregam sem conferência
+    conn.execute("UPDATE blocks SET checksum = NULL", [])
+        .unwrap();
+    let restored = Blockchain::load_from_db(&db).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(restored.chain[0].transactions[0].to, "e".repeat(40));
+}
+
+#[test]
+fn test_block_columns_stored_compressed_and_legacy_text_loads() {
+    let blockchain = populated_chain();
+    let path = temp_db("compressed");
+    let db = path.to_string_lossy().into_owned();
+    blockchain.save_to_db(&db).unwrap();
+    let conn = rusqlite::Connection::open(&path).unwrap();
+
+    for table in ["block_transactions", "block_contracts"] {
+        let frames: Vec<Vec<u8>> = conn
+            .prepare(&format!("SELECT data FROM {}", table))
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(!frames.is_empty(), "{}", table);
+        assert!(frames.iter().all(|frame| frame[0] == Codec::Zstd.id()));
+    }
+
+    // Bancos gravados antes da compressão guardam o JSON como texto
+    let frames: Vec<(i64, Vec<u8>)> = conn
+        .prepare("SELECT rowid, data FROM block_transactions")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    for (rowid, frame) in frames {
+        let text = String::from_utf8(compression::decode(&frame, MAX_BLOCK_SIZE).unwrap()).unwrap();
+        conn.execute(
+            "UPDATE block_transactions SET data = ?1 WHERE rowid = ?2",
+            rusqlite::params![text, rowid],
+        )
+        .unwrap();
+    }
+    let restored = Blockchain::load_from_db(&db).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(restored.chain.len(), blockchain.chain.len());
+    for (restored, original) in restored.chain.iter().zip(&blockchain.chain) {
+        assert_eq!(restored.hash, original.hash);
+        assert_eq!(restored.transactions.len(), original.transactions.len());
+    }
+}
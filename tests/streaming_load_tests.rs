@@ -0,0 +1,99 @@
+use kybelith::blockchain::{Block, Blockchain};
+use kybelith::test_utils::fixtures::temp_path;
+use kybelith::transaction::SecureTransaction;
+use kybelith::utils::address::derive_address;
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+
+/// Cadeia com `blocks` blocos encadeados, cada um com uma transação assinada
+fn long_chain(blocks: u64) -> Blockchain {
+    let keys = keypair();
+    let timestamp = chrono::Utc::now().timestamp();
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut previous_hash = "0".repeat(64);
+    for index in 0..blocks {
+        let tx = SecureTransaction::new(
+            derive_address(keys.0.as_bytes()),
+            "b".repeat(40),
+            index + 1,
+            timestamp,
+            index + 1,
+            &keys.1,
+            &keys.0,
+        )
+        .unwrap();
+        let block = Block::new(index, vec![tx], Vec::new(), previous_hash).unwrap();
+        previous_hash = block.hash.clone();
+        blockchain.chain.push(block);
+    }
+    blockchain
+}
+
+#[test]
+fn test_load_recent_from_file_keeps_only_recent_bodies() {
+    let blockchain = long_chain(6);
+    let path = temp_path("file.json").to_string_lossy().into_owned();
+    blockchain.save_to_file(&path).unwrap();
+
+    let mut heights = Vec::new();
+    Blockchain::for_each_block_in_file(&path, |block| {
+        heights.push(block.index);
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(heights, vec![0, 1, 2, 3, 4, 5]);
+
+    let recent = Blockchain::load_recent_from_file(&path, 2).unwrap();
+    assert_eq!(recent.height(), 6);
+    assert!(recent.is_detached(3) && !recent.is_detached(4));
+    assert!(recent.chain[3].transactions.is_empty());
+    assert_eq!(recent.chain[5].transactions.len(), 1);
+    assert_eq!(
+        recent.tokens["0"].total_supply,
+        blockchain.tokens["0"].total_supply
+    );
+    assert!(recent.is_chain_valid().unwrap());
+    assert_eq!(
+        recent.headers_root(5).unwrap(),
+        blockchain.headers_root(5).unwrap()
+    );
+
+    // Regravar a cadeia parcial apagaria os corpos antigos do disco
+    assert!(recent.save_to_file(&path).is_err());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_load_recent_from_db_and_corrupted_stream() {
+    let blockchain = long_chain(5);
+    let db = temp_path("chain.db").to_string_lossy().into_owned();
+    blockchain.save_to_db(&db).unwrap();
+
+    let mut count = 0;
+    Blockchain::for_each_block_in_db(&db, |_| {
+        count += 1;
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(count, 5);
+
+    let recent = Blockchain::load_recent_from_db(&db, 1).unwrap();
+    assert_eq!(recent.detached_blocks.len(), 4);
+    assert!(recent.persist_new_blocks_since(&db, 0).is_err());
+    assert_eq!(recent.persist_new_blocks_since(&db, 5).unwrap(), 0);
+    let _ = std::fs::remove_file(&db);
+
+    // Um bloco adulterado no meio da cadeia é recusado durante a leitura
+    let mut tampered = long_chain(3);
+    tampered.chain[1].transactions[0].amount += 1;
+    let path = temp_path("tampered.json").to_string_lossy().into_owned();
+    tampered.save_to_file(&path).unwrap();
+    let err = Blockchain::load_recent_from_file(&path, 1).unwrap_err();
+    let _ = std::fs::remove_file(&path);
+    assert!(
+        err.to_string()
+            .contains("Bloco 1 traz transação com assinatura inválida"),
+        "{}",
+        err
+    );
+}
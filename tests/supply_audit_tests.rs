@@ -0,0 +1,82 @@
+use kybelith::blockchain::{Blockchain, SupplyChangeKind};
+use kybelith::test_utils::fixtures::{commit, Account};
+use kybelith::transaction::OperationKind;
+use kybelith::{Database, KeyManager, QuantumBlockchainApp};
+
+#[test]
+fn test_genesis_and_new_tokens_are_consistent() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let report = &blockchain.supply_audit()[0];
+    assert!(report.is_consistent(), "{:?}", report);
+    assert_eq!(report.issued, 10_000_000);
+    assert_eq!(report.minted, 1_000_000);
+    assert_eq!(report.expected_supply, Some(11_000_000));
+
+    let mut alice = Account::new();
+    let token_id = blockchain.next_token_id;
+    let create = alice.operation(OperationKind::CreateToken {
+        token_id,
+        name: "Cota de Equipe".to_string(),
+        symbol: "TEAM".to_string(),
+        total_supply: 5_000,
+    });
+    commit(&mut blockchain, [create]);
+    let grant = alice.operation(OperationKind::CreateVesting {
+        token_id,
+        beneficiary: "b".repeat(40),
+        amount: 1_200,
+        start: chrono::Utc::now().timestamp() as u64 + 10_000,
+        cliff_secs: 0,
+        duration_secs: 100,
+    });
+    commit(&mut blockchain, [grant]);
+
+    let reports = blockchain.supply_audit();
+    let team = reports
+        .iter()
+        .find(|report| report.token_id == token_id.to_string())
+        .unwrap();
+    assert!(team.is_consistent(), "{:?}", team);
+    assert_eq!(team.public_balances, 3_800);
+    assert_eq!(team.vesting_locked, 1_200);
+    assert_eq!(team.expected_supply, Some(5_000));
+    assert!(blockchain
+        .supply_history
+        .iter()
+        .any(|change| change.token_id == token_id.to_string()
+            && change.kind == SupplyChangeKind::Issue
+            && change.amount == 5_000));
+}
+
+#[test]
+fn test_audit_flags_balance_created_out_of_thin_air() {
+    let dir = std::env::temp_dir().join(format!("kybelith-supply-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut app = QuantumBlockchainApp {
+        blockchain: Blockchain::new().unwrap(),
+        key_manager: KeyManager::new().unwrap(),
+        database: Database::new(&dir.join("supply.db").to_string_lossy()).unwrap(),
+    };
+    assert!(app.verify_chain_integrity_with(true).unwrap());
+
+    app.blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert("a".repeat(40), 500);
+    let report = &app.blockchain.supply_audit()[0];
+    assert!(!report.is_consistent());
+    assert_eq!(report.circulating, report.total_supply + 500);
+
+    // Sem a auditoria, só a estrutura da cadeia é conferida
+    assert!(app.verify_chain_integrity().unwrap());
+    assert!(!app.verify_chain_integrity_with(true).unwrap());
+
+    // Ajustar o total também não basta: o histórico não registra a emissão
+    app.blockchain.tokens.get_mut("0").unwrap().total_supply += 500;
+    let report = &app.blockchain.supply_audit()[0];
+    assert_eq!(report.circulating, report.total_supply);
+    assert!(!report.is_consistent());
+    let _ = std::fs::remove_dir_all(&dir);
+}
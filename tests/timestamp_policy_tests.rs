@@ -0,0 +1,93 @@
+use kybelith::config::ClockConfig;
+use kybelith::consensus::types::VerificationResult;
+use kybelith::consensus::{
+    BlockProposal, ProposalVerifier, ReputationSystem, Validator, ValidatorSet,
+};
+use kybelith::transaction::Transaction;
+use kybelith::utils::clock::clock;
+use kybelith::utils::timestamp_policy::{TimestampContext, TimestampPolicy, TimestampViolation};
+use std::sync::{Arc, RwLock};
+
+#[test]
+fn test_policy_bounds_in_seconds_and_millis() {
+    let policy = TimestampPolicy::new(60, 3_600);
+    let now = 1_700_000_000;
+
+    assert!(policy.check_secs(now + 60, now).is_ok());
+    assert!(policy.check_secs(now - 3_600, now).is_ok());
+    assert_eq!(
+        policy.check_secs(now + 61, now),
+        Err(TimestampViolation::InFuture { excess_ms: 1_000 })
+    );
+    assert_eq!(
+        policy.check_secs(now - 3_602, now),
+        Err(TimestampViolation::TooOld { excess_ms: 2_000 })
+    );
+
+    let now_ms = now * 1000;
+    assert!(policy.check_millis(now_ms + 60_000, now_ms).is_ok());
+    assert_eq!(
+        policy.check_millis(now_ms + 60_001, now_ms),
+        Err(TimestampViolation::InFuture { excess_ms: 1 })
+    );
+    assert!(TimestampPolicy::symmetric(5)
+        .check_secs(i64::MIN, i64::MAX)
+        .is_err());
+}
+
+#[test]
+fn test_every_path_follows_its_configured_context() {
+    // Futuro curto e passado longo: só a idade diferencia os contextos
+    let config = ClockConfig {
+        transaction_timestamps: TimestampPolicy::new(5, 30),
+        proposal_timestamps: TimestampPolicy::new(5, 600),
+        ..ClockConfig::default()
+    };
+    clock().configure(config.clone());
+    assert_eq!(
+        TimestampPolicy::for_context(TimestampContext::IncludedTransaction),
+        config.included_transaction_timestamps
+    );
+
+    let now = chrono::Utc::now().timestamp();
+    let tx = |timestamp| {
+        Transaction::with_timestamp("a".repeat(40), "b".repeat(40), 5, vec![1; 32], timestamp)
+            .unwrap()
+    };
+    assert!(tx(now - 20).validate_timestamp().is_ok());
+    assert!(tx(now - 120).validate_timestamp().is_err());
+    assert!(tx(now + 60).validate_timestamp().is_err());
+
+    let validators = ValidatorSet::new(vec![Validator::new(
+        "v1".to_string(),
+        "addr-v1".to_string(),
+        vec![1; 32],
+        1_000,
+    )]);
+    let verifier = ProposalVerifier::new(
+        Arc::new(RwLock::new(validators)),
+        Arc::new(RwLock::new(ReputationSystem::new())),
+    );
+    let mut proposal = BlockProposal::new(
+        "hash".to_string(),
+        1,
+        "parent".to_string(),
+        "v1".to_string(),
+        Vec::new(),
+        vec![1],
+        Vec::new(),
+    );
+    let now_ms = proposal.timestamp;
+    proposal.timestamp = now_ms - 120_000;
+    assert!(matches!(
+        verifier.verify(&proposal).unwrap(),
+        VerificationResult::Valid
+    ));
+    proposal.timestamp = now_ms + 60_000;
+    assert!(matches!(
+        verifier.verify(&proposal).unwrap(),
+        VerificationResult::Invalid(_)
+    ));
+
+    clock().configure(ClockConfig::default());
+}
@@ -0,0 +1,135 @@
+use kybelith::blockchain::{Blockchain, LifecycleState, SharedBlockchain};
+use kybelith::error::{ErrorCode, TransactionError};
+use kybelith::rpc::{RpcService, SubmitResult, TokenResponse};
+use kybelith::test_utils::fixtures::{bob, fund, Account};
+use kybelith::transaction::OperationKind;
+use serde_json::json;
+
+fn commit(blockchain: &mut Blockchain, account: &mut Account, kind: OperationKind) {
+    let operation = account.operation(kind);
+    blockchain.submit_operation(operation).unwrap();
+    blockchain.produce_block(10).unwrap();
+}
+
+fn create_token(blockchain: &mut Blockchain, owner: &mut Account) -> u64 {
+    let token_id = blockchain.next_token_id;
+    let kind = OperationKind::CreateToken {
+        token_id,
+        name: "Token Administrado".to_string(),
+        symbol: "ADM".to_string(),
+        total_supply: 1_000,
+    };
+    commit(blockchain, owner, kind);
+    // KYBL para as taxas das transferências do dono
+    fund(blockchain, &owner.address, 100);
+    token_id
+}
+
+#[test]
+fn test_mint_burn_and_pause() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut owner = Account::new();
+    let mut stranger = Account::new();
+    let token_id = create_token(&mut blockchain, &mut owner);
+
+    let mint = OperationKind::MintTokens {
+        token_id,
+        to: stranger.address.clone(),
+        amount: 500,
+    };
+    assert!(blockchain
+        .submit_operation(stranger.operation(mint.clone()))
+        .is_err());
+    commit(&mut blockchain, &mut owner, mint);
+    commit(
+        &mut blockchain,
+        &mut owner,
+        OperationKind::BurnTokens {
+            token_id,
+            amount: 200,
+        },
+    );
+
+    let token = blockchain.get_token(&token_id.to_string()).unwrap();
+    assert_eq!(token.total_supply, 1_300);
+    assert_eq!(token.balances[&owner.address], 800);
+    assert_eq!(token.balances[&stranger.address], 500);
+    let report = blockchain
+        .supply_audit()
+        .into_iter()
+        .find(|report| report.token_id == token_id.to_string())
+        .unwrap();
+    assert_eq!((report.minted, report.burned), (500, 200));
+    assert!(report.is_consistent());
+
+    commit(
+        &mut blockchain,
+        &mut owner,
+        OperationKind::SetTokenPaused {
+            token_id,
+            paused: true,
+        },
+    );
+    assert!(blockchain.is_token_paused(token_id));
+
+    // A transferência é admitida, mas descartada ao fechar o bloco
+    let tx = owner.transfer_token(token_id, &bob(), 10);
+    let txid = tx.txid();
+    blockchain.submit_transaction(tx).unwrap();
+    blockchain.produce_block(10).unwrap();
+    assert!(matches!(
+        blockchain.transaction_status(&txid).unwrap().state,
+        LifecycleState::Dropped { code: Some(code), .. }
+            if code == TransactionError::TokenPaused(token_id).code()
+    ));
+
+    commit(
+        &mut blockchain,
+        &mut owner,
+        OperationKind::SetTokenPaused {
+            token_id,
+            paused: false,
+        },
+    );
+    let tx = owner.transfer_token(token_id, &bob(), 10);
+    blockchain.submit_transaction(tx).unwrap();
+    blockchain.produce_block(10).unwrap();
+    let token = blockchain.get_token(&token_id.to_string()).unwrap();
+    assert_eq!(token.balances[&owner.address], 790);
+}
+
+#[test]
+fn test_submit_operation_rpc_and_token_info() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut owner = Account::new();
+    let token_id = create_token(&mut blockchain, &mut owner);
+    let shared = SharedBlockchain::new(blockchain);
+    let service = RpcService::new(shared.clone());
+
+    let pause = owner.operation(OperationKind::SetTokenPaused {
+        token_id,
+        paused: true,
+    });
+    let result: SubmitResult =
+        serde_json::from_value(service.handle("submit_operation", json!(pause)).unwrap()).unwrap();
+    assert!(result.accepted);
+
+    // Nonce repetido é recusado com o código estável
+    let result: SubmitResult =
+        serde_json::from_value(service.handle("submit_operation", json!(pause)).unwrap()).unwrap();
+    assert!(!result.accepted);
+    assert_eq!(
+        result.error_code,
+        Some(TransactionError::NonceInvalido.code())
+    );
+
+    shared.produce_block(10).unwrap();
+    let info: TokenResponse = serde_json::from_value(
+        service
+            .handle("get_token", json!({ "token_id": token_id.to_string() }))
+            .unwrap(),
+    )
+    .unwrap();
+    assert!(info.paused);
+    assert_eq!(info.metadata.owner, owner.address);
+}
@@ -0,0 +1,130 @@
+use kybelith::blockchain::Blockchain;
+use kybelith::error::TransactionError;
+use kybelith::test_utils::fixtures::Account;
+use kybelith::transaction::{OperationKind, TransferRule};
+
+/// Submete a operação e desfaz o nonce se ela for recusada
+fn submit(
+    account: &mut Account,
+    blockchain: &mut Blockchain,
+    kind: OperationKind,
+) -> Result<(), TransactionError> {
+    let operation = account.operation(kind);
+    blockchain.submit_operation(operation).inspect_err(|_| {
+        account.nonce -= 1;
+    })
+}
+
+fn commit(blockchain: &mut Blockchain, account: &mut Account, kind: OperationKind) {
+    submit(account, blockchain, kind).unwrap();
+    blockchain.produce_block(10).unwrap();
+}
+
+fn create_token(blockchain: &mut Blockchain, owner: &mut Account) -> u64 {
+    let token_id = blockchain.next_token_id;
+    let kind = OperationKind::CreateToken {
+        token_id,
+        name: "Token Gerido".to_string(),
+        symbol: "GER".to_string(),
+        total_supply: 1_000,
+    };
+    commit(blockchain, owner, kind);
+    token_id
+}
+
+fn limit(token_id: u64, amount: u64) -> OperationKind {
+    OperationKind::SetTransferPolicy {
+        token_id,
+        rules: vec![TransferRule::MaxTransferAmount(amount)],
+    }
+}
+
+#[test]
+fn test_two_step_ownership_transfer() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut alice = Account::new();
+    let mut bob = Account::new();
+    let mut mallory = Account::new();
+    let token_id = create_token(&mut blockchain, &mut alice);
+
+    let propose = |new_owner: &str| OperationKind::ProposeTokenOwner {
+        token_id,
+        new_owner: new_owner.to_string(),
+    };
+    assert!(matches!(
+        submit(&mut mallory, &mut blockchain, propose(&bob.address)),
+        Err(TransactionError::InvalidParameter(_))
+    ));
+    commit(&mut blockchain, &mut alice, propose(&bob.address));
+    assert_eq!(
+        blockchain.pending_token_owner(token_id),
+        Some(bob.address.as_str())
+    );
+    // A proposta sozinha não muda o dono
+    assert_eq!(
+        blockchain.token_owner(token_id),
+        Some(alice.address.as_str())
+    );
+
+    let accept = OperationKind::AcceptTokenOwner { token_id };
+    assert!(matches!(
+        submit(&mut mallory, &mut blockchain, accept.clone()),
+        Err(TransactionError::InvalidParameter(_))
+    ));
+    commit(&mut blockchain, &mut bob, accept);
+    assert_eq!(blockchain.token_owner(token_id), Some(bob.address.as_str()));
+    assert!(blockchain.pending_token_owner(token_id).is_none());
+
+    assert!(submit(&mut alice, &mut blockchain, limit(token_id, 10)).is_err());
+    commit(&mut blockchain, &mut bob, limit(token_id, 10));
+}
+
+#[test]
+fn test_secondary_admins() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut alice = Account::new();
+    let mut carol = Account::new();
+    let mut bob = Account::new();
+    let token_id = create_token(&mut blockchain, &mut alice);
+
+    let set_admin = |admin: &str, granted| OperationKind::SetTokenAdmin {
+        token_id,
+        admin: admin.to_string(),
+        granted,
+    };
+    commit(&mut blockchain, &mut alice, set_admin(&carol.address, true));
+    assert_eq!(
+        blockchain.token_admins(token_id),
+        vec![carol.address.clone()]
+    );
+
+    // Administradores definem a política, mas não nomeiam outros administradores
+    commit(&mut blockchain, &mut carol, limit(token_id, 50));
+    assert_eq!(
+        blockchain.transfer_policy(token_id),
+        [TransferRule::MaxTransferAmount(50)]
+    );
+    assert!(submit(&mut carol, &mut blockchain, set_admin(&bob.address, true)).is_err());
+
+    commit(
+        &mut blockchain,
+        &mut alice,
+        set_admin(&carol.address, false),
+    );
+    assert!(blockchain.token_admins(token_id).is_empty());
+    assert!(submit(&mut carol, &mut blockchain, limit(token_id, 5)).is_err());
+
+    // A troca de dono descarta os administradores do dono anterior
+    commit(&mut blockchain, &mut alice, set_admin(&carol.address, true));
+    let propose = OperationKind::ProposeTokenOwner {
+        token_id,
+        new_owner: bob.address.clone(),
+    };
+    commit(&mut blockchain, &mut alice, propose);
+    commit(
+        &mut blockchain,
+        &mut bob,
+        OperationKind::AcceptTokenOwner { token_id },
+    );
+    assert!(blockchain.token_admins(token_id).is_empty());
+}
@@ -0,0 +1,93 @@
+use kybelith::blockchain::{Blockchain, TransactionIndex};
+use kybelith::error::Error;
+use kybelith::test_utils::fixtures::{commit_transaction, fund, Account};
+use kybelith::transaction::OperationKind;
+use std::collections::HashMap;
+
+#[test]
+fn test_snapshot_tracks_balances_by_height() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut alice = Account::new();
+    let mut bob = Account::new();
+    // Bloco anterior ao token: a transferência sem saldo é descartada na confirmação
+    commit_transaction(&mut blockchain, alice.transfer_token(0, &bob.address, 1));
+    // KYBL para as taxas das transferências do token
+    fund(&mut blockchain, &alice.address, 10);
+    fund(&mut blockchain, &bob.address, 10);
+    let token_id = blockchain.next_token_id;
+    let operation = alice.operation(OperationKind::CreateToken {
+        token_id,
+        name: "Cota de Fundo".to_string(),
+        symbol: "FUND".to_string(),
+        total_supply: 10_000,
+    });
+    blockchain.submit_operation(operation).unwrap();
+    let created = blockchain.produce_block(10).unwrap().unwrap().index;
+
+    let first = commit_transaction(
+        &mut blockchain,
+        alice.transfer_token(token_id, &bob.address, 3_000),
+    )
+    .index;
+    let second = commit_transaction(
+        &mut blockchain,
+        bob.transfer_token(token_id, &alice.address, 3_000),
+    )
+    .index;
+
+    let at_creation = blockchain.snapshot(token_id, created).unwrap();
+    assert_eq!(at_creation.balance_of(&alice.address), 10_000);
+    assert_eq!(at_creation.len(), 1);
+
+    let after_first = blockchain.snapshot(token_id, first).unwrap();
+    assert_eq!(after_first.balance_of(&alice.address), 7_000);
+    assert_eq!(after_first.balance_of(&bob.address), 3_000);
+    assert_eq!(after_first.total(), 10_000);
+
+    // Bob zerou o saldo e deixa de aparecer como detentor
+    let after_second = blockchain.snapshot(token_id, second).unwrap();
+    assert_eq!(after_second.balance_of(&bob.address), 0);
+    assert_eq!(after_second.len(), 1);
+
+    // A foto não muda quando a cadeia avança
+    commit_transaction(
+        &mut blockchain,
+        alice.transfer_token(token_id, &bob.address, 1),
+    );
+    assert_eq!(blockchain.snapshot(token_id, first).unwrap(), after_first);
+
+    // O token nativo é acompanhado desde a criação da cadeia
+    assert_eq!(blockchain.snapshot(0, 0).unwrap().total(), 11_000_000);
+
+    let token = blockchain.get_token(&token_id.to_string()).unwrap();
+    assert!(matches!(
+        token.snapshot(created - 1, &blockchain.index),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        blockchain.snapshot(token_id, second + 10),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        blockchain.snapshot(99, second),
+        Err(Error::TokenNotFound)
+    ));
+}
+
+#[test]
+fn test_index_balances_between_checkpoints() {
+    let mut index = TransactionIndex::default();
+    let mut balances = HashMap::from([("a".to_string(), 50), ("b".to_string(), 50)]);
+    index.record_balances(7, 3, &balances);
+
+    balances.remove("b");
+    balances.insert("a".to_string(), 100);
+    index.record_balances(7, 8, &balances);
+
+    assert!(index.balances_at(7, 2).is_none());
+    assert!(index.balances_at(8, 5).is_none());
+    assert_eq!(index.balances_at(7, 5).unwrap()["b"], 50);
+    let latest = index.balances_at(7, 20).unwrap();
+    assert_eq!(latest.len(), 1);
+    assert_eq!(latest["a"], 100);
+}
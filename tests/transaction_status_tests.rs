@@ -0,0 +1,91 @@
+use kybelith::blockchain::{Blockchain, CheckpointAttestation, LifecycleState, SharedBlockchain};
+use kybelith::error::{ErrorCode, TransactionError};
+use kybelith::rpc::RpcService;
+use kybelith::test_utils::fixtures::{address_of, transfer};
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+use serde_json::json;
+
+#[test]
+fn test_status_follows_lifecycle_and_survives_sqlite() {
+    let keys = keypair();
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(address_of(&keys), 100);
+    let state = |blockchain: &Blockchain, txid: &str| {
+        blockchain.transaction_status(txid).unwrap().state.clone()
+    };
+
+    let paid = transfer(&keys, 10, 1);
+    let overdrawn = transfer(&keys, 1000, 2);
+    let (paid_id, overdrawn_id) = (paid.txid(), overdrawn.txid());
+    blockchain.submit_transaction(paid).unwrap();
+    blockchain.submit_transaction(overdrawn).unwrap();
+    assert_eq!(state(&blockchain, &paid_id), LifecycleState::Pending);
+
+    // O saldo só é conferido ao fechar o bloco, que descarta a segunda
+    blockchain.produce_block(10).unwrap().unwrap();
+    assert_eq!(
+        state(&blockchain, &paid_id),
+        LifecycleState::Included { height: 0 }
+    );
+    assert!(matches!(
+        state(&blockchain, &overdrawn_id),
+        LifecycleState::Dropped { code: Some(code), .. }
+            if code == TransactionError::InsufficientFunds.code()
+    ));
+
+    let validator = keypair();
+    blockchain.register_validator_key(validator.0.as_bytes().to_vec());
+    let attestation =
+        CheckpointAttestation::create(&blockchain, 0, &validator.0, &validator.1).unwrap();
+    blockchain
+        .record_checkpoint(attestation, &validator.0)
+        .unwrap();
+    assert_eq!(
+        state(&blockchain, &paid_id),
+        LifecycleState::Finalized { height: 0 }
+    );
+
+    let path = std::env::temp_dir().join(format!(
+        "kybelith-transaction-status-{}.db",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    blockchain.save_to_db(&path.to_string_lossy()).unwrap();
+    let restored = Blockchain::load_from_db(&path.to_string_lossy()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(
+        restored.transaction_status(&paid_id),
+        blockchain.transaction_status(&paid_id)
+    );
+    assert_eq!(restored.transaction_statuses.len(), 2);
+}
+
+#[test]
+fn test_get_transaction_status_rpc() {
+    let keys = keypair();
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .tokens
+        .get_mut("0")
+        .unwrap()
+        .balances
+        .insert(address_of(&keys), 100);
+    let tx = transfer(&keys, 10, 1);
+    let txid = tx.txid();
+    blockchain.submit_transaction(tx).unwrap();
+
+    let service = RpcService::new(SharedBlockchain::new(blockchain));
+    let status = service
+        .handle("get_transaction_status", json!({ "txid": txid }))
+        .unwrap();
+    assert_eq!(status["state"]["stage"], "pending");
+    assert!(service
+        .handle("get_transaction_status", json!({ "txid": "0".repeat(64) }))
+        .is_err());
+}
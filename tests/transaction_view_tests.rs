@@ -0,0 +1,88 @@
+use bytes::Bytes;
+use kybelith::error::TransactionError;
+use kybelith::test_utils::fixtures::transfer;
+use kybelith::transaction::{EncodedTransaction, Transaction, TransactionView};
+use pqcrypto_dilithium::dilithium5::keypair;
+use serde::Serialize;
+
+#[test]
+fn test_signing_encoding_matches_owned_layout() {
+    // Layout anterior, com campos proprietários: as assinaturas existentes
+    // precisam continuar válidas após a troca para campos emprestados
+    #[derive(Serialize)]
+    struct OwnedSignableData {
+        token_id: u64,
+        from: String,
+        to: String,
+        amount: u64,
+        timestamp: i64,
+        nonce: u64,
+        public_key: Vec<u8>,
+    }
+
+    let keys = keypair();
+    let tx = transfer(&keys, 250, 7);
+    let owned = bincode::serialize(&OwnedSignableData {
+        token_id: tx.token_id,
+        from: tx.from.clone(),
+        to: tx.to.clone(),
+        amount: tx.amount,
+        timestamp: tx.timestamp,
+        nonce: tx.nonce,
+        public_key: tx.public_key.clone(),
+    })
+    .unwrap();
+
+    assert_eq!(tx.serialize_for_signing().unwrap(), owned);
+
+    let mut buf = vec![0xAA; 16];
+    tx.serialize_for_signing_into(&mut buf).unwrap();
+    assert_eq!(buf, owned);
+}
+
+#[test]
+fn test_view_decodes_borrowed_fields() {
+    let keys = keypair();
+    let tx = transfer(&keys, 250, 7);
+    let data = bincode::serialize(&tx).unwrap();
+
+    let view = TransactionView::decode(&data).unwrap();
+
+    assert_eq!(view, tx.view());
+    // Os campos apontam para dentro do buffer decodificado
+    assert!(data.as_ptr_range().contains(&view.from.as_ptr()));
+    assert!(data.as_ptr_range().contains(&view.signature.as_ptr()));
+    view.verify(&keys.0).unwrap();
+    view.verify_embedded_key().unwrap();
+    assert_eq!(view.to_transaction().signature, tx.signature);
+}
+
+#[test]
+fn test_encoded_transaction_shares_buffer() {
+    let keys = keypair();
+    let tx = transfer(&keys, 250, 7);
+
+    let encoded = EncodedTransaction::encode(&tx).unwrap();
+    let copy = encoded.clone();
+
+    assert_eq!(encoded.as_bytes().as_ptr(), copy.as_bytes().as_ptr());
+    copy.view().verify_embedded_key().unwrap();
+    assert_eq!(
+        Transaction::deserialize_from_bytes(encoded.as_bytes())
+            .unwrap()
+            .nonce,
+        7
+    );
+}
+
+#[test]
+fn test_encoded_transaction_rejects_malformed_bytes() {
+    let result = EncodedTransaction::new(Bytes::from_static(&[0xff; 8]));
+    assert!(matches!(result, Err(TransactionError::InvalidDataFormat)));
+
+    let keys = keypair();
+    let mut tx = transfer(&keys, 250, 7);
+    tx.amount += 1;
+    let encoded = EncodedTransaction::encode(&tx).unwrap();
+    assert!(encoded.view().verify(&keys.0).is_err());
+}
@@ -0,0 +1,202 @@
+use kybelith::blockchain::Blockchain;
+use kybelith::error::TransactionError;
+use kybelith::rbac::AdminRole;
+use kybelith::test_utils::fixtures::{balance, fund, Account};
+use kybelith::token::policy::{evaluate, TransferCheck};
+use kybelith::transaction::{OperationKind, Transaction, TransferRule};
+
+const LIMIT_CONTRACT: &str = r#"
+(module
+  (func (export "check_transfer") (param i64 i32 i32) (result i32)
+    (if (result i32) (i64.gt_u (local.get 0) (i64.const 100))
+      (then (i32.const 1))
+      (else (i32.const 0)))))
+"#;
+
+fn create_token(blockchain: &mut Blockchain, creator: &mut Account) -> u64 {
+    let token_id = blockchain.next_token_id;
+    let operation = creator.operation(OperationKind::CreateToken {
+        token_id,
+        name: "Cota Regulada".to_string(),
+        symbol: "COTA".to_string(),
+        total_supply: 10_000,
+    });
+    blockchain.submit_operation(operation).unwrap();
+    blockchain.produce_block(10).unwrap();
+    // KYBL para as taxas das transferências do criador
+    fund(blockchain, &creator.address, 100);
+    token_id
+}
+
+fn commit(blockchain: &mut Blockchain, tx: Transaction) {
+    blockchain.submit_transaction(tx).unwrap();
+    blockchain.produce_block(10).unwrap();
+}
+
+#[test]
+fn test_builtin_policies_gate_transfers() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut issuer = Account::new();
+    let listed = Account::new();
+    let outsider = Account::new();
+    let token_id = create_token(&mut blockchain, &mut issuer);
+
+    let policy = OperationKind::SetTransferPolicy {
+        token_id,
+        rules: vec![
+            TransferRule::MaxTransferAmount(500),
+            TransferRule::AllowList(vec![issuer.address.clone(), listed.address.clone()]),
+        ],
+    };
+    // Só o criador do token define a política
+    let mut intruder = Account::new();
+    assert!(matches!(
+        blockchain.submit_operation(intruder.operation(policy.clone())),
+        Err(TransactionError::InvalidParameter(_))
+    ));
+    blockchain
+        .submit_operation(issuer.operation(policy))
+        .unwrap();
+    blockchain.produce_block(10).unwrap();
+    assert_eq!(blockchain.transfer_policy(token_id).len(), 2);
+
+    commit(
+        &mut blockchain,
+        issuer.transfer_token(token_id, &listed.address, 501),
+    );
+    commit(
+        &mut blockchain,
+        issuer.transfer_token(token_id, &outsider.address, 10),
+    );
+    commit(
+        &mut blockchain,
+        issuer.transfer_token(token_id, &listed.address, 500),
+    );
+    assert_eq!(balance(&blockchain, token_id, &listed.address), 500);
+    assert_eq!(balance(&blockchain, token_id, &outsider.address), 0);
+
+    // Política vazia remove as regras
+    let clear = issuer.operation(OperationKind::SetTransferPolicy {
+        token_id,
+        rules: Vec::new(),
+    });
+    blockchain.submit_operation(clear).unwrap();
+    blockchain.produce_block(10).unwrap();
+    commit(
+        &mut blockchain,
+        issuer.transfer_token(token_id, &outsider.address, 10),
+    );
+    assert_eq!(balance(&blockchain, token_id, &outsider.address), 10);
+}
+
+#[test]
+fn test_kyc_flags_set_by_operator() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut operator = Account::new();
+    let mut issuer = Account::new();
+    let holder = Account::new();
+    blockchain
+        .bootstrap_admin(operator.address.clone())
+        .unwrap();
+    let grant = operator.operation(OperationKind::AssignRole {
+        subject: operator.address.clone(),
+        role: AdminRole::Operator,
+        granted: true,
+    });
+    blockchain.submit_operation(grant).unwrap();
+    let token_id = create_token(&mut blockchain, &mut issuer);
+
+    let require_kyc = issuer.operation(OperationKind::SetTransferPolicy {
+        token_id,
+        rules: vec![TransferRule::RequireKyc],
+    });
+    blockchain.submit_operation(require_kyc).unwrap();
+    let self_kyc = issuer.operation(OperationKind::SetKyc {
+        subject: issuer.address.clone(),
+        verified: true,
+    });
+    assert!(blockchain.submit_operation(self_kyc).is_err());
+    issuer.nonce -= 1;
+    blockchain.produce_block(10).unwrap();
+
+    for subject in [&issuer.address, &holder.address] {
+        let mark = operator.operation(OperationKind::SetKyc {
+            subject: subject.clone(),
+            verified: true,
+        });
+        blockchain.submit_operation(mark).unwrap();
+    }
+    blockchain.produce_block(10).unwrap();
+    assert!(blockchain.is_kyc_verified(&holder.address));
+
+    commit(
+        &mut blockchain,
+        issuer.transfer_token(token_id, &holder.address, 25),
+    );
+    assert_eq!(balance(&blockchain, token_id, &holder.address), 25);
+
+    let unmark = operator.operation(OperationKind::SetKyc {
+        subject: holder.address.clone(),
+        verified: false,
+    });
+    blockchain.submit_operation(unmark).unwrap();
+    blockchain.produce_block(10).unwrap();
+    commit(
+        &mut blockchain,
+        issuer.transfer_token(token_id, &holder.address, 25),
+    );
+    assert_eq!(balance(&blockchain, token_id, &holder.address), 25);
+}
+
+#[test]
+fn test_contract_policy() {
+    let rules = vec![TransferRule::Contract {
+        code: LIMIT_CONTRACT.as_bytes().to_vec(),
+    }];
+    let check = |amount| TransferCheck {
+        token_id: 1,
+        from: "a",
+        to: "b",
+        amount,
+        from_kyc: false,
+        to_kyc: false,
+    };
+    evaluate(&rules, &check(100)).unwrap();
+    assert!(evaluate(&rules, &check(101)).is_err());
+    let broken = vec![TransferRule::Contract {
+        code: b"(module)".to_vec(),
+    }];
+    assert!(evaluate(&broken, &check(1)).is_err());
+
+    // Contrato na política exige que o criador seja operador
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut issuer = Account::new();
+    let holder = Account::new();
+    let token_id = create_token(&mut blockchain, &mut issuer);
+    let set = OperationKind::SetTransferPolicy { token_id, rules };
+    assert!(blockchain
+        .submit_operation(issuer.operation(set.clone()))
+        .is_err());
+    issuer.nonce -= 1;
+
+    blockchain.bootstrap_admin(issuer.address.clone()).unwrap();
+    let grant = issuer.operation(OperationKind::AssignRole {
+        subject: issuer.address.clone(),
+        role: AdminRole::Operator,
+        granted: true,
+    });
+    blockchain.submit_operation(grant).unwrap();
+    blockchain.produce_block(10).unwrap();
+    blockchain.submit_operation(issuer.operation(set)).unwrap();
+    blockchain.produce_block(10).unwrap();
+
+    commit(
+        &mut blockchain,
+        issuer.transfer_token(token_id, &holder.address, 150),
+    );
+    commit(
+        &mut blockchain,
+        issuer.transfer_token(token_id, &holder.address, 60),
+    );
+    assert_eq!(balance(&blockchain, token_id, &holder.address), 60);
+}
@@ -0,0 +1,203 @@
+use kybelith::network::{Initiator, NodeIdentity, Responder, SecureChannel, TransportError};
+use kybelith::utils::compression::{self, Codec};
+
+const GENESIS: [u8; 32] = [7; 32];
+
+#[tokio::test]
+async fn test_handshake_establishes_encrypted_channel() {
+    let alice = NodeIdentity::generate();
+    let bob = NodeIdentity::generate();
+    let bob_key = bob.public_key;
+    let (client, server) = tokio::io::duplex(64 * 1024);
+
+    let server = tokio::spawn(async move {
+        let mut channel = SecureChannel::accept(server, &bob, GENESIS, None)
+            .await
+            .unwrap();
+        let message = channel.recv().await.unwrap();
+        channel.send(b"pong").await.unwrap();
+        (message, *channel.peer(), channel.session_id())
+    });
+
+    let mut channel = SecureChannel::connect(client, &alice, GENESIS, Some(&bob_key))
+        .await
+        .unwrap();
+    channel.send(b"ping").await.unwrap();
+    assert_eq!(channel.recv().await.unwrap(), b"pong");
+
+    let (message, peer, session_id) = server.await.unwrap();
+    assert_eq!(message, b"ping");
+    assert!(peer == alice.public_key);
+    assert!(channel.peer() == &bob_key);
+    assert_eq!(session_id, channel.session_id());
+}
+
+#[tokio::test]
+async fn test_channel_compresses_with_negotiated_codec() {
+    let alice = NodeIdentity::generate();
+    let bob = NodeIdentity::generate();
+    let (client, server) = tokio::io::duplex(64 * 1024);
+    let block = vec![7u8; 256 * 1024];
+    let expected = block.clone();
+
+    let server = tokio::spawn(async move {
+        let mut channel = SecureChannel::accept(server, &bob, GENESIS, None)
+            .await
+            .unwrap();
+        (channel.codec(), channel.recv().await.unwrap())
+    });
+
+    let mut channel = SecureChannel::connect(client, &alice, GENESIS, None)
+        .await
+        .unwrap();
+    assert_eq!(channel.codec(), Codec::Zstd);
+    channel.send(&block).await.unwrap();
+
+    let (codec, received) = server.await.unwrap();
+    assert_eq!(codec, Codec::Zstd);
+    assert_eq!(received, expected);
+}
+
+#[test]
+fn test_handshake_authenticates_codec_choice() {
+    let alice = NodeIdentity::generate();
+    let bob = NodeIdentity::generate();
+
+    let (initiator, init) = Initiator::start(GENESIS).unwrap();
+    assert_eq!(init.codecs, Codec::supported_ids());
+    let (responder, response) = Responder::respond(&bob, GENESIS, &init).unwrap();
+    assert_eq!(response.codec, Codec::Zstd.id());
+    let (client, finish) = initiator.finish(&alice, &response, None).unwrap();
+    let server = responder.finish(&finish, None).unwrap();
+    assert_eq!((client.codec(), server.codec()), (Codec::Zstd, Codec::Zstd));
+
+    // Rebaixar a escolha do respondedor quebra a assinatura da transcrição
+    let (initiator, init) = Initiator::start(GENESIS).unwrap();
+    let (_, mut response) = Responder::respond(&bob, GENESIS, &init).unwrap();
+    response.codec = Codec::None.id();
+    assert!(matches!(
+        initiator.finish(&alice, &response, None),
+        Err(TransportError::InvalidSignature)
+    ));
+
+    // Assim como trocar a lista anunciada pelo iniciador
+    let (initiator, mut init) = Initiator::start(GENESIS).unwrap();
+    init.codecs = vec![Codec::None.id()];
+    let (_, response) = Responder::respond(&bob, GENESIS, &init).unwrap();
+    assert_eq!(response.codec, Codec::None.id());
+    assert!(matches!(
+        initiator.finish(&alice, &response, None),
+        Err(TransportError::InvalidSignature)
+    ));
+}
+
+#[test]
+fn test_handshake_rejects_unexpected_or_forged_peer() {
+    let alice = NodeIdentity::generate();
+    let bob = NodeIdentity::generate();
+    let mallory = NodeIdentity::generate();
+
+    // Responder com identidade diferente da fixada pelo iniciador
+    let (initiator, init) = Initiator::start(GENESIS).unwrap();
+    let (_, response) = Responder::respond(&mallory, GENESIS, &init).unwrap();
+    assert!(matches!(
+        initiator.finish(&alice, &response, Some(&bob.public_key)),
+        Err(TransportError::UnexpectedPeer)
+    ));
+
+    // Identidade trocada sem a assinatura correspondente
+    let (initiator, init) = Initiator::start(GENESIS).unwrap();
+    let (_, mut response) = Responder::respond(&mallory, GENESIS, &init).unwrap();
+    response.identity = pqcrypto_traits::sign::PublicKey::as_bytes(&bob.public_key).to_vec();
+    assert!(matches!(
+        initiator.finish(&alice, &response, None),
+        Err(TransportError::InvalidSignature)
+    ));
+}
+
+#[test]
+fn test_handshake_rejects_other_network() {
+    let alice = NodeIdentity::generate();
+    let bob = NodeIdentity::generate();
+
+    let (_, init) = Initiator::start([8; 32]).unwrap();
+    let err = Responder::respond(&bob, GENESIS, &init).err().unwrap();
+    assert!(matches!(err, TransportError::NetworkMismatch(_)));
+    assert!(err.misbehavior().is_none());
+
+    let (_, mut init) = Initiator::start(GENESIS).unwrap();
+    init.version += 1;
+    assert!(matches!(
+        Responder::respond(&bob, GENESIS, &init),
+        Err(TransportError::NetworkMismatch(_))
+    ));
+
+    // O gênese da resposta entra na transcrição assinada: trocá-lo não passa
+    let (initiator, init) = Initiator::start(GENESIS).unwrap();
+    let (_, mut response) = Responder::respond(&bob, GENESIS, &init).unwrap();
+    response.genesis_hash = [8; 32];
+    assert!(matches!(
+        initiator.finish(&alice, &response, None),
+        Err(TransportError::NetworkMismatch(_))
+    ));
+}
+
+#[test]
+fn test_session_rejects_tampered_and_replayed_frames() {
+    let alice = NodeIdentity::generate();
+    let bob = NodeIdentity::generate();
+
+    let (initiator, init) = Initiator::start(GENESIS).unwrap();
+    let (responder, response) = Responder::respond(&bob, GENESIS, &init).unwrap();
+    let (mut client, finish) = initiator.finish(&alice, &response, None).unwrap();
+    let mut server = responder.finish(&finish, Some(&alice.public_key)).unwrap();
+
+    let frame = client.seal(b"bloco 1");
+    assert_ne!(&frame[8..], b"bloco 1");
+
+    let mut tampered = frame.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    assert!(matches!(
+        server.open(&tampered),
+        Err(TransportError::Decryption)
+    ));
+
+    assert_eq!(server.open(&frame).unwrap(), b"bloco 1");
+    assert!(matches!(
+        server.open(&frame),
+        Err(TransportError::Replay {
+            expected: 1,
+            received: 0
+        })
+    ));
+
+    // Chaves são direcionais: um frame não volta para o próprio emissor
+    let echo = server.seal(b"resposta");
+    assert_eq!(client.open(&echo).unwrap(), b"resposta");
+    let own = client.seal(b"eco");
+    assert!(client.open(&own).is_err());
+}
+
+#[test]
+fn test_session_rejects_codec_other_than_negotiated() {
+    let alice = NodeIdentity::generate();
+    let bob = NodeIdentity::generate();
+
+    let (initiator, init) = Initiator::start(GENESIS).unwrap();
+    let (responder, response) = Responder::respond(&bob, GENESIS, &init).unwrap();
+    let (mut client, finish) = initiator.finish(&alice, &response, None).unwrap();
+    let mut server = responder.finish(&finish, None).unwrap();
+    assert_eq!(server.codec(), Codec::Zstd);
+
+    let payload = compression::encode(Codec::Zstd, b"bloco").unwrap();
+    let opened = server.open(&client.seal(&payload)).unwrap();
+    assert_eq!(server.decompress(&opened, 1024).unwrap(), b"bloco");
+
+    // Um payload sem compressão não passa numa sessão que negociou zstd
+    let payload = compression::encode(Codec::None, b"bloco").unwrap();
+    let opened = server.open(&client.seal(&payload)).unwrap();
+    assert!(matches!(
+        server.decompress(&opened, 1024),
+        Err(TransportError::Compression(_))
+    ));
+}
@@ -0,0 +1,99 @@
+use kybelith::blockchain::Blockchain;
+use kybelith::error::{Error, ErrorCode};
+use kybelith::test_utils::fixtures::Account;
+use kybelith::transaction::{Operation, OperationKind};
+use kybelith::utils::version::ClientVersion;
+
+fn schedule(account: &mut Account, min_version: &str, activation_height: u64) -> Operation {
+    account.operation(OperationKind::ScheduleUpgrade {
+        min_version: min_version.parse().unwrap(),
+        activation_height,
+    })
+}
+
+fn filler(account: &mut Account, blockchain: &Blockchain) -> Operation {
+    let token_id = blockchain.next_token_id;
+    account.operation(OperationKind::CreateToken {
+        token_id,
+        name: format!("Enchimento {}", token_id),
+        symbol: "FILL".to_string(),
+        total_supply: 1,
+    })
+}
+
+#[test]
+fn test_client_version_parsing_and_order() {
+    let version: ClientVersion = "1.10.2-rc1+build7".parse().unwrap();
+    assert_eq!(version, ClientVersion::new(1, 10, 2));
+    assert!(version > ClientVersion::new(1, 9, 30));
+    assert!(ClientVersion::new(2, 0, 0) > version);
+    for invalid in ["1.2", "1.2.3.4", "v1.2.3", "1.x.3", ""] {
+        assert!(invalid.parse::<ClientVersion>().is_err(), "{}", invalid);
+    }
+
+    assert_eq!(serde_json::to_string(&version).unwrap(), "\"1.10.2\"");
+    let parsed: ClientVersion = serde_json::from_str("\"3.0.1\"").unwrap();
+    assert_eq!(parsed, ClientVersion::new(3, 0, 1));
+    assert_eq!(
+        ClientVersion::current().to_string(),
+        env!("CARGO_PKG_VERSION")
+    );
+}
+
+#[test]
+fn test_outdated_node_stops_proposing_at_activation_height() {
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain.client_version = ClientVersion::new(1, 0, 0);
+    let mut admin = Account::new();
+    let mut user = Account::new();
+    blockchain.bootstrap_admin(admin.address.clone()).unwrap();
+
+    // Só admins agendam, e nunca para a altura do próprio bloco
+    assert!(blockchain
+        .submit_operation(schedule(&mut user, "2.0.0", 5))
+        .is_err());
+    assert!(blockchain
+        .submit_operation(schedule(&mut admin, "2.0.0", 0))
+        .is_err());
+    admin.nonce -= 1;
+
+    blockchain
+        .submit_operation(schedule(&mut admin, "2.0.0", 3))
+        .unwrap();
+    blockchain.produce_block(10).unwrap().unwrap();
+    let signal = blockchain.pending_upgrade(blockchain.height()).unwrap();
+    assert_eq!(signal.activation_height, 3);
+    assert_eq!(signal.scheduled_at, 0);
+    assert_eq!(blockchain.required_version(2), None);
+
+    for _ in 0..2 {
+        let filler = filler(&mut admin, &blockchain);
+        blockchain.submit_operation(filler).unwrap();
+        blockchain.produce_block(10).unwrap().unwrap();
+    }
+    assert_eq!(blockchain.height(), 3);
+    assert_eq!(
+        blockchain.required_version(3),
+        Some(ClientVersion::new(2, 0, 0))
+    );
+    assert!(blockchain.pending_upgrade(3).is_none());
+
+    let filler = filler(&mut admin, &blockchain);
+    blockchain.submit_operation(filler).unwrap();
+    let err = blockchain.produce_block(10).unwrap_err();
+    assert!(matches!(err, Error::UpgradeRequired(_)));
+    assert_eq!(err.code(), 1032);
+    let message = err.to_string();
+    assert!(
+        message.contains("2.0.0") && message.contains("Atualize"),
+        "{}",
+        message
+    );
+    assert!(blockchain.check_client_version(3).is_err());
+    assert_eq!(blockchain.pending_operations.len(), 1);
+
+    // Com o binário atualizado o nó volta a propor
+    blockchain.client_version = "2.0.0".parse().unwrap();
+    blockchain.produce_block(10).unwrap().unwrap();
+    assert_eq!(blockchain.height(), 4);
+}
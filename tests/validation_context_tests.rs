@@ -0,0 +1,107 @@
+use kybelith::blockchain::ValidationContext;
+use kybelith::error::TransactionError;
+use kybelith::test_utils::fixtures::{address_of, funded, transfer};
+use kybelith::transaction::SecureTransaction;
+use kybelith::utils::address::derive_address;
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+
+#[test]
+fn test_context_tracks_state_across_block() {
+    let keys = keypair();
+    let blockchain = funded(&address_of(&keys), 1_000);
+    let mut context = blockchain.validation_context();
+
+    for nonce in 1..=3 {
+        context
+            .validate_transaction(&transfer(&keys, 200, nonce))
+            .unwrap();
+    }
+
+    assert_eq!(context.nonce(&address_of(&keys)), 3);
+    assert_eq!(context.balance("0", &address_of(&keys)), 400);
+    assert_eq!(context.balance("0", &"b".repeat(40)), 600);
+
+    // O estado da blockchain não é alterado pela validação
+    assert_eq!(blockchain.nonces.get(&address_of(&keys)), None);
+    assert_eq!(blockchain.tokens["0"].balances[&address_of(&keys)], 1_000);
+}
+
+#[test]
+fn test_context_rejects_insufficient_funds_after_earlier_spend() {
+    let keys = keypair();
+    let blockchain = funded(&address_of(&keys), 500);
+    let mut context = blockchain.validation_context();
+
+    context
+        .validate_transaction(&transfer(&keys, 400, 1))
+        .unwrap();
+    let result = context.validate_transaction(&transfer(&keys, 400, 2));
+
+    assert!(matches!(result, Err(TransactionError::InsufficientFunds)));
+}
+
+#[test]
+fn test_context_rejects_unknown_token_and_duplicates() {
+    let keys = keypair();
+    let blockchain = funded(&address_of(&keys), 500);
+    let mut context = blockchain.validation_context();
+
+    let mut tx = transfer(&keys, 10, 1);
+    tx.token_id = 99;
+    tx.sign(&keys.1).unwrap();
+    assert!(matches!(
+        context.validate_transaction(&tx),
+        Err(TransactionError::TokenNaoEncontrado)
+    ));
+
+    let tx = transfer(&keys, 10, 1);
+    context.validate_transaction(&tx).unwrap();
+    assert!(context.validate_transaction(&tx).is_err());
+}
+
+#[test]
+fn test_context_self_transfer_keeps_balance() {
+    let (public_key, secret_key) = keypair();
+    let owner = derive_address(public_key.as_bytes());
+    let blockchain = funded(&owner, 500);
+    let mut context = blockchain.validation_context();
+    let to_self = |amount, nonce| {
+        SecureTransaction::new(
+            owner.clone(),
+            owner.clone(),
+            amount,
+            1_700_000_000,
+            nonce,
+            &secret_key,
+            &public_key,
+        )
+        .unwrap()
+    };
+
+    context
+        .validate_secure_transaction(&to_self(300, 1))
+        .unwrap();
+    assert_eq!(context.balance("0", &owner), 500);
+
+    // O saldo não cresce: um segundo envio acima do original é recusado
+    let result = context.validate_secure_transaction(&to_self(501, 2));
+    assert!(matches!(result, Err(TransactionError::InsufficientFunds)));
+}
+
+#[test]
+fn test_context_caches_lookups() {
+    let blockchain = funded(&"a".repeat(40), 500);
+    let mut context = ValidationContext::with_capacity(&blockchain, 2);
+
+    assert!(context.token("0").is_some());
+    assert!(context.token("0").is_some());
+    assert!(context.token("99").is_none());
+    assert!(context.token("99").is_none());
+    assert_eq!(context.balance("0", &"a".repeat(40)), 500);
+    assert_eq!(context.balance("0", &"a".repeat(40)), 500);
+
+    let stats = context.stats();
+    assert_eq!(stats.misses, 3);
+    assert_eq!(stats.hits, 3);
+}
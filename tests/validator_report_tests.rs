@@ -0,0 +1,110 @@
+use kybelith::blockchain::{Blockchain, SharedBlockchain};
+use kybelith::config::Settings;
+use kybelith::consensus::{
+    LivenessDuty, QuantumFlexConsensus, ReputationAction, ReputationSystem, Validator,
+};
+use kybelith::rpc::{RpcService, ValidatorReportResponse};
+use serde_json::json;
+use std::sync::Arc;
+
+#[test]
+fn test_performance_is_tracked_per_epoch() {
+    let mut reputation = ReputationSystem::new();
+    reputation.add_validator("v1".to_string());
+    reputation.add_validator("v2".to_string());
+
+    reputation
+        .update_reputation("v1", ReputationAction::ValidBlockProposed)
+        .unwrap();
+    reputation
+        .update_reputation("v1", ReputationAction::CorrectVote)
+        .unwrap();
+    reputation
+        .record_duty("v1", LivenessDuty::Proposal, false)
+        .unwrap();
+    reputation.record_reward("v1", 40).unwrap();
+
+    reputation.begin_epoch(1);
+    reputation
+        .update_reputation("v1", ReputationAction::DoubleVote)
+        .unwrap();
+    // Um segundo voto duplo derruba a pontuação abaixo do limiar de banimento
+    reputation
+        .update_reputation("v1", ReputationAction::DoubleVote)
+        .unwrap();
+    reputation
+        .record_duty("v2", LivenessDuty::Vote, false)
+        .unwrap();
+
+    let report = reputation.performance_report(None, 0, 1);
+    assert_eq!(report.records.len(), 3);
+    let first = &report.records[0];
+    assert_eq!((first.epoch, first.validator_id.as_str()), (0, "v1"));
+    assert_eq!(first.blocks_proposed, 1);
+    assert_eq!(first.blocks_missed, 1);
+    assert_eq!(first.votes_cast, 1);
+    assert_eq!(first.rewards, 40);
+    assert_eq!((first.reputation_start, first.reputation_end), (50.0, 53.0));
+    assert!(first.slashes.is_empty());
+
+    let slashed = &report.records[1];
+    assert_eq!((slashed.epoch, slashed.validator_id.as_str()), (1, "v1"));
+    assert_eq!(slashed.reputation_start, 53.0);
+    assert_eq!(slashed.reputation_end, 13.0);
+    assert_eq!(slashed.slashes.len(), 2);
+    assert_eq!(slashed.slashes[0].penalty, 20.0);
+    assert!(slashed.slashes[0].ban_secs.is_none());
+    assert_eq!(slashed.slashes[1].ban_secs, Some(3600));
+    assert_eq!(report.records[2].votes_missed, 1);
+
+    let only_v2 = reputation.performance_report(Some("v2"), 0, 1);
+    assert_eq!(only_v2.records.len(), 1);
+    assert!(reputation.performance_report(None, 2, 5).records.is_empty());
+    assert!(reputation.record_reward("v9", 1).is_err());
+
+    // Épocas além da retenção saem do livro
+    reputation.begin_epoch(1_000);
+    assert!(reputation.performance_report(None, 0, 1).records.is_empty());
+}
+
+#[test]
+fn test_validator_report_rpc_exports_csv() {
+    let validators = vec![Validator::new(
+        "validator,1".to_string(),
+        "127.0.0.1:8001".to_string(),
+        vec![1, 2, 3, 4],
+        3000,
+    )];
+    let consensus = Arc::new(QuantumFlexConsensus::new(
+        Arc::new(Settings::default()),
+        validators,
+    ));
+    consensus
+        .record_duty("validator,1", LivenessDuty::Proposal, false)
+        .unwrap();
+    consensus.record_reward("validator,1", 7).unwrap();
+    let service = RpcService::new(SharedBlockchain::new(Blockchain::new().unwrap()))
+        .with_consensus(consensus);
+
+    let reply = service
+        .handle("get_validator_report", json!({ "format": "csv" }))
+        .unwrap();
+    let ValidatorReportResponse::Csv { content } = serde_json::from_value(reply).unwrap() else {
+        panic!("relatório em CSV esperado");
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("epoch,validator_id,blocks_proposed"));
+    assert_eq!(
+        lines[1],
+        "0,\"validator,1\",0,0,1,0,0,50.00,50.00,50.00,50.00,7,0,0.00,0"
+    );
+
+    let reply = service
+        .handle("get_validator_report", json!({ "validator_id": "outro" }))
+        .unwrap();
+    let ValidatorReportResponse::Json { report } = serde_json::from_value(reply).unwrap() else {
+        panic!("relatório em JSON esperado");
+    };
+    assert!(report.records.is_empty());
+}
@@ -0,0 +1,72 @@
+use kybelith::blockchain::{Blockchain, SharedBlockchain};
+use kybelith::config::Settings;
+use kybelith::consensus::{LivenessDuty, QuantumFlexConsensus, Validator};
+use kybelith::rpc::{RpcService, ValidatorSetResponse, ValidatorStatus};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+fn consensus() -> Arc<QuantumFlexConsensus> {
+    let validators = vec![
+        Validator::new(
+            "validator1".to_string(),
+            "127.0.0.1:8001".to_string(),
+            vec![1, 2, 3, 4],
+            3000,
+        ),
+        Validator::new(
+            "validator2".to_string(),
+            "127.0.0.1:8002".to_string(),
+            vec![5, 6, 7, 8],
+            5000,
+        ),
+    ];
+    Arc::new(QuantumFlexConsensus::new(
+        Arc::new(Settings::default()),
+        validators,
+    ))
+}
+
+fn service(consensus: Arc<QuantumFlexConsensus>) -> RpcService {
+    RpcService::new(SharedBlockchain::new(Blockchain::new().unwrap())).with_consensus(consensus)
+}
+
+#[test]
+fn test_get_validators_reports_stake_and_reputation() {
+    let consensus = consensus();
+    let service = service(Arc::clone(&consensus));
+
+    let set: ValidatorSetResponse =
+        serde_json::from_value(service.handle("get_validators", Value::Null).unwrap()).unwrap();
+    let ids: Vec<&str> = set.validators.iter().map(|v| v.id.as_str()).collect();
+    assert_eq!(ids, vec!["validator2", "validator1"]);
+    assert_eq!(set.total_active_stake, 8000);
+    assert!(set
+        .validators
+        .iter()
+        .all(|v| v.score == 50.0 && !v.is_banned));
+
+    for _ in 0..5 {
+        consensus
+            .record_duty("validator1", LivenessDuty::Vote, false)
+            .unwrap();
+    }
+    let status: ValidatorStatus = serde_json::from_value(
+        service
+            .handle("get_validator", json!({ "validator_id": "validator1" }))
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(status.stake, 3000);
+    assert_eq!(status.score, 45.0);
+    assert_eq!(status.liveness.missed_votes, 5);
+    assert!(status.liveness.offline);
+    assert!(service
+        .handle("get_validator", json!({ "validator_id": "validator9" }))
+        .is_err());
+}
+
+#[test]
+fn test_validator_queries_require_consensus() {
+    let service = RpcService::new(SharedBlockchain::new(Blockchain::new().unwrap()));
+    assert!(service.handle("get_validators", Value::Null).is_err());
+}
@@ -0,0 +1,116 @@
+use kybelith::blockchain::{Block, Blockchain, SharedBlockchain};
+use kybelith::error::TransactionError;
+use kybelith::test_utils::fixtures::{address_of, transfer};
+use kybelith::transaction::{SecureTransaction, Transaction, VerificationService};
+use pqcrypto_dilithium::dilithium5::keypair;
+
+#[test]
+fn test_batch_results_follow_input_order() {
+    let service = VerificationService::new(2).unwrap();
+    assert_eq!(service.threads(), 2);
+
+    let keys = keypair();
+    let mut transactions: Vec<Transaction> =
+        (1..=6).map(|nonce| transfer(&keys, 100, nonce)).collect();
+    transactions[3].amount += 1;
+
+    let results = service.verify_batch(&transactions);
+
+    assert_eq!(results.len(), 6);
+    for (i, result) in results.iter().enumerate() {
+        assert_eq!(result.is_ok(), i != 3, "resultado {} fora de ordem", i);
+    }
+}
+
+#[test]
+fn test_secure_batch_detects_invalid_public_key() {
+    let keys = keypair();
+    let timestamp = chrono::Utc::now().timestamp();
+    let mut transactions: Vec<SecureTransaction> = (1..=3)
+        .map(|nonce| {
+            SecureTransaction::new(
+                address_of(&keys),
+                "b".repeat(40),
+                100,
+                timestamp,
+                nonce,
+                &keys.1,
+                &keys.0,
+            )
+            .unwrap()
+        })
+        .collect();
+    transactions[1].public_key.truncate(16);
+
+    let results = VerificationService::global().verify_secure_batch(&transactions);
+
+    assert!(matches!(results[0], Ok(true)));
+    assert!(matches!(
+        results[1],
+        Err(TransactionError::InvalidSignature(_))
+    ));
+    assert!(matches!(results[2], Ok(true)));
+}
+
+#[test]
+fn test_chain_validation_uses_batch_verification() {
+    let keys = keypair();
+    let timestamp = chrono::Utc::now().timestamp();
+    let transactions: Vec<SecureTransaction> = (1..=4)
+        .map(|nonce| {
+            SecureTransaction::new(
+                address_of(&keys),
+                "b".repeat(40),
+                100,
+                timestamp,
+                nonce,
+                &keys.1,
+                &keys.0,
+            )
+            .unwrap()
+        })
+        .collect();
+
+    let mut blockchain = Blockchain::new().unwrap();
+    let genesis = Block::new(0, Vec::new(), Vec::new(), "0".repeat(64)).unwrap();
+    let block = Block::new(1, transactions, Vec::new(), genesis.hash.clone()).unwrap();
+    blockchain.chain.push(genesis);
+    blockchain.chain.push(block);
+
+    assert!(blockchain.is_chain_valid().unwrap());
+
+    blockchain.chain[1].transactions[2].public_key.truncate(16);
+    assert!(blockchain.is_chain_valid().is_err());
+}
+
+#[test]
+fn test_submit_batch_admits_valid_transactions_in_order() {
+    let shared = SharedBlockchain::new(Blockchain::new().unwrap());
+    let keys = keypair();
+
+    let mut transactions: Vec<Transaction> =
+        (1..=3).map(|nonce| transfer(&keys, 100, nonce)).collect();
+    let mut forged = transfer(&keys, 100, 4);
+    forged.amount += 1;
+    transactions.push(forged);
+
+    let results = shared.submit_batch(transactions);
+
+    assert!(results[..3].iter().all(|result| result.is_ok()));
+    assert!(results[3].is_err());
+    assert_eq!(shared.pending_count(), 3);
+    assert_eq!(shared.nonce_of(&address_of(&keys)), 3);
+}
+
+#[tokio::test]
+async fn test_async_batch_does_not_block_runtime() {
+    let keys = keypair();
+    let transactions: Vec<Transaction> = (1..=4).map(|nonce| transfer(&keys, 100, nonce)).collect();
+
+    let results = VerificationService::global()
+        .verify_batch_async(transactions)
+        .await;
+
+    assert_eq!(results.len(), 4);
+    assert!(results.iter().all(|result| result.is_ok()));
+}
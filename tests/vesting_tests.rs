@@ -0,0 +1,102 @@
+use kybelith::blockchain::Blockchain;
+use kybelith::error::TransactionError;
+use kybelith::test_utils::fixtures::{balance, commit, Account};
+use kybelith::token::VestingSchedule;
+use kybelith::transaction::OperationKind;
+
+fn now() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+#[test]
+fn test_cliff_and_linear_release() {
+    let schedule = VestingSchedule {
+        token_id: 1,
+        grantor: "a".repeat(40),
+        beneficiary: "b".repeat(40),
+        amount: 1_000,
+        claimed: 100,
+        start: 1_000,
+        cliff_secs: 250,
+        duration_secs: 1_000,
+    };
+
+    assert_eq!(schedule.released_at(500), 0);
+    assert_eq!(schedule.released_at(1_249), 0);
+    assert_eq!(schedule.released_at(1_250), 250);
+    assert_eq!(schedule.claimable_at(1_500), 400);
+    assert_eq!(schedule.locked_at(1_500), 500);
+    assert_eq!(schedule.released_at(5_000), 1_000);
+    assert_eq!(schedule.locked_at(5_000), 0);
+}
+
+#[test]
+fn test_claim_moves_released_tokens() {
+    let mut blockchain = Blockchain::new().unwrap();
+    let mut alice = Account::new();
+    let mut bob = Account::new();
+    let token_id = blockchain.next_token_id;
+    let create = alice.operation(OperationKind::CreateToken {
+        token_id,
+        name: "Cota de Equipe".to_string(),
+        symbol: "TEAM".to_string(),
+        total_supply: 10_000,
+    });
+    commit(&mut blockchain, [create]);
+
+    let grant = |start: u64| OperationKind::CreateVesting {
+        token_id,
+        beneficiary: bob.address.clone(),
+        amount: 1_200,
+        start,
+        cliff_secs: 10,
+        duration_secs: 100,
+    };
+    let vested = grant(now() - 1_000);
+    let halfway = grant(now() - 50);
+    let future = grant(now() + 10_000);
+    for kind in [vested, halfway, future] {
+        commit(&mut blockchain, [alice.operation(kind)]);
+    }
+    assert_eq!(balance(&blockchain, token_id, &alice.address), 6_400);
+    assert_eq!(blockchain.vesting_for(&bob.address).count(), 3);
+
+    // Nada está no saldo antes do resgate, então a transferência é descartada
+    blockchain
+        .submit_transaction(bob.transfer_token(token_id, &alice.address, 100))
+        .unwrap();
+    blockchain.produce_block(10).unwrap();
+    assert_eq!(balance(&blockchain, token_id, &alice.address), 6_400);
+
+    commit(
+        &mut blockchain,
+        [bob.operation(OperationKind::ClaimVested { schedule_id: 0 })],
+    );
+    assert_eq!(balance(&blockchain, token_id, &bob.address), 1_200);
+
+    commit(
+        &mut blockchain,
+        [bob.operation(OperationKind::ClaimVested { schedule_id: 1 })],
+    );
+    let claimed = balance(&blockchain, token_id, &bob.address) - 1_200;
+    assert!((540..=720).contains(&claimed), "liberado: {}", claimed);
+    assert!(blockchain.locked_balance(token_id, &bob.address, now()) >= 1_200);
+    assert_eq!(
+        blockchain.locked_balance(token_id, &bob.address, now() + 20_000),
+        0
+    );
+
+    // Antes da carência o resgate falha na confirmação e o saldo não muda
+    let before = balance(&blockchain, token_id, &bob.address);
+    commit(
+        &mut blockchain,
+        [bob.operation(OperationKind::ClaimVested { schedule_id: 2 })],
+    );
+    assert_eq!(balance(&blockchain, token_id, &bob.address), before);
+    assert_eq!(blockchain.vesting_schedule(2).unwrap().claimed, 0);
+
+    assert!(matches!(
+        blockchain.submit_operation(alice.operation(OperationKind::ClaimVested { schedule_id: 0 })),
+        Err(TransactionError::InvalidParameter(_))
+    ));
+}
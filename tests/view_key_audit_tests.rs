@@ -0,0 +1,108 @@
+use kybelith::blockchain::{Block, Blockchain};
+use kybelith::error::TransactionError;
+use kybelith::transaction::confidential::ViewKey;
+use kybelith::transaction::SecureTransaction;
+use pqcrypto_dilithium::dilithium5::keypair;
+use secrecy::ExposeSecret;
+
+fn transaction(from: &str, to: &str, nonce: u64) -> SecureTransaction {
+    let keys = keypair();
+    SecureTransaction::new(
+        from.repeat(40),
+        to.repeat(40),
+        100 * nonce,
+        chrono::Utc::now().timestamp(),
+        nonce,
+        &keys.1,
+        &keys.0,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_exported_view_key_reads_payload() {
+    let keys = keypair();
+    let auditor = ViewKey::generate().unwrap();
+    let mut tx = SecureTransaction::new(
+        "a".repeat(40),
+        "b".repeat(40),
+        250,
+        1_700_000_000,
+        7,
+        &keys.1,
+        &keys.0,
+    )
+    .unwrap();
+    tx.grant_view_keys(&[auditor.public_key().to_vec()])
+        .unwrap();
+    assert!(tx.is_sealed());
+    assert!(tx.cipher_key.is_empty());
+    let signature = tx.signature.clone();
+    assert!(tx.verify(&keys.0, &signature).unwrap());
+
+    // A chave exportada volta a abrir o payload, sem nada da chave de assinatura
+    let exported = auditor.export();
+    let imported = ViewKey::import(exported.expose_secret()).unwrap();
+    assert_eq!(imported.id(), auditor.id());
+    let expected = format!("{}:{}:250:1700000000:7", "a".repeat(40), "b".repeat(40));
+    assert_eq!(tx.open_payload(&imported).unwrap(), expected);
+
+    assert!(matches!(
+        tx.open_payload(&ViewKey::generate().unwrap()),
+        Err(TransactionError::InvalidParameter(_))
+    ));
+    assert!(tx
+        .grant_view_keys(&[auditor.public_key().to_vec()])
+        .is_err());
+
+    // Payload trocado não passa por legítimo mesmo para o auditor
+    let mut altered = tx.clone();
+    altered.amount = 1;
+    assert!(altered.open_payload(&auditor).is_err());
+
+    assert!(ViewKey::import("kvk1:zz:00").is_err());
+    assert!(ViewKey::import(&exported.expose_secret().replace("kvk1", "kvk2")).is_err());
+}
+
+#[test]
+fn test_audit_payloads_by_account() {
+    let auditor = ViewKey::generate().unwrap();
+    let other = ViewKey::generate().unwrap();
+
+    let mut granted = transaction("a", "b", 1);
+    granted
+        .grant_view_keys(&[auditor.public_key().to_vec()])
+        .unwrap();
+    let mut incoming = transaction("c", "a", 2);
+    incoming
+        .grant_view_keys(&[other.public_key().to_vec(), auditor.public_key().to_vec()])
+        .unwrap();
+    let mut unrelated = transaction("c", "b", 3);
+    unrelated
+        .grant_view_keys(&[auditor.public_key().to_vec()])
+        .unwrap();
+    let not_granted = transaction("a", "c", 4);
+
+    let mut blockchain = Blockchain::new().unwrap();
+    blockchain
+        .chain
+        .push(Block::new(0, Vec::new(), Vec::new(), "0".repeat(64)).unwrap());
+    let previous_hash = blockchain.chain[0].hash.clone();
+    blockchain.chain.push(
+        Block::new(
+            1,
+            vec![granted, not_granted, unrelated, incoming],
+            Vec::new(),
+            previous_hash,
+        )
+        .unwrap(),
+    );
+
+    let audited = blockchain.audit_payloads(&"a".repeat(40), &auditor);
+    assert_eq!(audited.len(), 2);
+    assert!(audited.iter().all(|(height, _)| *height == 1));
+    assert!(audited[0].1.ends_with(":1"));
+    assert!(audited[1].1.starts_with(&"c".repeat(40)));
+
+    assert_eq!(blockchain.audit_payloads(&"a".repeat(40), &other).len(), 1);
+}
@@ -0,0 +1,78 @@
+use kybelith::consensus::{
+    ProposalVote, ReputationSystem, Validator, ValidatorSet, VotingCoordinator,
+};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+fn coordinator() -> VotingCoordinator {
+    let validators = (1..=4)
+        .map(|i| {
+            Validator::new(
+                format!("v{}", i),
+                format!("addr-v{}", i),
+                vec![i as u8; 32],
+                1_000,
+            )
+        })
+        .collect();
+    VotingCoordinator::new(
+        Arc::new(RwLock::new(ValidatorSet::new(validators))),
+        Arc::new(RwLock::new(ReputationSystem::new())),
+        66.0,
+    )
+}
+
+fn vote(coordinator: &mut VotingCoordinator, hash: &str, height: u64, validator: &str) {
+    coordinator
+        .process_vote(ProposalVote::new(
+            hash.to_string(),
+            height,
+            validator.to_string(),
+            true,
+            vec![1],
+        ))
+        .unwrap();
+}
+
+#[test]
+fn test_finalized_heights_pruned() {
+    let mut coordinator = coordinator();
+    vote(&mut coordinator, "a", 1, "v1");
+    vote(&mut coordinator, "b", 2, "v1");
+    vote(&mut coordinator, "c", 3, "v1");
+
+    assert_eq!(coordinator.prune_through(2), 2);
+    assert_eq!(coordinator.tracked_proposals(), 1);
+    assert!(coordinator.tally_votes("a").is_none());
+    assert!(coordinator.tally_votes("c").is_some());
+}
+
+#[test]
+fn test_abandoned_proposals_expire() {
+    let mut coordinator = coordinator().with_limits(Duration::from_millis(50), 8);
+    vote(&mut coordinator, "old", 1, "v1");
+    std::thread::sleep(Duration::from_millis(80));
+    vote(&mut coordinator, "new", 1, "v1");
+
+    assert_eq!(coordinator.expire_stale(), 1);
+    assert!(coordinator.tally_votes("old").is_none());
+    assert!(coordinator.tally_votes("new").is_some());
+}
+
+#[test]
+fn test_proposals_per_height_bounded() {
+    let mut coordinator = coordinator().with_limits(Duration::from_secs(60), 3);
+    vote(&mut coordinator, "honest", 5, "v1");
+    vote(&mut coordinator, "honest", 5, "v2");
+
+    for i in 0..10 {
+        vote(&mut coordinator, &format!("spam-{}", i), 5, "v3");
+    }
+    // Outras alturas não disputam o mesmo limite
+    vote(&mut coordinator, "next", 6, "v1");
+
+    assert_eq!(coordinator.tracked_proposals(), 4);
+    assert_eq!(coordinator.tally_votes("honest").unwrap().votes_in_favor, 2);
+    assert!(coordinator.tally_votes("spam-0").is_none());
+    assert!(coordinator.tally_votes("spam-9").is_some());
+}
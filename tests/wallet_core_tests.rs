@@ -0,0 +1,46 @@
+use kybelith::transaction::builder::TransactionBuilder;
+use kybelith::utils::address::{derive_address, DERIVED_ADDRESS_LEN};
+use pqcrypto_dilithium::dilithium5::keypair;
+use pqcrypto_traits::sign::PublicKey as _;
+
+#[test]
+fn test_builder_signs_transfer_from_derived_address() {
+    let (public_key, secret_key) = keypair();
+    let from = derive_address(public_key.as_bytes());
+    let to = derive_address(keypair().0.as_bytes());
+    assert_eq!(from.len(), DERIVED_ADDRESS_LEN);
+    assert_ne!(from, to);
+
+    let mut transaction = TransactionBuilder::new()
+        .token_id(2)
+        .from(from.clone())
+        .to(to)
+        .amount(75)
+        .timestamp(1_700_000_000)
+        .nonce(9)
+        .public_key(public_key.as_bytes().to_vec())
+        .build()
+        .unwrap();
+    assert_eq!(transaction.timestamp, 1_700_000_000);
+    assert_eq!(transaction.nonce, 9);
+    assert_eq!(transaction.token_id, 2);
+
+    transaction.sign(&secret_key).unwrap();
+    transaction.verify(&public_key).unwrap();
+    assert_eq!(derive_address(&transaction.public_key), from);
+}
+
+#[test]
+fn test_builder_defaults_to_system_clock() {
+    let (public_key, _) = keypair();
+    let transaction = TransactionBuilder::new()
+        .from("a".repeat(40))
+        .to("b".repeat(40))
+        .amount(1)
+        .public_key(public_key.as_bytes().to_vec())
+        .build()
+        .unwrap();
+
+    assert!((chrono::Utc::now().timestamp() - transaction.timestamp).abs() < 5);
+    assert_eq!(transaction.nonce, 1);
+}